@@ -0,0 +1,251 @@
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sqlx::{Connection, Row};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Manager, Window};
+use uuid::Uuid;
+
+use crate::database::open_connection;
+use crate::error::AppError;
+use crate::windows::verify_unrestricted_window;
+use paths::{long_path, sanitize_extension};
+
+pub mod orphans;
+pub mod paths;
+
+const OBJECTS_DIR: &str = "objects";
+// Freshly stored objects are only referenced once the owning row is saved, so GC leaves
+// recent files alone instead of racing an in-flight save
+const GC_GRACE_PERIOD: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Serialize)]
+pub struct StoredAsset {
+    pub hash: String,
+    // Relative to the app data dir, always with forward slashes
+    pub path: String,
+    pub size: usize,
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct AssetGcReport {
+    pub removed: usize,
+    pub freed_bytes: u64,
+}
+
+fn objects_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data dir: {}", e))?;
+    Ok(data_dir.join(OBJECTS_DIR))
+}
+
+// Write to a temp file first so a crash never leaves a truncated object under its hash. The temp
+// name is unique, so concurrent stores of the same content never write into one file, and losing
+// the rename to one of them is fine since the object holds the same bytes.
+fn write_object(dir: &Path, file_name: &str, data: &[u8]) -> Result<(), String> {
+    let target = dir.join(file_name);
+    if target.exists() {
+        // Stored again before its owning row exists: restart the GC grace period, or an object
+        // unreferenced for an hour could be collected before that row counts it
+        return fs::File::options()
+            .append(true)
+            .open(&target)
+            .and_then(|file| file.set_modified(SystemTime::now()))
+            .map_err(|e| format!("Failed to refresh asset: {}", e));
+    }
+
+    let temp = dir.join(format!("{}.{}.tmp", file_name, Uuid::new_v4().simple()));
+    fs::write(&temp, data).map_err(|e| format!("Failed to write asset: {}", e))?;
+    if let Err(e) = fs::rename(&temp, &target) {
+        let _ = fs::remove_file(&temp);
+        if !target.exists() {
+            return Err(format!("Failed to store asset: {}", e));
+        }
+    }
+    Ok(())
+}
+
+// Write a file into the content-addressed store. Identical content is written only once.
+#[tauri::command]
+pub fn store_asset(
+    app: AppHandle,
+    data_base64: String,
+    extension: String,
+) -> Result<StoredAsset, AppError> {
+    let data = BASE64
+        .decode(data_base64.trim())
+        .map_err(|e| AppError::validation(format!("Failed to decode asset data: {}", e)))?;
+
+    let hash = hex::encode(Sha256::digest(&data));
+    let extension = sanitize_extension(&extension);
+    let prefix = &hash[..2];
+    let file_name = format!("{}.{}", hash, extension);
+
+    let dir = long_path(&objects_dir(&app)?.join(prefix));
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create asset dir: {}", e))?;
+
+    write_object(&dir, &file_name, &data)?;
+
+    Ok(StoredAsset {
+        path: format!("{}/{}/{}", OBJECTS_DIR, prefix, file_name),
+        hash,
+        size: data.len(),
+    })
+}
+
+// Delete stored objects that no row references anymore
+#[tauri::command]
+pub async fn garbage_collect_assets(
+    app: AppHandle,
+    window: Window,
+) -> Result<AssetGcReport, AppError> {
+    verify_unrestricted_window(&window)?;
+    let objects = long_path(&objects_dir(&app)?);
+    if !objects.exists() {
+        return Ok(AssetGcReport::default());
+    }
+
+    let mut conn = open_connection(&app).await?;
+
+    let referenced: HashSet<String> = sqlx::query("SELECT hash FROM assets WHERE ref_count > 0")
+        .fetch_all(&mut conn)
+        .await
+        .map_err(|e| format!("Failed to read asset references: {}", e))?
+        .iter()
+        .map(|row| row.get::<String, _>("hash"))
+        .collect();
+
+    let mut report = AssetGcReport::default();
+    let mut removed_hashes = Vec::new();
+    let now = SystemTime::now();
+
+    let prefixes =
+        fs::read_dir(&objects).map_err(|e| format!("Failed to read objects dir: {}", e))?;
+    for prefix in prefixes.flatten() {
+        let Ok(entries) = fs::read_dir(prefix.path()) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(hash) = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.split('.').next())
+                .map(str::to_string)
+            else {
+                continue;
+            };
+            if referenced.contains(&hash) {
+                continue;
+            }
+
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            let is_recent = metadata
+                .modified()
+                .ok()
+                .and_then(|modified| now.duration_since(modified).ok())
+                .is_none_or(|age| age < GC_GRACE_PERIOD);
+            if is_recent {
+                continue;
+            }
+
+            if fs::remove_file(&path).is_ok() {
+                report.removed += 1;
+                report.freed_bytes += metadata.len();
+                removed_hashes.push(hash);
+            }
+        }
+    }
+
+    for hash in removed_hashes {
+        sqlx::query("DELETE FROM assets WHERE hash = $1 AND ref_count <= 0")
+            .bind(hash)
+            .execute(&mut conn)
+            .await
+            .map_err(|e| format!("Failed to remove asset record: {}", e))?;
+    }
+
+    let _ = conn.close().await;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("narratrix-assets-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn storing_existing_content_again_succeeds() {
+        let dir = temp_dir("existing");
+        write_object(&dir, "abc.png", b"image").unwrap();
+        write_object(&dir, "abc.png", b"image").unwrap();
+
+        let names: Vec<_> = fs::read_dir(&dir)
+            .unwrap()
+            .flatten()
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(names, vec!["abc.png".to_string()]);
+        assert_eq!(fs::read(dir.join("abc.png")).unwrap(), b"image");
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn storing_again_restarts_the_gc_grace_period() {
+        let dir = temp_dir("restore");
+        write_object(&dir, "abc.png", b"image").unwrap();
+        let target = dir.join("abc.png");
+        fs::File::options()
+            .append(true)
+            .open(&target)
+            .unwrap()
+            .set_modified(SystemTime::now() - GC_GRACE_PERIOD * 2)
+            .unwrap();
+
+        write_object(&dir, "abc.png", b"image").unwrap();
+
+        let age = SystemTime::now()
+            .duration_since(fs::metadata(&target).unwrap().modified().unwrap())
+            .unwrap_or_default();
+        assert!(age < GC_GRACE_PERIOD);
+        assert_eq!(fs::read(&target).unwrap(), b"image");
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn concurrent_stores_of_the_same_content_all_succeed() {
+        let dir = temp_dir("concurrent");
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let dir = dir.clone();
+                std::thread::spawn(move || write_object(&dir, "abc.png", b"image"))
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap().unwrap();
+        }
+
+        assert_eq!(fs::read(dir.join("abc.png")).unwrap(), b"image");
+        let leftovers = fs::read_dir(&dir)
+            .unwrap()
+            .flatten()
+            .filter(|entry| entry.file_name().to_string_lossy().ends_with(".tmp"))
+            .count();
+        assert_eq!(leftovers, 0);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,478 @@
+use serde::Serialize;
+use sqlx::sqlite::SqliteConnection;
+use sqlx::{Connection, Row};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager, Window};
+
+use super::paths::long_path;
+use super::{AssetGcReport, GC_GRACE_PERIOD, OBJECTS_DIR};
+use crate::database::open_connection;
+use crate::error::AppError;
+use crate::windows::{verify_unrestricted_window, verify_window_profile};
+
+// Image folder used before the asset store, still read for profiles that never migrated
+const LEGACY_IMAGES_DIR: &str = "images";
+// Avatar folder of older versions, shared with other profile files, so only `avatar_*` files count
+const LEGACY_PROFILES_DIR: &str = "profiles";
+const LEGACY_AVATAR_PREFIX: &str = "avatar_";
+// Only files under these folders are ever reported or removed
+const MANAGED_DIRS: [&str; 3] = [OBJECTS_DIR, LEGACY_IMAGES_DIR, LEGACY_PROFILES_DIR];
+
+// Every column that can hold a file path, relative to the app data dir or absolute
+const REFERENCED_PATHS_SQL: &str = "
+    SELECT avatar_path AS path FROM profiles WHERE avatar_path IS NOT NULL
+    UNION
+    SELECT avatar_path FROM characters WHERE avatar_path IS NOT NULL
+    UNION
+    SELECT json_extract(expression.value, '$.image_path')
+    FROM characters, json_each(CASE WHEN json_valid(characters.expressions) THEN characters.expressions ELSE '[]' END) AS expression
+    WHERE json_extract(expression.value, '$.image_path') IS NOT NULL";
+
+#[derive(Debug, Serialize)]
+pub struct OrphanInfo {
+    // Relative to the app data dir, always with forward slashes
+    pub path: String,
+    pub size: u64,
+    // Milliseconds since epoch
+    pub modified_ms: Option<u64>,
+}
+
+struct OrphanFile {
+    info: OrphanInfo,
+    absolute: PathBuf,
+}
+
+// What the database still points at: normalized paths, plus the hashes the asset table counts
+struct References {
+    paths: HashSet<String>,
+    hashes: HashSet<String>,
+}
+
+impl References {
+    fn contains(&self, relative: &str) -> bool {
+        if self.paths.contains(&relative.to_lowercase()) {
+            return true;
+        }
+        object_hash(relative).is_some_and(|hash| self.hashes.contains(&hash))
+    }
+}
+
+fn data_dir(app: &AppHandle) -> Result<PathBuf, AppError> {
+    app.path()
+        .app_data_dir()
+        .map_err(|e| AppError::internal(format!("Failed to resolve app data dir: {}", e)))
+}
+
+// Legacy profile files other than avatars (if any) are never treated as images
+fn is_managed_file(relative: &str) -> bool {
+    match relative.strip_prefix(LEGACY_PROFILES_DIR) {
+        Some(rest) => rest
+            .strip_prefix('/')
+            .is_some_and(|name| !name.contains('/') && name.starts_with(LEGACY_AVATAR_PREFIX)),
+        None => true,
+    }
+}
+
+// Stem of an asset store file, "objects/ab/<hash>.png" gives "<hash>"
+fn object_hash(relative: &str) -> Option<String> {
+    let rest = relative.strip_prefix(OBJECTS_DIR)?.strip_prefix('/')?;
+    let file_name = rest.rsplit('/').next()?;
+    file_name.split('.').next().map(str::to_lowercase)
+}
+
+// Stored paths come from several app versions: relative with either separator, or absolute.
+// Lowercased so a reference that differs only in case (Windows, macOS) still protects the file.
+fn normalize_reference(data_dir: &Path, raw: &str) -> Option<String> {
+    let raw = raw.trim();
+    if raw.is_empty() || raw.starts_with("data:") || raw.contains("://") {
+        return None;
+    }
+
+    let path = Path::new(raw);
+    let relative = if path.is_absolute() {
+        path.strip_prefix(data_dir)
+            .ok()?
+            .to_string_lossy()
+            .to_string()
+    } else {
+        raw.to_string()
+    };
+    let relative = relative.replace('\\', "/");
+    Some(relative.trim_start_matches("./").to_lowercase())
+}
+
+async fn load_references(
+    conn: &mut SqliteConnection,
+    data_dir: &Path,
+) -> Result<References, AppError> {
+    let paths = sqlx::query(REFERENCED_PATHS_SQL)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to read file references: {}", e))?
+        .iter()
+        .filter_map(|row| row.get::<Option<String>, _>("path"))
+        .filter_map(|path| normalize_reference(data_dir, &path))
+        .collect();
+
+    let hashes = sqlx::query("SELECT hash FROM assets WHERE ref_count > 0")
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to read asset references: {}", e))?
+        .iter()
+        .map(|row| row.get::<String, _>("hash").to_lowercase())
+        .collect();
+
+    Ok(References { paths, hashes })
+}
+
+// Walk a managed folder without following symlinks, so nothing outside it is ever reached
+fn collect_files(dir: &Path, relative: &str, files: &mut Vec<(PathBuf, String)>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let name = entry.file_name().to_string_lossy().to_string();
+        let child = format!("{}/{}", relative, name);
+        if file_type.is_dir() {
+            collect_files(&entry.path(), &child, files);
+        } else if file_type.is_file() {
+            files.push((entry.path(), child));
+        }
+    }
+}
+
+async fn find_orphans(
+    conn: &mut SqliteConnection,
+    data_dir: &Path,
+    now: SystemTime,
+) -> Result<Vec<OrphanFile>, AppError> {
+    let references = load_references(conn, data_dir).await?;
+
+    let mut files = Vec::new();
+    for dir in MANAGED_DIRS {
+        collect_files(&long_path(&data_dir.join(dir)), dir, &mut files);
+    }
+
+    let mut orphans = Vec::new();
+    for (absolute, relative) in files {
+        if !is_managed_file(&relative) || references.contains(&relative) {
+            continue;
+        }
+        let Ok(metadata) = fs::symlink_metadata(&absolute) else {
+            continue;
+        };
+        // Same grace period as asset GC: a file written moments ago may belong to a row being saved
+        let modified = metadata.modified().ok();
+        let is_recent = modified
+            .and_then(|modified| now.duration_since(modified).ok())
+            .is_none_or(|age| age < GC_GRACE_PERIOD);
+        if is_recent {
+            continue;
+        }
+
+        orphans.push(OrphanFile {
+            info: OrphanInfo {
+                path: relative,
+                size: metadata.len(),
+                modified_ms: modified
+                    .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+                    .map(|elapsed| elapsed.as_millis() as u64),
+            },
+            absolute,
+        });
+    }
+
+    orphans.sort_by(|a, b| a.info.path.cmp(&b.info.path));
+    Ok(orphans)
+}
+
+async fn remove_orphans(
+    conn: &mut SqliteConnection,
+    data_dir: &Path,
+    now: SystemTime,
+) -> Result<AssetGcReport, AppError> {
+    let mut report = AssetGcReport::default();
+    let mut removed_hashes = Vec::new();
+
+    for orphan in find_orphans(conn, data_dir, now).await? {
+        if fs::remove_file(&orphan.absolute).is_ok() {
+            report.removed += 1;
+            report.freed_bytes += orphan.info.size;
+            removed_hashes.extend(object_hash(&orphan.info.path));
+        }
+    }
+
+    for hash in removed_hashes {
+        sqlx::query("DELETE FROM assets WHERE hash = $1 AND ref_count <= 0")
+            .bind(hash)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to remove asset record: {}", e))?;
+    }
+
+    Ok(report)
+}
+
+async fn check_profile(conn: &mut SqliteConnection, profile_id: &str) -> Result<(), AppError> {
+    let exists = sqlx::query("SELECT 1 FROM profiles WHERE id = $1")
+        .bind(profile_id)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to read profile: {}", e))?
+        .is_some();
+    if exists {
+        Ok(())
+    } else {
+        Err(AppError::not_found(format!(
+            "Profile not found: {}",
+            profile_id
+        )))
+    }
+}
+
+// List files in the app's image folders that no profile or character points at anymore.
+// Files are shared between profiles (identical images are stored once), so a file only counts
+// as orphaned when no row of any profile references it, and only the main window may scan them.
+#[tauri::command]
+pub async fn find_orphaned_files(
+    app: AppHandle,
+    window: Window,
+    profile_id: String,
+) -> Result<Vec<OrphanInfo>, AppError> {
+    verify_unrestricted_window(&window)?;
+    verify_window_profile(&window, &profile_id)?;
+    let data_dir = data_dir(&app)?;
+    let mut conn = open_connection(&app).await?;
+    let result = async {
+        check_profile(&mut conn, &profile_id).await?;
+        find_orphans(&mut conn, &data_dir, SystemTime::now()).await
+    }
+    .await;
+    let _ = conn.close().await;
+    Ok(result?.into_iter().map(|orphan| orphan.info).collect())
+}
+
+// Delete the files `find_orphaned_files` reports. The folders are scanned again rather than
+// trusting a list from the frontend, and nothing is removed without `confirm`.
+#[tauri::command]
+pub async fn cleanup_orphaned_files(
+    app: AppHandle,
+    window: Window,
+    profile_id: String,
+    confirm: bool,
+) -> Result<AssetGcReport, AppError> {
+    verify_unrestricted_window(&window)?;
+    verify_window_profile(&window, &profile_id)?;
+    if !confirm {
+        return Err(AppError::validation(
+            "Removing orphaned files needs confirmation",
+        ));
+    }
+
+    let data_dir = data_dir(&app)?;
+    let mut conn = open_connection(&app).await?;
+    let result = async {
+        check_profile(&mut conn, &profile_id).await?;
+        remove_orphans(&mut conn, &data_dir, SystemTime::now()).await
+    }
+    .await;
+    let _ = conn.close().await;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_db::{connect, migrated_database};
+    use std::fs::File;
+    use std::time::Duration;
+
+    const HASH_KEPT: &str = "aa11111111111111111111111111111111111111111111111111111111111111";
+    const HASH_COUNTED: &str = "bb22222222222222222222222222222222222222222222222222222222222222";
+    const HASH_ORPHAN: &str = "cc33333333333333333333333333333333333333333333333333333333333333";
+
+    fn write(data_dir: &Path, relative: &str, bytes: usize, age: Duration) {
+        let path = data_dir.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, vec![0u8; bytes]).unwrap();
+        let file = File::options().write(true).open(&path).unwrap();
+        file.set_modified(SystemTime::now() - age).unwrap();
+    }
+
+    async fn setup(name: &str) -> (PathBuf, SqliteConnection) {
+        let db_path = migrated_database(&format!("orphans-{}", name)).await;
+        let data_dir = db_path.parent().unwrap().to_path_buf();
+        let mut conn = connect(&db_path).await;
+
+        let legacy_avatar = data_dir.join("images").join("avatar_p1.png");
+        let expressions = format!(
+            r#"[{{"id":"e1","name":"happy","image_path":"objects/aa/{}.png"}},{{"id":"e2","name":"sad","image_path":"images\\characters\\c1\\sad.png"}}]"#,
+            HASH_KEPT
+        );
+        for (sql, binds) in [
+            (
+                "INSERT INTO profiles (id, name, avatar_path) VALUES ('p1', 'Main', $1)",
+                vec![legacy_avatar.to_string_lossy().to_string()],
+            ),
+            (
+                "INSERT INTO characters (id, profile_id, name, type, version, expressions)
+                 VALUES ('c1', 'p1', 'Ann', 'character', '1.0.0', $1)",
+                vec![expressions],
+            ),
+            (
+                "INSERT INTO assets (hash, size, ref_count) VALUES ($1, 10, 1), ($2, 10, 0)",
+                vec![HASH_COUNTED.to_string(), HASH_ORPHAN.to_string()],
+            ),
+        ] {
+            let mut query = sqlx::query(sql);
+            for bind in binds {
+                query = query.bind(bind);
+            }
+            query.execute(&mut conn).await.unwrap();
+        }
+
+        let old = Duration::from_secs(2 * 60 * 60);
+        write(&data_dir, &format!("objects/aa/{}.png", HASH_KEPT), 10, old);
+        write(
+            &data_dir,
+            &format!("objects/bb/{}.png", HASH_COUNTED),
+            10,
+            old,
+        );
+        write(
+            &data_dir,
+            &format!("objects/cc/{}.png", HASH_ORPHAN),
+            10,
+            old,
+        );
+        write(&data_dir, "images/avatar_p1.png", 20, old);
+        write(&data_dir, "images/characters/c1/sad.png", 30, old);
+        write(&data_dir, "images/characters/gone/happy.png", 40, old);
+        write(&data_dir, "profiles/avatar_gone.png", 70, old);
+        // Not an avatar, left alone
+        write(&data_dir, "profiles/notes.json", 80, old);
+        // Just written, its row may not be saved yet
+        write(
+            &data_dir,
+            "images/characters/c1/new.png",
+            50,
+            Duration::ZERO,
+        );
+        // Outside the managed folders
+        write(&data_dir, "logs/inference.log", 60, old);
+
+        (data_dir, conn)
+    }
+
+    #[test]
+    fn normalizes_stored_paths() {
+        let root = std::env::temp_dir();
+        let data_dir = root.join("app");
+        let data_dir = data_dir.as_path();
+        let inside = data_dir.join("images").join("avatar.png");
+        let outside = root.join("elsewhere").join("avatar.png");
+        assert_eq!(
+            normalize_reference(data_dir, "images\\Characters\\c1\\Happy.png").as_deref(),
+            Some("images/characters/c1/happy.png")
+        );
+        assert_eq!(
+            normalize_reference(data_dir, &inside.to_string_lossy()).as_deref(),
+            Some("images/avatar.png")
+        );
+        assert_eq!(
+            normalize_reference(data_dir, &outside.to_string_lossy()),
+            None
+        );
+        assert_eq!(
+            normalize_reference(data_dir, "https://example.com/a.png"),
+            None
+        );
+        assert_eq!(
+            object_hash(&format!("objects/aa/{}.png", HASH_KEPT)).as_deref(),
+            Some(HASH_KEPT)
+        );
+        assert_eq!(object_hash("images/avatar.png"), None);
+    }
+
+    #[test]
+    fn finds_only_unreferenced_old_files_in_managed_folders() {
+        tauri::async_runtime::block_on(async {
+            let (data_dir, mut conn) = setup("find").await;
+
+            let orphans = find_orphans(&mut conn, &data_dir, SystemTime::now())
+                .await
+                .unwrap();
+            let paths: Vec<&str> = orphans
+                .iter()
+                .map(|orphan| orphan.info.path.as_str())
+                .collect();
+            assert_eq!(
+                paths,
+                vec![
+                    "images/characters/gone/happy.png".to_string(),
+                    format!("objects/cc/{}.png", HASH_ORPHAN),
+                    "profiles/avatar_gone.png".to_string(),
+                ]
+            );
+            assert_eq!(orphans[0].info.size, 40);
+
+            let _ = conn.close().await;
+        });
+    }
+
+    #[test]
+    fn cleanup_removes_orphans_and_their_asset_rows() {
+        tauri::async_runtime::block_on(async {
+            let (data_dir, mut conn) = setup("cleanup").await;
+
+            let report = remove_orphans(&mut conn, &data_dir, SystemTime::now())
+                .await
+                .unwrap();
+            assert_eq!(report.removed, 3);
+            assert_eq!(report.freed_bytes, 120);
+
+            assert!(!data_dir.join("images/characters/gone/happy.png").exists());
+            assert!(!data_dir.join("profiles/avatar_gone.png").exists());
+            assert!(!data_dir
+                .join(format!("objects/cc/{}.png", HASH_ORPHAN))
+                .exists());
+            for kept in [
+                format!("objects/aa/{}.png", HASH_KEPT),
+                format!("objects/bb/{}.png", HASH_COUNTED),
+                "images/avatar_p1.png".to_string(),
+                "images/characters/c1/sad.png".to_string(),
+                "images/characters/c1/new.png".to_string(),
+                "logs/inference.log".to_string(),
+                "profiles/notes.json".to_string(),
+            ] {
+                assert!(data_dir.join(&kept).exists(), "{} was removed", kept);
+            }
+
+            let asset_rows: Vec<String> = sqlx::query("SELECT hash FROM assets ORDER BY hash")
+                .fetch_all(&mut conn)
+                .await
+                .unwrap()
+                .iter()
+                .map(|row| row.get("hash"))
+                .collect();
+            assert_eq!(asset_rows, vec![HASH_COUNTED.to_string()]);
+
+            assert!(find_orphans(&mut conn, &data_dir, SystemTime::now())
+                .await
+                .unwrap()
+                .is_empty());
+            assert_eq!(
+                check_profile(&mut conn, "other").await.unwrap_err().code,
+                crate::error::ErrorCode::NotFound
+            );
+
+            let _ = conn.close().await;
+        });
+    }
+}
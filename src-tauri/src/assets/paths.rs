@@ -0,0 +1,278 @@
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use unicode_normalization::UnicodeNormalization;
+
+// Every file name the app derives from user text goes through here, so names from imports
+// (emoji, other scripts, device names, very long titles) are valid on Windows, macOS and Linux alike.
+
+// Longest stem kept, in UTF-8 bytes. A UTF-8 byte count never undercounts UTF-16 units,
+// so the whole name also stays under the 255 unit limit of NTFS.
+const MAX_STEM_BYTES: usize = 100;
+const HASH_LENGTH: usize = 8;
+// Beyond this many bytes, Windows paths need the extended-length form
+const WINDOWS_PATH_THRESHOLD: usize = 240;
+
+// Opened as devices on Windows whatever the extension
+const RESERVED_NAMES: [&str; 28] = [
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9", "COM¹", "COM²",
+    "COM³", "LPT¹", "LPT²", "LPT³",
+];
+
+fn is_unsafe_char(c: char) -> bool {
+    c.is_control() || matches!(c, '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*')
+}
+
+fn is_reserved_name(stem: &str) -> bool {
+    // "CON.txt" and "con .tar" are as reserved as "CON"
+    let base = stem.split('.').next().unwrap_or(stem).trim_end();
+    RESERVED_NAMES
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(base))
+}
+
+// Cut at the last character boundary within the budget
+fn truncate_to_bytes(value: &str, max_bytes: usize) -> &str {
+    if value.len() <= max_bytes {
+        return value;
+    }
+    let mut end = max_bytes;
+    while !value.is_char_boundary(end) {
+        end -= 1;
+    }
+    &value[..end]
+}
+
+// Extensions end up in file names, keep them to a short alphanumeric token
+pub(crate) fn sanitize_extension(extension: &str) -> String {
+    let extension: String = extension
+        .trim_start_matches('.')
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .take(8)
+        .collect::<String>()
+        .to_lowercase();
+    if extension.is_empty() {
+        "bin".to_string()
+    } else {
+        extension
+    }
+}
+
+fn safe_stem(normalized: &str) -> String {
+    let replaced: String = normalized
+        .chars()
+        .map(|c| if is_unsafe_char(c) { '_' } else { c })
+        .collect();
+    // Leading dots hide files on Unix, trailing dots and spaces are dropped by Windows
+    let stem = truncate_to_bytes(replaced.trim_start_matches(['.', ' ']), MAX_STEM_BYTES)
+        .trim_end_matches(['.', ' ']);
+
+    if stem.is_empty() {
+        "file".to_string()
+    } else if is_reserved_name(stem) {
+        format!("_{}", stem)
+    } else {
+        stem.to_string()
+    }
+}
+
+/// File name for user-provided text, with the sanitized extension. Unicode is normalized to NFC
+/// first, so the same name typed on macOS and Windows gives the same file name.
+pub fn safe_file_name(name: &str, extension: &str) -> String {
+    let normalized: String = name.nfc().collect();
+    format!(
+        "{}.{}",
+        safe_stem(&normalized),
+        sanitize_extension(extension)
+    )
+}
+
+/// Like `safe_file_name`, with a short hash of the normalized name so different names that
+/// sanitize or truncate to the same text never share a file.
+pub fn unique_file_name(name: &str, extension: &str) -> String {
+    let normalized: String = name.nfc().collect();
+    let hash = hex::encode(Sha256::digest(normalized.as_bytes()));
+    format!(
+        "{}-{}.{}",
+        safe_stem(&normalized),
+        &hash[..HASH_LENGTH],
+        sanitize_extension(extension)
+    )
+}
+
+// `\\?\` form of a long absolute Windows path, None when it isn't needed or can't apply
+fn extended_length_form(path: &str) -> Option<String> {
+    if path.len() < WINDOWS_PATH_THRESHOLD || path.starts_with(r"\\?\") {
+        return None;
+    }
+    if let Some(share) = path.strip_prefix(r"\\") {
+        return Some(format!(r"\\?\UNC\{}", share));
+    }
+    // Only drive-absolute paths ("C:\...") can take the prefix
+    let bytes = path.as_bytes();
+    if bytes.len() > 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' && bytes[2] == b'\\' {
+        return Some(format!(r"\\?\{}", path));
+    }
+    None
+}
+
+/// Path to hand to file APIs: on Windows, long absolute paths get the extended-length prefix
+/// so they aren't limited to MAX_PATH. Unchanged elsewhere.
+pub fn long_path(path: &Path) -> PathBuf {
+    if cfg!(windows) {
+        if let Some(extended) = path.to_str().and_then(extended_length_form) {
+            return PathBuf::from(extended);
+        }
+    }
+    path.to_path_buf()
+}
+
+/// Safe file name for user-provided text, e.g. the suggested name of an export
+#[tauri::command]
+pub fn sanitize_file_name(name: String, extension: String, unique: Option<bool>) -> String {
+    if unique.unwrap_or(false) {
+        unique_file_name(&name, &extension)
+    } else {
+        safe_file_name(&name, &extension)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const ADVERSARIAL_NAMES: &[&str] = &[
+        "",
+        " ",
+        "...",
+        ". .",
+        "CON",
+        "con",
+        "Con.txt",
+        "NUL ",
+        "lpt9",
+        "COM¹",
+        "AUX.tar.gz",
+        "trailing dot.",
+        "trailing space ",
+        ".hidden",
+        "a/b\\c:d*e?f\"g<h>i|j",
+        "tab\tnew\nline\u{0}nul\u{7f}",
+        "emoji 🐉🔥 party 🎉",
+        "日本語のキャラクター",
+        "Ελληνικά and Кириллица mixed עברית",
+        "e\u{301}te\u{301}",
+        "été",
+        "👨‍👩‍👧‍👦👨‍👩‍👧‍👦👨‍👩‍👧‍👦👨‍👩‍👧‍👦👨‍👩‍👧‍👦👨‍👩‍👧‍👦👨‍👩‍👧‍👦",
+        "a very long character name that goes on and on well past any sensible length for a file name on any operating system at all, really",
+    ];
+
+    // Rules shared by NTFS, APFS and ext4, plus Windows' own restrictions
+    fn assert_portable(name: &str) {
+        assert!(!name.is_empty(), "empty name");
+        assert!(name.len() <= 255, "{:?} is over 255 bytes", name);
+        assert!(
+            name.encode_utf16().count() <= 255,
+            "{:?} is over 255 UTF-16 units",
+            name
+        );
+        assert!(
+            !name.chars().any(is_unsafe_char),
+            "{:?} has a forbidden character",
+            name
+        );
+        assert!(
+            !name.ends_with(['.', ' ']),
+            "{:?} ends with a dot or space",
+            name
+        );
+        assert!(!name.starts_with('.'), "{:?} is hidden on Unix", name);
+        assert!(
+            !is_reserved_name(name),
+            "{:?} is a Windows device name",
+            name
+        );
+        assert!(name.nfc().eq(name.chars()), "{:?} is not NFC", name);
+    }
+
+    // Every adversarial name, alone and combined with others, with assorted extensions
+    fn cases() -> Vec<(String, &'static str)> {
+        let extensions = [
+            "png",
+            ".JPG",
+            "",
+            "tar.gz",
+            "../../exe",
+            "very-long-extension",
+        ];
+        let mut cases = Vec::new();
+        for (index, first) in ADVERSARIAL_NAMES.iter().enumerate() {
+            let second = ADVERSARIAL_NAMES[(index * 7 + 3) % ADVERSARIAL_NAMES.len()];
+            let extension = extensions[index % extensions.len()];
+            cases.push((first.to_string(), extension));
+            cases.push((format!("{}{}", first, second), extension));
+            cases.push((first.repeat(20), extension));
+        }
+        cases
+    }
+
+    #[test]
+    fn produces_portable_names() {
+        for (name, extension) in cases() {
+            assert_portable(&safe_file_name(&name, extension));
+            assert_portable(&unique_file_name(&name, extension));
+        }
+    }
+
+    #[test]
+    fn produced_names_are_writable_here() {
+        let dir = std::env::temp_dir().join(format!("narratrix-paths-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for (name, extension) in cases() {
+            let path = dir.join(unique_file_name(&name, extension));
+            fs::write(long_path(&path), b"ok").unwrap_or_else(|e| panic!("{:?}: {}", path, e));
+        }
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn keeps_the_extension_and_readable_text() {
+        assert_eq!(safe_file_name("Alice", "PNG"), "Alice.png");
+        assert_eq!(safe_file_name("CON", "txt"), "_CON.txt");
+        assert_eq!(safe_file_name("  notes. ", "md"), "notes.md");
+        assert_eq!(safe_file_name("日本語", "json"), "日本語.json");
+        assert!(safe_file_name(&"🐉".repeat(200), "png").ends_with(".png"));
+    }
+
+    #[test]
+    fn normalizes_before_hashing() {
+        // Decomposed (macOS) and composed (Windows) forms of the same name
+        assert_eq!(
+            unique_file_name("e\u{301}te\u{301}", "png"),
+            unique_file_name("été", "png")
+        );
+        assert_ne!(
+            unique_file_name("a?b", "png"),
+            unique_file_name("a*b", "png")
+        );
+    }
+
+    #[test]
+    fn prefixes_long_windows_paths() {
+        let long = format!(r"C:\Users\someone\AppData\Roaming\{}", "x".repeat(240));
+        assert_eq!(extended_length_form(&long), Some(format!(r"\\?\{}", long)));
+        let share = format!(r"\\server\share\{}", "x".repeat(240));
+        assert_eq!(
+            extended_length_form(&share),
+            Some(format!(r"\\?\UNC\server\share\{}", "x".repeat(240)))
+        );
+        assert_eq!(extended_length_form(r"C:\short"), None);
+        assert_eq!(extended_length_form(&format!(r"\\?\{}", long)), None);
+        assert_eq!(
+            extended_length_form(&format!("relative\\{}", "x".repeat(240))),
+            None
+        );
+    }
+}
@@ -0,0 +1,513 @@
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqliteConnection;
+use sqlx::{Connection, Row};
+use std::collections::HashMap;
+use tauri::{AppHandle, Window};
+
+use super::open_connection;
+use crate::error::AppError;
+use crate::inference::request_log::completed_request_times;
+use crate::windows::verify_window_profile;
+
+// A profile's activity over time, for a "year in review" style dashboard: messages, generations
+// and new chats and characters per day, week or month. Dates are compared as the ISO strings they
+// are stored as (UTC), so every count is a range read on an index (migration 29).
+
+// A day-by-day view of a few years at most
+const MAX_BUCKETS: usize = 1000;
+const TOP_LIMIT: i64 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Granularity {
+    Day,
+    Week,
+    Month,
+}
+
+impl Granularity {
+    // First day of the bucket holding `column`. Weeks start on Monday.
+    fn bucket_of(self, column: &str) -> String {
+        match self {
+            Granularity::Day => format!("date({})", column),
+            Granularity::Week => format!("date({}, 'weekday 0', '-6 days')", column),
+            Granularity::Month => format!("date({}, 'start of month')", column),
+        }
+    }
+
+    fn step(self) -> &'static str {
+        match self {
+            Granularity::Day => "+1 day",
+            Granularity::Week => "+7 days",
+            Granularity::Month => "+1 month",
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, PartialEq)]
+pub struct ActivityBucket {
+    // First day of the bucket, YYYY-MM-DD
+    pub start: String,
+    pub user_messages: i64,
+    pub character_messages: i64,
+    // Completed requests in the inference log
+    pub generations: i64,
+    pub chats_created: i64,
+    pub characters_created: i64,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct RankedItem {
+    pub id: String,
+    pub name: String,
+    pub messages: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ActivityTimeline {
+    pub granularity: Granularity,
+    // Every bucket from `from` to `to`, oldest first, including empty ones
+    pub buckets: Vec<ActivityBucket>,
+    // Characters with the most messages in the range
+    pub top_characters: Vec<RankedItem>,
+    // Chats with the most messages in the range
+    pub top_chats: Vec<RankedItem>,
+}
+
+// The inclusive range as [from, until) bounds for the ISO columns
+async fn day_range(
+    conn: &mut SqliteConnection,
+    from: &str,
+    to: &str,
+) -> Result<(String, String), AppError> {
+    let row =
+        sqlx::query("SELECT date($1) AS from_day, date($2) AS to_day, date($2, '+1 day') AS until")
+            .bind(from)
+            .bind(to)
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to read the date range: {}", e))?;
+    let (Some(from_day), Some(to_day), Some(until)) = (
+        row.get::<Option<String>, _>("from_day"),
+        row.get::<Option<String>, _>("to_day"),
+        row.get::<Option<String>, _>("until"),
+    ) else {
+        return Err(AppError::validation(format!(
+            "Expected dates as YYYY-MM-DD, got '{}' and '{}'",
+            from, to
+        )));
+    };
+    if from_day > to_day {
+        return Err(AppError::validation(format!(
+            "The range starts ({}) after it ends ({})",
+            from_day, to_day
+        )));
+    }
+    Ok((from_day, until))
+}
+
+async fn empty_buckets(
+    conn: &mut SqliteConnection,
+    granularity: Granularity,
+    from_day: &str,
+    until: &str,
+) -> Result<Vec<ActivityBucket>, AppError> {
+    let sql = format!(
+        "WITH RECURSIVE buckets(start) AS (
+            SELECT {}
+            UNION ALL
+            SELECT date(start, '{}') FROM buckets WHERE date(start, '{}') < $2
+         )
+         SELECT start FROM buckets LIMIT {}",
+        granularity.bucket_of("$1"),
+        granularity.step(),
+        granularity.step(),
+        MAX_BUCKETS + 1
+    );
+    let rows = sqlx::query(&sql)
+        .bind(from_day)
+        .bind(until)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to list activity buckets: {}", e))?;
+    if rows.len() > MAX_BUCKETS {
+        return Err(AppError::validation(format!(
+            "The range has more than {} buckets, pick a coarser granularity",
+            MAX_BUCKETS
+        )));
+    }
+    Ok(rows
+        .iter()
+        .map(|row| ActivityBucket {
+            start: row.get("start"),
+            ..Default::default()
+        })
+        .collect())
+}
+
+// The profile's messages of the range, per bucket and author
+fn message_counts_sql(granularity: Granularity) -> String {
+    format!(
+        "SELECT {} AS bucket,
+            SUM(m.type = 'user') AS user_messages,
+            SUM(m.type = 'character') AS character_messages
+         FROM chat_messages m JOIN chats c ON c.id = m.chat_id
+         WHERE c.profile_id = $1 AND m.created_at >= $2 AND m.created_at < $3
+         GROUP BY bucket",
+        granularity.bucket_of("m.created_at")
+    )
+}
+
+async fn ranked(
+    conn: &mut SqliteConnection,
+    sql: &str,
+    profile_id: &str,
+    from_day: &str,
+    until: &str,
+) -> Result<Vec<RankedItem>, String> {
+    let rows = sqlx::query(sql)
+        .bind(profile_id)
+        .bind(from_day)
+        .bind(until)
+        .bind(TOP_LIMIT)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to rank activity: {}", e))?;
+    Ok(rows
+        .iter()
+        .map(|row| RankedItem {
+            id: row.get("id"),
+            name: row.get("name"),
+            messages: row.get("messages"),
+        })
+        .collect())
+}
+
+async fn build_timeline(
+    conn: &mut SqliteConnection,
+    profile_id: &str,
+    granularity: Granularity,
+    from: &str,
+    to: &str,
+    // Timestamps of completed generations, already limited to the profile and range
+    generation_times: impl FnOnce(&str, &str) -> Result<Vec<String>, String>,
+) -> Result<ActivityTimeline, AppError> {
+    let (from_day, until) = day_range(conn, from, to).await?;
+    let mut buckets = empty_buckets(conn, granularity, &from_day, &until).await?;
+    let index: HashMap<String, usize> = buckets
+        .iter()
+        .enumerate()
+        .map(|(position, bucket)| (bucket.start.clone(), position))
+        .collect();
+
+    let rows = sqlx::query(&message_counts_sql(granularity))
+        .bind(profile_id)
+        .bind(&from_day)
+        .bind(&until)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to count messages: {}", e))?;
+    // Rows with an unreadable created_at have no bucket and are left out
+    for row in rows {
+        let bucket: Option<String> = row.get("bucket");
+        if let Some(&position) = bucket.and_then(|bucket| index.get(&bucket)) {
+            buckets[position].user_messages = row.get("user_messages");
+            buckets[position].character_messages = row.get("character_messages");
+        }
+    }
+
+    for (table, is_chats) in [("chats", true), ("characters", false)] {
+        let sql = format!(
+            "SELECT {} AS bucket, COUNT(*) AS created FROM {}
+             WHERE profile_id = $1 AND created_at >= $2 AND created_at < $3
+             GROUP BY bucket",
+            granularity.bucket_of("created_at"),
+            table
+        );
+        let rows = sqlx::query(&sql)
+            .bind(profile_id)
+            .bind(&from_day)
+            .bind(&until)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to count new {}: {}", table, e))?;
+        for row in rows {
+            let bucket: Option<String> = row.get("bucket");
+            if let Some(&position) = bucket.and_then(|bucket| index.get(&bucket)) {
+                let created: i64 = row.get("created");
+                if is_chats {
+                    buckets[position].chats_created = created;
+                } else {
+                    buckets[position].characters_created = created;
+                }
+            }
+        }
+    }
+
+    // Log timestamps are bucketed by SQLite too, so they land in the same buckets as the rows
+    let times = generation_times(&from_day, &until)?;
+    if !times.is_empty() {
+        let times = serde_json::to_string(&times)
+            .map_err(|e| format!("Failed to serialize generation times: {}", e))?;
+        let sql = format!(
+            "SELECT {} AS bucket, COUNT(*) AS generations FROM json_each($1) GROUP BY bucket",
+            granularity.bucket_of("value")
+        );
+        let rows = sqlx::query(&sql)
+            .bind(times)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to count generations: {}", e))?;
+        for row in rows {
+            let bucket: Option<String> = row.get("bucket");
+            if let Some(&position) = bucket.and_then(|bucket| index.get(&bucket)) {
+                buckets[position].generations = row.get("generations");
+            }
+        }
+    }
+
+    let top_characters = ranked(
+        conn,
+        "SELECT ch.id, ch.name, COUNT(*) AS messages
+         FROM chat_messages m
+         JOIN chats c ON c.id = m.chat_id
+         JOIN characters ch ON ch.id = m.character_id
+         WHERE c.profile_id = $1 AND m.type = 'character' AND m.created_at >= $2 AND m.created_at < $3
+         GROUP BY ch.id ORDER BY messages DESC, ch.name LIMIT $4",
+        profile_id,
+        &from_day,
+        &until,
+    )
+    .await?;
+    let top_chats = ranked(
+        conn,
+        "SELECT c.id, c.name, COUNT(*) AS messages
+         FROM chat_messages m JOIN chats c ON c.id = m.chat_id
+         WHERE c.profile_id = $1 AND m.created_at >= $2 AND m.created_at < $3
+         GROUP BY c.id ORDER BY messages DESC, c.name LIMIT $4",
+        profile_id,
+        &from_day,
+        &until,
+    )
+    .await?;
+
+    Ok(ActivityTimeline {
+        granularity,
+        buckets,
+        top_characters,
+        top_chats,
+    })
+}
+
+/// A profile's activity from `from` to `to` (inclusive, YYYY-MM-DD, UTC) in day, week or month
+/// buckets ready for charting: messages by author, completed generations and new chats and
+/// characters, plus the busiest characters and chats of the range.
+#[tauri::command]
+pub async fn get_activity_timeline(
+    app: AppHandle,
+    window: Window,
+    profile_id: String,
+    granularity: Granularity,
+    from: String,
+    to: String,
+) -> Result<ActivityTimeline, AppError> {
+    verify_window_profile(&window, &profile_id)?;
+    let mut conn = open_connection(&app).await?;
+    let result = build_timeline(
+        &mut conn,
+        &profile_id,
+        granularity,
+        &from,
+        &to,
+        |from_day, until| completed_request_times(&app, &profile_id, from_day, until),
+    )
+    .await;
+    let _ = conn.close().await;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_db::temp_database;
+    use crate::error::ErrorCode;
+
+    // 100k messages over 2024, one every 5 minutes (the last ones fall in mid-December), alternating
+    // user and character, spread over 18 chats of p1 and 2 chats of p2
+    const MESSAGES: i64 = 100_000;
+
+    async fn connect(name: &str) -> SqliteConnection {
+        temp_database(&format!("activity-{}", name)).await
+    }
+
+    async fn seed(conn: &mut SqliteConnection) {
+        for sql in [
+            "INSERT INTO profiles (id, name) VALUES ('p1', 'Me'), ('p2', 'Other')",
+            "INSERT INTO characters (id, profile_id, name, version, type, created_at)
+             VALUES ('k1', 'p1', 'Ann', '1.0.0', 'character', '2024-01-02T10:00:00.000Z'),
+                    ('k2', 'p1', 'Bo', '1.0.0', 'character', '2024-02-20 09:00:00')",
+            "WITH RECURSIVE n(i) AS (SELECT 0 UNION ALL SELECT i + 1 FROM n WHERE i < 19)
+             INSERT INTO chats (id, profile_id, name, participants, created_at)
+             SELECT 'c' || i, CASE WHEN i < 18 THEN 'p1' ELSE 'p2' END, 'Chat ' || i, '[]',
+                    strftime('%Y-%m-%dT%H:%M:%fZ', '2024-01-01', '+' || i || ' days')
+             FROM n",
+            "INSERT INTO chat_chapters (id, chat_id, title, sequence)
+             SELECT 'ch' || substr(id, 2), id, 'One', 1 FROM chats",
+            "WITH RECURSIVE n(i) AS (SELECT 0 UNION ALL SELECT i + 1 FROM n WHERE i < 99999)
+             INSERT INTO chat_messages (id, chat_id, chapter_id, character_id, type, position, messages, message_index, created_at)
+             SELECT 'm' || i, 'c' || (i % 20), 'ch' || (i % 20),
+                    CASE WHEN i % 2 = 1 THEN 'k' || (1 + (i / 2) % 2) END,
+                    CASE WHEN i % 2 = 0 THEN 'user' ELSE 'character' END,
+                    i, '[]', 0,
+                    strftime('%Y-%m-%dT%H:%M:%fZ', '2024-01-01', '+' || (i * 5) || ' minutes')
+             FROM n",
+        ] {
+            sqlx::query(sql).execute(&mut *conn).await.unwrap();
+        }
+    }
+
+    fn no_generations(_: &str, _: &str) -> Result<Vec<String>, String> {
+        Ok(Vec::new())
+    }
+
+    #[test]
+    fn aggregates_a_large_profile() {
+        tauri::async_runtime::block_on(async {
+            let mut conn = connect("large").await;
+            seed(&mut conn).await;
+
+            let started = std::time::Instant::now();
+            let timeline = build_timeline(
+                &mut conn,
+                "p1",
+                Granularity::Month,
+                "2024-01-01",
+                "2024-12-31",
+                |from, until| {
+                    assert_eq!((from, until), ("2024-01-01", "2025-01-01"));
+                    Ok(vec![
+                        "2024-01-31T23:59:59.000Z".to_string(),
+                        "2024-02-01T00:00:00.000Z".to_string(),
+                        "2024-02-14T12:00:00.000Z".to_string(),
+                    ])
+                },
+            )
+            .await
+            .unwrap();
+            let elapsed = started.elapsed();
+
+            assert_eq!(timeline.buckets.len(), 12);
+            assert_eq!(timeline.buckets[0].start, "2024-01-01");
+            assert_eq!(timeline.buckets[11].start, "2024-12-01");
+
+            // Chats 18 and 19 belong to p2: 10% of the messages
+            let user: i64 = timeline.buckets.iter().map(|b| b.user_messages).sum();
+            let character: i64 = timeline.buckets.iter().map(|b| b.character_messages).sum();
+            assert_eq!(user + character, MESSAGES * 18 / 20);
+            assert_eq!(user, character);
+            // 31 days of messages every 5 minutes (8928), less those of chats 18 and 19
+            let january = &timeline.buckets[0];
+            assert_eq!(january.user_messages + january.character_messages, 8036);
+
+            assert_eq!(january.chats_created, 18);
+            assert_eq!(january.characters_created, 1);
+            assert_eq!(timeline.buckets[1].characters_created, 1);
+            assert_eq!(january.generations, 1);
+            assert_eq!(timeline.buckets[1].generations, 2);
+
+            let characters: Vec<(&str, i64)> = timeline
+                .top_characters
+                .iter()
+                .map(|item| (item.name.as_str(), item.messages))
+                .collect();
+            // Chat 19 only ever gets Bo's messages
+            assert_eq!(characters, vec![("Ann", 25_000), ("Bo", 20_000)]);
+            assert_eq!(timeline.top_chats.len(), 5);
+            assert_eq!(timeline.top_chats[0].messages, MESSAGES / 20);
+
+            // Generous bound for slow CI; the plan check below is the real guarantee
+            assert!(elapsed.as_secs() < 10, "took {:?}", elapsed);
+        });
+    }
+
+    #[test]
+    fn reads_messages_through_the_date_index() {
+        tauri::async_runtime::block_on(async {
+            let mut conn = connect("plan").await;
+            for granularity in [Granularity::Day, Granularity::Week, Granularity::Month] {
+                let plan: Vec<String> = sqlx::query(&format!(
+                    "EXPLAIN QUERY PLAN {}",
+                    message_counts_sql(granularity)
+                ))
+                .bind("p1")
+                .bind("2024-01-01")
+                .bind("2025-01-01")
+                .fetch_all(&mut conn)
+                .await
+                .unwrap()
+                .iter()
+                .map(|row| row.get::<String, _>("detail"))
+                .collect();
+                assert!(
+                    plan.iter()
+                        .any(|step| step.contains("idx_messages_chat_created")),
+                    "{:?}",
+                    plan
+                );
+                assert!(
+                    !plan.iter().any(|step| step.starts_with("SCAN")),
+                    "{:?}",
+                    plan
+                );
+            }
+        });
+    }
+
+    #[test]
+    fn fills_empty_weeks_and_rejects_bad_ranges() {
+        tauri::async_runtime::block_on(async {
+            let mut conn = connect("weeks").await;
+
+            // 2024-01-03 is a Wednesday, its week starts on Monday 2024-01-01
+            let timeline = build_timeline(
+                &mut conn,
+                "p1",
+                Granularity::Week,
+                "2024-01-03",
+                "2024-01-22",
+                no_generations,
+            )
+            .await
+            .unwrap();
+            let starts: Vec<&str> = timeline.buckets.iter().map(|b| b.start.as_str()).collect();
+            assert_eq!(
+                starts,
+                vec!["2024-01-01", "2024-01-08", "2024-01-15", "2024-01-22"]
+            );
+            assert!(timeline.buckets.iter().all(|bucket| *bucket
+                == ActivityBucket {
+                    start: bucket.start.clone(),
+                    ..Default::default()
+                }));
+
+            for (from, to) in [("2024-02-01", "2024-01-01"), ("yesterday", "2024-01-01")] {
+                let error =
+                    build_timeline(&mut conn, "p1", Granularity::Day, from, to, no_generations)
+                        .await
+                        .unwrap_err();
+                assert_eq!(error.code, ErrorCode::Validation);
+            }
+            let error = build_timeline(
+                &mut conn,
+                "p1",
+                Granularity::Day,
+                "2000-01-01",
+                "2024-01-01",
+                no_generations,
+            )
+            .await
+            .unwrap_err();
+            assert!(error.message.contains("coarser granularity"));
+        });
+    }
+}
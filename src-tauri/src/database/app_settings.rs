@@ -0,0 +1,146 @@
+use serde_json::Value;
+use sqlx::sqlite::SqliteConnection;
+use sqlx::{Connection, Row};
+use tauri::AppHandle;
+
+use super::open_connection;
+use crate::error::AppError;
+
+// App-wide settings live outside the profile tables, so they can be read before any login
+
+const MAX_KEY_LENGTH: usize = 128;
+
+// Keys are short identifiers like "window.main" or "last_profile_id"
+fn validate_key(key: &str) -> Result<(), AppError> {
+    if key.is_empty() || key.len() > MAX_KEY_LENGTH {
+        return Err(AppError::validation(format!(
+            "App setting keys must be 1 to {} characters long",
+            MAX_KEY_LENGTH
+        )));
+    }
+    if !key
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+    {
+        return Err(AppError::validation(format!(
+            "Invalid app setting key \"{}\": use letters, digits, '_', '.' or '-'",
+            key
+        )));
+    }
+    Ok(())
+}
+
+async fn read_setting(conn: &mut SqliteConnection, key: &str) -> Result<Option<Value>, String> {
+    let row = sqlx::query("SELECT value FROM app_settings WHERE key = $1")
+        .bind(key)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to read app setting {}: {}", key, e))?;
+
+    row.map(|row| {
+        serde_json::from_str(&row.get::<String, _>("value"))
+            .map_err(|e| format!("App setting {} is not valid JSON: {}", key, e))
+    })
+    .transpose()
+}
+
+// A null value removes the key, so reading it gives None again
+async fn write_setting(
+    conn: &mut SqliteConnection,
+    key: &str,
+    value: &Value,
+) -> Result<(), String> {
+    let query = if value.is_null() {
+        sqlx::query("DELETE FROM app_settings WHERE key = $1").bind(key)
+    } else {
+        sqlx::query(
+            "INSERT INTO app_settings (key, value, updated_at) VALUES ($1, $2, CURRENT_TIMESTAMP)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+        )
+        .bind(key)
+        .bind(value.to_string())
+    };
+    query
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to save app setting {}: {}", key, e))?;
+    Ok(())
+}
+
+/// Value of an app-wide setting, or null when it was never set
+#[tauri::command]
+pub async fn get_app_setting(app: AppHandle, key: String) -> Result<Option<Value>, AppError> {
+    validate_key(&key)?;
+    let mut conn = open_connection(&app).await?;
+    let result = read_setting(&mut conn, &key).await;
+    let _ = conn.close().await;
+    result.map_err(AppError::from)
+}
+
+/// Store any JSON value under an app-wide key; null removes the key
+#[tauri::command]
+pub async fn set_app_setting(app: AppHandle, key: String, value: Value) -> Result<(), AppError> {
+    validate_key(&key)?;
+    let mut conn = open_connection(&app).await?;
+    let result = write_setting(&mut conn, &key, &value).await;
+    let _ = conn.close().await;
+    result.map_err(AppError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_db::temp_database;
+    use serde_json::json;
+
+    #[test]
+    fn stores_replaces_and_removes_values() {
+        tauri::async_runtime::block_on(async {
+            let mut conn = temp_database("app-settings-roundtrip").await;
+
+            assert_eq!(
+                read_setting(&mut conn, "last_profile_id").await.unwrap(),
+                None
+            );
+
+            write_setting(&mut conn, "last_profile_id", &json!("p1"))
+                .await
+                .unwrap();
+            write_setting(
+                &mut conn,
+                "window.main",
+                &json!({ "width": 1280, "maximized": false }),
+            )
+            .await
+            .unwrap();
+            write_setting(&mut conn, "last_profile_id", &json!("p2"))
+                .await
+                .unwrap();
+
+            assert_eq!(
+                read_setting(&mut conn, "last_profile_id").await.unwrap(),
+                Some(json!("p2"))
+            );
+            assert_eq!(
+                read_setting(&mut conn, "window.main").await.unwrap(),
+                Some(json!({ "width": 1280, "maximized": false }))
+            );
+
+            write_setting(&mut conn, "last_profile_id", &Value::Null)
+                .await
+                .unwrap();
+            assert_eq!(
+                read_setting(&mut conn, "last_profile_id").await.unwrap(),
+                None
+            );
+        });
+    }
+
+    #[test]
+    fn rejects_malformed_keys() {
+        assert!(validate_key("telemetry.opt_out").is_ok());
+        assert!(validate_key("").is_err());
+        assert!(validate_key("has space").is_err());
+        assert!(validate_key(&"k".repeat(MAX_KEY_LENGTH + 1)).is_err());
+    }
+}
@@ -0,0 +1,387 @@
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::sqlite::{SqliteConnection, SqliteRow};
+use sqlx::{Connection, Row};
+use std::collections::HashMap;
+use tauri::{AppHandle, Window};
+use uuid::Uuid;
+
+use super::open_connection;
+use crate::error::AppError;
+use crate::windows::verify_window_profile;
+
+// A duplicate is a full, independent copy of a chat: chapters, messages (every variant, metadata
+// and pin state) and markers, all under new ids. Nothing links it back to the original.
+
+// Shape of the TS `Chat`, with the JSON columns parsed
+#[derive(Debug, Serialize)]
+pub struct Chat {
+    pub id: String,
+    pub profile_id: String,
+    pub name: String,
+    pub chat_template_id: Option<String>,
+    pub active_chapter_id: Option<String>,
+    pub participants: Value,
+    pub user_character_id: Option<String>,
+    pub user_character_settings: Value,
+    pub settings: Option<Value>,
+    pub favorite: bool,
+    pub synopsis: Option<String>,
+    pub synopsis_up_to_sequence: i64,
+    pub synopsis_updated_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+pub(crate) const CHAT_COLUMNS: &str =
+    "id, profile_id, name, chat_template_id, active_chapter_id, participants,
+    user_character_id, user_character_settings, settings, favorite, synopsis,
+    synopsis_up_to_sequence, synopsis_updated_at, created_at, updated_at";
+
+fn parse_json(raw: Option<String>) -> Option<Value> {
+    raw.and_then(|raw| serde_json::from_str(&raw).ok())
+}
+
+pub(crate) fn chat_from_row(row: &SqliteRow) -> Chat {
+    Chat {
+        id: row.get("id"),
+        profile_id: row.get("profile_id"),
+        name: row.get("name"),
+        chat_template_id: row.get("chat_template_id"),
+        active_chapter_id: row.get("active_chapter_id"),
+        participants: parse_json(row.get("participants")).unwrap_or(Value::Array(Vec::new())),
+        user_character_id: row.get("user_character_id"),
+        user_character_settings: parse_json(row.get("user_character_settings"))
+            .unwrap_or(Value::Array(Vec::new())),
+        settings: parse_json(row.get("settings")),
+        favorite: row.get("favorite"),
+        synopsis: row.get("synopsis"),
+        synopsis_up_to_sequence: row.get("synopsis_up_to_sequence"),
+        synopsis_updated_at: row.get("synopsis_updated_at"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
+
+fn new_id() -> String {
+    Uuid::new_v4().to_string()
+}
+
+async fn copy_chat(
+    conn: &mut SqliteConnection,
+    chat_id: &str,
+    profile_id: &str,
+    new_title: &str,
+) -> Result<Chat, AppError> {
+    let new_title = new_title.trim();
+    if new_title.is_empty() {
+        return Err(AppError::validation("The copy needs a name"));
+    }
+
+    let mut tx = conn
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start chat copy: {}", e))?;
+
+    // Other profiles' chats look the same as missing ones
+    let original = sqlx::query(
+        "SELECT active_chapter_id, synopsis_up_to_sequence FROM chats
+         WHERE id = $1 AND profile_id = $2",
+    )
+    .bind(chat_id)
+    .bind(profile_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to read chat {}: {}", chat_id, e))?
+    .ok_or_else(|| AppError::not_found(format!("Chat {} not found", chat_id)))?;
+    let synopsis_up_to: i64 = original.get("synopsis_up_to_sequence");
+
+    let new_chat_id = new_id();
+    sqlx::query(
+        "INSERT INTO chats (id, profile_id, name, participants, chat_template_id,
+            user_character_id, user_character_settings, settings, synopsis, synopsis_updated_at)
+         SELECT $1, profile_id, $2, participants, chat_template_id,
+            user_character_id, user_character_settings, settings, synopsis, synopsis_updated_at
+         FROM chats WHERE id = $3",
+    )
+    .bind(&new_chat_id)
+    .bind(new_title)
+    .bind(chat_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to copy chat {}: {}", chat_id, e))?;
+
+    let chapters = sqlx::query("SELECT id FROM chat_chapters WHERE chat_id = $1")
+        .bind(chat_id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to read chapters of chat {}: {}", chat_id, e))?;
+    let mut chapter_ids = HashMap::with_capacity(chapters.len());
+    for chapter in chapters {
+        let old_id: String = chapter.get("id");
+        let new_chapter_id = new_id();
+        sqlx::query(
+            "INSERT INTO chat_chapters (id, chat_id, title, sequence, scenario, instructions,
+                start_message, custom)
+             SELECT $1, $2, title, sequence, scenario, instructions, start_message, custom
+             FROM chat_chapters WHERE id = $3",
+        )
+        .bind(&new_chapter_id)
+        .bind(&new_chat_id)
+        .bind(&old_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to copy chapter {}: {}", old_id, e))?;
+        chapter_ids.insert(old_id, new_chapter_id);
+    }
+
+    // Copied in rowid order, so the synopsis refresh point can move to the copy's own rowids
+    let messages = sqlx::query(
+        "SELECT rowid, id, chapter_id FROM chat_messages WHERE chat_id = $1 ORDER BY rowid",
+    )
+    .bind(chat_id)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to read messages of chat {}: {}", chat_id, e))?;
+    let mut new_synopsis_up_to = 0;
+    for message in &messages {
+        let old_id: String = message.get("id");
+        let Some(new_chapter_id) = chapter_ids.get(&message.get::<String, _>("chapter_id")) else {
+            continue;
+        };
+        let inserted = sqlx::query(
+            "INSERT INTO chat_messages (id, chat_id, chapter_id, character_id, type, position,
+                messages, message_index, disabled, extra, messages_vector, tokens, pinned,
+                created_at, updated_at)
+             SELECT $1, $2, $3, character_id, type, position, messages, message_index, disabled,
+                extra, messages_vector, tokens, pinned, created_at, updated_at
+             FROM chat_messages WHERE id = $4",
+        )
+        .bind(new_id())
+        .bind(&new_chat_id)
+        .bind(new_chapter_id)
+        .bind(&old_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to copy message {}: {}", old_id, e))?;
+        if message.get::<i64, _>("rowid") <= synopsis_up_to {
+            new_synopsis_up_to = inserted.last_insert_rowid();
+        }
+    }
+
+    let markers = sqlx::query("SELECT id, chapter_id FROM chat_markers WHERE chat_id = $1")
+        .bind(chat_id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to read markers of chat {}: {}", chat_id, e))?;
+    for marker in &markers {
+        let old_id: String = marker.get("id");
+        let Some(new_chapter_id) = chapter_ids.get(&marker.get::<String, _>("chapter_id")) else {
+            continue;
+        };
+        sqlx::query(
+            "INSERT INTO chat_markers (id, chat_id, chapter_id, title, position, color, context_reset)
+             SELECT $1, $2, $3, title, position, color, context_reset
+             FROM chat_markers WHERE id = $4",
+        )
+        .bind(new_id())
+        .bind(&new_chat_id)
+        .bind(new_chapter_id)
+        .bind(&old_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to copy marker {}: {}", old_id, e))?;
+    }
+
+    let active_chapter_id = original
+        .get::<Option<String>, _>("active_chapter_id")
+        .and_then(|id| chapter_ids.get(&id).cloned());
+    sqlx::query(
+        "UPDATE chats SET active_chapter_id = $1, synopsis_up_to_sequence = $2 WHERE id = $3",
+    )
+    .bind(active_chapter_id)
+    .bind(new_synopsis_up_to)
+    .bind(&new_chat_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to update chat copy: {}", e))?;
+
+    let row = sqlx::query(&format!("SELECT {} FROM chats WHERE id = $1", CHAT_COLUMNS))
+        .bind(&new_chat_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to read chat copy: {}", e))?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit chat copy: {}", e))?;
+    Ok(chat_from_row(&row))
+}
+
+/// Copy a chat with all its chapters, messages and markers into a new chat of the same profile.
+/// Everything is written in one transaction, so a failure leaves no partial copy.
+#[tauri::command]
+pub async fn duplicate_chat(
+    app: AppHandle,
+    window: Window,
+    chat_id: String,
+    profile_id: String,
+    new_title: String,
+) -> Result<Chat, AppError> {
+    verify_window_profile(&window, &profile_id)?;
+    let mut conn = open_connection(&app).await?;
+    let result = copy_chat(&mut conn, &chat_id, &profile_id, &new_title).await;
+    let _ = conn.close().await;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_db::temp_database;
+    use crate::error::ErrorCode;
+
+    async fn connect(name: &str) -> SqliteConnection {
+        let mut conn = temp_database(&format!("chat-copy-{}", name)).await;
+        for sql in [
+            "INSERT INTO profiles (id, name) VALUES ('p1', 'Profile'), ('p2', 'Other')",
+            "INSERT INTO chats (id, profile_id, name, participants, settings, synopsis)
+             VALUES ('c1', 'p1', 'Inn', '[{\"id\":\"x\"}]', '{\"hideScriptMessages\":true}', 'They meet.')",
+            "INSERT INTO chat_chapters (id, chat_id, title, sequence) VALUES ('ch1', 'c1', 'One', 1)",
+            "INSERT INTO chat_chapters (id, chat_id, title, sequence) VALUES ('ch2', 'c1', 'Two', 2)",
+            "UPDATE chats SET active_chapter_id = 'ch2' WHERE id = 'c1'",
+            "INSERT INTO chat_messages (id, chat_id, chapter_id, type, position, messages, message_index, extra, pinned)
+             VALUES ('m1', 'c1', 'ch1', 'user', 100, '[\"hi\"]', 0, '{\"key_event\":true}', 1)",
+            "INSERT INTO chat_messages (id, chat_id, chapter_id, type, position, messages, message_index, disabled)
+             VALUES ('m2', 'c1', 'ch1', 'character', 200, '[\"a\",\"b\"]', 1, 1)",
+            "INSERT INTO chat_messages (id, chat_id, chapter_id, type, position, messages, message_index)
+             VALUES ('m3', 'c1', 'ch2', 'user', 100, '[\"later\"]', 0)",
+            "INSERT INTO chat_markers (id, chat_id, chapter_id, title, position) VALUES ('k1', 'c1', 'ch1', 'Dawn', 150)",
+        ] {
+            sqlx::query(sql).execute(&mut conn).await.unwrap();
+        }
+        // The synopsis covers the first chapter
+        sqlx::query(
+            "UPDATE chats SET synopsis_up_to_sequence = (SELECT rowid FROM chat_messages WHERE id = 'm2')",
+        )
+        .execute(&mut conn)
+        .await
+        .unwrap();
+        conn
+    }
+
+    async fn messages(
+        conn: &mut SqliteConnection,
+        chat_id: &str,
+    ) -> Vec<(String, i64, String, i64, bool, bool)> {
+        sqlx::query(
+            "SELECT m.messages, m.position, c.title, m.message_index, m.disabled, m.pinned
+             FROM chat_messages m JOIN chat_chapters c ON c.id = m.chapter_id
+             WHERE m.chat_id = $1 ORDER BY c.sequence, m.position",
+        )
+        .bind(chat_id)
+        .fetch_all(&mut *conn)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|row| {
+            (
+                row.get("messages"),
+                row.get("position"),
+                row.get("title"),
+                row.get("message_index"),
+                row.get("disabled"),
+                row.get("pinned"),
+            )
+        })
+        .collect()
+    }
+
+    #[test]
+    fn copies_chapters_messages_and_markers_under_new_ids() {
+        tauri::async_runtime::block_on(async {
+            let mut conn = connect("copy").await;
+
+            let copy = copy_chat(&mut conn, "c1", "p1", " Inn, take two ")
+                .await
+                .unwrap();
+            assert_ne!(copy.id, "c1");
+            assert_eq!(copy.name, "Inn, take two");
+            assert_eq!(copy.profile_id, "p1");
+            assert_eq!(copy.participants, serde_json::json!([{ "id": "x" }]));
+            assert_eq!(
+                copy.settings,
+                Some(serde_json::json!({ "hideScriptMessages": true }))
+            );
+            assert_eq!(copy.synopsis.as_deref(), Some("They meet."));
+            assert!(!copy.favorite);
+
+            assert_eq!(
+                messages(&mut conn, &copy.id).await,
+                messages(&mut conn, "c1").await
+            );
+            let shared: i64 = sqlx::query(
+                "SELECT COUNT(*) AS n FROM chat_messages WHERE chat_id = $1
+                 AND id IN (SELECT id FROM chat_messages WHERE chat_id = 'c1')",
+            )
+            .bind(&copy.id)
+            .fetch_one(&mut conn)
+            .await
+            .unwrap()
+            .get("n");
+            assert_eq!(shared, 0);
+
+            let active: String = sqlx::query("SELECT title FROM chat_chapters WHERE id = $1")
+                .bind(copy.active_chapter_id.as_deref())
+                .fetch_one(&mut conn)
+                .await
+                .unwrap()
+                .get("title");
+            assert_eq!(active, "Two");
+
+            let marker: String = sqlx::query(
+                "SELECT c.title FROM chat_markers k JOIN chat_chapters c ON c.id = k.chapter_id
+                 WHERE k.chat_id = $1",
+            )
+            .bind(&copy.id)
+            .fetch_one(&mut conn)
+            .await
+            .unwrap()
+            .get("title");
+            assert_eq!(marker, "One");
+
+            // Only the copy of the last message stays ahead of the synopsis
+            let pending: i64 = sqlx::query(
+                "SELECT COUNT(*) AS n FROM chat_messages WHERE chat_id = $1 AND rowid > $2",
+            )
+            .bind(&copy.id)
+            .bind(copy.synopsis_up_to_sequence)
+            .fetch_one(&mut conn)
+            .await
+            .unwrap()
+            .get("n");
+            assert_eq!(pending, 1);
+        });
+    }
+
+    #[test]
+    fn refuses_chats_of_other_profiles() {
+        tauri::async_runtime::block_on(async {
+            let mut conn = connect("ownership").await;
+
+            let error = copy_chat(&mut conn, "c1", "p2", "Mine now")
+                .await
+                .unwrap_err();
+            assert_eq!(error.code, ErrorCode::NotFound);
+            let error = copy_chat(&mut conn, "c1", "p1", "  ").await.unwrap_err();
+            assert_eq!(error.code, ErrorCode::Validation);
+
+            let chats: i64 = sqlx::query("SELECT COUNT(*) AS n FROM chats")
+                .fetch_one(&mut conn)
+                .await
+                .unwrap()
+                .get("n");
+            assert_eq!(chats, 1);
+        });
+    }
+}
@@ -0,0 +1,393 @@
+use serde::Serialize;
+use sqlx::sqlite::SqliteConnection;
+use sqlx::{Connection, Row};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, State, Window};
+use uuid::Uuid;
+
+use super::open_connection;
+use crate::error::AppError;
+use crate::windows::verify_window_profile;
+
+// Deleting a chat or a profile can be made a two-step operation (profile setting
+// system.requireDeleteConfirmation): the first call only describes what would go and hands out a
+// token, the second call has to present that token for the same entity. A stale id from the UI
+// then shows up as a summary the user didn't expect instead of losing data.
+
+const CONFIRMATION_TTL: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeleteTarget {
+    Chat { chat_id: String, profile_id: String },
+    Profile { profile_id: String },
+}
+
+struct PendingDelete {
+    target: DeleteTarget,
+    expires_at: Instant,
+}
+
+// Tokens handed out and not yet used
+#[derive(Default)]
+pub struct DeleteConfirmations(Mutex<HashMap<String, PendingDelete>>);
+
+impl DeleteConfirmations {
+    fn issue(&self, target: DeleteTarget, now: Instant) -> Result<String, String> {
+        let mut pending = self
+            .0
+            .lock()
+            .map_err(|e| format!("Failed to lock delete confirmations: {}", e))?;
+        pending.retain(|_, entry| entry.expires_at > now);
+        let token = Uuid::new_v4().simple().to_string();
+        pending.insert(
+            token.clone(),
+            PendingDelete {
+                target,
+                expires_at: now + CONFIRMATION_TTL,
+            },
+        );
+        Ok(token)
+    }
+
+    // A token is used up by the first attempt, whether it matches or not
+    fn redeem(&self, token: &str, target: &DeleteTarget, now: Instant) -> Result<(), AppError> {
+        let entry = self
+            .0
+            .lock()
+            .map_err(|e| format!("Failed to lock delete confirmations: {}", e))?
+            .remove(token);
+        match entry {
+            Some(entry) if entry.expires_at <= now => Err(AppError::conflict(
+                "The delete confirmation expired, confirm the deletion again",
+            )),
+            Some(entry) if entry.target == *target => Ok(()),
+            Some(_) => Err(AppError::validation(
+                "The delete confirmation was issued for something else",
+            )),
+            None => Err(AppError::validation(
+                "Unknown or already used delete confirmation",
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum DeleteOutcome {
+    Deleted,
+    ConfirmationRequired {
+        token: String,
+        // What the deletion removes, e.g. "chat 'Long Campaign', 4,213 messages"
+        summary: String,
+        expires_in_secs: u64,
+    },
+}
+
+fn with_thousands(count: i64) -> String {
+    let digits = count.abs().to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    if count < 0 {
+        grouped.insert(0, '-');
+    }
+    grouped
+}
+
+fn counted(count: i64, noun: &str) -> String {
+    format!(
+        "{} {}{}",
+        with_thousands(count),
+        noun,
+        if count == 1 { "" } else { "s" }
+    )
+}
+
+async fn requires_confirmation(
+    conn: &mut SqliteConnection,
+    profile_id: &str,
+) -> Result<bool, String> {
+    let row = sqlx::query(
+        "SELECT json_extract(settings, '$.system.requireDeleteConfirmation') AS required
+         FROM profiles WHERE id = $1 AND json_valid(settings)",
+    )
+    .bind(profile_id)
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| format!("Failed to read profile settings: {}", e))?;
+    Ok(row
+        .and_then(|row| row.get::<Option<i64>, _>("required"))
+        .is_some_and(|required| required != 0))
+}
+
+async fn summarize(conn: &mut SqliteConnection, target: &DeleteTarget) -> Result<String, AppError> {
+    match target {
+        DeleteTarget::Chat {
+            chat_id,
+            profile_id,
+        } => {
+            let row = sqlx::query(
+                "SELECT name, (SELECT COUNT(*) FROM chat_messages WHERE chat_id = chats.id) AS messages
+                 FROM chats WHERE id = $1 AND profile_id = $2",
+            )
+            .bind(chat_id)
+            .bind(profile_id)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to read chat {}: {}", chat_id, e))?
+            .ok_or_else(|| AppError::not_found(format!("Chat {} not found", chat_id)))?;
+            Ok(format!(
+                "chat '{}', {}",
+                row.get::<String, _>("name"),
+                counted(row.get("messages"), "message")
+            ))
+        }
+        DeleteTarget::Profile { profile_id } => {
+            let row = sqlx::query(
+                "SELECT name,
+                    (SELECT COUNT(*) FROM chats WHERE profile_id = profiles.id) AS chats,
+                    (SELECT COUNT(*) FROM characters WHERE profile_id = profiles.id) AS characters
+                 FROM profiles WHERE id = $1",
+            )
+            .bind(profile_id)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to read profile {}: {}", profile_id, e))?
+            .ok_or_else(|| AppError::not_found(format!("Profile {} not found", profile_id)))?;
+            Ok(format!(
+                "profile '{}', {}, {}",
+                row.get::<String, _>("name"),
+                counted(row.get("chats"), "chat"),
+                counted(row.get("characters"), "character")
+            ))
+        }
+    }
+}
+
+async fn remove(conn: &mut SqliteConnection, target: &DeleteTarget) -> Result<(), AppError> {
+    let result = match target {
+        DeleteTarget::Chat {
+            chat_id,
+            profile_id,
+        } => sqlx::query("DELETE FROM chats WHERE id = $1 AND profile_id = $2")
+            .bind(chat_id)
+            .bind(profile_id)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to delete chat {}: {}", chat_id, e))?,
+        DeleteTarget::Profile { profile_id } => sqlx::query("DELETE FROM profiles WHERE id = $1")
+            .bind(profile_id)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to delete profile {}: {}", profile_id, e))?,
+    };
+    if result.rows_affected() == 0 {
+        return Err(AppError::not_found("Nothing to delete, it is already gone"));
+    }
+    Ok(())
+}
+
+async fn delete_target(
+    conn: &mut SqliteConnection,
+    confirmations: &DeleteConfirmations,
+    target: DeleteTarget,
+    confirmation_token: Option<&str>,
+) -> Result<DeleteOutcome, AppError> {
+    let profile_id = match &target {
+        DeleteTarget::Chat { profile_id, .. } | DeleteTarget::Profile { profile_id } => profile_id,
+    };
+
+    match confirmation_token {
+        Some(token) => confirmations.redeem(token, &target, Instant::now())?,
+        None if requires_confirmation(conn, profile_id).await? => {
+            let summary = summarize(conn, &target).await?;
+            return Ok(DeleteOutcome::ConfirmationRequired {
+                token: confirmations.issue(target, Instant::now())?,
+                summary,
+                expires_in_secs: CONFIRMATION_TTL.as_secs(),
+            });
+        }
+        None => {}
+    }
+
+    remove(conn, &target).await?;
+    Ok(DeleteOutcome::Deleted)
+}
+
+/// Delete a chat of the profile with everything in it. With delete confirmation on for the profile,
+/// a call without `confirmation_token` deletes nothing and returns the token to send within 30 s.
+#[tauri::command]
+pub async fn delete_chat(
+    app: AppHandle,
+    window: Window,
+    confirmations: State<'_, DeleteConfirmations>,
+    chat_id: String,
+    profile_id: String,
+    confirmation_token: Option<String>,
+) -> Result<DeleteOutcome, AppError> {
+    verify_window_profile(&window, &profile_id)?;
+    let mut conn = open_connection(&app).await?;
+    let target = DeleteTarget::Chat {
+        chat_id,
+        profile_id,
+    };
+    let result = delete_target(
+        &mut conn,
+        &confirmations,
+        target,
+        confirmation_token.as_deref(),
+    )
+    .await;
+    let _ = conn.close().await;
+    result
+}
+
+/// Delete a profile and all its data, confirmed the same way as delete_chat
+#[tauri::command]
+pub async fn delete_profile(
+    app: AppHandle,
+    window: Window,
+    confirmations: State<'_, DeleteConfirmations>,
+    profile_id: String,
+    confirmation_token: Option<String>,
+) -> Result<DeleteOutcome, AppError> {
+    verify_window_profile(&window, &profile_id)?;
+    let mut conn = open_connection(&app).await?;
+    let target = DeleteTarget::Profile { profile_id };
+    let result = delete_target(
+        &mut conn,
+        &confirmations,
+        target,
+        confirmation_token.as_deref(),
+    )
+    .await;
+    let _ = conn.close().await;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_db::temp_database;
+    use crate::error::ErrorCode;
+
+    fn chat(chat_id: &str) -> DeleteTarget {
+        DeleteTarget::Chat {
+            chat_id: chat_id.to_string(),
+            profile_id: "p1".to_string(),
+        }
+    }
+
+    async fn connect(name: &str) -> SqliteConnection {
+        let mut conn = temp_database(&format!("deletion-{}", name)).await;
+        for sql in [
+            "INSERT INTO profiles (id, name, settings)
+             VALUES ('p1', 'Careful', '{\"system\":{\"requireDeleteConfirmation\":true}}'),
+                    ('p2', 'Quick', '{\"system\":{}}')",
+            "INSERT INTO chats (id, profile_id, name, participants)
+             VALUES ('c1', 'p1', 'Long Campaign', '[]'), ('c2', 'p2', 'Scratch', '[]')",
+            "INSERT INTO chat_chapters (id, chat_id, title, sequence) VALUES ('ch1', 'c1', 'One', 1)",
+            "INSERT INTO chat_messages (id, chat_id, chapter_id, type, position, messages, message_index)
+             VALUES ('m1', 'c1', 'ch1', 'user', 100, '[\"hi\"]', 0),
+                    ('m2', 'c1', 'ch1', 'user', 200, '[\"again\"]', 0)",
+        ] {
+            sqlx::query(sql).execute(&mut conn).await.unwrap();
+        }
+        conn
+    }
+
+    async fn chat_exists(conn: &mut SqliteConnection, chat_id: &str) -> bool {
+        sqlx::query("SELECT id FROM chats WHERE id = $1")
+            .bind(chat_id)
+            .fetch_optional(&mut *conn)
+            .await
+            .unwrap()
+            .is_some()
+    }
+
+    #[test]
+    fn groups_thousands() {
+        assert_eq!(with_thousands(0), "0");
+        assert_eq!(with_thousands(999), "999");
+        assert_eq!(with_thousands(4213), "4,213");
+        assert_eq!(with_thousands(1234567), "1,234,567");
+        assert_eq!(counted(1, "message"), "1 message");
+        assert_eq!(counted(4213, "message"), "4,213 messages");
+    }
+
+    #[test]
+    fn tokens_are_single_use_short_lived_and_scoped() {
+        let confirmations = DeleteConfirmations::default();
+        let now = Instant::now();
+
+        let token = confirmations.issue(chat("c1"), now).unwrap();
+        assert!(confirmations.redeem(&token, &chat("c1"), now).is_ok());
+        let reused = confirmations.redeem(&token, &chat("c1"), now).unwrap_err();
+        assert_eq!(reused.code, ErrorCode::Validation);
+
+        let token = confirmations.issue(chat("c1"), now).unwrap();
+        let other = confirmations.redeem(&token, &chat("c2"), now).unwrap_err();
+        assert_eq!(other.code, ErrorCode::Validation);
+        // Trying it on the wrong chat burned it
+        assert!(confirmations.redeem(&token, &chat("c1"), now).is_err());
+
+        let token = confirmations.issue(chat("c1"), now).unwrap();
+        let late = now + CONFIRMATION_TTL + Duration::from_secs(1);
+        let expired = confirmations.redeem(&token, &chat("c1"), late).unwrap_err();
+        assert_eq!(expired.code, ErrorCode::Conflict);
+    }
+
+    #[test]
+    fn deletes_after_confirmation_when_the_profile_asks_for_it() {
+        tauri::async_runtime::block_on(async {
+            let mut conn = connect("confirm").await;
+            let confirmations = DeleteConfirmations::default();
+
+            let outcome = delete_target(&mut conn, &confirmations, chat("c1"), None)
+                .await
+                .unwrap();
+            let DeleteOutcome::ConfirmationRequired { token, summary, .. } = outcome else {
+                panic!("expected a confirmation request, got {:?}", outcome);
+            };
+            assert_eq!(summary, "chat 'Long Campaign', 2 messages");
+            assert!(chat_exists(&mut conn, "c1").await);
+
+            let outcome = delete_target(&mut conn, &confirmations, chat("c1"), Some(&token))
+                .await
+                .unwrap();
+            assert_eq!(outcome, DeleteOutcome::Deleted);
+            assert!(!chat_exists(&mut conn, "c1").await);
+        });
+    }
+
+    #[test]
+    fn deletes_at_once_without_the_setting_and_checks_ownership() {
+        tauri::async_runtime::block_on(async {
+            let mut conn = connect("direct").await;
+            let confirmations = DeleteConfirmations::default();
+
+            // c2 belongs to p2, so asking as p1 finds nothing
+            let error = delete_target(&mut conn, &confirmations, chat("c2"), None)
+                .await
+                .unwrap_err();
+            assert_eq!(error.code, ErrorCode::NotFound);
+            assert!(chat_exists(&mut conn, "c2").await);
+
+            let target = DeleteTarget::Chat {
+                chat_id: "c2".to_string(),
+                profile_id: "p2".to_string(),
+            };
+            let outcome = delete_target(&mut conn, &confirmations, target, None)
+                .await
+                .unwrap();
+            assert_eq!(outcome, DeleteOutcome::Deleted);
+            assert!(!chat_exists(&mut conn, "c2").await);
+        });
+    }
+}
@@ -0,0 +1,348 @@
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use sqlx::sqlite::SqliteConnection;
+use sqlx::{Connection, Row};
+use tauri::{AppHandle, Window};
+
+use super::open_connection;
+use crate::error::AppError;
+use crate::windows::verify_window_profile;
+
+// Message metadata is the `extra` JSON of chat_messages. Annotating a chat (plot events, OOC flags)
+// touches many messages at once, so updates are batched into one call and one transaction.
+
+#[derive(Debug, Deserialize)]
+pub struct MetadataUpdate {
+    pub message_id: String,
+    // Merged into the metadata like a JSON merge patch: objects merge, null removes a key
+    pub metadata_patch: Value,
+}
+
+fn merge_patch(target: &mut Value, patch: &Value) {
+    let Value::Object(patch) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = Value::Object(Map::new());
+    }
+    if let Value::Object(target) = target {
+        for (key, value) in patch {
+            if value.is_null() {
+                target.remove(key);
+            } else {
+                merge_patch(target.entry(key.clone()).or_insert(Value::Null), value);
+            }
+        }
+    }
+}
+
+// Rows written before `extra` existed, or with unreadable JSON, start from an empty object
+fn parse_metadata(raw: Option<String>) -> Value {
+    raw.and_then(|raw| serde_json::from_str::<Value>(&raw).ok())
+        .filter(Value::is_object)
+        .unwrap_or_else(|| Value::Object(Map::new()))
+}
+
+// Only chats of the calling profile can be read or annotated
+async fn ensure_chat_in_profile(
+    conn: &mut SqliteConnection,
+    chat_id: &str,
+    profile_id: &str,
+) -> Result<(), AppError> {
+    sqlx::query("SELECT 1 FROM chats WHERE id = $1 AND profile_id = $2")
+        .bind(chat_id)
+        .bind(profile_id)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to read chat {}: {}", chat_id, e))?
+        .ok_or_else(|| AppError::not_found(format!("Chat {} not found", chat_id)))?;
+    Ok(())
+}
+
+async fn apply_updates(
+    conn: &mut SqliteConnection,
+    chat_id: &str,
+    profile_id: &str,
+    updates: &[MetadataUpdate],
+) -> Result<usize, AppError> {
+    if let Some(update) = updates.iter().find(|u| !u.metadata_patch.is_object()) {
+        return Err(AppError::validation(format!(
+            "Metadata patch for message {} must be a JSON object",
+            update.message_id
+        )));
+    }
+
+    let mut tx = conn
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start metadata transaction: {}", e))?;
+    ensure_chat_in_profile(&mut *tx, chat_id, profile_id).await?;
+
+    for update in updates {
+        let row = sqlx::query("SELECT extra FROM chat_messages WHERE id = $1 AND chat_id = $2")
+            .bind(&update.message_id)
+            .bind(chat_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to read message {}: {}", update.message_id, e))?
+            // Dropping the transaction rolls back the messages already merged
+            .ok_or_else(|| {
+                AppError::not_found(format!(
+                    "Message {} does not belong to chat {}",
+                    update.message_id, chat_id
+                ))
+                .with_details(json!({ "message_id": update.message_id, "chat_id": chat_id }))
+            })?;
+
+        let mut metadata = parse_metadata(row.get::<Option<String>, _>("extra"));
+        merge_patch(&mut metadata, &update.metadata_patch);
+
+        sqlx::query(
+            "UPDATE chat_messages SET extra = $1, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+             WHERE id = $2 AND chat_id = $3",
+        )
+        .bind(metadata.to_string())
+        .bind(&update.message_id)
+        .bind(chat_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to update message {}: {}", update.message_id, e))?;
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit metadata updates: {}", e))?;
+    Ok(updates.len())
+}
+
+async fn find_by_metadata(
+    conn: &mut SqliteConnection,
+    chat_id: &str,
+    profile_id: &str,
+    key: &str,
+    value: &Value,
+) -> Result<Vec<String>, AppError> {
+    ensure_chat_in_profile(conn, chat_id, profile_id).await?;
+
+    // Compared in Rust: json_extract turns true into 1 and objects into text, so SQL equality
+    // can't tell `true` from `1` or match nested values
+    let rows = sqlx::query(
+        "SELECT id, extra FROM chat_messages WHERE chat_id = $1 AND extra IS NOT NULL
+         ORDER BY position",
+    )
+    .bind(chat_id)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| format!("Failed to read messages of chat {}: {}", chat_id, e))?;
+
+    Ok(rows
+        .into_iter()
+        .filter(|row| parse_metadata(row.get::<Option<String>, _>("extra")).get(key) == Some(value))
+        .map(|row| row.get::<String, _>("id"))
+        .collect())
+}
+
+/// Deep-merge a metadata patch into each message of the chat, all in one transaction.
+/// Fails without changing anything when a message isn't part of the chat.
+#[tauri::command]
+pub async fn update_messages_metadata_bulk(
+    app: AppHandle,
+    window: Window,
+    chat_id: String,
+    profile_id: String,
+    updates: Vec<MetadataUpdate>,
+) -> Result<usize, AppError> {
+    verify_window_profile(&window, &profile_id)?;
+    if updates.is_empty() {
+        return Ok(0);
+    }
+    let mut conn = open_connection(&app).await?;
+    let result = apply_updates(&mut conn, &chat_id, &profile_id, &updates).await;
+    let _ = conn.close().await;
+    result
+}
+
+/// Ids of the chat's messages whose metadata has `key` set to `value`, in chat order
+#[tauri::command]
+pub async fn get_messages_by_metadata(
+    app: AppHandle,
+    window: Window,
+    chat_id: String,
+    profile_id: String,
+    key: String,
+    value: Value,
+) -> Result<Vec<String>, AppError> {
+    verify_window_profile(&window, &profile_id)?;
+    let mut conn = open_connection(&app).await?;
+    let result = find_by_metadata(&mut conn, &chat_id, &profile_id, &key, &value).await;
+    let _ = conn.close().await;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_db::temp_database;
+    use crate::error::ErrorCode;
+
+    async fn seed(conn: &mut SqliteConnection) {
+        for sql in [
+            "INSERT INTO profiles (id, name) VALUES ('p1', 'Profile'), ('p2', 'Other')",
+            "INSERT INTO chats (id, profile_id, name, participants) VALUES ('c1', 'p1', 'One', '[]')",
+            "INSERT INTO chats (id, profile_id, name, participants) VALUES ('c2', 'p1', 'Two', '[]')",
+            "INSERT INTO chat_chapters (id, chat_id, title, sequence) VALUES ('ch1', 'c1', 'One', 1)",
+            "INSERT INTO chat_chapters (id, chat_id, title, sequence) VALUES ('ch2', 'c2', 'One', 1)",
+            "INSERT INTO chat_messages (id, chat_id, chapter_id, type, position, messages, message_index, extra)
+             VALUES ('m1', 'c1', 'ch1', 'user', 100, '[\"hi\"]', 0, '{\"script\":\"summary\",\"tags\":{\"mood\":\"calm\"}}')",
+            "INSERT INTO chat_messages (id, chat_id, chapter_id, type, position, messages, message_index, extra)
+             VALUES ('m2', 'c1', 'ch1', 'user', 200, '[\"ooc\"]', 0, NULL)",
+            "INSERT INTO chat_messages (id, chat_id, chapter_id, type, position, messages, message_index)
+             VALUES ('m3', 'c2', 'ch2', 'user', 100, '[\"other\"]', 0)",
+        ] {
+            sqlx::query(sql).execute(&mut *conn).await.unwrap();
+        }
+    }
+
+    async fn metadata(conn: &mut SqliteConnection, id: &str) -> Value {
+        let row = sqlx::query("SELECT extra FROM chat_messages WHERE id = $1")
+            .bind(id)
+            .fetch_one(&mut *conn)
+            .await
+            .unwrap();
+        parse_metadata(row.get::<Option<String>, _>("extra"))
+    }
+
+    fn update(id: &str, patch: Value) -> MetadataUpdate {
+        MetadataUpdate {
+            message_id: id.to_string(),
+            metadata_patch: patch,
+        }
+    }
+
+    async fn connect(name: &str) -> SqliteConnection {
+        let mut conn = temp_database(&format!("message-metadata-{}", name)).await;
+        seed(&mut conn).await;
+        conn
+    }
+
+    #[test]
+    fn merges_patches_and_finds_tagged_messages() {
+        tauri::async_runtime::block_on(async {
+            let mut conn = connect("merge").await;
+
+            let updated = apply_updates(
+                &mut conn,
+                "c1",
+                "p1",
+                &[
+                    update(
+                        "m1",
+                        json!({ "key_event": true, "tags": { "place": "inn" }, "script": null }),
+                    ),
+                    update("m2", json!({ "exclude_from_context": true })),
+                ],
+            )
+            .await
+            .unwrap();
+            assert_eq!(updated, 2);
+
+            assert_eq!(
+                metadata(&mut conn, "m1").await,
+                json!({ "key_event": true, "tags": { "mood": "calm", "place": "inn" } })
+            );
+            assert_eq!(
+                metadata(&mut conn, "m2").await,
+                json!({ "exclude_from_context": true })
+            );
+
+            assert_eq!(
+                find_by_metadata(&mut conn, "c1", "p1", "exclude_from_context", &json!(true))
+                    .await
+                    .unwrap(),
+                vec!["m2".to_string()]
+            );
+            assert!(
+                find_by_metadata(&mut conn, "c1", "p1", "key_event", &json!(1))
+                    .await
+                    .unwrap()
+                    .is_empty()
+            );
+        });
+    }
+
+    #[test]
+    fn rolls_back_when_a_message_is_outside_the_chat() {
+        tauri::async_runtime::block_on(async {
+            let mut conn = connect("rollback").await;
+
+            let error = apply_updates(
+                &mut conn,
+                "c1",
+                "p1",
+                &[
+                    update("m1", json!({ "key_event": true })),
+                    update("m3", json!({ "key_event": true })),
+                ],
+            )
+            .await
+            .unwrap_err();
+            assert_eq!(error.code, ErrorCode::NotFound);
+            assert!(error.message.contains("m3"));
+            assert_eq!(metadata(&mut conn, "m1").await["key_event"], Value::Null);
+
+            assert!(
+                apply_updates(&mut conn, "c1", "p1", &[update("m1", json!(true))])
+                    .await
+                    .is_err()
+            );
+        });
+    }
+
+    #[test]
+    fn refuses_chats_of_another_profile() {
+        tauri::async_runtime::block_on(async {
+            let mut conn = connect("profile").await;
+
+            let error = apply_updates(
+                &mut conn,
+                "c1",
+                "p2",
+                &[update("m1", json!({ "key_event": true }))],
+            )
+            .await
+            .unwrap_err();
+            assert_eq!(error.code, ErrorCode::NotFound);
+            assert_eq!(metadata(&mut conn, "m1").await["key_event"], Value::Null);
+
+            let error = find_by_metadata(&mut conn, "c1", "p2", "script", &json!("summary"))
+                .await
+                .unwrap_err();
+            assert_eq!(error.code, ErrorCode::NotFound);
+        });
+    }
+
+    #[test]
+    fn stamps_updates_in_utc_iso_format() {
+        tauri::async_runtime::block_on(async {
+            let mut conn = connect("timestamp").await;
+            apply_updates(
+                &mut conn,
+                "c1",
+                "p1",
+                &[update("m2", json!({ "ooc": true }))],
+            )
+            .await
+            .unwrap();
+
+            let updated_at: String =
+                sqlx::query("SELECT updated_at FROM chat_messages WHERE id = 'm2'")
+                    .fetch_one(&mut conn)
+                    .await
+                    .unwrap()
+                    .get("updated_at");
+            assert_eq!(updated_at.len(), "2026-01-01T00:00:00.000Z".len());
+            assert!(updated_at.contains('T') && updated_at.ends_with('Z'));
+        });
+    }
+}
@@ -0,0 +1,7 @@
+-- Migration: Add a stable alias to models
+-- Aliases let references survive a model being renamed or re-created
+
+ALTER TABLE models ADD COLUMN alias TEXT DEFAULT NULL;
+
+-- Aliases are unique within a profile
+CREATE UNIQUE INDEX idx_models_profile_alias ON models(profile_id, alias) WHERE alias IS NOT NULL;
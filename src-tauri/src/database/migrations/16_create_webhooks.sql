@@ -0,0 +1,32 @@
+CREATE TABLE IF NOT EXISTS webhooks (
+    id TEXT PRIMARY KEY,
+    profile_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    url TEXT NOT NULL,
+    secret TEXT, -- Encrypted with the same key as model API keys
+    events TEXT NOT NULL DEFAULT '[]', -- JSON array of subscribed event names
+    enabled BOOLEAN NOT NULL DEFAULT FALSE,
+    allow_insecure BOOLEAN NOT NULL DEFAULT FALSE, -- Allow plain HTTP to non-localhost hosts
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (profile_id) REFERENCES profiles(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_webhooks_profile_id ON webhooks(profile_id);
+
+CREATE TABLE IF NOT EXISTS webhook_deliveries (
+    id TEXT PRIMARY KEY,
+    webhook_id TEXT NOT NULL,
+    profile_id TEXT NOT NULL,
+    event TEXT NOT NULL,
+    delivered BOOLEAN NOT NULL DEFAULT FALSE,
+    status_code INTEGER,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    error TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (webhook_id) REFERENCES webhooks(id) ON DELETE CASCADE,
+    FOREIGN KEY (profile_id) REFERENCES profiles(id) ON DELETE CASCADE
+);
+
+-- Delivery log is read newest first per webhook
+CREATE INDEX idx_webhook_deliveries_webhook ON webhook_deliveries(webhook_id, created_at);
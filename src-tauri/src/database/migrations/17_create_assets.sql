@@ -0,0 +1,80 @@
+-- Content-addressed asset store. Files live at objects/{first two hex chars}/{sha256}.{ext}
+-- and entity columns store that relative path. Reference counts are maintained by the
+-- triggers below, so they commit or roll back together with the owning row.
+CREATE TABLE IF NOT EXISTS assets (
+    hash TEXT PRIMARY KEY,
+    original_name TEXT,
+    size INTEGER NOT NULL DEFAULT 0,
+    ref_count INTEGER NOT NULL DEFAULT 0,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_assets_ref_count ON assets(ref_count);
+
+-- The hash is the 64 characters after "objects/xx/"
+CREATE TRIGGER IF NOT EXISTS profiles_assets_insert AFTER INSERT ON profiles
+WHEN NEW.avatar_path LIKE 'objects/%'
+BEGIN
+    UPDATE assets SET ref_count = ref_count + 1 WHERE hash = substr(NEW.avatar_path, 12, 64);
+END;
+
+CREATE TRIGGER IF NOT EXISTS profiles_assets_delete AFTER DELETE ON profiles
+WHEN OLD.avatar_path LIKE 'objects/%'
+BEGIN
+    UPDATE assets SET ref_count = ref_count - 1 WHERE hash = substr(OLD.avatar_path, 12, 64);
+END;
+
+CREATE TRIGGER IF NOT EXISTS profiles_assets_update AFTER UPDATE OF avatar_path ON profiles
+WHEN OLD.avatar_path IS NOT NEW.avatar_path
+BEGIN
+    UPDATE assets SET ref_count = ref_count - 1
+    WHERE OLD.avatar_path LIKE 'objects/%' AND hash = substr(OLD.avatar_path, 12, 64);
+    UPDATE assets SET ref_count = ref_count + 1
+    WHERE NEW.avatar_path LIKE 'objects/%' AND hash = substr(NEW.avatar_path, 12, 64);
+END;
+
+-- Characters reference assets from avatar_path and from each expression's image_path.
+-- A row counts once per distinct asset it references.
+CREATE TRIGGER IF NOT EXISTS characters_assets_insert AFTER INSERT ON characters
+BEGIN
+    UPDATE assets SET ref_count = ref_count + 1
+    WHERE hash IN (
+        SELECT substr(NEW.avatar_path, 12, 64) WHERE NEW.avatar_path LIKE 'objects/%'
+        UNION
+        SELECT substr(json_extract(value, '$.image_path'), 12, 64)
+        FROM json_each(CASE WHEN json_valid(NEW.expressions) THEN NEW.expressions ELSE '[]' END)
+        WHERE json_extract(value, '$.image_path') LIKE 'objects/%'
+    );
+END;
+
+CREATE TRIGGER IF NOT EXISTS characters_assets_delete AFTER DELETE ON characters
+BEGIN
+    UPDATE assets SET ref_count = ref_count - 1
+    WHERE hash IN (
+        SELECT substr(OLD.avatar_path, 12, 64) WHERE OLD.avatar_path LIKE 'objects/%'
+        UNION
+        SELECT substr(json_extract(value, '$.image_path'), 12, 64)
+        FROM json_each(CASE WHEN json_valid(OLD.expressions) THEN OLD.expressions ELSE '[]' END)
+        WHERE json_extract(value, '$.image_path') LIKE 'objects/%'
+    );
+END;
+
+CREATE TRIGGER IF NOT EXISTS characters_assets_update AFTER UPDATE OF avatar_path, expressions ON characters
+BEGIN
+    UPDATE assets SET ref_count = ref_count - 1
+    WHERE hash IN (
+        SELECT substr(OLD.avatar_path, 12, 64) WHERE OLD.avatar_path LIKE 'objects/%'
+        UNION
+        SELECT substr(json_extract(value, '$.image_path'), 12, 64)
+        FROM json_each(CASE WHEN json_valid(OLD.expressions) THEN OLD.expressions ELSE '[]' END)
+        WHERE json_extract(value, '$.image_path') LIKE 'objects/%'
+    );
+    UPDATE assets SET ref_count = ref_count + 1
+    WHERE hash IN (
+        SELECT substr(NEW.avatar_path, 12, 64) WHERE NEW.avatar_path LIKE 'objects/%'
+        UNION
+        SELECT substr(json_extract(value, '$.image_path'), 12, 64)
+        FROM json_each(CASE WHEN json_valid(NEW.expressions) THEN NEW.expressions ELSE '[]' END)
+        WHERE json_extract(value, '$.image_path') LIKE 'objects/%'
+    );
+END;
@@ -0,0 +1,2 @@
+-- Pinned messages are always kept when the context limit trims the chat history
+ALTER TABLE chat_messages ADD COLUMN pinned BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,14 @@
+-- Where each profile left off in a chat. The chapter sequence and message position are kept
+-- so the marker can fall back to the nearest earlier message when the read message is deleted
+CREATE TABLE IF NOT EXISTS chat_read_state (
+    chat_id TEXT NOT NULL,
+    profile_id TEXT NOT NULL,
+    last_read_message_id TEXT NOT NULL,
+    last_read_chapter_id TEXT NOT NULL,
+    last_read_chapter_sequence INTEGER NOT NULL,
+    last_read_position INTEGER NOT NULL,
+    last_read_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (chat_id, profile_id),
+    FOREIGN KEY (chat_id) REFERENCES chats(id) ON DELETE CASCADE,
+    FOREIGN KEY (profile_id) REFERENCES profiles(id) ON DELETE CASCADE
+);
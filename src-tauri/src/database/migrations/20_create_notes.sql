@@ -0,0 +1,17 @@
+-- Free-form notes and prompt snippets. Titles are unique per profile so {{note:title}} is unambiguous.
+CREATE TABLE IF NOT EXISTS notes (
+    id TEXT PRIMARY KEY,
+    profile_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    content TEXT NOT NULL DEFAULT '', -- Encrypted with the same key as model API keys when encrypted is set
+    tags TEXT NOT NULL DEFAULT '[]', -- JSON array of strings
+    pinned BOOLEAN NOT NULL DEFAULT FALSE,
+    encrypted BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (profile_id, title),
+    FOREIGN KEY (profile_id) REFERENCES profiles(id) ON DELETE CASCADE
+);
+
+-- Listed pinned first, then most recently edited
+CREATE INDEX idx_notes_profile_updated ON notes(profile_id, pinned, updated_at);
@@ -0,0 +1,5 @@
+-- Migration: Add capability metadata to models
+-- JSON with context_length, supports_vision, supports_tools, supports_reasoning and modality.
+-- NULL until refreshed from the provider or the manifest defaults; unknown flags are stored as null.
+
+ALTER TABLE models ADD COLUMN capabilities TEXT DEFAULT NULL;
@@ -0,0 +1,19 @@
+-- Scene/chapter markers shown between chat messages. They are never sent to the model.
+-- A marker sits after the message at `position` in its chapter (0 = before the first message).
+-- Markers flagged context_reset hide everything before them from the prompt.
+CREATE TABLE IF NOT EXISTS chat_markers (
+    id TEXT PRIMARY KEY,
+    chat_id TEXT NOT NULL,
+    chapter_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    position INTEGER NOT NULL DEFAULT 0,
+    color TEXT DEFAULT NULL,
+    context_reset INTEGER NOT NULL DEFAULT 0,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (chat_id) REFERENCES chats(id) ON DELETE CASCADE,
+    FOREIGN KEY (chapter_id) REFERENCES chat_chapters(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_chat_markers_chapter_position ON chat_markers(chapter_id, position);
+CREATE INDEX IF NOT EXISTS idx_chat_markers_chat_id ON chat_markers(chat_id);
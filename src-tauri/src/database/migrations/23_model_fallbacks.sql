@@ -0,0 +1,5 @@
+-- Migration: Add a fallback chain to models
+-- JSON array of model ids tried in order when this model's provider is unreachable or keeps failing.
+-- Ids of deleted models are skipped when the chain is resolved.
+
+ALTER TABLE models ADD COLUMN fallback_model_ids TEXT NOT NULL DEFAULT '[]';
@@ -0,0 +1,12 @@
+-- Migration: Indexes for the global search
+-- Name lookups are case-insensitive and always scoped to a profile, so these let a search
+-- read the names of one profile straight from the index instead of scanning the table.
+-- notes already has UNIQUE (profile_id, title).
+
+CREATE INDEX IF NOT EXISTS idx_chats_profile_name ON chats(profile_id, name COLLATE NOCASE);
+CREATE INDEX IF NOT EXISTS idx_characters_profile_name ON characters(profile_id, name COLLATE NOCASE);
+CREATE INDEX IF NOT EXISTS idx_chat_template_profile_name ON chat_template(profile_id, name COLLATE NOCASE);
+CREATE INDEX IF NOT EXISTS idx_format_template_profile_name ON format_template(profile_id, name COLLATE NOCASE);
+CREATE INDEX IF NOT EXISTS idx_inference_template_profile_name ON inference_template(profile_id, name COLLATE NOCASE);
+CREATE INDEX IF NOT EXISTS idx_lorebooks_profile_name ON lorebooks(profile_id, name COLLATE NOCASE);
+CREATE INDEX IF NOT EXISTS idx_models_profile_name ON models(profile_id, name COLLATE NOCASE);
@@ -0,0 +1,18 @@
+-- Migration: Keep the creator metadata of character cards
+-- V2/V3 cards carry who made them, their own version string and notes from the creator.
+-- character_version is free text from the card, unlike the internal semver `version`.
+
+ALTER TABLE characters ADD COLUMN creator TEXT DEFAULT NULL;
+ALTER TABLE characters ADD COLUMN character_version TEXT DEFAULT NULL;
+ALTER TABLE characters ADD COLUMN creator_notes TEXT DEFAULT NULL;
+
+-- Earlier imports kept the creator as settings.author
+UPDATE characters
+SET creator = json_extract(settings, '$.author')
+WHERE CASE
+    WHEN json_valid(settings) THEN json_type(settings, '$.author') = 'text' AND json_extract(settings, '$.author') NOT IN ('', 'Unknown')
+    ELSE 0
+  END;
+
+-- Grouping the gallery by creator
+CREATE INDEX IF NOT EXISTS idx_characters_profile_creator ON characters(profile_id, creator);
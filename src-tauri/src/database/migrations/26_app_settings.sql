@@ -0,0 +1,9 @@
+-- Migration: App-wide settings
+-- Key-value store for settings that belong to the app rather than a profile (window preferences,
+-- last used profile...). Values are JSON. Deliberately not tied to profiles, so it is readable before login.
+
+CREATE TABLE IF NOT EXISTS app_settings (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL, -- JSON value
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,16 @@
+-- Migration: Monthly API usage per profile
+-- Month-to-date tokens and cost of the requests sent to paid providers, checked against the
+-- profile's budget. One row per profile and calendar month (UTC, 'YYYY-MM'); cost is only counted
+-- for models with prices set in their config.
+
+CREATE TABLE IF NOT EXISTS profile_usage (
+    profile_id TEXT NOT NULL,
+    month TEXT NOT NULL,
+    input_tokens INTEGER NOT NULL DEFAULT 0,
+    output_tokens INTEGER NOT NULL DEFAULT 0,
+    cost REAL NOT NULL DEFAULT 0,
+    request_count INTEGER NOT NULL DEFAULT 0,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (profile_id, month),
+    FOREIGN KEY (profile_id) REFERENCES profiles(id) ON DELETE CASCADE
+);
@@ -0,0 +1,8 @@
+-- Migration: Rolling synopsis per chat
+-- One always-current summary of the whole chat, refreshed by folding in the messages written since
+-- the last refresh. synopsis_up_to_sequence is the rowid of the last chat_messages row folded in:
+-- rowids grow as messages are added, across chapters, so anything above it is new.
+
+ALTER TABLE chats ADD COLUMN synopsis TEXT DEFAULT NULL;
+ALTER TABLE chats ADD COLUMN synopsis_up_to_sequence INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE chats ADD COLUMN synopsis_updated_at DATETIME DEFAULT NULL;
@@ -0,0 +1,8 @@
+-- Migration: Indexes for the activity timeline
+-- The timeline counts a profile's rows by creation date. Messages are reached through the profile's
+-- chats, so (chat_id, created_at) lets each chat read only its messages inside the date range;
+-- an index on created_at alone would walk every profile's messages of the period.
+
+CREATE INDEX IF NOT EXISTS idx_messages_chat_created ON chat_messages(chat_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_chats_profile_created ON chats(profile_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_characters_profile_created ON characters(profile_id, created_at);
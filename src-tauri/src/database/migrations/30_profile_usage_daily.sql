@@ -0,0 +1,15 @@
+-- Migration: Daily API usage per profile
+-- Same totals as profile_usage, bucketed by calendar day (UTC, 'YYYY-MM-DD') for the daily budget
+-- limits. Rows of past days are kept, like past months.
+
+CREATE TABLE IF NOT EXISTS profile_usage_daily (
+    profile_id TEXT NOT NULL,
+    day TEXT NOT NULL,
+    input_tokens INTEGER NOT NULL DEFAULT 0,
+    output_tokens INTEGER NOT NULL DEFAULT 0,
+    cost REAL NOT NULL DEFAULT 0,
+    request_count INTEGER NOT NULL DEFAULT 0,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (profile_id, day),
+    FOREIGN KEY (profile_id) REFERENCES profiles(id) ON DELETE CASCADE
+);
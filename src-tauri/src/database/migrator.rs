@@ -0,0 +1,386 @@
+use serde::Serialize;
+use sqlx::migrate::{Migrate, Migration as SqlxMigration, MigrationType};
+use sqlx::sqlite::{SqliteConnectOptions, SqliteConnection};
+use sqlx::{ConnectOptions, Connection};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_sql::{Migration, MigrationKind};
+
+use crate::error::AppError;
+
+pub const DB_FILE_NAME: &str = "narratrix_main.db";
+
+// Emitted once per pending migration, and once more when all of them are applied
+#[derive(Debug, Serialize, Clone)]
+pub struct MigrationProgress {
+    pub index: usize,
+    pub total: usize,
+    pub version: i64,
+    pub description: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct MigrationFailure {
+    pub version: Option<i64>,
+    pub description: Option<String>,
+    pub error: String,
+    pub backup_path: Option<String>,
+}
+
+// Startup state polled by the frontend, so it doesn't matter if the webview missed the events
+#[derive(Debug, Serialize, Clone)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum MigrationStatus {
+    Running { progress: Option<MigrationProgress> },
+    Ready,
+    Failed { failure: MigrationFailure },
+}
+
+pub struct MigrationState(Mutex<MigrationStatus>);
+
+impl Default for MigrationState {
+    fn default() -> Self {
+        Self(Mutex::new(MigrationStatus::Running { progress: None }))
+    }
+}
+
+impl MigrationState {
+    fn set(&self, status: MigrationStatus) {
+        if let Ok(mut current) = self.0.lock() {
+            *current = status;
+        }
+    }
+
+    fn get(&self) -> MigrationStatus {
+        self.0
+            .lock()
+            .map(|status| status.clone())
+            .unwrap_or(MigrationStatus::Running { progress: None })
+    }
+}
+
+pub fn database_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("Failed to resolve app config dir: {}", e))?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create app config dir: {}", e))?;
+    Ok(dir.join(DB_FILE_NAME))
+}
+
+// Run pending migrations in the background and report through `migration-progress`,
+// `migration-completed` and `migration-failed`. Failures are kept in state instead of
+// aborting startup, so the recovery screen can still talk to the backend.
+pub fn spawn_startup_migrations(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<MigrationState>();
+
+        let result = match database_path(&app) {
+            Ok(db_path) => {
+                let emitter = app.clone();
+                run_migrations(&db_path, super::get_migrations(), move |progress| {
+                    emitter
+                        .state::<MigrationState>()
+                        .set(MigrationStatus::Running {
+                            progress: Some(progress.clone()),
+                        });
+                    let _ = emitter.emit("migration-progress", progress);
+                })
+                .await
+            }
+            Err(error) => Err(MigrationFailure {
+                version: None,
+                description: None,
+                error,
+                backup_path: None,
+            }),
+        };
+
+        match result {
+            Ok(()) => {
+                state.set(MigrationStatus::Ready);
+                let _ = app.emit("migration-completed", ());
+            }
+            // The failure reaches the frontend through the state and the event
+            Err(failure) => {
+                state.set(MigrationStatus::Failed {
+                    failure: failure.clone(),
+                });
+                let _ = app.emit("migration-failed", failure);
+            }
+        }
+    });
+}
+
+// Apply every pending migration one at a time. A copy of the database is taken before the
+// first pending step and left on disk if anything fails.
+pub async fn run_migrations(
+    db_path: &Path,
+    migrations: Vec<Migration>,
+    mut on_progress: impl FnMut(&MigrationProgress),
+) -> Result<(), MigrationFailure> {
+    let failure = |version: Option<i64>,
+                   description: Option<String>,
+                   error: String,
+                   backup_path: Option<&PathBuf>| MigrationFailure {
+        version,
+        description,
+        error,
+        backup_path: backup_path.map(|path| path.to_string_lossy().to_string()),
+    };
+
+    let migrations: Vec<SqlxMigration> = migrations
+        .into_iter()
+        .filter(|migration| matches!(migration.kind, MigrationKind::Up))
+        .map(|migration| {
+            // Same shape the sql plugin builds, so checksums match its bookkeeping table
+            SqlxMigration::new(
+                migration.version,
+                migration.description.into(),
+                MigrationType::ReversibleUp,
+                migration.sql.into(),
+                false,
+            )
+        })
+        .collect();
+
+    let existed = db_path.exists();
+    let mut conn = connect(db_path)
+        .await
+        .map_err(|e| failure(None, None, e, None))?;
+
+    let pending = pending_migrations(&mut conn, &migrations)
+        .await
+        .map_err(|e| failure(None, None, e, None))?;
+    if pending.is_empty() {
+        let _ = conn.close().await;
+        return Ok(());
+    }
+
+    // Close before copying so the WAL is checkpointed into the main file
+    let _ = conn.close().await;
+    let backup_path = if existed {
+        let first_pending = pending.first().map(|m| m.version).unwrap_or(0);
+        Some(backup_database(db_path, first_pending).map_err(|e| failure(None, None, e, None))?)
+    } else {
+        None
+    };
+
+    let mut conn = connect(db_path)
+        .await
+        .map_err(|e| failure(None, None, e, backup_path.as_ref()))?;
+
+    let total = pending.len();
+    for (index, migration) in pending.into_iter().enumerate() {
+        on_progress(&MigrationProgress {
+            index,
+            total,
+            version: migration.version,
+            description: migration.description.to_string(),
+        });
+
+        if let Err(e) = conn.apply(migration).await {
+            let _ = conn.close().await;
+            return Err(failure(
+                Some(migration.version),
+                Some(migration.description.to_string()),
+                e.to_string(),
+                backup_path.as_ref(),
+            ));
+        }
+    }
+
+    on_progress(&MigrationProgress {
+        index: total,
+        total,
+        version: migrations.last().map(|m| m.version).unwrap_or(0),
+        description: "done".to_string(),
+    });
+
+    let _ = conn.close().await;
+    Ok(())
+}
+
+async fn connect(db_path: &Path) -> Result<SqliteConnection, String> {
+    SqliteConnectOptions::new()
+        .filename(db_path)
+        .create_if_missing(true)
+        .connect()
+        .await
+        .map_err(|e| format!("Failed to open database: {}", e))
+}
+
+async fn pending_migrations<'m>(
+    conn: &mut SqliteConnection,
+    migrations: &'m [SqlxMigration],
+) -> Result<Vec<&'m SqlxMigration>, String> {
+    conn.ensure_migrations_table()
+        .await
+        .map_err(|e| format!("Failed to prepare migrations table: {}", e))?;
+
+    if let Some(version) = conn
+        .dirty_version()
+        .await
+        .map_err(|e| format!("Failed to read migration state: {}", e))?
+    {
+        return Err(format!(
+            "Migration {} was left partially applied by a previous run",
+            version
+        ));
+    }
+
+    let applied: HashMap<i64, Vec<u8>> = conn
+        .list_applied_migrations()
+        .await
+        .map_err(|e| format!("Failed to list applied migrations: {}", e))?
+        .into_iter()
+        .map(|applied| (applied.version, applied.checksum.to_vec()))
+        .collect();
+
+    let mut pending = Vec::new();
+    for migration in migrations {
+        match applied.get(&migration.version) {
+            Some(checksum) if checksum.as_slice() != &*migration.checksum => {
+                return Err(format!(
+                    "Migration {} was modified after it was applied",
+                    migration.version
+                ));
+            }
+            Some(_) => {}
+            None => pending.push(migration),
+        }
+    }
+    Ok(pending)
+}
+
+// Named after the first migration that was about to run, e.g. narratrix_main.db.pre-v16.bak
+fn backup_path_for(db_path: &Path, first_pending: i64) -> PathBuf {
+    db_path.with_file_name(format!("{}.pre-v{}.bak", DB_FILE_NAME, first_pending))
+}
+
+fn backup_database(db_path: &Path, first_pending: i64) -> Result<PathBuf, String> {
+    let backup_path = backup_path_for(db_path, first_pending);
+    std::fs::copy(db_path, &backup_path)
+        .map_err(|e| format!("Failed to back up database before migrating: {}", e))?;
+    Ok(backup_path)
+}
+
+// Current startup state, for a webview that loaded after the events were emitted
+#[tauri::command]
+pub fn get_migration_status(state: State<'_, MigrationState>) -> MigrationStatus {
+    state.get()
+}
+
+// Put the pre-migration backup back in place. Only allowed from the recovery state,
+// while nothing else holds the database open.
+#[tauri::command]
+pub fn restore_migration_backup(
+    app: AppHandle,
+    state: State<'_, MigrationState>,
+) -> Result<(), AppError> {
+    let backup_path = match state.get() {
+        MigrationStatus::Failed { failure } => failure.backup_path.ok_or_else(|| {
+            AppError::not_found("No backup was taken before the failed migration")
+        })?,
+        _ => {
+            return Err(AppError::conflict(
+                "Backups can only be restored after a failed migration",
+            ))
+        }
+    };
+
+    let db_path = database_path(&app)?;
+    std::fs::copy(&backup_path, &db_path)
+        .map_err(|e| format!("Failed to restore database backup: {}", e))?;
+
+    // Stale journal files would otherwise be replayed on top of the restored copy
+    for suffix in ["-wal", "-shm"] {
+        let journal = db_path.with_file_name(format!("{}{}", DB_FILE_NAME, suffix));
+        if journal.exists() {
+            std::fs::remove_file(&journal)
+                .map_err(|e| format!("Failed to remove {}: {}", journal.display(), e))?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test-only list: the second step references a table that doesn't exist
+    fn failing_migrations() -> Vec<Migration> {
+        vec![
+            Migration {
+                version: 1,
+                description: "create_items",
+                sql: "CREATE TABLE items (id TEXT PRIMARY KEY, name TEXT NOT NULL);",
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 2,
+                description: "broken_step",
+                sql: "ALTER TABLE missing_table ADD COLUMN broken TEXT;",
+                kind: MigrationKind::Up,
+            },
+        ]
+    }
+
+    fn temp_db(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("narratrix-migrator-{}", name));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir.join(DB_FILE_NAME)
+    }
+
+    #[test]
+    fn failed_migration_reports_step_and_keeps_backup() {
+        let db_path = temp_db("failure");
+
+        tauri::async_runtime::block_on(async {
+            // First run applies only the good step, so the second run has something to back up
+            let mut first = failing_migrations();
+            first.truncate(1);
+            run_migrations(&db_path, first, |_| {}).await.unwrap();
+
+            let mut steps = Vec::new();
+            let failure = run_migrations(&db_path, failing_migrations(), |progress| {
+                steps.push(progress.version)
+            })
+            .await
+            .unwrap_err();
+
+            assert_eq!(steps, vec![2]);
+            assert_eq!(failure.version, Some(2));
+            assert_eq!(failure.description.as_deref(), Some("broken_step"));
+
+            let backup_path = PathBuf::from(failure.backup_path.unwrap());
+            assert_eq!(backup_path, backup_path_for(&db_path, 2));
+            assert!(backup_path.exists());
+        });
+    }
+
+    #[test]
+    fn up_to_date_database_is_not_backed_up() {
+        let db_path = temp_db("up-to-date");
+
+        tauri::async_runtime::block_on(async {
+            let mut good = failing_migrations();
+            good.truncate(1);
+            run_migrations(&db_path, good, |_| {}).await.unwrap();
+
+            let mut good = failing_migrations();
+            good.truncate(1);
+            let mut calls = 0;
+            run_migrations(&db_path, good, |_| calls += 1)
+                .await
+                .unwrap();
+
+            assert_eq!(calls, 0);
+            assert!(!backup_path_for(&db_path, 1).exists());
+        });
+    }
+}
@@ -1,5 +1,29 @@
+use sqlx::sqlite::{SqliteConnectOptions, SqliteConnection};
+use sqlx::ConnectOptions;
+use tauri::AppHandle;
 use tauri_plugin_sql::{Migration, MigrationKind};
 
+pub mod activity;
+pub mod app_settings;
+pub mod chat_copy;
+pub mod deletion;
+pub mod message_metadata;
+pub mod migrator;
+pub mod model_rebind;
+pub mod repair;
+#[cfg(test)]
+pub mod test_db;
+
+// Own connection to the app database for commands that query it from Rust, next to the
+// frontend's plugin pool. Callers close it when done.
+pub async fn open_connection(app: &AppHandle) -> Result<SqliteConnection, String> {
+    SqliteConnectOptions::new()
+        .filename(migrator::database_path(app)?)
+        .connect()
+        .await
+        .map_err(|e| format!("Failed to open database: {}", e))
+}
+
 pub fn get_migrations() -> Vec<Migration> {
     vec![
         Migration {
@@ -87,5 +111,101 @@ pub fn get_migrations() -> Vec<Migration> {
             sql: include_str!("./migrations/14_lorebook_rag.sql"),
             kind: MigrationKind::Up,
         },
+        Migration {
+            version: 15,
+            description: "model_alias",
+            sql: include_str!("./migrations/15_model_alias.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 16,
+            description: "create_webhooks",
+            sql: include_str!("./migrations/16_create_webhooks.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 17,
+            description: "create_assets",
+            sql: include_str!("./migrations/17_create_assets.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 18,
+            description: "message_pinned",
+            sql: include_str!("./migrations/18_message_pinned.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 19,
+            description: "chat_read_state",
+            sql: include_str!("./migrations/19_chat_read_state.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 20,
+            description: "create_notes",
+            sql: include_str!("./migrations/20_create_notes.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 21,
+            description: "model_capabilities",
+            sql: include_str!("./migrations/21_model_capabilities.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 22,
+            description: "create_chat_markers",
+            sql: include_str!("./migrations/22_create_chat_markers.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 23,
+            description: "model_fallbacks",
+            sql: include_str!("./migrations/23_model_fallbacks.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 24,
+            description: "search_indexes",
+            sql: include_str!("./migrations/24_search_indexes.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 25,
+            description: "character_card_metadata",
+            sql: include_str!("./migrations/25_character_card_metadata.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 26,
+            description: "app_settings",
+            sql: include_str!("./migrations/26_app_settings.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 27,
+            description: "profile_usage",
+            sql: include_str!("./migrations/27_profile_usage.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 28,
+            description: "chat_synopsis",
+            sql: include_str!("./migrations/28_chat_synopsis.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 29,
+            description: "activity_indexes",
+            sql: include_str!("./migrations/29_activity_indexes.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 30,
+            description: "profile_usage_daily",
+            sql: include_str!("./migrations/30_profile_usage_daily.sql"),
+            kind: MigrationKind::Up,
+        },
     ]
 }
@@ -0,0 +1,326 @@
+use serde::Serialize;
+use sqlx::sqlite::SqliteConnection;
+use sqlx::{Connection, Row};
+use tauri::{AppHandle, Window};
+
+use super::open_connection;
+use crate::error::AppError;
+use crate::windows::verify_window_profile;
+
+// Rebinding points everything that references a model (chat templates, the translation and
+// synopsis models of chat settings, lorebook embeddings, other models' fallback chains) at another model of the same profile, e.g. after re-creating a
+// model with a different provider. The alias moves along when the target model has none.
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct ModelRebind {
+    pub chat_templates: u64,
+    // Translation and synopsis models in `chats.settings`, one per rebound setting
+    pub chat_settings: u64,
+    pub lorebooks: u64,
+    pub fallback_chains: u64,
+    pub alias_moved: bool,
+}
+
+async fn rebind(
+    conn: &mut SqliteConnection,
+    old_id: &str,
+    new_id: &str,
+    profile_id: &str,
+) -> Result<ModelRebind, AppError> {
+    if old_id == new_id {
+        return Err(AppError::validation("A model can't be rebound to itself"));
+    }
+
+    let mut tx = conn
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start model rebind: {}", e))?;
+
+    // Other profiles' models look the same as missing ones
+    let new_model = sqlx::query("SELECT alias FROM models WHERE id = $1 AND profile_id = $2")
+        .bind(new_id)
+        .bind(profile_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to read model {}: {}", new_id, e))?
+        .ok_or_else(|| AppError::not_found(format!("Model {} not found", new_id)))?;
+    let new_alias: Option<String> = new_model.get("alias");
+
+    // The old model may already be deleted, leaving its id behind in the references
+    let old_model = sqlx::query("SELECT profile_id, alias FROM models WHERE id = $1")
+        .bind(old_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to read model {}: {}", old_id, e))?;
+    let mut old_alias: Option<String> = None;
+    if let Some(old_model) = old_model {
+        if old_model.get::<String, _>("profile_id") != profile_id {
+            return Err(AppError::not_found(format!("Model {} not found", old_id)));
+        }
+        old_alias = old_model.get("alias");
+    }
+
+    let chat_templates = sqlx::query(
+        "UPDATE chat_template SET model_id = $1 WHERE model_id = $2 AND profile_id = $3",
+    )
+    .bind(new_id)
+    .bind(old_id)
+    .bind(profile_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to rebind chat templates: {}", e))?
+    .rows_affected();
+
+    let mut chat_settings = 0;
+    for path in ["$.translation.model_id", "$.synopsis.model_id"] {
+        chat_settings += sqlx::query(
+            "UPDATE chats SET settings = json_set(settings, $4, $1)
+             WHERE profile_id = $3 AND json_extract(settings, $4) = $2",
+        )
+        .bind(new_id)
+        .bind(old_id)
+        .bind(profile_id)
+        .bind(path)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to rebind chat settings: {}", e))?
+        .rows_affected();
+    }
+
+    let lorebooks = sqlx::query(
+        "UPDATE lorebooks SET embedding_model_id = $1
+         WHERE embedding_model_id = $2 AND profile_id = $3",
+    )
+    .bind(new_id)
+    .bind(old_id)
+    .bind(profile_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to rebind lorebooks: {}", e))?
+    .rows_affected();
+
+    // Fallback chains name models by id inside a JSON array; the new model never falls back to itself
+    let old_json = serde_json::to_string(old_id).map_err(|e| e.to_string())?;
+    let new_json = serde_json::to_string(new_id).map_err(|e| e.to_string())?;
+    let fallback_chains = sqlx::query(
+        "UPDATE models SET fallback_model_ids = REPLACE(fallback_model_ids, $1, $2)
+         WHERE profile_id = $3 AND id != $4 AND instr(fallback_model_ids, $1) > 0",
+    )
+    .bind(&old_json)
+    .bind(&new_json)
+    .bind(profile_id)
+    .bind(new_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| format!("Failed to rebind fallback chains: {}", e))?
+    .rows_affected();
+
+    // Cleared first: aliases are unique within a profile
+    let alias_moved = match (old_alias, new_alias) {
+        (Some(alias), None) => {
+            sqlx::query("UPDATE models SET alias = NULL WHERE id = $1")
+                .bind(old_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| format!("Failed to clear alias of model {}: {}", old_id, e))?;
+            sqlx::query("UPDATE models SET alias = $1 WHERE id = $2")
+                .bind(&alias)
+                .bind(new_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| format!("Failed to move alias to model {}: {}", new_id, e))?;
+            true
+        }
+        _ => false,
+    };
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit model rebind: {}", e))?;
+    Ok(ModelRebind {
+        chat_templates,
+        chat_settings,
+        lorebooks,
+        fallback_chains,
+        alias_moved,
+    })
+}
+
+/// Point every reference to `old_id` within the profile at `new_id`, in one transaction, so a
+/// failure leaves all references on the old model.
+#[tauri::command]
+pub async fn rebind_model(
+    app: AppHandle,
+    window: Window,
+    old_id: String,
+    new_id: String,
+    profile_id: String,
+) -> Result<ModelRebind, AppError> {
+    verify_window_profile(&window, &profile_id)?;
+    let mut conn = open_connection(&app).await?;
+    let result = rebind(&mut conn, &old_id, &new_id, &profile_id).await;
+    let _ = conn.close().await;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_db::temp_database;
+    use crate::error::ErrorCode;
+
+    async fn connect(name: &str) -> SqliteConnection {
+        let mut conn = temp_database(&format!("model-rebind-{}", name)).await;
+        for sql in [
+            "INSERT INTO profiles (id, name) VALUES ('p1', 'Profile'), ('p2', 'Other')",
+            "INSERT INTO models (id, profile_id, name, type, manifest_id, config, alias)
+             VALUES ('old', 'p1', 'Old', 'llm', 'openai', '{}', 'writer')",
+            "INSERT INTO models (id, profile_id, name, type, manifest_id, config)
+             VALUES ('new', 'p1', 'New', 'llm', 'openai', '{}')",
+            "INSERT INTO models (id, profile_id, name, type, manifest_id, config, fallback_model_ids)
+             VALUES ('backup', 'p1', 'Backup', 'llm', 'openai', '{}', '[\"old\",\"other\"]')",
+            "INSERT INTO models (id, profile_id, name, type, manifest_id, config)
+             VALUES ('theirs', 'p2', 'Theirs', 'llm', 'openai', '{}')",
+            "INSERT INTO chat_template (id, profile_id, name, model_id, config)
+             VALUES ('t1', 'p1', 'Story', 'old', '{}'), ('t2', 'p1', 'Other', 'backup', '{}')",
+            "INSERT INTO lorebooks (id, profile_id, name, embedding_model_id)
+             VALUES ('l1', 'p1', 'World', 'old')",
+            "INSERT INTO chats (id, profile_id, name, participants, settings)
+             VALUES ('c1', 'p1', 'Both', '[]',
+                     '{\"translation\":{\"model_id\":\"old\"},\"synopsis\":{\"model_id\":\"old\"}}'),
+                    ('c2', 'p1', 'Other model', '[]', '{\"synopsis\":{\"model_id\":\"backup\"}}'),
+                    ('c3', 'p1', 'No settings', '[]', NULL),
+                    ('c4', 'p2', 'Theirs', '[]', '{\"translation\":{\"model_id\":\"old\"}}')",
+        ] {
+            sqlx::query(sql).execute(&mut conn).await.unwrap();
+        }
+        conn
+    }
+
+    async fn value(conn: &mut SqliteConnection, sql: &str) -> Option<String> {
+        sqlx::query(sql).fetch_one(&mut *conn).await.unwrap().get(0)
+    }
+
+    #[test]
+    fn moves_references_and_alias_to_the_new_model() {
+        tauri::async_runtime::block_on(async {
+            let mut conn = connect("moves").await;
+
+            let report = rebind(&mut conn, "old", "new", "p1").await.unwrap();
+            assert_eq!(
+                report,
+                ModelRebind {
+                    chat_templates: 1,
+                    chat_settings: 2,
+                    lorebooks: 1,
+                    fallback_chains: 1,
+                    alias_moved: true,
+                }
+            );
+            assert_eq!(
+                value(
+                    &mut conn,
+                    "SELECT model_id FROM chat_template WHERE id = 't1'"
+                )
+                .await,
+                Some("new".to_string())
+            );
+            assert_eq!(
+                value(
+                    &mut conn,
+                    "SELECT model_id FROM chat_template WHERE id = 't2'"
+                )
+                .await,
+                Some("backup".to_string())
+            );
+            assert_eq!(
+                value(
+                    &mut conn,
+                    "SELECT json_extract(settings, '$.translation.model_id') || ',' ||
+                            json_extract(settings, '$.synopsis.model_id')
+                     FROM chats WHERE id = 'c1'"
+                )
+                .await,
+                Some("new,new".to_string())
+            );
+            assert_eq!(
+                value(
+                    &mut conn,
+                    "SELECT json_extract(settings, '$.synopsis.model_id') FROM chats WHERE id = 'c2'"
+                )
+                .await,
+                Some("backup".to_string())
+            );
+            assert_eq!(
+                value(
+                    &mut conn,
+                    "SELECT json_extract(settings, '$.translation.model_id') FROM chats WHERE id = 'c4'"
+                )
+                .await,
+                Some("old".to_string())
+            );
+            assert_eq!(
+                value(&mut conn, "SELECT embedding_model_id FROM lorebooks").await,
+                Some("new".to_string())
+            );
+            assert_eq!(
+                value(
+                    &mut conn,
+                    "SELECT fallback_model_ids FROM models WHERE id = 'backup'"
+                )
+                .await,
+                Some("[\"new\",\"other\"]".to_string())
+            );
+            assert_eq!(
+                value(&mut conn, "SELECT alias FROM models WHERE id = 'new'").await,
+                Some("writer".to_string())
+            );
+            assert_eq!(
+                value(&mut conn, "SELECT alias FROM models WHERE id = 'old'").await,
+                None
+            );
+        });
+    }
+
+    #[test]
+    fn rebinds_fallback_chains_of_a_deleted_model() {
+        tauri::async_runtime::block_on(async {
+            let mut conn = connect("deleted").await;
+            // Foreign keys clear the template's model, but fallback chains keep the stale id
+            sqlx::query("DELETE FROM models WHERE id = 'old'")
+                .execute(&mut conn)
+                .await
+                .unwrap();
+
+            let report = rebind(&mut conn, "old", "new", "p1").await.unwrap();
+            assert_eq!(report.chat_templates, 0);
+            assert_eq!(report.fallback_chains, 1);
+            assert!(!report.alias_moved);
+        });
+    }
+
+    #[test]
+    fn refuses_models_of_other_profiles_without_writing() {
+        tauri::async_runtime::block_on(async {
+            let mut conn = connect("ownership").await;
+
+            let error = rebind(&mut conn, "old", "theirs", "p1").await.unwrap_err();
+            assert_eq!(error.code, ErrorCode::NotFound);
+            let error = rebind(&mut conn, "theirs", "new", "p1").await.unwrap_err();
+            assert_eq!(error.code, ErrorCode::NotFound);
+            let error = rebind(&mut conn, "old", "new", "p2").await.unwrap_err();
+            assert_eq!(error.code, ErrorCode::NotFound);
+            let error = rebind(&mut conn, "new", "new", "p1").await.unwrap_err();
+            assert_eq!(error.code, ErrorCode::Validation);
+
+            assert_eq!(
+                value(
+                    &mut conn,
+                    "SELECT model_id FROM chat_template WHERE id = 't1'"
+                )
+                .await,
+                Some("old".to_string())
+            );
+        });
+    }
+}
@@ -0,0 +1,442 @@
+use serde::Serialize;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteConnection};
+use sqlx::{ConnectOptions, Connection};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Window};
+
+use super::migrator::{database_path, DB_FILE_NAME};
+use crate::error::AppError;
+use crate::windows::verify_unrestricted_window;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RepairAction {
+    // The row can't be used without its parent
+    Delete,
+    // Only an optional reference is dangling, so it is cleared and the row kept
+    Detach,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OrphanCategory {
+    pub category: &'static str,
+    pub label: &'static str,
+    pub action: RepairAction,
+    pub count: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OrphanReport {
+    pub dry_run: bool,
+    // Only categories with at least one row
+    pub categories: Vec<OrphanCategory>,
+    pub total: u64,
+    pub backup_path: Option<String>,
+}
+
+struct RepairStep {
+    category: &'static str,
+    label: &'static str,
+    action: RepairAction,
+    sql: &'static str,
+}
+
+const fn delete(category: &'static str, label: &'static str, sql: &'static str) -> RepairStep {
+    RepairStep {
+        category,
+        label,
+        action: RepairAction::Delete,
+        sql,
+    }
+}
+
+const fn detach(category: &'static str, label: &'static str, sql: &'static str) -> RepairStep {
+    RepairStep {
+        category,
+        label,
+        action: RepairAction::Detach,
+        sql,
+    }
+}
+
+// Parents before children, so rows orphaned by an earlier step are picked up by a later one
+// in the same pass. Foreign keys are off while repairing, so nothing cascades uncounted.
+const REPAIR_STEPS: &[RepairStep] = &[
+    delete(
+        "format_templates_without_profile",
+        "format templates without a profile",
+        "DELETE FROM format_template WHERE profile_id NOT IN (SELECT id FROM profiles)",
+    ),
+    delete(
+        "inference_templates_without_profile",
+        "inference templates without a profile",
+        "DELETE FROM inference_template WHERE profile_id NOT IN (SELECT id FROM profiles)",
+    ),
+    delete(
+        "chat_templates_without_profile",
+        "chat templates without a profile",
+        "DELETE FROM chat_template WHERE profile_id NOT IN (SELECT id FROM profiles)",
+    ),
+    delete(
+        "models_without_profile",
+        "models without a profile",
+        "DELETE FROM models WHERE profile_id NOT IN (SELECT id FROM profiles)",
+    ),
+    delete(
+        "characters_without_profile",
+        "characters without a profile",
+        "DELETE FROM characters WHERE profile_id NOT IN (SELECT id FROM profiles)",
+    ),
+    delete(
+        "lorebooks_without_profile",
+        "lorebooks without a profile",
+        "DELETE FROM lorebooks WHERE profile_id NOT IN (SELECT id FROM profiles)",
+    ),
+    delete(
+        "agents_without_profile",
+        "agents without a profile",
+        "DELETE FROM agents WHERE profile_id NOT IN (SELECT id FROM profiles)",
+    ),
+    delete(
+        "custom_nodes_without_profile",
+        "custom nodes without a profile",
+        "DELETE FROM custom_nodes WHERE profile_id NOT IN (SELECT id FROM profiles)",
+    ),
+    delete(
+        "webhooks_without_profile",
+        "webhooks without a profile",
+        "DELETE FROM webhooks WHERE profile_id NOT IN (SELECT id FROM profiles)",
+    ),
+    delete(
+        "notes_without_profile",
+        "notes without a profile",
+        "DELETE FROM notes WHERE profile_id NOT IN (SELECT id FROM profiles)",
+    ),
+    delete(
+        "usage_without_profile",
+        "monthly usage totals without a profile",
+        "DELETE FROM profile_usage WHERE profile_id NOT IN (SELECT id FROM profiles)",
+    ),
+    delete(
+        "daily_usage_without_profile",
+        "daily usage totals without a profile",
+        "DELETE FROM profile_usage_daily WHERE profile_id NOT IN (SELECT id FROM profiles)",
+    ),
+    delete(
+        "chats_without_profile",
+        "chats without a profile",
+        "DELETE FROM chats WHERE profile_id NOT IN (SELECT id FROM profiles)",
+    ),
+    delete(
+        "chapters_without_chat",
+        "chapters without a chat",
+        "DELETE FROM chat_chapters WHERE chat_id NOT IN (SELECT id FROM chats)",
+    ),
+    delete(
+        "messages_without_chat",
+        "messages without a chat",
+        "DELETE FROM chat_messages WHERE chat_id NOT IN (SELECT id FROM chats)",
+    ),
+    delete(
+        "messages_without_chapter",
+        "messages without a chapter",
+        "DELETE FROM chat_messages WHERE chapter_id NOT IN (SELECT id FROM chat_chapters)",
+    ),
+    delete(
+        "summaries_without_parent",
+        "message summaries without a chat or chapter",
+        "DELETE FROM chat_message_summaries
+         WHERE chat_id NOT IN (SELECT id FROM chats) OR chapter_id NOT IN (SELECT id FROM chat_chapters)",
+    ),
+    delete(
+        "memories_without_parent",
+        "memories without a chat, chapter or character",
+        "DELETE FROM chat_memories
+         WHERE chat_id NOT IN (SELECT id FROM chats)
+            OR (chapter_id IS NOT NULL AND chapter_id NOT IN (SELECT id FROM chat_chapters))
+            OR (character_id IS NOT NULL AND character_id NOT IN (SELECT id FROM characters))",
+    ),
+    delete(
+        "markers_without_parent",
+        "chat markers without a chat or chapter",
+        "DELETE FROM chat_markers
+         WHERE chat_id NOT IN (SELECT id FROM chats) OR chapter_id NOT IN (SELECT id FROM chat_chapters)",
+    ),
+    delete(
+        "read_states_without_parent",
+        "read positions without a chat or profile",
+        "DELETE FROM chat_read_state
+         WHERE chat_id NOT IN (SELECT id FROM chats) OR profile_id NOT IN (SELECT id FROM profiles)",
+    ),
+    delete(
+        "lorebook_entries_without_lorebook",
+        "lorebook entries without a lorebook",
+        "DELETE FROM lorebook_entries WHERE lorebook_id NOT IN (SELECT id FROM lorebooks)",
+    ),
+    delete(
+        "webhook_deliveries_without_parent",
+        "webhook deliveries without a webhook or profile",
+        "DELETE FROM webhook_deliveries
+         WHERE webhook_id NOT IN (SELECT id FROM webhooks) OR profile_id NOT IN (SELECT id FROM profiles)",
+    ),
+    detach(
+        "chats_missing_user_character",
+        "chats pointing at a deleted user character",
+        "UPDATE chats SET user_character_id = NULL
+         WHERE user_character_id IS NOT NULL AND user_character_id NOT IN (SELECT id FROM characters)",
+    ),
+    detach(
+        "chats_missing_chat_template",
+        "chats pointing at a deleted chat template",
+        "UPDATE chats SET chat_template_id = NULL
+         WHERE chat_template_id IS NOT NULL AND chat_template_id NOT IN (SELECT id FROM chat_template)",
+    ),
+    detach(
+        "chats_missing_active_chapter",
+        "chats pointing at a deleted chapter",
+        "UPDATE chats SET active_chapter_id = NULL
+         WHERE active_chapter_id IS NOT NULL AND active_chapter_id NOT IN (SELECT id FROM chat_chapters)",
+    ),
+    detach(
+        "messages_missing_character",
+        "messages from a deleted character",
+        "UPDATE chat_messages SET character_id = NULL
+         WHERE character_id IS NOT NULL AND character_id NOT IN (SELECT id FROM characters)",
+    ),
+    detach(
+        "chat_templates_missing_model",
+        "chat templates pointing at a deleted model",
+        "UPDATE chat_template SET model_id = NULL
+         WHERE model_id IS NOT NULL AND model_id NOT IN (SELECT id FROM models)",
+    ),
+    detach(
+        "chat_templates_missing_format_template",
+        "chat templates pointing at a deleted format template",
+        "UPDATE chat_template SET format_template_id = NULL
+         WHERE format_template_id IS NOT NULL AND format_template_id NOT IN (SELECT id FROM format_template)",
+    ),
+    detach(
+        "models_missing_inference_template",
+        "models pointing at a deleted inference template",
+        "UPDATE models SET inference_template_id = NULL
+         WHERE inference_template_id IS NOT NULL AND inference_template_id NOT IN (SELECT id FROM inference_template)",
+    ),
+    detach(
+        "characters_missing_lorebook",
+        "characters pointing at a deleted lorebook",
+        "UPDATE characters SET lorebook_id = NULL
+         WHERE lorebook_id IS NOT NULL AND lorebook_id NOT IN (SELECT id FROM lorebooks)",
+    ),
+    detach(
+        "lorebooks_missing_embedding_model",
+        "lorebooks pointing at a deleted embedding model",
+        "UPDATE lorebooks SET embedding_model_id = NULL
+         WHERE embedding_model_id IS NOT NULL AND embedding_model_id NOT IN (SELECT id FROM models)",
+    ),
+];
+
+// Find rows whose parent is gone (left behind by deletes made without cascading) and, unless
+// this is a dry run, delete or detach them. A copy of the database is taken before any change.
+#[tauri::command]
+pub async fn repair_orphans(
+    app: AppHandle,
+    window: Window,
+    dry_run: bool,
+) -> Result<OrphanReport, AppError> {
+    verify_unrestricted_window(&window)?;
+    let db_path = database_path(&app)?;
+    let mut conn = SqliteConnectOptions::new()
+        .filename(&db_path)
+        .foreign_keys(false)
+        .connect()
+        .await
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+
+    let result = repair_with_backup(&mut conn, &db_path, dry_run).await;
+    let _ = conn.close().await;
+    result.map_err(AppError::from)
+}
+
+async fn repair_with_backup(
+    conn: &mut SqliteConnection,
+    db_path: &Path,
+    dry_run: bool,
+) -> Result<OrphanReport, String> {
+    let preview = run_repair(conn, true).await?;
+    if dry_run || preview.total == 0 {
+        return Ok(preview);
+    }
+
+    let backup_path = backup_path_for(db_path);
+    // VACUUM INTO gives a consistent copy while the app still holds the database open
+    sqlx::query("VACUUM INTO $1")
+        .bind(backup_path.to_string_lossy().to_string())
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to back up database before repairing: {}", e))?;
+
+    let mut report = run_repair(conn, false).await?;
+    report.backup_path = Some(backup_path.to_string_lossy().to_string());
+    Ok(report)
+}
+
+// Every step runs in one transaction; a dry run rolls it back so the counts include rows
+// that only become orphaned once their parent is removed
+async fn run_repair(conn: &mut SqliteConnection, dry_run: bool) -> Result<OrphanReport, String> {
+    let mut tx = conn
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start repair transaction: {}", e))?;
+
+    let mut categories = Vec::new();
+    for step in REPAIR_STEPS {
+        let count = sqlx::query(step.sql)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to repair {}: {}", step.label, e))?
+            .rows_affected();
+        if count > 0 {
+            categories.push(OrphanCategory {
+                category: step.category,
+                label: step.label,
+                action: step.action,
+                count,
+            });
+        }
+    }
+
+    if dry_run {
+        tx.rollback()
+            .await
+            .map_err(|e| format!("Failed to roll back repair preview: {}", e))?;
+    } else {
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit repair: {}", e))?;
+    }
+
+    Ok(OrphanReport {
+        dry_run,
+        total: categories.iter().map(|category| category.count).sum(),
+        categories,
+        backup_path: None,
+    })
+}
+
+// e.g. narratrix_main.db.pre-repair-1718000000.bak, next to the database
+fn backup_path_for(db_path: &Path) -> PathBuf {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0);
+    db_path.with_file_name(format!("{}.pre-repair-{}.bak", DB_FILE_NAME, timestamp))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_db::migrated_database;
+    use sqlx::Row;
+
+    async fn seed_orphans(conn: &mut SqliteConnection) {
+        for sql in [
+            "INSERT INTO profiles (id, name) VALUES ('p1', 'Alive')",
+            "INSERT INTO chats (id, profile_id, name, participants, user_character_id)
+             VALUES ('c1', 'p1', 'Kept', '[]', 'deleted-character')",
+            "INSERT INTO chats (id, profile_id, name, participants) VALUES ('c2', 'gone', 'Orphan', '[]')",
+            "INSERT INTO chat_chapters (id, chat_id, title, sequence) VALUES ('ch1', 'c1', 'One', 1)",
+            "INSERT INTO chat_chapters (id, chat_id, title, sequence) VALUES ('ch2', 'c2', 'One', 1)",
+            "INSERT INTO chat_messages (id, chat_id, chapter_id, type, position, messages, message_index)
+             VALUES ('m1', 'c1', 'ch1', 'user', 100, '[\"hi\"]', 0)",
+            "INSERT INTO chat_messages (id, chat_id, chapter_id, type, position, messages, message_index)
+             VALUES ('m2', 'c2', 'ch2', 'user', 100, '[\"hi\"]', 0)",
+            "INSERT INTO chat_messages (id, chat_id, chapter_id, type, position, messages, message_index)
+             VALUES ('m3', 'missing-chat', 'ch1', 'user', 200, '[\"hi\"]', 0)",
+        ] {
+            sqlx::query(sql).execute(&mut *conn).await.unwrap();
+        }
+    }
+
+    async fn count(conn: &mut SqliteConnection, sql: &str) -> i64 {
+        sqlx::query(sql).fetch_one(&mut *conn).await.unwrap().get(0)
+    }
+
+    fn category_count(report: &OrphanReport, category: &str) -> u64 {
+        report
+            .categories
+            .iter()
+            .find(|entry| entry.category == category)
+            .map(|entry| entry.count)
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn dry_run_counts_without_changing_anything() {
+        tauri::async_runtime::block_on(async {
+            let db_path = migrated_database("repair-dry-run").await;
+            let mut conn = SqliteConnectOptions::new()
+                .filename(&db_path)
+                .foreign_keys(false)
+                .connect()
+                .await
+                .unwrap();
+            seed_orphans(&mut conn).await;
+
+            let report = repair_with_backup(&mut conn, &db_path, true).await.unwrap();
+
+            assert!(report.dry_run);
+            assert_eq!(category_count(&report, "chats_without_profile"), 1);
+            assert_eq!(category_count(&report, "chapters_without_chat"), 1);
+            // m2 is only orphaned once its chat is removed, m3 never had one
+            assert_eq!(category_count(&report, "messages_without_chat"), 2);
+            assert_eq!(category_count(&report, "chats_missing_user_character"), 1);
+            assert_eq!(report.total, 5);
+            assert!(report.backup_path.is_none());
+            assert_eq!(
+                count(&mut conn, "SELECT COUNT(*) FROM chat_messages").await,
+                3
+            );
+        });
+    }
+
+    #[test]
+    fn repair_removes_orphans_and_keeps_a_backup() {
+        tauri::async_runtime::block_on(async {
+            let db_path = migrated_database("repair-repair").await;
+            let mut conn = SqliteConnectOptions::new()
+                .filename(&db_path)
+                .foreign_keys(false)
+                .connect()
+                .await
+                .unwrap();
+            seed_orphans(&mut conn).await;
+
+            let report = repair_with_backup(&mut conn, &db_path, false)
+                .await
+                .unwrap();
+
+            assert_eq!(report.total, 5);
+            assert!(PathBuf::from(report.backup_path.unwrap()).exists());
+            assert_eq!(count(&mut conn, "SELECT COUNT(*) FROM chats").await, 1);
+            assert_eq!(
+                count(&mut conn, "SELECT COUNT(*) FROM chat_messages").await,
+                1
+            );
+            assert_eq!(
+                count(
+                    &mut conn,
+                    "SELECT COUNT(*) FROM chats WHERE user_character_id IS NULL"
+                )
+                .await,
+                1
+            );
+
+            let again = repair_with_backup(&mut conn, &db_path, false)
+                .await
+                .unwrap();
+            assert_eq!(again.total, 0);
+            assert!(again.backup_path.is_none());
+        });
+    }
+}
@@ -0,0 +1,32 @@
+use sqlx::sqlite::{SqliteConnectOptions, SqliteConnection};
+use sqlx::ConnectOptions;
+use std::path::{Path, PathBuf};
+
+use super::get_migrations;
+use super::migrator::{run_migrations, DB_FILE_NAME};
+
+// Path of a fresh, fully migrated database in its own temp folder ("narratrix-<name>"), emptied
+// first so reruns start clean. Tests that need files next to the database use its parent.
+pub async fn migrated_database(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("narratrix-{}", name));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let db_path = dir.join(DB_FILE_NAME);
+    run_migrations(&db_path, get_migrations(), |_| {})
+        .await
+        .unwrap();
+    db_path
+}
+
+pub async fn connect(db_path: &Path) -> SqliteConnection {
+    SqliteConnectOptions::new()
+        .filename(db_path)
+        .connect()
+        .await
+        .unwrap()
+}
+
+// Connection to a fresh, fully migrated database
+pub async fn temp_database(name: &str) -> SqliteConnection {
+    connect(&migrated_database(name).await).await
+}
@@ -0,0 +1,125 @@
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+use std::fmt;
+
+// Error returned by commands. It reaches the frontend as `{ code, message, details }`, so callers
+// can switch on `code` instead of matching message text (see src/commands/errors.ts). Helpers that
+// still return `Result<_, String>` convert with `?` and count as internal errors.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    NotFound,
+    Unauthorized,
+    // The arguments were rejected; sending them again won't help
+    Validation,
+    // The request clashes with the current state, e.g. an action not allowed right now
+    Conflict,
+    // A remote service (model hub, webhook target...) failed
+    Provider,
+    Internal,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AppError {
+    pub code: ErrorCode,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<JsonValue>,
+}
+
+impl AppError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::NotFound, message)
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Validation, message)
+    }
+
+    pub fn conflict(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Conflict, message)
+    }
+
+    pub fn provider(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Provider, message)
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Internal, message)
+    }
+
+    pub fn with_details(mut self, details: JsonValue) -> Self {
+        self.details = Some(details);
+        self
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        Self::internal(message)
+    }
+}
+
+impl From<&str> for AppError {
+    fn from(message: &str) -> Self {
+        Self::internal(message)
+    }
+}
+
+// For callers still on `Result<_, String>`
+impl From<AppError> for String {
+    fn from(error: AppError) -> Self {
+        error.message
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serializes_a_stable_code() {
+        let error = AppError::not_found("No such chat").with_details(json!({ "chat_id": "c1" }));
+        assert_eq!(
+            serde_json::to_value(&error).unwrap(),
+            json!({ "code": "not_found", "message": "No such chat", "details": { "chat_id": "c1" } })
+        );
+        assert_eq!(
+            serde_json::to_value(AppError::validation("Bad key")).unwrap(),
+            json!({ "code": "validation", "message": "Bad key" })
+        );
+    }
+
+    #[test]
+    fn converts_from_and_to_strings() {
+        fn legacy() -> Result<(), String> {
+            Err("Disk full".to_string())
+        }
+        fn migrated() -> Result<(), AppError> {
+            legacy()?;
+            Ok(())
+        }
+
+        let error = migrated().unwrap_err();
+        assert_eq!(error.code, ErrorCode::Internal);
+        assert_eq!(String::from(error), "Disk full");
+    }
+}
@@ -0,0 +1,432 @@
+use once_cell::sync::Lazy;
+use pulldown_cmark::{html, CowStr, Event, Options, Parser, Tag};
+use regex::{Captures, Regex};
+use serde::Deserialize;
+use std::fmt::Write;
+
+use crate::scrub::{ScrubOptions, Scrubber};
+
+// Rendering of a chat into one self-contained HTML page. Message content is untrusted: raw HTML
+// in the markdown is shown as text and links can't run script, so opening the file is safe. The
+// page also ships a CSP that blocks scripts, in case anything slips through.
+
+const TEMPLATE: &str = include_str!("chat.html");
+
+static PLACEHOLDER_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{\{(\w+)\}\}").unwrap());
+static REASONING_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<(?:think|thinking)>(.*?)</(?:think|thinking)>").unwrap());
+// Avatars end up in a CSS url(), so only plain base64 images are let through
+static DATA_URL_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^data:image/(?:png|jpeg|gif|webp);base64,[A-Za-z0-9+/]+=*$").unwrap()
+});
+
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HtmlTheme {
+    #[default]
+    Light,
+    Dark,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ChatHtmlOptions {
+    pub theme: HtmlTheme,
+    pub include_system: bool,
+    // Collapsed under each message
+    pub include_reasoning: bool,
+    // Names and personal details replaced through the scrub module, see ScrubOptions.replacements
+    pub redact: Option<ScrubOptions>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageKind {
+    User,
+    Character,
+    System,
+}
+
+impl MessageKind {
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "user" => MessageKind::User,
+            "character" => MessageKind::Character,
+            _ => MessageKind::System,
+        }
+    }
+
+    fn class(&self) -> &'static str {
+        match self {
+            MessageKind::User => "user",
+            MessageKind::Character => "character",
+            MessageKind::System => "system",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct HtmlParticipant {
+    pub name: String,
+    // data: URL of the avatar image
+    pub avatar: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct HtmlMessage {
+    pub kind: MessageKind,
+    // Index into HtmlChat.participants
+    pub author: usize,
+    pub text: String,
+}
+
+#[derive(Debug)]
+pub struct HtmlChapter {
+    pub title: String,
+    pub messages: Vec<HtmlMessage>,
+}
+
+#[derive(Debug)]
+pub struct HtmlChat {
+    pub name: String,
+    pub participants: Vec<HtmlParticipant>,
+    pub chapters: Vec<HtmlChapter>,
+}
+
+pub struct RenderedChat {
+    pub html: String,
+    pub messages: usize,
+}
+
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+pub fn is_safe_data_url(url: &str) -> bool {
+    DATA_URL_RE.is_match(url)
+}
+
+// Relative links and web or mail links only; `javascript:` and the like become "#"
+fn safe_url(url: CowStr<'_>, image: bool) -> CowStr<'_> {
+    let trimmed = url.trim();
+    let scheme_end = trimmed.find([':', '/', '?', '#']);
+    let Some(end) = scheme_end.filter(|&end| trimmed[end..].starts_with(':')) else {
+        return url;
+    };
+    let scheme = trimmed[..end].to_ascii_lowercase();
+    let allowed = matches!(scheme.as_str(), "http" | "https" | "mailto")
+        || (image && scheme == "data" && is_safe_data_url(trimmed));
+    if allowed {
+        url
+    } else {
+        CowStr::Borrowed("#")
+    }
+}
+
+/// Markdown to HTML for untrusted text: raw HTML is escaped, unsafe link targets are dropped and
+/// single line breaks are kept, as chat messages rely on them.
+pub fn markdown_to_html(text: &str) -> String {
+    let options = Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TABLES;
+    let events = Parser::new_ext(text, options).map(|event| match event {
+        Event::Html(raw) | Event::InlineHtml(raw) => Event::Text(raw),
+        Event::SoftBreak => Event::HardBreak,
+        Event::Start(Tag::Link {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Link {
+            link_type,
+            dest_url: safe_url(dest_url, false),
+            title,
+            id,
+        }),
+        Event::Start(Tag::Image {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Image {
+            link_type,
+            dest_url: safe_url(dest_url, true),
+            title,
+            id,
+        }),
+        event => event,
+    });
+
+    let mut output = String::new();
+    html::push_html(&mut output, events);
+    output
+}
+
+// Reasoning blocks (<think>...</think>) apart from the answer
+fn split_reasoning(text: &str) -> (Vec<String>, String) {
+    let reasoning = REASONING_RE
+        .captures_iter(text)
+        .map(|caps| caps[1].trim().to_string())
+        .filter(|block| !block.is_empty())
+        .collect();
+    (
+        reasoning,
+        REASONING_RE.replace_all(text, "").trim().to_string(),
+    )
+}
+
+fn initial(name: &str) -> String {
+    name.chars()
+        .find(|c| c.is_alphanumeric())
+        .map(|c| c.to_uppercase().collect())
+        .unwrap_or_else(|| "?".to_string())
+}
+
+// Placeholders are filled in one pass over the template, so values are never scanned again
+fn fill_template(values: &[(&str, &str)]) -> String {
+    PLACEHOLDER_RE
+        .replace_all(TEMPLATE, |caps: &Captures| {
+            values
+                .iter()
+                .find(|(key, _)| *key == &caps[1])
+                .map(|(_, value)| value.to_string())
+                .unwrap_or_default()
+        })
+        .into_owned()
+}
+
+pub fn render_chat_html(
+    chat: &HtmlChat,
+    options: &ChatHtmlOptions,
+) -> Result<RenderedChat, String> {
+    let mut scrubber = options.redact.clone().map(Scrubber::new).transpose()?;
+    let mut redact = |text: &str| match scrubber.as_mut() {
+        Some(scrubber) => scrubber.scrub(text),
+        None => text.to_string(),
+    };
+
+    let title = redact(&chat.name);
+    let names: Vec<String> = chat
+        .participants
+        .iter()
+        .map(|participant| redact(&participant.name))
+        .collect();
+
+    let mut avatar_styles = String::new();
+    for (index, participant) in chat.participants.iter().enumerate() {
+        if let Some(avatar) = participant
+            .avatar
+            .as_deref()
+            .filter(|url| is_safe_data_url(url))
+        {
+            let _ = writeln!(
+                avatar_styles,
+                ".avatar-{} {{ background-image: url(\"{}\"); }}",
+                index, avatar
+            );
+        }
+    }
+
+    let mut body = String::new();
+    let mut count = 0;
+    for chapter in &chat.chapters {
+        let messages: Vec<&HtmlMessage> = chapter
+            .messages
+            .iter()
+            .filter(|message| options.include_system || message.kind != MessageKind::System)
+            .collect();
+        if messages.is_empty() {
+            continue;
+        }
+        if chat.chapters.len() > 1 {
+            let _ = writeln!(
+                body,
+                "<h2 class=\"chapter\">{}</h2>",
+                escape_html(&redact(&chapter.title))
+            );
+        }
+
+        for message in messages {
+            let (reasoning, answer) = split_reasoning(&redact(&message.text));
+            let name = names
+                .get(message.author)
+                .map(String::as_str)
+                .unwrap_or("Unknown");
+            let has_avatar = chat
+                .participants
+                .get(message.author)
+                .and_then(|participant| participant.avatar.as_deref())
+                .is_some_and(is_safe_data_url);
+
+            let _ = writeln!(body, "<article class=\"message {}\">", message.kind.class());
+            if has_avatar {
+                let _ = writeln!(
+                    body,
+                    "<div class=\"avatar avatar-{}\"></div>",
+                    message.author
+                );
+            } else {
+                let _ = writeln!(
+                    body,
+                    "<div class=\"avatar\">{}</div>",
+                    escape_html(&initial(name))
+                );
+            }
+            body.push_str("<div class=\"bubble\">\n");
+            let _ = writeln!(body, "<div class=\"name\">{}</div>", escape_html(name));
+            if options.include_reasoning {
+                for block in &reasoning {
+                    let _ = write!(
+                        body,
+                        "<details class=\"reasoning\"><summary>Reasoning</summary>\n{}</details>\n",
+                        markdown_to_html(block)
+                    );
+                }
+            }
+            let _ = write!(
+                body,
+                "<div class=\"text\">\n{}</div>\n</div>\n</article>\n",
+                markdown_to_html(&answer)
+            );
+            count += 1;
+        }
+    }
+
+    let theme = match options.theme {
+        HtmlTheme::Light => "light",
+        HtmlTheme::Dark => "dark",
+    };
+    let html = fill_template(&[
+        ("title", &escape_html(&title)),
+        ("theme", theme),
+        ("avatar_styles", &avatar_styles),
+        ("body", &body),
+    ]);
+
+    Ok(RenderedChat {
+        html,
+        messages: count,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::path::Path;
+
+    const PIXEL: &str = "data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNk+M9QDwADhgGAWjR9awAAAABJRU5ErkJggg==";
+
+    fn fixture() -> HtmlChat {
+        HtmlChat {
+            name: "The Lighthouse".to_string(),
+            participants: vec![
+                HtmlParticipant {
+                    name: "Vitor".to_string(),
+                    avatar: None,
+                },
+                HtmlParticipant {
+                    name: "Mira".to_string(),
+                    avatar: Some(PIXEL.to_string()),
+                },
+                HtmlParticipant {
+                    name: "System".to_string(),
+                    avatar: None,
+                },
+            ],
+            chapters: vec![
+                HtmlChapter {
+                    title: "Arrival".to_string(),
+                    messages: vec![
+                        HtmlMessage {
+                            kind: MessageKind::User,
+                            author: 0,
+                            text: "Is anyone *up there*?\nI brought the oil.".to_string(),
+                        },
+                        HtmlMessage {
+                            kind: MessageKind::Character,
+                            author: 1,
+                            text: "<think>Vitor sounds tired.</think>Come up, **Vitor**. Mind the [stairs](https://example.com/stairs)."
+                                .to_string(),
+                        },
+                        HtmlMessage {
+                            kind: MessageKind::System,
+                            author: 2,
+                            text: "Night falls.".to_string(),
+                        },
+                    ],
+                },
+                HtmlChapter {
+                    title: "The Lamp".to_string(),
+                    messages: vec![HtmlMessage {
+                        kind: MessageKind::Character,
+                        author: 1,
+                        text: "The lamp <b onclick=\"alert(1)\">flickers</b>.".to_string(),
+                    }],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn renders_the_fixture_chat() {
+        let options = ChatHtmlOptions {
+            theme: HtmlTheme::Dark,
+            include_system: true,
+            include_reasoning: true,
+            redact: Some(ScrubOptions {
+                replacements: HashMap::from([("Vitor".to_string(), "Traveler".to_string())]),
+                ..ScrubOptions::default()
+            }),
+        };
+        let rendered = render_chat_html(&fixture(), &options).unwrap();
+        assert_eq!(rendered.messages, 4);
+
+        // UPDATE_GOLDEN=1 cargo test rewrites the golden file after an intended change
+        let golden =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("src/exports/testdata/chat.golden.html");
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            std::fs::write(&golden, &rendered.html).unwrap();
+        }
+        assert_eq!(rendered.html, std::fs::read_to_string(&golden).unwrap());
+    }
+
+    #[test]
+    fn leaves_out_system_messages_and_reasoning_by_default() {
+        let rendered = render_chat_html(&fixture(), &ChatHtmlOptions::default()).unwrap();
+        assert_eq!(rendered.messages, 3);
+        assert!(!rendered.html.contains("Night falls."));
+        assert!(!rendered.html.contains("sounds tired"));
+        assert!(rendered.html.contains("Vitor"));
+    }
+
+    #[test]
+    fn never_lets_html_or_scripts_through() {
+        let html = markdown_to_html(
+            "<script>alert(1)</script>\n\n<img src=x onerror=alert(1)> [click](javascript:alert(1)) ![x](JavaScript:alert(1)) [ok](/relative)",
+        );
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("<img src=x"));
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(html.contains("<a href=\"#\">click</a>"));
+        assert!(html.contains("<img src=\"#\""));
+        assert!(html.contains("<a href=\"/relative\">ok</a>"));
+    }
+
+    #[test]
+    fn only_accepts_plain_image_data_urls() {
+        assert!(is_safe_data_url(PIXEL));
+        assert!(!is_safe_data_url("data:image/svg+xml;base64,PHN2Zz4="));
+        assert!(!is_safe_data_url(
+            "data:image/png;base64,AAA\"); } body { x: url(\""
+        ));
+    }
+}
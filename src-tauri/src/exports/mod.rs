@@ -0,0 +1,218 @@
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use sqlx::sqlite::SqliteConnection;
+use sqlx::{Connection, Row};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager, Window};
+
+use crate::database::open_connection;
+use crate::error::AppError;
+use crate::windows::verify_window_profile;
+use html::{
+    render_chat_html, ChatHtmlOptions, HtmlChapter, HtmlChat, HtmlMessage, HtmlParticipant,
+    MessageKind,
+};
+
+pub mod html;
+
+// Larger avatars are left out rather than bloating the page
+const MAX_AVATAR_BYTES: u64 = 4 * 1024 * 1024;
+
+fn image_mime(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    match extension.as_str() {
+        "png" => Some("image/png"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "gif" => Some("image/gif"),
+        "webp" => Some("image/webp"),
+        _ => None,
+    }
+}
+
+// Avatar as a data: URL. Paths are relative to the app data dir or absolute; remote URLs and
+// unreadable files are skipped so the page stays self-contained.
+fn inline_avatar(data_dir: &Path, avatar_path: Option<&str>) -> Option<String> {
+    let avatar_path = avatar_path.map(str::trim).filter(|path| !path.is_empty())?;
+    if avatar_path.starts_with("data:") {
+        return html::is_safe_data_url(avatar_path).then(|| avatar_path.to_string());
+    }
+    if avatar_path.contains("://") {
+        return None;
+    }
+
+    let path = PathBuf::from(avatar_path);
+    let path = if path.is_absolute() {
+        path
+    } else {
+        data_dir.join(path)
+    };
+    let mime = image_mime(&path)?;
+    if fs::metadata(&path).ok()?.len() > MAX_AVATAR_BYTES {
+        return None;
+    }
+    let data = fs::read(&path).ok()?;
+    Some(format!("data:{};base64,{}", mime, BASE64.encode(data)))
+}
+
+// Active variant of a message row
+fn active_text(messages: &str, index: i64) -> String {
+    let variants: Vec<String> = serde_json::from_str(messages).unwrap_or_default();
+    usize::try_from(index)
+        .ok()
+        .and_then(|index| variants.get(index).cloned())
+        .or_else(|| variants.into_iter().next())
+        .unwrap_or_default()
+}
+
+async fn load_chat(
+    conn: &mut SqliteConnection,
+    data_dir: &Path,
+    chat_id: &str,
+    profile_id: &str,
+) -> Result<HtmlChat, AppError> {
+    let chat = sqlx::query(
+        "SELECT c.name, c.user_character_id, p.name AS profile_name, p.avatar_path AS profile_avatar
+         FROM chats c JOIN profiles p ON p.id = c.profile_id
+         WHERE c.id = $1 AND c.profile_id = $2",
+    )
+    .bind(chat_id)
+    .bind(profile_id)
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| format!("Failed to read chat {}: {}", chat_id, e))?
+    .ok_or_else(|| AppError::not_found(format!("Chat {} not found", chat_id)))?;
+
+    let user_character_id: Option<String> = chat.get("user_character_id");
+
+    // Participant 0 is the user, then characters in the order they first speak
+    let mut participants = vec![HtmlParticipant {
+        name: chat.get("profile_name"),
+        avatar: inline_avatar(data_dir, chat.get("profile_avatar")),
+    }];
+    let mut authors: HashMap<String, usize> = HashMap::new();
+
+    let characters = sqlx::query(
+        "SELECT id, name, avatar_path FROM characters WHERE profile_id = $1 AND id IN
+         (SELECT character_id FROM chat_messages WHERE chat_id = $2 AND character_id IS NOT NULL
+          UNION SELECT $3)",
+    )
+    .bind(profile_id)
+    .bind(chat_id)
+    .bind(user_character_id.as_deref())
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| format!("Failed to read chat characters: {}", e))?;
+    let characters: HashMap<String, (String, Option<String>)> = characters
+        .into_iter()
+        .map(|row| (row.get("id"), (row.get("name"), row.get("avatar_path"))))
+        .collect();
+
+    if let Some((name, avatar)) = user_character_id.as_ref().and_then(|id| characters.get(id)) {
+        participants[0] = HtmlParticipant {
+            name: name.clone(),
+            avatar: inline_avatar(data_dir, avatar.as_deref()),
+        };
+    }
+
+    let chapters = sqlx::query(
+        "SELECT id, title FROM chat_chapters WHERE chat_id = $1 ORDER BY sequence, created_at",
+    )
+    .bind(chat_id)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| format!("Failed to read chat chapters: {}", e))?;
+
+    let mut stand_ins: HashMap<&str, usize> = HashMap::new();
+    let mut html_chapters = Vec::with_capacity(chapters.len());
+    for chapter in chapters {
+        let chapter_id: String = chapter.get("id");
+        let rows = sqlx::query(
+            "SELECT type, character_id, messages, message_index FROM chat_messages
+             WHERE chat_id = $1 AND chapter_id = $2 AND disabled = 0 ORDER BY position",
+        )
+        .bind(chat_id)
+        .bind(&chapter_id)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to read chapter {}: {}", chapter_id, e))?;
+
+        let mut messages = Vec::with_capacity(rows.len());
+        for row in rows {
+            let kind = MessageKind::parse(row.get::<String, _>("type").as_str());
+            let character_id: Option<String> = row.get("character_id");
+            let author = match (kind, character_id) {
+                (MessageKind::User, _) => 0,
+                (_, Some(id)) if characters.contains_key(&id) => {
+                    *authors.entry(id.clone()).or_insert_with(|| {
+                        let (name, avatar) = &characters[&id];
+                        participants.push(HtmlParticipant {
+                            name: name.clone(),
+                            avatar: inline_avatar(data_dir, avatar.as_deref()),
+                        });
+                        participants.len() - 1
+                    })
+                }
+                // System messages and characters deleted since
+                (kind, _) => {
+                    let name = if kind == MessageKind::System {
+                        "System"
+                    } else {
+                        "Character"
+                    };
+                    *stand_ins.entry(name).or_insert_with(|| {
+                        participants.push(HtmlParticipant {
+                            name: name.to_string(),
+                            avatar: None,
+                        });
+                        participants.len() - 1
+                    })
+                }
+            };
+            messages.push(HtmlMessage {
+                kind,
+                author,
+                text: active_text(&row.get::<String, _>("messages"), row.get("message_index")),
+            });
+        }
+
+        html_chapters.push(HtmlChapter {
+            title: chapter.get("title"),
+            messages,
+        });
+    }
+
+    Ok(HtmlChat {
+        name: chat.get("name"),
+        participants,
+        chapters: html_chapters,
+    })
+}
+
+// Render a chat into one self-contained HTML page at `output_path`. Returns the number of
+// messages written.
+#[tauri::command]
+pub async fn export_chat_html(
+    app: AppHandle,
+    window: Window,
+    chat_id: String,
+    profile_id: String,
+    output_path: String,
+    options: Option<ChatHtmlOptions>,
+) -> Result<usize, AppError> {
+    verify_window_profile(&window, &profile_id)?;
+    let options = options.unwrap_or_default();
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data dir: {}", e))?;
+
+    let mut conn = open_connection(&app).await?;
+    let chat = load_chat(&mut conn, &data_dir, &chat_id, &profile_id).await;
+    let _ = conn.close().await;
+
+    let rendered = render_chat_html(&chat?, &options).map_err(AppError::validation)?;
+    fs::write(&output_path, rendered.html)
+        .map_err(|e| format!("Failed to write {}: {}", output_path, e))?;
+    Ok(rendered.messages)
+}
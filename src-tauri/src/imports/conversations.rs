@@ -0,0 +1,787 @@
+use serde::Deserialize;
+use serde_json::Value;
+use sqlx::sqlite::SqliteConnection;
+use sqlx::Connection;
+use std::collections::{HashMap, HashSet};
+use tauri::{AppHandle, Window};
+use uuid::Uuid;
+
+use crate::database::chat_copy::{chat_from_row, Chat, CHAT_COLUMNS};
+use crate::database::open_connection;
+use crate::error::AppError;
+use crate::windows::verify_window_profile;
+
+// Conversations exported from ChatGPT (Settings > Data controls > Export) and Claude (Settings >
+// Privacy > Export data). Both ship a conversations.json holding an array of conversations; a single
+// conversation object is accepted too. Each conversation becomes a chat with one chapter, user
+// turns as user messages and the assistant's as character messages without a character.
+// SillyTavern chat exports (JSONL: a header line, then one message per line) only reach this
+// module through `import_from_url` and are imported the same way.
+
+const DEFAULT_TITLE: &str = "Imported conversation";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Author {
+    User,
+    Character,
+    System,
+}
+
+impl Author {
+    fn message_type(self) -> &'static str {
+        match self {
+            Author::User => "user",
+            Author::Character => "character",
+            Author::System => "system",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct ImportedMessage {
+    author: Author,
+    text: String,
+    // Unix seconds or an ISO date, normalized by SQLite on insert
+    created_at: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+struct ImportedConversation {
+    title: String,
+    created_at: Option<String>,
+    messages: Vec<ImportedMessage>,
+}
+
+fn title_or_default(title: Option<String>) -> String {
+    title
+        .map(|title| title.trim().to_string())
+        .filter(|title| !title.is_empty())
+        .unwrap_or_else(|| DEFAULT_TITLE.to_string())
+}
+
+// conversations.json is an array, a conversation copied out of it is a single object
+fn conversation_values(json: &str) -> Result<Vec<Value>, AppError> {
+    match serde_json::from_str::<Value>(json) {
+        Ok(Value::Array(items)) => Ok(items),
+        Ok(item @ Value::Object(_)) => Ok(vec![item]),
+        Ok(_) => Err(AppError::validation(
+            "Expected a conversation or a list of conversations",
+        )),
+        Err(e) => Err(AppError::validation(format!("Invalid JSON: {}", e))),
+    }
+}
+
+#[derive(Deserialize)]
+struct OpenAiConversation {
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    create_time: Option<f64>,
+    mapping: HashMap<String, OpenAiNode>,
+    // Last message of the branch that was on screen
+    #[serde(default)]
+    current_node: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct OpenAiNode {
+    #[serde(default)]
+    message: Option<OpenAiMessage>,
+    #[serde(default)]
+    parent: Option<String>,
+    #[serde(default)]
+    children: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct OpenAiMessage {
+    author: OpenAiAuthor,
+    #[serde(default)]
+    create_time: Option<f64>,
+    #[serde(default)]
+    content: Option<OpenAiContent>,
+    #[serde(default)]
+    metadata: Value,
+}
+
+#[derive(Deserialize)]
+struct OpenAiAuthor {
+    role: String,
+}
+
+#[derive(Deserialize)]
+struct OpenAiContent {
+    // Text lives in string parts; other parts are images and files
+    #[serde(default)]
+    parts: Vec<Value>,
+}
+
+// Node ids of the main branch, root first: back from current_node when the export has one,
+// otherwise down from the root through the latest child of each node
+fn openai_main_branch(conversation: &OpenAiConversation) -> Vec<&str> {
+    let mapping = &conversation.mapping;
+    let mut seen = HashSet::new();
+
+    if let Some(current) = conversation
+        .current_node
+        .as_deref()
+        .filter(|id| mapping.contains_key(*id))
+    {
+        let mut branch = Vec::new();
+        let mut next = Some(current);
+        while let Some(id) = next.filter(|id| seen.insert(*id)) {
+            let Some(node) = mapping.get(id) else {
+                break;
+            };
+            branch.push(id);
+            next = node.parent.as_deref();
+        }
+        branch.reverse();
+        return branch;
+    }
+
+    let root = mapping
+        .iter()
+        .filter(|(_, node)| {
+            node.parent
+                .as_deref()
+                .is_none_or(|parent| !mapping.contains_key(parent))
+        })
+        .map(|(id, _)| id.as_str())
+        .min();
+    let mut branch = Vec::new();
+    let mut next = root;
+    while let Some(id) = next.filter(|id| seen.insert(*id)) {
+        let Some(node) = mapping.get(id) else {
+            break;
+        };
+        branch.push(id);
+        next = node.children.last().map(String::as_str);
+    }
+    branch
+}
+
+fn parse_openai_conversation(value: Value) -> Result<ImportedConversation, String> {
+    let conversation: OpenAiConversation =
+        serde_json::from_value(value).map_err(|e| format!("Not a ChatGPT conversation: {}", e))?;
+
+    let messages = openai_main_branch(&conversation)
+        .into_iter()
+        .filter_map(|id| conversation.mapping[id].message.as_ref())
+        .filter(|message| {
+            message.metadata.get("is_visually_hidden_from_conversation") != Some(&Value::Bool(true))
+        })
+        .filter_map(|message| {
+            let author = match message.author.role.as_str() {
+                "user" => Author::User,
+                "assistant" => Author::Character,
+                "system" => Author::System,
+                // Tool calls and their results
+                _ => return None,
+            };
+            let text = message
+                .content
+                .as_ref()?
+                .parts
+                .iter()
+                .filter_map(Value::as_str)
+                .filter(|part| !part.trim().is_empty())
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            (!text.is_empty()).then(|| ImportedMessage {
+                author,
+                text,
+                created_at: message.create_time.map(|time| time.to_string()),
+            })
+        })
+        .collect();
+
+    Ok(ImportedConversation {
+        title: title_or_default(conversation.title),
+        created_at: conversation.create_time.map(|time| time.to_string()),
+        messages,
+    })
+}
+
+#[derive(Deserialize)]
+struct ClaudeConversation {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    created_at: Option<String>,
+    chat_messages: Vec<ClaudeMessage>,
+}
+
+#[derive(Deserialize)]
+struct ClaudeMessage {
+    sender: String,
+    // Plain text of the turn; newer exports also split it into typed content blocks
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    content: Vec<Value>,
+    #[serde(default)]
+    created_at: Option<String>,
+}
+
+fn parse_anthropic_conversation(value: Value) -> Result<ImportedConversation, String> {
+    let conversation: ClaudeConversation =
+        serde_json::from_value(value).map_err(|e| format!("Not a Claude conversation: {}", e))?;
+
+    let messages = conversation
+        .chat_messages
+        .into_iter()
+        .filter_map(|message| {
+            let author = match message.sender.as_str() {
+                "human" => Author::User,
+                "assistant" => Author::Character,
+                _ => return None,
+            };
+            let blocks = message
+                .content
+                .iter()
+                .filter(|block| block.get("type").and_then(Value::as_str) == Some("text"))
+                .filter_map(|block| block.get("text").and_then(Value::as_str))
+                .filter(|text| !text.trim().is_empty())
+                .collect::<Vec<_>>();
+            // Tool use and thinking blocks are left out, the text blocks are what was shown
+            let text = if blocks.is_empty() {
+                message.text.trim().to_string()
+            } else {
+                blocks.join("\n\n")
+            };
+            (!text.is_empty()).then_some(ImportedMessage {
+                author,
+                text,
+                created_at: message.created_at,
+            })
+        })
+        .collect();
+
+    Ok(ImportedConversation {
+        title: title_or_default(conversation.name),
+        created_at: conversation.created_at,
+        messages,
+    })
+}
+
+#[derive(Deserialize)]
+struct SillyTavernLine {
+    #[serde(default)]
+    character_name: Option<String>,
+    #[serde(default)]
+    mes: Option<String>,
+    #[serde(default)]
+    is_user: bool,
+    // Narrator messages and messages hidden from the prompt
+    #[serde(default)]
+    is_system: bool,
+    #[serde(default)]
+    send_date: Option<Value>,
+}
+
+// A SillyTavern chat, as JSONL or as a JSON array of its lines. Only the shown swipe is imported.
+fn parse_sillytavern_chat(text: &str) -> Result<ImportedConversation, String> {
+    let values = match serde_json::from_str::<Value>(text) {
+        Ok(Value::Array(items)) => items,
+        _ => text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                serde_json::from_str::<Value>(line)
+                    .map_err(|e| format!("Line {}: invalid JSON: {}", index + 1, e))
+            })
+            .collect::<Result<_, _>>()?,
+    };
+
+    let mut title = None;
+    let mut messages = Vec::new();
+    for (index, value) in values.into_iter().enumerate() {
+        let line: SillyTavernLine = serde_json::from_value(value)
+            .map_err(|e| format!("Line {}: not a chat line: {}", index + 1, e))?;
+        let Some(text) = line.mes else {
+            // The header line names the character the chat was with
+            title = title.or(line.character_name);
+            continue;
+        };
+        let text = text.trim().to_string();
+        if text.is_empty() {
+            continue;
+        }
+        let author = if line.is_user {
+            Author::User
+        } else if line.is_system {
+            Author::System
+        } else {
+            Author::Character
+        };
+        messages.push(ImportedMessage {
+            author,
+            text,
+            // Usually a display date SQLite can't read, which falls back to the import time
+            created_at: line
+                .send_date
+                .and_then(|date| date.as_str().map(str::to_string)),
+        });
+    }
+
+    if messages.is_empty() {
+        return Err("The chat has no messages".to_string());
+    }
+    Ok(ImportedConversation {
+        title: title_or_default(title),
+        created_at: messages[0].created_at.clone(),
+        messages,
+    })
+}
+
+fn parse_conversations(
+    json: &str,
+    parse: fn(Value) -> Result<ImportedConversation, String>,
+) -> Result<Vec<ImportedConversation>, AppError> {
+    let mut conversations = Vec::new();
+    for (index, value) in conversation_values(json)?.into_iter().enumerate() {
+        let conversation = parse(value)
+            .map_err(|e| AppError::validation(format!("Conversation {}: {}", index + 1, e)))?;
+        // Conversations that were started and left empty
+        if !conversation.messages.is_empty() {
+            conversations.push(conversation);
+        }
+    }
+    if conversations.is_empty() {
+        return Err(AppError::validation("No conversation with messages found"));
+    }
+    Ok(conversations)
+}
+
+fn new_id() -> String {
+    Uuid::new_v4().to_string()
+}
+
+// Imported times in the ISO format the app writes, the import time when missing or unreadable
+const TIMESTAMP_SQL: &str = "COALESCE(strftime('%Y-%m-%dT%H:%M:%fZ', $1, 'auto'),
+    strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))";
+
+async fn insert_conversations(
+    conn: &mut SqliteConnection,
+    profile_id: &str,
+    conversations: &[ImportedConversation],
+) -> Result<Vec<Chat>, AppError> {
+    let mut tx = conn
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start import: {}", e))?;
+
+    sqlx::query("SELECT id FROM profiles WHERE id = $1")
+        .bind(profile_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to read profile {}: {}", profile_id, e))?
+        .ok_or_else(|| AppError::not_found(format!("Profile {} not found", profile_id)))?;
+
+    let mut chats = Vec::with_capacity(conversations.len());
+    for conversation in conversations {
+        let chat_id = new_id();
+        sqlx::query(&format!(
+            "INSERT INTO chats (id, profile_id, name, participants, user_character_settings,
+                created_at, updated_at)
+             VALUES ($2, $3, $4, '[]', '[]', {0}, {0})",
+            TIMESTAMP_SQL
+        ))
+        .bind(&conversation.created_at)
+        .bind(&chat_id)
+        .bind(profile_id)
+        .bind(&conversation.title)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to create chat '{}': {}", conversation.title, e))?;
+
+        let chapter_id = new_id();
+        sqlx::query(
+            "INSERT INTO chat_chapters (id, chat_id, title, sequence) VALUES ($1, $2, 'Chapter 1', 1)",
+        )
+        .bind(&chapter_id)
+        .bind(&chat_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to create chapter: {}", e))?;
+
+        for (index, message) in conversation.messages.iter().enumerate() {
+            let variants = serde_json::to_string(&[&message.text])
+                .map_err(|e| format!("Failed to serialize message: {}", e))?;
+            sqlx::query(&format!(
+                "INSERT INTO chat_messages (id, chat_id, chapter_id, type, position, messages,
+                    message_index, created_at, updated_at)
+                 VALUES ($2, $3, $4, $5, $6, $7, 0, {0}, {0})",
+                TIMESTAMP_SQL
+            ))
+            .bind(&message.created_at)
+            .bind(new_id())
+            .bind(&chat_id)
+            .bind(&chapter_id)
+            .bind(message.author.message_type())
+            .bind((index as i64 + 1) * 100)
+            .bind(variants)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to import a message: {}", e))?;
+        }
+
+        let row = sqlx::query(&format!(
+            "UPDATE chats SET active_chapter_id = $1 WHERE id = $2 RETURNING {}",
+            CHAT_COLUMNS
+        ))
+        .bind(&chapter_id)
+        .bind(&chat_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to read imported chat: {}", e))?;
+        chats.push(chat_from_row(&row));
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit import: {}", e))?;
+    Ok(chats)
+}
+
+async fn import_conversations(
+    app: &AppHandle,
+    profile_id: &str,
+    conversations: Vec<ImportedConversation>,
+) -> Result<Vec<Chat>, AppError> {
+    let mut conn = open_connection(app).await?;
+    let result = insert_conversations(&mut conn, profile_id, &conversations).await;
+    let _ = conn.close().await;
+    result
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ChatExportFormat {
+    OpenAi,
+    Anthropic,
+    SillyTavern,
+}
+
+/// Parse a chat export and write it as new chats of the profile, in one transaction
+pub(crate) async fn import_chat_export(
+    conn: &mut SqliteConnection,
+    profile_id: &str,
+    format: ChatExportFormat,
+    text: &str,
+) -> Result<Vec<Chat>, AppError> {
+    let conversations = match format {
+        ChatExportFormat::OpenAi => parse_conversations(text, parse_openai_conversation)?,
+        ChatExportFormat::Anthropic => parse_conversations(text, parse_anthropic_conversation)?,
+        ChatExportFormat::SillyTavern => {
+            vec![parse_sillytavern_chat(text).map_err(AppError::validation)?]
+        }
+    };
+    insert_conversations(conn, profile_id, &conversations).await
+}
+
+/// Import a ChatGPT export (conversations.json, or one conversation of it) into new chats of the
+/// profile, following the branch that was last shown where a conversation was edited or
+/// regenerated. Everything is written in one transaction. Returns the new chats.
+#[tauri::command]
+pub async fn import_openai_conversation(
+    app: AppHandle,
+    window: Window,
+    profile_id: String,
+    json: String,
+) -> Result<Vec<Chat>, AppError> {
+    verify_window_profile(&window, &profile_id)?;
+    let conversations = parse_conversations(&json, parse_openai_conversation)?;
+    import_conversations(&app, &profile_id, conversations).await
+}
+
+/// Import a Claude export (conversations.json, or one conversation of it) into new chats of the
+/// profile. Everything is written in one transaction. Returns the new chats.
+#[tauri::command]
+pub async fn import_anthropic_conversation(
+    app: AppHandle,
+    window: Window,
+    profile_id: String,
+    json: String,
+) -> Result<Vec<Chat>, AppError> {
+    verify_window_profile(&window, &profile_id)?;
+    let conversations = parse_conversations(&json, parse_anthropic_conversation)?;
+    import_conversations(&app, &profile_id, conversations).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_db::temp_database;
+    use crate::error::ErrorCode;
+    use serde_json::json;
+    use sqlx::Row;
+
+    fn node(id: &str, parent: Option<&str>, children: &[&str], role: &str, text: &str) -> Value {
+        json!({
+            "id": id,
+            "parent": parent,
+            "children": children,
+            "message": {
+                "author": { "role": role },
+                "create_time": 1714560000.5,
+                "content": { "content_type": "text", "parts": [text] },
+                "metadata": {},
+            },
+        })
+    }
+
+    // A regenerated answer: a1 was replaced by a2, and the user went on from a2
+    fn chatgpt_export(current_node: Option<&str>) -> String {
+        let root = json!({ "id": "root", "parent": null, "children": ["sys"], "message": null });
+        let mut hidden = node("sys", Some("root"), &["u1"], "system", "You are ChatGPT");
+        hidden["message"]["metadata"]["is_visually_hidden_from_conversation"] = json!(true);
+        json!([{
+            "title": "Trip ideas",
+            "create_time": 1714560000.0,
+            "current_node": current_node,
+            "mapping": {
+                "root": root,
+                "sys": hidden,
+                "u1": node("u1", Some("sys"), &["a1", "a2"], "user", "Where should I go?"),
+                "a1": node("a1", Some("u1"), &[], "assistant", "Paris."),
+                "a2": node("a2", Some("u1"), &["u2"], "assistant", "Try Lisbon."),
+                "u2": node("u2", Some("a2"), &["t1"], "user", "Why Lisbon?"),
+                "t1": node("t1", Some("u2"), &["a3"], "tool", "search results"),
+                "a3": node("a3", Some("t1"), &[], "assistant", "Food and light."),
+            },
+        }])
+        .to_string()
+    }
+
+    fn texts(conversation: &ImportedConversation) -> Vec<(Author, &str)> {
+        conversation
+            .messages
+            .iter()
+            .map(|message| (message.author, message.text.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn follows_the_main_chatgpt_branch() {
+        let expected = vec![
+            (Author::User, "Where should I go?"),
+            (Author::Character, "Try Lisbon."),
+            (Author::User, "Why Lisbon?"),
+            (Author::Character, "Food and light."),
+        ];
+        for current_node in [Some("a3"), None] {
+            let conversations =
+                parse_conversations(&chatgpt_export(current_node), parse_openai_conversation)
+                    .unwrap();
+            assert_eq!(conversations.len(), 1);
+            assert_eq!(conversations[0].title, "Trip ideas");
+            assert_eq!(texts(&conversations[0]), expected);
+        }
+
+        // A branch that was left for another one stays out when it isn't the current node
+        let other =
+            parse_conversations(&chatgpt_export(Some("a1")), parse_openai_conversation).unwrap();
+        assert_eq!(
+            texts(&other[0]),
+            vec![
+                (Author::User, "Where should I go?"),
+                (Author::Character, "Paris.")
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_claude_text_blocks() {
+        let export = json!({
+            "uuid": "c1",
+            "name": "",
+            "created_at": "2024-05-01T12:00:00.123456Z",
+            "chat_messages": [
+                { "sender": "human", "text": "Hello", "content": [], "created_at": "2024-05-01T12:00:01Z" },
+                {
+                    "sender": "assistant",
+                    "text": "ignored when there are blocks",
+                    "content": [
+                        { "type": "thinking", "thinking": "hmm" },
+                        { "type": "text", "text": "Hi there." },
+                        { "type": "tool_use", "name": "search" },
+                        { "type": "text", "text": "How can I help?" },
+                    ],
+                },
+                { "sender": "human", "text": "  ", "content": [] },
+            ],
+        })
+        .to_string();
+
+        let conversations = parse_conversations(&export, parse_anthropic_conversation).unwrap();
+        assert_eq!(conversations[0].title, DEFAULT_TITLE);
+        assert_eq!(
+            texts(&conversations[0]),
+            vec![
+                (Author::User, "Hello"),
+                (Author::Character, "Hi there.\n\nHow can I help?")
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_other_files() {
+        for (json, parse) in [
+            (
+                "not json",
+                parse_openai_conversation as fn(Value) -> Result<ImportedConversation, String>,
+            ),
+            ("[]", parse_anthropic_conversation),
+            (
+                r#"[{"name": "x", "chat_messages": []}]"#,
+                parse_anthropic_conversation,
+            ),
+            (
+                r#"[{"title": "claude?", "chat_messages": []}]"#,
+                parse_openai_conversation,
+            ),
+        ] {
+            let error = parse_conversations(json, parse).unwrap_err();
+            assert_eq!(error.code, ErrorCode::Validation, "{}", json);
+        }
+    }
+
+    #[test]
+    fn reads_sillytavern_chats() {
+        let jsonl = [
+            json!({ "user_name": "You", "character_name": "Seraphina", "create_date": "2024-5-1 @10h 40m" }),
+            json!({ "name": "Seraphina", "is_user": false, "mes": "Welcome, traveler.", "send_date": "2024-05-01T10:40:00Z", "swipes": ["Welcome, traveler.", "Hello."] }),
+            json!({ "name": "You", "is_user": true, "mes": " Thanks. ", "send_date": "May 1, 2024 10:41am" }),
+            json!({ "name": "Narrator", "is_user": false, "is_system": true, "mes": "Night falls." }),
+            json!({ "name": "You", "is_user": true, "mes": "" }),
+        ]
+        .map(|line| line.to_string())
+        .join("\n");
+
+        let conversation = parse_sillytavern_chat(&jsonl).unwrap();
+        assert_eq!(conversation.title, "Seraphina");
+        assert_eq!(
+            conversation.created_at.as_deref(),
+            Some("2024-05-01T10:40:00Z")
+        );
+        assert_eq!(
+            texts(&conversation),
+            vec![
+                (Author::Character, "Welcome, traveler."),
+                (Author::User, "Thanks."),
+                (Author::System, "Night falls."),
+            ]
+        );
+
+        // The same lines as a JSON array, without a header
+        let array = format!("[{}]", jsonl.lines().skip(1).collect::<Vec<_>>().join(","));
+        let conversation = parse_sillytavern_chat(&array).unwrap();
+        assert_eq!(conversation.title, DEFAULT_TITLE);
+        assert_eq!(conversation.messages.len(), 3);
+
+        assert!(parse_sillytavern_chat(r#"{"user_name": "You"}"#).is_err());
+        assert!(parse_sillytavern_chat("{\"mes\": \"Hi\"}\nnot json").is_err());
+    }
+
+    #[test]
+    fn imports_chat_exports_by_format() {
+        tauri::async_runtime::block_on(async {
+            let mut conn = temp_database("chat-export-import").await;
+            sqlx::query("INSERT INTO profiles (id, name) VALUES ('p1', 'Profile')")
+                .execute(&mut conn)
+                .await
+                .unwrap();
+
+            let jsonl = format!(
+                "{}\n{}",
+                json!({ "user_name": "You", "character_name": "Seraphina" }),
+                json!({ "name": "You", "is_user": true, "mes": "Hello" })
+            );
+            let chats = import_chat_export(&mut conn, "p1", ChatExportFormat::SillyTavern, &jsonl)
+                .await
+                .unwrap();
+            assert_eq!(chats.len(), 1);
+            assert_eq!(chats[0].name, "Seraphina");
+
+            let error = import_chat_export(
+                &mut conn,
+                "p1",
+                ChatExportFormat::Anthropic,
+                &chatgpt_export(None),
+            )
+            .await
+            .unwrap_err();
+            assert_eq!(error.code, ErrorCode::Validation);
+        });
+    }
+
+    #[test]
+    fn imports_into_new_chats() {
+        tauri::async_runtime::block_on(async {
+            let mut conn = temp_database("conversation-import").await;
+            sqlx::query("INSERT INTO profiles (id, name) VALUES ('p1', 'Profile')")
+                .execute(&mut conn)
+                .await
+                .unwrap();
+
+            let conversations =
+                parse_conversations(&chatgpt_export(Some("a3")), parse_openai_conversation)
+                    .unwrap();
+            let missing = insert_conversations(&mut conn, "nobody", &conversations)
+                .await
+                .unwrap_err();
+            assert_eq!(missing.code, ErrorCode::NotFound);
+
+            let chats = insert_conversations(&mut conn, "p1", &conversations)
+                .await
+                .unwrap();
+            assert_eq!(chats.len(), 1);
+            assert_eq!(chats[0].name, "Trip ideas");
+            assert_eq!(chats[0].created_at, "2024-05-01T10:40:00.000Z");
+            assert!(chats[0].active_chapter_id.is_some());
+
+            let rows = sqlx::query(
+                "SELECT type, position, messages, chapter_id, created_at FROM chat_messages
+                 WHERE chat_id = $1 ORDER BY position",
+            )
+            .bind(&chats[0].id)
+            .fetch_all(&mut conn)
+            .await
+            .unwrap();
+            let messages: Vec<(String, i64, String)> = rows
+                .iter()
+                .map(|row| (row.get("type"), row.get("position"), row.get("messages")))
+                .collect();
+            assert_eq!(
+                messages,
+                vec![
+                    (
+                        "user".to_string(),
+                        100,
+                        r#"["Where should I go?"]"#.to_string()
+                    ),
+                    (
+                        "character".to_string(),
+                        200,
+                        r#"["Try Lisbon."]"#.to_string()
+                    ),
+                    ("user".to_string(), 300, r#"["Why Lisbon?"]"#.to_string()),
+                    (
+                        "character".to_string(),
+                        400,
+                        r#"["Food and light."]"#.to_string()
+                    ),
+                ]
+            );
+            assert!(rows.iter().all(
+                |row| row.get::<Option<String>, _>("chapter_id") == chats[0].active_chapter_id
+            ));
+            assert_eq!(
+                rows[0].get::<String, _>("created_at"),
+                "2024-05-01T10:40:00.500Z"
+            );
+        });
+    }
+}
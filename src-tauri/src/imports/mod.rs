@@ -0,0 +1,643 @@
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use serde::Serialize;
+use sqlx::sqlite::SqliteConnection;
+use sqlx::{Connection, Row};
+use std::fmt;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Window};
+use tauri_plugin_http::reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use tauri_plugin_http::reqwest::{self, redirect};
+
+use crate::database::chat_copy::Chat;
+use crate::database::open_connection;
+use crate::error::{AppError, ErrorCode};
+use crate::windows::verify_window_profile;
+use conversations::ChatExportFormat;
+
+pub mod conversations;
+pub mod transcript;
+pub mod watch;
+
+// Hard limits for remote imports
+const MAX_DOWNLOAD_BYTES: usize = 20 * 1024 * 1024;
+const MAX_REDIRECTS: usize = 5;
+const REQUEST_TIMEOUT_SECS: u64 = 30;
+
+const PNG_SIGNATURE: &[u8] = &[0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+// Content types we are willing to download
+const ALLOWED_CONTENT_TYPES: &[&str] = &[
+    "image/png",
+    "application/json",
+    "application/jsonl",
+    "application/x-ndjson",
+    "application/octet-stream",
+    "text/json",
+    "text/plain",
+];
+
+// Kind of resource detected at the URL
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportKind {
+    CharacterPng,
+    CharacterJson,
+    LorebookJson,
+    OpenaiChat,
+    AnthropicChat,
+    SillytavernChat,
+}
+
+impl ImportKind {
+    fn chat_format(self) -> Option<ChatExportFormat> {
+        match self {
+            ImportKind::OpenaiChat => Some(ChatExportFormat::OpenAi),
+            ImportKind::AnthropicChat => Some(ChatExportFormat::Anthropic),
+            ImportKind::SillytavernChat => Some(ChatExportFormat::SillyTavern),
+            _ => None,
+        }
+    }
+}
+
+// What a URL import produced. Chat exports are imported here; cards and lorebooks are handed back
+// so the frontend importers run them through the same Zod validation as a file picked from disk.
+#[derive(Debug, Serialize)]
+pub struct UrlImportResult {
+    pub kind: ImportKind,
+    pub final_url: String,
+    pub content_type: Option<String>,
+    pub size: usize,
+    // Cards and lorebooks only: base64 for PNG cards, raw UTF-8 text for everything else
+    pub data: Option<String>,
+    // Chat exports only: the chats created from it
+    pub chats: Vec<Chat>,
+}
+
+// Error surfaced to the UI, split so network failures can be told apart from bad files
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", content = "message", rename_all = "snake_case")]
+pub enum UrlImportError {
+    Network(String),
+    Rejected(String),
+    Parse(String),
+    // The file was read but creating the entity failed
+    Import(String),
+}
+
+impl From<AppError> for UrlImportError {
+    fn from(error: AppError) -> Self {
+        match error.code {
+            ErrorCode::Validation => UrlImportError::Parse(error.message),
+            _ => UrlImportError::Import(error.message),
+        }
+    }
+}
+
+// Set by the resolver and the redirect policy when a host points into the local network, found
+// again in the request error so it's reported as rejected instead of as a network failure
+#[derive(Debug)]
+struct BlockedHost(String);
+
+impl fmt::Display for BlockedHost {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} is a local or private address and can't be imported from",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for BlockedHost {}
+
+// Resolves names like the system resolver, leaving out local and private addresses, so a public
+// name can't point the download (or one of its redirects) at the user's own network
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(resolve_public(name.as_str().to_string()))
+    }
+}
+
+async fn resolve_public(host: String) -> Result<Addrs, Box<dyn std::error::Error + Send + Sync>> {
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), 0))
+        .await?
+        .filter(|addr| !is_local_address(addr.ip()))
+        .collect();
+    if addrs.is_empty() {
+        return Err(Box::new(BlockedHost(host)));
+    }
+    Ok(Box::new(addrs.into_iter()))
+}
+
+/// Download a character card, lorebook or chat export from a URL and import it into the profile.
+/// Chat exports (ChatGPT, Claude, SillyTavern) are written as new chats here. Cards and lorebooks
+/// come back as data for the frontend importers, which own their validation. HTTPS is required
+/// unless the profile sets `system.allowInsecureUrlImports`; local and private hosts are always
+/// refused, including as redirect targets.
+#[tauri::command]
+pub async fn import_from_url(
+    app: AppHandle,
+    window: Window,
+    profile_id: String,
+    url: String,
+    kind_hint: Option<String>,
+) -> Result<UrlImportResult, UrlImportError> {
+    verify_window_profile(&window, &profile_id)?;
+
+    let parsed = reqwest::Url::parse(url.trim())
+        .map_err(|e| UrlImportError::Rejected(format!("Invalid URL: {}", e)))?;
+
+    let mut conn = open_connection(&app)
+        .await
+        .map_err(UrlImportError::Import)?;
+    let result = import_resource(&mut conn, &profile_id, parsed, kind_hint.as_deref()).await;
+    let _ = conn.close().await;
+    result
+}
+
+async fn import_resource(
+    conn: &mut SqliteConnection,
+    profile_id: &str,
+    url: reqwest::Url,
+    kind_hint: Option<&str>,
+) -> Result<UrlImportResult, UrlImportError> {
+    let allow_insecure = allows_insecure_imports(conn, profile_id).await?;
+    let (final_url, content_type, body) = download(url, allow_insecure).await?;
+
+    let kind = sniff_kind(&body, kind_hint)?;
+    let size = body.len();
+    let (data, chats) = match kind.chat_format() {
+        Some(format) => {
+            let text = std::str::from_utf8(&body)
+                .map_err(|e| UrlImportError::Parse(format!("File is not valid UTF-8: {}", e)))?
+                .trim_start_matches('\u{feff}');
+            let chats = conversations::import_chat_export(conn, profile_id, format, text).await?;
+            (None, chats)
+        }
+        None if kind == ImportKind::CharacterPng => (Some(BASE64.encode(&body)), Vec::new()),
+        None => {
+            let text = String::from_utf8(body)
+                .map_err(|e| UrlImportError::Parse(format!("File is not valid UTF-8: {}", e)))?;
+            (Some(text), Vec::new())
+        }
+    };
+
+    Ok(UrlImportResult {
+        kind,
+        final_url,
+        content_type,
+        size,
+        data,
+        chats,
+    })
+}
+
+async fn allows_insecure_imports(
+    conn: &mut SqliteConnection,
+    profile_id: &str,
+) -> Result<bool, AppError> {
+    let row = sqlx::query(
+        "SELECT CASE WHEN json_valid(settings)
+            THEN json_extract(settings, '$.system.allowInsecureUrlImports') END AS allow_insecure
+         FROM profiles WHERE id = $1",
+    )
+    .bind(profile_id)
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| format!("Failed to read profile {}: {}", profile_id, e))?
+    .ok_or_else(|| AppError::not_found(format!("Profile {} not found", profile_id)))?;
+    Ok(row.get::<Option<i64>, _>("allow_insecure") == Some(1))
+}
+
+// Fetch the URL within the size, content type and redirect limits. Returns the final URL, the
+// content type and the body.
+async fn download(
+    url: reqwest::Url,
+    allow_insecure: bool,
+) -> Result<(String, Option<String>, Vec<u8>), UrlImportError> {
+    check_scheme(&url, allow_insecure)?;
+    check_host(&url)?;
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .dns_resolver(Arc::new(PublicResolver))
+        .redirect(redirect::Policy::custom(move |attempt| {
+            if attempt.previous().len() >= MAX_REDIRECTS {
+                attempt.error(format!("Too many redirects (limit {})", MAX_REDIRECTS))
+            } else if !allow_insecure && attempt.url().scheme() != "https" {
+                attempt.error("Redirect to a non-HTTPS URL was blocked")
+            } else if let Err(blocked) = local_host(attempt.url()) {
+                attempt.error(blocked)
+            } else {
+                attempt.follow()
+            }
+        }))
+        .build()
+        .map_err(|e| UrlImportError::Network(format!("Failed to create HTTP client: {}", e)))?;
+
+    let mut response = client.get(url).send().await.map_err(request_error)?;
+
+    if !response.status().is_success() {
+        return Err(UrlImportError::Network(format!(
+            "Server responded with status {}",
+            response.status()
+        )));
+    }
+
+    let final_url = response.url().to_string();
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.split(';').next().unwrap_or("").trim().to_lowercase());
+
+    // A missing content type is refused too, the allowlist is what keeps arbitrary pages out
+    match &content_type {
+        Some(content_type) if ALLOWED_CONTENT_TYPES.contains(&content_type.as_str()) => {}
+        Some(content_type) => {
+            return Err(UrlImportError::Rejected(format!(
+                "Unsupported content type: {}",
+                content_type
+            )))
+        }
+        None => {
+            return Err(UrlImportError::Rejected(
+                "The server did not send a content type".to_string(),
+            ))
+        }
+    }
+
+    if let Some(length) = response.content_length() {
+        if length as usize > MAX_DOWNLOAD_BYTES {
+            return Err(UrlImportError::Rejected(format!(
+                "File is too large ({} bytes, limit {})",
+                length, MAX_DOWNLOAD_BYTES
+            )));
+        }
+    }
+
+    // Read the body in chunks so a missing or lying Content-Length can't exceed the limit
+    let mut body: Vec<u8> = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| UrlImportError::Network(format!("Failed to read response: {}", e)))?
+    {
+        if body.len() + chunk.len() > MAX_DOWNLOAD_BYTES {
+            return Err(UrlImportError::Rejected(format!(
+                "File is too large (limit {} bytes)",
+                MAX_DOWNLOAD_BYTES
+            )));
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    Ok((final_url, content_type, body))
+}
+
+// A blocked host anywhere in the chain is a rejection, anything else a network failure
+fn request_error(error: reqwest::Error) -> UrlImportError {
+    let mut source: Option<&(dyn std::error::Error + 'static)> = Some(&error);
+    while let Some(current) = source {
+        if let Some(blocked) = current.downcast_ref::<BlockedHost>() {
+            return UrlImportError::Rejected(blocked.to_string());
+        }
+        source = current.source();
+    }
+    UrlImportError::Network(format!("Request failed: {}", error))
+}
+
+fn check_scheme(url: &reqwest::Url, allow_insecure: bool) -> Result<(), UrlImportError> {
+    match url.scheme() {
+        "https" => Ok(()),
+        "http" if allow_insecure => Ok(()),
+        "http" => Err(UrlImportError::Rejected(
+            "Only HTTPS URLs are allowed for imports".to_string(),
+        )),
+        scheme => Err(UrlImportError::Rejected(format!(
+            "Unsupported URL scheme: {}",
+            scheme
+        ))),
+    }
+}
+
+fn check_host(url: &reqwest::Url) -> Result<(), UrlImportError> {
+    local_host(url).map_err(|blocked| UrlImportError::Rejected(blocked.to_string()))
+}
+
+// Names and IP literals that are local before any lookup. Other names go through PublicResolver.
+fn local_host(url: &reqwest::Url) -> Result<(), BlockedHost> {
+    let Some(host) = url.host_str() else {
+        return Err(BlockedHost(url.to_string()));
+    };
+    let name = host.trim_end_matches('.').to_ascii_lowercase();
+    // IPv6 hosts come back bracketed ("[::1]")
+    let is_local = name == "localhost"
+        || name.ends_with(".localhost")
+        || name
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<IpAddr>()
+            .is_ok_and(is_local_address);
+    if is_local {
+        return Err(BlockedHost(host.to_string()));
+    }
+    Ok(())
+}
+
+// Loopback, private, link-local, shared (CGNAT) and unspecified addresses, IPv4-mapped ones included
+fn is_local_address(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || (ip.octets()[0] == 100 && (ip.octets()[1] & 0xc0) == 64)
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_local_address(IpAddr::V4(ip)),
+            None => {
+                let first = ip.segments()[0];
+                ip.is_loopback()
+                    || ip.is_unspecified()
+                    // Unique local (fc00::/7) and link-local (fe80::/10)
+                    || (first & 0xfe00) == 0xfc00
+                    || (first & 0xffc0) == 0xfe80
+            }
+        },
+    }
+}
+
+// Detect the resource kind from its bytes, using the hint only when the content is ambiguous
+fn sniff_kind(body: &[u8], kind_hint: Option<&str>) -> Result<ImportKind, UrlImportError> {
+    if body.starts_with(PNG_SIGNATURE) {
+        if !has_card_metadata(body) {
+            return Err(UrlImportError::Parse(
+                "PNG does not contain character card metadata".to_string(),
+            ));
+        }
+        return Ok(ImportKind::CharacterPng);
+    }
+
+    let text = std::str::from_utf8(body)
+        .map_err(|_| UrlImportError::Parse("Unrecognized binary file".to_string()))?
+        .trim_start_matches('\u{feff}')
+        .trim();
+
+    if let Ok(json) = serde_json::from_str::<serde_json::Value>(text) {
+        if let Some(kind) = sniff_json(&json).or_else(|| sniff_chat(&json)) {
+            return Ok(kind);
+        }
+    } else if is_jsonl_chat(text) {
+        return Ok(ImportKind::SillytavernChat);
+    } else {
+        return Err(UrlImportError::Parse(
+            "File is neither a PNG card nor valid JSON".to_string(),
+        ));
+    }
+
+    match kind_hint {
+        Some("character") => Ok(ImportKind::CharacterJson),
+        Some("lorebook") => Ok(ImportKind::LorebookJson),
+        _ => Err(UrlImportError::Parse(
+            "Could not determine what kind of file this is".to_string(),
+        )),
+    }
+}
+
+fn sniff_json(json: &serde_json::Value) -> Option<ImportKind> {
+    match json.get("export_type").and_then(|value| value.as_str()) {
+        Some("character") => return Some(ImportKind::CharacterJson),
+        Some("lorebook") => return Some(ImportKind::LorebookJson),
+        _ => {}
+    }
+
+    match json.get("spec").and_then(|value| value.as_str()) {
+        Some("chara_card_v2") | Some("chara_card_v3") => return Some(ImportKind::CharacterJson),
+        Some("lorebook_v2") | Some("lorebook_v3") => return Some(ImportKind::LorebookJson),
+        _ => {}
+    }
+
+    // SillyTavern world info keeps its entries as an object keyed by uid
+    if json
+        .get("entries")
+        .is_some_and(|entries| entries.is_object() || entries.is_array())
+    {
+        return Some(ImportKind::LorebookJson);
+    }
+
+    // V1 cards have no spec field but always carry these
+    if json.get("first_mes").is_some() && json.get("name").is_some() {
+        return Some(ImportKind::CharacterJson);
+    }
+
+    None
+}
+
+// ChatGPT and Claude exports are a list of conversations or a single one, SillyTavern chats can
+// also come as a JSON array of their lines
+fn sniff_chat(json: &serde_json::Value) -> Option<ImportKind> {
+    let first = json.as_array().map_or(Some(json), |items| items.first())?;
+    if first
+        .get("mapping")
+        .is_some_and(|mapping| mapping.is_object())
+    {
+        Some(ImportKind::OpenaiChat)
+    } else if first
+        .get("chat_messages")
+        .is_some_and(|messages| messages.is_array())
+    {
+        Some(ImportKind::AnthropicChat)
+    } else if json.is_array() && is_chat_line(first) {
+        Some(ImportKind::SillytavernChat)
+    } else {
+        None
+    }
+}
+
+// SillyTavern chat exports are JSONL: a header line followed by one message per line
+fn is_jsonl_chat(text: &str) -> bool {
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .take(2)
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .any(|line| line.get("user_name").is_some() || is_chat_line(&line))
+}
+
+fn is_chat_line(value: &serde_json::Value) -> bool {
+    value.get("mes").is_some() && value.get("is_user").is_some()
+}
+
+// Card metadata lives in a tEXt chunk keyed "chara" (V2) or "ccv3" (V3)
+fn has_card_metadata(body: &[u8]) -> bool {
+    let mut offset = PNG_SIGNATURE.len();
+    while offset + 8 <= body.len() {
+        let length = u32::from_be_bytes([
+            body[offset],
+            body[offset + 1],
+            body[offset + 2],
+            body[offset + 3],
+        ]) as usize;
+        let chunk_type = &body[offset + 4..offset + 8];
+        let data_start = offset + 8;
+        let data_end = data_start.saturating_add(length);
+        if data_end > body.len() {
+            return false;
+        }
+
+        if chunk_type == b"tEXt" {
+            let data = &body[data_start..data_end];
+            let keyword = data.split(|byte| *byte == 0).next().unwrap_or_default();
+            if keyword == b"chara" || keyword == b"ccv3" {
+                return true;
+            }
+        }
+
+        if chunk_type == b"IEND" {
+            return false;
+        }
+
+        // Skip data and the 4-byte CRC
+        offset = data_end + 4;
+    }
+    false
+}
+
+// Parse a JSON or YAML config file into JSON so the frontend can validate it with its schemas
+#[tauri::command]
+pub fn parse_config_file(
+    file_contents: String,
+    format: String,
+) -> Result<serde_json::Value, AppError> {
+    match format.to_lowercase().as_str() {
+        "json" => serde_json::from_str(&file_contents)
+            .map_err(|e| AppError::validation(format!("Failed to parse JSON: {}", e))),
+        "yaml" | "yml" => serde_yaml::from_str(&file_contents)
+            .map_err(|e| AppError::validation(format!("Failed to parse YAML: {}", e))),
+        other => Err(AppError::validation(format!(
+            "Unsupported config format: {}",
+            other
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_db::temp_database;
+    use serde_json::json;
+
+    fn url(url: &str) -> reqwest::Url {
+        reqwest::Url::parse(url).unwrap()
+    }
+
+    #[test]
+    fn refuses_local_and_private_hosts() {
+        for blocked in [
+            "https://localhost/card.png",
+            "https://app.localhost/card.png",
+            "https://LOCALHOST./card.png",
+            "https://127.0.0.1/card.png",
+            "https://10.0.0.8/card.png",
+            "https://172.16.4.1/card.png",
+            "https://192.168.1.20/card.png",
+            "https://169.254.169.254/latest/meta-data",
+            "https://100.64.0.1/card.png",
+            "https://0.0.0.0/card.png",
+            "https://[::1]/card.png",
+            "https://[fd00::1]/card.png",
+            "https://[fe80::1]/card.png",
+            "https://[::ffff:192.168.1.20]/card.png",
+        ] {
+            assert!(local_host(&url(blocked)).is_err(), "{}", blocked);
+        }
+
+        for allowed in [
+            "https://example.com/card.png",
+            "https://8.8.8.8/card.png",
+            "https://100.128.0.1/card.png",
+            "https://[2606:4700::1111]/card.png",
+        ] {
+            assert!(local_host(&url(allowed)).is_ok(), "{}", allowed);
+        }
+    }
+
+    #[test]
+    fn reports_blocked_hosts_as_rejected() {
+        match check_host(&url("https://192.168.1.20/card.png")) {
+            Err(UrlImportError::Rejected(message)) => assert!(message.contains("192.168.1.20")),
+            other => panic!("expected a rejection, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn detects_chat_exports() {
+        let sniff = |value: serde_json::Value| sniff_kind(value.to_string().as_bytes(), None).ok();
+
+        assert_eq!(
+            sniff(json!([{ "title": "Trip", "mapping": {} }])),
+            Some(ImportKind::OpenaiChat)
+        );
+        assert_eq!(
+            sniff(json!({ "name": "Hello", "chat_messages": [] })),
+            Some(ImportKind::AnthropicChat)
+        );
+        assert_eq!(
+            sniff(json!([{ "name": "You", "is_user": true, "mes": "Hi" }])),
+            Some(ImportKind::SillytavernChat)
+        );
+        let jsonl = format!(
+            "{}\n{}",
+            json!({ "user_name": "You", "character_name": "Seraphina" }),
+            json!({ "name": "You", "is_user": true, "mes": "Hi" })
+        );
+        assert_eq!(
+            sniff_kind(jsonl.as_bytes(), None).ok(),
+            Some(ImportKind::SillytavernChat)
+        );
+
+        // Cards and lorebooks are still told apart first
+        assert_eq!(
+            sniff(json!({ "spec": "chara_card_v2", "data": {} })),
+            Some(ImportKind::CharacterJson)
+        );
+        assert_eq!(
+            sniff(json!({ "entries": {} })),
+            Some(ImportKind::LorebookJson)
+        );
+        assert_eq!(sniff(json!([{ "unrelated": true }])), None);
+    }
+
+    #[test]
+    fn reads_the_insecure_import_setting_of_the_profile() {
+        tauri::async_runtime::block_on(async {
+            let mut conn = temp_database("url-import-settings").await;
+            sqlx::query(
+                "INSERT INTO profiles (id, name, settings) VALUES
+                    ('strict', 'Strict', '{\"system\": {\"allowInsecureUrlImports\": false}}'),
+                    ('lenient', 'Lenient', '{\"system\": {\"allowInsecureUrlImports\": true}}'),
+                    ('broken', 'Broken', 'not json')",
+            )
+            .execute(&mut conn)
+            .await
+            .unwrap();
+
+            assert!(!allows_insecure_imports(&mut conn, "strict").await.unwrap());
+            assert!(allows_insecure_imports(&mut conn, "lenient").await.unwrap());
+            assert!(!allows_insecure_imports(&mut conn, "broken").await.unwrap());
+            assert_eq!(
+                allows_insecure_imports(&mut conn, "nobody")
+                    .await
+                    .unwrap_err()
+                    .code,
+                ErrorCode::NotFound
+            );
+        });
+    }
+}
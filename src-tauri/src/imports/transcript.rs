@@ -0,0 +1,895 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::sqlite::SqliteConnection;
+use sqlx::{Connection, Row};
+use std::collections::HashMap;
+use std::path::Path;
+use tauri::{AppHandle, Window};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, BufReader};
+use uuid::Uuid;
+
+use crate::database::chat_copy::{chat_from_row, Chat, CHAT_COLUMNS};
+use crate::database::open_connection;
+use crate::error::AppError;
+use crate::windows::verify_window_profile;
+
+// Roleplays kept as plain text or Markdown, one "Name: line" per turn. Lines that don't start with a
+// speaker continue the message above; text before the first speaker becomes a narration message.
+// The file is read line by line and written as it goes, so long logs never sit in memory whole.
+
+const DEFAULT_SPEAKER_PATTERN: &str = r"^(\w[\w ]{0,40}):\s";
+const DEFAULT_CHAT_NAME: &str = "Imported transcript";
+// Lets the placeholder characters made for unknown speakers be found and cleaned up later
+const PLACEHOLDER_TAGS: &str = r#"["imported"]"#;
+
+// "**Name:** line" and "**Name**: line", rewritten to "Name: line" before the speaker pattern runs
+static MARKDOWN_SPEAKER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*(?:\*\*|__)([^*_:\n]{1,41}?)(?::\s*(?:\*\*|__)|(?:\*\*|__)\s*:)\s*").unwrap()
+});
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+pub struct TextImportOptions {
+    // The speaker is the `speaker` named group, or the first group. The message starts after the match.
+    pub speaker_pattern: Option<String>,
+    // Speaker whose lines become user messages, matched case-insensitively
+    pub user_speaker: Option<String>,
+    pub create_missing_characters: bool,
+    pub dry_run: bool,
+    // Defaults to the file name
+    pub chat_name: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TextEncoding {
+    Utf8,
+    Windows1252,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct DetectedSpeaker {
+    pub name: String,
+    pub messages: usize,
+    // Existing or newly created character; None for unmatched speakers and in dry runs for new ones
+    pub character_id: Option<String>,
+    pub is_user: bool,
+    // A placeholder character is (or in a dry run would be) created for this speaker
+    pub placeholder: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TextImportReport {
+    pub dry_run: bool,
+    pub encoding: TextEncoding,
+    pub lines: usize,
+    pub messages: usize,
+    // Messages without a speaker, imported as system messages
+    pub narration_messages: usize,
+    // In order of first appearance
+    pub speakers: Vec<DetectedSpeaker>,
+    pub chat: Option<Chat>,
+}
+
+#[derive(Debug, PartialEq)]
+struct ParsedMessage {
+    speaker: Option<String>,
+    text: String,
+}
+
+struct TranscriptParser {
+    pattern: Regex,
+    markdown: bool,
+    speaker: Option<String>,
+    lines: Vec<String>,
+}
+
+impl TranscriptParser {
+    fn new(pattern: Option<&str>, markdown: bool) -> Result<Self, AppError> {
+        let pattern = Regex::new(pattern.unwrap_or(DEFAULT_SPEAKER_PATTERN))
+            .map_err(|e| AppError::validation(format!("Invalid speaker pattern: {}", e)))?;
+        if pattern.captures_len() < 2 {
+            return Err(AppError::validation(
+                "The speaker pattern needs a group capturing the speaker's name",
+            ));
+        }
+        Ok(Self {
+            pattern,
+            markdown,
+            speaker: None,
+            lines: Vec::new(),
+        })
+    }
+
+    fn speaker_line(&self, line: &str) -> Option<(String, String)> {
+        let normalized;
+        let markdown_speaker = self
+            .markdown
+            .then(|| MARKDOWN_SPEAKER.captures(line))
+            .flatten();
+        let line = match markdown_speaker {
+            Some(captures) => {
+                let whole = captures.get(0).unwrap();
+                normalized = format!("{}: {}", captures[1].trim(), &line[whole.end()..]);
+                normalized.as_str()
+            }
+            None => line,
+        };
+
+        let captures = self.pattern.captures(line)?;
+        let speaker = captures
+            .name("speaker")
+            .or_else(|| captures.get(1))?
+            .as_str()
+            .trim();
+        if speaker.is_empty() {
+            return None;
+        }
+        let rest = &line[captures.get(0).unwrap().end()..];
+        Some((speaker.to_string(), rest.to_string()))
+    }
+
+    // The message finished by this line, if it starts a new one
+    fn push_line(&mut self, line: &str) -> Option<ParsedMessage> {
+        match self.speaker_line(line) {
+            Some((speaker, text)) => {
+                let finished = self.take();
+                self.speaker = Some(speaker);
+                self.lines.push(text);
+                finished
+            }
+            None => {
+                self.lines.push(line.to_string());
+                None
+            }
+        }
+    }
+
+    // The message still open at the end of the file
+    fn finish(&mut self) -> Option<ParsedMessage> {
+        self.take()
+    }
+
+    fn take(&mut self) -> Option<ParsedMessage> {
+        let speaker = self.speaker.take();
+        let text = std::mem::take(&mut self.lines)
+            .join("\n")
+            .trim()
+            .to_string();
+        (!text.is_empty()).then_some(ParsedMessage { speaker, text })
+    }
+}
+
+// Bytes 0x80-0x9F of Windows-1252; the rest of the range maps straight to U+0080-U+00FF
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '\u{20AC}', '\u{0081}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{02C6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{008D}', '\u{017D}', '\u{008F}',
+    '\u{0090}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{02DC}', '\u{2122}', '\u{0161}', '\u{203A}', '\u{0153}', '\u{009D}', '\u{017E}', '\u{0178}',
+];
+
+fn decode_windows_1252(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|&byte| match byte {
+            0x80..=0x9F => WINDOWS_1252_HIGH[(byte - 0x80) as usize],
+            _ => byte as char,
+        })
+        .collect()
+}
+
+// UTF-8 until a line isn't valid UTF-8, Windows-1252 from then on
+fn decode_line(bytes: &[u8], encoding: &mut TextEncoding) -> String {
+    if *encoding == TextEncoding::Utf8 {
+        if let Ok(text) = std::str::from_utf8(bytes) {
+            return text.to_string();
+        }
+        *encoding = TextEncoding::Windows1252;
+    }
+    decode_windows_1252(bytes)
+}
+
+fn normalize_name(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+// Who each speaker is, filled in as they show up
+struct SpeakerBook {
+    // Lowercased name -> character id, for the profile's characters
+    characters: HashMap<String, String>,
+    user_speaker: Option<String>,
+    create_missing: bool,
+    speakers: Vec<DetectedSpeaker>,
+    index: HashMap<String, usize>,
+}
+
+impl SpeakerBook {
+    fn speaker(&self, name: &str) -> Option<&DetectedSpeaker> {
+        self.index
+            .get(&normalize_name(name))
+            .map(|&index| &self.speakers[index])
+    }
+
+    // Record one more message of the speaker
+    fn count(&mut self, name: &str) {
+        let key = normalize_name(name);
+        if !self.index.contains_key(&key) {
+            let character_id = self.characters.get(&key).cloned();
+            let is_user = self.user_speaker.as_deref().map(normalize_name) == Some(key.clone());
+            self.index.insert(key.clone(), self.speakers.len());
+            self.speakers.push(DetectedSpeaker {
+                name: name.trim().to_string(),
+                messages: 0,
+                placeholder: character_id.is_none() && !is_user && self.create_missing,
+                character_id,
+                is_user,
+            });
+        }
+        self.speakers[self.index[&key]].messages += 1;
+    }
+
+    fn assign_character(&mut self, name: &str, character_id: String) {
+        if let Some(&index) = self.index.get(&normalize_name(name)) {
+            self.speakers[index].character_id = Some(character_id);
+        }
+    }
+}
+
+struct ChatTarget {
+    chat_id: String,
+    chapter_id: String,
+}
+
+fn new_id() -> String {
+    Uuid::new_v4().to_string()
+}
+
+const NOW_SQL: &str = "strftime('%Y-%m-%dT%H:%M:%fZ', 'now')";
+
+async fn create_chat(
+    conn: &mut SqliteConnection,
+    profile_id: &str,
+    name: &str,
+) -> Result<ChatTarget, AppError> {
+    let chat_id = new_id();
+    sqlx::query(&format!(
+        "INSERT INTO chats (id, profile_id, name, participants, user_character_settings,
+            created_at, updated_at)
+         VALUES ($1, $2, $3, '[]', '[]', {0}, {0})",
+        NOW_SQL
+    ))
+    .bind(&chat_id)
+    .bind(profile_id)
+    .bind(name)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| format!("Failed to create chat '{}': {}", name, e))?;
+
+    let chapter_id = new_id();
+    sqlx::query(
+        "INSERT INTO chat_chapters (id, chat_id, title, sequence) VALUES ($1, $2, 'Chapter 1', 1)",
+    )
+    .bind(&chapter_id)
+    .bind(&chat_id)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| format!("Failed to create chapter: {}", e))?;
+
+    Ok(ChatTarget {
+        chat_id,
+        chapter_id,
+    })
+}
+
+async fn create_placeholder_character(
+    conn: &mut SqliteConnection,
+    profile_id: &str,
+    name: &str,
+) -> Result<String, AppError> {
+    let id = new_id();
+    sqlx::query(&format!(
+        "INSERT INTO characters (id, profile_id, name, version, type, tags, settings, custom,
+            created_at, updated_at)
+         VALUES ($1, $2, $3, '1.0.0', 'character', $4, '{{}}', '{{}}', {0}, {0})",
+        NOW_SQL
+    ))
+    .bind(&id)
+    .bind(profile_id)
+    .bind(name)
+    .bind(PLACEHOLDER_TAGS)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| format!("Failed to create character '{}': {}", name, e))?;
+    Ok(id)
+}
+
+// Write one parsed message, resolving (and if asked, creating) its speaker's character
+async fn insert_message(
+    conn: &mut SqliteConnection,
+    profile_id: &str,
+    target: &ChatTarget,
+    position: usize,
+    speakers: &mut SpeakerBook,
+    message: ParsedMessage,
+) -> Result<(), AppError> {
+    let (message_type, character_id, text) = match &message.speaker {
+        None => ("system", None, message.text),
+        Some(name) => {
+            let speaker = speakers
+                .speaker(name)
+                .cloned()
+                .ok_or_else(|| AppError::internal(format!("Speaker '{}' was not counted", name)))?;
+            let character_id = match (&speaker.character_id, speaker.placeholder) {
+                (None, true) => {
+                    let id = create_placeholder_character(conn, profile_id, &speaker.name).await?;
+                    speakers.assign_character(name, id.clone());
+                    Some(id)
+                }
+                (id, _) => id.clone(),
+            };
+            if speaker.is_user {
+                ("user", character_id, message.text)
+            } else if character_id.is_some() {
+                ("character", character_id, message.text)
+            } else {
+                // No character to attach it to, the name stays in the text
+                (
+                    "character",
+                    None,
+                    format!("{}: {}", speaker.name, message.text),
+                )
+            }
+        }
+    };
+
+    let variants = serde_json::to_string(&[&text])
+        .map_err(|e| format!("Failed to serialize message: {}", e))?;
+    sqlx::query(&format!(
+        "INSERT INTO chat_messages (id, chat_id, chapter_id, character_id, type, position,
+            messages, message_index, created_at, updated_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, 0, {0}, {0})",
+        NOW_SQL
+    ))
+    .bind(new_id())
+    .bind(&target.chat_id)
+    .bind(&target.chapter_id)
+    .bind(character_id)
+    .bind(message_type)
+    .bind((position as i64 + 1) * 100)
+    .bind(variants)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| format!("Failed to import a message: {}", e))?;
+    Ok(())
+}
+
+async fn load_characters(
+    conn: &mut SqliteConnection,
+    profile_id: &str,
+) -> Result<HashMap<String, String>, AppError> {
+    let rows = sqlx::query(
+        "SELECT id, name FROM characters WHERE profile_id = $1 AND type = 'character'
+         ORDER BY created_at, id",
+    )
+    .bind(profile_id)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| format!("Failed to read characters: {}", e))?;
+
+    let mut characters = HashMap::new();
+    for row in rows {
+        // The oldest character wins when two share a name
+        characters
+            .entry(normalize_name(&row.get::<String, _>("name")))
+            .or_insert_with(|| row.get::<String, _>("id"));
+    }
+    Ok(characters)
+}
+
+// Parse the transcript and, unless it is a dry run, write it as a new chat. Nothing is committed on error.
+async fn import_transcript<R: AsyncBufRead + Unpin>(
+    conn: &mut SqliteConnection,
+    profile_id: &str,
+    mut reader: R,
+    chat_name: &str,
+    markdown: bool,
+    options: &TextImportOptions,
+) -> Result<TextImportReport, AppError> {
+    let mut parser = TranscriptParser::new(options.speaker_pattern.as_deref(), markdown)?;
+
+    let mut tx = conn
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start import: {}", e))?;
+
+    sqlx::query("SELECT id FROM profiles WHERE id = $1")
+        .bind(profile_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to read profile {}: {}", profile_id, e))?
+        .ok_or_else(|| AppError::not_found(format!("Profile {} not found", profile_id)))?;
+
+    let mut speakers = SpeakerBook {
+        characters: load_characters(&mut tx, profile_id).await?,
+        user_speaker: options
+            .user_speaker
+            .clone()
+            .filter(|name| !name.trim().is_empty()),
+        create_missing: options.create_missing_characters,
+        speakers: Vec::new(),
+        index: HashMap::new(),
+    };
+    let target = if options.dry_run {
+        None
+    } else {
+        Some(create_chat(&mut tx, profile_id, chat_name).await?)
+    };
+
+    let mut encoding = TextEncoding::Utf8;
+    let mut lines = 0;
+    let mut messages = 0;
+    let mut narration_messages = 0;
+    let mut buffer = Vec::new();
+    loop {
+        buffer.clear();
+        let read = reader
+            .read_until(b'\n', &mut buffer)
+            .await
+            .map_err(|e| format!("Failed to read transcript: {}", e))?;
+
+        let finished = if read == 0 {
+            parser.finish()
+        } else {
+            let mut bytes = buffer.as_slice();
+            if lines == 0 {
+                if bytes.starts_with(&[0xFF, 0xFE]) || bytes.starts_with(&[0xFE, 0xFF]) {
+                    return Err(AppError::validation(
+                        "UTF-16 transcripts aren't supported, save the file as UTF-8",
+                    ));
+                }
+                bytes = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]).unwrap_or(bytes);
+            }
+            lines += 1;
+            let line = decode_line(bytes, &mut encoding);
+            parser.push_line(line.trim_end_matches(['\n', '\r']))
+        };
+
+        if let Some(message) = finished {
+            match &message.speaker {
+                Some(name) => speakers.count(name),
+                None => narration_messages += 1,
+            }
+            if let Some(target) = &target {
+                insert_message(
+                    &mut tx,
+                    profile_id,
+                    target,
+                    messages,
+                    &mut speakers,
+                    message,
+                )
+                .await?;
+            }
+            messages += 1;
+        }
+        if read == 0 {
+            break;
+        }
+    }
+
+    if messages == 0 {
+        return Err(AppError::validation("No messages found in the transcript"));
+    }
+
+    let chat = match &target {
+        None => None,
+        Some(target) => {
+            let participants: Vec<_> = speakers
+                .speakers
+                .iter()
+                .filter(|speaker| !speaker.is_user)
+                .filter_map(|speaker| speaker.character_id.as_ref())
+                .map(|id| json!({ "id": id, "enabled": true, "settings": {} }))
+                .collect();
+            let user_character_id = speakers
+                .speakers
+                .iter()
+                .find(|speaker| speaker.is_user)
+                .and_then(|speaker| speaker.character_id.clone());
+
+            let row = sqlx::query(&format!(
+                "UPDATE chats SET active_chapter_id = $1, participants = $2, user_character_id = $3
+                 WHERE id = $4 RETURNING {}",
+                CHAT_COLUMNS
+            ))
+            .bind(&target.chapter_id)
+            .bind(serde_json::Value::Array(participants).to_string())
+            .bind(user_character_id)
+            .bind(&target.chat_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to read imported chat: {}", e))?;
+            Some(chat_from_row(&row))
+        }
+    };
+
+    if chat.is_some() {
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit import: {}", e))?;
+    }
+
+    Ok(TextImportReport {
+        dry_run: options.dry_run,
+        encoding,
+        lines,
+        messages,
+        narration_messages,
+        speakers: speakers.speakers,
+        chat,
+    })
+}
+
+fn is_markdown(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("md") || ext.eq_ignore_ascii_case("markdown"))
+}
+
+/// Import a plain text or Markdown transcript with "Name: line" turns as a new chat of the profile.
+/// Speakers are matched to the profile's characters by name; `create_missing_characters` makes
+/// placeholder characters for the others. With `dry_run` nothing is written and the report lists
+/// the detected speakers and message counts.
+#[tauri::command]
+pub async fn import_chat_from_text(
+    app: AppHandle,
+    window: Window,
+    profile_id: String,
+    file_path: String,
+    options: Option<TextImportOptions>,
+) -> Result<TextImportReport, AppError> {
+    verify_window_profile(&window, &profile_id)?;
+    let options = options.unwrap_or_default();
+    let path = Path::new(&file_path);
+    let file = tokio::fs::File::open(path)
+        .await
+        .map_err(|e| AppError::not_found(format!("Failed to open {}: {}", file_path, e)))?;
+
+    let chat_name = options
+        .chat_name
+        .as_deref()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .or_else(|| path.file_stem().and_then(|stem| stem.to_str()))
+        .unwrap_or(DEFAULT_CHAT_NAME)
+        .to_string();
+
+    let mut conn = open_connection(&app).await?;
+    let result = import_transcript(
+        &mut conn,
+        &profile_id,
+        BufReader::new(file),
+        &chat_name,
+        is_markdown(path),
+        &options,
+    )
+    .await;
+    let _ = conn.close().await;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_db::temp_database;
+    use crate::error::ErrorCode;
+
+    fn parse(text: &str, pattern: Option<&str>, markdown: bool) -> Vec<ParsedMessage> {
+        let mut parser = TranscriptParser::new(pattern, markdown).unwrap();
+        let mut messages: Vec<ParsedMessage> = text
+            .lines()
+            .filter_map(|line| parser.push_line(line))
+            .collect();
+        messages.extend(parser.finish());
+        messages
+    }
+
+    fn message(speaker: Option<&str>, text: &str) -> ParsedMessage {
+        ParsedMessage {
+            speaker: speaker.map(str::to_string),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn folds_continuation_lines_into_the_speaker_above() {
+        let transcript = "The tavern is loud tonight.\n\nBob: Hello?\nAnyone here?\n\nMary Ann: Over here.\n\n\nSit down, Bob.\n";
+        assert_eq!(
+            parse(transcript, None, false),
+            vec![
+                message(None, "The tavern is loud tonight."),
+                message(Some("Bob"), "Hello?\nAnyone here?"),
+                message(Some("Mary Ann"), "Over here.\n\n\nSit down, Bob."),
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_markdown_speakers_and_custom_patterns() {
+        let transcript = "# Night at the inn\n**Bob:** Hello?\n__Ann__: Hi.\nBob: plain too";
+        assert_eq!(
+            parse(transcript, None, true),
+            vec![
+                message(None, "# Night at the inn"),
+                message(Some("Bob"), "Hello?"),
+                message(Some("Ann"), "Hi."),
+                message(Some("Bob"), "plain too"),
+            ]
+        );
+        // Without the Markdown handling the bold names are just text
+        assert_eq!(
+            parse(transcript, None, false)[0],
+            message(None, "# Night at the inn\n**Bob:** Hello?")
+        );
+
+        let bracketed = "[Bob] Hello?\n[Ann] Hi.";
+        assert_eq!(
+            parse(bracketed, Some(r"^\[(?P<speaker>[^\]]+)\]\s*"), false),
+            vec![message(Some("Bob"), "Hello?"), message(Some("Ann"), "Hi.")]
+        );
+    }
+
+    #[test]
+    fn rejects_patterns_without_a_speaker_group() {
+        for pattern in [r"^\w+:\s", "(unclosed"] {
+            let error = TranscriptParser::new(Some(pattern), false).err().unwrap();
+            assert_eq!(error.code, ErrorCode::Validation, "{}", pattern);
+        }
+    }
+
+    #[test]
+    fn falls_back_to_windows_1252() {
+        let mut encoding = TextEncoding::Utf8;
+        assert_eq!(decode_line("Café".as_bytes(), &mut encoding), "Café");
+        assert_eq!(encoding, TextEncoding::Utf8);
+
+        assert_eq!(
+            decode_line(b"\x93Caf\xe9\x94 \x96 \x80", &mut encoding),
+            "\u{201C}Café\u{201D} \u{2013} €"
+        );
+        assert_eq!(encoding, TextEncoding::Windows1252);
+    }
+
+    async fn connect(name: &str) -> SqliteConnection {
+        let mut conn = temp_database(name).await;
+        for sql in [
+            "INSERT INTO profiles (id, name) VALUES ('p1', 'Profile'), ('p2', 'Other')",
+            "INSERT INTO characters (id, profile_id, name, version, type)
+             VALUES ('k1', 'p1', 'Mary Ann', '1.0.0', 'character'),
+                    ('k2', 'p1', 'Bob', '1.0.0', 'character'),
+                    ('k3', 'p2', 'Cole', '1.0.0', 'character')",
+        ] {
+            sqlx::query(sql).execute(&mut conn).await.unwrap();
+        }
+        conn
+    }
+
+    const TRANSCRIPT: &[u8] =
+        b"\xef\xbb\xbfA storm outside.\r\nBob: Hello?\r\nmary ann: Hi, Bob.\r\nCole: Evening.\r\nBob: Who's that?\r\n";
+
+    fn options(dry_run: bool, create_missing_characters: bool) -> TextImportOptions {
+        TextImportOptions {
+            user_speaker: Some("bob".to_string()),
+            create_missing_characters,
+            dry_run,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn previews_without_writing() {
+        tauri::async_runtime::block_on(async {
+            let mut conn = connect("transcript-preview").await;
+
+            let report = import_transcript(
+                &mut conn,
+                "p1",
+                TRANSCRIPT,
+                "Storm",
+                false,
+                &options(true, true),
+            )
+            .await
+            .unwrap();
+            assert!(report.chat.is_none());
+            assert_eq!(
+                (report.lines, report.messages, report.narration_messages),
+                (5, 5, 1)
+            );
+            assert_eq!(report.encoding, TextEncoding::Utf8);
+            let speakers: Vec<_> = report
+                .speakers
+                .iter()
+                .map(|speaker| {
+                    (
+                        speaker.name.as_str(),
+                        speaker.messages,
+                        speaker.character_id.as_deref(),
+                        speaker.is_user,
+                        speaker.placeholder,
+                    )
+                })
+                .collect();
+            assert_eq!(
+                speakers,
+                vec![
+                    ("Bob", 2, Some("k2"), true, false),
+                    ("mary ann", 1, Some("k1"), false, false),
+                    // Cole belongs to another profile
+                    ("Cole", 1, None, false, true),
+                ]
+            );
+
+            let counts = sqlx::query("SELECT (SELECT COUNT(*) FROM chats) AS chats, (SELECT COUNT(*) FROM characters) AS characters")
+                .fetch_one(&mut conn)
+                .await
+                .unwrap();
+            assert_eq!(
+                (
+                    counts.get::<i64, _>("chats"),
+                    counts.get::<i64, _>("characters")
+                ),
+                (0, 3)
+            );
+
+            let missing = import_transcript(
+                &mut conn,
+                "nobody",
+                TRANSCRIPT,
+                "Storm",
+                false,
+                &options(true, false),
+            )
+            .await
+            .unwrap_err();
+            assert_eq!(missing.code, ErrorCode::NotFound);
+            let empty = import_transcript(
+                &mut conn,
+                "p1",
+                &b"\n\n"[..],
+                "Storm",
+                false,
+                &options(true, false),
+            )
+            .await
+            .unwrap_err();
+            assert_eq!(empty.code, ErrorCode::Validation);
+        });
+    }
+
+    #[test]
+    fn imports_with_placeholder_characters() {
+        tauri::async_runtime::block_on(async {
+            let mut conn = connect("transcript-import").await;
+
+            let report = import_transcript(
+                &mut conn,
+                "p1",
+                TRANSCRIPT,
+                "Storm",
+                false,
+                &options(false, true),
+            )
+            .await
+            .unwrap();
+            let chat = report.chat.unwrap();
+            assert_eq!(chat.name, "Storm");
+
+            let cole = sqlx::query(
+                "SELECT id, tags FROM characters WHERE profile_id = 'p1' AND name = 'Cole'",
+            )
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+            let cole_id: String = cole.get("id");
+            assert_eq!(cole.get::<String, _>("tags"), PLACEHOLDER_TAGS);
+            assert_eq!(
+                report.speakers[2].character_id.as_deref(),
+                Some(cole_id.as_str())
+            );
+
+            let row =
+                sqlx::query("SELECT participants, user_character_id FROM chats WHERE id = $1")
+                    .bind(&chat.id)
+                    .fetch_one(&mut conn)
+                    .await
+                    .unwrap();
+            assert_eq!(
+                row.get::<Option<String>, _>("user_character_id").as_deref(),
+                Some("k2")
+            );
+            let participants: serde_json::Value =
+                serde_json::from_str(&row.get::<String, _>("participants")).unwrap();
+            assert_eq!(
+                participants,
+                json!([
+                    { "id": "k1", "enabled": true, "settings": {} },
+                    { "id": cole_id, "enabled": true, "settings": {} },
+                ])
+            );
+
+            let messages: Vec<(String, Option<String>, String)> = sqlx::query("SELECT type, character_id, messages FROM chat_messages WHERE chat_id = $1 ORDER BY position")
+                .bind(&chat.id)
+                .fetch_all(&mut conn)
+                .await
+                .unwrap()
+                .iter()
+                .map(|row| (row.get("type"), row.get("character_id"), row.get("messages")))
+                .collect();
+            assert_eq!(
+                messages,
+                vec![
+                    (
+                        "system".to_string(),
+                        None,
+                        r#"["A storm outside."]"#.to_string()
+                    ),
+                    (
+                        "user".to_string(),
+                        Some("k2".to_string()),
+                        r#"["Hello?"]"#.to_string()
+                    ),
+                    (
+                        "character".to_string(),
+                        Some("k1".to_string()),
+                        r#"["Hi, Bob."]"#.to_string()
+                    ),
+                    (
+                        "character".to_string(),
+                        Some(cole_id.clone()),
+                        r#"["Evening."]"#.to_string()
+                    ),
+                    (
+                        "user".to_string(),
+                        Some("k2".to_string()),
+                        r#"["Who's that?"]"#.to_string()
+                    ),
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn keeps_unknown_speakers_in_the_text() {
+        tauri::async_runtime::block_on(async {
+            let mut conn = connect("transcript-unknown").await;
+
+            let report = import_transcript(
+                &mut conn,
+                "p1",
+                TRANSCRIPT,
+                "Storm",
+                false,
+                &options(false, false),
+            )
+            .await
+            .unwrap();
+            let chat = report.chat.unwrap();
+
+            let row = sqlx::query("SELECT character_id, messages FROM chat_messages WHERE chat_id = $1 AND position = 400")
+                .bind(&chat.id)
+                .fetch_one(&mut conn)
+                .await
+                .unwrap();
+            assert_eq!(row.get::<Option<String>, _>("character_id"), None);
+            assert_eq!(row.get::<String, _>("messages"), r#"["Cole: Evening."]"#);
+            let characters = sqlx::query("SELECT COUNT(*) AS count FROM characters")
+                .fetch_one(&mut conn)
+                .await
+                .unwrap();
+            assert_eq!(characters.get::<i64, _>("count"), 3);
+        });
+    }
+}
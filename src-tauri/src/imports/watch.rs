@@ -0,0 +1,513 @@
+use notify::{Config, PollWatcher, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, State, Window};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+
+use crate::error::AppError;
+use crate::windows::verify_window_profile;
+
+// Folder watch for automatic character imports. Changes arrive through OS file events (notify);
+// network shares don't raise those reliably, so they are polled with notify's PollWatcher instead,
+// as is any folder the OS watcher fails on. Every event triggers a scan of the folder. Files are
+// only ever read; importing is up to the frontend.
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+// Rescan after a change until the new files settle, and now and then in case an event was missed
+const SETTLE_INTERVAL: Duration = Duration::from_secs(1);
+const IDLE_RESCAN_INTERVAL: Duration = Duration::from_secs(60);
+// Back off while the folder is unavailable (unmounted network drive...), up to this
+const MAX_RETRY_INTERVAL: Duration = Duration::from_secs(30);
+const WATCHED_EXTENSIONS: &[&str] = &["png", "json"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct FileStamp {
+    modified: SystemTime,
+    size: u64,
+}
+
+// Decides which files to report. A new or changed file is reported once it looks the same on two
+// scans in a row, so a card still being copied or synced isn't picked up half-written, and a burst
+// of changes becomes one report.
+#[derive(Default)]
+struct ChangeTracker {
+    reported: HashMap<PathBuf, FileStamp>,
+    pending: HashMap<PathBuf, FileStamp>,
+    initialized: bool,
+}
+
+impl ChangeTracker {
+    // Files modified after `since` count as new on the first scan, everything older as already seen
+    fn scan(
+        &mut self,
+        current: HashMap<PathBuf, FileStamp>,
+        since: Option<SystemTime>,
+    ) -> Vec<PathBuf> {
+        self.reported.retain(|path, _| current.contains_key(path));
+        self.pending.retain(|path, _| current.contains_key(path));
+
+        let first_scan = !self.initialized;
+        self.initialized = true;
+
+        let mut ready = Vec::new();
+        for (path, stamp) in current {
+            if first_scan && !since.is_some_and(|since| stamp.modified > since) {
+                self.reported.insert(path, stamp);
+                continue;
+            }
+            if self.reported.get(&path) == Some(&stamp) {
+                continue;
+            }
+            if self.pending.get(&path) == Some(&stamp) {
+                self.pending.remove(&path);
+                self.reported.insert(path.clone(), stamp);
+                ready.push(path);
+            } else {
+                self.pending.insert(path, stamp);
+            }
+        }
+
+        ready.sort();
+        ready
+    }
+
+    // Files seen once that need another scan before they are reported
+    fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+}
+
+// UNC paths (\\server\share, //server/share) are network shares
+fn is_network_path(dir: &Path) -> bool {
+    let path = dir.to_string_lossy();
+    path.starts_with(r"\\") || path.starts_with("//")
+}
+
+// Watch a folder through OS events, or by polling for network shares and when the OS watcher
+// can't be set up, in which case the reason comes back with the watcher. Every event wakes the
+// scan loop.
+fn create_watcher(
+    dir: &Path,
+    wake: UnboundedSender<()>,
+) -> Result<(Box<dyn Watcher + Send>, Option<String>), String> {
+    let handler = {
+        let wake = wake.clone();
+        move |_event: notify::Result<notify::Event>| {
+            let _ = wake.send(());
+        }
+    };
+    let mut fallback = None;
+    if !is_network_path(dir) {
+        let native = RecommendedWatcher::new(handler, Config::default()).and_then(|mut watcher| {
+            watcher.watch(dir, RecursiveMode::NonRecursive)?;
+            Ok(watcher)
+        });
+        match native {
+            Ok(watcher) => return Ok((Box::new(watcher), None)),
+            Err(e) => {
+                fallback = Some(format!(
+                    "Checking {} every few seconds, file events are unavailable: {}",
+                    dir.display(),
+                    e
+                ))
+            }
+        }
+    }
+
+    let mut watcher = PollWatcher::new(
+        move |_event: notify::Result<notify::Event>| {
+            let _ = wake.send(());
+        },
+        Config::default().with_poll_interval(POLL_INTERVAL),
+    )
+    .map_err(|e| format!("Failed to watch {}: {}", dir.display(), e))?;
+    watcher
+        .watch(dir, RecursiveMode::NonRecursive)
+        .map_err(|e| format!("Failed to watch {}: {}", dir.display(), e))?;
+    Ok((Box::new(watcher), fallback))
+}
+
+fn list_watched_files(dir: &Path) -> Result<HashMap<PathBuf, FileStamp>, String> {
+    let entries =
+        fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+
+    let mut files = HashMap::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let watched = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| WATCHED_EXTENSIONS.contains(&ext.to_lowercase().as_str()));
+        if !watched {
+            continue;
+        }
+        // Skip what can't be read right now; it shows up again on a later scan
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if !metadata.is_file() {
+            continue;
+        }
+        files.insert(
+            path,
+            FileStamp {
+                modified: metadata.modified().unwrap_or(UNIX_EPOCH),
+                size: metadata.len(),
+            },
+        );
+    }
+    Ok(files)
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportWatchFiles {
+    pub profile_id: String,
+    pub paths: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportWatchStatus {
+    pub profile_id: String,
+    pub directory: String,
+    pub available: bool,
+    pub error: Option<String>,
+    // Set while the folder is readable but not watched as it should be: polled because file
+    // events failed, or not watched at all and only rescanned now and then
+    pub warning: Option<String>,
+}
+
+struct ActiveWatch {
+    directory: String,
+    stop: Arc<AtomicBool>,
+    // Wakes the scan loop, so a stop takes effect right away
+    wake: UnboundedSender<()>,
+    status: Arc<Mutex<ImportWatchStatus>>,
+}
+
+// Profile id -> its folder watch
+#[derive(Default)]
+pub struct ImportWatches(Mutex<HashMap<String, ActiveWatch>>);
+
+impl ImportWatches {
+    fn stop(&self, profile_id: &str) -> Result<(), String> {
+        let mut watches = self
+            .0
+            .lock()
+            .map_err(|e| format!("Failed to lock import watch state: {}", e))?;
+        if let Some(watch) = watches.remove(profile_id) {
+            watch.stop.store(true, Ordering::Relaxed);
+            let _ = watch.wake.send(());
+        }
+        Ok(())
+    }
+}
+
+fn spawn_watch(
+    app: AppHandle,
+    profile_id: String,
+    dir: PathBuf,
+    since: Option<SystemTime>,
+    stop: Arc<AtomicBool>,
+    status: Arc<Mutex<ImportWatchStatus>>,
+) -> UnboundedSender<()> {
+    let (wake, mut woken) = unbounded_channel();
+    let handle = wake.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut tracker = ChangeTracker::default();
+        let mut retry_interval = POLL_INTERVAL;
+        // Set up once the folder is readable, dropped while it is unavailable
+        let mut watcher: Option<Box<dyn Watcher + Send>> = None;
+        let mut warning: Option<String> = None;
+
+        while !stop.load(Ordering::Relaxed) {
+            let scan = {
+                let dir = dir.clone();
+                tauri::async_runtime::spawn_blocking(move || list_watched_files(&dir))
+                    .await
+                    .unwrap_or_else(|e| Err(format!("Folder scan failed: {}", e)))
+            };
+
+            let (available, error) = match scan {
+                Ok(files) => {
+                    retry_interval = POLL_INTERVAL;
+                    if watcher.is_none() {
+                        match create_watcher(&dir, wake.clone()) {
+                            Ok((created, fallback)) => {
+                                watcher = Some(created);
+                                warning = fallback;
+                            }
+                            Err(error) => warning = Some(error),
+                        }
+                    }
+                    let ready = tracker.scan(files, since);
+                    if !ready.is_empty() && !stop.load(Ordering::Relaxed) {
+                        let paths = ready
+                            .iter()
+                            .map(|path| path.to_string_lossy().to_string())
+                            .collect();
+                        let _ = app.emit(
+                            "import-watch-files",
+                            ImportWatchFiles {
+                                profile_id: profile_id.clone(),
+                                paths,
+                            },
+                        );
+                    }
+                    (true, None)
+                }
+                // Known files are kept, so nothing is imported again when the folder comes back
+                Err(error) => {
+                    watcher = None;
+                    warning = None;
+                    retry_interval = (retry_interval * 2).min(MAX_RETRY_INTERVAL);
+                    (false, Some(error))
+                }
+            };
+
+            if let Ok(mut current) = status.lock() {
+                if current.available != available || current.warning != warning {
+                    current.available = available;
+                    current.error = error;
+                    current.warning = warning.clone();
+                    let _ = app.emit("import-watch-status", current.clone());
+                }
+            }
+
+            let wait = if !available || watcher.is_none() {
+                retry_interval
+            } else if tracker.has_pending() {
+                SETTLE_INTERVAL
+            } else {
+                IDLE_RESCAN_INTERVAL
+            };
+            tokio::select! {
+                _ = woken.recv() => {
+                    // One scan for a burst of events
+                    tokio::time::sleep(SETTLE_INTERVAL).await;
+                    while woken.try_recv().is_ok() {}
+                }
+                _ = tokio::time::sleep(wait) => {}
+            }
+        }
+    });
+    handle
+}
+
+/// Start watching a folder for character cards on behalf of a profile, replacing its previous
+/// watch. New and changed `.png`/`.json` files are reported through `import-watch-files`; with
+/// `since_ms`, files modified after that time count as new on the first scan. Changes of
+/// availability or of the watch warning are reported through `import-watch-status`.
+#[tauri::command]
+pub fn start_import_watch(
+    app: AppHandle,
+    window: Window,
+    watches: State<'_, ImportWatches>,
+    profile_id: String,
+    directory: String,
+    since_ms: Option<u64>,
+) -> Result<ImportWatchStatus, AppError> {
+    verify_window_profile(&window, &profile_id)?;
+    let dir = PathBuf::from(&directory);
+    if !dir.is_absolute() {
+        return Err(AppError::validation(
+            "The watched folder must be an absolute path",
+        ));
+    }
+
+    watches.stop(&profile_id)?;
+
+    // An unavailable folder is not an error: the watch retries until it shows up
+    let error = match fs::metadata(&dir) {
+        Ok(metadata) if metadata.is_dir() => None,
+        Ok(_) => {
+            return Err(AppError::validation(format!(
+                "{} is not a folder",
+                directory
+            )))
+        }
+        Err(e) => Some(format!("Failed to read {}: {}", directory, e)),
+    };
+    let status = ImportWatchStatus {
+        profile_id: profile_id.clone(),
+        directory: directory.clone(),
+        available: error.is_none(),
+        error,
+        warning: None,
+    };
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let shared_status = Arc::new(Mutex::new(status.clone()));
+    let since = since_ms.map(|ms| UNIX_EPOCH + Duration::from_millis(ms));
+    let wake = spawn_watch(
+        app,
+        profile_id.clone(),
+        dir,
+        since,
+        stop.clone(),
+        shared_status.clone(),
+    );
+
+    watches
+        .0
+        .lock()
+        .map_err(|e| format!("Failed to lock import watch state: {}", e))?
+        .insert(
+            profile_id,
+            ActiveWatch {
+                directory,
+                stop,
+                wake,
+                status: shared_status,
+            },
+        );
+    Ok(status)
+}
+
+/// Stop the folder watch of a profile. Does nothing when it has none.
+#[tauri::command]
+pub fn stop_import_watch(
+    window: Window,
+    watches: State<'_, ImportWatches>,
+    profile_id: String,
+) -> Result<(), AppError> {
+    verify_window_profile(&window, &profile_id)?;
+    Ok(watches.stop(&profile_id)?)
+}
+
+/// Folder watched for a profile and whether it's reachable, or None when it has no watch
+#[tauri::command]
+pub fn get_import_watch_status(
+    window: Window,
+    watches: State<'_, ImportWatches>,
+    profile_id: String,
+) -> Result<Option<ImportWatchStatus>, AppError> {
+    verify_window_profile(&window, &profile_id)?;
+    let watches = watches
+        .0
+        .lock()
+        .map_err(|e| format!("Failed to lock import watch state: {}", e))?;
+    Ok(watches.get(&profile_id).map(|watch| {
+        watch
+            .status
+            .lock()
+            .map(|status| status.clone())
+            .unwrap_or(ImportWatchStatus {
+                profile_id: profile_id.clone(),
+                directory: watch.directory.clone(),
+                available: false,
+                error: None,
+                warning: None,
+            })
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stamp(seconds: u64, size: u64) -> FileStamp {
+        FileStamp {
+            modified: UNIX_EPOCH + Duration::from_secs(seconds),
+            size,
+        }
+    }
+
+    fn files(entries: &[(&str, FileStamp)]) -> HashMap<PathBuf, FileStamp> {
+        entries
+            .iter()
+            .map(|(name, stamp)| (PathBuf::from(name), *stamp))
+            .collect()
+    }
+
+    #[test]
+    fn existing_files_are_not_reported() {
+        let mut tracker = ChangeTracker::default();
+        assert!(tracker
+            .scan(files(&[("a.png", stamp(10, 1))]), None)
+            .is_empty());
+        assert!(tracker
+            .scan(files(&[("a.png", stamp(10, 1))]), None)
+            .is_empty());
+    }
+
+    #[test]
+    fn files_newer_than_since_count_as_new() {
+        let mut tracker = ChangeTracker::default();
+        let since = Some(UNIX_EPOCH + Duration::from_secs(50));
+        let current = files(&[("old.png", stamp(10, 1)), ("new.png", stamp(60, 1))]);
+
+        assert!(tracker.scan(current.clone(), since).is_empty());
+        assert_eq!(tracker.scan(current, since), vec![PathBuf::from("new.png")]);
+    }
+
+    #[test]
+    fn waits_for_a_file_to_settle() {
+        let mut tracker = ChangeTracker::default();
+        tracker.scan(HashMap::new(), None);
+
+        // Still growing on the second scan
+        assert!(tracker
+            .scan(files(&[("a.json", stamp(10, 100))]), None)
+            .is_empty());
+        assert!(tracker
+            .scan(files(&[("a.json", stamp(11, 200))]), None)
+            .is_empty());
+        assert_eq!(
+            tracker.scan(files(&[("a.json", stamp(11, 200))]), None),
+            vec![PathBuf::from("a.json")]
+        );
+        assert!(tracker
+            .scan(files(&[("a.json", stamp(11, 200))]), None)
+            .is_empty());
+    }
+
+    #[test]
+    fn reports_changed_and_re_added_files() {
+        let mut tracker = ChangeTracker::default();
+        tracker.scan(files(&[("a.png", stamp(10, 1))]), None);
+
+        tracker.scan(files(&[("a.png", stamp(20, 2))]), None);
+        assert_eq!(
+            tracker.scan(files(&[("a.png", stamp(20, 2))]), None),
+            vec![PathBuf::from("a.png")]
+        );
+
+        tracker.scan(HashMap::new(), None);
+        tracker.scan(files(&[("a.png", stamp(20, 2))]), None);
+        assert_eq!(
+            tracker.scan(files(&[("a.png", stamp(20, 2))]), None),
+            vec![PathBuf::from("a.png")]
+        );
+    }
+
+    #[test]
+    fn unc_paths_are_polled() {
+        assert!(is_network_path(Path::new(r"\\nas\cards")));
+        assert!(is_network_path(Path::new("//nas/cards")));
+        assert!(!is_network_path(Path::new(r"C:\Users\me\cards")));
+        assert!(!is_network_path(Path::new("/home/me/cards")));
+    }
+
+    #[test]
+    fn only_lists_card_files() {
+        let dir = std::env::temp_dir().join("narratrix-import-watch-listing");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("nested.png")).unwrap();
+        for name in ["card.png", "card.JSON", "notes.txt"] {
+            fs::write(dir.join(name), "{}").unwrap();
+        }
+
+        let mut listed: Vec<String> = list_watched_files(&dir)
+            .unwrap()
+            .keys()
+            .map(|path| path.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        listed.sort();
+        assert_eq!(listed, vec!["card.JSON", "card.png"]);
+    }
+}
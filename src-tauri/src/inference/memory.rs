@@ -0,0 +1,21 @@
+use serde::Serialize;
+use std::time::Duration;
+
+use super::tokenizer;
+use crate::error::AppError;
+
+// Backend side of the idle memory trim. The frontend runs it periodically with the profile's
+// threshold (see useIdleMemoryTrim) and trims its own caches at the same time.
+
+#[derive(Debug, Serialize)]
+pub struct TrimMemoryReport {
+    // What was dropped, e.g. the tokenizer repos
+    pub evicted: Vec<String>,
+}
+
+/// Drop cached resources not used for `max_idle_secs`. They are loaded again on next use.
+#[tauri::command]
+pub fn trim_memory(max_idle_secs: u64) -> Result<TrimMemoryReport, AppError> {
+    let evicted = tokenizer::evict_idle_tokenizers(Duration::from_secs(max_idle_secs))?;
+    Ok(TrimMemoryReport { evicted })
+}
@@ -1 +1,3 @@
+pub mod memory;
+pub mod request_log;
 pub mod tokenizer;
@@ -0,0 +1,581 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Manager, Window};
+
+use crate::error::AppError;
+use crate::windows::verify_window_profile;
+
+const LOG_FILE_NAME: &str = "inference.log";
+// Size and age caps so the log can't grow unbounded
+const MAX_FILE_BYTES: u64 = 5 * 1024 * 1024;
+const MAX_ROTATED_FILES: usize = 4;
+const MAX_FILE_AGE: Duration = Duration::from_secs(14 * 24 * 60 * 60);
+
+// Parameter names containing any of these are never written to disk
+pub(crate) const SENSITIVE_KEYS: &[&str] = &[
+    "api_key",
+    "apikey",
+    "secret",
+    "password",
+    "authorization",
+    "access_token",
+    "bearer",
+];
+
+// Serializes writes and rotation across concurrent requests
+static LOG_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct InferenceLogEntry {
+    pub timestamp: String,
+    pub request_id: String,
+    // Entries written before profiles were recorded have none and are left out of exports
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile_id: Option<String>,
+    pub model_id: String,
+    #[serde(default)]
+    pub engine: Option<String>,
+    #[serde(default)]
+    pub actual_model: Option<String>,
+    #[serde(default)]
+    pub params: serde_json::Value,
+    pub status: String,
+    pub latency_ms: u64,
+    #[serde(default)]
+    pub usage: Option<serde_json::Value>,
+    #[serde(default)]
+    pub error_kind: Option<String>,
+    // System prompt and messages, only when the profile opted in to storing prompts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<serde_json::Value>,
+}
+
+// Append one request lifecycle to the rotating inference log in the app log dir
+#[tauri::command]
+pub fn append_inference_log(app: AppHandle, mut entry: InferenceLogEntry) -> Result<(), AppError> {
+    let log_dir = app
+        .path()
+        .app_log_dir()
+        .map_err(|e| format!("Failed to resolve log dir: {}", e))?;
+    fs::create_dir_all(&log_dir).map_err(|e| format!("Failed to create log dir: {}", e))?;
+
+    redact(&mut entry.params);
+    if let Some(prompt) = entry.prompt.as_mut() {
+        redact(prompt);
+    }
+
+    let mut line =
+        serde_json::to_string(&entry).map_err(|e| format!("Failed to serialize entry: {}", e))?;
+    line.push('\n');
+
+    let _guard = LOG_LOCK
+        .lock()
+        .map_err(|e| format!("Failed to lock inference log: {}", e))?;
+
+    let log_path = log_dir.join(LOG_FILE_NAME);
+    rotate_if_needed(&log_path, line.len() as u64)?;
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&log_path)
+        .map_err(|e| format!("Failed to open inference log: {}", e))?;
+    file.write_all(line.as_bytes())
+        .map_err(|e| AppError::internal(format!("Failed to write inference log: {}", e)))
+}
+
+// Path of the current log file, so the UI can offer it for bug reports
+#[tauri::command]
+pub fn get_inference_log_path(app: AppHandle) -> Result<String, AppError> {
+    let log_dir = app
+        .path()
+        .app_log_dir()
+        .map_err(|e| format!("Failed to resolve log dir: {}", e))?;
+    Ok(log_dir.join(LOG_FILE_NAME).to_string_lossy().to_string())
+}
+
+// Latest entry of a request, searching the current file then the rotated ones, newest first.
+// A request logs once per outcome, so the last line wins within a file.
+#[tauri::command]
+pub fn find_inference_log_entry(
+    app: AppHandle,
+    request_id: String,
+) -> Result<Option<InferenceLogEntry>, AppError> {
+    let log_dir = app
+        .path()
+        .app_log_dir()
+        .map_err(|e| format!("Failed to resolve log dir: {}", e))?;
+    let log_path = log_dir.join(LOG_FILE_NAME);
+
+    let _guard = LOG_LOCK
+        .lock()
+        .map_err(|e| format!("Failed to lock inference log: {}", e))?;
+
+    let paths = std::iter::once(log_path.clone())
+        .chain((1..=MAX_ROTATED_FILES).map(|index| rotated_path(&log_path, index)));
+    for path in paths {
+        if let Some(entry) = find_in_file(&path, &request_id)? {
+            return Ok(Some(entry));
+        }
+    }
+    Ok(None)
+}
+
+fn find_in_file(path: &Path, request_id: &str) -> Result<Option<InferenceLogEntry>, String> {
+    let file = match fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("Failed to open inference log: {}", e)),
+    };
+
+    let mut found = None;
+    for line in BufReader::new(file).lines() {
+        let line = line.map_err(|e| format!("Failed to read inference log: {}", e))?;
+        // Cheap check before parsing; lines that don't parse (a torn write) are skipped
+        if !line.contains(request_id) {
+            continue;
+        }
+        if let Ok(entry) = serde_json::from_str::<InferenceLogEntry>(&line) {
+            if entry.request_id == request_id {
+                found = Some(entry);
+            }
+        }
+    }
+    Ok(found)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogExportFormat {
+    Csv,
+    Json,
+}
+
+const CSV_COLUMNS: &[&str] = &[
+    "timestamp",
+    "request_id",
+    "model_id",
+    "engine",
+    "actual_model",
+    "status",
+    "latency_ms",
+    "prompt_tokens",
+    "completion_tokens",
+    "total_tokens",
+    "error_kind",
+];
+
+// One exported entry: the stats columns only, prompts and parameters stay in the log
+#[derive(Debug, Serialize)]
+struct ExportRow<'a> {
+    timestamp: &'a str,
+    request_id: &'a str,
+    model_id: &'a str,
+    engine: Option<&'a str>,
+    actual_model: Option<&'a str>,
+    status: &'a str,
+    latency_ms: u64,
+    prompt_tokens: Option<u64>,
+    completion_tokens: Option<u64>,
+    total_tokens: Option<u64>,
+    error_kind: Option<&'a str>,
+}
+
+impl<'a> ExportRow<'a> {
+    fn new(entry: &'a InferenceLogEntry) -> Self {
+        let usage = |key: &str| {
+            entry
+                .usage
+                .as_ref()
+                .and_then(|usage| usage.get(key))
+                .and_then(|value| value.as_u64())
+        };
+        Self {
+            timestamp: &entry.timestamp,
+            request_id: &entry.request_id,
+            model_id: &entry.model_id,
+            engine: entry.engine.as_deref(),
+            actual_model: entry.actual_model.as_deref(),
+            status: &entry.status,
+            latency_ms: entry.latency_ms,
+            prompt_tokens: usage("prompt_tokens"),
+            completion_tokens: usage("completion_tokens"),
+            total_tokens: usage("total_tokens"),
+            error_kind: entry.error_kind.as_deref(),
+        }
+    }
+
+    fn csv_line(&self) -> String {
+        let number = |value: Option<u64>| value.map(|v| v.to_string()).unwrap_or_default();
+        [
+            csv_field(self.timestamp),
+            csv_field(self.request_id),
+            csv_field(self.model_id),
+            csv_field(self.engine.unwrap_or_default()),
+            csv_field(self.actual_model.unwrap_or_default()),
+            csv_field(self.status),
+            self.latency_ms.to_string(),
+            number(self.prompt_tokens),
+            number(self.completion_tokens),
+            number(self.total_tokens),
+            csv_field(self.error_kind.unwrap_or_default()),
+        ]
+        .join(",")
+    }
+}
+
+// RFC 4180: quote fields holding a separator, quote or line break, doubling inner quotes
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+// Write a profile's entries from `since` (an ISO timestamp, like the ones logged) to `out`, oldest
+// first, reading one line at a time. Returns how many were written.
+fn export_entries(
+    log_path: &Path,
+    profile_id: &str,
+    since: Option<&str>,
+    format: LogExportFormat,
+    out: &mut impl Write,
+) -> Result<usize, String> {
+    let write_error = |e: std::io::Error| format!("Failed to write export: {}", e);
+    match format {
+        LogExportFormat::Csv => writeln!(out, "{}", CSV_COLUMNS.join(",")).map_err(write_error)?,
+        LogExportFormat::Json => writeln!(out, "[").map_err(write_error)?,
+    }
+
+    let paths = (1..=MAX_ROTATED_FILES)
+        .rev()
+        .map(|index| rotated_path(log_path, index))
+        .chain(std::iter::once(log_path.to_path_buf()));
+
+    let mut written = 0;
+    for path in paths {
+        let file = match fs::File::open(&path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(format!("Failed to open inference log: {}", e)),
+        };
+        for line in BufReader::new(file).lines() {
+            let line = line.map_err(|e| format!("Failed to read inference log: {}", e))?;
+            // Lines that don't parse (a torn write) are skipped
+            let Ok(entry) = serde_json::from_str::<InferenceLogEntry>(&line) else {
+                continue;
+            };
+            if entry.profile_id.as_deref() != Some(profile_id)
+                || since.is_some_and(|since| entry.timestamp.as_str() < since)
+            {
+                continue;
+            }
+
+            let row = ExportRow::new(&entry);
+            match format {
+                LogExportFormat::Csv => writeln!(out, "{}", row.csv_line()),
+                LogExportFormat::Json => {
+                    let json = serde_json::to_string(&row)
+                        .map_err(|e| format!("Failed to serialize entry: {}", e))?;
+                    let separator = if written == 0 { "" } else { ",\n" };
+                    write!(out, "{}  {}", separator, json)
+                }
+            }
+            .map_err(write_error)?;
+            written += 1;
+        }
+    }
+
+    if format == LogExportFormat::Json {
+        let end = if written == 0 { "]" } else { "\n]" };
+        writeln!(out, "{}", end).map_err(write_error)?;
+    }
+    Ok(written)
+}
+
+/// Export a profile's inference log entries from `since` to `output_path` as CSV or JSON, for
+/// spreadsheets. Only the stats columns are exported. Returns the number of entries written.
+#[tauri::command]
+pub fn export_inference_logs(
+    app: AppHandle,
+    window: Window,
+    profile_id: String,
+    since: Option<String>,
+    format: LogExportFormat,
+    output_path: String,
+) -> Result<usize, AppError> {
+    verify_window_profile(&window, &profile_id)?;
+    let log_dir = app
+        .path()
+        .app_log_dir()
+        .map_err(|e| format!("Failed to resolve log dir: {}", e))?;
+    let log_path = log_dir.join(LOG_FILE_NAME);
+
+    let output = fs::File::create(&output_path)
+        .map_err(|e| format!("Failed to create {}: {}", output_path, e))?;
+    let mut out = std::io::BufWriter::new(output);
+
+    let _guard = LOG_LOCK
+        .lock()
+        .map_err(|e| format!("Failed to lock inference log: {}", e))?;
+    let written = export_entries(&log_path, &profile_id, since.as_deref(), format, &mut out)?;
+    out.flush()
+        .map_err(|e| format!("Failed to write export: {}", e))?;
+    Ok(written)
+}
+
+// Timestamps of a profile's completed requests in [from, until), compared as ISO strings like the
+// logged ones, from the current and rotated files. Feeds the activity timeline.
+pub(crate) fn completed_request_times(
+    app: &AppHandle,
+    profile_id: &str,
+    from: &str,
+    until: &str,
+) -> Result<Vec<String>, String> {
+    let log_dir = app
+        .path()
+        .app_log_dir()
+        .map_err(|e| format!("Failed to resolve log dir: {}", e))?;
+
+    let _guard = LOG_LOCK
+        .lock()
+        .map_err(|e| format!("Failed to lock inference log: {}", e))?;
+    completed_in_logs(&log_dir.join(LOG_FILE_NAME), profile_id, from, until)
+}
+
+fn completed_in_logs(
+    log_path: &Path,
+    profile_id: &str,
+    from: &str,
+    until: &str,
+) -> Result<Vec<String>, String> {
+    let paths = std::iter::once(log_path.to_path_buf())
+        .chain((1..=MAX_ROTATED_FILES).map(|index| rotated_path(log_path, index)));
+
+    let mut times = Vec::new();
+    for path in paths {
+        let file = match fs::File::open(&path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(format!("Failed to open inference log: {}", e)),
+        };
+        for line in BufReader::new(file).lines() {
+            let line = line.map_err(|e| format!("Failed to read inference log: {}", e))?;
+            // Cheap check before parsing; lines that don't parse (a torn write) are skipped
+            if !line.contains(profile_id) {
+                continue;
+            }
+            let Ok(entry) = serde_json::from_str::<InferenceLogEntry>(&line) else {
+                continue;
+            };
+            if entry.profile_id.as_deref() == Some(profile_id)
+                && entry.status == "completed"
+                && entry.timestamp.as_str() >= from
+                && entry.timestamp.as_str() < until
+            {
+                times.push(entry.timestamp);
+            }
+        }
+    }
+    Ok(times)
+}
+
+fn redact(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                let key = key.to_lowercase();
+                if SENSITIVE_KEYS
+                    .iter()
+                    .any(|sensitive| key.contains(sensitive))
+                {
+                    *value = serde_json::Value::String("[redacted]".to_string());
+                } else {
+                    redact(value);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+fn rotated_path(log_path: &Path, index: usize) -> PathBuf {
+    log_path.with_file_name(format!("inference.{}.log", index))
+}
+
+// inference.log -> inference.1.log -> ... -> inference.N.log, dropping the oldest.
+// Rotated files past the age cap are removed regardless of count.
+fn rotate_if_needed(log_path: &Path, incoming: u64) -> Result<(), String> {
+    for index in 1..=MAX_ROTATED_FILES {
+        let path = rotated_path(log_path, index);
+        let expired = fs::metadata(&path)
+            .and_then(|meta| meta.modified())
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .is_some_and(|age| age > MAX_FILE_AGE);
+        if expired {
+            let _ = fs::remove_file(&path);
+        }
+    }
+
+    let current_size = fs::metadata(log_path).map(|meta| meta.len()).unwrap_or(0);
+    if current_size == 0 || current_size + incoming <= MAX_FILE_BYTES {
+        return Ok(());
+    }
+
+    let _ = fs::remove_file(rotated_path(log_path, MAX_ROTATED_FILES));
+    for index in (1..MAX_ROTATED_FILES).rev() {
+        let from = rotated_path(log_path, index);
+        if from.exists() {
+            fs::rename(&from, rotated_path(log_path, index + 1))
+                .map_err(|e| format!("Failed to rotate inference log: {}", e))?;
+        }
+    }
+    fs::rename(log_path, rotated_path(log_path, 1))
+        .map_err(|e| format!("Failed to rotate inference log: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(profile_id: Option<&str>, timestamp: &str, model_id: &str) -> String {
+        let mut entry = serde_json::json!({
+            "timestamp": timestamp,
+            "request_id": format!("req-{}", timestamp),
+            "model_id": model_id,
+            "params": {},
+            "status": "completed",
+            "latency_ms": 1200,
+            "usage": { "prompt_tokens": 100, "completion_tokens": 20, "total_tokens": 120 },
+            "prompt": { "system_prompt": "secret plot" },
+        });
+        if let Some(profile_id) = profile_id {
+            entry["profile_id"] = profile_id.into();
+        }
+        entry.to_string()
+    }
+
+    fn log_dir(name: &str, files: &[(&str, Vec<String>)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("narratrix-log-export-{}", name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        for (file, lines) in files {
+            fs::write(dir.join(file), lines.join("\n")).unwrap();
+        }
+        dir.join(LOG_FILE_NAME)
+    }
+
+    #[test]
+    fn escapes_csv_fields() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field(r#"say "hi""#), r#""say ""hi""""#);
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn exports_a_profile_oldest_first_as_csv() {
+        let log_path = log_dir(
+            "csv",
+            &[
+                (
+                    "inference.1.log",
+                    vec![
+                        entry(Some("p1"), "2026-01-01T00:00:00.000Z", "old"),
+                        entry(Some("p1"), "2026-01-02T00:00:00.000Z", "model, \"quoted\""),
+                    ],
+                ),
+                (
+                    "inference.log",
+                    vec![
+                        entry(Some("p2"), "2026-01-03T00:00:00.000Z", "other profile"),
+                        entry(None, "2026-01-03T00:00:00.000Z", "no profile"),
+                        "{torn".to_string(),
+                        entry(Some("p1"), "2026-01-04T00:00:00.000Z", "new"),
+                    ],
+                ),
+            ],
+        );
+
+        let mut out = Vec::new();
+        let written = export_entries(
+            &log_path,
+            "p1",
+            Some("2026-01-02T00:00:00.000Z"),
+            LogExportFormat::Csv,
+            &mut out,
+        )
+        .unwrap();
+
+        let csv = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(written, 2);
+        assert_eq!(lines[0], CSV_COLUMNS.join(","));
+        assert_eq!(
+            lines[1],
+            "2026-01-02T00:00:00.000Z,req-2026-01-02T00:00:00.000Z,\"model, \"\"quoted\"\"\",,,completed,1200,100,20,120,"
+        );
+        assert!(lines[2].contains(",new,"));
+        assert!(!csv.contains("secret plot"));
+    }
+
+    #[test]
+    fn exports_valid_json() {
+        let log_path = log_dir(
+            "json",
+            &[(
+                "inference.log",
+                vec![
+                    entry(Some("p1"), "2026-01-01T00:00:00.000Z", "a"),
+                    entry(Some("p1"), "2026-01-02T00:00:00.000Z", "b"),
+                ],
+            )],
+        );
+
+        let mut out = Vec::new();
+        export_entries(&log_path, "p1", None, LogExportFormat::Json, &mut out).unwrap();
+        let rows: Vec<serde_json::Value> = serde_json::from_slice(&out).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1]["model_id"], "b");
+        assert_eq!(rows[1]["total_tokens"], 120);
+
+        let mut empty = Vec::new();
+        export_entries(&log_path, "nobody", None, LogExportFormat::Json, &mut empty).unwrap();
+        assert_eq!(String::from_utf8(empty).unwrap(), "[\n]\n");
+    }
+
+    #[test]
+    fn finds_completed_requests_of_a_profile_in_range() {
+        let cancelled = entry(Some("p1"), "2026-01-02T12:00:00.000Z", "a")
+            .replace("\"completed\"", "\"cancelled\"");
+        let log_path = log_dir(
+            "activity",
+            &[
+                (
+                    "inference.2.log",
+                    vec![entry(Some("p1"), "2025-12-31T23:59:59.000Z", "a")],
+                ),
+                (
+                    "inference.log",
+                    vec![
+                        entry(Some("p1"), "2026-01-01T08:00:00.000Z", "a"),
+                        cancelled,
+                        entry(Some("p2"), "2026-01-02T13:00:00.000Z", "a"),
+                        entry(Some("p1"), "2026-01-03T00:00:00.000Z", "a"),
+                    ],
+                ),
+            ],
+        );
+
+        let times = completed_in_logs(&log_path, "p1", "2026-01-01", "2026-01-03").unwrap();
+        assert_eq!(times, vec!["2026-01-01T08:00:00.000Z"]);
+    }
+}
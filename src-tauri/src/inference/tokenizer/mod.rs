@@ -1,9 +1,14 @@
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
-use std::sync::OnceLock;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
 use tiktoken_rs::cl100k_base;
 use tokenizers::tokenizer::Tokenizer;
 
+use crate::error::AppError;
+
 // Supported model types
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub enum ModelType {
@@ -21,31 +26,200 @@ pub struct TokenCountResponse {
     pub model: String,
 }
 
-// Cache for HuggingFace tokenizers
-static LLAMA_TOKENIZER: OnceLock<Tokenizer> = OnceLock::new();
-static MISTRAL_TOKENIZER: OnceLock<Tokenizer> = OnceLock::new();
+const LLAMA_REPO: &str = "meta-llama/Llama-2-7b-chat-hf";
+const MISTRAL_REPO: &str = "mistralai/Mistral-7B-Instruct-v0.1";
 
-// Initialize tokenizers lazily
-fn get_llama_tokenizer() -> &'static Tokenizer {
-    LLAMA_TOKENIZER
-        .get_or_init(|| Tokenizer::from_pretrained("meta-llama/Llama-2-7b-chat-hf", None).unwrap())
+// A lazily loaded value that can be dropped while idle; the next use loads it again
+struct IdleSlot<T> {
+    value: RwLock<Option<Arc<T>>>,
+    last_used: Mutex<Option<Instant>>,
 }
 
-fn get_mistral_tokenizer() -> &'static Tokenizer {
-    MISTRAL_TOKENIZER.get_or_init(|| {
-        Tokenizer::from_pretrained("mistralai/Mistral-7B-Instruct-v0.1", None).unwrap()
+impl<T> IdleSlot<T> {
+    const fn new() -> Self {
+        Self {
+            value: RwLock::new(None),
+            last_used: Mutex::new(None),
+        }
+    }
+
+    fn get_or_load(&self, load: impl FnOnce() -> Result<T>) -> Result<Arc<T>> {
+        if let Ok(mut last_used) = self.last_used.lock() {
+            *last_used = Some(Instant::now());
+        }
+
+        if let Some(value) = self
+            .value
+            .read()
+            .map_err(|e| anyhow!("Cache poisoned: {}", e))?
+            .as_ref()
+        {
+            return Ok(value.clone());
+        }
+
+        let mut cached = self
+            .value
+            .write()
+            .map_err(|e| anyhow!("Cache poisoned: {}", e))?;
+        // Another caller may have loaded it while we waited for the write lock
+        if let Some(value) = cached.as_ref() {
+            return Ok(value.clone());
+        }
+
+        let value = Arc::new(load()?);
+        *cached = Some(value.clone());
+        Ok(value)
+    }
+
+    fn is_loaded(&self) -> Result<bool, String> {
+        Ok(self
+            .value
+            .read()
+            .map_err(|e| format!("Cache poisoned: {}", e))?
+            .is_some())
+    }
+
+    fn clear(&self) -> Result<(), String> {
+        *self
+            .value
+            .write()
+            .map_err(|e| format!("Failed to lock cache: {}", e))? = None;
+        Ok(())
+    }
+
+    // Drop the value when it wasn't used for `max_idle`. Callers holding it keep their Arc.
+    fn evict_if_idle(&self, max_idle: Duration, now: Instant) -> Result<bool, String> {
+        let idle = self
+            .last_used
+            .lock()
+            .map_err(|e| format!("Failed to lock cache: {}", e))?
+            .is_none_or(|last_used| now.saturating_duration_since(last_used) >= max_idle);
+        if !idle || !self.is_loaded()? {
+            return Ok(false);
+        }
+        self.clear()?;
+        Ok(true)
+    }
+}
+
+// Cache for HuggingFace tokenizers. Replaceable so a corrupted download can be dropped and re-fetched.
+type TokenizerSlot = IdleSlot<Tokenizer>;
+static LLAMA_TOKENIZER: TokenizerSlot = IdleSlot::new();
+static MISTRAL_TOKENIZER: TokenizerSlot = IdleSlot::new();
+
+fn get_tokenizer(slot: &TokenizerSlot, repo: &str) -> Result<Arc<Tokenizer>> {
+    slot.get_or_load(|| {
+        Tokenizer::from_pretrained(repo, None)
+            .map_err(|e| anyhow!("Failed to load tokenizer {}: {}", repo, e))
     })
 }
 
+// Drop the tokenizers not used for `max_idle`, returning their repos. They are loaded again
+// (from the disk cache) on the next count.
+pub fn evict_idle_tokenizers(max_idle: Duration) -> Result<Vec<String>, String> {
+    let now = Instant::now();
+    let mut evicted = Vec::new();
+    for (slot, repo) in [
+        (&LLAMA_TOKENIZER, LLAMA_REPO),
+        (&MISTRAL_TOKENIZER, MISTRAL_REPO),
+    ] {
+        if slot.evict_if_idle(max_idle, now)? {
+            evicted.push(repo.to_string());
+        }
+    }
+    Ok(evicted)
+}
+
+fn get_llama_tokenizer() -> Result<Arc<Tokenizer>> {
+    get_tokenizer(&LLAMA_TOKENIZER, LLAMA_REPO)
+}
+
+fn get_mistral_tokenizer() -> Result<Arc<Tokenizer>> {
+    get_tokenizer(&MISTRAL_TOKENIZER, MISTRAL_REPO)
+}
+
+// Downloaded tokenizers live in the HuggingFace hub cache ($HF_HOME/hub, default ~/.cache/huggingface/hub)
+fn hub_cache_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    if let Ok(hf_home) = std::env::var("HF_HOME") {
+        return Ok(PathBuf::from(hf_home).join("hub"));
+    }
+    let home = app
+        .path()
+        .home_dir()
+        .map_err(|e| format!("Failed to resolve home dir: {}", e))?;
+    Ok(home.join(".cache").join("huggingface").join("hub"))
+}
+
+#[derive(Debug, Serialize)]
+pub struct TokenizerCacheEntry {
+    pub repo: String,
+    pub loaded: bool,
+    pub downloaded: bool,
+}
+
+// Whether each HuggingFace tokenizer is in memory and on disk, for the app status
+pub fn tokenizer_cache_status(app: &AppHandle) -> Result<Vec<TokenizerCacheEntry>, String> {
+    let hub_dir = hub_cache_dir(app)?;
+    [
+        (&LLAMA_TOKENIZER, LLAMA_REPO),
+        (&MISTRAL_TOKENIZER, MISTRAL_REPO),
+    ]
+    .into_iter()
+    .map(|(slot, repo)| {
+        Ok(TokenizerCacheEntry {
+            repo: repo.to_string(),
+            loaded: slot.is_loaded()?,
+            downloaded: hub_dir
+                .join(format!("models--{}", repo.replace('/', "--")))
+                .exists(),
+        })
+    })
+    .collect()
+}
+
+// Drop the in-memory tokenizers and delete their downloaded files.
+// Only our repos are removed, the hub cache may be shared with other tools.
+#[tauri::command]
+pub fn clear_tokenizer_cache(app: AppHandle) -> Result<(), AppError> {
+    for slot in [&LLAMA_TOKENIZER, &MISTRAL_TOKENIZER] {
+        slot.clear()?;
+    }
+
+    let hub_dir = hub_cache_dir(&app)?;
+    for repo in [LLAMA_REPO, MISTRAL_REPO] {
+        let repo_dir = hub_dir.join(format!("models--{}", repo.replace('/', "--")));
+        if repo_dir.exists() {
+            std::fs::remove_dir_all(&repo_dir)
+                .map_err(|e| format!("Failed to remove tokenizer cache for {}: {}", repo, e))?;
+        }
+    }
+
+    Ok(())
+}
+
+// Load (downloading if needed) every HuggingFace tokenizer so the first count isn't slow
+#[tauri::command]
+pub async fn preload_tokenizers() -> Result<(), AppError> {
+    tauri::async_runtime::spawn_blocking(|| {
+        get_llama_tokenizer()?;
+        get_mistral_tokenizer()?;
+        Ok::<(), anyhow::Error>(())
+    })
+    .await
+    .map_err(|e| format!("Failed to preload tokenizers: {}", e))?
+    // Mostly a failed download from the HuggingFace hub
+    .map_err(|e| AppError::provider(format!("Failed to preload tokenizers: {}", e)))
+}
+
 // Main token counting function exposed to Tauri
 #[tauri::command]
 pub async fn count_tokens(
     text: String,
     model_type: ModelType,
-) -> Result<TokenCountResponse, String> {
+) -> Result<TokenCountResponse, AppError> {
     let count = match count_tokens_for_model(&text, &model_type) {
         Ok(count) => count,
-        Err(e) => return Err(format!("Failed to count tokens: {}", e)),
+        Err(e) => return Err(AppError::internal(format!("Failed to count tokens: {}", e))),
     };
 
     Ok(TokenCountResponse {
@@ -59,7 +233,7 @@ fn count_tokens_for_model(text: &str, model_type: &ModelType) -> Result<usize> {
     match model_type {
         // Llama models
         ModelType::Llama2 | ModelType::Llama3 => {
-            let tokenizer = get_llama_tokenizer();
+            let tokenizer = get_llama_tokenizer()?;
             let encoding = tokenizer
                 .encode(text, false)
                 .map_err(|e| anyhow!("Llama tokenization failed: {}", e))?;
@@ -69,7 +243,7 @@ fn count_tokens_for_model(text: &str, model_type: &ModelType) -> Result<usize> {
         // Mistral & Deepseek
         ModelType::Mistral | ModelType::Deepseek => {
             // Similar tokenization to Llama for Mistral
-            let tokenizer = get_mistral_tokenizer();
+            let tokenizer = get_mistral_tokenizer()?;
             let encoding = tokenizer
                 .encode(text, false)
                 .map_err(|e| anyhow!("Mistral tokenization failed: {}", e))?;
@@ -84,3 +258,42 @@ fn count_tokens_for_model(text: &str, model_type: &ModelType) -> Result<usize> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn evicted_values_load_again_on_next_use() {
+        let slot: IdleSlot<usize> = IdleSlot::new();
+        let loads = AtomicUsize::new(0);
+        let load = || Ok(loads.fetch_add(1, Ordering::SeqCst) + 1);
+
+        assert_eq!(*slot.get_or_load(load).unwrap(), 1);
+        assert_eq!(*slot.get_or_load(load).unwrap(), 1);
+
+        let later = Instant::now() + Duration::from_secs(120);
+        assert!(slot.evict_if_idle(Duration::from_secs(60), later).unwrap());
+        assert!(!slot.is_loaded().unwrap());
+
+        assert_eq!(*slot.get_or_load(load).unwrap(), 2);
+        assert_eq!(loads.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn recently_used_values_are_kept() {
+        let slot: IdleSlot<usize> = IdleSlot::new();
+        let held = slot.get_or_load(|| Ok(7)).unwrap();
+
+        assert!(!slot
+            .evict_if_idle(Duration::from_secs(60), Instant::now())
+            .unwrap());
+        assert!(slot.is_loaded().unwrap());
+
+        // Evicting an empty slot reports nothing, and callers keep what they hold
+        slot.clear().unwrap();
+        assert!(!slot.evict_if_idle(Duration::ZERO, Instant::now()).unwrap());
+        assert_eq!(*held, 7);
+    }
+}
@@ -6,9 +6,18 @@
 use std::env;
 
 use tauri::Emitter;
+mod assets;
 mod database;
+mod error;
+mod exports;
+mod imports;
 mod inference;
+mod scrub;
+mod starter;
+mod support;
 mod utils;
+mod webhooks;
+mod windows;
 
 #[derive(Clone, serde::Serialize)]
 struct Payload {
@@ -31,17 +40,71 @@ fn main() {
             app.emit("single-instance", Payload { args: argv, cwd })
                 .unwrap();
         }))
-        .plugin(
-            tauri_plugin_sql::Builder::default()
-                .add_migrations("sqlite:narratrix_main.db", database::get_migrations())
-                .build(),
-        )
+        // Migrations are applied by database::migrator, not at plugin init, so a failing step
+        // can report progress and fall back to a recovery screen instead of aborting startup
+        .plugin(tauri_plugin_sql::Builder::default().build())
+        .manage(windows::ProfileWindows::default())
+        .manage(database::migrator::MigrationState::default())
+        .manage(imports::watch::ImportWatches::default())
+        .manage(database::deletion::DeleteConfirmations::default())
+        .manage(windows::recovery::WindowRecovery::default())
+        .setup(|app| {
+            // The window-state plugin already restored the main window, maybe onto a monitor that is gone
+            windows::recovery::ensure_main_window_visible(app.handle());
+            database::migrator::spawn_startup_migrations(app.handle().clone());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             utils::hash_password,
             utils::verify_password,
             utils::encrypt_api_key,
             utils::decrypt_api_key,
             inference::tokenizer::count_tokens,
+            inference::tokenizer::clear_tokenizer_cache,
+            inference::tokenizer::preload_tokenizers,
+            inference::memory::trim_memory,
+            inference::request_log::append_inference_log,
+            inference::request_log::get_inference_log_path,
+            database::activity::get_activity_timeline,
+            inference::request_log::find_inference_log_entry,
+            inference::request_log::export_inference_logs,
+            imports::import_from_url,
+            imports::parse_config_file,
+            imports::conversations::import_openai_conversation,
+            imports::conversations::import_anthropic_conversation,
+            imports::transcript::import_chat_from_text,
+            imports::watch::start_import_watch,
+            imports::watch::stop_import_watch,
+            imports::watch::get_import_watch_status,
+            scrub::scrub_text,
+            scrub::preview_scrub,
+            exports::export_chat_html,
+            windows::create_profile_window,
+            windows::bind_window_profile,
+            windows::unbind_window_profile,
+            windows::get_window_profile,
+            windows::recovery::reset_window_state,
+            database::migrator::get_migration_status,
+            database::migrator::restore_migration_backup,
+            database::repair::repair_orphans,
+            database::app_settings::get_app_setting,
+            database::app_settings::set_app_setting,
+            database::message_metadata::update_messages_metadata_bulk,
+            database::message_metadata::get_messages_by_metadata,
+            database::chat_copy::duplicate_chat,
+            database::model_rebind::rebind_model,
+            database::deletion::delete_chat,
+            database::deletion::delete_profile,
+            webhooks::deliver_webhook,
+            support::create_support_bundle,
+            support::status::get_app_status,
+            starter::list_available_starter_packs,
+            starter::get_starter_packs,
+            assets::store_asset,
+            assets::garbage_collect_assets,
+            assets::orphans::find_orphaned_files,
+            assets::orphans::cleanup_orphaned_files,
+            assets::paths::sanitize_file_name,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,373 @@
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::error::AppError;
+
+// Detector patterns. Matches are post-filtered in `accept_match` where the regex crate's lack of
+// look-around would otherwise produce false positives (versions, URLs).
+static EMAIL_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,}").unwrap()
+});
+static PHONE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:\+\d{1,3}[ .-]?)?(?:\(\d{2,4}\)[ .-]?|\d{2,4}[ .-])\d{3,5}[ .-]?\d{3,4}")
+        .unwrap()
+});
+static IPV4_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b(?:\d{1,3}\.){3}\d{1,3}\b").unwrap());
+// Windows paths, also in their JSON-escaped form (C:\\Users\\...)
+static WINDOWS_PATH_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"\b[A-Za-z]:(?:\\{1,2}[^\\/:*?"<>|\r\n\t ]+)+\\{0,2}"#).unwrap());
+// Only user-identifying roots are considered, so API routes and URLs are left alone
+static UNIX_PATH_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?:~|/(?:home|Users|root|var/folders|private/var|mnt|media))/[^\s"'<>|]*"#)
+        .unwrap()
+});
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ScrubKind {
+    Email,
+    Path,
+    Ipv4,
+    Phone,
+    Word,
+    Name,
+}
+
+impl ScrubKind {
+    fn label(&self) -> &'static str {
+        match self {
+            ScrubKind::Email => "email",
+            ScrubKind::Path => "path",
+            ScrubKind::Ipv4 => "ip",
+            ScrubKind::Phone => "phone",
+            ScrubKind::Word => "word",
+            ScrubKind::Name => "name",
+        }
+    }
+}
+
+// Detector toggles, all regex detectors are on by default
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct ScrubOptions {
+    pub emails: bool,
+    pub phones: bool,
+    pub ipv4: bool,
+    pub paths: bool,
+    pub words: Vec<String>,
+    // Names swapped for the given text instead of a placeholder, e.g. a real name for a pen name.
+    // Matched like `words`
+    pub replacements: HashMap<String, String>,
+}
+
+impl Default for ScrubOptions {
+    fn default() -> Self {
+        Self {
+            emails: true,
+            phones: true,
+            ipv4: true,
+            paths: true,
+            words: Vec::new(),
+            replacements: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ScrubMatch {
+    pub kind: ScrubKind,
+    pub placeholder: String,
+    pub original: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScrubPreview {
+    pub text: String,
+    pub matches: Vec<ScrubMatch>,
+}
+
+// Replaces sensitive values with typed placeholders. One scrubber should be used per document
+// so the same value always maps to the same placeholder.
+pub struct Scrubber {
+    options: ScrubOptions,
+    words_re: Option<Regex>,
+    replacements_re: Option<Regex>,
+    // Keyed by the lowercased name
+    replacements: HashMap<String, String>,
+    placeholders: HashMap<(ScrubKind, String), String>,
+    counters: HashMap<ScrubKind, usize>,
+    matches: Vec<ScrubMatch>,
+}
+
+impl Scrubber {
+    pub fn new(options: ScrubOptions) -> Result<Self, String> {
+        let words: Vec<String> = options
+            .words
+            .iter()
+            .map(|word| word.trim())
+            .filter(|word| !word.is_empty())
+            .map(regex::escape)
+            .collect();
+
+        let words_re = if words.is_empty() {
+            None
+        } else {
+            let pattern = format!(r"(?i)\b(?:{})\b", words.join("|"));
+            Some(Regex::new(&pattern).map_err(|e| format!("Invalid word list: {}", e))?)
+        };
+
+        let replacements: HashMap<String, String> = options
+            .replacements
+            .iter()
+            .map(|(name, replacement)| (name.trim().to_lowercase(), replacement.clone()))
+            .filter(|(name, _)| !name.is_empty())
+            .collect();
+        // Longest first, so "Mary Ann" is not replaced as "Mary"
+        let mut names: Vec<&String> = replacements.keys().collect();
+        names.sort_by_key(|name| std::cmp::Reverse(name.len()));
+        let replacements_re = if names.is_empty() {
+            None
+        } else {
+            let names: Vec<String> = names.into_iter().map(|name| regex::escape(name)).collect();
+            let pattern = format!(r"(?i)\b(?:{})\b", names.join("|"));
+            Some(Regex::new(&pattern).map_err(|e| format!("Invalid name replacements: {}", e))?)
+        };
+
+        Ok(Self {
+            options,
+            words_re,
+            replacements_re,
+            replacements,
+            placeholders: HashMap::new(),
+            counters: HashMap::new(),
+            matches: Vec::new(),
+        })
+    }
+
+    pub fn scrub(&mut self, text: &str) -> String {
+        let mut output = text.to_string();
+
+        // Emails first so their domains are not picked up by other detectors
+        if self.options.emails {
+            output = self.replace_all(&EMAIL_RE, &output, ScrubKind::Email);
+        }
+        if self.options.paths {
+            output = self.replace_all(&WINDOWS_PATH_RE, &output, ScrubKind::Path);
+            output = self.replace_all(&UNIX_PATH_RE, &output, ScrubKind::Path);
+        }
+        if self.options.ipv4 {
+            output = self.replace_all(&IPV4_RE, &output, ScrubKind::Ipv4);
+        }
+        if self.options.phones {
+            output = self.replace_all(&PHONE_RE, &output, ScrubKind::Phone);
+        }
+        if let Some(replacements_re) = self.replacements_re.clone() {
+            output = self.replace_names(&replacements_re, &output);
+        }
+        if let Some(words_re) = self.words_re.clone() {
+            output = self.replace_all(&words_re, &output, ScrubKind::Word);
+        }
+
+        output
+    }
+
+    pub fn into_matches(self) -> Vec<ScrubMatch> {
+        self.matches
+    }
+
+    fn replace_all(&mut self, re: &Regex, text: &str, kind: ScrubKind) -> String {
+        re.replace_all(text, |caps: &Captures| {
+            let found = caps.get(0).unwrap();
+            if !accept_match(text, found.start(), found.end(), kind) {
+                return found.as_str().to_string();
+            }
+            self.placeholder_for(kind, found.as_str())
+        })
+        .into_owned()
+    }
+
+    fn replace_names(&mut self, re: &Regex, text: &str) -> String {
+        re.replace_all(text, |caps: &Captures| {
+            let found = caps.get(0).unwrap().as_str();
+            let key = found.to_lowercase();
+            let Some(replacement) = self.replacements.get(&key).cloned() else {
+                return found.to_string();
+            };
+            if !self
+                .placeholders
+                .contains_key(&(ScrubKind::Name, key.clone()))
+            {
+                self.placeholders
+                    .insert((ScrubKind::Name, key), replacement.clone());
+                self.matches.push(ScrubMatch {
+                    kind: ScrubKind::Name,
+                    placeholder: replacement.clone(),
+                    original: found.to_string(),
+                });
+            }
+            replacement
+        })
+        .into_owned()
+    }
+
+    fn placeholder_for(&mut self, kind: ScrubKind, value: &str) -> String {
+        // Custom words are matched case-insensitively, so they share a placeholder across casings
+        let key_value = match kind {
+            ScrubKind::Word => value.to_lowercase(),
+            _ => value.to_string(),
+        };
+
+        if let Some(placeholder) = self.placeholders.get(&(kind, key_value.clone())) {
+            return placeholder.clone();
+        }
+
+        let counter = self.counters.entry(kind).or_insert(0);
+        *counter += 1;
+        let placeholder = format!("[{}-{}]", kind.label(), counter);
+
+        self.placeholders
+            .insert((kind, key_value), placeholder.clone());
+        self.matches.push(ScrubMatch {
+            kind,
+            placeholder: placeholder.clone(),
+            original: value.to_string(),
+        });
+        placeholder
+    }
+}
+
+// Reject matches that the patterns alone can't rule out
+fn accept_match(text: &str, start: usize, end: usize, kind: ScrubKind) -> bool {
+    let before = text[..start].chars().next_back();
+    let after = text[end..].chars().next();
+    let after_next = text[end..].chars().nth(1);
+
+    match kind {
+        ScrubKind::Ipv4 => {
+            // Version strings: "v1.2.3.4", "10.0.19041.1.2" or octets above 255
+            if matches!(before, Some('v' | 'V' | '.' | '-' | '_')) {
+                return false;
+            }
+            if after == Some('.') && after_next.is_some_and(|c| c.is_ascii_digit()) {
+                return false;
+            }
+            let previous_word = text[..start]
+                .trim_end()
+                .rsplit(char::is_whitespace)
+                .next()
+                .unwrap_or("");
+            if ["version", "ver", "ver.", "build", "v"]
+                .contains(&previous_word.to_lowercase().as_str())
+            {
+                return false;
+            }
+            text[start..end]
+                .split('.')
+                .all(|octet| octet.parse::<u16>().is_ok_and(|value| value <= 255))
+        }
+        ScrubKind::Phone => {
+            // Avoid eating into longer digit runs, dotted versions and ISO dates
+            if before.is_some_and(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-') {
+                return false;
+            }
+            if after.is_some_and(|c| c.is_ascii_alphanumeric())
+                || (after == Some('.') && after_next.is_some_and(|c| c.is_ascii_digit()))
+            {
+                return false;
+            }
+            let digits = text[start..end]
+                .chars()
+                .filter(|c| c.is_ascii_digit())
+                .count();
+            (7..=15).contains(&digits)
+        }
+        ScrubKind::Path => {
+            // Skip path segments that are part of a URL ("https://host/home/...")
+            !before.is_some_and(|c| c.is_ascii_alphanumeric() || c == '/' || c == '.')
+        }
+        ScrubKind::Email | ScrubKind::Word | ScrubKind::Name => true,
+    }
+}
+
+// Scrub a single document, used by the export paths
+#[tauri::command]
+pub fn scrub_text(text: String, options: Option<ScrubOptions>) -> Result<String, AppError> {
+    let mut scrubber = Scrubber::new(options.unwrap_or_default()).map_err(AppError::validation)?;
+    Ok(scrubber.scrub(&text))
+}
+
+// Show what would be replaced, including the original values, before exporting anything
+#[tauri::command]
+pub fn preview_scrub(
+    text: String,
+    options: Option<ScrubOptions>,
+) -> Result<ScrubPreview, AppError> {
+    let mut scrubber = Scrubber::new(options.unwrap_or_default()).map_err(AppError::validation)?;
+    let text = scrubber.scrub(&text);
+
+    Ok(ScrubPreview {
+        text,
+        matches: scrubber.into_matches(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scrub(text: &str) -> String {
+        Scrubber::new(ScrubOptions::default()).unwrap().scrub(text)
+    }
+
+    #[test]
+    fn version_strings_are_not_addresses() {
+        for text in [
+            "Running v1.2.3.4 now",
+            "Updated to version 10.0.0.1 today",
+            "firmware 10.0.0.1.2 installed",
+            "octets 999.1.1.1 are out of range",
+        ] {
+            assert_eq!(scrub(text), text);
+        }
+        assert_eq!(
+            scrub("The server is at 192.168.1.20."),
+            "The server is at [ip-1]."
+        );
+    }
+
+    #[test]
+    fn windows_paths_are_replaced() {
+        assert_eq!(
+            scrub(r"Saved to C:\Users\alice\notes.txt today"),
+            "Saved to [path-1] today"
+        );
+        // JSON-escaped, as in an exported chat
+        assert_eq!(
+            scrub(r#"{"file":"C:\\Users\\alice\\notes.txt"}"#),
+            r#"{"file":"[path-1]"}"#
+        );
+        assert_eq!(scrub("https://example.com/a"), "https://example.com/a");
+    }
+
+    #[test]
+    fn same_value_gets_the_same_placeholder() {
+        let mut scrubber = Scrubber::new(ScrubOptions {
+            words: vec!["Eldoria".to_string()],
+            ..ScrubOptions::default()
+        })
+        .unwrap();
+
+        assert_eq!(
+            scrubber.scrub("Mail bob@example.com or ann@example.com, again bob@example.com"),
+            "Mail [email-1] or [email-2], again [email-1]"
+        );
+        // Across calls on the same document, and across casings for words
+        assert_eq!(
+            scrubber.scrub("bob@example.com left Eldoria for eldoria"),
+            "[email-1] left [word-1] for [word-1]"
+        );
+        assert_eq!(scrubber.into_matches().len(), 3);
+    }
+}
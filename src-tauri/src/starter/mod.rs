@@ -0,0 +1,193 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+use crate::error::AppError;
+
+// Starter packs are bundled example content (characters, lorebooks, format templates, quick
+// actions) offered to new profiles. Each pack is a JSON file under resources/starter-packs named
+// after its id. The rows themselves are created by the frontend through its regular services, so
+// every item goes through the same Zod validation as content made by hand.
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StarterPack {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    #[serde(default)]
+    pub characters: Vec<Value>,
+    // Each lorebook carries its `entries`
+    #[serde(default)]
+    pub lorebooks: Vec<Value>,
+    #[serde(default)]
+    pub format_templates: Vec<Value>,
+    #[serde(default)]
+    pub quick_actions: Vec<Value>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StarterPackInfo {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub characters: usize,
+    pub lorebooks: usize,
+    pub format_templates: usize,
+    pub quick_actions: usize,
+}
+
+impl From<&StarterPack> for StarterPackInfo {
+    fn from(pack: &StarterPack) -> Self {
+        StarterPackInfo {
+            id: pack.id.clone(),
+            name: pack.name.clone(),
+            description: pack.description.clone(),
+            characters: pack.characters.len(),
+            lorebooks: pack.lorebooks.len(),
+            format_templates: pack.format_templates.len(),
+            quick_actions: pack.quick_actions.len(),
+        }
+    }
+}
+
+fn starter_packs_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let resource_dir = app
+        .path()
+        .resource_dir()
+        .map_err(|e| format!("Failed to resolve resource dir: {}", e))?;
+    Ok(resource_dir.join("resources").join("starter-packs"))
+}
+
+// All packs of the directory, sorted by id. A broken pack fails the whole listing so it can't ship unnoticed.
+fn load_packs(dir: &Path) -> Result<Vec<StarterPack>, String> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read starter packs in {}: {}", dir.display(), e))?;
+
+    let mut packs = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        let contents = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read starter pack {}: {}", file_name, e))?;
+        let pack: StarterPack = serde_json::from_str(&contents)
+            .map_err(|e| format!("Invalid starter pack {}: {}", file_name, e))?;
+        if path.file_stem().and_then(|stem| stem.to_str()) != Some(pack.id.as_str()) {
+            return Err(format!(
+                "Starter pack {} has id \"{}\", expected the file name",
+                file_name, pack.id
+            ));
+        }
+        packs.push(pack);
+    }
+
+    packs.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(packs)
+}
+
+/// Names and contents of the starter packs shipped with the app
+#[tauri::command]
+pub fn list_available_starter_packs(app: AppHandle) -> Result<Vec<StarterPackInfo>, AppError> {
+    let packs = load_packs(&starter_packs_dir(&app)?)?;
+    Ok(packs.iter().map(StarterPackInfo::from).collect())
+}
+
+/// Full content of the requested packs, in the requested order. Unknown ids are an error.
+#[tauri::command]
+pub fn get_starter_packs(app: AppHandle, packs: Vec<String>) -> Result<Vec<StarterPack>, AppError> {
+    let mut available = load_packs(&starter_packs_dir(&app)?)?;
+    packs
+        .iter()
+        .map(|id| {
+            available
+                .iter()
+                .position(|pack| &pack.id == id)
+                .map(|index| available.swap_remove(index))
+                .ok_or_else(|| AppError::not_found(format!("Unknown starter pack: {}", id)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn bundled_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("resources")
+            .join("starter-packs")
+    }
+
+    fn names(items: &[Value]) -> Vec<&str> {
+        items
+            .iter()
+            .map(|item| item["name"].as_str().unwrap_or_default())
+            .collect()
+    }
+
+    #[test]
+    fn bundled_packs_are_valid() {
+        let packs = load_packs(&bundled_dir()).unwrap();
+        assert!(!packs.is_empty());
+
+        for pack in &packs {
+            assert!(
+                !pack.name.is_empty() && !pack.description.is_empty(),
+                "{}",
+                pack.id
+            );
+
+            for name in names(&pack.characters)
+                .into_iter()
+                .chain(names(&pack.lorebooks))
+                .chain(names(&pack.format_templates))
+            {
+                assert!(!name.is_empty(), "unnamed item in {}", pack.id);
+            }
+            for lorebook in &pack.lorebooks {
+                let entries = lorebook["entries"].as_array();
+                assert!(entries.is_some_and(|e| !e.is_empty()), "{}", pack.id);
+            }
+            for action in &pack.quick_actions {
+                for field in ["icon", "label", "userPrompt", "streamOption"] {
+                    assert!(
+                        action[field].is_string(),
+                        "{} quick action lacks {}",
+                        pack.id,
+                        field
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn quick_action_labels_are_unique() {
+        let mut labels = HashSet::new();
+        for pack in load_packs(&bundled_dir()).unwrap() {
+            for action in &pack.quick_actions {
+                assert!(labels.insert(action["label"].as_str().unwrap().to_string()));
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_a_pack_named_differently_from_its_file() {
+        let dir = std::env::temp_dir().join("narratrix-starter-packs-mismatch");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("one.json"),
+            r#"{ "id": "two", "name": "Two", "description": "Mismatched" }"#,
+        )
+        .unwrap();
+
+        assert!(load_packs(&dir).unwrap_err().contains("one.json"));
+    }
+}
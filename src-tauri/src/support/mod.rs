@@ -0,0 +1,419 @@
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::sqlite::{SqliteConnectOptions, SqliteConnection};
+use sqlx::{ConnectOptions, Connection, Row};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Manager, Window};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+pub mod status;
+
+use crate::database::migrator::database_path;
+use crate::error::AppError;
+use crate::inference::request_log::SENSITIVE_KEYS;
+use crate::scrub::{ScrubOptions, Scrubber};
+use crate::windows::verify_window_profile;
+
+const MANIFEST_DIRS: &[&str] = &["models", "characters", "embeddings"];
+// Only recent logs are bundled, capped so the archive stays small enough to attach to an issue
+const MAX_LOG_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+const MAX_LOG_BYTES: u64 = 10 * 1024 * 1024;
+// AES-GCM output is a 12 byte nonce plus a 16 byte tag, so anything shorter can't be an encrypted value
+const MIN_ENCRYPTED_BYTES: usize = 28;
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+pub struct SupportBundleInclude {
+    pub logs: bool,
+    pub diagnostics: bool,
+    pub last_debug_captures: bool,
+    pub manifest_list: bool,
+    pub model_configs_redacted: bool,
+}
+
+// Pieces only the frontend knows about. Scrubbed like everything else before being written.
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+pub struct SupportBundleExtras {
+    pub diagnostics: Option<Value>,
+    pub debug_captures: Vec<Value>,
+    pub manifest_errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SupportBundleFile {
+    pub name: String,
+    pub bytes: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SupportBundleSummary {
+    pub path: String,
+    pub files: Vec<SupportBundleFile>,
+    pub scrubbed_values: usize,
+}
+
+struct BundleEntry {
+    name: String,
+    contents: String,
+}
+
+// Zip logs, diagnostics and redacted model configs for a bug report. Every entry goes through the scrubber.
+#[tauri::command]
+pub async fn create_support_bundle(
+    app: AppHandle,
+    window: Window,
+    output_path: String,
+    profile_id: String,
+    include: SupportBundleInclude,
+    extras: Option<SupportBundleExtras>,
+) -> Result<SupportBundleSummary, AppError> {
+    verify_window_profile(&window, &profile_id)?;
+    let extras = extras.unwrap_or_default();
+    let mut entries = vec![BundleEntry {
+        name: "summary.json".to_string(),
+        contents: to_pretty_json(&json!({
+            "app_version": app.package_info().version.to_string(),
+            "os": std::env::consts::OS,
+            "arch": std::env::consts::ARCH,
+            "included": {
+                "logs": include.logs,
+                "diagnostics": include.diagnostics,
+                "last_debug_captures": include.last_debug_captures,
+                "manifest_list": include.manifest_list,
+                "model_configs_redacted": include.model_configs_redacted,
+            },
+        }))?,
+    }];
+
+    if include.logs {
+        let log_dir = app
+            .path()
+            .app_log_dir()
+            .map_err(|e| format!("Failed to resolve log dir: {}", e))?;
+        entries.extend(collect_logs(&log_dir)?);
+    }
+
+    if include.diagnostics {
+        if let Some(diagnostics) = &extras.diagnostics {
+            entries.push(BundleEntry {
+                name: "diagnostics.json".to_string(),
+                contents: to_pretty_json(&redact_value(diagnostics.clone()))?,
+            });
+        }
+    }
+
+    if include.last_debug_captures && !extras.debug_captures.is_empty() {
+        let captures: Vec<Value> = extras
+            .debug_captures
+            .iter()
+            .cloned()
+            .map(redact_value)
+            .collect();
+        entries.push(BundleEntry {
+            name: "debug_captures.json".to_string(),
+            contents: to_pretty_json(&captures)?,
+        });
+    }
+
+    if include.manifest_list {
+        let resource_dir = app
+            .path()
+            .resource_dir()
+            .map_err(|e| format!("Failed to resolve resource dir: {}", e))?;
+        entries.push(BundleEntry {
+            name: "manifests.json".to_string(),
+            contents: to_pretty_json(&json!({
+                "manifests": list_manifests(&resource_dir.join("resources").join("manifests")),
+                "load_errors": extras.manifest_errors,
+            }))?,
+        });
+    }
+
+    if include.model_configs_redacted {
+        let models = load_model_configs(&database_path(&app)?, &profile_id).await?;
+        entries.push(BundleEntry {
+            name: "model_configs.json".to_string(),
+            contents: to_pretty_json(&models)?,
+        });
+    }
+
+    Ok(write_bundle(Path::new(&output_path), entries)?)
+}
+
+// Scrub every entry with one scrubber so a value maps to the same placeholder across files, then zip them
+fn write_bundle(
+    output_path: &Path,
+    entries: Vec<BundleEntry>,
+) -> Result<SupportBundleSummary, String> {
+    let mut scrubber = Scrubber::new(ScrubOptions::default())?;
+    let file =
+        File::create(output_path).map_err(|e| format!("Failed to create support bundle: {}", e))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    let mut files = Vec::new();
+    for entry in entries {
+        let contents = scrubber.scrub(&entry.contents);
+        zip.start_file(entry.name.as_str(), options)
+            .map_err(|e| format!("Failed to add {} to support bundle: {}", entry.name, e))?;
+        zip.write_all(contents.as_bytes())
+            .map_err(|e| format!("Failed to write {} to support bundle: {}", entry.name, e))?;
+        files.push(SupportBundleFile {
+            name: entry.name,
+            bytes: contents.len(),
+        });
+    }
+
+    zip.finish()
+        .map_err(|e| format!("Failed to finish support bundle: {}", e))?;
+
+    Ok(SupportBundleSummary {
+        path: output_path.to_string_lossy().to_string(),
+        files,
+        scrubbed_values: scrubber.into_matches().len(),
+    })
+}
+
+fn to_pretty_json<T: Serialize>(value: &T) -> Result<String, String> {
+    serde_json::to_string_pretty(value)
+        .map_err(|e| format!("Failed to serialize bundle entry: {}", e))
+}
+
+// Newest log files first, skipping old ones and stopping at the size cap
+fn collect_logs(log_dir: &Path) -> Result<Vec<BundleEntry>, String> {
+    let Ok(dir) = fs::read_dir(log_dir) else {
+        return Ok(Vec::new());
+    };
+
+    let now = SystemTime::now();
+    let mut logs: Vec<(PathBuf, SystemTime, u64)> = dir
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let metadata = entry.metadata().ok()?;
+            let modified = metadata.modified().ok()?;
+            let is_log = path.extension().is_some_and(|ext| ext == "log");
+            let is_recent = now
+                .duration_since(modified)
+                .is_ok_and(|age| age <= MAX_LOG_AGE);
+            (metadata.is_file() && is_log && is_recent).then_some((path, modified, metadata.len()))
+        })
+        .collect();
+    logs.sort_by_key(|log| std::cmp::Reverse(log.1));
+
+    let mut total = 0;
+    let mut entries = Vec::new();
+    for (path, _, size) in logs {
+        if total + size > MAX_LOG_BYTES {
+            break;
+        }
+        let Ok(contents) = fs::read_to_string(&path) else {
+            continue;
+        };
+        total += size;
+        entries.push(BundleEntry {
+            name: format!(
+                "logs/{}",
+                path.file_name().unwrap_or_default().to_string_lossy()
+            ),
+            contents: redact_log_lines(&contents),
+        });
+    }
+    Ok(entries)
+}
+
+// Log lines are JSONL; redact the parsed lines and keep anything else as plain text
+fn redact_log_lines(contents: &str) -> String {
+    contents
+        .lines()
+        .map(|line| match serde_json::from_str::<Value>(line) {
+            Ok(value) => serde_json::to_string(&redact_value(value)).unwrap_or_default(),
+            Err(_) => line.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn list_manifests(manifests_dir: &Path) -> Vec<String> {
+    let mut manifests = Vec::new();
+    for kind in MANIFEST_DIRS {
+        let Ok(dir) = fs::read_dir(manifests_dir.join(kind)) else {
+            continue;
+        };
+        for entry in dir.flatten() {
+            manifests.push(format!("{}/{}", kind, entry.file_name().to_string_lossy()));
+        }
+    }
+    manifests.sort();
+    manifests
+}
+
+async fn load_model_configs(db_path: &Path, profile_id: &str) -> Result<Vec<Value>, String> {
+    let mut conn: SqliteConnection = SqliteConnectOptions::new()
+        .filename(db_path)
+        .read_only(true)
+        .connect()
+        .await
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+
+    let rows = sqlx::query(
+        "SELECT id, name, type, manifest_id, config, max_concurrency FROM models WHERE profile_id = $1 ORDER BY name",
+    )
+    .bind(profile_id)
+    .fetch_all(&mut conn)
+    .await
+    .map_err(|e| format!("Failed to read model configs: {}", e))?;
+    let _ = conn.close().await;
+
+    Ok(rows
+        .iter()
+        .map(|row| {
+            let config = serde_json::from_str::<Value>(&row.get::<String, _>("config"))
+                .unwrap_or(Value::Null);
+            json!({
+                "id": row.get::<String, _>("id"),
+                "name": row.get::<String, _>("name"),
+                "type": row.get::<String, _>("type"),
+                "manifest_id": row.get::<String, _>("manifest_id"),
+                "max_concurrency": row.get::<i64, _>("max_concurrency"),
+                "config": redact_value(config),
+            })
+        })
+        .collect())
+}
+
+fn is_sensitive_key(key: &str) -> bool {
+    let key = key.to_lowercase();
+    SENSITIVE_KEYS
+        .iter()
+        .any(|sensitive| key.contains(sensitive))
+}
+
+// Stored secrets are base64(nonce + ciphertext); flag any string with that shape regardless of its key
+fn looks_encrypted(value: &str) -> bool {
+    value.len() >= 40
+        && !value.contains(char::is_whitespace)
+        && BASE64
+            .decode(value)
+            .is_ok_and(|bytes| bytes.len() >= MIN_ENCRYPTED_BYTES)
+}
+
+fn redacted_length(value: &Value) -> Value {
+    let length = match value {
+        Value::String(text) => text.chars().count(),
+        Value::Null => 0,
+        other => other.to_string().len(),
+    };
+    Value::String(format!("[redacted: {} chars]", length))
+}
+
+// Replace secret fields with their lengths so "is the key set" stays answerable without the key itself
+fn redact_value(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| {
+                    if is_sensitive_key(&key) {
+                        let redacted = redacted_length(&value);
+                        (key, redacted)
+                    } else {
+                        (key, redact_value(value))
+                    }
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.into_iter().map(redact_value).collect()),
+        Value::String(text) if looks_encrypted(&text) => redacted_length(&Value::String(text)),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    const PLAINTEXT_KEY: &str = "sk-PLAINTEXT-MARKER-0123456789";
+    const AWS_SECRET: &str = "AWS-SECRET-MARKER-abcdefghij";
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("narratrix-support-{}-{}", std::process::id(), name))
+    }
+
+    fn read_archive(path: &Path) -> String {
+        let mut archive = zip::ZipArchive::new(File::open(path).unwrap()).unwrap();
+        let mut contents = String::new();
+        for index in 0..archive.len() {
+            archive
+                .by_index(index)
+                .unwrap()
+                .read_to_string(&mut contents)
+                .unwrap();
+        }
+        contents
+    }
+
+    #[test]
+    fn bundle_contains_no_plaintext_or_encrypted_secrets() {
+        let encrypted = BASE64.encode([7u8; 64]);
+        let config = json!({
+            "model": "gpt-4o",
+            "api_key": PLAINTEXT_KEY,
+            "aws_secret_access_key": AWS_SECRET,
+            "base_url": "https://api.openai.com/v1",
+            "nested": { "token_like": encrypted },
+        });
+        let log = format!(
+            "{}\nplain line from /home/alice/app\n",
+            json!({ "request_id": "r1", "params": { "apiKey": PLAINTEXT_KEY } })
+        );
+
+        let path = temp_path("secrets.zip");
+        let summary = write_bundle(
+            &path,
+            vec![
+                BundleEntry {
+                    name: "model_configs.json".to_string(),
+                    contents: to_pretty_json(&redact_value(config)).unwrap(),
+                },
+                BundleEntry {
+                    name: "logs/inference.log".to_string(),
+                    contents: redact_log_lines(&log),
+                },
+            ],
+        )
+        .unwrap();
+
+        let raw = fs::read(&path).unwrap();
+        let contents = read_archive(&path);
+        fs::remove_file(&path).unwrap();
+
+        for marker in [PLAINTEXT_KEY, AWS_SECRET, encrypted.as_str(), "/home/alice"] {
+            assert!(!contents.contains(marker), "archive contains {}", marker);
+            assert!(
+                !raw.windows(marker.len()).any(|w| w == marker.as_bytes()),
+                "raw archive contains {}",
+                marker
+            );
+        }
+        assert!(contents.contains(&format!("[redacted: {} chars]", PLAINTEXT_KEY.len())));
+        assert!(contents.contains("gpt-4o"));
+        assert_eq!(summary.files.len(), 2);
+        assert!(summary.scrubbed_values >= 1);
+    }
+
+    #[test]
+    fn short_and_plain_strings_are_kept() {
+        let value = redact_value(
+            json!({ "model": "claude-sonnet-4", "base_url": "http://127.0.0.1:11434" }),
+        );
+        assert_eq!(value["model"], "claude-sonnet-4");
+        assert_eq!(value["base_url"], "http://127.0.0.1:11434");
+    }
+}
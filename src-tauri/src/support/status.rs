@@ -0,0 +1,115 @@
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::{SqliteConnectOptions, SqliteConnection};
+use sqlx::{ConnectOptions, Connection, Row};
+use std::path::Path;
+use tauri::{AppHandle, Manager, State};
+
+use super::list_manifests;
+use crate::database::get_migrations;
+use crate::database::migrator::{
+    database_path, get_migration_status, MigrationState, MigrationStatus,
+};
+use crate::error::AppError;
+use crate::inference::tokenizer::{tokenizer_cache_status, TokenizerCacheEntry};
+use crate::utils::master_key_is_secure;
+use crate::windows::recovery::{window_state_status, WindowStateStatus};
+
+// Inference runs in the frontend, so it reports its own queue
+#[derive(Debug, Deserialize, Serialize, Default, Clone, Copy)]
+#[serde(default)]
+pub struct InferenceTaskCounts {
+    pub active: usize,
+    pub queued: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DatabaseStatus {
+    pub reachable: bool,
+    // Highest applied migration, None if it couldn't be read
+    pub version: Option<i64>,
+    pub latest_version: i64,
+    pub migrations: MigrationStatus,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AppStatus {
+    pub app_version: String,
+    pub database: DatabaseStatus,
+    pub inference: InferenceTaskCounts,
+    pub tokenizers: Vec<TokenizerCacheEntry>,
+    pub master_key_secure: bool,
+    pub manifest_count: usize,
+    pub window_state: WindowStateStatus,
+}
+
+// One snapshot of every subsystem, for the about page and bug reports. Holds no secrets.
+#[tauri::command]
+pub async fn get_app_status(
+    app: AppHandle,
+    migration_state: State<'_, MigrationState>,
+    inference: Option<InferenceTaskCounts>,
+) -> Result<AppStatus, AppError> {
+    let migrations = get_migration_status(migration_state);
+    let latest_version = get_migrations()
+        .iter()
+        .map(|migration| migration.version)
+        .max()
+        .unwrap_or(0);
+
+    let database = match read_database_version(&database_path(&app)?).await {
+        Ok(version) => DatabaseStatus {
+            reachable: true,
+            version,
+            latest_version,
+            migrations,
+            error: None,
+        },
+        Err(error) => DatabaseStatus {
+            reachable: false,
+            version: None,
+            latest_version,
+            migrations,
+            error: Some(error),
+        },
+    };
+
+    let resource_dir = app
+        .path()
+        .resource_dir()
+        .map_err(|e| format!("Failed to resolve resource dir: {}", e))?;
+
+    Ok(AppStatus {
+        app_version: app.package_info().version.to_string(),
+        database,
+        inference: inference.unwrap_or_default(),
+        tokenizers: tokenizer_cache_status(&app)?,
+        master_key_secure: master_key_is_secure(),
+        manifest_count: list_manifests(&resource_dir.join("resources").join("manifests")).len(),
+        window_state: window_state_status(&app),
+    })
+}
+
+// Read-only, so checking the status never creates or locks the database
+async fn read_database_version(db_path: &Path) -> Result<Option<i64>, String> {
+    if !db_path.exists() {
+        return Err("Database file does not exist".to_string());
+    }
+
+    let mut conn: SqliteConnection = SqliteConnectOptions::new()
+        .filename(db_path)
+        .read_only(true)
+        .connect()
+        .await
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+
+    let version =
+        sqlx::query("SELECT MAX(version) AS version FROM _sqlx_migrations WHERE success = 1")
+            .fetch_one(&mut conn)
+            .await
+            .map(|row| row.get::<Option<i64>, _>("version"))
+            .map_err(|e| format!("Failed to read schema version: {}", e));
+
+    let _ = conn.close().await;
+    version
+}
@@ -2,34 +2,44 @@ use aes_gcm::{
     aead::{Aead, KeyInit},
     Aes256Gcm, Nonce,
 };
+use argon2::password_hash::rand_core::{OsRng, RngCore};
 use argon2::{
     password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
     Argon2,
 };
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use env_vars::get_master_key;
-use argon2::password_hash::rand_core::{OsRng, RngCore};
 use std::convert::TryInto; // Required for try_into()
 
+use crate::error::{AppError, ErrorCode};
+
 mod env_vars;
 
+// Value used when no MASTER_KEY is configured. Keys encrypted with it are only obfuscated.
+const DEFAULT_MASTER_KEY: &str = "MASTER_KEY";
+
+// Whether a real master key was configured at build time or in the environment. Never exposes the key.
+pub fn master_key_is_secure() -> bool {
+    get_master_key(DEFAULT_MASTER_KEY) != DEFAULT_MASTER_KEY
+}
+
 // Helper function to hash a password using Argon2
 #[tauri::command(scope = "app")]
-pub fn hash_password(password: &str) -> Result<String, String> {
+pub fn hash_password(password: &str) -> Result<String, AppError> {
     let salt = SaltString::generate(&mut OsRng);
     let argon2 = Argon2::default();
 
     argon2
         .hash_password(password.as_bytes(), &salt)
         .map(|hash| hash.to_string())
-        .map_err(|e| format!("Failed to hash password: {}", e))
+        .map_err(|e| AppError::internal(format!("Failed to hash password: {}", e)))
 }
 
 // Helper function to verify a password against its hash
 #[tauri::command(scope = "app")]
-pub fn verify_password(password: &str, hash: &str) -> Result<bool, String> {
-    let parsed_hash =
-        PasswordHash::new(hash).map_err(|e| format!("Failed to parse password hash: {}", e))?;
+pub fn verify_password(password: &str, hash: &str) -> Result<bool, AppError> {
+    let parsed_hash = PasswordHash::new(hash)
+        .map_err(|e| AppError::validation(format!("Failed to parse password hash: {}", e)))?;
 
     Ok(Argon2::default()
         .verify_password(password.as_bytes(), &parsed_hash)
@@ -38,7 +48,7 @@ pub fn verify_password(password: &str, hash: &str) -> Result<bool, String> {
 
 // Helper function to encrypt an API key
 #[tauri::command(scope = "app")]
-pub fn encrypt_api_key(api_key: &str) -> Result<String, String> {
+pub fn encrypt_api_key(api_key: &str) -> Result<String, AppError> {
     // Generate a random salt for this encryption
     let salt = SaltString::generate(&mut OsRng);
     // Use the full PHC string representation
@@ -75,15 +85,17 @@ pub fn encrypt_api_key(api_key: &str) -> Result<String, String> {
 
 // Helper function to decrypt an API key
 #[tauri::command(scope = "app")]
-pub fn decrypt_api_key(encrypted_api_key: &str) -> Result<String, String> {
+pub fn decrypt_api_key(encrypted_api_key: &str) -> Result<String, AppError> {
     // Decode the base64 string
     let combined = BASE64
         .decode(encrypted_api_key)
-        .map_err(|e| format!("Failed to decode base64: {}", e))?;
+        .map_err(|e| AppError::validation(format!("Failed to decode base64: {}", e)))?;
 
     // Minimum length: 4 (len) + 0 (salt) + 12 (nonce)
     if combined.len() < 16 {
-        return Err("Invalid encrypted data format: too short".to_string());
+        return Err(AppError::validation(
+            "Invalid encrypted data format: too short",
+        ));
     }
 
     // Extract salt length (first 4 bytes)
@@ -98,11 +110,11 @@ pub fn decrypt_api_key(encrypted_api_key: &str) -> Result<String, String> {
 
     // Check if combined length is sufficient for salt, nonce, and potentially ciphertext
     if combined.len() < nonce_end_index {
-        return Err(format!(
+        return Err(AppError::validation(format!(
             "Invalid encrypted data format: length mismatch. Expected at least {}, got {}",
             nonce_end_index,
             combined.len()
-        ));
+        )));
     }
 
     // Extract salt bytes
@@ -122,25 +134,27 @@ pub fn decrypt_api_key(encrypted_api_key: &str) -> Result<String, String> {
     let nonce_bytes = &combined[salt_end_index..nonce_end_index];
     // Ensure nonce is exactly 12 bytes before creating Nonce slice
     if nonce_bytes.len() != 12 {
-        return Err(format!(
+        return Err(AppError::validation(format!(
             "Invalid nonce length: expected 12, got {}",
             nonce_bytes.len()
-        ));
+        )));
     }
     let nonce = Nonce::from_slice(nonce_bytes); // Safe now
     let ciphertext = &combined[nonce_end_index..];
 
-    // Decrypt the API key
+    // Decrypt the API key. The authentication tag only fails to match when the key was
+    // encrypted under another master key (or tampered with)
     let plaintext = cipher
         .decrypt(nonce, ciphertext)
-        .map_err(|e| format!("Decryption failed: {}", e))?;
+        .map_err(|e| AppError::new(ErrorCode::Unauthorized, format!("Decryption failed: {}", e)))?;
 
-    String::from_utf8(plaintext)
-        .map_err(|e| format!("Failed to convert decrypted data to string: {}", e))
+    String::from_utf8(plaintext).map_err(|e| {
+        AppError::internal(format!("Failed to convert decrypted data to string: {}", e))
+    })
 }
 
 fn derive_encryption_key_with_salt(salt_phc_string: &str) -> Result<[u8; 32], String> {
-    let master_key = get_master_key("MASTER_KEY");
+    let master_key = get_master_key(DEFAULT_MASTER_KEY);
 
     // Parse the full PHC string. Use 'new' as it handles PHC format.
     let salt = SaltString::from_b64(salt_phc_string)
@@ -0,0 +1,155 @@
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+use std::net::IpAddr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri_plugin_http::reqwest::{self, redirect, StatusCode};
+
+use crate::error::AppError;
+use crate::utils::decrypt_api_key;
+
+const MAX_ATTEMPTS: u32 = 3;
+const INITIAL_BACKOFF_MS: u64 = 1000;
+const REQUEST_TIMEOUT_SECS: u64 = 10;
+
+// Events the frontend is allowed to dispatch
+const WEBHOOK_EVENTS: &[&str] = &[
+    "inference.completed",
+    "inference.error",
+    "chat.message_added",
+];
+
+#[derive(Debug, Serialize)]
+pub struct WebhookDeliveryResult {
+    pub delivered: bool,
+    pub status_code: Option<u16>,
+    pub attempts: u32,
+    pub error: Option<String>,
+}
+
+// POST an event payload to a webhook, signed with HMAC-SHA256 when a secret is configured.
+// The secret arrives encrypted and is only decrypted here, never in the webview.
+#[tauri::command]
+pub async fn deliver_webhook(
+    url: String,
+    encrypted_secret: Option<String>,
+    event: String,
+    payload: serde_json::Value,
+    allow_insecure: Option<bool>,
+) -> Result<WebhookDeliveryResult, AppError> {
+    if !WEBHOOK_EVENTS.contains(&event.as_str()) {
+        return Err(AppError::validation(format!(
+            "Unknown webhook event: {}",
+            event
+        )));
+    }
+
+    let target = reqwest::Url::parse(url.trim())
+        .map_err(|e| AppError::validation(format!("Invalid URL: {}", e)))?;
+    check_target(&target, allow_insecure.unwrap_or(false)).map_err(AppError::validation)?;
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| format!("Failed to read system time: {}", e))?
+        .as_secs();
+    let body =
+        serde_json::to_vec(&payload).map_err(|e| format!("Failed to serialize payload: {}", e))?;
+
+    let signature = match encrypted_secret.as_deref().filter(|s| !s.is_empty()) {
+        Some(encrypted) => Some(sign(&decrypt_api_key(encrypted)?, timestamp, &body)?),
+        None => None,
+    };
+
+    // Redirects are not followed so a target can't bounce the payload to another host
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .redirect(redirect::Policy::none())
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    let mut result = WebhookDeliveryResult {
+        delivered: false,
+        status_code: None,
+        attempts: 0,
+        error: None,
+    };
+
+    while result.attempts < MAX_ATTEMPTS {
+        if result.attempts > 0 {
+            let backoff = INITIAL_BACKOFF_MS * 2u64.pow(result.attempts - 1);
+            tokio::time::sleep(Duration::from_millis(backoff)).await;
+        }
+        result.attempts += 1;
+
+        let mut request = client
+            .post(target.clone())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header("X-Narratrix-Event", &event)
+            .header("X-Narratrix-Timestamp", timestamp.to_string())
+            .body(body.clone());
+        if let Some(signature) = &signature {
+            request = request.header("X-Narratrix-Signature", format!("sha256={}", signature));
+        }
+
+        match request.send().await {
+            Ok(response) => {
+                let status = response.status();
+                result.status_code = Some(status.as_u16());
+                if status.is_success() {
+                    result.delivered = true;
+                    result.error = None;
+                    break;
+                }
+                result.error = Some(format!("Server responded with status {}", status));
+                if !is_retryable(status) {
+                    break;
+                }
+            }
+            Err(e) => {
+                result.status_code = None;
+                result.error = Some(format!("Request failed: {}", e));
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+// HTTPS anywhere, plain HTTP only for loopback unless the webhook opts out
+fn check_target(url: &reqwest::Url, allow_insecure: bool) -> Result<(), String> {
+    match url.scheme() {
+        "https" => Ok(()),
+        "http" if allow_insecure || is_loopback(url) => Ok(()),
+        "http" => Err("Only HTTPS or localhost webhook URLs are allowed".to_string()),
+        scheme => Err(format!("Unsupported URL scheme: {}", scheme)),
+    }
+}
+
+fn is_loopback(url: &reqwest::Url) -> bool {
+    let Some(host) = url.host_str() else {
+        return false;
+    };
+    if host.eq_ignore_ascii_case("localhost") {
+        return true;
+    }
+    // IPv6 hosts come back bracketed ("[::1]")
+    host.trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse::<IpAddr>()
+        .is_ok_and(|ip| ip.is_loopback())
+}
+
+// Retry on rate limiting and server errors, other client errors won't change on a resend
+fn is_retryable(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+// Signature covers "<timestamp>.<body>" so receivers can reject replayed deliveries
+fn sign(secret: &str, timestamp: u64, body: &[u8]) -> Result<String, String> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .map_err(|e| format!("Invalid webhook secret: {}", e))?;
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    Ok(hex::encode(mac.finalize().into_bytes()))
+}
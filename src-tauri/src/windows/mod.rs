@@ -0,0 +1,232 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, State, WebviewUrl, WebviewWindowBuilder, Window, WindowEvent};
+
+use crate::error::{AppError, ErrorCode};
+
+pub mod recovery;
+
+const MAIN_WINDOW_LABEL: &str = "main";
+const PROFILE_WINDOW_PREFIX: &str = "profile-";
+
+// Window label -> profile id. Profile windows are locked to the profile they were opened for,
+// the main window can switch profiles on logout/login. Only commands check the binding: the
+// frontend queries SQLite through tauri_plugin_sql, which every window is granted and which has
+// no notion of profiles, so that path relies on the profile filter of each query.
+#[derive(Default)]
+pub struct ProfileWindows(Mutex<HashMap<String, String>>);
+
+impl ProfileWindows {
+    fn bind(&self, label: &str, profile_id: &str) -> Result<(), AppError> {
+        let mut windows = self
+            .0
+            .lock()
+            .map_err(|e| format!("Failed to lock window state: {}", e))?;
+
+        if let Some(bound_profile) = windows.get(label) {
+            if bound_profile != profile_id && label != MAIN_WINDOW_LABEL {
+                return Err(AppError::new(
+                    ErrorCode::Unauthorized,
+                    "This window is bound to a different profile",
+                ));
+            }
+        }
+
+        windows.insert(label.to_string(), profile_id.to_string());
+        Ok(())
+    }
+
+    fn unbind(&self, label: &str) {
+        if let Ok(mut windows) = self.0.lock() {
+            windows.remove(label);
+        }
+    }
+
+    // Whether the window may act on the profile. The main window is unbound until login and may
+    // then only use its own profile; profile windows are bound from creation.
+    fn verify(&self, label: &str, profile_id: &str) -> Result<(), String> {
+        let windows = self
+            .0
+            .lock()
+            .map_err(|e| format!("Failed to lock window state: {}", e))?;
+
+        match windows.get(label) {
+            Some(bound_profile) if bound_profile == profile_id => Ok(()),
+            Some(_) => Err("This window is bound to a different profile".to_string()),
+            None if label == MAIN_WINDOW_LABEL => Ok(()),
+            None => Err("This window is not bound to a profile".to_string()),
+        }
+    }
+
+    // Whether the window may run maintenance over every profile's data
+    fn verify_unrestricted(&self, label: &str) -> Result<(), String> {
+        if label == MAIN_WINDOW_LABEL {
+            Ok(())
+        } else {
+            Err("Only the main window can change data of every profile".to_string())
+        }
+    }
+
+    fn profile_for(&self, label: &str) -> Option<String> {
+        self.0.lock().ok()?.get(label).cloned()
+    }
+
+    fn window_for(&self, profile_id: &str) -> Option<String> {
+        self.0
+            .lock()
+            .ok()?
+            .iter()
+            .find(|(label, bound_profile)| {
+                label.starts_with(PROFILE_WINDOW_PREFIX) && bound_profile.as_str() == profile_id
+            })
+            .map(|(label, _)| label.clone())
+    }
+}
+
+// Reject commands whose profile_id isn't the profile the calling window is bound to
+pub fn verify_window_profile(window: &Window, profile_id: &str) -> Result<(), AppError> {
+    window
+        .state::<ProfileWindows>()
+        .verify(window.label(), profile_id)
+        .map_err(|message| AppError::new(ErrorCode::Unauthorized, message))
+}
+
+// Reject database-wide commands from windows locked to a single profile
+pub fn verify_unrestricted_window(window: &Window) -> Result<(), AppError> {
+    window
+        .state::<ProfileWindows>()
+        .verify_unrestricted(window.label())
+        .map_err(|message| AppError::new(ErrorCode::Unauthorized, message))
+}
+
+// Open a new window locked to a profile, or focus the one that is already open. Only an
+// unrestricted window may do it, so a profile window can't reach the data of another profile by
+// opening a window for it.
+#[tauri::command]
+pub async fn create_profile_window(
+    app: AppHandle,
+    window: Window,
+    windows: State<'_, ProfileWindows>,
+    profile_id: String,
+) -> Result<String, AppError> {
+    verify_unrestricted_window(&window)?;
+    // Window labels only accept alphanumerics, '-', '/', ':' and '_'
+    if profile_id.is_empty()
+        || !profile_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-')
+    {
+        return Err(AppError::validation("Invalid profile id"));
+    }
+
+    if let Some(label) = windows.window_for(&profile_id) {
+        if let Some(window) = app.get_webview_window(&label) {
+            window
+                .set_focus()
+                .map_err(|e| format!("Failed to focus window: {}", e))?;
+            return Ok(label);
+        }
+        windows.unbind(&label);
+    }
+
+    let label = format!("{}{}", PROFILE_WINDOW_PREFIX, profile_id);
+    windows.bind(&label, &profile_id)?;
+
+    let window = WebviewWindowBuilder::new(&app, &label, WebviewUrl::App("index.html".into()))
+        .title("Narratrix")
+        .inner_size(1280.0, 800.0)
+        .build()
+        .map_err(|e| {
+            windows.unbind(&label);
+            format!("Failed to create window: {}", e)
+        })?;
+
+    let app_handle = app.clone();
+    let closed_label = label.clone();
+    window.on_window_event(move |event| {
+        if let WindowEvent::Destroyed = event {
+            app_handle.state::<ProfileWindows>().unbind(&closed_label);
+        }
+    });
+
+    Ok(label)
+}
+
+// Bind the calling window to a profile after login. Fails if the window is locked to another profile.
+#[tauri::command]
+pub fn bind_window_profile(
+    window: Window,
+    windows: State<'_, ProfileWindows>,
+    profile_id: String,
+) -> Result<(), AppError> {
+    windows.bind(window.label(), &profile_id)
+}
+
+// Release the main window's profile on logout. Profile windows keep their binding until closed.
+#[tauri::command]
+pub fn unbind_window_profile(window: Window, windows: State<'_, ProfileWindows>) {
+    if window.label() == MAIN_WINDOW_LABEL {
+        windows.unbind(window.label());
+    }
+}
+
+// Profile the calling window is bound to, if any
+#[tauri::command]
+pub fn get_window_profile(window: Window, windows: State<'_, ProfileWindows>) -> Option<String> {
+    windows.profile_for(window.label())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profile_windows_stay_on_their_profile() {
+        let windows = ProfileWindows::default();
+        windows.bind("profile-p1", "p1").unwrap();
+
+        assert!(windows.bind("profile-p1", "p1").is_ok());
+        assert!(windows.bind("profile-p1", "p2").is_err());
+        assert_eq!(windows.profile_for("profile-p1").as_deref(), Some("p1"));
+        assert_eq!(windows.window_for("p1").as_deref(), Some("profile-p1"));
+        // The main window isn't a profile window, even when bound to the same profile
+        windows.bind(MAIN_WINDOW_LABEL, "p2").unwrap();
+        assert_eq!(windows.window_for("p2"), None);
+    }
+
+    #[test]
+    fn main_window_switches_profiles() {
+        let windows = ProfileWindows::default();
+        windows.bind(MAIN_WINDOW_LABEL, "p1").unwrap();
+        windows.bind(MAIN_WINDOW_LABEL, "p2").unwrap();
+        assert_eq!(
+            windows.profile_for(MAIN_WINDOW_LABEL).as_deref(),
+            Some("p2")
+        );
+
+        windows.unbind(MAIN_WINDOW_LABEL);
+        assert_eq!(windows.profile_for(MAIN_WINDOW_LABEL), None);
+    }
+
+    #[test]
+    fn verifies_the_calling_window() {
+        let windows = ProfileWindows::default();
+        windows.bind("profile-p1", "p1").unwrap();
+        windows.bind(MAIN_WINDOW_LABEL, "p2").unwrap();
+
+        assert!(windows.verify("profile-p1", "p1").is_ok());
+        assert!(windows.verify("profile-p1", "p2").is_err());
+        assert!(windows.verify(MAIN_WINDOW_LABEL, "p2").is_ok());
+        assert!(windows.verify(MAIN_WINDOW_LABEL, "p1").is_err());
+        // Before login the main window can still reach the profiles, other windows never can
+        windows.unbind(MAIN_WINDOW_LABEL);
+        assert!(windows.verify(MAIN_WINDOW_LABEL, "p1").is_ok());
+        assert!(windows.verify("profile-p3", "p3").is_err());
+        // A closed profile window loses its access
+        windows.unbind("profile-p1");
+        assert!(windows.verify("profile-p1", "p1").is_err());
+
+        assert!(windows.verify_unrestricted(MAIN_WINDOW_LABEL).is_ok());
+        assert!(windows.verify_unrestricted("profile-p1").is_err());
+    }
+}
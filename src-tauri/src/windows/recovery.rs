@@ -0,0 +1,340 @@
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, Monitor, PhysicalPosition, PhysicalSize, WebviewWindow};
+use tauri_plugin_window_state::DEFAULT_FILENAME;
+
+use super::MAIN_WINDOW_LABEL;
+use crate::error::AppError;
+
+const RECOVERED_EVENT: &str = "window-state-recovered";
+// How much of the window must be on a monitor to count as reachable, enough to grab the title bar
+const MIN_VISIBLE_PX: i64 = 48;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Bounds {
+    fn of_monitor(monitor: &Monitor) -> Self {
+        let position = monitor.position();
+        let size = monitor.size();
+        Bounds {
+            x: position.x,
+            y: position.y,
+            width: size.width,
+            height: size.height,
+        }
+    }
+
+    fn of_window(window: &WebviewWindow) -> Result<Self, String> {
+        let position = window
+            .outer_position()
+            .map_err(|e| format!("Failed to read window position: {}", e))?;
+        let size = window
+            .outer_size()
+            .map_err(|e| format!("Failed to read window size: {}", e))?;
+        Ok(Bounds {
+            x: position.x,
+            y: position.y,
+            width: size.width,
+            height: size.height,
+        })
+    }
+
+    fn right(&self) -> i64 {
+        self.x as i64 + self.width as i64
+    }
+
+    fn bottom(&self) -> i64 {
+        self.y as i64 + self.height as i64
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RecoveryReason {
+    // The restored bounds were on no connected monitor
+    OffScreen,
+    // reset_window_state was called
+    Reset,
+}
+
+// Payload of the window-state-recovered event, kept for the app status
+#[derive(Debug, Serialize, Clone)]
+pub struct WindowStateRecovered {
+    pub label: String,
+    pub reason: RecoveryReason,
+    pub previous: Bounds,
+    pub restored: Bounds,
+    pub monitors: Vec<Bounds>,
+}
+
+#[derive(Default)]
+struct RecoveryRecord {
+    // Last recovery since launch, None while the window was never moved back
+    last: Option<WindowStateRecovered>,
+    // Why the position couldn't be checked at startup
+    check_error: Option<String>,
+}
+
+#[derive(Default)]
+pub struct WindowRecovery(Mutex<RecoveryRecord>);
+
+#[derive(Debug, Serialize)]
+pub struct WindowStateStatus {
+    // None when the main window or the monitors couldn't be read
+    pub on_screen: Option<bool>,
+    pub last_recovery: Option<WindowStateRecovered>,
+    pub check_error: Option<String>,
+}
+
+// Whether enough of the window overlaps one monitor to be reached with the mouse
+fn is_reachable(window: &Bounds, monitors: &[Bounds]) -> bool {
+    monitors.iter().any(|monitor| {
+        let overlap_width =
+            window.right().min(monitor.right()) - (window.x as i64).max(monitor.x as i64);
+        let overlap_height =
+            window.bottom().min(monitor.bottom()) - (window.y as i64).max(monitor.y as i64);
+        overlap_width >= MIN_VISIBLE_PX.min(window.width as i64)
+            && overlap_height >= MIN_VISIBLE_PX.min(window.height as i64)
+    })
+}
+
+// The window centered on the monitor, shrunk to fit when it is larger
+fn centered_in(monitor: &Bounds, width: u32, height: u32) -> Bounds {
+    let width = width.min(monitor.width);
+    let height = height.min(monitor.height);
+    Bounds {
+        x: monitor.x + ((monitor.width - width) / 2) as i32,
+        y: monitor.y + ((monitor.height - height) / 2) as i32,
+        width,
+        height,
+    }
+}
+
+fn monitor_bounds(window: &WebviewWindow) -> Result<Vec<Bounds>, String> {
+    window
+        .available_monitors()
+        .map(|monitors| monitors.iter().map(Bounds::of_monitor).collect())
+        .map_err(|e| format!("Failed to list monitors: {}", e))
+}
+
+// Move the window to the middle of the primary monitor (the first one when none is marked primary)
+fn recenter(window: &WebviewWindow, monitors: &[Bounds]) -> Result<Bounds, String> {
+    let primary = window
+        .primary_monitor()
+        .map_err(|e| format!("Failed to read the primary monitor: {}", e))?
+        .map(|monitor| Bounds::of_monitor(&monitor))
+        .or_else(|| monitors.first().copied())
+        .ok_or_else(|| "No monitor is connected".to_string())?;
+
+    if window.is_maximized().unwrap_or(false) {
+        window
+            .unmaximize()
+            .map_err(|e| format!("Failed to unmaximize window: {}", e))?;
+    }
+
+    let outer = window
+        .outer_size()
+        .map_err(|e| format!("Failed to read window size: {}", e))?;
+    let target = centered_in(&primary, outer.width, outer.height);
+    if target.width != outer.width || target.height != outer.height {
+        // set_size takes the inner size, so the frame is taken off
+        let inner = window
+            .inner_size()
+            .map_err(|e| format!("Failed to read window size: {}", e))?;
+        let frame_width = outer.width.saturating_sub(inner.width);
+        let frame_height = outer.height.saturating_sub(inner.height);
+        window
+            .set_size(PhysicalSize::new(
+                target.width.saturating_sub(frame_width),
+                target.height.saturating_sub(frame_height),
+            ))
+            .map_err(|e| format!("Failed to resize window: {}", e))?;
+    }
+    window
+        .set_position(PhysicalPosition::new(target.x, target.y))
+        .map_err(|e| format!("Failed to move window: {}", e))?;
+
+    Ok(target)
+}
+
+fn record_recovery(app: &AppHandle, recovery: WindowStateRecovered) {
+    if let Ok(mut record) = app.state::<WindowRecovery>().0.lock() {
+        record.last = Some(recovery.clone());
+    }
+    // The recovery stays in the app status when no window is listening yet
+    let _ = app.emit(RECOVERED_EVENT, recovery);
+}
+
+// After the window-state plugin restored the main window, move it back when it ended up on a monitor
+// that is no longer connected. A failure is kept for the app status rather than stopping startup.
+pub fn ensure_main_window_visible(app: &AppHandle) {
+    if let Err(error) = recover_main_window(app) {
+        if let Ok(mut record) = app.state::<WindowRecovery>().0.lock() {
+            record.check_error = Some(error);
+        }
+    }
+}
+
+// Minimized windows report placeholder coordinates and are left alone
+fn recover_main_window(app: &AppHandle) -> Result<(), String> {
+    let Some(window) = app.get_webview_window(MAIN_WINDOW_LABEL) else {
+        return Ok(());
+    };
+    if window.is_minimized().unwrap_or(false) {
+        return Ok(());
+    }
+
+    let monitors = monitor_bounds(&window)?;
+    let previous = Bounds::of_window(&window)?;
+    // Without any monitor reported there is nothing to compare against
+    if monitors.is_empty() || is_reachable(&previous, &monitors) {
+        return Ok(());
+    }
+
+    let restored = recenter(&window, &monitors)?;
+    record_recovery(
+        app,
+        WindowStateRecovered {
+            label: MAIN_WINDOW_LABEL.to_string(),
+            reason: RecoveryReason::OffScreen,
+            previous,
+            restored,
+            monitors,
+        },
+    );
+    Ok(())
+}
+
+// Forget the saved window geometry and put the main window back in the middle of the primary monitor
+#[tauri::command]
+pub fn reset_window_state(app: AppHandle) -> Result<Bounds, AppError> {
+    let state_file = app
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("Failed to resolve config dir: {}", e))?
+        .join(DEFAULT_FILENAME);
+    if state_file.exists() {
+        std::fs::remove_file(&state_file)
+            .map_err(|e| format!("Failed to remove saved window state: {}", e))?;
+    }
+
+    let window = app
+        .get_webview_window(MAIN_WINDOW_LABEL)
+        .ok_or_else(|| AppError::not_found("Main window not found"))?;
+    if window.is_minimized().unwrap_or(false) {
+        window
+            .unminimize()
+            .map_err(|e| format!("Failed to restore window: {}", e))?;
+    }
+
+    let monitors = monitor_bounds(&window)?;
+    let previous = Bounds::of_window(&window)?;
+    let restored = recenter(&window, &monitors)?;
+    record_recovery(
+        &app,
+        WindowStateRecovered {
+            label: MAIN_WINDOW_LABEL.to_string(),
+            reason: RecoveryReason::Reset,
+            previous,
+            restored,
+            monitors,
+        },
+    );
+    Ok(restored)
+}
+
+// Window placement for the app status: whether the main window is reachable, the last recovery
+// and why the startup check failed, if it did
+pub fn window_state_status(app: &AppHandle) -> WindowStateStatus {
+    let on_screen = app
+        .get_webview_window(MAIN_WINDOW_LABEL)
+        .and_then(|window| {
+            let monitors = monitor_bounds(&window).ok()?;
+            let bounds = Bounds::of_window(&window).ok()?;
+            Some(monitors.is_empty() || is_reachable(&bounds, &monitors))
+        });
+    let (last_recovery, check_error) = app
+        .state::<WindowRecovery>()
+        .0
+        .lock()
+        .map(|record| (record.last.clone(), record.check_error.clone()))
+        .unwrap_or_default();
+
+    WindowStateStatus {
+        on_screen,
+        last_recovery,
+        check_error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LAPTOP: Bounds = Bounds {
+        x: 0,
+        y: 0,
+        width: 1920,
+        height: 1080,
+    };
+    // A second monitor to the left of the laptop screen
+    const LEFT_MONITOR: Bounds = Bounds {
+        x: -2560,
+        y: 0,
+        width: 2560,
+        height: 1440,
+    };
+
+    fn window(x: i32, y: i32) -> Bounds {
+        Bounds {
+            x,
+            y,
+            width: 1280,
+            height: 800,
+        }
+    }
+
+    #[test]
+    fn window_on_a_connected_monitor_is_reachable() {
+        assert!(is_reachable(&window(100, 100), &[LAPTOP]));
+        assert!(is_reachable(&window(-1800, 200), &[LEFT_MONITOR, LAPTOP]));
+        // Mostly off the right edge, but the title bar can still be grabbed
+        assert!(is_reachable(&window(1800, 100), &[LAPTOP]));
+    }
+
+    #[test]
+    fn window_left_on_a_disconnected_monitor_is_not() {
+        assert!(!is_reachable(&window(-1800, 200), &[LAPTOP]));
+        assert!(!is_reachable(&window(1900, 100), &[LAPTOP]));
+        assert!(!is_reachable(&window(100, 5000), &[LAPTOP, LEFT_MONITOR]));
+        assert!(!is_reachable(&window(100, 100), &[]));
+    }
+
+    #[test]
+    fn recentering_fits_the_window_on_the_monitor() {
+        assert_eq!(
+            centered_in(&LAPTOP, 1280, 800),
+            Bounds {
+                x: 320,
+                y: 140,
+                width: 1280,
+                height: 800,
+            }
+        );
+        assert_eq!(
+            centered_in(&LEFT_MONITOR, 3000, 1000),
+            Bounds {
+                x: -2560,
+                y: 220,
+                width: 2560,
+                height: 1000,
+            }
+        );
+    }
+}
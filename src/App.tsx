@@ -1,11 +1,14 @@
 import React, { useEffect } from "react";
 import { InferenceServiceProvider } from "@/providers/inferenceChatProvider";
 import Content from "./components/layout/Content";
+import MigrationGate from "./components/layout/MigrationGate";
 import Sidebar from "./components/layout/Sidebar";
 import { Toaster } from "./components/ui/sonner";
 import { TooltipProvider } from "./components/ui/tooltip";
 import { useCurrentProfile, useInitializeProfiles, useIsAuthenticated, useProfileSynchronization } from "./hooks/ProfileStore";
 import { initializeTheme } from "./hooks/ThemeContext";
+import { useAutoImportWatcher } from "./hooks/useAutoImportWatcher";
+import { useIdleMemoryTrim } from "./hooks/useIdleMemoryTrim";
 import ProfilePicker from "./pages/profileLogin/ProfilePage";
 import { checkForUpdates } from "./services/updater";
 
@@ -15,6 +18,8 @@ const AppContent: React.FC = () => {
 
   // Initialize profile synchronization
   useProfileSynchronization();
+  useAutoImportWatcher();
+  useIdleMemoryTrim();
 
   // Show Profile Picker if no profile is logged in
   if (!currentProfile || !isAuthenticated) {
@@ -32,10 +37,13 @@ const AppContent: React.FC = () => {
   );
 };
 
-const App: React.FC = () => {
-  // Initialize profiles on app startup
+// Profiles are loaded from the database, so this only mounts once migrations are applied
+const ProfileInitializer: React.FC = () => {
   useInitializeProfiles();
+  return <AppContent />;
+};
 
+const App: React.FC = () => {
   useEffect(() => {
     // Initialize theme system
     initializeTheme();
@@ -46,7 +54,9 @@ const App: React.FC = () => {
 
   return (
     <TooltipProvider>
-      <AppContent />
+      <MigrationGate>
+        <ProfileInitializer />
+      </MigrationGate>
       <Toaster richColors closeButton position="bottom-right" />
     </TooltipProvider>
   );
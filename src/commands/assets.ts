@@ -0,0 +1,66 @@
+import { invoke } from "@tauri-apps/api/core";
+import { invokeCommand } from "./errors";
+
+export interface StoredAsset {
+  hash: string;
+  /** Relative to the app data dir, e.g. objects/ab/ab12....png */
+  path: string;
+  size: number;
+}
+
+export interface AssetGcReport {
+  removed: number;
+  freed_bytes: number;
+}
+
+/**
+ * Write a file into the content-addressed asset store. Identical content is stored once.
+ * @param dataBase64 File contents as base64 (no data URL prefix)
+ * @param extension File extension without the dot
+ */
+export function storeAsset(dataBase64: string, extension: string): Promise<StoredAsset> {
+  return invokeCommand<StoredAsset>("store_asset", { dataBase64, extension });
+}
+
+/**
+ * Remove stored objects that are no longer referenced by any profile or character
+ */
+export function garbageCollectAssets(): Promise<AssetGcReport> {
+  return invokeCommand<AssetGcReport>("garbage_collect_assets");
+}
+
+export interface OrphanInfo {
+  /** Relative to the app data dir, e.g. images/characters/<id>/happy.png */
+  path: string;
+  size: number;
+  /** Milliseconds since epoch */
+  modified_ms: number | null;
+}
+
+/**
+ * List image files (asset store, legacy images folder and legacy profile avatars) that no profile or character
+ * references anymore. Files written in the last hour are left out, their row may still be saving.
+ * Only the main window may call it, the files are shared by every profile.
+ */
+export function findOrphanedFiles(profileId: string): Promise<OrphanInfo[]> {
+  return invokeCommand<OrphanInfo[]>("find_orphaned_files", { profileId });
+}
+
+/**
+ * Delete the files `findOrphanedFiles` reports, rescanned on the Rust side. Rejects unless `confirm` is true.
+ * @returns How many files were removed and the bytes reclaimed
+ */
+export function cleanupOrphanedFiles(profileId: string, confirm: boolean): Promise<AssetGcReport> {
+  return invokeCommand<AssetGcReport>("cleanup_orphaned_files", { profileId, confirm });
+}
+
+/**
+ * Turn user text (a character or template name) into a file name valid on every OS: unsafe characters replaced,
+ * Windows device names escaped, length bounded and Unicode normalized to NFC
+ * @param name Text to base the name on
+ * @param extension File extension without the dot
+ * @param unique Append a short hash of the name so different names never collide
+ */
+export function sanitizeFileName(name: string, extension: string, unique = false): Promise<string> {
+  return invoke<string>("sanitize_file_name", { name, extension, unique });
+}
@@ -0,0 +1,179 @@
+import type { z } from "zod";
+import type { Chat } from "@/schema/chat-schema";
+import { invokeCommand } from "./errors";
+
+export interface OrphanCategory {
+  category: string;
+  /** Human readable, e.g. "messages without a chat" */
+  label: string;
+  /** delete removes the row, detach only clears a dangling optional reference */
+  action: "delete" | "detach";
+  count: number;
+}
+
+export interface OrphanReport {
+  dry_run: boolean;
+  /** Only categories with at least one row */
+  categories: OrphanCategory[];
+  total: number;
+  /** Copy of the database taken before the repair, null for dry runs or when nothing was changed */
+  backup_path: string | null;
+}
+
+/**
+ * Find rows whose parent was deleted (messages without chats, characters without profiles...)
+ * @param dryRun Only count them. Otherwise they are deleted or detached in one transaction, after a backup.
+ */
+export function repairOrphans(dryRun: boolean): Promise<OrphanReport> {
+  return invokeCommand<OrphanReport>("repair_orphans", { dryRun });
+}
+
+/**
+ * Read an app-wide setting (not tied to a profile, available before login)
+ * @param key e.g. "last_profile_id" or "window.main"
+ * @param schema Validates the stored JSON; a value that doesn't match is treated as unset
+ */
+export async function getAppSetting<T>(key: string, schema: z.ZodType<T>): Promise<T | null> {
+  const value = await invokeCommand<unknown>("get_app_setting", { key });
+  if (value === null || value === undefined) {
+    return null;
+  }
+  const result = schema.safeParse(value);
+  if (!result.success) {
+    console.warn(`Ignoring app setting ${key} with an unexpected shape:`, result.error.issues);
+    return null;
+  }
+  return result.data;
+}
+
+/**
+ * Store an app-wide setting as JSON. Passing null removes it.
+ */
+export function setAppSetting(key: string, value: unknown): Promise<void> {
+  return invokeCommand<void>("set_app_setting", { key, value });
+}
+
+export interface MessageMetadataUpdate {
+  message_id: string;
+  /** Deep-merged into the message's `extra`; a null value removes the key */
+  metadata_patch: Record<string, unknown>;
+}
+
+/**
+ * Annotate many messages of a chat in one call and one transaction. Nothing changes if the chat
+ * isn't the profile's or any message is not part of the chat.
+ * @returns The number of messages updated
+ */
+export function updateMessagesMetadataBulk(chatId: string, profileId: string, updates: MessageMetadataUpdate[]): Promise<number> {
+  return invokeCommand<number>("update_messages_metadata_bulk", { chatId, profileId, updates });
+}
+
+/**
+ * Ids of the chat's messages whose metadata has `key` set to exactly `value`, in chat order.
+ * Fails when the chat isn't the profile's.
+ */
+export function getMessagesByMetadata(chatId: string, profileId: string, key: string, value: unknown): Promise<string[]> {
+  return invokeCommand<string[]>("get_messages_by_metadata", { chatId, profileId, key, value });
+}
+
+type DuplicatedChat = Omit<Chat, "created_at" | "updated_at" | "synopsis_updated_at"> & { created_at: string; updated_at: string; synopsis_updated_at: string | null };
+
+/**
+ * Copy a chat with all its chapters, messages and markers into a new chat of the same profile, for
+ * experimenting without touching the original. Unlike a branch, the copy is complete and unlinked.
+ * @returns The new chat
+ */
+export async function duplicateChat(chatId: string, profileId: string, newTitle: string): Promise<Chat> {
+  const chat = await invokeCommand<DuplicatedChat>("duplicate_chat", { chatId, profileId, newTitle });
+  return {
+    ...chat,
+    synopsis_updated_at: chat.synopsis_updated_at ? new Date(chat.synopsis_updated_at) : null,
+    created_at: new Date(chat.created_at),
+    updated_at: new Date(chat.updated_at),
+  };
+}
+
+/**
+ * Result of a delete command. With "Confirm deletion a second time" on for the profile, the first
+ * call deletes nothing and returns a single-use token for the same entity, valid for `expires_in_secs`.
+ */
+export type DeleteOutcome =
+  | { status: "deleted" }
+  | {
+      status: "confirmation_required";
+      token: string;
+      /** What would be removed, e.g. "chat 'Long Campaign', 4,213 messages" */
+      summary: string;
+      expires_in_secs: number;
+    };
+
+/**
+ * Delete a chat of the profile with its chapters, messages and markers
+ * @param confirmationToken The token of an earlier "confirmation_required" outcome for this chat
+ */
+export function deleteChatCommand(chatId: string, profileId: string, confirmationToken?: string): Promise<DeleteOutcome> {
+  return invokeCommand<DeleteOutcome>("delete_chat", { chatId, profileId, confirmationToken: confirmationToken ?? null });
+}
+
+/**
+ * Delete a profile with all its data
+ * @param confirmationToken The token of an earlier "confirmation_required" outcome for this profile
+ */
+export function deleteProfileCommand(profileId: string, confirmationToken?: string): Promise<DeleteOutcome> {
+  return invokeCommand<DeleteOutcome>("delete_profile", { profileId, confirmationToken: confirmationToken ?? null });
+}
+
+export type ActivityGranularity = "day" | "week" | "month";
+
+export interface ActivityBucket {
+  /** First day of the bucket, YYYY-MM-DD (weeks start on Monday) */
+  start: string;
+  user_messages: number;
+  character_messages: number;
+  /** Completed requests in the inference log */
+  generations: number;
+  chats_created: number;
+  characters_created: number;
+}
+
+export interface ActivityRankedItem {
+  id: string;
+  name: string;
+  messages: number;
+}
+
+export interface ActivityTimeline {
+  granularity: ActivityGranularity;
+  /** Every bucket of the range, oldest first, empty ones included */
+  buckets: ActivityBucket[];
+  top_characters: ActivityRankedItem[];
+  top_chats: ActivityRankedItem[];
+}
+
+/**
+ * A profile's activity per day, week or month, for charts
+ * @param from First day, YYYY-MM-DD (UTC)
+ * @param to Last day, included
+ */
+export function getActivityTimeline(profileId: string, granularity: ActivityGranularity, from: string, to: string): Promise<ActivityTimeline> {
+  return invokeCommand<ActivityTimeline>("get_activity_timeline", { profileId, granularity, from, to });
+}
+
+export interface ModelRebind {
+  chat_templates: number;
+  /** Translation and synopsis models in chat settings */
+  chat_settings: number;
+  lorebooks: number;
+  /** Other models whose fallback chain named the old model */
+  fallback_chains: number;
+  /** The old model's alias now belongs to the new one */
+  alias_moved: boolean;
+}
+
+/**
+ * Point chat templates, lorebook embeddings and fallback chains of the profile that use `oldId`
+ * at `newId`, in one transaction. The old model's alias moves along when the new one has none.
+ */
+export function rebindModelCommand(oldId: string, newId: string, profileId: string): Promise<ModelRebind> {
+  return invokeCommand<ModelRebind>("rebind_model", { oldId, newId, profileId });
+}
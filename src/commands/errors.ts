@@ -0,0 +1,66 @@
+import { invoke, type InvokeArgs } from "@tauri-apps/api/core";
+import { z } from "zod";
+
+// Mirrors ErrorCode in src-tauri/src/error.rs
+export const COMMAND_ERROR_CODES = ["not_found", "unauthorized", "validation", "conflict", "provider", "internal"] as const;
+export type CommandErrorCode = (typeof COMMAND_ERROR_CODES)[number];
+
+const appErrorSchema = z.object({
+  code: z.enum(COMMAND_ERROR_CODES),
+  message: z.string(),
+  details: z.unknown().optional(),
+});
+
+/**
+ * Failure of a backend command. Switch on `code` rather than matching the message.
+ */
+export class CommandError extends Error {
+  readonly code: CommandErrorCode;
+  readonly details?: unknown;
+
+  constructor(code: CommandErrorCode, message: string, details?: unknown) {
+    super(message);
+    this.name = "CommandError";
+    this.code = code;
+    this.details = details;
+  }
+
+  // Callers that still show `String(error)` keep getting the plain message
+  override toString(): string {
+    return this.message;
+  }
+}
+
+/**
+ * Turn what a command rejected with into a CommandError. Commands still returning plain strings
+ * (and anything unexpected) become `internal` errors.
+ */
+export function toCommandError(error: unknown): CommandError {
+  if (error instanceof CommandError) {
+    return error;
+  }
+  const parsed = appErrorSchema.safeParse(error);
+  if (parsed.success) {
+    return new CommandError(parsed.data.code, parsed.data.message, parsed.data.details);
+  }
+  if (typeof error === "string") {
+    return new CommandError("internal", error);
+  }
+  const message = (error as { message?: unknown } | null)?.message;
+  return new CommandError("internal", typeof message === "string" ? message : String(error));
+}
+
+export function isCommandError(error: unknown, code?: CommandErrorCode): error is CommandError {
+  return error instanceof CommandError && (code === undefined || error.code === code);
+}
+
+/**
+ * `invoke` for commands returning AppError, rejecting with a CommandError
+ */
+export async function invokeCommand<T>(command: string, args?: InvokeArgs): Promise<T> {
+  try {
+    return await invoke<T>(command, args);
+  } catch (error) {
+    throw toCommandError(error);
+  }
+}
@@ -0,0 +1,21 @@
+import { invokeCommand } from "./errors";
+import type { ScrubOptions } from "./scrub";
+
+export interface ChatHtmlOptions {
+  theme?: "light" | "dark";
+  /** System messages are left out by default */
+  include_system?: boolean;
+  /** `<think>` blocks shown collapsed under their message, left out by default */
+  include_reasoning?: boolean;
+  /** Scrub names and personal details, use `replacements` to swap character names */
+  redact?: ScrubOptions | null;
+}
+
+/**
+ * Render a chat into a single HTML file with avatars inlined, viewable without the app.
+ * Message markdown is converted, raw HTML in messages is shown as text.
+ * @returns The number of messages written
+ */
+export function exportChatHtml(chatId: string, profileId: string, outputPath: string, options?: ChatHtmlOptions): Promise<number> {
+  return invokeCommand<number>("export_chat_html", { chatId, profileId, outputPath, options: options ?? null });
+}
@@ -0,0 +1,173 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { Chat } from "@/schema/chat-schema";
+import { invokeCommand } from "./errors";
+
+type ImportedChat = Omit<Chat, "created_at" | "updated_at" | "synopsis_updated_at"> & { created_at: string; updated_at: string; synopsis_updated_at: string | null };
+
+function toChat(chat: ImportedChat): Chat {
+  return {
+    ...chat,
+    synopsis_updated_at: chat.synopsis_updated_at ? new Date(chat.synopsis_updated_at) : null,
+    created_at: new Date(chat.created_at),
+    updated_at: new Date(chat.updated_at),
+  };
+}
+
+export type UrlImportKind = "character_png" | "character_json" | "lorebook_json" | "openai_chat" | "anthropic_chat" | "sillytavern_chat";
+
+export interface UrlImportResult {
+  kind: UrlImportKind;
+  final_url: string;
+  content_type: string | null;
+  size: number;
+  /** Cards and lorebooks only: base64 for PNG cards, raw text for JSON resources */
+  data: string | null;
+  /** Chat exports only: the chats created from it */
+  chats: Chat[];
+}
+
+export interface UrlImportError {
+  kind: "network" | "rejected" | "parse" | "import";
+  message: string;
+}
+
+/**
+ * Download a character card, lorebook or chat export from a URL into the profile. Chat exports are imported
+ * by the backend; cards and lorebooks come back as `data` for the frontend importers.
+ * HTTPS is required unless the profile sets `system.allowInsecureUrlImports`, local and private hosts are refused.
+ * Rejects with a `UrlImportError` so network failures can be told apart from bad files.
+ * @param profileId The profile the imported entity belongs to
+ * @param url The URL to download from
+ * @param kindHint Optional hint used when the content is ambiguous
+ */
+export async function importFromUrl(profileId: string, url: string, kindHint?: "character" | "lorebook"): Promise<UrlImportResult> {
+  const result = await invoke<Omit<UrlImportResult, "chats"> & { chats: ImportedChat[] }>("import_from_url", {
+    profileId,
+    url,
+    kindHint: kindHint ?? null,
+  });
+  return { ...result, chats: result.chats.map(toChat) };
+}
+
+/**
+ * Parse a JSON or YAML config file into a plain JSON value
+ * @param fileContents The raw file contents
+ * @param format The file format
+ */
+export function parseConfigFile(fileContents: string, format: "json" | "yaml"): Promise<unknown> {
+  return invokeCommand<unknown>("parse_config_file", { fileContents, format });
+}
+
+export interface ImportWatchStatus {
+  profile_id: string;
+  directory: string;
+  /** False while the folder can't be read; the watch keeps retrying */
+  available: boolean;
+  error: string | null;
+  /** Set while the folder is readable but polled (file events failed) or not watched at all */
+  warning: string | null;
+}
+
+/** Payload of `import-watch-files`: new or changed card files, settled for at least one scan */
+export interface ImportWatchFiles {
+  profile_id: string;
+  paths: string[];
+}
+
+/**
+ * Watch a folder for `.png`/`.json` character cards on behalf of a profile, replacing its previous watch.
+ * Files are only read, never moved or deleted.
+ * @param profileId The profile the reported files are meant for
+ * @param directory Absolute path of the folder
+ * @param sinceMs Files modified after this time (ms since epoch) count as new on the first scan
+ */
+export function startImportWatch(profileId: string, directory: string, sinceMs?: number | null): Promise<ImportWatchStatus> {
+  return invokeCommand<ImportWatchStatus>("start_import_watch", { profileId, directory, sinceMs: sinceMs ?? null });
+}
+
+/**
+ * Stop the folder watch of a profile
+ */
+export function stopImportWatch(profileId: string): Promise<void> {
+  return invokeCommand<void>("stop_import_watch", { profileId });
+}
+
+/**
+ * The watched folder of a profile and whether it's reachable, or null without a watch
+ */
+export function getImportWatchStatus(profileId: string): Promise<ImportWatchStatus | null> {
+  return invokeCommand<ImportWatchStatus | null>("get_import_watch_status", { profileId });
+}
+
+/**
+ * Import a ChatGPT data export (`conversations.json`, or a single conversation) into new chats.
+ * Only the branch that was last shown in ChatGPT is kept; edits and regenerations are dropped.
+ * @returns One chat per non-empty conversation
+ */
+export async function importOpenAIConversation(profileId: string, json: string): Promise<Chat[]> {
+  const chats = await invokeCommand<ImportedChat[]>("import_openai_conversation", { profileId, json });
+  return chats.map(toChat);
+}
+
+/**
+ * Import a Claude data export (`conversations.json`, or a single conversation) into new chats.
+ * @returns One chat per non-empty conversation
+ */
+export async function importAnthropicConversation(profileId: string, json: string): Promise<Chat[]> {
+  const chats = await invokeCommand<ImportedChat[]>("import_anthropic_conversation", { profileId, json });
+  return chats.map(toChat);
+}
+
+export interface TextImportOptions {
+  /** Regex matching a speaker line; the speaker is the `speaker` group or the first group. Default `^(\w[\w ]{0,40}):\s` */
+  speakerPattern?: string;
+  /** Speaker whose lines become user messages, matched case-insensitively */
+  userSpeaker?: string;
+  /** Create placeholder characters (tagged `imported`) for speakers without a character of that name */
+  createMissingCharacters?: boolean;
+  /** Only report what would be imported */
+  dryRun?: boolean;
+  /** Defaults to the file name */
+  chatName?: string;
+}
+
+export interface DetectedSpeaker {
+  name: string;
+  messages: number;
+  character_id: string | null;
+  is_user: boolean;
+  placeholder: boolean;
+}
+
+export interface TextImportReport {
+  dry_run: boolean;
+  encoding: "utf8" | "windows1252";
+  lines: number;
+  messages: number;
+  /** Lines before the first speaker, imported as system messages */
+  narration_messages: number;
+  /** In order of first appearance */
+  speakers: DetectedSpeaker[];
+  /** Null on dry runs */
+  chat: Chat | null;
+}
+
+/**
+ * Import a plain text or Markdown (`**Name:**`) transcript as a new chat. Lines without a speaker
+ * continue the message above. Run with `dryRun` first to preview the speakers and message counts.
+ * @param filePath Absolute path of the `.txt` or `.md` file
+ */
+export async function importChatFromText(profileId: string, filePath: string, options: TextImportOptions = {}): Promise<TextImportReport> {
+  const report = await invokeCommand<Omit<TextImportReport, "chat"> & { chat: ImportedChat | null }>("import_chat_from_text", {
+    profileId,
+    filePath,
+    options: {
+      speaker_pattern: options.speakerPattern ?? null,
+      user_speaker: options.userSpeaker ?? null,
+      create_missing_characters: options.createMissingCharacters ?? false,
+      dry_run: options.dryRun ?? false,
+      chat_name: options.chatName ?? null,
+    },
+  });
+  return { ...report, chat: report.chat ? toChat(report.chat) : null };
+}
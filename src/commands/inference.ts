@@ -1,9 +1,82 @@
-import { invoke } from "@tauri-apps/api/core";
+import type { InferenceMessage } from "@/schema/inference-engine-schema";
+import { invokeCommand } from "./errors";
 
 type TemporaryModelType = "Llama2" | "Llama3" | "Deepseek" | "Mistral" | "DEFAULT";
 export function countTokens(text: string, modelType: TemporaryModelType): Promise<{ count: number }> {
-  return invoke<{ count: number }>("count_tokens", {
+  return invokeCommand<{ count: number }>("count_tokens", {
     text,
     modelType,
   });
 }
+
+/**
+ * Drop the cached HuggingFace tokenizers from memory and delete their downloaded files
+ */
+export function clearTokenizerCache(): Promise<void> {
+  return invokeCommand<void>("clear_tokenizer_cache");
+}
+
+/**
+ * Load (downloading if needed) every HuggingFace tokenizer used by countTokens
+ */
+export function preloadTokenizers(): Promise<void> {
+  return invokeCommand<void>("preload_tokenizers");
+}
+
+export interface TrimMemoryReport {
+  evicted: string[];
+}
+
+/**
+ * Drop the backend caches (tokenizers) not used for `maxIdleSecs`. They are loaded again on next use.
+ */
+export function trimMemory(maxIdleSecs: number): Promise<TrimMemoryReport> {
+  return invokeCommand<TrimMemoryReport>("trim_memory", { maxIdleSecs });
+}
+
+export interface InferenceLogEntry {
+  timestamp: string;
+  request_id: string;
+  /** Entries without one are left out of exports */
+  profile_id?: string;
+  model_id: string;
+  engine?: string;
+  actual_model?: string;
+  /** Sampler parameters. Secret-looking keys are redacted by the backend before writing. */
+  params: Record<string, unknown>;
+  status: "completed" | "cancelled" | "error";
+  latency_ms: number;
+  usage?: Record<string, number> | null;
+  error_kind?: string | null;
+  /** Only written when the profile opted in to storing prompts */
+  prompt?: { system_prompt: string; messages: InferenceMessage[] } | null;
+}
+
+/**
+ * Append a request lifecycle entry to the rotating inference log file
+ */
+export function appendInferenceLog(entry: InferenceLogEntry): Promise<void> {
+  return invokeCommand<void>("append_inference_log", { entry });
+}
+
+/**
+ * Write a profile's inference log entries from `since` (ISO timestamp) to `outputPath`, as CSV or JSON.
+ * Only the stats columns (model, status, latency, tokens...) are exported. Resolves with the number of entries.
+ */
+export function exportInferenceLogs(profileId: string, outputPath: string, format: "csv" | "json", since?: string): Promise<number> {
+  return invokeCommand<number>("export_inference_logs", { profileId, since: since ?? null, format, outputPath });
+}
+
+/**
+ * Location of the inference log file, for attaching to bug reports
+ */
+export function getInferenceLogPath(): Promise<string> {
+  return invokeCommand<string>("get_inference_log_path");
+}
+
+/**
+ * Latest log entry of a request, or null when it was never logged or has rotated out
+ */
+export function findInferenceLogEntry(requestId: string): Promise<InferenceLogEntry | null> {
+  return invokeCommand<InferenceLogEntry | null>("find_inference_log_entry", { requestId });
+}
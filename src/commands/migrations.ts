@@ -0,0 +1,82 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { invokeCommand } from "./errors";
+
+export interface MigrationProgress {
+  index: number;
+  total: number;
+  version: number;
+  description: string;
+}
+
+export interface MigrationFailure {
+  version: number | null;
+  description: string | null;
+  error: string;
+  backup_path: string | null;
+}
+
+export type MigrationStatus =
+  | { state: "running"; progress: MigrationProgress | null }
+  | { state: "ready" }
+  | { state: "failed"; failure: MigrationFailure };
+
+/**
+ * Get the state of the startup database migrations
+ */
+export function getMigrationStatus(): Promise<MigrationStatus> {
+  return invoke<MigrationStatus>("get_migration_status");
+}
+
+/**
+ * Restore the database copy taken before the failed migration. Only available after a failure.
+ */
+export function restoreMigrationBackup(): Promise<void> {
+  return invokeCommand<void>("restore_migration_backup");
+}
+
+/**
+ * Subscribe to migration progress and completion/failure events
+ * @returns A function that removes every listener
+ */
+export async function onMigrationStatus(callback: (status: MigrationStatus) => void): Promise<() => void> {
+  const unlisteners = await Promise.all([
+    listen<MigrationProgress>("migration-progress", (event) => callback({ state: "running", progress: event.payload })),
+    listen("migration-completed", () => callback({ state: "ready" })),
+    listen<MigrationFailure>("migration-failed", (event) => callback({ state: "failed", failure: event.payload })),
+  ]);
+  return () => {
+    for (const unlisten of unlisteners) {
+      unlisten();
+    }
+  };
+}
+
+/**
+ * Resolve once startup migrations are applied, reject if they failed
+ */
+export async function waitForMigrations(): Promise<void> {
+  let unlisten: (() => void) | undefined;
+  try {
+    await new Promise<void>((resolve, reject) => {
+      const settle = (status: MigrationStatus) => {
+        if (status.state === "ready") {
+          resolve();
+        } else if (status.state === "failed") {
+          reject(new Error(`Database migration failed: ${status.failure.error}`));
+        }
+      };
+
+      // Listen first so a completion between the status check and the subscription is not lost
+      onMigrationStatus(settle)
+        .then((remove) => {
+          unlisten = remove;
+          return getMigrationStatus();
+        })
+        .then(settle)
+        .catch(reject);
+    });
+  } finally {
+    unlisten?.();
+  }
+}
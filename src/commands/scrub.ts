@@ -0,0 +1,37 @@
+import { invokeCommand } from "./errors";
+
+export interface ScrubOptions {
+  emails?: boolean;
+  phones?: boolean;
+  ipv4?: boolean;
+  paths?: boolean;
+  /** Extra words (names, handles) replaced case-insensitively */
+  words?: string[];
+  /** Names swapped for the given text instead of a placeholder, matched like `words` */
+  replacements?: Record<string, string>;
+}
+
+export interface ScrubMatch {
+  kind: "email" | "path" | "ipv4" | "phone" | "word" | "name";
+  placeholder: string;
+  original: string;
+}
+
+/**
+ * Replace emails, phone numbers, IPv4 addresses, file paths and custom words with typed placeholders (`[email-1]`).
+ * The same value maps to the same placeholder within the text.
+ * @param text The text to scrub
+ * @param options Detector toggles, all regex detectors are enabled by default
+ */
+export function scrubText(text: string, options?: ScrubOptions): Promise<string> {
+  return invokeCommand<string>("scrub_text", { text, options: options ?? null });
+}
+
+/**
+ * Preview what `scrubText` would replace, including the original values.
+ * @param text The text to scrub
+ * @param options Detector toggles, all regex detectors are enabled by default
+ */
+export function previewScrub(text: string, options?: ScrubOptions): Promise<{ text: string; matches: ScrubMatch[] }> {
+  return invokeCommand<{ text: string; matches: ScrubMatch[] }>("preview_scrub", { text, options: options ?? null });
+}
@@ -1,4 +1,4 @@
-import { invoke } from "@tauri-apps/api/core";
+import { CommandError, invokeCommand, toCommandError } from "./errors";
 
 /**
  * Hash a password using Argon2
@@ -6,7 +6,7 @@ import { invoke } from "@tauri-apps/api/core";
  * @returns A promise that resolves to the hashed password
  */
 async function hashPassword(password: string): Promise<string> {
-  return await invoke<string>("hash_password", { password });
+  return await invokeCommand<string>("hash_password", { password });
 }
 
 /**
@@ -16,7 +16,7 @@ async function hashPassword(password: string): Promise<string> {
  * @returns A promise that resolves to a boolean indicating if the password is valid
  */
 async function verifyPassword(password: string, hash: string): Promise<boolean> {
-  return await invoke<boolean>("verify_password", {
+  return await invokeCommand<boolean>("verify_password", {
     password,
     hash,
   });
@@ -25,26 +25,29 @@ async function verifyPassword(password: string, hash: string): Promise<boolean>
 /**
  * Encrypt an API key using the backend encryption function
  * @param apiKey The API key to encrypt
- * @returns A promise that resolves to the encrypted API key or rejects with an error message
+ * @returns A promise that resolves to the encrypted API key or rejects with a CommandError
  */
 async function encryptApiKey(apiKey: string): Promise<string> {
   try {
-    return await invoke<string>("encrypt_api_key", { apiKey });
+    return await invokeCommand<string>("encrypt_api_key", { apiKey });
   } catch (error) {
-    throw new Error(`Failed to encrypt API key: ${error}`);
+    const commandError = toCommandError(error);
+    throw new CommandError(commandError.code, `Failed to encrypt API key: ${commandError.message}`, commandError.details);
   }
 }
 
 /**
  * Decrypt an API key using the backend decryption function
  * @param encryptedApiKey The encrypted API key to decrypt
- * @returns A promise that resolves to the decrypted API key or rejects with an error message
+ * @returns A promise that resolves to the decrypted API key or rejects with a CommandError,
+ * `unauthorized` when the key was encrypted under another master key
  */
 async function decryptApiKey(encryptedApiKey: string): Promise<string> {
   try {
-    return await invoke<string>("decrypt_api_key", { encryptedApiKey });
+    return await invokeCommand<string>("decrypt_api_key", { encryptedApiKey });
   } catch (error) {
-    throw new Error(`Failed to decrypt API key: ${error}`);
+    const commandError = toCommandError(error);
+    throw new CommandError(commandError.code, `Failed to decrypt API key: ${commandError.message}`, commandError.details);
   }
 }
 
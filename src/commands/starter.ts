@@ -0,0 +1,36 @@
+import { invokeCommand } from "./errors";
+
+export interface StarterPackInfo {
+  id: string;
+  name: string;
+  description: string;
+  characters: number;
+  lorebooks: number;
+  format_templates: number;
+  quick_actions: number;
+}
+
+/** Raw pack content, validated by the create services when installed */
+export interface StarterPack {
+  id: string;
+  name: string;
+  description: string;
+  characters: Record<string, unknown>[];
+  lorebooks: (Record<string, unknown> & { entries?: Record<string, unknown>[] })[];
+  format_templates: Record<string, unknown>[];
+  quick_actions: Record<string, unknown>[];
+}
+
+/**
+ * Starter packs bundled with the app, with how many items of each kind they hold
+ */
+export function listAvailableStarterPacks(): Promise<StarterPackInfo[]> {
+  return invokeCommand<StarterPackInfo[]>("list_available_starter_packs");
+}
+
+/**
+ * Content of the given starter packs, in order. Rejects on an unknown pack id.
+ */
+export function getStarterPacks(packs: string[]): Promise<StarterPack[]> {
+  return invokeCommand<StarterPack[]>("get_starter_packs", { packs });
+}
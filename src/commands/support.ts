@@ -0,0 +1,73 @@
+import { invokeCommand } from "./errors";
+import type { MigrationStatus } from "./migrations";
+import type { WindowStateRecovered } from "./windows";
+
+export interface SupportBundleInclude {
+  logs?: boolean;
+  diagnostics?: boolean;
+  last_debug_captures?: boolean;
+  manifest_list?: boolean;
+  model_configs_redacted?: boolean;
+}
+
+/** Frontend-only pieces of the bundle. Secrets are redacted and everything is scrubbed by the backend. */
+export interface SupportBundleExtras {
+  diagnostics?: Record<string, unknown>;
+  debug_captures?: unknown[];
+  manifest_errors?: string[];
+}
+
+export interface SupportBundleSummary {
+  path: string;
+  files: { name: string; bytes: number }[];
+  scrubbed_values: number;
+}
+
+/**
+ * Zip logs, diagnostics, recent inference captures and redacted model configs for a bug report
+ * @param outputPath Where to write the .zip
+ * @param profileId Only this profile's models are included
+ */
+export function createSupportBundle(outputPath: string, profileId: string, include: SupportBundleInclude, extras?: SupportBundleExtras): Promise<SupportBundleSummary> {
+  return invokeCommand<SupportBundleSummary>("create_support_bundle", {
+    outputPath,
+    profileId,
+    include,
+    extras,
+  });
+}
+
+export interface InferenceTaskCounts {
+  active: number;
+  queued: number;
+}
+
+export interface AppStatus {
+  app_version: string;
+  database: {
+    reachable: boolean;
+    version: number | null;
+    latest_version: number;
+    migrations: MigrationStatus;
+    error: string | null;
+  };
+  inference: InferenceTaskCounts;
+  tokenizers: { repo: string; loaded: boolean; downloaded: boolean }[];
+  master_key_secure: boolean;
+  manifest_count: number;
+  window_state: {
+    // null when the main window or the monitors couldn't be read
+    on_screen: boolean | null;
+    last_recovery: WindowStateRecovered | null;
+    // Why the window position couldn't be checked at startup
+    check_error: string | null;
+  };
+}
+
+/**
+ * Status of every backend subsystem in one call, for the settings page and bug reports. Contains no secrets.
+ * @param inference Inference runs in the frontend, so its queue is reported by the caller
+ */
+export function getAppStatus(inference?: InferenceTaskCounts): Promise<AppStatus> {
+  return invokeCommand<AppStatus>("get_app_status", { inference });
+}
@@ -0,0 +1,36 @@
+import { invoke } from "@tauri-apps/api/core";
+import { describe, expect, it, vi } from "vitest";
+import { CommandError, invokeCommand, isCommandError, toCommandError } from "../errors";
+
+vi.mock("@tauri-apps/api/core", () => ({ invoke: vi.fn() }));
+
+describe("toCommandError", () => {
+  it("keeps the code of a typed error", () => {
+    const error = toCommandError({ code: "not_found", message: "Message m3 is not in chat c1", details: { message_id: "m3" } });
+    expect(error).toBeInstanceOf(CommandError);
+    expect(error).toMatchObject({ code: "not_found", message: "Message m3 is not in chat c1", details: { message_id: "m3" } });
+    expect(String(error)).toBe("Message m3 is not in chat c1");
+  });
+
+  it("treats plain strings and unknown codes as internal", () => {
+    expect(toCommandError("Disk full")).toMatchObject({ code: "internal", message: "Disk full" });
+    expect(toCommandError({ code: "teapot", message: "Short and stout" })).toMatchObject({ code: "internal", message: "Short and stout" });
+  });
+});
+
+describe("isCommandError", () => {
+  it("checks the code when given", () => {
+    const error = new CommandError("validation", "Bad key");
+    expect(isCommandError(error)).toBe(true);
+    expect(isCommandError(error, "validation")).toBe(true);
+    expect(isCommandError(error, "conflict")).toBe(false);
+    expect(isCommandError(new Error("Bad key"))).toBe(false);
+  });
+});
+
+describe("invokeCommand", () => {
+  it("rejects with a CommandError", async () => {
+    vi.mocked(invoke).mockRejectedValueOnce({ code: "conflict", message: "Nothing to restore right now" });
+    await expect(invokeCommand("restore_migration_backup")).rejects.toMatchObject({ code: "conflict" });
+  });
+});
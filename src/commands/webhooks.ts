@@ -0,0 +1,25 @@
+import { invokeCommand } from "./errors";
+
+export interface WebhookDeliveryResult {
+  delivered: boolean;
+  status_code: number | null;
+  attempts: number;
+  error: string | null;
+}
+
+/**
+ * POST a signed event payload to a webhook, retrying with backoff on network and 5xx errors
+ * @param url Target URL (HTTPS or localhost unless allowInsecure is set)
+ * @param encryptedSecret The webhook secret as stored in the database, decrypted by the backend
+ * @param event Event name, sent in the X-Narratrix-Event header
+ * @param payload JSON body
+ */
+export function deliverWebhook(url: string, encryptedSecret: string | null, event: string, payload: unknown, allowInsecure = false): Promise<WebhookDeliveryResult> {
+  return invokeCommand<WebhookDeliveryResult>("deliver_webhook", {
+    url,
+    encryptedSecret,
+    event,
+    payload,
+    allowInsecure,
+  });
+}
@@ -0,0 +1,83 @@
+import { invoke } from "@tauri-apps/api/core";
+import { type EventCallback, emitTo, type UnlistenFn } from "@tauri-apps/api/event";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
+import { invokeCommand } from "./errors";
+
+/**
+ * Whether this window is locked to one profile (opened with `openProfileWindow`). Such windows can't open others.
+ */
+export function isProfileWindow(): boolean {
+  return getCurrentWebviewWindow().label.startsWith("profile-");
+}
+
+/**
+ * Open a window locked to a profile, or focus it if it is already open. Only the main window may call this.
+ * @param profileId The profile the new window is bound to
+ * @returns The label of the profile window
+ */
+export function openProfileWindow(profileId: string): Promise<string> {
+  return invokeCommand<string>("create_profile_window", { profileId });
+}
+
+/**
+ * Bind the current window to a profile. Rejects if the window is locked to another profile.
+ * @param profileId The profile that logged in on this window
+ */
+export function bindWindowProfile(profileId: string): Promise<void> {
+  return invokeCommand<void>("bind_window_profile", { profileId });
+}
+
+/**
+ * Release the current window's profile binding (only the main window can switch profiles)
+ */
+export function unbindWindowProfile(): Promise<void> {
+  return invoke<void>("unbind_window_profile");
+}
+
+/**
+ * Get the profile the current window is bound to
+ */
+export function getWindowProfile(): Promise<string | null> {
+  return invoke<string | null>("get_window_profile");
+}
+
+/**
+ * Emit an event to this window only. Inference events belong to the window whose request it was,
+ * so another profile's window never sees them.
+ */
+export function emitToCurrentWindow(event: string, payload?: unknown): Promise<void> {
+  return emitTo(getCurrentWebviewWindow().label, event, payload);
+}
+
+/**
+ * Listen to events sent to this window (with `emitToCurrentWindow`) or broadcast to all windows
+ */
+export function listenInCurrentWindow<T>(event: string, handler: EventCallback<T>): Promise<UnlistenFn> {
+  return getCurrentWebviewWindow().listen<T>(event, handler);
+}
+
+export interface WindowBounds {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+}
+
+/** Payload of the `window-state-recovered` event, emitted when the main window was moved back on screen */
+export interface WindowStateRecovered {
+  label: string;
+  reason: "off_screen" | "reset";
+  previous: WindowBounds;
+  restored: WindowBounds;
+  monitors: WindowBounds[];
+}
+
+export const WINDOW_STATE_RECOVERED_EVENT = "window-state-recovered";
+
+/**
+ * Forget the saved window size and position and center the main window on the primary monitor
+ * @returns The bounds the main window was moved to, in physical pixels
+ */
+export function resetWindowState(): Promise<WindowBounds> {
+  return invokeCommand<WindowBounds>("reset_window_state");
+}
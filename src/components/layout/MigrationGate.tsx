@@ -0,0 +1,101 @@
+import { AlertTriangle, Database, Loader2 } from "lucide-react";
+import React, { useEffect, useState } from "react";
+import { toast } from "sonner";
+import { getMigrationStatus, MigrationStatus, onMigrationStatus, restoreMigrationBackup } from "@/commands/migrations";
+import { Button } from "@/components/ui/button";
+import { Card, CardContent, CardDescription, CardFooter, CardHeader, CardTitle } from "@/components/ui/card";
+import { Progress } from "@/components/ui/progress";
+
+// Holds the app on a progress screen while startup migrations run, and shows a recovery
+// screen instead of the app when one of them failed
+const MigrationGate: React.FC<{ children: React.ReactNode }> = ({ children }) => {
+  const [status, setStatus] = useState<MigrationStatus>({ state: "running", progress: null });
+  const [isRestoring, setIsRestoring] = useState(false);
+  const [isRestored, setIsRestored] = useState(false);
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    let isMounted = true;
+
+    onMigrationStatus((next) => isMounted && setStatus(next))
+      .then((remove) => {
+        unlisten = remove;
+        return getMigrationStatus();
+      })
+      .then((current) => isMounted && setStatus(current))
+      .catch((error) => console.error("Failed to read migration status:", error));
+
+    return () => {
+      isMounted = false;
+      unlisten?.();
+    };
+  }, []);
+
+  const handleRestore = async () => {
+    setIsRestoring(true);
+    try {
+      await restoreMigrationBackup();
+      setIsRestored(true);
+    } catch (error) {
+      console.error("Failed to restore database backup:", error);
+      toast.error(typeof error === "string" ? error : "Failed to restore database backup");
+    } finally {
+      setIsRestoring(false);
+    }
+  };
+
+  if (status.state === "ready") {
+    return <>{children}</>;
+  }
+
+  if (status.state === "failed") {
+    const { failure } = status;
+    return (
+      <div className="flex h-screen items-center justify-center p-6">
+        <Card className="w-full max-w-lg">
+          <CardHeader>
+            <CardTitle className="flex items-center gap-2">
+              <AlertTriangle className="h-5 w-5 text-destructive" />
+              Database update failed
+            </CardTitle>
+            <CardDescription>
+              {failure.description ? `Step ${failure.version} (${failure.description}) could not be applied.` : "The database could not be prepared."}
+            </CardDescription>
+          </CardHeader>
+          <CardContent className="space-y-3 text-sm">
+            <pre className="max-h-40 overflow-auto whitespace-pre-wrap rounded bg-muted p-2 text-xs">{failure.error}</pre>
+            {failure.backup_path ? (
+              <p className="text-muted-foreground">
+                A backup was saved before updating: <span className="break-all font-mono">{failure.backup_path}</span>
+              </p>
+            ) : (
+              <p className="text-muted-foreground">No backup was needed for this database.</p>
+            )}
+            {isRestored && <p>The backup was restored. Close Narratrix and install the previous version, or report this error.</p>}
+          </CardContent>
+          {failure.backup_path && (
+            <CardFooter>
+              <Button onClick={handleRestore} disabled={isRestoring || isRestored}>
+                {isRestoring && <Loader2 className="mr-2 h-4 w-4 animate-spin" />}
+                Restore backup
+              </Button>
+            </CardFooter>
+          )}
+        </Card>
+      </div>
+    );
+  }
+
+  const { progress } = status;
+  return (
+    <div className="flex h-screen flex-col items-center justify-center gap-4 p-6">
+      <Database className="h-8 w-8 text-muted-foreground" />
+      <p className="text-sm text-muted-foreground">
+        {progress && progress.index < progress.total ? `Updating database (${progress.index + 1}/${progress.total}): ${progress.description}` : "Preparing database..."}
+      </p>
+      {progress && <Progress className="w-72" value={(progress.index / Math.max(progress.total, 1)) * 100} />}
+    </div>
+  );
+};
+
+export default MigrationGate;
@@ -1,11 +1,12 @@
-import { Book, Bot, BoxIcon, ChevronDown, ChevronsLeft, ChevronsRight, Heart, HelpCircle, LogOut, MessageCircle, MessageSquare, Monitor, Moon, Settings, Sun, Users } from "lucide-react";
+import { AppWindow, Book, Bot, BoxIcon, ChevronDown, ChevronsLeft, ChevronsRight, Heart, HelpCircle, LogOut, MessageCircle, MessageSquare, Monitor, Moon, Settings, Sun, Users } from "lucide-react";
 import { useEffect, useMemo, useRef, useState } from "react";
 import { toast } from "sonner";
+import { isProfileWindow, openProfileWindow } from "@/commands/windows";
 import { Avatar, AvatarFallback, AvatarImage } from "@/components/ui/avatar";
 import { DropdownMenu, DropdownMenuContent, DropdownMenuItem, DropdownMenuSeparator, DropdownMenuTrigger } from "@/components/ui/dropdown-menu";
 import { Tooltip, TooltipContent, TooltipTrigger } from "@/components/ui/tooltip";
 import { useChatActions, useChatList, useCurrentChatId } from "@/hooks/chatStore";
-import { useCurrentProfile, useProfileActions } from "@/hooks/ProfileStore";
+import { useCurrentProfile, useProfileActions, useProfiles } from "@/hooks/ProfileStore";
 import { type Theme, useThemeStore } from "@/hooks/ThemeContext";
 import { useUIStore } from "@/hooks/UIStore";
 import { useImageUrl } from "@/hooks/useImageUrl";
@@ -66,7 +67,19 @@ const ProfileMenuContent: React.FC<ProfileMenuProps> = ({ onSettings, onLogout }
   const theme = useThemeStore((s) => s.theme);
   const setTheme = useThemeStore((s) => s.setTheme);
   const currentProfile = useCurrentProfile();
+  const profiles = useProfiles();
   const { setCurrentProfile } = useProfileActions();
+  // Profile windows can't open windows for other profiles
+  const otherProfiles = isProfileWindow() ? [] : profiles.filter((profile) => profile.id !== currentProfile?.id);
+
+  const handleOpenWindow = async (profileId: string) => {
+    try {
+      await openProfileWindow(profileId);
+    } catch (error) {
+      console.error("Failed to open profile window:", error);
+      toast.error("Failed to open a window for this profile", { description: String(error) });
+    }
+  };
 
   const handleThemeChange = async (next: Theme) => {
     setTheme(next);
@@ -121,6 +134,24 @@ const ProfileMenuContent: React.FC<ProfileMenuProps> = ({ onSettings, onLogout }
         <span>Settings</span>
       </DropdownMenuItem>
 
+      {otherProfiles.length > 0 && (
+        <>
+          <DropdownMenuSeparator className="bg-border/50 my-1" />
+          <div className="px-2 pt-0.5 pb-1 font-display italic text-[10.5px] text-muted-foreground/60 lowercase">open in a new window</div>
+          {otherProfiles.map((profile) => (
+            <DropdownMenuItem
+              key={profile.id}
+              onClick={() => handleOpenWindow(profile.id)}
+              className="font-ui text-[12.5px] gap-2.5 px-2 py-1.5 rounded-sm cursor-pointer [&>svg]:size-3.5 [&>svg]:text-muted-foreground/70 focus:[&>svg]:text-foreground"
+            >
+              <AppWindow strokeWidth={1.5} />
+              <span className="truncate">{profile.name}</span>
+            </DropdownMenuItem>
+          ))}
+          <DropdownMenuSeparator className="bg-border/50 my-1" />
+        </>
+      )}
+
       <DropdownMenuItem
         onClick={onLogout}
         className="font-ui text-[12.5px] gap-2.5 px-2 py-1.5 rounded-sm cursor-pointer text-muted-foreground/85 [&>svg]:size-3.5 [&>svg]:text-muted-foreground/60 focus:bg-destructive/10 focus:text-destructive focus:[&>svg]:text-destructive"
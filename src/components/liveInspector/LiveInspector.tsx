@@ -1,5 +1,5 @@
 import { formatDistanceToNow } from "date-fns";
-import { Bot, ChevronRight, Cpu, Terminal, Wrench } from "lucide-react";
+import { Bot, ChevronRight, Cpu, History, Terminal, TriangleAlert, Wrench } from "lucide-react";
 import React, { useEffect, useMemo, useState } from "react";
 import { LuInbox, LuSearchX, LuTrash2 } from "react-icons/lu";
 import { Badge } from "@/components/ui/badge";
@@ -9,7 +9,7 @@ import { Collapsible, CollapsibleContent, CollapsibleTrigger } from "@/component
 import { ResizableHandle, ResizablePanel, ResizablePanelGroup } from "@/components/ui/resizable";
 import { ScrollArea } from "@/components/ui/scroll-area";
 import { Tabs, TabsContent, TabsList, TabsTrigger } from "@/components/ui/tabs";
-import { type ConsoleLogEntry, type ConsoleLogType, type NodeExecutionEntry, type ToolCallEntry, useConsoleStoreActions, useConsoleStoreLogs, useConsoleStoreRequests } from "@/hooks/consoleStore";
+import { type ConsoleLogEntry, type ConsoleLogType, type NodeExecutionEntry, type ToolCallEntry, useConsoleStoreActions, useConsoleStoreInspectedRequestId, useConsoleStoreLogs, useConsoleStoreRequests } from "@/hooks/consoleStore";
 import { useModels } from "@/hooks/modelsStore";
 import { cn } from "@/lib/utils";
 import { Separator } from "../ui/separator";
@@ -347,7 +347,8 @@ const LogsPanel: React.FC<{ maxHeight: string }> = ({ maxHeight }) => {
 export const LiveInspector: React.FC<LiveInspectorProps> = ({ maxHeight = "100%" }) => {
   const requests = useConsoleStoreRequests();
   const logs = useConsoleStoreLogs();
-  const { clearHistory } = useConsoleStoreActions();
+  const { clearHistory, clearInspectedRequest } = useConsoleStoreActions();
+  const inspectedRequestId = useConsoleStoreInspectedRequestId();
   const modelList = useModels();
   const [mode, setMode] = useState<"requests" | "logs">("requests");
   const [selectedRequestId, setSelectedRequestId] = useState<string | null>(requests.length > 0 ? requests[0].id : null);
@@ -359,6 +360,14 @@ export const LiveInspector: React.FC<LiveInspectorProps> = ({ maxHeight = "100%"
     }
   }, [requests, selectedRequestId]);
 
+  useEffect(() => {
+    if (inspectedRequestId) {
+      setMode("requests");
+      setSelectedRequestId(inspectedRequestId);
+      clearInspectedRequest();
+    }
+  }, [inspectedRequestId, clearInspectedRequest]);
+
   const selectedRequest = requests.find((req) => req.id === selectedRequestId);
 
   const formatTimestamp = (timestamp: number) => {
@@ -467,6 +476,20 @@ export const LiveInspector: React.FC<LiveInspectorProps> = ({ maxHeight = "100%"
                       <TabsTrigger value="response">Response</TabsTrigger>
                       <TabsTrigger value="stats">Stats</TabsTrigger>
                     </TabsList>
+                    {selectedRequest.reconstructed && (
+                      <div className="mt-2 space-y-1">
+                        <Badge variant="outline" className="text-xs gap-1 border-amber-500/50 text-amber-600 dark:text-amber-400">
+                          <History className="h-3 w-3" />
+                          Reconstructed
+                        </Badge>
+                        {selectedRequest.warnings?.map((warning) => (
+                          <p key={warning} className="flex items-start gap-1 text-xs text-amber-600 dark:text-amber-400">
+                            <TriangleAlert className="h-3 w-3 mt-0.5 flex-shrink-0" />
+                            {warning}
+                          </p>
+                        ))}
+                      </div>
+                    )}
                   </div>
 
                   <div className="flex-1 overflow-hidden">
@@ -0,0 +1,84 @@
+import { Link } from "lucide-react";
+import { useState } from "react";
+import { toast } from "sonner";
+import { Dialog, DialogBody, DialogContent, DialogFooter, DialogHeader, DialogTitle } from "@/components/shared/Dialog";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import { useCurrentProfile } from "@/hooks/ProfileStore";
+import { importFromUrl, UrlImportFailure, type UrlImportSummary } from "@/services/imports/import-from-url";
+
+interface ImportFromUrlDialogProps {
+  open: boolean;
+  onOpenChange: (open: boolean) => void;
+  // What the page imports, used when the file doesn't say what it is
+  kindHint: "character" | "lorebook";
+  onImported: (summary: UrlImportSummary) => void;
+}
+
+const FAILURE_TITLES: Record<UrlImportFailure["kind"], string> = {
+  network: "Couldn't download the file",
+  rejected: "The URL was refused",
+  parse: "The file couldn't be read",
+  invalid: "The file isn't a valid card or lorebook",
+  import: "The file couldn't be imported",
+};
+
+// Downloads a character card, lorebook or chat export and imports it into the current profile
+export function ImportFromUrlDialog({ open, onOpenChange, kindHint, onImported }: ImportFromUrlDialogProps) {
+  const currentProfile = useCurrentProfile();
+  const [url, setUrl] = useState("");
+  const [isImporting, setIsImporting] = useState(false);
+
+  const handleImport = async () => {
+    if (!currentProfile) {
+      return;
+    }
+    setIsImporting(true);
+    try {
+      const summary = await importFromUrl(currentProfile.id, url.trim(), kindHint);
+      setUrl("");
+      onOpenChange(false);
+      onImported(summary);
+    } catch (error) {
+      console.error("Import from URL failed:", error);
+      const title = error instanceof UrlImportFailure ? FAILURE_TITLES[error.kind] : "Import from URL failed";
+      toast.error(title, { description: error instanceof Error ? error.message : String(error) });
+    } finally {
+      setIsImporting(false);
+    }
+  };
+
+  return (
+    <Dialog open={open} onOpenChange={(next) => !isImporting && onOpenChange(next)}>
+      <DialogContent size="small">
+        <DialogHeader>
+          <DialogTitle>Import from URL</DialogTitle>
+        </DialogHeader>
+
+        <DialogBody className="space-y-2">
+          <Label htmlFor="import-url">Link to a PNG card, a JSON {kindHint === "lorebook" ? "lorebook" : "character"} or a chat export</Label>
+          <Input
+            id="import-url"
+            value={url}
+            placeholder="https://"
+            onChange={(e) => setUrl(e.target.value)}
+            onKeyDown={(e) => {
+              if (e.key === "Enter" && url.trim() && !isImporting) {
+                handleImport();
+              }
+            }}
+            disabled={isImporting}
+          />
+        </DialogBody>
+
+        <DialogFooter className="flex justify-end gap-2">
+          <Button onClick={handleImport} disabled={!url.trim() || isImporting || !currentProfile} size="dialog">
+            <Link className="h-4 w-4" />
+            {isImporting ? "Importing..." : "Import"}
+          </Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+}
@@ -1,6 +1,8 @@
 import { useEffect } from "react";
 import { toast } from "sonner";
 import { create } from "zustand";
+import type { DeleteOutcome } from "@/commands/database";
+import { bindWindowProfile, getWindowProfile, unbindWindowProfile } from "@/commands/windows";
 import { runProfileMigrations } from "@/services/update-profile";
 import { useSessionProfile } from "@/utils/session-storage";
 import { ProfileListItem, ProfileResponse, UpdateProfileParams } from "../schema/profiles-schema";
@@ -29,7 +31,7 @@ interface ProfileActions {
   fetchProfiles: () => Promise<ProfileListItem[]>;
   addProfile: (profile: UpdateProfileParams) => Promise<void>;
   updateProfile: (profileChanges: UpdateProfileParams) => Promise<void>;
-  removeProfile: (id: string) => Promise<void>;
+  removeProfile: (id: string, confirmationToken?: string) => Promise<DeleteOutcome>;
   setCurrentProfile: (profile: ProfileResponse | undefined) => void;
   login: (id: string, password?: string) => Promise<ProfileResponse | null>;
   logout: () => void;
@@ -135,13 +137,17 @@ export const useProfileStore = create<ProfileStore>((set, get) => {
         }
       },
 
-      removeProfile: async (id: string) => {
+      removeProfile: async (id: string, confirmationToken?: string) => {
         try {
           set({ isLoading: true, error: null });
 
           const isCurrentProfile = get().currentProfile?.id === id;
 
-          await deleteProfile(id);
+          const outcome = await deleteProfile(id, confirmationToken);
+          if (outcome.status === "confirmation_required") {
+            set({ isLoading: false });
+            return outcome;
+          }
 
           set((state) => ({
             profiles: state.profiles.filter((profile) => profile.id !== id),
@@ -151,6 +157,7 @@ export const useProfileStore = create<ProfileStore>((set, get) => {
           if (isCurrentProfile) {
             get().actions.logout();
           }
+          return outcome;
         } catch (error) {
           console.error("Failed to delete profile:", error);
           toast.error(error instanceof Error ? error.message : "Failed to delete profile");
@@ -206,6 +213,16 @@ export const useProfileStore = create<ProfileStore>((set, get) => {
             return null;
           }
 
+          try {
+            await bindWindowProfile(fullProfile.id);
+          } catch (bindError) {
+            const bindMessage = typeof bindError === "string" ? bindError : "This window belongs to another profile.";
+            console.error("Window profile binding failed:", bindError);
+            toast.error(bindMessage);
+            set({ isLoading: false, error: bindMessage });
+            return null;
+          }
+
           // --- MIGRATION HOOK ---
           try {
             const migratedProfile = await runProfileMigrations(fullProfile.id);
@@ -234,6 +251,7 @@ export const useProfileStore = create<ProfileStore>((set, get) => {
       },
 
       logout: () => {
+        unbindWindowProfile().catch((error) => console.error("Failed to release window profile:", error));
         set({
           currentProfile: null,
           isAuthenticated: false,
@@ -311,7 +329,15 @@ export const useInitializeProfiles = () => {
         sessionStorage.removeItem("sessionProfile");
       }
 
-      const savedProfileId = savedProfileData?.id;
+      // Windows opened for a profile log straight into it
+      const windowProfileId = await getWindowProfile().catch((error) => {
+        console.error("Failed to read the window's profile:", error);
+        return null;
+      });
+      if (!isMounted) {
+        return;
+      }
+      const savedProfileId = windowProfileId ?? savedProfileData?.id;
 
       if (savedProfileId) {
         const currentProfileId = useProfileStore.getState().currentProfile?.id;
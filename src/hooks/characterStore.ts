@@ -9,6 +9,7 @@ import {
   deleteCharacter as deleteCharacterAPI,
   getCharacterById as getCharacterByIdAPI,
   listCharacters as listCharactersAPI,
+  setCharacterFavorite as setCharacterFavoriteAPI,
   updateCharacter as updateCharacterAPI,
 } from "@/services/character-service";
 import { getImageUrl } from "@/services/file-system-service";
@@ -27,6 +28,7 @@ interface CharacterState {
     getCharacterById: (id: string) => Promise<Character | null>;
     updateCharacter: (profile_id: string, id: string, updateData: Partial<Omit<Character, "id" | "profile_id" | "created_at" | "updated_at">>) => Promise<Character | null>;
     deleteCharacter: (id: string) => Promise<boolean>;
+    setFavorite: (profile_id: string, id: string, favorite: boolean) => Promise<boolean>;
 
     // List Operations
     fetchCharacters: (profile_id: string, filter?: CharacterFilter) => Promise<void>;
@@ -170,6 +172,17 @@ export const useCharacterStore: UseBoundStore<StoreApi<CharacterState>> = create
       }
     },
 
+    // Throws when the update fails; false when the character isn't in the profile
+    setFavorite: async (profile_id: string, id: string, favorite: boolean) => {
+      const updated = await setCharacterFavoriteAPI(id, profile_id, favorite);
+      if (updated) {
+        set((state) => ({
+          characters: state.characters.map((character) => (character.id === id ? { ...character, favorite } : character)),
+        }));
+      }
+      return updated;
+    },
+
     // List Operations
     fetchCharacters: async (profile_id: string, filter?: CharacterFilter) => {
       try {
@@ -1,6 +1,8 @@
 import { toast } from "sonner";
 import { create } from "zustand";
+import type { DeleteOutcome } from "@/commands/database";
 import { ChatChapter } from "@/schema/chat-chapter-schema";
+import { ChatMarker, CreateChatMarkerParams, UpdateChatMarkerParams } from "@/schema/chat-marker-schema";
 import { ChatMemory, CreateChatMemoryParams, UpdateChatMemoryParams } from "@/schema/chat-memory-schema";
 import { ChatMessage, ChatMessageType, CreateChatMessageParams, UpdateChatMessageParams } from "@/schema/chat-message-schema";
 import { Chat, ChatParticipant, CreateChatParams } from "@/schema/chat-schema";
@@ -12,16 +14,19 @@ import {
   getChaptersByChatId,
   getNextChapterSequence,
 } from "@/services/chat-chapter-service";
+import { createChatMarker as apiCreateChatMarker, deleteChatMarker as apiDeleteChatMarker, updateChatMarker as apiUpdateChatMarker, listChatMarkers } from "@/services/chat-marker-service";
 import { createChatMemory as apiCreateChatMemory, deleteChatMemory as apiDeleteChatMemory, updateChatMemory as apiUpdateChatMemory, getShortTermMemories } from "@/services/chat-memory-service";
 import {
   createChatMessage as apiCreateChatMessage,
   deleteChatMessage as apiDeleteChatMessage,
   updateChatMessage as apiUpdateChatMessage,
+  setActiveVariant as apiSetActiveVariant,
   getChatMessagesByChatId,
   getNextMessagePosition,
   updateChatMessagesUsingFilter,
 } from "@/services/chat-message-service";
 import { createChat as apiCreateChat, deleteChat as apiDeleteChat, updateChat as apiUpdateChat, getChatById, listChats } from "@/services/chat-service";
+import { setFocusedChat } from "@/services/inference/request-priority";
 
 // Create a type that omits chat_id and makes position optional for store use
 type AddChatMessageParams = Omit<CreateChatMessageParams, "chat_id" | "chapter_id" | "disabled" | "position" | "message_index"> & {
@@ -37,12 +42,17 @@ type AddChatChapterParams = Omit<ChatChapter, "id" | "chat_id" | "created_at" |
 // Create a type for adding memories through the store
 type AddChatMemoryParams = Omit<CreateChatMemoryParams, "chat_id">;
 
+// Markers are added to the active chapter
+type AddChatMarkerParams = Omit<CreateChatMarkerParams, "chat_id" | "chapter_id">;
+
 interface chatState {
   chatList: Pick<Chat, "id" | "name" | "updated_at">[];
   selectedChat: Chat;
   selectedChatMessages: ChatMessage[];
   selectedChatChapters: ChatChapter[];
   selectedChatMemories: ChatMemory[];
+  // Markers of the active chapter, ordered by position
+  selectedChatMarkers: ChatMarker[];
   participantIndex: number;
   isLoading: boolean;
   error: string | null;
@@ -56,6 +66,7 @@ interface chatState {
     deleteChatMessage: (messageId: string) => Promise<void>;
     updateChatMessage: (messageId: string, message: Partial<UpdateChatMessageParams>, forceUpdate?: boolean) => Promise<ChatMessage>;
     fetchChatMessages: (chatId?: string, chapterId?: string) => Promise<ChatMessage[]>;
+    setActiveVariant: (messageId: string, variantIndex: number) => Promise<void>;
 
     // Chapters
     addChatChapter: (chapter: AddChatChapterParams) => Promise<ChatChapter>;
@@ -65,6 +76,11 @@ interface chatState {
     fetchChatChapters: (chatId: string) => Promise<ChatChapter[]>;
     switchChatChapter: (chapterId: string) => Promise<void>;
 
+    // Markers
+    addChatMarker: (marker: AddChatMarkerParams) => Promise<ChatMarker>;
+    updateChatMarker: (markerId: string, marker: UpdateChatMarkerParams) => Promise<ChatMarker | null>;
+    deleteChatMarker: (markerId: string) => Promise<boolean>;
+
     // Memories
     addChatMemory: (memory: AddChatMemoryParams) => Promise<ChatMemory>;
     deleteChatMemory: (memoryId: string) => Promise<boolean>;
@@ -89,7 +105,7 @@ interface chatState {
     advanceParticipantIndex: (maxExclusive: number) => void;
 
     createChat: (chat: CreateChatParams, skipDefaultChapter?: boolean) => Promise<Chat>;
-    deleteChat: (id: string) => Promise<void>;
+    deleteChat: (id: string, profileId: string, confirmationToken?: string) => Promise<DeleteOutcome>;
     clearChatList: () => void;
     clearError: () => void;
   };
@@ -103,6 +119,7 @@ export const useChatStore = create<chatState>((set, get) => ({
   selectedChatReasonings: [],
   selectedChatChapters: [],
   selectedChatMemories: [],
+  selectedChatMarkers: [],
   participantIndex: 0,
   isLoading: false,
   error: null,
@@ -141,17 +158,20 @@ export const useChatStore = create<chatState>((set, get) => ({
         // Fetch chapters for the selected chat
         const chapters = await getChaptersByChatId(id);
 
-        // Fetch short-term memories for the current chapter only
+        // Fetch short-term memories and markers for the current chapter only
         const memories = chat.active_chapter_id ? await getShortTermMemories(id, chat.active_chapter_id) : [];
+        const markers = chat.active_chapter_id ? await listChatMarkers(id, chat.active_chapter_id) : [];
 
         set({
           selectedChat: chat,
           selectedChatMessages: messages,
           selectedChatChapters: chapters,
           selectedChatMemories: memories,
+          selectedChatMarkers: markers,
           participantIndex: 0,
           isLoading: false,
         });
+        setFocusedChat(chat.id);
       } catch (error) {
         set({
           error: error instanceof Error ? error.message : "Failed to fetch chat",
@@ -241,17 +261,19 @@ export const useChatStore = create<chatState>((set, get) => ({
       }
     },
 
-    deleteChat: async (id: string) => {
+    deleteChat: async (id: string, profileId: string, confirmationToken?: string) => {
       try {
         set({ isLoading: true, error: null });
 
         // Check if the deleted chat is the selected chat
         const isSelectedChat = get().selectedChat?.id === id;
 
-        const success = await apiDeleteChat(id);
+        const outcome = await apiDeleteChat(id, profileId, confirmationToken);
 
-        if (!success) {
-          throw new Error(`Failed to delete chat with ID ${id}`);
+        // Nothing was deleted yet, the caller asks the user again with the token
+        if (outcome.status === "confirmation_required") {
+          set({ isLoading: false });
+          return outcome;
         }
 
         // Update store state
@@ -264,6 +286,7 @@ export const useChatStore = create<chatState>((set, get) => ({
           participantIndex: 0,
           isLoading: false,
         });
+        return outcome;
       } catch (error) {
         toast.error(error instanceof Error ? error.message : "Failed to delete chat");
         set({
@@ -414,6 +437,7 @@ export const useChatStore = create<chatState>((set, get) => ({
     },
 
     clearChatList: () => {
+      setFocusedChat(null);
       set({
         chatList: [],
         selectedChat: {} as Chat,
@@ -530,6 +554,18 @@ export const useChatStore = create<chatState>((set, get) => ({
       }
     },
 
+    setActiveVariant: async (messageId, variantIndex) => {
+      try {
+        await apiSetActiveVariant(messageId, get().selectedChat.id, variantIndex);
+        set((state) => ({
+          selectedChatMessages: state.selectedChatMessages.map((message) => (message.id === messageId ? { ...message, message_index: variantIndex } : message)),
+        }));
+      } catch (error) {
+        toast.error(error instanceof Error ? error.message : "Failed to switch variant");
+        throw error;
+      }
+    },
+
     fetchChatMessages: async (chatId?: string, chapterId?: string) => {
       try {
         set({ isLoading: true, error: null });
@@ -539,7 +575,8 @@ export const useChatStore = create<chatState>((set, get) => ({
         }
 
         const messages = await getChatMessagesByChatId(chatId || currentChat.id, chapterId || currentChat.active_chapter_id!);
-        set({ selectedChatMessages: messages, isLoading: false });
+        const markers = await listChatMarkers(chatId || currentChat.id, chapterId || currentChat.active_chapter_id!);
+        set({ selectedChatMessages: messages, selectedChatMarkers: markers, isLoading: false });
         return messages;
       } catch (error) {
         toast.error(error instanceof Error ? error.message : "Failed to fetch chat messages");
@@ -767,13 +804,15 @@ export const useChatStore = create<chatState>((set, get) => ({
         // Fetch messages for the new chapter
         const messages = await getChatMessagesByChatId(currentChat.id, chapterId);
 
-        // Fetch short-term memories for the new chapter
+        // Fetch short-term memories and markers for the new chapter
         const memories = await getShortTermMemories(currentChat.id, chapterId);
+        const markers = await listChatMarkers(currentChat.id, chapterId);
 
-        // Update the messages and memories in the store
+        // Update the messages, memories and markers in the store
         set({
           selectedChatMessages: messages,
           selectedChatMemories: memories,
+          selectedChatMarkers: markers,
           participantIndex: 0,
           isLoading: false,
         });
@@ -818,6 +857,50 @@ export const useChatStore = create<chatState>((set, get) => ({
       }
     },
 
+    addChatMarker: async (markerData: AddChatMarkerParams) => {
+      try {
+        const currentChat = get().selectedChat;
+        if (!currentChat?.active_chapter_id) {
+          throw new Error("No chapter selected");
+        }
+
+        const marker = await apiCreateChatMarker({ ...markerData, chat_id: currentChat.id, chapter_id: currentChat.active_chapter_id });
+        set((state) => ({ selectedChatMarkers: [...state.selectedChatMarkers, marker].sort((a, b) => a.position - b.position) }));
+        return marker;
+      } catch (error) {
+        toast.error(error instanceof Error ? error.message : "Failed to add marker");
+        throw error;
+      }
+    },
+
+    updateChatMarker: async (markerId: string, markerData: UpdateChatMarkerParams) => {
+      try {
+        const updatedMarker = await apiUpdateChatMarker(markerId, get().selectedChat.id, markerData);
+        if (updatedMarker) {
+          set((state) => ({
+            selectedChatMarkers: state.selectedChatMarkers.map((marker) => (marker.id === markerId ? updatedMarker : marker)).sort((a, b) => a.position - b.position),
+          }));
+        }
+        return updatedMarker;
+      } catch (error) {
+        toast.error(error instanceof Error ? error.message : "Failed to update marker");
+        throw error;
+      }
+    },
+
+    deleteChatMarker: async (markerId: string) => {
+      try {
+        const success = await apiDeleteChatMarker(markerId, get().selectedChat.id);
+        if (success) {
+          set((state) => ({ selectedChatMarkers: state.selectedChatMarkers.filter((marker) => marker.id !== markerId) }));
+        }
+        return success;
+      } catch (error) {
+        toast.error(error instanceof Error ? error.message : "Failed to delete marker");
+        throw error;
+      }
+    },
+
     deleteChatMemory: async (memoryId: string) => {
       try {
         set({ isLoading: true, error: null });
@@ -1030,11 +1113,14 @@ export const useCurrentChatChapters = () => useChatStore((state) => state.select
 export const useCurrentChatMessages = () => useChatStore((state) => state.selectedChatMessages);
 
 export const useCurrentChatSettings = () => useChatStore((state) => state.selectedChat?.settings);
+export const useCurrentChatSynopsis = () => useChatStore((state) => state.selectedChat?.synopsis);
+export const useCurrentChatSynopsisUpdatedAt = () => useChatStore((state) => state.selectedChat?.synopsis_updated_at);
 
 export const useCurrentChatParticipantIndex = () => useChatStore((state) => state.participantIndex);
 
 // Memory hooks
 export const useCurrentChatMemories = () => useChatStore((state) => state.selectedChatMemories);
+export const useCurrentChatMarkers = () => useChatStore((state) => state.selectedChatMarkers);
 
 export const useCurrentChatLongTermMemories = () => useChatStore((state) => state.selectedChatMemories.filter((m) => m.chapter_id === null));
 
@@ -16,6 +16,9 @@ export interface ConsoleRequest {
   resolvedParameters?: ResolvedParameters;
   engine: Engine;
   fullResponse?: string;
+  // Prompt of a past message rebuilt from current data rather than captured as sent
+  reconstructed?: boolean;
+  warnings?: string[];
 }
 
 export type ConsoleLogType = "agent-run" | "tool-call" | "node-execution" | "js-console";
@@ -64,11 +67,16 @@ export interface ConsoleLogEntry {
 interface ConsoleState {
   requests: ConsoleRequest[];
   logs: ConsoleLogEntry[];
+  // Request the Live Inspector should open on, e.g. the prompt of a past message
+  inspectedRequestId: string | null;
   actions: {
     addRequest: (request: Omit<ConsoleRequest, "timestamp">) => void;
+    inspectRequest: (request: Omit<ConsoleRequest, "timestamp">) => void;
+    clearInspectedRequest: () => void;
     updateRequestResolvedParams: (id: string, resolvedParameters: ResolvedParameters) => void;
     updateRequestResponse: (id: string, response: InferenceResponse) => void;
     clearHistory: () => void;
+    trimIdleRequests: (maxIdleMs: number) => number;
     getRequestById: (id: string) => ConsoleRequest | undefined;
     addLog: (entry: Omit<ConsoleLogEntry, "id" | "timestamp"> & { id?: string }) => void;
     updateLog: (id: string, updates: Partial<ConsoleLogEntry>) => void;
@@ -92,6 +100,7 @@ const MAX_LOGS_LENGTH = 50;
 export const useConsoleStore = create<ConsoleState>((set, get) => ({
   requests: [],
   logs: [],
+  inspectedRequestId: null,
   actions: {
     /**
      * Add a new request to the console history
@@ -139,6 +148,16 @@ export const useConsoleStore = create<ConsoleState>((set, get) => ({
         };
       }),
 
+    /**
+     * Add a request and ask the Live Inspector to show it
+     */
+    inspectRequest: (request) => {
+      get().actions.addRequest(request);
+      set({ inspectedRequestId: request.id });
+    },
+
+    clearInspectedRequest: () => set({ inspectedRequestId: null }),
+
     updateRequestResolvedParams: (id, resolvedParameters) =>
       set((state) => ({
         requests: state.requests.map((req) => (req.id === id ? { ...req, resolvedParameters } : req)),
@@ -176,6 +195,20 @@ export const useConsoleStore = create<ConsoleState>((set, get) => ({
         requests: [],
       }),
 
+    /**
+     * Drop request snapshots older than `maxIdleMs`, except the one open in the Live Inspector.
+     * Returns how many were dropped.
+     */
+    trimIdleRequests: (maxIdleMs) => {
+      const { requests, inspectedRequestId } = get();
+      const cutoff = Date.now() - maxIdleMs;
+      const kept = requests.filter((request) => request.timestamp >= cutoff || request.id === inspectedRequestId);
+      if (kept.length !== requests.length) {
+        set({ requests: kept });
+      }
+      return requests.length - kept.length;
+    },
+
     /**
      * Get a specific request by ID
      */
@@ -267,3 +300,4 @@ export const useConsoleStore = create<ConsoleState>((set, get) => ({
 export const useConsoleStoreActions = () => useConsoleStore((state) => state.actions);
 export const useConsoleStoreRequests = () => useConsoleStore((state) => state.requests);
 export const useConsoleStoreLogs = () => useConsoleStore((state) => state.logs);
+export const useConsoleStoreInspectedRequestId = () => useConsoleStore((state) => state.inspectedRequestId);
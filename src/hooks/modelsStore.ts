@@ -3,11 +3,14 @@ import { Model, ModelType } from "@/schema/models-schema";
 import {
   createModel as createModelAPI,
   deleteModel as deleteModelAPI,
+  findModelByIdOrAlias,
   getModelById as getModelByIdAPI,
   getModelsByProfileGroupedByType as getModelsByProfileGroupedByTypeAPI,
   listModels as listModelsAPI,
   ModelFilter,
   NewModelParams,
+  refreshModelCapabilities as refreshModelCapabilitiesAPI,
+  setModelFavorite as setModelFavoriteAPI,
   updateModel as updateModelAPI,
 } from "@/services/model-service";
 
@@ -23,6 +26,8 @@ interface ModelsState {
     getModelById: (id: string) => Promise<Model | null>;
     updateModel: (id: string, updateData: Partial<Omit<Model, "id" | "profile_id" | "created_at" | "updated_at">>) => Promise<Model | null>;
     deleteModel: (id: string) => Promise<boolean>;
+    setFavorite: (profileId: string, id: string, favorite: boolean) => Promise<boolean>;
+    refreshModelCapabilities: (id: string, profileId: string) => Promise<Model | null>;
 
     // List Operations
     fetchModels: (filter?: ModelFilter) => Promise<void>;
@@ -48,6 +53,8 @@ export const useModelsStore = create<ModelsState>((set, get) => ({
         const newModel = await createModelAPI(modelData, isDuplicate);
 
         await get().actions.fetchModels({ profile_id: newModel.profile_id });
+        // In the background: the provider may be slow or unreachable, the model works without it
+        void get().actions.refreshModelCapabilities(newModel.id, newModel.profile_id);
 
         return newModel;
       } catch (error) {
@@ -63,8 +70,8 @@ export const useModelsStore = create<ModelsState>((set, get) => ({
       try {
         set({ isLoading: true, error: null });
 
-        // First check if the model is already in the store
-        const cachedModel = get().models.find((model) => model.id === id);
+        // First check if the model is already in the store, under its id or alias
+        const cachedModel = findModelByIdOrAlias(get().models, id);
         if (cachedModel) {
           set({ isLoading: false });
           return cachedModel;
@@ -100,6 +107,10 @@ export const useModelsStore = create<ModelsState>((set, get) => ({
 
         if (updatedModel) {
           await get().actions.fetchModels({ profile_id: updatedModel.profile_id });
+          // A different model name or endpoint can mean different capabilities
+          if (updateData.config) {
+            void get().actions.refreshModelCapabilities(updatedModel.id, updatedModel.profile_id);
+          }
         } else {
           set({ isLoading: false });
         }
@@ -114,6 +125,19 @@ export const useModelsStore = create<ModelsState>((set, get) => ({
       }
     },
 
+    refreshModelCapabilities: async (id: string, profileId: string) => {
+      try {
+        const refreshedModel = await refreshModelCapabilitiesAPI(id, profileId);
+        if (refreshedModel) {
+          set((state) => ({ models: state.models.map((model) => (model.id === id ? refreshedModel : model)) }));
+        }
+        return refreshedModel;
+      } catch (error) {
+        set({ error: `Failed to refresh the capabilities of model ${id}: ${error instanceof Error ? error.message : String(error)}` });
+        return null;
+      }
+    },
+
     deleteModel: async (id: string) => {
       try {
         set({ isLoading: true, error: null });
@@ -139,6 +163,17 @@ export const useModelsStore = create<ModelsState>((set, get) => ({
       }
     },
 
+    // Throws when the update fails; false when the model isn't in the profile
+    setFavorite: async (profileId: string, id: string, favorite: boolean) => {
+      const updated = await setModelFavoriteAPI(id, profileId, favorite);
+      if (updated) {
+        set((state) => ({
+          models: state.models.map((model) => (model.id === id ? { ...model, favorite } : model)),
+        }));
+      }
+      return updated;
+    },
+
     // List Operations
     fetchModels: async (filter?: ModelFilter) => {
       try {
@@ -187,7 +222,7 @@ export const useModelsStore = create<ModelsState>((set, get) => ({
 }));
 
 export const useModels = () => useModelsStore((state) => state.models);
-export const useModelById = (id: string) => useModelsStore((state) => state.models.find((model) => model.id === id));
+export const useModelById = (id: string) => useModelsStore((state) => findModelByIdOrAlias(state.models, id));
 export const useModelsLoading = () => useModelsStore((state) => state.isLoading);
 export const useModelsError = () => useModelsStore((state) => state.error);
 export const useModelsActions = () => useModelsStore((state) => state.actions);
@@ -186,8 +186,9 @@ export function useAgentWorkflow() {
       },
       runInference: async (opts: {
         messages: import("@/schema/inference-engine-schema").InferenceMessage[];
-        modelSpecs: { id: string; model_type: "chat" | "completion"; config: any; max_concurrent_requests: number; engine: string };
+        modelSpecs: { id: string; model_type: "chat" | "completion"; config: any; max_concurrent_requests: number; engine: string; supports_reasoning?: boolean };
         systemPrompt?: string;
+        examples?: import("@/schema/inference-engine-schema").InferenceMessage[];
         parameters?: Record<string, any>;
         stream?: boolean;
         toolset?: WorkflowToolDefinition[];
@@ -199,6 +200,7 @@ export function useAgentWorkflow() {
           messages: opts.messages,
           modelSpecs: opts.modelSpecs,
           systemPrompt: opts.systemPrompt,
+          examples: opts.examples,
           parameters: opts.parameters,
           stream: false,
           tools: executableTools.length > 0 ? executableTools : undefined,
@@ -0,0 +1,80 @@
+import { listen } from "@tauri-apps/api/event";
+import { useEffect } from "react";
+import { toast } from "sonner";
+import { type ImportWatchFiles, type ImportWatchStatus, startImportWatch, stopImportWatch } from "@/commands/imports";
+import { useCharacterActions } from "@/hooks/characterStore";
+import { useCurrentProfile, useIsAuthenticated } from "@/hooks/ProfileStore";
+import { importWatchedFiles } from "@/services/imports/auto-import";
+
+// When the last batch was imported, so cards added while the app was closed are picked up next time
+const lastImportKey = (profileId: string) => `auto-import-last-scan-${profileId}`;
+
+/**
+ * Runs the profile's folder watch (Settings > System) while it's logged in, and imports what it reports
+ */
+export function useAutoImportWatcher() {
+  const currentProfile = useCurrentProfile();
+  const isAuthenticated = useIsAuthenticated();
+  const { fetchCharacters } = useCharacterActions();
+
+  const profileId = isAuthenticated ? currentProfile?.id : undefined;
+  const enabled = !!currentProfile?.settings?.system?.autoImportEnabled;
+  const directory = currentProfile?.settings?.system?.autoImportDirectory ?? "";
+
+  useEffect(() => {
+    if (!profileId || !enabled || !directory) {
+      return;
+    }
+
+    // One batch at a time, so names taken by a batch are seen by the next
+    let queue = Promise.resolve();
+
+    const unlistenFiles = listen<ImportWatchFiles>("import-watch-files", ({ payload }) => {
+      if (payload.profile_id !== profileId) {
+        return;
+      }
+      queue = queue
+        .then(async () => {
+          const results = await importWatchedFiles(profileId, payload.paths);
+          localStorage.setItem(lastImportKey(profileId), String(Date.now()));
+
+          const failed = results.filter((result) => result.status === "failed");
+          if (failed.length > 0) {
+            toast.error(`Failed to import ${failed.length} ${failed.length === 1 ? "card" : "cards"} from the watched folder`, {
+              description: failed.map((result) => `${result.fileName}: ${result.error}`).join("\n"),
+            });
+          }
+          if (failed.length < results.length) {
+            await fetchCharacters(profileId);
+          }
+        })
+        .catch((error) => toast.error("Failed to import from the watched folder", { description: String(error) }));
+    });
+
+    const unlistenStatus = listen<ImportWatchStatus>("import-watch-status", ({ payload }) => {
+      if (payload.profile_id !== profileId) {
+        return;
+      }
+      if (!payload.available) {
+        toast.warning("The watched import folder is unavailable", { description: `${payload.error ?? payload.directory}. Retrying until it's back.` });
+      } else if (payload.warning) {
+        toast.warning("The import folder can't be watched normally", { description: `${payload.warning}. New cards may take longer to show up.` });
+      }
+    });
+
+    const stored = localStorage.getItem(lastImportKey(profileId));
+    if (!stored) {
+      // First run: what is already in the folder isn't new
+      localStorage.setItem(lastImportKey(profileId), String(Date.now()));
+    }
+    startImportWatch(profileId, directory, stored ? Number(stored) : null).catch((error) => {
+      toast.error("Failed to watch the import folder", { description: String(error) });
+    });
+
+    return () => {
+      unlistenFiles.then((unlisten) => unlisten());
+      unlistenStatus.then((unlisten) => unlisten());
+      stopImportWatch(profileId).catch((error) => console.error("Failed to stop the import folder watch:", error));
+    };
+  }, [profileId, enabled, directory, fetchCharacters]);
+}
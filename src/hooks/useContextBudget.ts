@@ -0,0 +1,49 @@
+import { useCallback } from "react";
+import { useCurrentChatId, useCurrentChatMarkers, useCurrentChatMessages, useCurrentChatParticipants } from "@/hooks/chatStore";
+import { useModels } from "@/hooks/modelsStore";
+import { buildContextBudget, type ContextBudget } from "@/services/inference/context-budget";
+import { applyContextReset } from "@/services/inference/formatter/apply-context-reset";
+import { usePromptFormatter } from "@/services/inference/prompt-formatter";
+import { findModelByIdOrAlias } from "@/services/model-service";
+
+/**
+ * Context budget of the next request in the current chat, formatted the way a generation would be
+ * for its first enabled participant. `modelId` picks the model whose context length is reported;
+ * by default the chat template's.
+ */
+export function useContextBudget() {
+  const currentChatId = useCurrentChatId();
+  const chatMessages = useCurrentChatMessages();
+  const markers = useCurrentChatMarkers();
+  const participants = useCurrentChatParticipants();
+  const models = useModels();
+  const { formatPrompt } = usePromptFormatter();
+
+  const getContextBudget = useCallback(
+    async (chatId: string, modelId?: string): Promise<ContextBudget> => {
+      if (chatId !== currentChatId) {
+        throw new Error("The context budget is only available for the open chat");
+      }
+
+      const characterId = participants?.find((participant) => participant.enabled)?.id;
+      const prompt = await formatPrompt(undefined, characterId);
+      if (!prompt?.statistics) {
+        throw new Error("Failed to format the prompt");
+      }
+
+      const model = modelId ? findModelByIdOrAlias(models, modelId) : prompt.modelSettings;
+      if (!model) {
+        throw new Error(`Model ${modelId} not found`);
+      }
+
+      return buildContextBudget(prompt.statistics, {
+        maxContext: Number(prompt.chatTemplate.config?.max_context) || 0,
+        modelContextLength: model.capabilities?.context_length,
+        messages: applyContextReset(chatMessages, markers),
+      });
+    },
+    [currentChatId, participants, formatPrompt, models, chatMessages, markers],
+  );
+
+  return { getContextBudget };
+}
@@ -0,0 +1,46 @@
+import { useEffect } from "react";
+import { trimMemory } from "@/commands/inference";
+import { useConsoleStore } from "@/hooks/consoleStore";
+import { useCurrentProfile } from "@/hooks/ProfileStore";
+import { trimProviderCache } from "@/services/ai-providers/aisdk/provider-cache";
+import { trimTokenCache } from "@/services/inference/formatter/apply-context-limit";
+
+const TRIM_INTERVAL_MS = 5 * 60 * 1000;
+
+/**
+ * Drop the caches not used for `maxIdleMs`: backend tokenizers, cached token counts, console
+ * request snapshots and inference providers. Dropped entries are rebuilt on next use. Logs what was freed.
+ */
+export async function trimIdleMemory(maxIdleMs: number) {
+  const tokenCounts = trimTokenCache(maxIdleMs);
+  const requests = useConsoleStore.getState().actions.trimIdleRequests(maxIdleMs);
+  const providers = trimProviderCache(maxIdleMs);
+  const backend = await trimMemory(Math.floor(maxIdleMs / 1000)).catch((error) => {
+    console.error("Failed to trim backend caches:", error);
+    return { evicted: [] as string[] };
+  });
+
+  if (tokenCounts.evicted > 0 || requests > 0 || providers > 0 || backend.evicted.length > 0) {
+    const tokenizers = backend.evicted.length > 0 ? ` (${backend.evicted.join(", ")})` : "";
+    console.info(
+      `Idle trim: ${tokenCounts.evicted} token counts (~${Math.round(tokenCounts.estimatedBytes / 1024)} KB), ${requests} request snapshots, ${providers} providers, ${backend.evicted.length} tokenizers${tokenizers}`,
+    );
+  }
+}
+
+/**
+ * Runs trimIdleMemory every few minutes with the profile's idle threshold (Settings > System)
+ */
+export function useIdleMemoryTrim() {
+  const currentProfile = useCurrentProfile();
+  const hasProfile = !!currentProfile;
+  const idleMinutes = currentProfile?.settings?.system?.idleTrimMinutes ?? 30;
+
+  useEffect(() => {
+    if (!hasProfile || idleMinutes <= 0) {
+      return;
+    }
+    const interval = setInterval(() => trimIdleMemory(idleMinutes * 60 * 1000), TRIM_INTERVAL_MS);
+    return () => clearInterval(interval);
+  }, [hasProfile, idleMinutes]);
+}
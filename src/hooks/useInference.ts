@@ -1,4 +1,5 @@
 import { useCallback, useEffect, useRef, useState } from "react";
+import { toast } from "sonner";
 
 import {
   InferenceCancelledResponse,
@@ -10,13 +11,27 @@ import {
   InferenceToolDefinition,
   ModelSpecs,
 } from "@/schema/inference-engine-schema";
+import { appendInferenceLog, InferenceLogEntry } from "@/commands/inference";
 import { Engine } from "@/schema/model-manifest-schema";
 import { callProviderConverseEndpoint } from "@/services/ai-providers/start-inference";
-import type { AIEvent, AIStreamPayload } from "@/services/ai-providers/types/ai-event.type";
+import { type AIEvent, type AIStreamPayload, type AIUsage, BUDGET_EXCEEDED, DUPLICATE_REQUEST } from "@/services/ai-providers/types/ai-event.type";
+import { checkBudget, recordRequestUsage } from "@/services/inference/budget";
+import { acquireGlobalSlot, setGlobalConcurrency } from "@/services/inference/global-concurrency";
+import { attributeToFallback, canFallBack, emitFallbackUsed, holdFallbackError, resolveFallbackModel } from "@/services/inference/model-fallback";
+import { checkParameterStrictness } from "@/services/inference/parameter-strictness";
+import { claimRequest, releaseRequest, requestFingerprint } from "@/services/inference/request-dedupe";
+import { type PrioritizedRequest, type RequestPriority, takeNextRequest } from "@/services/inference/request-priority";
+import { validateInferenceRequest } from "@/services/inference/request-validation";
+import { appendSnapshotText, dropRequestSnapshot, setSnapshotStatus, trackRequestSnapshot } from "@/services/inference/stream-snapshots";
+import { trackInferenceProgress, untrackInferenceProgress, updateInferenceProgress } from "@/services/inference/taskbar-progress";
 
 import { useConsoleStoreActions } from "./consoleStore";
+import { useProfileStore } from "./ProfileStore";
 
-type InferenceStatus = "idle" | "queued" | "streaming" | "completed" | "error" | "cancelled";
+// "started" is set when the request leaves the queue, before the first chunk arrives
+type InferenceStatus = "idle" | "queued" | "started" | "streaming" | "completed" | "error" | "cancelled";
+
+export const isActiveInferenceStatus = (status: InferenceStatus | null | undefined): boolean => status === "queued" || status === "started" || status === "streaming";
 
 interface InferenceRequestState {
   id: string;
@@ -28,6 +43,7 @@ interface InferenceRequestState {
 }
 
 interface UseInferenceOptions {
+  onStart?: (requestId: string, modelId: string) => void;
   onComplete?: (response: InferenceCompletedResponse | InferenceCancelledResponse, requestId: string) => void;
   onError?: (error: unknown, requestId: string) => void;
   onStream?: (partialResponse: InferenceStreamingResponse, requestId: string) => void;
@@ -41,30 +57,66 @@ interface InferenceParams {
   messages: InferenceMessage[];
   modelSpecs: ModelSpecs;
   systemPrompt?: string;
+  // Few-shot examples, sent after the system prompt and before the conversation
+  examples?: InferenceMessage[];
   parameters?: Record<string, unknown>;
   stream?: boolean;
   requestId?: string;
   disableLogs?: boolean;
   tools?: ExecutableToolDefinition[];
+  // Chat the prompt belongs to, used to track which prefix stayed the same for prompt caching
+  // and to start the request first while that chat is focused
+  chatId?: string;
+  // Order among waiting requests, "normal" by default: "interactive" when the user waits on the reply,
+  // "background" for jobs like summaries (see request-priority.ts)
+  priority?: RequestPriority;
+  // Exact text to send instead of `messages`, which must then be empty. Completion models receive it
+  // without any inference template framing, chat models as a single user message.
+  rawPrompt?: string;
+  // Echoed as `client_metadata` on every response of the request, never sent to the provider
+  clientMetadata?: unknown;
 }
 
 interface RequestRuntimeState {
+  // Profile the request's usage counts against
+  profileId?: string;
+  // The model currently serving the request, a fallback once the primary failed
   modelId: string;
+  // Its config, for the prices of the usage
+  modelConfig: Record<string, unknown>;
+  fallbackFrom?: string;
+  engine: string;
+  actualModel?: string;
+  parameters: Record<string, unknown>;
+  // As sent, for the inference log
+  prompt: { system_prompt: string; messages: InferenceMessage[] };
+  clientMetadata?: unknown;
+  startedAt: number;
   accumulatedText: string;
   accumulatedReasoning: string;
   accumulatedFullResponse: string;
   toolCalls: InferenceToolCall[];
+  notices: string[];
+  // Summed over every attempt, since providers bill the retried ones too
+  usage?: AIUsage;
+  guardrailTrace?: unknown;
+  finishReason?: string;
   abort?: () => void;
+  // A provider call is running, so a cancel has a stream to close
+  inFlight: boolean;
   cancelled: boolean;
+  cancelRequestedAt?: number;
   finished: boolean;
 }
 
 interface ConcurrencyState {
   active: number;
-  queue: Array<() => void>;
+  queue: Array<PrioritizedRequest & { start: () => void }>;
 }
 
 const MAX_COMPLETED_AGE_MS = 10 * 60 * 1000; // 10 minutes
+// How long a cancel waits for the provider stream to close before reporting it unconfirmed
+const CANCEL_CONFIRM_TIMEOUT_MS = 2000;
 
 const createStreamingResult = (payload: AIStreamPayload, state: RequestRuntimeState) => ({
   text: payload.text,
@@ -96,6 +148,56 @@ const mergeToolCalls = (existing: InferenceToolCall[], incoming?: InferenceToolC
   return Array.from(callMap.values());
 };
 
+// Rough error classification for the inference log, the full message stays in the console
+const classifyError = (message: string): string => {
+  const lower = message.toLowerCase();
+  if (/\b(401|403)\b|unauthorized|forbidden|api key/.test(lower)) {
+    return "auth";
+  }
+  if (/\b429\b|rate limit|too many requests/.test(lower)) {
+    return "rate_limit";
+  }
+  if (/\b5\d\d\b|internal server error|bad gateway|service unavailable/.test(lower)) {
+    return "server";
+  }
+  if (/timeout|timed out/.test(lower)) {
+    return "timeout";
+  }
+  if (/fetch failed|network|econnrefused|enotfound|connection/.test(lower)) {
+    return "network";
+  }
+  if (/context length|too long|maximum context/.test(lower)) {
+    return "context_length";
+  }
+  return "unknown";
+};
+
+// Write the request to the rotating log file when the profile opted in. Never throws.
+const logRequestToFile = (requestId: string, runtime: RequestRuntimeState, status: InferenceLogEntry["status"], errorMessage?: string, errorCode?: string) => {
+  const currentProfile = useProfileStore.getState().currentProfile;
+  const systemSettings = currentProfile?.settings?.system;
+  if (!systemSettings?.inferenceFileLog) {
+    return;
+  }
+
+  appendInferenceLog({
+    timestamp: new Date().toISOString(),
+    request_id: requestId,
+    profile_id: currentProfile?.id,
+    model_id: runtime.modelId,
+    engine: runtime.engine,
+    actual_model: runtime.actualModel,
+    params: runtime.parameters,
+    status,
+    latency_ms: Date.now() - runtime.startedAt,
+    usage: runtime.usage
+      ? { prompt_tokens: runtime.usage.inputTokens, completion_tokens: runtime.usage.outputTokens, total_tokens: runtime.usage.inputTokens + runtime.usage.outputTokens }
+      : null,
+    error_kind: errorCode || (errorMessage ? classifyError(errorMessage) : null),
+    prompt: systemSettings.inferenceLogPrompts ? runtime.prompt : undefined,
+  }).catch((error) => console.error("Failed to write inference log:", error));
+};
+
 export function useInference(options: UseInferenceOptions = {}) {
   const [requests, setRequests] = useState<Record<string, InferenceRequestState>>({});
   const consoleActions = useConsoleStoreActions();
@@ -118,6 +220,13 @@ export function useInference(options: UseInferenceOptions = {}) {
   }, []);
 
   const finalizeRequest = useCallback((requestId: string) => {
+    const runtime = runtimeStateRef.current[requestId];
+    if (runtime?.profileId && runtime.usage) {
+      recordRequestUsage(runtime.profileId, runtime.modelId, runtime.modelConfig, runtime.usage).catch((error) => console.error("Failed to record usage:", error));
+    }
+    untrackInferenceProgress(requestId);
+    releaseRequest(requestId);
+    dropRequestSnapshot(requestId);
     delete runtimeStateRef.current[requestId];
   }, []);
 
@@ -128,23 +237,25 @@ export function useInference(options: UseInferenceOptions = {}) {
     }
 
     state.active = Math.max(0, state.active - 1);
-    const next = state.queue.shift();
+    const next = takeNextRequest(state.queue);
     if (next) {
       state.active += 1;
-      next();
+      next.start();
     }
   }, []);
 
   const handleStream = useCallback(
     (requestId: string, payload: AIStreamPayload) => {
       const runtime = runtimeStateRef.current[requestId];
-      if (!runtime || runtime.finished) {
+      // Chunks still arriving while a cancelled stream closes are dropped
+      if (!runtime || runtime.finished || runtime.cancelled) {
         return;
       }
 
       if (payload.text) {
         runtime.accumulatedText += payload.text;
         runtime.accumulatedFullResponse = runtime.accumulatedText;
+        appendSnapshotText(requestId, payload.text);
       }
 
       if (payload.fullResponse) {
@@ -157,8 +268,37 @@ export function useInference(options: UseInferenceOptions = {}) {
 
       runtime.toolCalls = mergeToolCalls(runtime.toolCalls, payload.toolCalls);
 
+      // Rough count (~4 characters a token), providers report usage only at the end
+      updateInferenceProgress(requestId, Math.ceil((runtime.accumulatedText.length + runtime.accumulatedReasoning.length) / 4));
+
+      if (payload.usage) {
+        runtime.usage = {
+          inputTokens: (runtime.usage?.inputTokens ?? 0) + payload.usage.inputTokens,
+          outputTokens: (runtime.usage?.outputTokens ?? 0) + payload.usage.outputTokens,
+        };
+        if (!payload.text && !payload.reasoning && !payload.fullResponse && !payload.toolCalls && !payload.notices && !payload.finishReason) {
+          return;
+        }
+      }
+
+      if (payload.finishReason) {
+        runtime.finishReason = payload.finishReason;
+        if (!payload.text && !payload.reasoning && !payload.fullResponse && !payload.toolCalls && !payload.notices) {
+          return;
+        }
+      }
+
+      if (payload.notices || payload.guardrailTrace !== undefined) {
+        runtime.notices = [...runtime.notices, ...(payload.notices ?? [])];
+        runtime.guardrailTrace = payload.guardrailTrace ?? runtime.guardrailTrace;
+        if (!payload.text && !payload.reasoning && !payload.fullResponse && !payload.toolCalls) {
+          return;
+        }
+      }
+
       const streamingResponse: InferenceStreamingResponse = {
         request_id: requestId,
+        client_metadata: runtime.clientMetadata,
         status: "streaming",
         result: createStreamingResult(payload, runtime),
       };
@@ -180,7 +320,7 @@ export function useInference(options: UseInferenceOptions = {}) {
   const handleCompletion = useCallback(
     (requestId: string, payload?: AIStreamPayload) => {
       const runtime = runtimeStateRef.current[requestId];
-      if (!runtime || runtime.finished) {
+      if (!runtime || runtime.finished || runtime.cancelled) {
         return;
       }
 
@@ -192,12 +332,20 @@ export function useInference(options: UseInferenceOptions = {}) {
 
       const result: InferenceCompletedResponse = {
         request_id: requestId,
+        client_metadata: runtime.clientMetadata,
         status: "completed",
         result: {
           text: runtime.accumulatedText || payload?.text,
           reasoning: runtime.accumulatedReasoning || payload?.reasoning,
           full_response: runtime.accumulatedFullResponse || payload?.fullResponse,
           tool_calls: runtime.toolCalls.length > 0 ? runtime.toolCalls : payload?.toolCalls,
+          notices: runtime.notices.length > 0 ? runtime.notices : undefined,
+          guardrail_trace: runtime.guardrailTrace,
+          empty_retries: payload?.emptyRetries,
+          finish_reason: runtime.finishReason,
+          model_id: runtime.modelId,
+          fallback_from: runtime.fallbackFrom,
+          usage: runtime.usage,
         },
       };
 
@@ -211,14 +359,16 @@ export function useInference(options: UseInferenceOptions = {}) {
       }));
 
       consoleActions.updateRequestResponse(requestId, result);
+      logRequestToFile(requestId, runtime, "completed");
       optionsRef.current.onComplete?.(result, requestId);
       finalizeRequest(requestId);
     },
     [consoleActions, finalizeRequest, handleStream, updateRequestState],
   );
 
+  // `streamClosed` tells whether the provider call returned after the cancel, so the connection was dropped
   const handleCancellation = useCallback(
-    (requestId: string) => {
+    (requestId: string, streamClosed = true) => {
       const runtime = runtimeStateRef.current[requestId];
       if (!runtime || runtime.finished) {
         return;
@@ -228,12 +378,15 @@ export function useInference(options: UseInferenceOptions = {}) {
 
       const result: InferenceCancelledResponse = {
         request_id: requestId,
+        client_metadata: runtime.clientMetadata,
         status: "cancelled",
         result: {
           text: runtime.accumulatedText,
           reasoning: runtime.accumulatedReasoning,
           full_response: runtime.accumulatedFullResponse,
           tool_calls: runtime.toolCalls.length > 0 ? runtime.toolCalls : undefined,
+          cancel_latency_ms: runtime.cancelRequestedAt !== undefined ? Date.now() - runtime.cancelRequestedAt : undefined,
+          stream_closed: streamClosed,
         },
         error: undefined,
       };
@@ -248,6 +401,7 @@ export function useInference(options: UseInferenceOptions = {}) {
       }));
 
       consoleActions.updateRequestResponse(requestId, result);
+      logRequestToFile(requestId, runtime, "cancelled");
       optionsRef.current.onComplete?.(result, requestId);
       finalizeRequest(requestId);
     },
@@ -257,7 +411,8 @@ export function useInference(options: UseInferenceOptions = {}) {
   const handleError = useCallback(
     (requestId: string, error: unknown) => {
       const runtime = runtimeStateRef.current[requestId];
-      if (!runtime || runtime.finished) {
+      // The abort of a cancelled request may surface as an error, the cancellation is reported instead
+      if (!runtime || runtime.finished || runtime.cancelled) {
         return;
       }
 
@@ -271,12 +426,24 @@ export function useInference(options: UseInferenceOptions = {}) {
             : typeof error === "string"
               ? error
               : "Unknown error";
-      const serializedError = JSON.stringify({ message: errorMessage, details: error });
+      const errorCode = typeof error === "object" && error && "code" in error ? String((error as { code?: unknown }).code || "") || undefined : undefined;
+      const retryable = typeof error === "object" && error && "retryable" in error ? !!(error as { retryable?: unknown }).retryable : undefined;
+      const serializedError = JSON.stringify({ message: errorMessage, code: errorCode, retryable, details: error });
 
+      // Keep whatever was produced before the failure so reasoning isn't lost when the answer never arrived
+      const hasPartialResult = !!(runtime.accumulatedText || runtime.accumulatedReasoning);
       const response: InferenceResponse = {
         request_id: requestId,
+        client_metadata: runtime.clientMetadata,
         status: "error",
         error: serializedError,
+        result: hasPartialResult
+          ? {
+              text: runtime.accumulatedText || undefined,
+              reasoning: runtime.accumulatedReasoning || undefined,
+              full_response: runtime.accumulatedFullResponse || undefined,
+            }
+          : undefined,
       } as InferenceResponse;
 
       updateRequestState(requestId, (previous) => ({
@@ -289,6 +456,7 @@ export function useInference(options: UseInferenceOptions = {}) {
       }));
 
       consoleActions.updateRequestResponse(requestId, response);
+      logRequestToFile(requestId, runtime, "error", errorMessage, errorCode);
       optionsRef.current.onError?.(error, requestId);
       finalizeRequest(requestId);
     },
@@ -314,16 +482,20 @@ export function useInference(options: UseInferenceOptions = {}) {
   );
 
   const enqueueRequest = useCallback(
-    async (modelKey: string, maxConcurrent: number, executor: () => Promise<void>) => {
+    async (modelKey: string, maxConcurrent: number, executor: () => Promise<void>, chatId?: string, priority?: RequestPriority) => {
       const state: ConcurrencyState = concurrencyRef.current[modelKey] ?? { active: 0, queue: [] };
       concurrencyRef.current[modelKey] = state;
 
-      const run = () => {
+      const run = async () => {
+        // Every request also needs a global slot so the total across models stays bounded
+        setGlobalConcurrency(useProfileStore.getState().currentProfile?.settings?.system?.maxConcurrentRequests ?? 0);
+        const releaseGlobalSlot = await acquireGlobalSlot(chatId, priority);
         executor()
           .catch(() => {
             /* errors handled downstream */
           })
           .finally(() => {
+            releaseGlobalSlot();
             releaseConcurrencySlot(modelKey);
           });
       };
@@ -332,8 +504,13 @@ export function useInference(options: UseInferenceOptions = {}) {
         state.active += 1;
         run();
       } else {
-        state.queue.push(() => {
-          run();
+        state.queue.push({
+          chatId,
+          priority,
+          enqueuedAt: Date.now(),
+          start: () => {
+            run();
+          },
         });
       }
     },
@@ -342,16 +519,45 @@ export function useInference(options: UseInferenceOptions = {}) {
 
   const runInference = useCallback(
     async (params: InferenceParams) => {
-      const { messages, modelSpecs, systemPrompt, parameters = {}, requestId: providedId, disableLogs } = params;
+      const { messages, modelSpecs, systemPrompt, examples, parameters = {}, requestId: providedId, disableLogs } = params;
+      // Throws InvalidInferenceRequestError before anything is tracked or queued
+      validateInferenceRequest({ requestId: providedId, messages, examples, parameters });
 
       const requestId = providedId || `req_${Date.now()}_${Math.random().toString(36).substring(2, 9)}`;
+      const sentMessages = params.rawPrompt !== undefined ? [{ role: "user" as const, text: params.rawPrompt }] : examples?.length ? [...examples, ...messages] : messages;
+
+      const profile = useProfileStore.getState().currentProfile;
+
+      // Double sends are refused before anything is tracked, so the earlier request carries on untouched
+      const existing = runtimeStateRef.current[requestId];
+      let duplicateOf = existing && !existing.finished ? requestId : null;
+      const dedupeWindowMs = profile?.settings?.system?.dedupeWindowMs ?? 2000;
+      if (!duplicateOf && params.chatId && dedupeWindowMs > 0) {
+        const fingerprint = await requestFingerprint([modelSpecs.id, params.chatId, systemPrompt ?? "", sentMessages, parameters]);
+        duplicateOf = claimRequest(fingerprint, requestId, dedupeWindowMs);
+      }
+      if (duplicateOf) {
+        console.warn(`Ignoring request ${requestId}, a duplicate of ${duplicateOf}`);
+        optionsRef.current.onError?.({ message: "The same request was just sent", code: DUPLICATE_REQUEST, retryable: false, details: { duplicate_of: duplicateOf } }, requestId);
+        return null;
+      }
 
       runtimeStateRef.current[requestId] = {
+        profileId: profile?.id,
         modelId: modelSpecs.id,
+        modelConfig: modelSpecs.config,
+        engine: modelSpecs.engine,
+        actualModel: typeof modelSpecs.config?.model === "string" ? modelSpecs.config.model : undefined,
+        parameters,
+        prompt: { system_prompt: systemPrompt || "", messages: sentMessages },
+        clientMetadata: params.clientMetadata,
+        startedAt: Date.now(),
         accumulatedText: "",
         accumulatedReasoning: "",
         accumulatedFullResponse: "",
         toolCalls: [],
+        notices: [],
+        inFlight: false,
         cancelled: false,
         finished: false,
       };
@@ -364,46 +570,131 @@ export function useInference(options: UseInferenceOptions = {}) {
         error: null,
         timestamp: Date.now(),
       }));
+      trackInferenceProgress(requestId, Number(parameters.max_tokens) || undefined);
+      trackRequestSnapshot(requestId, params.chatId ?? null);
 
       if (!disableLogs) {
         consoleActions.addRequest({
           id: requestId,
           systemPrompt: systemPrompt || "",
-          messages,
+          messages: sentMessages,
           modelSpecs,
           parameters: parameters,
           engine: modelSpecs.engine as Engine,
         });
       }
 
-      const executor = async () => {
+      // Moves the request to the next model of the primary's fallback chain, in that model's own queue
+      const fallBack = async (failedSpecs: ModelSpecs, tried: string[], error: unknown): Promise<boolean> => {
+        const runtime = runtimeStateRef.current[requestId];
+        const next = await resolveFallbackModel(modelSpecs, tried);
+        if (!runtime || runtime.cancelled || !next) {
+          return false;
+        }
+
+        attributeToFallback(runtime, modelSpecs.id, next);
+
+        const reason = typeof error === "object" && error && "code" in error ? String((error as { code?: unknown }).code) : undefined;
+        console.warn(`Model ${failedSpecs.id} failed (${reason ?? "unknown error"}), falling back to ${next.id}`);
+        emitFallbackUsed({ requestId, fromModelId: failedSpecs.id, toModelId: next.id, reason });
+
+        updateRequestState(requestId, (previous) => ({
+          id: requestId,
+          modelId: next.id,
+          status: "queued",
+          response: null,
+          error: null,
+          timestamp: previous?.timestamp || Date.now(),
+        }));
+        setSnapshotStatus(requestId, "queued");
+        await enqueueRequest(next.id, next.max_concurrent_requests, createExecutor(next, [...tried, next.id]), params.chatId, params.priority);
+        return true;
+      };
+
+      const createExecutor = (specs: ModelSpecs, tried: string[]) => async () => {
         const runtime = runtimeStateRef.current[requestId];
         if (!runtime) {
           return;
         }
 
-        const event = createEvent(requestId, disableLogs);
+        // A failure before any output is held back while another model of the chain can still take the request
+        const { event, hold, heldError } = holdFallbackError(createEvent(requestId, disableLogs), (error) =>
+          canFallBack(runtime, tried.length, modelSpecs.fallback_model_ids?.length ?? 0, error),
+        );
+
+        if (!runtime.cancelled) {
+          updateRequestState(requestId, (previous) => ({
+            id: requestId,
+            modelId: specs.id,
+            status: "started",
+            response: null,
+            error: null,
+            timestamp: previous?.timestamp || Date.now(),
+          }));
+          setSnapshotStatus(requestId, "started");
+          optionsRef.current.onStart?.(requestId, specs.id);
+        }
 
+        runtime.inFlight = true;
         try {
-          await callProviderConverseEndpoint(event, params);
-
-          if (runtime.cancelled) {
-            handleCancellation(requestId);
-            return;
-          }
-
-          handleCompletion(requestId);
+          await callProviderConverseEndpoint(event, specs === modelSpecs ? params : { ...params, modelSpecs: specs });
         } catch (error) {
-          if (runtime.cancelled) {
-            handleCancellation(requestId);
+          if (!runtime.cancelled && !hold(error)) {
+            handleError(requestId, error);
             return;
           }
+        } finally {
+          runtime.inFlight = false;
+        }
+
+        // The provider call returned, so a cancelled stream is closed
+        if (runtime.cancelled) {
+          handleCancellation(requestId, true);
+          return;
+        }
 
-          handleError(requestId, error);
+        const held = heldError();
+        if (held !== undefined) {
+          if (!(await fallBack(specs, tried, held))) {
+            handleError(requestId, held);
+          }
+          return;
         }
+
+        handleCompletion(requestId);
       };
 
-      await enqueueRequest(modelSpecs.id, modelSpecs.max_concurrent_requests, executor);
+      // Checked once for the primary, so a request already under way isn't stopped by a fallback
+      const budget = profile
+        ? await checkBudget(profile.id, profile.settings?.budget, modelSpecs.id).catch((error) => {
+            console.error("Failed to check the budget:", error);
+            return null;
+          })
+        : null;
+      const budgetPeriod = budget?.status.period === "day" ? { label: "Daily", name: "daily", next: "tomorrow" } : { label: "Monthly", name: "monthly", next: "next month" };
+      if (budget?.warned) {
+        toast.warning(budget.status.state === "exceeded" ? `${budgetPeriod.label} API budget reached` : `80% of the ${budgetPeriod.name} API budget used`, {
+          description:
+            budget.status.action === "block" && budget.status.state === "exceeded" ? `Requests to paid providers are blocked until ${budgetPeriod.next} or a higher limit.` : undefined,
+        });
+      }
+      if (budget?.blocked) {
+        handleError(requestId, {
+          message: `The ${budgetPeriod.name} API budget of this profile is used up. Raise the limit in Settings or wait until ${budgetPeriod.next}.`,
+          code: BUDGET_EXCEEDED,
+          retryable: false,
+        });
+        return requestId;
+      }
+
+      // Against the primary's engine; a fallback takes the same parameters on a best-effort basis
+      const parameterError = checkParameterStrictness(requestId, modelSpecs, parameters, profile?.settings?.system?.parameterStrictness ?? "warn");
+      if (parameterError) {
+        handleError(requestId, parameterError);
+        return requestId;
+      }
+
+      await enqueueRequest(modelSpecs.id, modelSpecs.max_concurrent_requests, createExecutor(modelSpecs, [modelSpecs.id]), params.chatId, params.priority);
 
       return requestId;
     },
@@ -418,9 +709,17 @@ export function useInference(options: UseInferenceOptions = {}) {
       }
 
       runtime.cancelled = true;
-      runtime.abort?.();
+      runtime.cancelRequestedAt = Date.now();
+      if (!runtime.inFlight) {
+        // Still queued, nothing was sent
+        handleCancellation(requestId);
+        return true;
+      }
 
-      handleCancellation(requestId);
+      // Aborting drops the HTTP stream; the executor reports the cancel once the provider call returns.
+      // Providers bill what they generated until then, so a stream that won't close is reported as such.
+      runtime.abort?.();
+      window.setTimeout(() => handleCancellation(requestId, false), CANCEL_CONFIRM_TIMEOUT_MS);
 
       return true;
     },
@@ -452,10 +751,10 @@ export function useInference(options: UseInferenceOptions = {}) {
     runInference,
     cancelRequest,
     requests,
-    getActiveRequestIds: () => Object.keys(requests).filter((id) => requests[id]?.status === "queued" || requests[id]?.status === "streaming"),
+    getActiveRequestIds: () => Object.keys(requests).filter((id) => isActiveInferenceStatus(requests[id]?.status)),
     getRequestById: (id: string) => requests[id] || null,
     cancelAllRequests: async () => {
-      const activeIds = Object.keys(requests).filter((id) => requests[id]?.status === "queued" || requests[id]?.status === "streaming");
+      const activeIds = Object.keys(requests).filter((id) => isActiveInferenceStatus(requests[id]?.status));
       const results = await Promise.all(activeIds.map((id) => cancelRequest(id)));
       return results.every(Boolean);
     },
@@ -0,0 +1,132 @@
+import { useCallback, useRef } from "react";
+import type { InferenceMessage, ModelSpecs } from "@/schema/inference-engine-schema";
+import { buildSweepMembers, collectSweepResults, type SweepAxis, type SweepMember, type SweepMemberResult, type SweepResult } from "@/services/inference/parameter-sweep";
+import { useInference } from "./useInference";
+
+export interface SweepOptions {
+  modelSpecs: ModelSpecs;
+  messages: InferenceMessage[];
+  systemPrompt?: string;
+  // Sent with every member; the swept parameter overrides its own key
+  parameters?: Record<string, unknown>;
+  axis: SweepAxis;
+  repetitions: number;
+  // Called as each member finishes, in completion order
+  onProgress?: (result: SweepMemberResult, completed: number, total: number) => void;
+}
+
+interface PendingMember {
+  member: SweepMember;
+  // Set when the provider call starts, so the latency leaves out the time spent queued
+  startedAt?: number;
+  resolve: (result: SweepMemberResult) => void;
+}
+
+/**
+ * Runs parameter sweeps (see services/inference/parameter-sweep.ts) on the inference queue.
+ * `runSweep` throws right away for an invalid sweep; its `result` resolves once every member finished, failed
+ * or was cancelled. `cancelSweep` cancels the members still queued or running.
+ */
+export function useParameterSweep() {
+  const pendingRef = useRef<Record<string, PendingMember>>({});
+  const sweepRequestsRef = useRef<Record<string, string[]>>({});
+
+  const settle = useCallback((requestId: string, outcome: Omit<SweepMemberResult, "combination" | "repetition" | "latency_ms">) => {
+    const pending = pendingRef.current[requestId];
+    if (!pending) {
+      return;
+    }
+    delete pendingRef.current[requestId];
+    pending.resolve({
+      combination: pending.member.combination,
+      repetition: pending.member.repetition,
+      latency_ms: pending.startedAt ? Date.now() - pending.startedAt : 0,
+      ...outcome,
+    });
+  }, []);
+
+  const { runInference, cancelRequest } = useInference({
+    onStart: (requestId) => {
+      const pending = pendingRef.current[requestId];
+      if (pending) {
+        pending.startedAt = Date.now();
+      }
+    },
+    onComplete: (response, requestId) => {
+      const text = response.result?.text || response.result?.full_response;
+      if (response.status === "cancelled") {
+        settle(requestId, { status: "cancelled", text });
+        return;
+      }
+      settle(requestId, {
+        status: "completed",
+        text,
+        input_tokens: response.result?.usage?.inputTokens,
+        output_tokens: response.result?.usage?.outputTokens,
+      });
+    },
+    onError: (error, requestId) => {
+      const message = error instanceof Error ? error.message : typeof error === "object" && error && "message" in error ? String((error as { message?: unknown }).message) : String(error);
+      settle(requestId, { status: "error", error: message });
+    },
+  });
+
+  const runSweep = useCallback(
+    (options: SweepOptions): { sweepId: string; result: Promise<SweepResult> } => {
+      const members = buildSweepMembers(options.axis, options.repetitions, options.parameters);
+      const sweepId = crypto.randomUUID();
+      const requestIds = members.map((_, index) => `sweep_${sweepId}_${index}`);
+      sweepRequestsRef.current[sweepId] = requestIds;
+
+      let completed = 0;
+      const runMember = async (member: SweepMember, requestId: string) => {
+        const done = new Promise<SweepMemberResult>((resolve) => {
+          pendingRef.current[requestId] = { member, resolve };
+        });
+
+        let started: string | null = null;
+        try {
+          started = await runInference({
+            messages: options.messages,
+            modelSpecs: options.modelSpecs,
+            systemPrompt: options.systemPrompt,
+            parameters: member.parameters,
+            stream: false,
+            requestId,
+            priority: "background",
+            clientMetadata: { sweep_id: sweepId, combination: member.combination, repetition: member.repetition },
+          });
+        } catch (error) {
+          console.error(`Failed to start sweep request ${requestId}:`, error);
+          settle(requestId, { status: "error", error: error instanceof Error ? error.message : String(error) });
+        }
+        if (!started) {
+          settle(requestId, { status: "error", error: "The request was refused" });
+        }
+
+        const result = await done;
+        completed += 1;
+        options.onProgress?.(result, completed, members.length);
+        return result;
+      };
+
+      const result = Promise.all(members.map((member, index) => runMember(member, requestIds[index])))
+        .then((results) => collectSweepResults(sweepId, options.axis.parameter.trim(), members, results))
+        .finally(() => {
+          delete sweepRequestsRef.current[sweepId];
+        });
+      return { sweepId, result };
+    },
+    [runInference, settle],
+  );
+
+  const cancelSweep = useCallback(
+    async (sweepId: string) => {
+      const requestIds = sweepRequestsRef.current[sweepId] ?? [];
+      await Promise.all(requestIds.filter((requestId) => pendingRef.current[requestId]).map((requestId) => cancelRequest(requestId)));
+    },
+    [cancelRequest],
+  );
+
+  return { runSweep, cancelSweep };
+}
@@ -0,0 +1,77 @@
+import { useCallback } from "react";
+import { findInferenceLogEntry } from "@/commands/inference";
+import { useCurrentChatId } from "@/hooks/chatStore";
+import { useConsoleStore } from "@/hooks/consoleStore";
+import { getChatMessageById, getChatMessagesByChatId } from "@/services/chat-message-service";
+import { removeNestedFields } from "@/services/inference/formatter/remove-nested-fields";
+import { type PromptSnapshot, redactSecrets, snapshotFromCapture, snapshotFromLogEntry } from "@/services/inference/prompt-reconstruction";
+import { usePromptFormatter } from "@/services/inference/prompt-formatter";
+
+/**
+ * Finds the prompt that produced a past message of the current chat: the exact one when it was
+ * captured or logged, otherwise a best-effort rebuild from the chat as it is now.
+ */
+export function usePromptReconstruction() {
+  const currentChatId = useCurrentChatId();
+  const { formatPrompt } = usePromptFormatter();
+
+  const reconstructPrompt = useCallback(
+    async (messageId: string): Promise<{ snapshot: PromptSnapshot; response: string }> => {
+      const message = await getChatMessageById(messageId);
+      if (!message || message.chat_id !== currentChatId) {
+        throw new Error("Message not found in the current chat");
+      }
+      if (message.type !== "character" || !message.character_id) {
+        throw new Error("Only character messages have a prompt to inspect");
+      }
+
+      const response = message.messages[message.message_index] ?? "";
+      const variantModel = message.extra?.variantModels?.[message.message_index] ?? null;
+      const requestId = variantModel?.request_id ?? null;
+
+      if (requestId) {
+        const capture = useConsoleStore.getState().actions.getRequestById(requestId);
+        if (capture) {
+          return { snapshot: snapshotFromCapture(message.id, capture), response };
+        }
+        const logEntry = await findInferenceLogEntry(requestId);
+        const logged = logEntry ? snapshotFromLogEntry(message.id, logEntry) : null;
+        if (logged) {
+          return { snapshot: logged, response };
+        }
+      }
+
+      const chapterMessages = await getChatMessagesByChatId(message.chat_id, message.chapter_id);
+      const priorMessages = chapterMessages.filter((entry) => entry.position < message.position);
+      const prompt = await formatPrompt(undefined, message.character_id, undefined, undefined, priorMessages, undefined, message.id);
+      if (!prompt) {
+        throw new Error("Failed to rebuild the prompt");
+      }
+
+      const warnings = ["Rebuilt from the current templates, characters and lorebooks; the prompt actually sent may have differed."];
+      if (variantModel && prompt.modelSettings && variantModel.model_id !== prompt.modelSettings.id) {
+        warnings.push(`Generated with ${variantModel.model_name ?? variantModel.model_id}, but the chat now uses ${prompt.modelSettings.name}.`);
+      }
+
+      const snapshot: PromptSnapshot = {
+        message_id: message.id,
+        request_id: requestId,
+        system_prompt: prompt.systemPrompt ?? "",
+        messages: prompt.examples?.length ? [...prompt.examples, ...prompt.inferenceMessages] : prompt.inferenceMessages,
+        parameters: redactSecrets(removeNestedFields(prompt.chatTemplate?.config || {})),
+        model: {
+          id: variantModel?.model_id ?? prompt.modelSettings?.id ?? "",
+          name: variantModel?.model_name ?? prompt.modelSettings?.name,
+          actual_model: variantModel?.actual_model,
+          engine: prompt.manifestSettings?.engine,
+        },
+        reconstructed: true,
+        warnings,
+      };
+      return { snapshot, response };
+    },
+    [currentChatId, formatPrompt],
+  );
+
+  return { reconstructPrompt };
+}
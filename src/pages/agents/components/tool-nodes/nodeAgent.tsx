@@ -8,7 +8,9 @@ import { Label } from "@/components/ui/label";
 import { useChatTemplate } from "@/hooks/chatTemplateStore";
 import WidgetConfig from "@/pages/chat/components/WidgetConfig";
 import { promptReplacementSuggestionList } from "@/schema/chat-message-schema";
+import type { InferenceMessage } from "@/schema/inference-engine-schema";
 import { NodeExecutionResult, NodeExecutor } from "@/services/agent-workflow/types";
+import { manifestSupportsReasoning } from "@/services/ai-providers/aisdk/reasoning-support";
 import { estimateTokens } from "@/services/inference/formatter/apply-context-limit";
 import { useTakeSnapshot } from "../../hooks/useUndoRedo";
 import { NodeBase, NodeInput, NodeOutput } from "../tool-components/NodeBase";
@@ -20,6 +22,8 @@ export interface AgentNodeConfig {
   chatTemplateID?: string;
   systemPromptOverride: string;
   inputPrompt: string;
+  // Few-shot examples sent ahead of the history, never trimmed by the context limit
+  examples?: InferenceMessage[];
 }
 
 /**
@@ -85,6 +89,7 @@ const executeAgentNode: NodeExecutor = async (node, inputs, ctx, _agent, deps):
       formatTemplate,
       chatTemplate,
       systemOverridePrompt: systemPrompt,
+      examples: Array.isArray(cfg.examples) ? cfg.examples : undefined,
       chatConfig: {
         character: participantCharacter?.type === "character" ? participantCharacter : undefined,
         user_character: userCharacter ? { name: userCharacter.name, custom: userCharacter.custom, lorebook_id: userCharacter.lorebook_id } : undefined,
@@ -92,7 +97,7 @@ const executeAgentNode: NodeExecutor = async (node, inputs, ctx, _agent, deps):
       },
     });
 
-    const { inferenceMessages, systemPrompt: formattedSystemPrompt, customStopStrings } = promptResult;
+    const { inferenceMessages, systemPrompt: formattedSystemPrompt, examples, customStopStrings } = promptResult;
     const paramsBase = deps.removeNestedFields(chatTemplate?.config || {});
     const fixedParameters = deps.removeNestedFields({ ...(paramsBase || {}), ...((cfg as any).parameters || {}), ...(inputs.parameters || {}) });
     if (customStopStrings) {
@@ -105,6 +110,9 @@ const executeAgentNode: NodeExecutor = async (node, inputs, ctx, _agent, deps):
       config: model.config,
       max_concurrent_requests: model.max_concurrency || 1,
       engine: manifest.engine as string,
+      supports_reasoning: manifestSupportsReasoning(manifest),
+      capabilities: model.capabilities,
+      fallback_model_ids: model.fallback_model_ids,
     };
 
     const toolset = Array.isArray(inputs.toolset) ? inputs.toolset : [];
@@ -112,6 +120,7 @@ const executeAgentNode: NodeExecutor = async (node, inputs, ctx, _agent, deps):
       messages: inferenceMessages,
       modelSpecs,
       systemPrompt: formattedSystemPrompt,
+      examples,
       parameters: fixedParameters,
       stream: false,
       toolset,
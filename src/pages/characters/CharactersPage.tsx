@@ -1,16 +1,20 @@
 import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
-import { open as openDialog } from "@tauri-apps/plugin-dialog";
-import { FileUp, Plus, RefreshCw, Search, SortAsc, Upload, X } from "lucide-react";
+import { open as openDialog, save as saveDialog } from "@tauri-apps/plugin-dialog";
+import { FileUp, Link, Plus, RefreshCw, Search, SortAsc, Upload, X } from "lucide-react";
 import { useEffect, useMemo, useRef, useState } from "react";
 import { toast } from "sonner";
+import { sanitizeFileName } from "@/commands/assets";
+import { ImportFromUrlDialog } from "@/components/shared/ImportFromUrlDialog";
 import { Button, buttonVariants } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import { Select, SelectContent, SelectItem, SelectTrigger } from "@/components/ui/select";
 import { useCharacterActions, useCharacterAvatars, useCharacters, useCharactersLoading } from "@/hooks/characterStore";
+import { useChatActions } from "@/hooks/chatStore";
 import { useLorebookStoreActions } from "@/hooks/lorebookStore";
 import { useCurrentProfile } from "@/hooks/ProfileStore";
 import { Character } from "@/schema/characters-schema";
 import { getCharacterById } from "@/services/character-service";
+import { exportCharacterBundle } from "@/services/exports/character-bundle";
 import { exportCharacterToPng } from "@/services/exports/character-png-export";
 import { prepareLorebookForEmbedding } from "@/services/imports/shared/lorebook-export";
 import { exportSingleToJsonFile } from "@/utils/export-utils";
@@ -44,8 +48,9 @@ export default function Characters() {
   // Store and Local Storage
   const characters = useCharacters();
   const isLoadingCharacters = useCharactersLoading();
-  const { fetchCharacters, deleteCharacter } = useCharacterActions();
+  const { fetchCharacters, deleteCharacter, setFavorite } = useCharacterActions();
   const { loadLorebooks } = useLorebookStoreActions();
+  const { fetchChatList } = useChatActions();
   const [settings, setSettings] = useLocalCharactersPagesSettings();
   const [, setIsEditing] = useState(false);
 
@@ -59,6 +64,7 @@ export default function Characters() {
   const currentProfile = useCurrentProfile();
   const [createDialogOpen, setCreateDialogOpen] = useState(false);
   const [isDraggingFile, setIsDraggingFile] = useState(false);
+  const [isUrlImportOpen, setIsUrlImportOpen] = useState(false);
 
   // Export state
   const [isExportOptionsDialogOpen, setIsExportOptionsDialogOpen] = useState(false);
@@ -179,6 +185,17 @@ export default function Characters() {
   };
 
   const performCharacterExport = async (character: any, options: ExportOptions) => {
+    if (options.exportFormat === "bundle") {
+      const outputPath = await saveDialog({
+        defaultPath: await sanitizeFileName(`character_bundle_${character.name}`, "json"),
+        filters: [{ name: "JSON Files", extensions: ["json"] }],
+      });
+      if (outputPath) {
+        await exportCharacterBundle(character.id, currentProfile!.id, !!options.includeChats, outputPath);
+      }
+      return;
+    }
+
     const exportedCharacter: any = structuredClone(character);
 
     // Include lorebook if requested
@@ -197,7 +214,7 @@ export default function Characters() {
     exportedCharacter.expressions = [];
     exportedCharacter.avatar_path = null;
 
-    const fileName = `character_${character.name.replace(/[^a-zA-Z0-9]/g, "_")}`;
+    const fileName = `character_${character.name}`;
 
     let success = false;
     if (options.exportFormat === "png") {
@@ -225,6 +242,14 @@ export default function Characters() {
     }
   };
 
+  const handleToggleFavorite = async (character: Character) => {
+    try {
+      await setFavorite(character.profile_id, character.id, !character.favorite);
+    } catch (error) {
+      toast.error("Failed to update favorite", { description: error instanceof Error ? error.message : String(error) });
+    }
+  };
+
   const handleTagSelect = (tag: string) => {
     setSettings((prev) => ({
       ...prev,
@@ -344,6 +369,10 @@ export default function Characters() {
                   <Upload className="h-4 w-4" />
                 </Button>
 
+                <Button variant="outline" size="icon" className="bg-background" onClick={() => setIsUrlImportOpen(true)} title="Import from URL">
+                  <Link className="h-4 w-4" />
+                </Button>
+
                 <Select
                   value={`${settings.sort.field}-${settings.sort.direction}`}
                   onValueChange={(value) => {
@@ -404,6 +433,7 @@ export default function Characters() {
                     isLoadingAvatar={isLoadingAvatars}
                     onEdit={handleEdit}
                     onDelete={handleDelete}
+                    onToggleFavorite={handleToggleFavorite}
                     onExport={handleExportCharacter}
                   />
                 ))}
@@ -470,6 +500,25 @@ export default function Characters() {
           }}
         />
 
+        <ImportFromUrlDialog
+          open={isUrlImportOpen}
+          onOpenChange={setIsUrlImportOpen}
+          kindHint="character"
+          onImported={(summary) => {
+            // A chat export lands in the chat list, nothing on this page changes
+            if (summary.kind === "chat") {
+              fetchChatList(currentProfile!.id).catch((error) => console.error("Failed to refresh chats:", error));
+              return;
+            }
+            loadLorebooks(currentProfile!.id);
+            fetchCharacters(currentProfile!.id).then(() => {
+              if (summary.kind === "character") {
+                reloadAvatars(summary.id);
+              }
+            });
+          }}
+        />
+
         {/* Export Options Dialog */}
         <ExportOptionsDialog
           open={isExportOptionsDialogOpen}
@@ -244,7 +244,7 @@ export const CharacterForm = forwardRef<CharacterFormRef, CharacterFormProps>(({
   const { url: avatarUrl, isLoading: isLoadingAvatar } = useImageUrl(avatarImage);
 
   // Set author from profile settings or use a default
-  const [author, setAuthor] = useState((initialData?.settings?.author as string) || currentProfile?.name);
+  const [author, setAuthor] = useState(initialData?.creator || (initialData?.settings?.author as string) || currentProfile?.name);
 
   // Reset form state when editing a different character
   React.useEffect(() => {
@@ -256,7 +256,7 @@ export const CharacterForm = forwardRef<CharacterFormRef, CharacterFormProps>(({
 
     setSystemPrompt(initialData?.system_override || "");
     setSelectedLorebookId(initialData?.lorebook_id || null);
-    setAuthor((initialData?.settings?.author as string) || currentProfile?.name);
+    setAuthor(initialData?.creator || (initialData?.settings?.author as string) || currentProfile?.name);
   }, [initialData?.id]);
 
   useEffect(() => {
@@ -304,6 +304,7 @@ export const CharacterForm = forwardRef<CharacterFormRef, CharacterFormProps>(({
         profile_id: profileId,
         tags,
         settings,
+        creator: author || null,
         system_override: systemPrompt || null,
         external_update_link: null,
         auto_update: true,
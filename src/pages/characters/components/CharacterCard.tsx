@@ -1,4 +1,4 @@
-import { CalendarClock, Download, LoaderIcon, Trash2 } from "lucide-react";
+import { CalendarClock, Download, Heart, LoaderIcon, Trash2 } from "lucide-react";
 import { Badge } from "@/components/ui/badge";
 import { Button } from "@/components/ui/button";
 import { Card, CardContent } from "@/components/ui/card";
@@ -12,6 +12,7 @@ interface CharacterCardProps {
   cardSize: "small" | "medium" | "large";
   onEdit: (model: Character) => void;
   onDelete: (model: Character) => void;
+  onToggleFavorite: (model: Character) => void;
   onExport?: (characterId: string) => void;
 }
 
@@ -36,7 +37,7 @@ const cardSizeClasses: Record<CharacterCardProps["cardSize"], { image: string; t
   },
 };
 
-export function CharacterCard({ model, avatarUrl, isLoadingAvatar, cardSize, onEdit, onDelete, onExport }: CharacterCardProps) {
+export function CharacterCard({ model, avatarUrl, isLoadingAvatar, cardSize, onEdit, onDelete, onToggleFavorite, onExport }: CharacterCardProps) {
   const defaultAvatar = "/avatars/default.jpg";
   const author = (model.settings?.author as string) || "Unknown";
   const tags = model.tags || [];
@@ -126,9 +127,23 @@ export function CharacterCard({ model, avatarUrl, isLoadingAvatar, cardSize, onE
           {tags.length === 0 && <span className="text-xs text-muted-foreground/70">No tags</span>}
         </div>
 
-        <div className="mt-auto flex items-center border-t border-border/60 pt-3 text-xs text-muted-foreground">
-          <CalendarClock className="mr-1.5 h-3.5 w-3.5" />
-          Updated {updatedDate}
+        <div className="mt-auto flex items-center justify-between gap-3 border-t border-border/60 pt-3 text-xs text-muted-foreground">
+          <div className="flex min-w-0 items-center">
+            <CalendarClock className="mr-1.5 h-3.5 w-3.5" />
+            Updated {updatedDate}
+          </div>
+          <Button
+            variant="ghost"
+            size="icon"
+            className="h-8 w-8 rounded-full text-muted-foreground hover:text-primary"
+            onClick={(e) => {
+              e.stopPropagation();
+              onToggleFavorite(model);
+            }}
+            title={model.favorite ? "Remove from favorites" : "Add to favorites"}
+          >
+            <Heart className={cn("h-4 w-4", model.favorite ? "fill-primary text-primary" : "")} />
+          </Button>
         </div>
       </CardContent>
     </Card>
@@ -4,13 +4,22 @@ import { readFile } from "@tauri-apps/plugin-fs";
 import { Upload } from "lucide-react";
 import { forwardRef, useCallback, useEffect, useImperativeHandle, useRef, useState } from "react";
 import { toast } from "sonner";
+import type { z } from "zod";
 import { AlertDialog, AlertDialogAction, AlertDialogCancel, AlertDialogContent, AlertDialogDescription, AlertDialogFooter, AlertDialogHeader, AlertDialogTitle } from "@/components/ui/alert-dialog";
 import { useChatActions } from "@/hooks/chatStore";
 import { useCurrentProfile } from "@/hooks/ProfileStore";
-import { Character } from "@/schema/characters-schema";
+import { Character, CreateCharacterSchema } from "@/schema/characters-schema";
+import { listCharacters } from "@/services/character-service";
 import { saveImage } from "@/services/file-system-service";
 import { extractCharacterSpecV2FromPng } from "@/services/imports/formats/character_spec_png";
-import { importCharacter, parseCharacterContent, validateAndTransformCharacterData } from "@/services/imports/import-character";
+import {
+  findOutdatedCharacter,
+  importCharacter,
+  parseCharacterContent,
+  updateCharacterFromImport,
+  validateAndTransformCharacterData,
+} from "@/services/imports/import-character";
+import { importCharacterBundle } from "@/services/imports/import-character-bundle";
 import { fetchImageAsDataUrl } from "@/utils/image-utils";
 
 interface CharacterImportProps {
@@ -38,6 +47,22 @@ export const CharacterImport = forwardRef<CharacterImportHandle, CharacterImport
   }>({ show: false, chatFields: null, characterData: null, validationResult: null, filePath: "" });
   const [importProgress, setImportProgress] = useState<{ current: number; total: number } | null>(null);
   const [pendingFiles, setPendingFiles] = useState<string[]>([]);
+  // Import of a newer card version of a character the profile has, waiting for update-or-copy
+  const [versionConfirm, setVersionConfirm] = useState<{
+    existing: Character;
+    incoming: z.infer<typeof CreateCharacterSchema>;
+    resolve: (update: boolean) => void;
+  } | null>(null);
+
+  const confirmVersionUpdate = useCallback(
+    (existing: Character, incoming: z.infer<typeof CreateCharacterSchema>) => new Promise<boolean>((resolve) => setVersionConfirm({ existing, incoming, resolve })),
+    [],
+  );
+
+  const answerVersionConfirm = (update: boolean) => {
+    versionConfirm?.resolve(update);
+    setVersionConfirm(null);
+  };
 
   // Main import logic
   const processImport = useCallback(
@@ -108,6 +133,7 @@ export const CharacterImport = forwardRef<CharacterImportHandle, CharacterImport
       setImportProgress({ current: 0, total: paths.length });
       let successCount = 0;
       let failCount = 0;
+      const existingCharacters = await listCharacters(currentProfile.id, { type: "character" }).catch(() => [] as Character[]);
       for (let i = 0; i < paths.length; i++) {
         const filePath = paths[i];
         setImportProgress({ current: i + 1, total: paths.length });
@@ -143,6 +169,21 @@ export const CharacterImport = forwardRef<CharacterImportHandle, CharacterImport
             const decoder = new TextDecoder("utf-8");
             const fileContentString = decoder.decode(fileContentBinary);
             const parsedData = parseCharacterContent(fileContentString);
+            // Bundles carry their own images and chats, and are imported as a whole
+            if (parsedData?.export_type === "character_bundle") {
+              const report = await importCharacterBundle(currentProfile.id, filePath);
+              if (report.placeholderCharacters.length > 0) {
+                toast.warning(`${report.character.name}: created placeholder characters`, {
+                  description: `Not found in this profile: ${report.placeholderCharacters.join(", ")}. Edit them or replace them in the imported chats.`,
+                });
+              }
+              if (report.chats > 0) {
+                fetchChatList(currentProfile.id);
+              }
+              onImportComplete?.(report.character);
+              successCount++;
+              continue;
+            }
             validationResult = validateAndTransformCharacterData(parsedData, currentProfile.id);
             // Handle avatar URL download for chara_card_v2 and similar formats
             if (validationResult.valid && validationResult.data && typeof parsedData === "object" && parsedData.data && typeof parsedData.data.avatar === "string") {
@@ -172,6 +213,16 @@ export const CharacterImport = forwardRef<CharacterImportHandle, CharacterImport
             failCount++;
             continue;
           }
+          // A newer version of a character the profile already has can update it in place
+          const outdated = findOutdatedCharacter(validationResult.data, existingCharacters);
+          if (outdated && (await confirmVersionUpdate(outdated, validationResult.data))) {
+            const updated = await updateCharacterFromImport(outdated.id, validationResult.data);
+            if (updated) {
+              onImportComplete?.(updated);
+              successCount++;
+              continue;
+            }
+          }
           // If chatFields are present, show dialog and pause batch import until user responds
           if (validationResult.chatFields) {
             setChatConfirm({
@@ -210,7 +261,7 @@ export const CharacterImport = forwardRef<CharacterImportHandle, CharacterImport
         toast.error(`${failCount} file${failCount > 1 ? "s" : ""} failed to import.`);
       }
     },
-    [currentProfile, processImport],
+    [currentProfile, processImport, fetchChatList, onImportComplete, confirmVersionUpdate],
   );
 
   // Resume batch import after chat dialog is handled
@@ -282,6 +333,22 @@ export const CharacterImport = forwardRef<CharacterImportHandle, CharacterImport
           </div>
         </div>
       )}
+      <AlertDialog open={!!versionConfirm} onOpenChange={(open) => !open && answerVersionConfirm(false)}>
+        <AlertDialogContent>
+          <AlertDialogHeader>
+            <AlertDialogTitle>Newer Version of {versionConfirm?.existing.name}</AlertDialogTitle>
+            <AlertDialogDescription>
+              This file is version {versionConfirm?.incoming.character_version} of a character you already have
+              {versionConfirm?.existing.character_version ? ` (version ${versionConfirm.existing.character_version})` : " without a version"}. Update it? Its chats and
+              lorebook are kept.
+            </AlertDialogDescription>
+          </AlertDialogHeader>
+          <AlertDialogFooter>
+            <AlertDialogCancel onClick={() => answerVersionConfirm(false)}>Import as New Character</AlertDialogCancel>
+            <AlertDialogAction onClick={() => answerVersionConfirm(true)}>Update Character</AlertDialogAction>
+          </AlertDialogFooter>
+        </AlertDialogContent>
+      </AlertDialog>
       <AlertDialog open={chatConfirm.show} onOpenChange={(open) => !open && setChatConfirm((prev) => ({ ...prev, show: false }))}>
         <AlertDialogContent>
           <AlertDialogHeader>
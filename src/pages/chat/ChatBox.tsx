@@ -18,6 +18,7 @@ interface ChatboxProps {
   handleRenameRequest: (tabId: string) => void;
   handleDuplicateRequest: (tabId: string) => void;
   handleDeleteRequest: (tabId: string) => void;
+  handleFavoriteRequest: (chatId: string) => void;
   handleTabReorder: (newTabOrder: string[]) => void;
   inspectorOpen: boolean;
   onToggleInspector: () => void;
@@ -35,6 +36,7 @@ export const Chatbox: FC<ChatboxProps> = ({
   handleRenameRequest,
   handleDuplicateRequest,
   handleDeleteRequest,
+  handleFavoriteRequest,
   handleTabReorder,
   onToggleInspector,
 }) => {
@@ -52,6 +54,7 @@ export const Chatbox: FC<ChatboxProps> = ({
           onRenameRequest={handleRenameRequest}
           onDuplicateRequest={handleDuplicateRequest}
           onDeleteRequest={handleDeleteRequest}
+          onFavoriteRequest={handleFavoriteRequest}
           onTabReorder={handleTabReorder}
         />
         <div className="flex-1 overflow-hidden">{selectedChatID && <GridLayout tabId={selectedChatID} onToggleInspector={onToggleInspector} />}</div>
@@ -73,6 +76,7 @@ export const Chatbox: FC<ChatboxProps> = ({
             onRenameRequest={handleRenameRequest}
             onDuplicateRequest={handleDuplicateRequest}
             onDeleteRequest={handleDeleteRequest}
+            onFavoriteRequest={handleFavoriteRequest}
           >
             <Button>Create New Chat</Button>
           </ChatMenuDropdown>
@@ -5,12 +5,13 @@ import { DestructiveConfirmDialog } from "@/components/shared/DestructiveConfirm
 import { EditNameDialog } from "@/components/shared/EditNameDialog";
 import { Sheet, SheetContent } from "@/components/ui/sheet";
 import { useChatActions, useChatList, useChatStore, useCurrentChatId } from "@/hooks/chatStore";
+import { useConsoleStoreInspectedRequestId } from "@/hooks/consoleStore";
 import { useCurrentProfile } from "@/hooks/ProfileStore";
 import type { Chat } from "@/schema/chat-schema";
 import { ChatTab, CreateChatParams } from "@/schema/chat-schema";
 import { createChatChapter, listChatChapters } from "@/services/chat-chapter-service";
 import { createChatMessage, listChatMessages } from "@/services/chat-message-service";
-import { getChatById, listChats, updateChat } from "@/services/chat-service";
+import { getChatById, listChats, setChatFavorite, updateChat } from "@/services/chat-service";
 import { useLocalChatTabs } from "@/utils/local-storage";
 import { Chatbox } from "./ChatBox";
 
@@ -41,6 +42,8 @@ export default function ChatPage() {
   // State for delete confirmation dialog
   const [isDeleteDialogOpen, setIsDeleteDialogOpen] = useState(false);
   const [chatToDeleteId, setChatToDeleteId] = useState<string | null>(null);
+  // Set when the profile wants the deletion confirmed a second time
+  const [deleteConfirmation, setDeleteConfirmation] = useState<{ token: string; summary: string; expiresInSecs: number } | null>(null);
 
   // State for Live Inspector Drawer
   const [isInspectorOpen, setIsInspectorOpen] = useState(false);
@@ -102,6 +105,14 @@ export default function ChatPage() {
     };
   }, []); // Empty dependency array ensures this runs only on mount and unmount
 
+  // Open the Live Inspector when a request is sent to it, e.g. a past message's prompt
+  const inspectedRequestId = useConsoleStoreInspectedRequestId();
+  useEffect(() => {
+    if (inspectedRequestId) {
+      setIsInspectorOpen(true);
+    }
+  }, [inspectedRequestId]);
+
   // Memoize tabs to prevent unnecessary recalculations
   const tabs = useMemo(() => {
     if (chatList.length === 0 || openTabIds.length === 0) {
@@ -266,9 +277,29 @@ export default function ChatPage() {
   // Handle delete request from context menu
   const handleDeleteRequest = useCallback((tabId: string) => {
     setChatToDeleteId(tabId);
+    setDeleteConfirmation(null);
     setIsDeleteDialogOpen(true);
   }, []);
 
+  const handleFavoriteRequest = useCallback(
+    async (chatId: string) => {
+      const chat = allChats.find((item) => item.id === chatId);
+      if (!chat) {
+        return;
+      }
+      try {
+        const favorite = !chat.favorite;
+        if (await setChatFavorite(chatId, profileId, favorite)) {
+          setAllChats((prev) => prev.map((item) => (item.id === chatId ? { ...item, favorite } : item)));
+        }
+      } catch (error) {
+        console.error("Failed to update favorite:", error);
+        toast.error("Failed to update favorite", { description: error instanceof Error ? error.message : String(error) });
+      }
+    },
+    [allChats, profileId],
+  );
+
   // Handle tab reordering from drag and drop
   const handleTabReorder = useCallback(
     (newTabOrder: string[]) => {
@@ -286,7 +317,12 @@ export default function ChatPage() {
     const chatName = allChats.find((c) => c.id === chatToDeleteId)?.name || "Chat";
 
     try {
-      await deleteChat(chatToDeleteId);
+      const outcome = await deleteChat(chatToDeleteId, profileId, deleteConfirmation?.token);
+      if (outcome.status === "confirmation_required") {
+        // Keep the dialog open and ask again with what the backend is about to delete
+        setDeleteConfirmation({ token: outcome.token, summary: outcome.summary, expiresInSecs: outcome.expires_in_secs });
+        return;
+      }
       // The deleteChat action in the store handles updating the list and selected chat
       // We just need to update the local `allChats` state and `openTabIds`
       setAllChats((prev) => prev.filter((chat) => chat.id !== chatToDeleteId));
@@ -294,11 +330,11 @@ export default function ChatPage() {
     } catch (error) {
       console.error("Failed to delete chat:", error);
       toast.error(`Failed to delete "${chatName}".`);
-    } finally {
-      setChatToDeleteId(null);
-      setIsDeleteDialogOpen(false);
     }
-  }, [chatToDeleteId, deleteChat, allChats, setOpenTabIds]);
+    setChatToDeleteId(null);
+    setDeleteConfirmation(null);
+    setIsDeleteDialogOpen(false);
+  }, [chatToDeleteId, deleteConfirmation, deleteChat, profileId, allChats, setOpenTabIds]);
 
   // Handle the actual renaming process
   const handleRenameSubmit = async (newName: string) => {
@@ -378,6 +414,7 @@ export default function ChatPage() {
           handleRenameRequest={handleRenameRequest}
           handleDuplicateRequest={handleDuplicateRequest}
           handleDeleteRequest={handleDeleteRequest}
+          handleFavoriteRequest={handleFavoriteRequest}
           handleTabReorder={handleTabReorder}
           inspectorOpen={isInspectorOpen}
           onToggleInspector={() => setIsInspectorOpen((prev) => !prev)}
@@ -402,13 +439,18 @@ export default function ChatPage() {
           onOpenChange={setIsDeleteDialogOpen}
           title="Are you absolutely sure?"
           description={
-            <>This action cannot be undone. This will permanently delete the chat "{allChats.find((c) => c.id === chatToDeleteId)?.name || "this chat"}" and all associated messages and chapters.</>
+            deleteConfirmation ? (
+              <>This will permanently delete the {deleteConfirmation.summary}. Confirm within {deleteConfirmation.expiresInSecs} seconds to go ahead.</>
+            ) : (
+              <>This action cannot be undone. This will permanently delete the chat "{allChats.find((c) => c.id === chatToDeleteId)?.name || "this chat"}" and all associated messages and chapters.</>
+            )
           }
           onConfirm={handleDeleteConfirm}
           onCancel={() => {
             setChatToDeleteId(null);
+            setDeleteConfirmation(null);
           }}
-          confirmText="Delete"
+          confirmText={deleteConfirmation ? "Delete permanently" : "Delete"}
         />
       </div>
     </Sheet>
@@ -21,6 +21,7 @@ interface ChatTabsProps {
   onRenameRequest: (tabId: string) => void;
   onDuplicateRequest: (tabId: string) => void;
   onDeleteRequest: (tabId: string) => void;
+  onFavoriteRequest: (chatId: string) => void;
   onTabReorder: (newTabOrder: string[]) => void;
 }
 
@@ -93,7 +94,7 @@ function SortableTab({ tab, index, activeTab, onTabChange, onCloseTab, onRenameR
   );
 }
 
-export function ChatTabs({ tabs, allChats, profileId, activeTab, onTabChange, onNewChat, onCloseTab, onRenameRequest, onDuplicateRequest, onDeleteRequest, onTabReorder }: ChatTabsProps) {
+export function ChatTabs({ tabs, allChats, profileId, activeTab, onTabChange, onNewChat, onCloseTab, onRenameRequest, onDuplicateRequest, onDeleteRequest, onFavoriteRequest, onTabReorder }: ChatTabsProps) {
   const [activeId, setActiveId] = useState<string | null>(null);
   const [draggedTab, setDraggedTab] = useState<ChatTab | null>(null);
 
@@ -190,6 +191,7 @@ export function ChatTabs({ tabs, allChats, profileId, activeTab, onTabChange, on
               onRenameRequest={onRenameRequest}
               onDuplicateRequest={onDuplicateRequest}
               onDeleteRequest={onDeleteRequest}
+              onFavoriteRequest={onFavoriteRequest}
             >
               <Button variant="ghost" size="sm" className="px-2">
                 <LuPlus className="h-4 w-4 text-foreground" />
@@ -1,5 +1,5 @@
 import { useMemo, useState } from "react";
-import { LuPlus, LuSearch, LuTrash2 } from "react-icons/lu";
+import { LuHeart, LuPlus, LuSearch, LuTrash2 } from "react-icons/lu";
 import { Avatar, AvatarFallback, AvatarImage } from "@/components/ui/avatar";
 import { Command, CommandGroup, CommandItem, CommandList, CommandSeparator } from "@/components/ui/command";
 import { ContextMenu, ContextMenuContent, ContextMenuItem, ContextMenuTrigger } from "@/components/ui/context-menu";
@@ -22,10 +22,11 @@ interface ChatListItemProps {
   onRenameRequest: (chatId: string) => void;
   onDuplicateRequest: (chatId: string) => void;
   onDeleteRequest: (chatId: string) => void;
+  onFavoriteRequest: (chatId: string) => void;
   setOpen: (open: boolean) => void;
 }
 
-function ChatListItem({ chat, avatarUrlMap, participantNameMap, onSelectChat, onRenameRequest, onDuplicateRequest, onDeleteRequest, setOpen }: ChatListItemProps) {
+function ChatListItem({ chat, avatarUrlMap, participantNameMap, onSelectChat, onRenameRequest, onDuplicateRequest, onDeleteRequest, onFavoriteRequest, setOpen }: ChatListItemProps) {
   const handleSelect = () => {
     setTimeout(() => {
       if (!document.querySelector("[data-radix-context-menu-content]")) {
@@ -51,8 +52,14 @@ function ChatListItem({ chat, avatarUrlMap, participantNameMap, onSelectChat, on
         <CommandItem className="flex items-center gap-2.5 px-2 py-1.5 cursor-pointer rounded-md group/row" onSelect={handleSelect}>
           {/* Name + last used */}
           <div className="flex-1 min-w-0">
-            <div className="text-xs font-bold truncate leading-tight">{chat.name}</div>
-            <div className="text-[11px] text-muted-foreground/70 leading-tight mt-0.5">{formatRelativeTime(new Date(chat.updated_at))}</div>
+            <div className="flex items-center gap-1">
+              <span className="text-xs font-bold truncate leading-tight">{chat.name}</span>
+              {chat.favorite && <LuHeart className="h-3 w-3 shrink-0 fill-primary text-primary" aria-label="Favorite" />}
+            </div>
+            <div className="text-[11px] text-muted-foreground/70 leading-tight mt-0.5">
+              {formatRelativeTime(new Date(chat.updated_at))}
+              {!!chat.unread_count && <span className="ml-1.5 font-semibold text-primary">{chat.unread_count} unread</span>}
+            </div>
           </div>
 
           {/* Avatar deck */}
@@ -93,6 +100,7 @@ function ChatListItem({ chat, avatarUrlMap, participantNameMap, onSelectChat, on
       <ContextMenuContent>
         <ContextMenuItem onSelect={() => onRenameRequest(chat.id)}>Rename</ContextMenuItem>
         <ContextMenuItem onSelect={() => onDuplicateRequest(chat.id)}>Duplicate</ContextMenuItem>
+        <ContextMenuItem onSelect={() => onFavoriteRequest(chat.id)}>{chat.favorite ? "Remove from favorites" : "Add to favorites"}</ContextMenuItem>
       </ContextMenuContent>
     </ContextMenu>
   );
@@ -106,10 +114,11 @@ interface ChatListDisplayProps {
   onRenameRequest: (chatId: string) => void;
   onDuplicateRequest: (chatId: string) => void;
   onDeleteRequest: (chatId: string) => void;
+  onFavoriteRequest: (chatId: string) => void;
   setOpen: (open: boolean) => void;
 }
 
-function ChatListDisplay({ chats, avatarUrlMap, participantNameMap, onSelectChat, onRenameRequest, onDuplicateRequest, onDeleteRequest, setOpen }: ChatListDisplayProps) {
+function ChatListDisplay({ chats, avatarUrlMap, participantNameMap, onSelectChat, onRenameRequest, onDuplicateRequest, onDeleteRequest, onFavoriteRequest, setOpen }: ChatListDisplayProps) {
   return (
     <CommandGroup className="p-1">
       {chats.map((chat) => (
@@ -122,6 +131,7 @@ function ChatListDisplay({ chats, avatarUrlMap, participantNameMap, onSelectChat
           onRenameRequest={onRenameRequest}
           onDuplicateRequest={onDuplicateRequest}
           onDeleteRequest={onDeleteRequest}
+          onFavoriteRequest={onFavoriteRequest}
           setOpen={setOpen}
         />
       ))}
@@ -138,10 +148,11 @@ interface ChatMenuDropdownProps {
   onRenameRequest: (chatId: string) => void;
   onDuplicateRequest: (chatId: string) => void;
   onDeleteRequest: (chatId: string) => void;
+  onFavoriteRequest: (chatId: string) => void;
   children: React.ReactNode;
 }
 
-export function ChatMenuDropdown({ allChats, openChatIds, onSelectChat, onCreateChat, onRenameRequest, onDuplicateRequest, onDeleteRequest, children }: ChatMenuDropdownProps) {
+export function ChatMenuDropdown({ allChats, openChatIds, onSelectChat, onCreateChat, onRenameRequest, onDuplicateRequest, onDeleteRequest, onFavoriteRequest, children }: ChatMenuDropdownProps) {
   const [open, setOpen] = useState(false);
   const [searchQuery, setSearchQuery] = useState("");
   const [sortMode, setSortMode] = useState<"recent" | "name">("recent");
@@ -168,14 +179,18 @@ export function ChatMenuDropdown({ allChats, openChatIds, onSelectChat, onCreate
     return (chat.participants ?? []).some((p) => participantNameMap.get(p.id)?.toLowerCase().includes(q));
   });
 
+  // Favorites first, like the chat list query
   const sortedChats = [...filteredChats].sort((a, b) => {
+    if (!!a.favorite !== !!b.favorite) {
+      return a.favorite ? -1 : 1;
+    }
     if (sortMode === "recent") {
       return new Date(b.updated_at).getTime() - new Date(a.updated_at).getTime();
     }
     return a.name.localeCompare(b.name);
   });
 
-  const chatListHandlers = { onSelectChat, onRenameRequest, onDuplicateRequest, onDeleteRequest, setOpen };
+  const chatListHandlers = { onSelectChat, onRenameRequest, onDuplicateRequest, onDeleteRequest, onFavoriteRequest, setOpen };
   const noChatsMessage = searchQuery ? "No matching chats found" : "No closed chats available";
 
   return (
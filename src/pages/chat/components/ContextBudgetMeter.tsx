@@ -0,0 +1,79 @@
+import { useEffect, useRef, useState } from "react";
+import { Progress } from "@/components/ui/progress";
+import { useCurrentChatId, useCurrentChatMessages } from "@/hooks/chatStore";
+import { useChatTemplate } from "@/hooks/chatTemplateStore";
+import { useContextBudget } from "@/hooks/useContextBudget";
+import type { ContextBudget } from "@/services/inference/context-budget";
+
+const REFRESH_DELAY_MS = 500;
+
+interface ContextBudgetMeterProps {
+  chatTemplateId: string;
+}
+
+/**
+ * "X of Y tokens used" for the open chat. Recomputed when a message is added or removed and when the
+ * chat template is saved, not on every streamed chunk.
+ */
+export function ContextBudgetMeter({ chatTemplateId }: ContextBudgetMeterProps) {
+  const currentChatId = useCurrentChatId();
+  const chatMessages = useCurrentChatMessages();
+  const chatTemplate = useChatTemplate(chatTemplateId);
+  const { getContextBudget } = useContextBudget();
+  const [budget, setBudget] = useState<ContextBudget | null>(null);
+
+  // The callback changes with every streamed chunk; the effect's dependencies decide when to run it
+  const getContextBudgetRef = useRef(getContextBudget);
+  getContextBudgetRef.current = getContextBudget;
+
+  // biome-ignore lint/correctness/useExhaustiveDependencies: refresh when messages are added or removed and when the template changes
+  useEffect(() => {
+    let cancelled = false;
+    const timeout = setTimeout(() => {
+      getContextBudgetRef
+        .current(currentChatId)
+        .then((result) => !cancelled && setBudget(result))
+        .catch((error) => {
+          // An empty chat or a strict prompt lock has no budget to show
+          console.warn("Failed to compute the context budget:", error);
+          if (!cancelled) {
+            setBudget(null);
+          }
+        });
+    }, REFRESH_DELAY_MS);
+
+    return () => {
+      cancelled = true;
+      clearTimeout(timeout);
+    };
+  }, [currentChatId, chatMessages.length, chatTemplate]);
+
+  if (!budget || budget.contextWindow <= 0) {
+    return null;
+  }
+
+  const percentage = Math.min((budget.usedTokens / budget.contextWindow) * 100, 100);
+  const details = [
+    `System prompt: ${budget.systemTokens}`,
+    budget.exampleTokens > 0 ? `Examples: ${budget.exampleTokens}` : null,
+    `History: ${budget.historyTokens}`,
+    `Left for the reply: ${budget.remainingTokens} (max ${budget.responseTokens})`,
+    budget.modelContextLength ? `Model context length: ${budget.modelContextLength}` : null,
+  ].filter(Boolean);
+
+  return (
+    <div className="space-y-1" title={details.join("\n")}>
+      <Progress value={percentage} className="h-1.5" />
+      <div className="flex justify-between text-xxs text-muted-foreground">
+        <span>
+          {budget.usedTokens.toLocaleString()} of {budget.contextWindow.toLocaleString()} tokens used
+        </span>
+        {budget.trimmedMessageIds.length > 0 && (
+          <span>
+            {budget.trimmedMessageIds.length} {budget.trimmedMessageIds.length === 1 ? "message" : "messages"} trimmed
+          </span>
+        )}
+      </div>
+    </div>
+  );
+}
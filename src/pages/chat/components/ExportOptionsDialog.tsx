@@ -19,19 +19,23 @@ interface ExportOptionsDialogProps {
 export interface ExportOptions {
   includeFormatTemplate: boolean;
   includeLorebooks: boolean;
-  exportFormat: "json" | "png";
+  exportFormat: "json" | "png" | "bundle";
+  // Bundle only: also export every chat the character took part in
+  includeChats?: boolean;
 }
 
 export function ExportOptionsDialog({ open, onOpenChange, onConfirm, templateName, hasFormatTemplate, hasLorebooks, hasAvatar = false, isCharacterExport = false }: ExportOptionsDialogProps) {
   const [includeFormatTemplate, setIncludeFormatTemplate] = useState(true);
   const [includeLorebooks, setIncludeLorebooks] = useState(true);
-  const [exportFormat, setExportFormat] = useState<"json" | "png">("png");
+  const [exportFormat, setExportFormat] = useState<ExportOptions["exportFormat"]>("png");
+  const [includeChats, setIncludeChats] = useState(true);
 
   const handleConfirm = () => {
     onConfirm({
       includeFormatTemplate: includeFormatTemplate && hasFormatTemplate,
       includeLorebooks: includeLorebooks && hasLorebooks,
       exportFormat,
+      includeChats: exportFormat === "bundle" && includeChats,
     });
     onOpenChange(false);
   };
@@ -52,7 +56,7 @@ export function ExportOptionsDialog({ open, onOpenChange, onConfirm, templateNam
           {isCharacterExport && (
             <div className="space-y-3">
               <Label className="text-sm font-medium">Export Format</Label>
-              <RadioGroup value={exportFormat} onValueChange={(value) => setExportFormat(value as "json" | "png")}>
+              <RadioGroup value={exportFormat} onValueChange={(value) => setExportFormat(value as ExportOptions["exportFormat"])}>
                 <div className="flex items-center space-x-2">
                   <RadioGroupItem value="json" id="json" />
                   <Label htmlFor="json" className="text-sm font-normal">
@@ -65,10 +69,25 @@ export function ExportOptionsDialog({ open, onOpenChange, onConfirm, templateNam
                     PNG file with embedded data {!hasAvatar && "(requires avatar)"}
                   </Label>
                 </div>
+                <div className="flex items-center space-x-2">
+                  <RadioGroupItem value="bundle" id="bundle" />
+                  <Label htmlFor="bundle" className="text-sm font-normal">
+                    Bundle with images and lorebook, to move to another install
+                  </Label>
+                </div>
               </RadioGroup>
             </div>
           )}
 
+          {isCharacterExport && exportFormat === "bundle" && (
+            <div className="flex items-center space-x-2">
+              <Checkbox id="chats" checked={includeChats} onCheckedChange={(checked) => setIncludeChats(checked === true)} />
+              <label htmlFor="chats" className="text-sm font-medium leading-none peer-disabled:cursor-not-allowed peer-disabled:opacity-70">
+                Include chats with this character
+              </label>
+            </div>
+          )}
+
           {hasFormatTemplate && (
             <div className="flex items-center space-x-2">
               <Checkbox id="format-template" checked={includeFormatTemplate} onCheckedChange={(checked) => setIncludeFormatTemplate(checked === true)} />
@@ -78,7 +97,7 @@ export function ExportOptionsDialog({ open, onOpenChange, onConfirm, templateNam
             </div>
           )}
 
-          {hasLorebooks && (
+          {hasLorebooks && exportFormat !== "bundle" && (
             <div className="flex items-center space-x-2">
               <Checkbox id="lorebooks" checked={includeLorebooks} onCheckedChange={(checked) => setIncludeLorebooks(checked === true)} />
               <label htmlFor="lorebooks" className="text-sm font-medium leading-none peer-disabled:cursor-not-allowed peer-disabled:opacity-70">
@@ -1,4 +1,4 @@
-import { BookOpenCheck, ChevronDown, Layers, Layers2, PaperclipIcon, Pencil, PlusIcon, ServerIcon, XIcon } from "lucide-react";
+import { BookOpenCheck, ChevronDown, Layers, Layers2, PaperclipIcon, Pencil, PlusIcon, ServerIcon, TriangleAlert, XIcon } from "lucide-react";
 import { useCallback, useEffect, useMemo, useRef, useState } from "react";
 import isEqual from "react-fast-compare";
 import { toast } from "sonner";
@@ -10,6 +10,7 @@ import { Checkbox } from "@/components/ui/checkbox";
 import { Combobox } from "@/components/ui/combobox";
 import { Command, CommandEmpty, CommandGroup, CommandInput, CommandItem, CommandList } from "@/components/ui/command";
 import { Popover, PopoverContent, PopoverTrigger } from "@/components/ui/popover";
+import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from "@/components/ui/select";
 import { Separator } from "@/components/ui/separator";
 import { StepButton } from "@/components/ui/step-button";
 import { useCharacters } from "@/hooks/characterStore";
@@ -27,11 +28,15 @@ import { parseChatTemplateContent, validateAndTransformChatTemplateData } from "
 import { validateAndTransformFormatTemplateData } from "@/services/imports/import-format-template";
 import { importLorebook, validateAndTransformLorebookData } from "@/services/imports/import-lorebook";
 import { prepareLorebooksForEmbedding } from "@/services/imports/shared/lorebook-export";
+import { validateParameters } from "@/services/inference/parameter-validation";
+import { isResponseLength, RESPONSE_LENGTHS, type ResponseLength } from "@/services/inference/response-length";
+import { findModelByIdOrAlias } from "@/services/model-service";
 import { getChatTemplateById, NewChatTemplateParams } from "@/services/template-chat-service";
 import { createFormatTemplate, getFormatTemplateById } from "@/services/template-format-service";
 import { ExportType, exportSingleToJsonFile } from "@/utils/export-utils";
 import { sortTemplatesByFavoriteAndName } from "@/utils/sorting";
 import { configFields } from "../manifests/configFields";
+import { ContextBudgetMeter } from "./ContextBudgetMeter";
 import { CustomPromptModal } from "./custom-prompt/CustomPromptModal";
 import { CustomPromptsList } from "./custom-prompt/CustomPromptsList";
 import { ExportOptions, ExportOptionsDialog } from "./ExportOptionsDialog";
@@ -103,6 +108,7 @@ const WidgetConfig = ({ currentChatTemplateID, onChatTemplateChange }: ChatTempl
   const [selectedLorebookList, setSelectedLorebookList] = useState<string[]>([]);
   const [contextSize, setContextSize] = useState<number>(4096);
   const [responseLength, setResponseLength] = useState<number>(1024);
+  const [responseLengthPreset, setResponseLengthPreset] = useState<ResponseLength | "custom">("custom");
   const [maxDepth, setMaxDepth] = useState<number>(1000);
   const [lorebookTokenBudget, setLorebookTokenBudget] = useState<number>(2048);
   const [isFormatTemplateModalOpen, setIsFormatTemplateModalOpen] = useState(false);
@@ -218,11 +224,13 @@ const WidgetConfig = ({ currentChatTemplateID, onChatTemplateChange }: ChatTempl
       if (currentTemplate.config) {
         setContextSize(currentTemplate.config.max_context || 4096);
         setResponseLength(currentTemplate.config.max_tokens || 1024);
+        setResponseLengthPreset(isResponseLength(currentTemplate.config.response_length) ? currentTemplate.config.response_length : "custom");
         setMaxDepth(currentTemplate.config.max_depth || 100);
         setLorebookTokenBudget(currentTemplate.config.lorebook_token_budget ?? 2048);
       } else {
         setContextSize(4096);
         setResponseLength(1024);
+        setResponseLengthPreset("custom");
         setMaxDepth(100);
         setLorebookTokenBudget(2048);
       }
@@ -230,7 +238,7 @@ const WidgetConfig = ({ currentChatTemplateID, onChatTemplateChange }: ChatTempl
       setCustomPrompts(currentTemplate.custom_prompts || []);
 
       const templateConfig = currentTemplate.config || {};
-      const configKeys = Object.keys(templateConfig).filter((key) => key !== "max_tokens" && key !== "max_context" && key !== "max_depth");
+      const configKeys = Object.keys(templateConfig).filter((key) => key !== "max_tokens" && key !== "max_context" && key !== "max_depth" && key !== "response_length");
       setActiveFields(configKeys);
 
       const configValues: Record<string, any> = {};
@@ -246,6 +254,7 @@ const WidgetConfig = ({ currentChatTemplateID, onChatTemplateChange }: ChatTempl
       setSelectedFormatTemplateId(null);
       setContextSize(4096);
       setResponseLength(1024);
+      setResponseLengthPreset("custom");
       setMaxDepth(100);
       setLorebookTokenBudget(2048);
       setCustomPrompts([]);
@@ -253,10 +262,9 @@ const WidgetConfig = ({ currentChatTemplateID, onChatTemplateChange }: ChatTempl
     }
   }, [currentTemplate, flushPendingSave]);
 
-  // Get model manifest
-  const manifestId = useMemo(() => {
-    return models.find((model) => model.id === selectedModelId)?.manifest_id;
-  }, [models, selectedModelId]);
+  // The template may reference its model by alias; the picker works with ids
+  const selectedModel = useMemo(() => findModelByIdOrAlias(models, selectedModelId), [models, selectedModelId]);
+  const manifestId = selectedModel?.manifest_id;
 
   const selectedModelManifest = useModelManifestById(manifestId || "");
   const availableInferenceFields = useMemo(() => selectedModelManifest?.inference_fields || [], [selectedModelManifest]);
@@ -392,6 +400,9 @@ const WidgetConfig = ({ currentChatTemplateID, onChatTemplateChange }: ChatTempl
    * @param fieldName - The name of the field to check
    * @returns boolean indicating if the field is supported
    */
+  // Advisory only: combinations that won't give the repeatable output the user likely expects
+  const parameterWarnings = useMemo(() => validateParameters(values, selectedModelId ? selectedModelManifest : null), [values, selectedModelId, selectedModelManifest]);
+
   const isFieldSupportedByModel = (fieldName: string): boolean => {
     if (!selectedModelId || !selectedModelManifest) {
       return true; // If no model selected, don't apply strikethrough
@@ -520,6 +531,10 @@ const WidgetConfig = ({ currentChatTemplateID, onChatTemplateChange }: ChatTempl
       max_depth: maxDepth,
     };
 
+    if (responseLengthPreset !== "custom") {
+      configValues.response_length = responseLengthPreset;
+    }
+
     if (selectedLorebookList.length > 0) {
       configValues.lorebook_token_budget = lorebookTokenBudget;
     } else {
@@ -554,6 +569,7 @@ const WidgetConfig = ({ currentChatTemplateID, onChatTemplateChange }: ChatTempl
     selectedLorebookList,
     contextSize,
     responseLength,
+    responseLengthPreset,
     maxDepth,
     lorebookTokenBudget,
     values,
@@ -717,7 +733,7 @@ const WidgetConfig = ({ currentChatTemplateID, onChatTemplateChange }: ChatTempl
     delete exportedTemplates.format_template_id;
 
     // Use the export utility to handle the export
-    const success = await exportSingleToJsonFile(exportedTemplates, "chat_template", `chat_template_${template.name.replace(/[^a-zA-Z0-9]/g, "_")}`);
+    const success = await exportSingleToJsonFile(exportedTemplates, "chat_template", `chat_template_${template.name}`);
 
     if (!success) {
       console.warn("Export was cancelled or failed");
@@ -926,11 +942,11 @@ const WidgetConfig = ({ currentChatTemplateID, onChatTemplateChange }: ChatTempl
             <Combobox
               items={modelOptions}
               onChange={setSelectedModelId}
-              selectedValue={selectedModelId}
+              selectedValue={selectedModel?.id ?? selectedModelId}
               placeholder="Search a model..."
               trigger={
                 <Button variant={selectedFormatTemplateId ? "outline" : "destructive"} className="w-full justify-between text-xs px-2" disabled={isDisabled}>
-                  {selectedModelId ? modelOptions.find((model) => model.value === selectedModelId)?.label || "Select a model..." : "Select a model..."}
+                  {selectedModel ? modelOptions.find((model) => model.value === selectedModel.id)?.label || "Select a model..." : "Select a model..."}
                   <ChevronDown className="ml-auto !h-3 !w-3" />
                 </Button>
               }
@@ -1041,6 +1057,11 @@ const WidgetConfig = ({ currentChatTemplateID, onChatTemplateChange }: ChatTempl
           </div>
           <div className="flex-1">
             <StepButton value={contextSize} showSlider onValueChange={setContextSize} min={512} max={32768 * 10} step={512} className="h-7" disabled={isDisabled} />
+            {!onChatTemplateChange && currentTemplate && (
+              <div className="mt-1">
+                <ContextBudgetMeter chatTemplateId={currentTemplate.id} />
+              </div>
+            )}
           </div>
         </div>
 
@@ -1060,11 +1081,34 @@ const WidgetConfig = ({ currentChatTemplateID, onChatTemplateChange }: ChatTempl
         {/* Response Length */}
         <div className={`${bigScreenBreakpoints} items-center gap-2`}>
           <div className="flex items-center gap-1 col-span-2 min-w-0">
-            <HelpTooltip>Sets the maximum number of tokens the model is allowed to generate in a single response.</HelpTooltip>
+            <HelpTooltip>
+              Sets the maximum number of tokens the model is allowed to generate in a single response. Presets use the token limits and instructions from Settings &gt; Chat.
+            </HelpTooltip>
             <h3 className="text-xs font-normal truncate">Response Length:</h3>
           </div>
-          <div className="flex-1">
-            <StepButton value={responseLength} onValueChange={setResponseLength} min={1} max={99999} step={50} className="h-7" disabled={isDisabled} />
+          <div className="flex flex-1 gap-1">
+            <Select value={responseLengthPreset} onValueChange={(value) => setResponseLengthPreset(value as ResponseLength | "custom")} disabled={isDisabled}>
+              <SelectTrigger className="h-7 w-28 text-xs">
+                <SelectValue />
+              </SelectTrigger>
+              <SelectContent>
+                <SelectItem value="custom">Custom</SelectItem>
+                {RESPONSE_LENGTHS.map((preset) => (
+                  <SelectItem key={preset} value={preset} className="capitalize">
+                    {preset}
+                  </SelectItem>
+                ))}
+              </SelectContent>
+            </Select>
+            <StepButton
+              value={responseLength}
+              onValueChange={setResponseLength}
+              min={1}
+              max={99999}
+              step={50}
+              className="h-7 flex-1"
+              disabled={isDisabled || responseLengthPreset !== "custom"}
+            />
           </div>
         </div>
 
@@ -1162,6 +1206,12 @@ const WidgetConfig = ({ currentChatTemplateID, onChatTemplateChange }: ChatTempl
               />
             );
           })}
+          {parameterWarnings.map((warning) => (
+            <p key={warning.code} className="flex items-start gap-1.5 text-xs text-amber-500">
+              <TriangleAlert className="mt-0.5 !h-3 !w-3 shrink-0" />
+              {warning.message}
+            </p>
+          ))}
         </div>
       </div>
 
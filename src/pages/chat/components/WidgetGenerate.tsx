@@ -12,10 +12,12 @@ import { useAgentWorkflow } from "@/hooks/useAgentWorkflow";
 import type { GenerationOptions, StreamingState } from "@/hooks/useChatInference";
 import { useInferenceServiceFromContext } from "@/hooks/useChatInference";
 import { cn } from "@/lib/utils";
+import type { ChatMessage } from "@/schema/chat-message-schema";
 import { QuickAction } from "@/schema/profiles-schema";
 import { cancelChatGeneration, clearChatGenerationCancellation, isChatGenerationCancelled } from "@/services/chat-generation-cancellation";
 import { orchestrateGeneration } from "@/services/chat-generation-orchestrator";
 import { createChatMessage, getChatMessagesByChatId, getNextMessagePosition } from "@/services/chat-message-service";
+import { formatDiceRoll, resolveDiceMacros, rollDice } from "@/services/inference/formatter/dice";
 import { useLocalGenerationInputHistory } from "@/utils/local-storage";
 import QuickActions from "./utils-generate/QuickActions";
 
@@ -45,19 +47,19 @@ const WidgetGenerate: React.FC<WidgetGenerateProps> = () => {
 
   const { executeWorkflow } = useAgentWorkflow();
 
-  const addUserMessageToChat = useCallback(async (chatId: string, chapterId: string, message: string) => {
+  const addMessageToChat = useCallback(async (chatId: string, chapterId: string, type: "user" | "system", message: string, extra: ChatMessage["extra"] = {}) => {
     const position = await getNextMessagePosition(chatId, chapterId);
     const newMessage = await createChatMessage({
       chat_id: chatId,
       chapter_id: chapterId,
       character_id: null,
-      type: "user",
+      type,
       messages: [message],
       message_index: 0,
       position,
       disabled: false,
       tokens: null,
-      extra: {},
+      extra,
     });
 
     if (chatId === getCurrentChatId()) {
@@ -70,6 +72,15 @@ const WidgetGenerate: React.FC<WidgetGenerateProps> = () => {
     return newMessage;
   }, []);
 
+  // Rolls are resolved once, when sending, so the message, the prompt and every regeneration agree on them
+  const addUserMessageToChat = useCallback(
+    async (chatId: string, chapterId: string, message: string) => {
+      const { text: resolved, rolls } = resolveDiceMacros(message);
+      return addMessageToChat(chatId, chapterId, "user", resolved, rolls.length > 0 ? { dice_rolls: rolls } : {});
+    },
+    [addMessageToChat],
+  );
+
   // Get enabled participants for message generation
   const enabledParticipants = participants?.filter((p) => p.enabled) || [];
 
@@ -295,6 +306,20 @@ const WidgetGenerate: React.FC<WidgetGenerateProps> = () => {
   const executeQuickAction = async (action: QuickAction, participantId?: string) => {
     const nextCharacter = participantId ? enabledParticipants.find((p) => p.id === participantId) : enabledParticipants[0];
 
+    if (action.rollBeforeGenerate) {
+      if (!currentChatActiveChapterId) {
+        toast.error("No active chapter found");
+        return;
+      }
+      try {
+        const roll = rollDice(action.rollBeforeGenerate);
+        await addMessageToChat(currentChatId, currentChatActiveChapterId, "system", formatDiceRoll(roll), { dice_rolls: [roll] });
+      } catch (error) {
+        toast.error("Failed to roll the dice", { description: error instanceof Error ? error.message : String(error) });
+        return;
+      }
+    }
+
     const quietResponse = action.streamOption === "textarea";
 
     const generationConfig: GenerationOptions = {
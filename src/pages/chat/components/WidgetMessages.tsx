@@ -1,7 +1,8 @@
 import { useCallback, useEffect, useMemo, useRef, useState } from "react";
-import { LuChevronDown } from "react-icons/lu";
+import { LuBookmark, LuChevronDown, LuRotateCcw } from "react-icons/lu";
 import { toast } from "sonner";
 import { Button } from "@/components/ui/button";
+import { DropdownMenu, DropdownMenuContent, DropdownMenuItem, DropdownMenuLabel, DropdownMenuTrigger } from "@/components/ui/dropdown-menu";
 import { useAgents } from "@/hooks/agentStore";
 import { useCharacterAvatars, useCharacters } from "@/hooks/characterStore";
 import {
@@ -9,20 +10,27 @@ import {
   useChatStore,
   useCurrentChatActiveChapterID,
   useCurrentChatId,
+  useCurrentChatMarkers,
   useCurrentChatMessages,
   useCurrentChatParticipants,
   useCurrentChatSettings,
   useCurrentChatUserCharacterID,
 } from "@/hooks/chatStore";
+import { useConsoleStoreActions } from "@/hooks/consoleStore";
 import { useExpressionStore } from "@/hooks/expressionStore";
 import { useCurrentProfile } from "@/hooks/ProfileStore";
 import { useAgentWorkflow } from "@/hooks/useAgentWorkflow";
 import { useInferenceServiceFromContext } from "@/hooks/useChatInference";
 import { useImageUrl } from "@/hooks/useImageUrl";
+import { usePromptReconstruction } from "@/hooks/usePromptReconstruction";
 import type { TriggerContext } from "@/schema/agent-schema";
+import type { ChatMarker } from "@/schema/chat-marker-schema";
 import { generateCharacterWithAgents } from "@/services/chat-generation-orchestrator";
+import { getChatReadPosition, setChatReadPosition } from "@/services/chat-service";
+import { snapshotToConsoleRequest } from "@/services/inference/prompt-reconstruction";
 import type { ChatMessage } from "@/services/chat-message-service";
 import { deleteChatMessage as apiDeleteChatMessage, getChatMessagesByChatId, updateChatMessagesUsingFilter } from "@/services/chat-message-service";
+import ChatMarkerDivider from "./message-controls/ChatMarkerDivider";
 import MessageItem from "./message-controls/MessageItem";
 import MidMessageLayerWrapper from "./message-controls/MidMessageLayerWrapper";
 import { NoMessagePlaceholder } from "./message-controls/NoMessagePlaceholder";
@@ -32,10 +40,14 @@ import UserChoicePrompt from "./UserChoicePrompt";
 const MESSAGE_CONTAINER_STYLES = "relative flex flex-col h-full @container";
 const MESSAGE_GROUP_STYLES = "message-group relative group/message";
 const SCROLL_BUTTON_STYLES = "absolute bottom-4 right-4 rounded-full shadow-md bg-background z-10 opacity-80 hover:opacity-100";
+const MARKER_NAV_STYLES = "absolute top-2 right-4 h-7 w-7 rounded-full shadow-md bg-background z-10 opacity-60 hover:opacity-100";
 
 // In column-reverse, scrollTop ~0 means the user is at the visual bottom
 const AT_BOTTOM_THRESHOLD = 30;
 
+// Scrolling settles before the read position is saved
+const READ_POSITION_SAVE_DELAY = 800;
+
 const WidgetMessages: React.FC = () => {
   const inferenceService = useInferenceServiceFromContext();
 
@@ -43,8 +55,9 @@ const WidgetMessages: React.FC = () => {
   const currentChatId = useCurrentChatId();
   const currentChatActiveChapterID = useCurrentChatActiveChapterID();
   const messages = useCurrentChatMessages();
+  const markers = useCurrentChatMarkers();
   const chatSettings = useCurrentChatSettings();
-  const { updateChatMessage, addChatMessage, fetchChatMessages, deleteChatMessage } = useChatActions();
+  const { updateChatMessage, addChatMessage, fetchChatMessages, deleteChatMessage, setActiveVariant } = useChatActions();
   const setSelectedText = useExpressionStore((state) => state.setSelectedText);
   const currentChatParticipants = useCurrentChatParticipants();
   const agentList = useAgents();
@@ -69,11 +82,33 @@ const WidgetMessages: React.FC = () => {
     });
   }, [messages, chatSettings]);
 
+  // Markers sit before the first message placed after them; the rest come after the last message
+  const { markersBeforeMessage, trailingMarkers } = useMemo(() => {
+    const byMessage = new Map<string, ChatMarker[]>();
+    let markerIndex = 0;
+    for (const message of filteredMessages) {
+      const before: ChatMarker[] = [];
+      while (markerIndex < markers.length && markers[markerIndex].position < message.position) {
+        before.push(markers[markerIndex++]);
+      }
+      if (before.length > 0) {
+        byMessage.set(message.id, before);
+      }
+    }
+    return { markersBeforeMessage: byMessage, trailingMarkers: markers.slice(markerIndex) };
+  }, [filteredMessages, markers]);
+
   const [isEditingID, setIsEditingID] = useState<string | null>(null);
   const [editedContent, setEditedContent] = useState<string>("");
   const [streamingMessageId, setStreamingMessageId] = useState<string | null>(null);
   const [messageReasonings, setMessageReasonings] = useState<Record<string, string>>({});
   const [isAtBottom, setIsAtBottom] = useState(true);
+  // Last read message to scroll to once the chat's messages are rendered
+  const [pendingResumeId, setPendingResumeId] = useState<string | null>(null);
+  const [resumeMarkerId, setResumeMarkerId] = useState<string | null>(null);
+  const readPositionLoadedRef = useRef(false);
+  const lastSavedReadIdRef = useRef<string | null>(null);
+  const readSaveTimeoutRef = useRef<number | null>(null);
 
   const scrollContainerRef = useRef<HTMLDivElement>(null);
   const selectionTimeoutRef = useRef<number | null>(null);
@@ -100,8 +135,15 @@ const WidgetMessages: React.FC = () => {
         const updatedMessages = [...message.messages];
         updatedMessages[message.message_index] = editedContent;
 
+        // An edited variant no longer matches its stored translation, so the edit is sent as-is
+        const translations = message.extra?.translations;
+        const hasTranslation = !!translations?.[message.message_index];
+
         await updateChatMessage(messageId, {
           messages: updatedMessages,
+          ...(hasTranslation && {
+            extra: { ...message.extra, translations: translations!.map((translation, index) => (index === message.message_index ? null : translation)) },
+          }),
         });
       } catch (error) {
         console.error("Failed to edit message:", error);
@@ -206,7 +248,9 @@ const WidgetMessages: React.FC = () => {
             inferenceService.regenerateMessage(messageId, {
               chatId: currentChatId,
               characterId: message.character_id as string,
-              messageIndex: targetIndex !== undefined ? targetIndex : message.message_index,
+              // Swiping past the last version asks for a new one
+              mode: targetIndex !== undefined && targetIndex >= message.messages.length ? "add_version" : "replace",
+              messageIndex: targetIndex ?? message.message_index,
               emitChatEvents: false,
               onStreamingStateChange: (state) => {
                 if (!state) {
@@ -256,12 +300,10 @@ const WidgetMessages: React.FC = () => {
       const clampedIndex = Math.max(0, Math.min(newIndex, message.messages.length - 1));
 
       if (clampedIndex !== currentIndex) {
-        updateChatMessage(messageId, {
-          message_index: clampedIndex,
-        });
+        setActiveVariant(messageId, clampedIndex);
       }
     },
-    [messages, streamingMessageId, updateChatMessage, onRegenerateMessage],
+    [messages, streamingMessageId, setActiveVariant, onRegenerateMessage],
   );
 
   useEffect(() => {
@@ -306,6 +348,24 @@ const WidgetMessages: React.FC = () => {
     [setSelectedText],
   );
 
+  const { reconstructPrompt } = usePromptReconstruction();
+  const { inspectRequest } = useConsoleStoreActions();
+
+  const handleInspectPrompt = useCallback(
+    async (messageId: string) => {
+      try {
+        const { snapshot, response } = await reconstructPrompt(messageId);
+        inspectRequest(snapshotToConsoleRequest(snapshot, response));
+      } catch (error) {
+        console.error("Failed to reconstruct prompt:", error);
+        toast.error("Failed to load the prompt for this message", {
+          description: error instanceof Error ? error.message : String(error),
+        });
+      }
+    },
+    [reconstructPrompt, inspectRequest],
+  );
+
   const handleSummarizeMessages = useCallback(
     async (messageBefore: string, settings: SummarySettings) => {
       const messageUpdatedList = await fetchChatMessages();
@@ -433,6 +493,95 @@ const WidgetMessages: React.FC = () => {
     setIsAtBottom(true);
   }, [currentChatId]);
 
+  const profileId = currentProfile?.id;
+
+  useEffect(() => {
+    readPositionLoadedRef.current = false;
+    lastSavedReadIdRef.current = null;
+    setPendingResumeId(null);
+    setResumeMarkerId(null);
+    if (!currentChatId || !profileId) {
+      return;
+    }
+
+    let cancelled = false;
+    getChatReadPosition(currentChatId, profileId)
+      .then((position) => {
+        if (cancelled) {
+          return;
+        }
+        lastSavedReadIdRef.current = position?.message_id ?? null;
+        setPendingResumeId(position?.message_id ?? null);
+        readPositionLoadedRef.current = true;
+      })
+      .catch((error) => console.error("Failed to load read position:", error));
+
+    return () => {
+      cancelled = true;
+    };
+  }, [currentChatId, profileId]);
+
+  // Scroll back to where the user left off, unless nothing arrived since
+  useEffect(() => {
+    if (!pendingResumeId || filteredMessages.length === 0) {
+      return;
+    }
+    if (filteredMessages[filteredMessages.length - 1].id === pendingResumeId) {
+      setPendingResumeId(null);
+      return;
+    }
+    const el = scrollContainerRef.current?.querySelector(`[data-message-id="${pendingResumeId}"]`);
+    if (!el) {
+      return;
+    }
+    setPendingResumeId(null);
+    setResumeMarkerId(pendingResumeId);
+    el.scrollIntoView({ block: "end" });
+  }, [pendingResumeId, filteredMessages]);
+
+  // Saves the lowest message visible in the viewport as the read position
+  const scheduleReadPositionSave = useCallback(() => {
+    if (!readPositionLoadedRef.current || !currentChatId || !profileId) {
+      return;
+    }
+    if (readSaveTimeoutRef.current) {
+      clearTimeout(readSaveTimeoutRef.current);
+    }
+    readSaveTimeoutRef.current = window.setTimeout(() => {
+      const container = scrollContainerRef.current;
+      if (!container) {
+        return;
+      }
+      const viewportBottom = container.getBoundingClientRect().bottom;
+      let lastVisibleId: string | null = null;
+      for (const el of container.querySelectorAll<HTMLElement>("[data-message-id]")) {
+        if (el.getBoundingClientRect().top >= viewportBottom) {
+          break;
+        }
+        lastVisibleId = el.dataset.messageId ?? null;
+      }
+      if (!lastVisibleId || lastVisibleId === lastSavedReadIdRef.current) {
+        return;
+      }
+      lastSavedReadIdRef.current = lastVisibleId;
+      setChatReadPosition(currentChatId, profileId, lastVisibleId).catch((error) => console.error("Failed to save read position:", error));
+    }, READ_POSITION_SAVE_DELAY);
+  }, [currentChatId, profileId]);
+
+  useEffect(() => {
+    if (isAtBottom) {
+      scheduleReadPositionSave();
+    }
+  }, [filteredMessages, isAtBottom, scheduleReadPositionSave]);
+
+  useEffect(() => {
+    return () => {
+      if (readSaveTimeoutRef.current) {
+        clearTimeout(readSaveTimeoutRef.current);
+      }
+    };
+  }, []);
+
   const handleScroll = useCallback(() => {
     const el = scrollContainerRef.current;
     if (!el) {
@@ -440,6 +589,11 @@ const WidgetMessages: React.FC = () => {
     }
     const atBottom = Math.abs(el.scrollTop) <= AT_BOTTOM_THRESHOLD;
     setIsAtBottom(atBottom);
+    scheduleReadPositionSave();
+  }, [scheduleReadPositionSave]);
+
+  const scrollToMarker = useCallback((markerId: string) => {
+    scrollContainerRef.current?.querySelector(`[data-marker-id="${markerId}"]`)?.scrollIntoView({ behavior: "smooth", block: "start" });
   }, []);
 
   const scrollToBottom = useCallback(() => {
@@ -481,9 +635,12 @@ const WidgetMessages: React.FC = () => {
 
             return (
               <div key={message.id}>
+                {markersBeforeMessage.get(message.id)?.map((marker) => (
+                  <ChatMarkerDivider key={marker.id} marker={marker} />
+                ))}
                 {showMidLayer && <MidMessageLayerWrapper messageBefore={filteredMessages[index - 1]} messageAfter={message} onSummarize={handleSummarizeMessages} />}
 
-                <div className={MESSAGE_GROUP_STYLES} style={{ contentVisibility: "auto", containIntrinsicSize: "auto 200px" }}>
+                <div className={MESSAGE_GROUP_STYLES} data-message-id={message.id} style={{ contentVisibility: "auto", containIntrinsicSize: "auto 200px" }}>
                   <MessageItem
                     message={message}
                     index={index}
@@ -505,15 +662,47 @@ const WidgetMessages: React.FC = () => {
                     setIsEditingID={setIsEditingID}
                     updateChatMessage={updateChatMessage}
                     deleteChatMessage={deleteChatMessage}
+                    onInspectPrompt={handleInspectPrompt}
                   />
                 </div>
+
+                {resumeMarkerId === message.id && index < filteredMessages.length - 1 && (
+                  <div className="flex items-center gap-2 my-2 text-[11px] font-medium text-primary/80">
+                    <div className="h-px flex-1 bg-primary/40" />
+                    New since you left
+                    <div className="h-px flex-1 bg-primary/40" />
+                  </div>
+                )}
               </div>
             );
           })}
+          {trailingMarkers.map((marker) => (
+            <ChatMarkerDivider key={marker.id} marker={marker} />
+          ))}
           <UserChoicePrompt />
         </div>
       </div>
 
+      {markers.length > 0 && (
+        <DropdownMenu>
+          <DropdownMenuTrigger asChild>
+            <Button variant="outline" size="icon" className={MARKER_NAV_STYLES} title="Jump to marker">
+              <LuBookmark className="h-3.5 w-3.5" />
+            </Button>
+          </DropdownMenuTrigger>
+          <DropdownMenuContent align="end" className="max-h-80 overflow-y-auto">
+            <DropdownMenuLabel>Markers</DropdownMenuLabel>
+            {markers.map((marker) => (
+              <DropdownMenuItem key={marker.id} onSelect={() => scrollToMarker(marker.id)}>
+                <span className="h-2 w-2 rounded-full bg-border shrink-0" style={marker.color ? { backgroundColor: marker.color } : undefined} />
+                <span className="truncate">{marker.title}</span>
+                {marker.context_reset && <LuRotateCcw className="ml-auto h-3 w-3 text-muted-foreground" />}
+              </DropdownMenuItem>
+            ))}
+          </DropdownMenuContent>
+        </DropdownMenu>
+      )}
+
       {!isAtBottom && (
         <Button variant="outline" size="icon" className={SCROLL_BUTTON_STYLES} onClick={scrollToBottom} title="Scroll to latest messages">
           <LuChevronDown className="h-4 w-4" />
@@ -5,18 +5,20 @@ import { CSS } from "@dnd-kit/utilities";
 import { motion } from "framer-motion";
 import { useCallback, useEffect, useRef, useState } from "react";
 import { BiSolidZap } from "react-icons/bi";
-import { LuCirclePlay, LuCircleStop, LuEyeOff, LuGripVertical, LuMessageSquareOff, LuSettings, LuTrash2, LuUserPlus } from "react-icons/lu";
+import { LuCirclePlay, LuCircleStop, LuEraser, LuEyeOff, LuGripVertical, LuLanguages, LuLock, LuMessageSquareOff, LuRefreshCw, LuSettings, LuTrash2, LuUserPlus, LuUserX } from "react-icons/lu";
 import { RiArrowLeftRightLine, RiCloseLine } from "react-icons/ri";
 import { toast } from "sonner";
+import { listenInCurrentWindow } from "@/commands/windows";
 import { BorderBeam } from "@/components/magicui/border-beam";
 import { Avatar, AvatarFallback, AvatarImage } from "@/components/ui/avatar";
 import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
 import { Popover, PopoverContent, PopoverTrigger } from "@/components/ui/popover";
 import { Switch } from "@/components/ui/switch";
 import { useAgents } from "@/hooks/agentStore";
 import { cancelAgentWorkflow, makeRunKey, useAgentWorkflowState, useAgentWorkflowStore } from "@/hooks/agentWorkflowStore";
 import { useCharacterAvatars, useCharacters } from "@/hooks/characterStore";
-import { useChatActions, useCurrentChatId, useCurrentChatParticipants, useCurrentChatSettings, useCurrentChatUserCharacterID } from "@/hooks/chatStore";
+import { useChatActions, useCurrentChatId, useCurrentChatParticipants, useCurrentChatSettings, useCurrentChatTemplateID, useCurrentChatUserCharacterID } from "@/hooks/chatStore";
 import { useCurrentProfile } from "@/hooks/ProfileStore";
 import { useUIStore } from "@/hooks/UIStore";
 import { useAgentWorkflow } from "@/hooks/useAgentWorkflow";
@@ -26,8 +28,10 @@ import { cn } from "@/lib/utils";
 import { CharacterForm } from "@/pages/characters/components/AddCharacterForm";
 import type { AgentTriggerType, AgentType, TriggerContext } from "@/schema/agent-schema";
 import { Character } from "@/schema/characters-schema";
+import { type ChatDisplaySettings, chatTranslationSettingsSchema, type ChatTranslationSettings } from "@/schema/chat-schema";
 import { cancelChatGeneration, clearChatGenerationCancellation, isChatGenerationCancelled } from "@/services/chat-generation-cancellation";
 import { generateCharacterWithAgents } from "@/services/chat-generation-orchestrator";
+import { lockSystemPrompt, PROMPT_DRIFT_EVENT, type PromptDriftPayload, rebaselineSystemPrompt, unlockSystemPrompt } from "@/services/inference/system-prompt-lock";
 import AddParticipantPopover from "./AddParticipantPopover";
 
 // Types
@@ -291,7 +295,71 @@ const WidgetParticipants: React.FC<WidgetParticipantsProps> = (_props) => {
   const currentChatUserCharacterID = useCurrentChatUserCharacterID();
   const participants = useCurrentChatParticipants() || [];
   const chatSettings = useCurrentChatSettings();
+  const currentChatTemplateId = useCurrentChatTemplateID();
   const { addParticipant, removeParticipant, toggleParticipantEnabled, updateSelectedChat } = useChatActions();
+  const translationSettings = chatTranslationSettingsSchema.parse(chatSettings?.translation ?? {});
+
+  const updateTranslationSettings = useCallback(
+    (changes: Partial<ChatTranslationSettings>) => {
+      updateSelectedChat({
+        settings: {
+          ...chatSettings,
+          hideDisabledMessages: chatSettings?.hideDisabledMessages ?? false,
+          hideScriptMessages: chatSettings?.hideScriptMessages ?? false,
+          translation: { ...translationSettings, ...changes },
+        },
+      });
+    },
+    [chatSettings, translationSettings, updateSelectedChat],
+  );
+
+  const updateOutputGuard = useCallback(
+    (changes: Pick<ChatDisplaySettings, "strip_name_prefix" | "stop_on_user_impersonation">) => {
+      updateSelectedChat({
+        settings: {
+          ...chatSettings,
+          hideDisabledMessages: chatSettings?.hideDisabledMessages ?? false,
+          hideScriptMessages: chatSettings?.hideScriptMessages ?? false,
+          ...changes,
+        },
+      });
+    },
+    [chatSettings, updateSelectedChat],
+  );
+
+  const updateSystemPromptLock = useCallback(
+    async (action: "lock" | "strict" | "unlock" | "rebaseline", strict = false) => {
+      const chat = { chat_template_id: currentChatTemplateId, settings: chatSettings };
+      try {
+        const settings =
+          action === "unlock"
+            ? unlockSystemPrompt(chatSettings)
+            : action === "rebaseline"
+              ? await rebaselineSystemPrompt(chat)
+              : action === "strict"
+                ? { ...chatSettings!, system_prompt_lock_strict: strict }
+                : await lockSystemPrompt(chat, strict);
+        await updateSelectedChat({ settings });
+      } catch (error) {
+        toast.error("Failed to update the system prompt lock", { description: error instanceof Error ? error.message : String(error) });
+      }
+    },
+    [chatSettings, currentChatTemplateId, updateSelectedChat],
+  );
+
+  // Strict locks block the generation, which reports its own error; the others only warn
+  useEffect(() => {
+    const unlisten = listenInCurrentWindow<PromptDriftPayload>(PROMPT_DRIFT_EVENT, ({ payload }) => {
+      if (payload.chatId === currentChatId && !payload.blocked) {
+        toast.warning("The locked system prompt changed", {
+          description: `${payload.addedLines} lines added, ${payload.removedLines} removed. Take a new baseline to accept the change.`,
+        });
+      }
+    });
+    return () => {
+      unlisten.then((stop) => stop()).catch(() => {});
+    };
+  }, [currentChatId]);
 
   const [isAddParticipantOpen, setIsAddParticipantOpen] = useState(false);
   const inferenceService = useInferenceServiceFromContext();
@@ -591,7 +659,7 @@ const WidgetParticipants: React.FC<WidgetParticipantsProps> = (_props) => {
                   checked={chatSettings?.hideDisabledMessages ?? false}
                   onCheckedChange={(checked) =>
                     updateSelectedChat({
-                      settings: { hideDisabledMessages: checked, hideScriptMessages: chatSettings?.hideScriptMessages ?? false },
+                      settings: { ...chatSettings, hideDisabledMessages: checked, hideScriptMessages: chatSettings?.hideScriptMessages ?? false },
                     })
                   }
                 />
@@ -609,11 +677,83 @@ const WidgetParticipants: React.FC<WidgetParticipantsProps> = (_props) => {
                   checked={chatSettings?.hideScriptMessages ?? false}
                   onCheckedChange={(checked) =>
                     updateSelectedChat({
-                      settings: { hideDisabledMessages: chatSettings?.hideDisabledMessages ?? false, hideScriptMessages: checked },
+                      settings: { ...chatSettings, hideDisabledMessages: chatSettings?.hideDisabledMessages ?? false, hideScriptMessages: checked },
                     })
                   }
                 />
               </label>
+              <label className="group/setting flex cursor-pointer items-center gap-2.5 rounded-md px-2 py-2 transition-colors hover:bg-muted/50">
+                <div className="flex h-7 w-7 shrink-0 items-center justify-center rounded-md bg-muted/40 transition-colors group-hover/setting:bg-muted/70">
+                  <LuLanguages className="h-3.5 w-3.5 text-muted-foreground" />
+                </div>
+                <div className="flex min-w-0 flex-1 flex-col gap-0.5">
+                  <span className="text-xs font-medium leading-tight">Translate messages</span>
+                  <span className="text-[10.5px] leading-tight text-muted-foreground/70">Talk to the model in another language</span>
+                </div>
+                <Switch size="sm" checked={translationSettings.enabled} onCheckedChange={(checked) => updateTranslationSettings({ enabled: checked })} />
+              </label>
+              {translationSettings.enabled && (
+                <div className="flex gap-1.5 px-2 pb-2">
+                  <Input
+                    key={`target-${currentChatId}`}
+                    className="h-7 text-xs"
+                    placeholder="Model language"
+                    title="Language your messages are translated to"
+                    defaultValue={translationSettings.target_language}
+                    onBlur={(e) => updateTranslationSettings({ target_language: e.target.value.trim() || "English" })}
+                  />
+                  <Input
+                    key={`user-${currentChatId}`}
+                    className="h-7 text-xs"
+                    placeholder="Your language"
+                    title="Language replies are translated back to"
+                    defaultValue={translationSettings.user_language}
+                    onBlur={(e) => updateTranslationSettings({ user_language: e.target.value.trim() })}
+                  />
+                </div>
+              )}
+              <label className="group/setting flex cursor-pointer items-center gap-2.5 rounded-md px-2 py-2 transition-colors hover:bg-muted/50">
+                <div className="flex h-7 w-7 shrink-0 items-center justify-center rounded-md bg-muted/40 transition-colors group-hover/setting:bg-muted/70">
+                  <LuEraser className="h-3.5 w-3.5 text-muted-foreground" />
+                </div>
+                <div className="flex min-w-0 flex-1 flex-col gap-0.5">
+                  <span className="text-xs font-medium leading-tight">Strip name prefix</span>
+                  <span className="text-[10.5px] leading-tight text-muted-foreground/70">Drop a leading "Name:" from replies</span>
+                </div>
+                <Switch size="sm" checked={chatSettings?.strip_name_prefix ?? false} onCheckedChange={(checked) => updateOutputGuard({ strip_name_prefix: checked })} />
+              </label>
+              <label className="group/setting flex cursor-pointer items-center gap-2.5 rounded-md px-2 py-2 transition-colors hover:bg-muted/50">
+                <div className="flex h-7 w-7 shrink-0 items-center justify-center rounded-md bg-muted/40 transition-colors group-hover/setting:bg-muted/70">
+                  <LuUserX className="h-3.5 w-3.5 text-muted-foreground" />
+                </div>
+                <div className="flex min-w-0 flex-1 flex-col gap-0.5">
+                  <span className="text-xs font-medium leading-tight">Stop on impersonation</span>
+                  <span className="text-[10.5px] leading-tight text-muted-foreground/70">End replies that start writing for you or others</span>
+                </div>
+                <Switch size="sm" checked={chatSettings?.stop_on_user_impersonation ?? false} onCheckedChange={(checked) => updateOutputGuard({ stop_on_user_impersonation: checked })} />
+              </label>
+              <label className="group/setting flex cursor-pointer items-center gap-2.5 rounded-md px-2 py-2 transition-colors hover:bg-muted/50">
+                <div className="flex h-7 w-7 shrink-0 items-center justify-center rounded-md bg-muted/40 transition-colors group-hover/setting:bg-muted/70">
+                  <LuLock className="h-3.5 w-3.5 text-muted-foreground" />
+                </div>
+                <div className="flex min-w-0 flex-1 flex-col gap-0.5">
+                  <span className="text-xs font-medium leading-tight">Lock system prompt</span>
+                  <span className="text-[10.5px] leading-tight text-muted-foreground/70">Warn when template edits change it</span>
+                </div>
+                <Switch size="sm" checked={chatSettings?.system_prompt_locked ?? false} onCheckedChange={(checked) => updateSystemPromptLock(checked ? "lock" : "unlock")} />
+              </label>
+              {chatSettings?.system_prompt_locked && (
+                <div className="flex items-center gap-1.5 px-2 pb-2">
+                  <label className="flex flex-1 cursor-pointer items-center gap-2 text-[10.5px] text-muted-foreground">
+                    <Switch size="sm" checked={chatSettings.system_prompt_lock_strict ?? false} onCheckedChange={(checked) => updateSystemPromptLock("strict", checked)} />
+                    Block generation on change
+                  </label>
+                  <Button variant="ghost" size="sm" className="h-7 gap-1 text-xs" title="Accept the current templates as the locked prompt" onClick={() => updateSystemPromptLock("rebaseline")}>
+                    <LuRefreshCw className="h-3 w-3" />
+                    Re-baseline
+                  </Button>
+                </div>
+              )}
             </div>
           </PopoverContent>
         </Popover>
@@ -0,0 +1,97 @@
+import { formatDistanceToNow } from "date-fns";
+import { useCallback, useState } from "react";
+import { LuRefreshCw } from "react-icons/lu";
+import { toast } from "sonner";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Switch } from "@/components/ui/switch";
+import { Textarea } from "@/components/ui/textarea";
+import { useChatActions, useCurrentChatId, useCurrentChatSettings, useCurrentChatSynopsis, useCurrentChatSynopsisUpdatedAt } from "@/hooks/chatStore";
+import type { ChatSynopsisSettings } from "@/schema/chat-schema";
+import { getSynopsisSettings, useChatSynopsis } from "@/services/inference/synopsis";
+
+/**
+ * Rolling synopsis of the open chat: read or edit it, refresh it now, or let it refresh on its own.
+ */
+const WidgetSynopsis = () => {
+  const currentChatId = useCurrentChatId();
+  const chatSettings = useCurrentChatSettings();
+  const synopsis = useCurrentChatSynopsis();
+  const updatedAt = useCurrentChatSynopsisUpdatedAt();
+  const { updateSelectedChat } = useChatActions();
+  const { refreshChatSynopsis, editChatSynopsis } = useChatSynopsis();
+  const [isRefreshing, setIsRefreshing] = useState(false);
+  const synopsisSettings = getSynopsisSettings({ settings: chatSettings });
+
+  const updateSynopsisSettings = useCallback(
+    (changes: Partial<ChatSynopsisSettings>) => {
+      updateSelectedChat({
+        settings: {
+          ...chatSettings,
+          hideDisabledMessages: chatSettings?.hideDisabledMessages ?? false,
+          hideScriptMessages: chatSettings?.hideScriptMessages ?? false,
+          synopsis: { ...synopsisSettings, ...changes },
+        },
+      });
+    },
+    [chatSettings, synopsisSettings, updateSelectedChat],
+  );
+
+  const handleRefresh = async () => {
+    setIsRefreshing(true);
+    try {
+      await refreshChatSynopsis(currentChatId);
+    } catch (error) {
+      toast.error("Couldn't refresh the synopsis", { description: error instanceof Error ? error.message : String(error) });
+    } finally {
+      setIsRefreshing(false);
+    }
+  };
+
+  const handleEdit = (text: string) => {
+    if (text.trim() === (synopsis ?? "").trim()) {
+      return;
+    }
+    editChatSynopsis(currentChatId, text).catch((error) => {
+      toast.error("Couldn't save the synopsis", { description: error instanceof Error ? error.message : String(error) });
+    });
+  };
+
+  return (
+    <div className="flex h-full flex-col gap-2 p-2">
+      <Textarea
+        key={`${currentChatId}-${updatedAt?.getTime() ?? 0}`}
+        className="min-h-[120px] flex-1 resize-none text-xs"
+        placeholder="No synopsis yet. Refresh to write one from the chat so far."
+        defaultValue={synopsis ?? ""}
+        onBlur={(e) => handleEdit(e.target.value)}
+        disabled={isRefreshing}
+      />
+      <div className="flex items-center justify-between gap-2">
+        <span className="text-[10.5px] text-muted-foreground">{updatedAt ? `Updated ${formatDistanceToNow(updatedAt, { addSuffix: true })}` : "Use {{synopsis}} in prompts"}</span>
+        <Button variant="outline" size="sm" className="h-7 gap-1 text-xs" onClick={handleRefresh} disabled={isRefreshing || !currentChatId}>
+          <LuRefreshCw className={`h-3 w-3 ${isRefreshing ? "animate-spin" : ""}`} />
+          Refresh
+        </Button>
+      </div>
+      <label className="flex cursor-pointer items-center gap-2 text-xs">
+        <Switch size="sm" checked={synopsisSettings.auto_refresh} onCheckedChange={(checked) => updateSynopsisSettings({ auto_refresh: checked })} />
+        <span className="flex-1">Refresh every</span>
+        <Input
+          key={`every-${currentChatId}`}
+          className="h-7 w-16 text-xs"
+          type="number"
+          min={1}
+          defaultValue={synopsisSettings.refresh_every}
+          onBlur={(e) => {
+            const value = Math.floor(Number(e.target.value));
+            updateSynopsisSettings({ refresh_every: Number.isFinite(value) && value >= 1 ? value : synopsisSettings.refresh_every });
+          }}
+        />
+        <span className="text-muted-foreground">messages</span>
+      </label>
+    </div>
+  );
+};
+
+export default WidgetSynopsis;
@@ -5,8 +5,10 @@ import { Card } from "@/components/ui/card";
 import { DragArray } from "@/components/ui/drag-array";
 import { CommandTagInput } from "@/components/ui/input-tag";
 import { RandomButton } from "@/components/ui/random-button";
+import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from "@/components/ui/select";
 import { StepButton } from "@/components/ui/step-button";
-import type { ConfigField, DragArrayField, NumericField, RandomNumberField, SectionField, StringArrayField } from "@/schema/template-chat-settings-types";
+import { Switch } from "@/components/ui/switch";
+import type { BooleanField, ConfigField, DragArrayField, NumericField, RandomNumberField, SectionField, SelectField, StringArrayField } from "@/schema/template-chat-settings-types";
 
 interface ConfigItemProps {
   field: ConfigField;
@@ -51,6 +53,29 @@ export const ConfigItem = ({ field, value, onChange, onRemove, isNested = false,
         const arrayValue = Array.isArray(value) ? value : (dragField.default ?? []);
         return <DragArray items={arrayValue} onChange={onChange} className="max-w-md" />;
       }
+      case "boolean": {
+        const booleanField = field as BooleanField;
+        const booleanValue = typeof value === "boolean" ? value : booleanField.default;
+        return <Switch checked={booleanValue} onCheckedChange={(checked) => onChange(!!checked)} />;
+      }
+      case "select": {
+        const selectField = field as SelectField;
+        const selectValue = typeof value === "string" ? value : selectField.default;
+        return (
+          <Select value={selectValue} onValueChange={onChange}>
+            <SelectTrigger className="h-8 w-48 text-xs">
+              <SelectValue />
+            </SelectTrigger>
+            <SelectContent>
+              {selectField.options.map((option) => (
+                <SelectItem key={option.value} value={option.value} className="text-xs">
+                  {option.label}
+                </SelectItem>
+              ))}
+            </SelectContent>
+          </Select>
+        );
+      }
       case "section": {
         const sectionField = field as SectionField;
         return (
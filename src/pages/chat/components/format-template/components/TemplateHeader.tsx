@@ -37,6 +37,7 @@ export function TemplateHeader({ formatTemplateID, onTemplateChange }: TemplateH
         apply_censorship: false,
         merge_messages_on_user: false,
         merge_subsequent_messages: true,
+        substitute_message_macros: true,
       },
     [localSettings],
   );
@@ -132,6 +133,7 @@ export function TemplateHeader({ formatTemplateID, onTemplateChange }: TemplateH
           apply_censorship: false,
           merge_messages_on_user: false,
           merge_subsequent_messages: true,
+          substitute_message_macros: true,
         },
         reasoning: {
           prefix: "<think>",
@@ -202,7 +204,7 @@ export function TemplateHeader({ formatTemplateID, onTemplateChange }: TemplateH
 
       delete template.profile_id;
 
-      const success = await exportSingleToJsonFile(template, "format_template", `format_template_${template.name!.replace(/[^a-zA-Z0-9]/g, "_")}`);
+      const success = await exportSingleToJsonFile(template, "format_template", `format_template_${template.name}`);
 
       if (!success) {
         console.log("Export was cancelled or failed");
@@ -360,6 +362,23 @@ export function TemplateHeader({ formatTemplateID, onTemplateChange }: TemplateH
               </HelpTooltip>
             </div>
           </div>
+          <div className="flex items-center space-x-2">
+            <Checkbox
+              id="substituteMessageMacros"
+              checked={memoizedSettings.substitute_message_macros}
+              onCheckedChange={useCallback((checked: boolean | "indeterminate") => handleSettingChange("substitute_message_macros", checked as boolean), [handleSettingChange])}
+              disabled={!currentTemplate}
+            />
+            <div className="flex items-center space-x-1">
+              <Label htmlFor="substituteMessageMacros">Replace macros in messages</Label>
+              <HelpTooltip>
+                <p>
+                  Renders macros like <i>{"{{char}}"}</i>, <i>{"{{user}}"}</i> or <i>{"{{roll:1d20}}"}</i> written in chat messages. When off, messages reach the model exactly as written;
+                  the system prompt and template prompts are always rendered.
+                </p>
+              </HelpTooltip>
+            </div>
+          </div>
         </div>
 
         {/* Prefix Messages */}
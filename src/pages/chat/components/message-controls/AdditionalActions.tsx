@@ -1,6 +1,6 @@
 import { writeText } from "@tauri-apps/plugin-clipboard-manager";
 import { useState } from "react";
-import { LuBookmarkMinus, LuBookmarkPlus, LuCheck, LuCopy, LuEllipsis, LuFlag, LuImage, LuLanguages, LuLoaderCircle, LuPencil, LuRefreshCw, LuScissors, LuTrash2, LuX } from "react-icons/lu";
+import { LuBookmarkMinus, LuBookmarkPlus, LuCheck, LuCopy, LuEllipsis, LuFlag, LuImage, LuLanguages, LuLoaderCircle, LuPencil, LuPin, LuPinOff, LuRefreshCw, LuScissors, LuScrollText, LuTrash2, LuX } from "react-icons/lu";
 import { toast } from "sonner";
 import { Button } from "@/components/ui/button";
 import { DropdownMenu, DropdownMenuContent, DropdownMenuItem, DropdownMenuTrigger } from "@/components/ui/dropdown-menu";
@@ -13,6 +13,7 @@ export const MessageActions = ({
   isStreaming,
   isLastMessage,
   isDisabled,
+  isPinned,
   isAgentMessage,
   onEdit,
   onRegenerateMessage,
@@ -21,12 +22,15 @@ export const MessageActions = ({
   onCreateCheckpoint,
   onGenerateImage,
   onExcludeFromPrompt,
+  onTogglePinned,
+  onInspectPrompt,
 }: {
   messageId: string;
   messageType: string;
   isStreaming: boolean;
   isLastMessage: boolean;
   isDisabled: boolean;
+  isPinned: boolean;
   isAgentMessage?: boolean;
   onEdit: (id: string) => void;
   onRegenerateMessage: (id: string) => void;
@@ -35,6 +39,8 @@ export const MessageActions = ({
   onCreateCheckpoint: (id: string) => void;
   onGenerateImage: (id: string) => void;
   onExcludeFromPrompt: (id: string) => void;
+  onTogglePinned: (id: string) => void;
+  onInspectPrompt: (id: string) => void;
 }) => {
   const [isDropdownOpen, setIsDropdownOpen] = useState(false);
   const [isRegenerating, setIsRegenerating] = useState(false);
@@ -116,6 +122,16 @@ export const MessageActions = ({
             {isDisabled ? <LuBookmarkPlus className="w-4 h-4 mr-2" /> : <LuBookmarkMinus className="w-4 h-4 mr-2" />}
             {isDisabled ? "Restore to history" : "Exclude from history"}
           </DropdownMenuItem>
+          <DropdownMenuItem onClick={() => onTogglePinned(messageId)}>
+            {isPinned ? <LuPinOff className="w-4 h-4 mr-2" /> : <LuPin className="w-4 h-4 mr-2" />}
+            {isPinned ? "Unpin from context" : "Pin to context"}
+          </DropdownMenuItem>
+          {messageType === "character" && (
+            <DropdownMenuItem onClick={() => onInspectPrompt(messageId)} disabled={isStreaming}>
+              <LuScrollText className="w-4 h-4 mr-2" />
+              Inspect prompt
+            </DropdownMenuItem>
+          )}
         </DropdownMenuContent>
       </DropdownMenu>
     </div>
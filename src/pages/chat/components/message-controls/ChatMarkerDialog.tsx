@@ -0,0 +1,97 @@
+import React, { useEffect, useState } from "react";
+import { Dialog, DialogBody, DialogContent, DialogFooter, DialogHeader, DialogTitle } from "@/components/shared/Dialog";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import { Switch } from "@/components/ui/switch";
+import type { ChatMarker } from "@/schema/chat-marker-schema";
+
+export interface ChatMarkerFormData {
+  title: string;
+  color: string | null;
+  context_reset: boolean;
+}
+
+interface ChatMarkerDialogProps {
+  isOpen: boolean;
+  onOpenChange: (isOpen: boolean) => void;
+  // Marker being edited, undefined when adding one
+  marker?: ChatMarker;
+  onSave: (data: ChatMarkerFormData) => void;
+}
+
+const DEFAULT_MARKER_COLOR = "#8b5cf6";
+
+export const ChatMarkerDialog: React.FC<ChatMarkerDialogProps> = ({ isOpen, onOpenChange, marker, onSave }) => {
+  const [title, setTitle] = useState("");
+  const [color, setColor] = useState<string | null>(null);
+  const [contextReset, setContextReset] = useState(false);
+
+  useEffect(() => {
+    if (isOpen) {
+      setTitle(marker?.title ?? "");
+      setColor(marker?.color ?? null);
+      setContextReset(marker?.context_reset ?? false);
+    }
+  }, [isOpen, marker]);
+
+  const handleSave = () => {
+    if (!title.trim()) {
+      return;
+    }
+    onSave({ title: title.trim(), color, context_reset: contextReset });
+    onOpenChange(false);
+  };
+
+  return (
+    <Dialog open={isOpen} onOpenChange={onOpenChange}>
+      <DialogContent size="small">
+        <DialogHeader>
+          <DialogTitle>{marker ? "Edit Marker" : "Add Marker"}</DialogTitle>
+        </DialogHeader>
+        <DialogBody>
+          <div className="space-y-4">
+            <div className="space-y-1">
+              <Label htmlFor="marker-title">Title</Label>
+              <Input
+                id="marker-title"
+                value={title}
+                maxLength={200}
+                placeholder="e.g. Scene 2: The Harbor"
+                onChange={(e) => setTitle(e.target.value)}
+                onKeyDown={(e) => e.key === "Enter" && handleSave()}
+                autoFocus
+              />
+            </div>
+            <div className="flex items-center justify-between">
+              <Label htmlFor="marker-color">Color</Label>
+              <div className="flex items-center gap-2">
+                <input id="marker-color" type="color" className="h-7 w-10 cursor-pointer bg-transparent" value={color ?? DEFAULT_MARKER_COLOR} onChange={(e) => setColor(e.target.value)} />
+                {color && (
+                  <Button variant="ghost" size="sm" onClick={() => setColor(null)}>
+                    Clear
+                  </Button>
+                )}
+              </div>
+            </div>
+            <div className="flex items-center justify-between gap-4">
+              <div>
+                <Label htmlFor="marker-context-reset">Reset context</Label>
+                <p className="text-xs text-muted-foreground">Messages above this marker are left out of the prompt.</p>
+              </div>
+              <Switch id="marker-context-reset" checked={contextReset} onCheckedChange={setContextReset} />
+            </div>
+          </div>
+        </DialogBody>
+        <DialogFooter>
+          <Button variant="outline" onClick={() => onOpenChange(false)}>
+            Cancel
+          </Button>
+          <Button onClick={handleSave} disabled={!title.trim()}>
+            Save
+          </Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+};
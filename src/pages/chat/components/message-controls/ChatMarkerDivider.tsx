@@ -0,0 +1,58 @@
+import { Pencil, RotateCcw, Trash2 } from "lucide-react";
+import { memo, useState } from "react";
+import { DestructiveConfirmDialog } from "@/components/shared/DestructiveConfirmDialog";
+import { Button } from "@/components/ui/button";
+import { useChatActions } from "@/hooks/chatStore";
+import type { ChatMarker } from "@/schema/chat-marker-schema";
+import { ChatMarkerDialog, ChatMarkerFormData } from "./ChatMarkerDialog";
+
+interface ChatMarkerDividerProps {
+  marker: ChatMarker;
+}
+
+const ChatMarkerDivider = ({ marker }: ChatMarkerDividerProps) => {
+  const [isEditOpen, setIsEditOpen] = useState(false);
+  const [isDeleteOpen, setIsDeleteOpen] = useState(false);
+  const { updateChatMarker, deleteChatMarker } = useChatActions();
+
+  const lineStyle = marker.color ? { backgroundColor: marker.color } : undefined;
+
+  const handleSave = async (data: ChatMarkerFormData) => {
+    await updateChatMarker(marker.id, data);
+  };
+
+  const handleDelete = async () => {
+    await deleteChatMarker(marker.id);
+    setIsDeleteOpen(false);
+  };
+
+  return (
+    <div className="group/marker flex items-center gap-2 my-3 px-2" data-marker-id={marker.id}>
+      <div className="h-px flex-1 bg-border" style={lineStyle} />
+      <h3 className="flex items-center gap-1.5 text-sm font-semibold text-foreground/80" style={marker.color ? { color: marker.color } : undefined}>
+        {marker.context_reset && <RotateCcw className="h-3.5 w-3.5" aria-label="Context resets here" />}
+        {marker.title}
+      </h3>
+      <div className="flex gap-0.5 opacity-0 group-hover/marker:opacity-100 transition-opacity">
+        <Button variant="ghost" size="icon" className="h-6 w-6" onClick={() => setIsEditOpen(true)} title="Edit marker">
+          <Pencil className="h-3 w-3" />
+        </Button>
+        <Button variant="ghost" size="icon" className="h-6 w-6 text-destructive" onClick={() => setIsDeleteOpen(true)} title="Delete marker">
+          <Trash2 className="h-3 w-3" />
+        </Button>
+      </div>
+      <div className="h-px flex-1 bg-border" style={lineStyle} />
+
+      <ChatMarkerDialog isOpen={isEditOpen} onOpenChange={setIsEditOpen} marker={marker} onSave={handleSave} />
+      <DestructiveConfirmDialog
+        open={isDeleteOpen}
+        onOpenChange={setIsDeleteOpen}
+        onConfirm={handleDelete}
+        title="Delete marker?"
+        description="The marker is removed. Messages are kept."
+      />
+    </div>
+  );
+};
+
+export default memo(ChatMarkerDivider);
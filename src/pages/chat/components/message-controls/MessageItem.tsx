@@ -1,10 +1,11 @@
 import React, { memo, useCallback, useEffect, useRef, useState } from "react";
-import { LuBot, LuEyeOff, LuFileText, LuPlay, LuRefreshCw, LuZap } from "react-icons/lu";
+import { LuBot, LuDices, LuEyeOff, LuFileText, LuPin, LuPlay, LuRefreshCw, LuZap } from "react-icons/lu";
 import { toast } from "sonner";
 import { MarkdownTextArea } from "@/components/markdownRender/markdown-textarea";
 import { useLazyRender } from "@/hooks/useLazyRender";
 import { cn } from "@/lib/utils";
 import type { ChatMessage, PromptConfig, UpdateChatMessageParams } from "@/schema/chat-message-schema";
+import { formatDiceRoll } from "@/services/inference/formatter/dice";
 import { ContextCutDivider, EditControls, MessageActions, StreamingIndicator } from "./AdditionalActions";
 import { MessageAvatar } from "./MessageAvatar";
 import { ReasoningSection } from "./ReasoningCollapsible";
@@ -87,6 +88,15 @@ const DisabledIndicator = () => (
   </div>
 );
 
+const PinnedIndicator = () => (
+  <div className="absolute top-2 right-2 z-10" title="Pinned: always kept in the context">
+    <div className="flex items-center gap-1.5 px-2 py-1 bg-primary/90 text-primary-foreground text-xs font-medium rounded-md shadow-sm backdrop-blur-sm">
+      <LuPin className="h-3 w-3" />
+      <span className="hidden @sm:inline">Pinned</span>
+    </div>
+  </div>
+);
+
 const ScriptIndicator = ({ script }: { script: keyof typeof SCRIPT_CONFIGS }) => {
   const config = SCRIPT_CONFIGS[script];
   const Icon = config.icon;
@@ -191,6 +201,7 @@ interface MessageItemProps {
   setIsEditingID: (id: string | null) => void;
   updateChatMessage: (messageId: string, message: Partial<UpdateChatMessageParams>, forceUpdate?: boolean) => Promise<ChatMessage>;
   deleteChatMessage: (messageId: string) => Promise<void>;
+  onInspectPrompt: (messageId: string) => void;
 }
 
 const MessageItem = ({
@@ -213,8 +224,10 @@ const MessageItem = ({
   setIsEditingID,
   updateChatMessage,
   deleteChatMessage,
+  onInspectPrompt,
 }: MessageItemProps) => {
   const isDisabled = !!message.disabled;
+  const isPinned = !!message.pinned;
 
   // Lazy rendering: skip the expensive markdown pipeline until the item is near the viewport.
   // Always render immediately for streaming/editing messages since they are always visible.
@@ -249,6 +262,15 @@ const MessageItem = ({
     }
   }, [message.id, isDisabled, updateChatMessage]);
 
+  const onTogglePinned = useCallback(async () => {
+    try {
+      await updateChatMessage(message.id, { pinned: !isPinned });
+    } catch (error) {
+      console.error("Failed to pin message:", error);
+      toast.error(isPinned ? "Failed to unpin message" : "Failed to pin message");
+    }
+  }, [message.id, isPinned, updateChatMessage]);
+
   const onDeleteMessage = useCallback(async () => {
     try {
       await deleteChatMessage(message.id);
@@ -322,6 +344,7 @@ const MessageItem = ({
             <DisabledIndicator />
           </>
         )}
+        {isPinned && !isDisabled && <PinnedIndicator />}
 
         {showAvatar && (message.type === "user" || message.type === "character") && (
           <MessageAvatar avatarPath={avatarPath || "/avatars/default.jpg"} messageType={message.type} isStreaming={isStreaming} />
@@ -348,6 +371,13 @@ const MessageItem = ({
                 </div>
               ))}
 
+            {message.type === "user" && !!message.extra?.dice_rolls?.length && (
+              <div className="flex justify-end mb-1 text-xs text-muted-foreground" title={message.extra.dice_rolls.map(formatDiceRoll).join("\n")}>
+                <LuDices className="w-3.5 h-3.5 mr-1" />
+                {message.extra.dice_rolls.length === 1 ? "1 roll" : `${message.extra.dice_rolls.length} rolls`}
+              </div>
+            )}
+
             <MarkdownTextArea
               autofocus={isEditing}
               initialValue={isEditing ? editedContent : displayContent}
@@ -373,6 +403,8 @@ const MessageItem = ({
                         messageType={message.type}
                         currentIndex={message.message_index}
                         totalVersions={message.messages.length}
+                        variantModel={message.extra?.variantModels?.[message.message_index]}
+                        finishReason={message.extra?.finishReasons?.[message.message_index]}
                         onSwipe={handleSwipe}
                         isLastMessage={isLastMessage}
                         isStreaming={isStreaming}
@@ -384,6 +416,7 @@ const MessageItem = ({
                       messageId={message.id}
                       messageType={message.type}
                       isDisabled={isDisabled}
+                      isPinned={isPinned}
                       isStreaming={isStreaming}
                       isAgentMessage={!!message.extra?.agentId && !!message.extra?.triggerContext}
                       onEdit={startEditing}
@@ -393,6 +426,8 @@ const MessageItem = ({
                       onCreateCheckpoint={onCreateCheckpoint}
                       onGenerateImage={onGenerateImage}
                       onExcludeFromPrompt={onExcludeFromPrompt}
+                      onTogglePinned={onTogglePinned}
+                      onInspectPrompt={onInspectPrompt}
                       isLastMessage={isLastMessage}
                     />
                   </div>
@@ -1,9 +1,10 @@
-import { BookUp2, LinkIcon, MergeIcon, ScissorsIcon } from "lucide-react";
+import { BookmarkPlus, BookUp2, LinkIcon, MergeIcon, ScissorsIcon } from "lucide-react";
 import { useEffect, useState } from "react";
 import { DestructiveConfirmDialog } from "@/components/shared/DestructiveConfirmDialog";
 import { Button } from "@/components/ui/button";
 import { useChatActions, useCurrentChatActiveChapterID, useCurrentChatId } from "@/hooks/chatStore";
 import { cn } from "@/lib/utils";
+import { ChatMarkerDialog, ChatMarkerFormData } from "@/pages/chat/components/message-controls/ChatMarkerDialog";
 import { SummaryDialog, SummarySettings } from "@/pages/chat/components/message-controls/SummaryDialog";
 import { ChatMessage, deleteChatMessagesByFilter } from "@/services/chat-message-service";
 import { useLocalSummarySettings } from "@/utils/local-storage";
@@ -19,10 +20,11 @@ export const MidMessageLayerControl: React.FC<MidMessageLayerControlProps> = ({
   const [isDeleteDialogOpen, setIsDeleteDialogOpen] = useState(false);
   const [isMergeDialogOpen, setIsMergeDialogOpen] = useState(false);
   const [isSummaryDialogOpen, setIsSummaryDialogOpen] = useState(false);
+  const [isMarkerDialogOpen, setIsMarkerDialogOpen] = useState(false);
 
   const chatId = useCurrentChatId();
   const chapterID = useCurrentChatActiveChapterID();
-  const { fetchChatMessages, updateChatMessage, deleteChatMessage } = useChatActions();
+  const { fetchChatMessages, updateChatMessage, deleteChatMessage, addChatMarker } = useChatActions();
 
   const [, setLocalSummarySettings] = useLocalSummarySettings();
   const handleDelete = async () => {
@@ -40,7 +42,12 @@ export const MidMessageLayerControl: React.FC<MidMessageLayerControlProps> = ({
     setTimeout(() => {
       setIsHovered(false);
     }, 800); // Just a animation delay
-  }, [isMergeDialogOpen, isSummaryDialogOpen, isDeleteDialogOpen]);
+  }, [isMergeDialogOpen, isSummaryDialogOpen, isDeleteDialogOpen, isMarkerDialogOpen]);
+
+  // A marker shares the position of the message above it, so it shows before the next one
+  const handleAddMarker = async (data: ChatMarkerFormData) => {
+    await addChatMarker({ ...data, position: messageBefore.position });
+  };
 
   const handleMerge = async () => {
     try {
@@ -126,6 +133,9 @@ export const MidMessageLayerControl: React.FC<MidMessageLayerControlProps> = ({
         <Button variant="ghost" size="icon" className="h-7 w-7" onClick={() => setIsSummaryDialogOpen(true)} title="Transform previous content into a summary">
           <BookUp2 className="h-4 w-4" />
         </Button>
+        <Button variant="ghost" size="icon" className="h-7 w-7" onClick={() => setIsMarkerDialogOpen(true)} title="Add a scene marker here">
+          <BookmarkPlus className="h-4 w-4" />
+        </Button>
         <Button variant="ghost" size="icon" className="h-7 w-7 text-destructive" onClick={() => setIsDeleteDialogOpen(true)} title="Remove all following messages">
           <ScissorsIcon className="h-4 w-4" />
         </Button>
@@ -134,6 +144,8 @@ export const MidMessageLayerControl: React.FC<MidMessageLayerControlProps> = ({
       {/* Summary Dialog */}
       <SummaryDialog isOpen={isSummaryDialogOpen} onOpenChange={setIsSummaryDialogOpen} onSave={handleSummarySettings} />
 
+      <ChatMarkerDialog isOpen={isMarkerDialogOpen} onOpenChange={setIsMarkerDialogOpen} onSave={handleAddMarker} />
+
       <DestructiveConfirmDialog
         open={isDeleteDialogOpen}
         onOpenChange={setIsDeleteDialogOpen}
@@ -2,6 +2,7 @@ import { ChevronLeft, ChevronRight } from "lucide-react";
 import { useState } from "react";
 import { Button } from "@/components/ui/button";
 import { cn } from "@/lib/utils";
+import type { VariantModel } from "@/schema/chat-message-schema";
 
 // Extracted VersionControls component
 export const VersionControls = ({
@@ -12,6 +13,8 @@ export const VersionControls = ({
   onSwipe,
   isLastMessage,
   isStreaming,
+  variantModel,
+  finishReason,
 }: {
   messageId: string;
   messageType: string;
@@ -19,6 +22,8 @@ export const VersionControls = ({
   totalVersions: number;
   isLastMessage: boolean;
   isStreaming: boolean;
+  variantModel?: VariantModel | null;
+  finishReason?: string | null;
   onSwipe: (id: string, direction: "left" | "right") => void;
 }) => {
   const [isProcessingSwipe, setIsProcessingSwipe] = useState(false);
@@ -39,8 +44,14 @@ export const VersionControls = ({
 
   return (
     <div className={cn("flex items-center gap-1", messageType === "character" ? "order-1" : "order-2")}>
-      <span className="text-xs text-muted-foreground ml-1">
+      <span className="text-xs text-muted-foreground ml-1" title={variantModel?.actual_model}>
         {currentIndex + 1}/{totalVersions}
+        {variantModel?.model_name && totalVersions > 1 && <span className="ml-1">· {variantModel.model_name}</span>}
+        {finishReason === "length" && (
+          <span className="ml-1 text-orange-400" title="Generation stopped at the max tokens limit">
+            · cut off
+          </span>
+        )}
       </span>
       <Button
         variant="ghost"
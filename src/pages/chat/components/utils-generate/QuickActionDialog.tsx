@@ -1,5 +1,5 @@
 import { motion } from "framer-motion";
-import { MessageCircle, MessageSquarePlus, Settings, Wand2 } from "lucide-react";
+import { Dices, MessageCircle, MessageSquarePlus, Settings, Wand2 } from "lucide-react";
 import React, { useEffect, useState } from "react";
 import { MarkdownTextArea } from "@/components/markdownRender/markdown-textarea";
 import { Dialog, DialogBody, DialogContent, DialogFooter, DialogHeader, DialogTitle } from "@/components/shared/Dialog";
@@ -14,6 +14,7 @@ import WidgetConfig from "@/pages/chat/components/WidgetConfig";
 import { promptReplacementSuggestionList } from "@/schema/chat-message-schema";
 import { QuickAction } from "@/schema/profiles-schema";
 import { estimateTokens } from "@/services/inference/formatter/apply-context-limit";
+import { parseDiceExpression } from "@/services/inference/formatter/dice";
 
 interface QuickActionDialogProps {
   isOpen: boolean;
@@ -31,11 +32,25 @@ const DEFAULT_ACTION_STATE: Partial<QuickAction> = {
   systemPromptOverride: "",
   streamOption: "textarea",
   participantMessageType: "new", // Default value for participant message type
+  rollBeforeGenerate: "",
 };
 
+function getRollError(expression: string | null | undefined): string | null {
+  if (!expression?.trim()) {
+    return null;
+  }
+  try {
+    parseDiceExpression(expression);
+    return null;
+  } catch (error) {
+    return error instanceof Error ? error.message : String(error);
+  }
+}
+
 export const QuickActionDialog: React.FC<QuickActionDialogProps> = ({ isOpen, onOpenChange, isEditMode, initialData, onSave }) => {
   const [currentAction, setCurrentAction] = useState<Partial<QuickAction>>(DEFAULT_ACTION_STATE);
   const [activeTab, setActiveTab] = useState("basic");
+  const rollError = getRollError(currentAction.rollBeforeGenerate);
 
   useEffect(() => {
     if (isOpen) {
@@ -46,7 +61,7 @@ export const QuickActionDialog: React.FC<QuickActionDialogProps> = ({ isOpen, on
   }, [isOpen, isEditMode, initialData]);
 
   const handleSave = () => {
-    if (!currentAction.label || !currentAction.userPrompt) {
+    if (!currentAction.label || !currentAction.userPrompt || rollError) {
       // Basic validation
       return;
     }
@@ -60,6 +75,7 @@ export const QuickActionDialog: React.FC<QuickActionDialogProps> = ({ isOpen, on
       systemPromptOverride: currentAction.systemPromptOverride || "",
       streamOption: currentAction.streamOption || "textarea",
       participantMessageType: currentAction.participantMessageType || "new",
+      rollBeforeGenerate: currentAction.rollBeforeGenerate?.trim() || null,
     };
 
     onSave(actionToSave);
@@ -196,6 +212,24 @@ export const QuickActionDialog: React.FC<QuickActionDialogProps> = ({ isOpen, on
                   Use {"{{user.message}}"} to refer to the last user message
                 </p>
               </div>
+
+              <div className="space-y-1">
+                <Label htmlFor="rollBeforeGenerate" className="text-sm font-medium flex items-center gap-1">
+                  <Dices className="h-3 w-3" /> Roll Before Generating
+                </Label>
+                <Input
+                  id="rollBeforeGenerate"
+                  value={currentAction.rollBeforeGenerate || ""}
+                  onChange={(e) => handleFieldChange("rollBeforeGenerate", e.target.value)}
+                  placeholder="e.g. 1d20+5, d20 adv, 4d6! (leave empty to skip)"
+                  className="h-8"
+                />
+                {rollError ? (
+                  <p className="text-xs text-destructive mt-0.5">{rollError}</p>
+                ) : (
+                  <p className="text-xs text-muted-foreground mt-0.5">The result is posted to the chat as a system message, so the model reads the roll instead of making one up</p>
+                )}
+              </div>
             </TabsContent>
 
             <TabsContent value="template" className="space-y-3 mt-2">
@@ -236,7 +270,7 @@ export const QuickActionDialog: React.FC<QuickActionDialogProps> = ({ isOpen, on
           <Button
             type="button"
             onClick={handleSave}
-            disabled={!currentAction.label?.trim() || !currentAction.userPrompt?.trim()}
+            disabled={!currentAction.label?.trim() || !currentAction.userPrompt?.trim() || !!rollError}
             className="bg-primary hover:bg-primary/90 text-primary-foreground transition-colors h-8 px-3 py-1"
           >
             {isEditMode ? "Update" : "Save"} Action
@@ -1,4 +1,4 @@
-import { BookOpen, Database, FileTextIcon, HelpCircle, MessageSquare, Settings, Smile, Sparkles, Users } from "lucide-react";
+import { BookOpen, Database, FileTextIcon, HelpCircle, MessageSquare, ScrollText, Settings, Smile, Sparkles, Users } from "lucide-react";
 import React from "react";
 import WidgetChapters from "@/pages/chat/components/WidgetChapters";
 // import WidgetCharacterSheet from "@/pages/chat/components/WidgetCharacterSheet";
@@ -9,12 +9,13 @@ import WidgetGenerate from "@/pages/chat/components/WidgetGenerate";
 import WidgetMessages from "@/pages/chat/components/WidgetMessages";
 import WidgetParticipants from "@/pages/chat/components/WidgetParticipants";
 import WidgetScript from "@/pages/chat/components/WidgetScript";
+import WidgetSynopsis from "@/pages/chat/components/WidgetSynopsis";
 import WidgetExpressions from "../components/WidgetExpressions";
 import { WidgetHelp } from "../components/WidgetHelp";
 
 // Import types for props if available
 
-export type WidgetId = "messages" | "config" | "generate" | "participants" | "scripts" | "database" | "chapters" | "expressions" | "help";
+export type WidgetId = "messages" | "config" | "generate" | "participants" | "scripts" | "database" | "chapters" | "synopsis" | "expressions" | "help";
 
 export const widgetTitles: Record<WidgetId, string> = {
   messages: "Messages",
@@ -26,6 +27,7 @@ export const widgetTitles: Record<WidgetId, string> = {
   // memory: "Short-Term Memory",
   database: "Database",
   chapters: "Chapters",
+  synopsis: "Synopsis",
   expressions: "Expressions",
   help: "Help",
 };
@@ -103,6 +105,13 @@ export const widgetConfigurations: Record<WidgetId, WidgetConfiguration<any>> =
     defaultProps: {},
     icon: <BookOpen className="w-4 h-4" />,
   },
+  synopsis: {
+    id: "synopsis",
+    title: widgetTitles.synopsis,
+    component: WidgetSynopsis,
+    defaultProps: {},
+    icon: <ScrollText className="w-4 h-4" />,
+  },
   expressions: {
     id: "expressions",
     title: widgetTitles.expressions,
@@ -331,22 +331,54 @@ export const configFields: ConfigFields = [
     description: "Sequences to stop generation when encountered.",
     default: [],
   },
+  {
+    name: "normalize_leading_whitespace",
+    type: "boolean",
+    title: "Trim Leading Whitespace",
+    description: "Remove spaces and newlines some providers send before the start of the answer. The rest of the text is kept exactly as received.",
+    default: true,
+  },
+  {
+    name: "inject_speaker_names",
+    type: "boolean",
+    title: "Speaker Names",
+    description: "Tell the model who wrote each message. OpenAI, OpenRouter and OpenAI-compatible chat models get the name as the message's name field; other engines get \"Name: \" in front of the text.",
+    default: false,
+  },
   {
     name: "prompt_cache",
     type: "section",
     title: "Prompt Cache",
-    description: "Settings for caching prompt computations to speed up generation, primarily for specific APIs/models.",
+    description: "Mark the system prompt and recent messages as cacheable. Applied on Anthropic and AWS Bedrock; OpenAI caches automatically and other engines ignore it.",
     fields: [
       {
-        name: "prompt_cache_depth",
+        name: "cache_system_prompt",
+        type: "boolean",
+        title: "Cache System Prompt",
+        description: "Place a cache breakpoint after the system prompt.",
+        default: true,
+      },
+      {
+        name: "cache_last_n_messages",
         type: "stepbutton",
-        title: "Chat Depth",
-        description: "Number of recent conversation turns to include in the prompt cache.",
-        min: 1,
-        max: 50,
+        title: "Cached Messages",
+        description: "Number of most recent messages that get a cache breakpoint. Providers allow at most 4 breakpoints per request, including the system prompt.",
+        min: 0,
+        max: 4,
         step: 1,
         default: 1,
       },
+      {
+        name: "cache_ttl",
+        type: "select",
+        title: "Cache Lifetime",
+        description: "How long Anthropic keeps the cached prompt. The 1 hour cache costs more to write but pays off in long sessions with pauses between messages. Bedrock always uses 5 minutes.",
+        options: [
+          { value: "5m", label: "5 minutes" },
+          { value: "1h", label: "1 hour" },
+        ],
+        default: "5m",
+      },
     ],
   },
 ];
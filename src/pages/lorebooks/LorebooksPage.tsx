@@ -1,12 +1,14 @@
 import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
 import { open as openDialog } from "@tauri-apps/plugin-dialog";
-import { ArrowLeft, Download, FileUp, Plus, RefreshCw, Search, Settings, SortAsc, Upload, X } from "lucide-react";
+import { ArrowLeft, Download, FileUp, Link, Plus, RefreshCw, Search, Settings, SortAsc, Upload, X } from "lucide-react";
 import { useEffect, useMemo, useRef, useState } from "react";
 import { toast } from "sonner";
 import { DestructiveConfirmDialog } from "@/components/shared/DestructiveConfirmDialog";
+import { ImportFromUrlDialog } from "@/components/shared/ImportFromUrlDialog";
 import { Button, buttonVariants } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import { Select, SelectContent, SelectItem, SelectTrigger } from "@/components/ui/select";
+import { useChatActions } from "@/hooks/chatStore";
 import { useIsLoadingLorebooks, useLorebookStoreActions, useLorebooks, useSelectedLorebookId } from "@/hooks/lorebookStore";
 import { useCurrentProfile } from "@/hooks/ProfileStore";
 import { Lorebook } from "@/schema/lorebook-schema";
@@ -27,6 +29,7 @@ export default function LorebooksPage() {
   const selectedLorebookId = useSelectedLorebookId();
   const isLoading = useIsLoadingLorebooks();
   const { loadLorebooks, deleteLorebook, selectLorebook, updateLorebook } = useLorebookStoreActions();
+  const { fetchChatList } = useChatActions();
 
   // State for UI controls
   const [searchQuery, setSearchQuery] = useState("");
@@ -37,6 +40,7 @@ export default function LorebooksPage() {
   const [lorebookToDelete, setLorebookToDelete] = useState<Lorebook | null>(null);
   const [settings, setSettings] = useLocalLorebookPageSettings();
   const [isDraggingFile, setIsDraggingFile] = useState(false);
+  const [isUrlImportOpen, setIsUrlImportOpen] = useState(false);
   const selectedTags = settings.selectedTags ?? [];
 
   // Load lorebooks when component mounts or profile changes
@@ -301,6 +305,9 @@ export default function LorebooksPage() {
                     <Button variant="outline" size="icon" className="bg-background" onClick={handleImportClick} title="Import Lorebook">
                       <Upload className="h-4 w-4" />
                     </Button>
+                    <Button variant="outline" size="icon" className="bg-background" onClick={() => setIsUrlImportOpen(true)} title="Import from URL">
+                      <Link className="h-4 w-4" />
+                    </Button>
                     <Select
                       value={`${settings.sort.field}-${settings.sort.direction}`}
                       onValueChange={(value) => {
@@ -455,6 +462,22 @@ export default function LorebooksPage() {
 
       {currentProfile?.id && <LorebookFormDialog open={isFormDialogOpen} onOpenChange={setIsFormDialogOpen} profileId={currentProfile.id} initialLorebook={lorebookToEdit} />}
 
+      <ImportFromUrlDialog
+        open={isUrlImportOpen}
+        onOpenChange={setIsUrlImportOpen}
+        kindHint="lorebook"
+        onImported={(summary) => {
+          if (!currentProfile?.id) {
+            return;
+          }
+          if (summary.kind === "chat") {
+            fetchChatList(currentProfile.id).catch((error) => console.error("Failed to refresh chats:", error));
+          } else {
+            loadLorebooks(currentProfile.id);
+          }
+        }}
+      />
+
       {lorebookToDelete && currentProfile?.id && (
         <DestructiveConfirmDialog
           title={`Delete "${lorebookToDelete.name}"?`}
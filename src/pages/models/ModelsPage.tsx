@@ -1,19 +1,22 @@
 import { type ReactNode, useEffect, useMemo, useState } from "react";
 import type { IconType } from "react-icons";
-import { LuArrowDownAZ, LuBrain, LuDatabase, LuFileSearch, LuImage, LuMusic, LuPlus, LuRefreshCw, LuSearch } from "react-icons/lu";
+import { LuActivity, LuArrowDownAZ, LuBrain, LuDatabase, LuFileSearch, LuImage, LuMusic, LuPlus, LuRefreshCw, LuSearch } from "react-icons/lu";
+import { toast } from "sonner";
 import { DestructiveConfirmDialog } from "@/components/shared/DestructiveConfirmDialog";
 import { Button, buttonVariants } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
-import { Select, SelectContent, SelectItem, SelectTrigger } from "@/components/ui/select";
+import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from "@/components/ui/select";
 import { Tabs, TabsList, TabsTrigger } from "@/components/ui/tabs";
 import { useEmbeddingManifests, useEmbeddingManifestsActions, useModelManifests, useModelManifestsActions } from "@/hooks/manifestStore";
 import { useModelsActions, useModelsLoading } from "@/hooks/modelsStore";
 import { useCurrentProfile } from "@/hooks/ProfileStore";
-import type { NewModelParams } from "@/services/model-service";
+import { type ModelValidation, validateAllModels } from "@/services/inference/model-validation";
+import { type NewModelParams, rebindModel } from "@/services/model-service";
 import { useLocalModelsPageSettings } from "@/utils/local-storage";
 import type { Model, ModelType } from "../../schema/models-schema";
 import { ModelCard } from "./components/ModelCard";
 import { ModelDialog } from "./components/ModelDialog";
+import { ParameterSweepDialog } from "./components/ParameterSweepDialog";
 
 export type ModelsPageSettings = {
   view: {
@@ -75,10 +78,12 @@ export default function Models() {
   const [modelDialogOpen, setModelDialogOpen] = useState(false);
   const [modelDialogMode, setModelDialogMode] = useState<"add" | "edit">("add");
   const [deleteDialogOpen, setDeleteDialogOpen] = useState(false);
+  const [sweepModel, setSweepModel] = useState<Model | null>(null);
 
   const [selectedModel, setSelectedModel] = useState<Model | null>(null);
+  const [replacementModelId, setReplacementModelId] = useState<string>("none");
   const currentProfile = useCurrentProfile();
-  const { getModelsByProfileGroupedByType, deleteModel, createModel } = useModelsActions();
+  const { getModelsByProfileGroupedByType, deleteModel, createModel, setFavorite } = useModelsActions();
   const { fetchManifests } = useModelManifestsActions();
   const { fetchManifests: fetchEmbeddingManifests } = useEmbeddingManifestsActions();
   const manifests = useModelManifests();
@@ -88,6 +93,8 @@ export default function Models() {
   const isLoading = useModelsLoading();
   const [search, setSearch] = useState("");
   const [settings, setSettings] = useLocalModelsPageSettings();
+  const [validations, setValidations] = useState<Record<string, ModelValidation>>({});
+  const [isValidating, setIsValidating] = useState(false);
 
   useEffect(() => {
     const loadModels = async () => {
@@ -212,26 +219,48 @@ export default function Models() {
 
   const handleDelete = async (model: Model) => {
     setSelectedModel(model);
+    setReplacementModelId("none");
     setDeleteDialogOpen(true);
   };
 
+  // Models the deleted one's chat templates, lorebooks and fallback chains can move to
+  const replacementModels = useMemo(
+    () => (selectedModel ? allModels.filter((model) => model.type === selectedModel.type && model.id !== selectedModel.id) : []),
+    [allModels, selectedModel],
+  );
+
   const confirmDelete = async () => {
     if (!selectedModel) {
       return;
     }
 
     try {
+      if (replacementModelId !== "none" && currentProfile?.id) {
+        await rebindModel(selectedModel.id, replacementModelId, currentProfile.id);
+      }
       const success = await deleteModel(selectedModel.id);
       if (success && currentProfile?.id) {
         refreshModels();
       }
     } catch (error) {
       console.error("Failed to delete model:", error);
+      toast.error("Failed to delete model", { description: error instanceof Error ? error.message : String(error) });
     } finally {
       setDeleteDialogOpen(false);
     }
   };
 
+  const handleToggleFavorite = async (model: Model) => {
+    try {
+      if (await setFavorite(model.profile_id, model.id, !model.favorite)) {
+        await refreshModels();
+      }
+    } catch (error) {
+      console.error("Failed to update favorite:", error);
+      toast.error("Failed to update favorite", { description: error instanceof Error ? error.message : String(error) });
+    }
+  };
+
   const refreshModels = async () => {
     if (currentProfile?.id) {
       const groupedModels = await getModelsByProfileGroupedByType(currentProfile.id);
@@ -254,6 +283,28 @@ export default function Models() {
     }
   };
 
+  const checkAllModels = async () => {
+    if (!currentProfile?.id) {
+      return;
+    }
+
+    setIsValidating(true);
+    try {
+      const results = await validateAllModels(currentProfile.id);
+      setValidations(Object.fromEntries(results.map((result) => [result.model_id, result])));
+      const failed = results.filter((result) => result.status === "error");
+      if (failed.length > 0) {
+        toast.warning(`${failed.length} of ${results.filter((result) => result.status !== "skipped").length} models failed the connection check`, {
+          description: failed.map((result) => result.name).join(", "),
+        });
+      }
+    } catch (error) {
+      toast.error(error instanceof Error ? error.message : "Failed to check models");
+    } finally {
+      setIsValidating(false);
+    }
+  };
+
   const handleDuplicate = async (model: Model) => {
     if (!currentProfile?.id) {
       console.error("No current profile available for duplication");
@@ -311,6 +362,10 @@ export default function Models() {
             </div>
 
             <div className="flex shrink-0 items-center gap-2">
+              <Button variant="outline" size="icon" onClick={checkAllModels} disabled={isValidating || modelCounts.llm === 0} title="Check All Language Models" className="bg-background">
+                <LuActivity className={`h-4 w-4 ${isValidating ? "animate-pulse" : ""}`} />
+              </Button>
+
               <Button variant="outline" size="icon" onClick={refreshModels} disabled={isLoading} title="Refresh Models" className="bg-background">
                 <LuRefreshCw className={`h-4 w-4 ${isLoading ? "animate-spin" : ""}`} />
               </Button>
@@ -386,7 +441,16 @@ export default function Models() {
 
                   <div className="grid grid-cols-[repeat(auto-fill,minmax(26rem,1fr))] gap-3">
                     {group.models.map((model) => (
-                      <ModelCard key={model.id} model={model} onDelete={handleDelete} onDuplicate={handleDuplicate} onOpenSettings={openEditDialog} />
+                      <ModelCard
+                        key={model.id}
+                        model={model}
+                        onDelete={handleDelete}
+                        onDuplicate={handleDuplicate}
+                        onToggleFavorite={handleToggleFavorite}
+                        onCompareParameters={setSweepModel}
+                        onOpenSettings={openEditDialog}
+                        validation={validations[model.id]}
+                      />
                     ))}
                   </div>
                 </section>
@@ -428,10 +492,30 @@ export default function Models() {
           <>
             <p>Are you sure you want to delete {selectedModel?.name}?</p>
             <p className="text-sm text-muted-foreground">This action cannot be undone.</p>
+            {replacementModels.length > 0 && (
+              <span className="mt-3 flex items-center gap-2 text-sm">
+                <span className="shrink-0">Move what uses it to</span>
+                <Select value={replacementModelId} onValueChange={setReplacementModelId}>
+                  <SelectTrigger className="h-8">
+                    <SelectValue />
+                  </SelectTrigger>
+                  <SelectContent>
+                    <SelectItem value="none">Nothing, leave them without a model</SelectItem>
+                    {replacementModels.map((model) => (
+                      <SelectItem key={model.id} value={model.id}>
+                        {model.name}
+                      </SelectItem>
+                    ))}
+                  </SelectContent>
+                </Select>
+              </span>
+            )}
           </>
         }
         onConfirm={confirmDelete}
       />
+
+      <ParameterSweepDialog model={sweepModel} open={!!sweepModel} onOpenChange={(open) => !open && setSweepModel(null)} />
     </div>
   );
 }
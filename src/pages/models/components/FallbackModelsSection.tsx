@@ -0,0 +1,68 @@
+import { LuArrowDown, LuArrowUp, LuX } from "react-icons/lu";
+import { Button } from "@/components/ui/button";
+import { Label } from "@/components/ui/label";
+import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from "@/components/ui/select";
+import { useModels } from "@/hooks/modelsStore";
+
+interface FallbackModelsSectionProps {
+  // The model being edited, never offered as its own fallback
+  modelId: string;
+  value: string[];
+  onChange: (value: string[]) => void;
+}
+
+export function FallbackModelsSection({ modelId, value, onChange }: FallbackModelsSectionProps) {
+  const models = useModels();
+  const candidates = models.filter((model) => model.type === "llm" && model.id !== modelId && !value.includes(model.id));
+
+  const move = (index: number, offset: number) => {
+    const next = [...value];
+    [next[index], next[index + offset]] = [next[index + offset], next[index]];
+    onChange(next);
+  };
+
+  return (
+    <div className="space-y-2">
+      <Label className="text-sm font-medium">Fallback Models</Label>
+      <p className="text-xs text-muted-foreground">
+        Tried in order when this model's provider is unreachable or keeps failing before it answers. Models expecting a different completion mode are skipped.
+      </p>
+
+      {value.length > 0 && (
+        <ol className="space-y-1">
+          {value.map((fallbackId, index) => {
+            const fallback = models.find((model) => model.id === fallbackId);
+            return (
+              <li key={fallbackId} className="flex items-center gap-2 rounded-md border border-border px-2 py-1 text-sm">
+                <span className="text-xs text-muted-foreground w-4">{index + 1}.</span>
+                <span className={fallback ? "flex-1 truncate" : "flex-1 truncate italic text-muted-foreground"}>{fallback?.name ?? "Deleted model"}</span>
+                <Button variant="ghost" size="icon" className="h-6 w-6" disabled={index === 0} onClick={() => move(index, -1)} title="Move up">
+                  <LuArrowUp className="h-3 w-3" />
+                </Button>
+                <Button variant="ghost" size="icon" className="h-6 w-6" disabled={index === value.length - 1} onClick={() => move(index, 1)} title="Move down">
+                  <LuArrowDown className="h-3 w-3" />
+                </Button>
+                <Button variant="ghost" size="icon" className="h-6 w-6" onClick={() => onChange(value.filter((id) => id !== fallbackId))} title="Remove">
+                  <LuX className="h-3 w-3" />
+                </Button>
+              </li>
+            );
+          })}
+        </ol>
+      )}
+
+      <Select value="" onValueChange={(id) => onChange([...value, id])} disabled={candidates.length === 0}>
+        <SelectTrigger className="w-full">
+          <SelectValue placeholder={candidates.length === 0 ? "No other language models" : "Add a fallback model"} />
+        </SelectTrigger>
+        <SelectContent>
+          {candidates.map((model) => (
+            <SelectItem key={model.id} value={model.id}>
+              {model.name}
+            </SelectItem>
+          ))}
+        </SelectContent>
+      </Select>
+    </div>
+  );
+}
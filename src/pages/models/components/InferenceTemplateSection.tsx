@@ -319,7 +319,7 @@ export function InstructTemplateSection({ disabled, onChange, modelTemplateID }:
 
       // Use the export utility to handle the export
       const exportType: ExportType = "instruction_template";
-      const success = await exportSingleToJsonFile(exportTemplate, exportType, `inference_template_${template.name.replace(/[^a-zA-Z0-9]/g, "_")}`);
+      const success = await exportSingleToJsonFile(exportTemplate, exportType, `inference_template_${template.name}`);
 
       if (!success) {
         console.warn("Export was cancelled or failed");
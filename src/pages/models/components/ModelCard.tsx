@@ -1,9 +1,11 @@
 import { useEffect, useState } from "react";
-import { LuCopy, LuTrash2 } from "react-icons/lu";
+import { LuCopy, LuFlaskConical, LuHeart, LuTrash2 } from "react-icons/lu";
 import { Button } from "@/components/ui/button";
 import { useEmbeddingManifestsActions, useModelManifestsActions } from "@/hooks/manifestStore";
 import { useInferenceTemplate } from "@/hooks/templateStore";
+import { cn } from "@/lib/utils";
 import type { Engine } from "@/schema/model-manifest-schema";
+import type { ModelValidation } from "@/services/inference/model-validation";
 import { getEngineColor, getEngineIcon } from "@/utils/engine-icons";
 import type { Model } from "../../../schema/models-schema";
 
@@ -11,10 +13,15 @@ interface ModelCardProps {
   model: Model;
   onDelete?: (model: Model) => void;
   onDuplicate?: (model: Model) => void;
+  onToggleFavorite?: (model: Model) => void;
+  // Language models only: run one prompt at several parameter values
+  onCompareParameters?: (model: Model) => void;
   onOpenSettings: (model: Model) => void;
+  // Result of the last "Check All" run, if the model was part of it
+  validation?: ModelValidation;
 }
 
-export function ModelCard({ model, onDelete, onDuplicate, onOpenSettings }: ModelCardProps) {
+export function ModelCard({ model, onDelete, onDuplicate, onToggleFavorite, onCompareParameters, onOpenSettings, validation }: ModelCardProps) {
   const { getManifestById } = useModelManifestsActions();
   const { getManifestById: getEmbeddingManifestById } = useEmbeddingManifestsActions();
   const [manifestName, setManifestName] = useState<string>("");
@@ -72,15 +79,50 @@ export function ModelCard({ model, onDelete, onDuplicate, onOpenSettings }: Mode
       <div className="min-w-0 flex-1">
         <div className="flex items-center gap-2">
           <h3 className="text-sm font-semibold truncate">{model.name}</h3>
+          {model.favorite && <LuHeart className="h-3 w-3 shrink-0 fill-primary text-primary" aria-label="Favorite" />}
           {inferenceMode && <span className="shrink-0 text-[10px] font-medium text-muted-foreground/70 bg-muted/60 rounded px-1.5 py-0.5">{inferenceMode}</span>}
         </div>
 
         <p className="text-xs text-muted-foreground mt-0.5">{manifestName || model.manifest_id}</p>
 
         {modelValue && <p className="text-xs text-muted-foreground/60 font-mono mt-1 truncate">{modelValue}</p>}
+
+        {validation && validation.status !== "skipped" && (
+          <p className={`text-xs mt-1 truncate ${validation.status === "ok" ? "text-green-600 dark:text-green-400" : "text-destructive"}`} title={validation.error?.message}>
+            {validation.status === "ok" ? `Connected in ${validation.latency_ms} ms` : `Failed: ${validation.error?.message}`}
+          </p>
+        )}
       </div>
 
       <div className="absolute right-1.5 top-1.5 flex gap-0.5 opacity-0 transition-opacity group-hover:opacity-100">
+        {onCompareParameters && model.type === "llm" && (
+          <Button
+            variant="ghost"
+            size="icon"
+            className="h-7 w-7 text-muted-foreground hover:text-foreground"
+            onClick={(e) => {
+              e.stopPropagation();
+              onCompareParameters(model);
+            }}
+            title="Compare parameters"
+          >
+            <LuFlaskConical className="h-3.5 w-3.5" />
+          </Button>
+        )}
+        {onToggleFavorite && (
+          <Button
+            variant="ghost"
+            size="icon"
+            className="h-7 w-7 text-muted-foreground hover:text-primary"
+            onClick={(e) => {
+              e.stopPropagation();
+              onToggleFavorite(model);
+            }}
+            title={model.favorite ? "Remove from favorites" : "Add to favorites"}
+          >
+            <LuHeart className={cn("h-3.5 w-3.5", model.favorite ? "fill-primary text-primary" : "")} />
+          </Button>
+        )}
         <Button
           variant="ghost"
           size="icon"
@@ -10,6 +10,7 @@ import { useEmbeddingManifests, useModelManifests } from "@/hooks/manifestStore"
 import { InstructTemplateSection } from "@/pages/models/components/InferenceTemplateSection";
 import type { Manifest } from "@/schema/model-manifest-schema";
 import type { Model } from "@/schema/models-schema";
+import { FallbackModelsSection } from "./FallbackModelsSection";
 import { ModelForm, type ModelFormRef } from "./ModelForm";
 
 interface ModelDialogProps {
@@ -29,6 +30,7 @@ export function ModelDialog({ mode, model, open, onOpenChange, onSuccess }: Mode
   const [maxConcurrency, setMaxConcurrency] = useState<number>(1);
   const [completionType, setCompletionType] = useState<"chat" | "text">("chat");
   const [inferenceTemplateID, setInferenceTemplateID] = useState<string | null>(null);
+  const [fallbackModelIds, setFallbackModelIds] = useState<string[]>([]);
   const [selectedManifest, setSelectedManifest] = useState<Manifest | null>(null);
   const [isSaving, setIsSaving] = useState(false);
 
@@ -48,11 +50,13 @@ export function ModelDialog({ mode, model, open, onOpenChange, onSuccess }: Mode
       const templateId = model.inference_template_id || null;
       setInferenceTemplateID(templateId);
       setCompletionType(templateId ? "text" : "chat");
+      setFallbackModelIds(model.fallback_model_ids ?? []);
       setActiveTab("connection");
     } else {
       setMaxConcurrency(1);
       setInferenceTemplateID(null);
       setCompletionType("chat");
+      setFallbackModelIds([]);
       setSelectedManifest(null);
       setActiveTab("connection");
     }
@@ -70,8 +74,9 @@ export function ModelDialog({ mode, model, open, onOpenChange, onSuccess }: Mode
     () => ({
       max_concurrency: maxConcurrency,
       inference_template_id: completionType === "text" ? inferenceTemplateID : null,
+      fallback_model_ids: fallbackModelIds,
     }),
-    [maxConcurrency, completionType, inferenceTemplateID],
+    [maxConcurrency, completionType, inferenceTemplateID, fallbackModelIds],
   );
 
   const handleSave = async () => {
@@ -138,6 +143,8 @@ export function ModelDialog({ mode, model, open, onOpenChange, onSuccess }: Mode
                   <StepButton id="concurrency" min={1} max={10} step={1} value={maxConcurrency} onValueChange={setMaxConcurrency} />
                 </div>
 
+                {model?.type === "llm" && <FallbackModelsSection modelId={model.id} value={fallbackModelIds} onChange={setFallbackModelIds} />}
+
                 {supportsCompletion && (
                   <>
                     <div className="space-y-2">
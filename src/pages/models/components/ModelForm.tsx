@@ -13,10 +13,12 @@ import { Input } from "@/components/ui/input";
 import { useEmbeddingManifests, useEmbeddingManifestsActions, useEmbeddingManifestsLoading, useModelManifests, useModelManifestsActions, useModelManifestsLoading } from "@/hooks/manifestStore";
 import { useModelsActions } from "@/hooks/modelsStore";
 import { useCurrentProfile } from "@/hooks/ProfileStore";
-import { useInference } from "@/hooks/useInference";
+import { isActiveInferenceStatus, useInference } from "@/hooks/useInference";
 import { ModelSpecsSchema } from "@/schema/inference-engine-schema";
 import type { Manifest } from "@/schema/model-manifest-schema";
 import type { Model, ModelType } from "@/schema/models-schema";
+import { manifestSupportsReasoning } from "@/services/ai-providers/aisdk/reasoning-support";
+import { validateModelConfig } from "@/services/model-service";
 import { getEngineColor, getEngineIcon } from "@/utils/engine-icons";
 import { ModelInputFields } from "./ModelInputFields";
 
@@ -36,7 +38,7 @@ interface ModelFormProps {
   model?: Model;
   mode: "add" | "edit" | "duplicate";
   hideSubmit?: boolean;
-  inferenceData?: { max_concurrency: number; inference_template_id?: string | null };
+  inferenceData?: { max_concurrency: number; inference_template_id?: string | null; fallback_model_ids?: string[] };
   onManifestChange?: (manifest: Manifest | null) => void;
 }
 
@@ -354,6 +356,7 @@ export const ModelForm = forwardRef<ModelFormRef, ModelFormProps>(({ onSuccess,
         config: configFields,
         max_concurrent_requests: 1,
         engine: selectedManifest.engine,
+        supports_reasoning: manifestSupportsReasoning(selectedManifest),
       });
 
       const requestId = await runInference({
@@ -362,6 +365,7 @@ export const ModelForm = forwardRef<ModelFormRef, ModelFormProps>(({ onSuccess,
         systemPrompt: "You are a helpful assistant. Reply with 'Connection successful' to confirm the connection works.",
         parameters: {},
         stream: false,
+        priority: "interactive",
       });
 
       if (requestId) {
@@ -400,6 +404,12 @@ export const ModelForm = forwardRef<ModelFormRef, ModelFormProps>(({ onSuccess,
         }
       }
 
+      // Errors are enforced by the model service; warnings don't block the save
+      const { warnings } = validateModelConfig(selectedManifest, configFields as Record<string, any>);
+      for (const warning of warnings) {
+        toast.warning(warning);
+      }
+
       if (mode === "edit" && model) {
         await updateModel(model.id, {
           name: values.name as string,
@@ -543,7 +553,7 @@ export const ModelForm = forwardRef<ModelFormRef, ModelFormProps>(({ onSuccess,
           variant={testResult?.state === "success" ? "default" : testResult?.state === "error" ? "destructive" : "outline"}
           disabled={!selectedManifest || !selectedType || selectedType === "embedding"}
           onClick={() => {
-            if (testRequestStatus === "queued" || testRequestStatus === "streaming") {
+            if (isActiveInferenceStatus(testRequestStatus)) {
               if (testRequestId) {
                 cancelRequest(testRequestId);
                 setTestRequestId(null);
@@ -554,7 +564,7 @@ export const ModelForm = forwardRef<ModelFormRef, ModelFormProps>(({ onSuccess,
           }}
           className={`w-full ${testResult?.state === "success" ? "bg-green-600 hover:bg-green-700 text-white" : ""}`}
         >
-          {testRequestStatus === "queued" || testRequestStatus === "streaming" ? (
+          {isActiveInferenceStatus(testRequestStatus) ? (
             <>
               <Loader2 className="mr-2 h-4 w-4 animate-spin" />
               <span>Cancel Test Request</span>
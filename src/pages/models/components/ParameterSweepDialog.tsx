@@ -0,0 +1,159 @@
+import { useEffect, useState } from "react";
+import { LuCircleStop, LuPlay } from "react-icons/lu";
+import { toast } from "sonner";
+import { Dialog, DialogBody, DialogContent, DialogFooter, DialogHeader, DialogTitle } from "@/components/shared/Dialog";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import { StepButton } from "@/components/ui/step-button";
+import { Textarea } from "@/components/ui/textarea";
+import { useModelManifests } from "@/hooks/manifestStore";
+import { useParameterSweep } from "@/hooks/useParameterSweep";
+import type { ModelSpecs } from "@/schema/inference-engine-schema";
+import type { Model } from "@/schema/models-schema";
+import { manifestSupportsReasoning } from "@/services/ai-providers/aisdk/reasoning-support";
+import { parseSweepValues, type SweepResult } from "@/services/inference/parameter-sweep";
+
+interface ParameterSweepDialogProps {
+  model: Model | null;
+  open: boolean;
+  onOpenChange: (open: boolean) => void;
+}
+
+// Sends one prompt at several values of a sampler parameter and shows the outputs side by side
+export function ParameterSweepDialog({ model, open, onOpenChange }: ParameterSweepDialogProps) {
+  const manifests = useModelManifests();
+  const { runSweep, cancelSweep } = useParameterSweep();
+
+  const [prompt, setPrompt] = useState("");
+  const [parameter, setParameter] = useState("temperature");
+  const [values, setValues] = useState("0.2, 0.7, 1.2");
+  const [repetitions, setRepetitions] = useState(1);
+  const [sweepId, setSweepId] = useState<string | null>(null);
+  const [progress, setProgress] = useState({ completed: 0, total: 0 });
+  const [result, setResult] = useState<SweepResult | null>(null);
+
+  useEffect(() => {
+    if (open) {
+      setResult(null);
+      setProgress({ completed: 0, total: 0 });
+    }
+  }, [open]);
+
+  const handleRun = async () => {
+    const manifest = model ? manifests.find((entry) => entry.id === model.manifest_id) : undefined;
+    if (!model || !manifest) {
+      toast.error("The model's provider isn't available");
+      return;
+    }
+
+    const modelSpecs: ModelSpecs = {
+      id: model.id,
+      model_type: model.inference_template_id ? "completion" : "chat",
+      config: model.config,
+      max_concurrent_requests: model.max_concurrency,
+      engine: manifest.engine,
+      supports_reasoning: manifestSupportsReasoning(manifest),
+      inference_fields: manifest.inference_fields,
+      capabilities: model.capabilities,
+    };
+
+    try {
+      const sweep = runSweep({
+        modelSpecs,
+        messages: [{ role: "user", text: prompt }],
+        axis: { parameter, values: parseSweepValues(values) },
+        repetitions,
+        onProgress: (_, completed, total) => setProgress({ completed, total }),
+      });
+      setResult(null);
+      setSweepId(sweep.sweepId);
+      setResult(await sweep.result);
+    } catch (error) {
+      console.error("Parameter sweep failed:", error);
+      toast.error("Parameter sweep failed", { description: error instanceof Error ? error.message : String(error) });
+    } finally {
+      setSweepId(null);
+    }
+  };
+
+  const handleCancel = async () => {
+    if (!sweepId) {
+      return;
+    }
+    try {
+      await cancelSweep(sweepId);
+    } catch (error) {
+      console.error("Failed to cancel the sweep:", error);
+      toast.error("Failed to cancel the sweep", { description: error instanceof Error ? error.message : String(error) });
+    }
+  };
+
+  return (
+    <Dialog open={open} onOpenChange={(next) => !sweepId && onOpenChange(next)}>
+      <DialogContent>
+        <DialogHeader>
+          <DialogTitle>Compare parameters{model ? ` for ${model.name}` : ""}</DialogTitle>
+        </DialogHeader>
+
+        <DialogBody className="space-y-4">
+          <div className="space-y-2">
+            <Label htmlFor="sweep-prompt">Prompt</Label>
+            <Textarea id="sweep-prompt" value={prompt} onChange={(e) => setPrompt(e.target.value)} rows={4} disabled={!!sweepId} />
+          </div>
+
+          <div className="grid grid-cols-[1fr_2fr_auto] items-end gap-3">
+            <div className="space-y-2">
+              <Label htmlFor="sweep-parameter">Parameter</Label>
+              <Input id="sweep-parameter" value={parameter} onChange={(e) => setParameter(e.target.value)} disabled={!!sweepId} />
+            </div>
+            <div className="space-y-2">
+              <Label htmlFor="sweep-values">Values (comma separated)</Label>
+              <Input id="sweep-values" value={values} onChange={(e) => setValues(e.target.value)} disabled={!!sweepId} />
+            </div>
+            <div className="space-y-2">
+              <Label>Repetitions</Label>
+              <StepButton min={1} max={10} step={1} value={repetitions} onValueChange={setRepetitions} />
+            </div>
+          </div>
+
+          {sweepId && (
+            <p className="text-sm text-muted-foreground">
+              {progress.completed} of {progress.total || "?"} requests done
+            </p>
+          )}
+
+          {result &&
+            Object.entries(result.results).map(([combination, members]) => (
+              <div key={combination} className="space-y-1 rounded-md border border-border p-2">
+                <h4 className="text-sm font-medium font-mono">{combination}</h4>
+                {members.map((member) => (
+                  <div key={member.repetition} className="text-sm">
+                    <p className="text-xs text-muted-foreground">
+                      #{member.repetition + 1} · {member.status} · {member.latency_ms} ms
+                      {member.output_tokens !== undefined ? ` · ${member.input_tokens ?? 0} in / ${member.output_tokens} out tokens` : ""}
+                    </p>
+                    <p className={`whitespace-pre-wrap ${member.status === "error" ? "text-destructive" : ""}`}>{member.status === "error" ? member.error : member.text}</p>
+                  </div>
+                ))}
+              </div>
+            ))}
+        </DialogBody>
+
+        <DialogFooter className="flex justify-end gap-2">
+          {sweepId ? (
+            <Button variant="outline" onClick={handleCancel}>
+              <LuCircleStop className="h-4 w-4" />
+              Stop
+            </Button>
+          ) : (
+            <Button onClick={handleRun} disabled={!prompt.trim() || !model} size="dialog">
+              <LuPlay className="h-4 w-4" />
+              Run
+            </Button>
+          )}
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+}
@@ -30,6 +30,8 @@ const ProfilePicker: React.FC = () => {
   const [isManageMode, setIsManageMode] = useState(false);
   const [showDeleteDialog, setShowDeleteDialog] = useState(false);
   const [profileToDelete, setProfileToDelete] = useState<string | null>(null);
+  // Set when the profile wants the deletion confirmed a second time
+  const [deleteConfirmation, setDeleteConfirmation] = useState<{ token: string; summary: string } | null>(null);
   const [isProfilesLoaded, setIsProfilesLoaded] = useState(false);
 
   // Memoize the getter functions with correct types
@@ -97,18 +99,25 @@ const ProfilePicker: React.FC = () => {
   const initiateProfileDelete = (id: string, e: React.MouseEvent) => {
     e.stopPropagation();
     setProfileToDelete(id);
+    setDeleteConfirmation(null);
     setShowDeleteDialog(true);
   };
 
   const confirmDeleteProfile = () => {
     if (profileToDelete) {
-      removeProfile(profileToDelete)
-        .then(() => {
+      removeProfile(profileToDelete, deleteConfirmation?.token)
+        .then((outcome) => {
+          if (outcome.status === "confirmation_required") {
+            setDeleteConfirmation({ token: outcome.token, summary: outcome.summary });
+            return;
+          }
           setProfileToDelete(null);
+          setDeleteConfirmation(null);
           setShowDeleteDialog(false);
         })
         .catch((error) => {
           console.error("Error deleting profile:", error);
+          setDeleteConfirmation(null);
           toast.error("Failed to delete profile. Please try again.");
         });
     }
@@ -199,11 +208,22 @@ const ProfilePicker: React.FC = () => {
         <AlertDialogContent>
           <AlertDialogHeader>
             <AlertDialogTitle>Are you sure?</AlertDialogTitle>
-            <AlertDialogDescription>This will permanently delete this profile and all associated data. This action cannot be undone.</AlertDialogDescription>
+            <AlertDialogDescription>
+              {deleteConfirmation
+                ? `This will permanently delete the ${deleteConfirmation.summary}. Confirm again to go ahead.`
+                : "This will permanently delete this profile and all associated data. This action cannot be undone."}
+            </AlertDialogDescription>
           </AlertDialogHeader>
           <AlertDialogFooter>
             <AlertDialogCancel>Cancel</AlertDialogCancel>
-            <AlertDialogAction onClick={confirmDeleteProfile}>Delete Profile</AlertDialogAction>
+            <AlertDialogAction
+              onClick={(e) => {
+                e.preventDefault();
+                confirmDeleteProfile();
+              }}
+            >
+              {deleteConfirmation ? "Delete Permanently" : "Delete Profile"}
+            </AlertDialogAction>
           </AlertDialogFooter>
         </AlertDialogContent>
       </AlertDialog>
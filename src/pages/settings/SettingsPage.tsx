@@ -8,6 +8,7 @@ import { defaultSettings } from "@/schema/default-settings";
 import { AppSettings } from "@/schema/profiles-schema";
 import { updateProfileSettings } from "@/services/profile-service";
 import { AppearanceSection } from "./components/AppearanceSection";
+import { BudgetSection } from "./components/BudgetSection";
 import { ChatSection } from "./components/ChatSection";
 import { GeneralSection } from "./components/GeneralSection";
 import { ProfileSection } from "./components/ProfileSection";
@@ -182,6 +183,8 @@ export default function Settings() {
 
           <AppearanceSection settings={settings} onSettingChange={handleSettingChange} />
 
+          <BudgetSection settings={settings} onSettingChange={handleSettingChange} />
+
           <SystemSection settings={settings} onSettingChange={handleSettingChange} />
         </div>
 
@@ -0,0 +1,152 @@
+import { CalendarDays, Coins, Hash, ShieldAlert, Wallet } from "lucide-react";
+import React, { useEffect, useState } from "react";
+import { Input } from "@/components/ui/input";
+import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from "@/components/ui/select";
+import { Separator } from "@/components/ui/separator";
+import { useCurrentProfile } from "@/hooks/ProfileStore";
+import { AppSettings, BudgetSettings } from "@/schema/profiles-schema";
+import { type BudgetStatus, type BudgetUsage, evaluateBudget, getDailyUsage, getMonthlyUsage } from "@/services/inference/budget";
+import { SettingItem, SettingSection } from "./ui/setting-section";
+
+// Empty, zero or invalid means no limit
+function parseLimit(value: string, integer = false): number | null {
+  const limit = integer ? Math.floor(Number(value.trim())) : Number(value.trim());
+  return value.trim() && Number.isFinite(limit) && limit > 0 ? limit : null;
+}
+
+function describeBudgetStatus(status: BudgetStatus): string {
+  const parts = [
+    `${status.tokensUsed.toLocaleString()}${status.tokenLimit ? ` / ${status.tokenLimit.toLocaleString()}` : ""} tokens`,
+    `$${status.costUsed.toFixed(2)}${status.costLimit ? ` / $${status.costLimit.toFixed(2)}` : ""}`,
+    `${status.requestCount} ${status.requestCount === 1 ? "request" : "requests"}`,
+  ];
+  if (status.state === "exceeded") {
+    parts.push(status.action === "block" ? "limit reached, paid requests blocked" : "limit reached");
+  } else if (status.state === "warning") {
+    parts.push(`${Math.floor((status.usedRatio ?? 0) * 100)}% used`);
+  }
+  return parts.join(" · ");
+}
+
+/**
+ * Props for the BudgetSection component.
+ */
+interface BudgetSectionProps {
+  settings: AppSettings;
+  onSettingChange: (section: keyof AppSettings, key: string, value: unknown) => void;
+}
+
+/**
+ * Monthly and daily API budget of the profile, with this month's and today's usage.
+ */
+export const BudgetSection: React.FC<BudgetSectionProps> = ({ settings, onSettingChange }) => {
+  const currentProfile = useCurrentProfile();
+  const [usage, setUsage] = useState<{ month: BudgetUsage; day: BudgetUsage } | null>(null);
+  const budget: Partial<BudgetSettings> = settings.budget ?? {};
+
+  useEffect(() => {
+    if (!currentProfile) {
+      return;
+    }
+    Promise.all([getMonthlyUsage(currentProfile.id), getDailyUsage(currentProfile.id)])
+      .then(([month, day]) => setUsage({ month, day }))
+      .catch((error) => console.error("Failed to load the budget usage:", error));
+  }, [currentProfile]);
+
+  const status = currentProfile && usage ? evaluateBudget(currentProfile.id, usage.month, budget) : null;
+  const dailyStatus = currentProfile && usage ? evaluateBudget(currentProfile.id, usage.day, budget) : null;
+
+  return (
+    <SettingSection title="Budget">
+      <SettingItem icon={<Coins className="w-4 h-4" />} label="Monthly cost limit ($)" htmlFor="budget-cost-limit">
+        <Input
+          id="budget-cost-limit"
+          key={`cost-${budget.monthlyCostLimit}`}
+          className="h-8 w-32 text-xs"
+          type="number"
+          min={0}
+          step="0.01"
+          placeholder="Unlimited"
+          defaultValue={budget.monthlyCostLimit ?? ""}
+          onBlur={(e) => onSettingChange("budget", "monthlyCostLimit", parseLimit(e.target.value))}
+        />
+      </SettingItem>
+
+      <Separator />
+
+      <SettingItem icon={<Hash className="w-4 h-4" />} label="Monthly token limit" htmlFor="budget-token-limit">
+        <Input
+          id="budget-token-limit"
+          key={`tokens-${budget.monthlyTokenLimit}`}
+          className="h-8 w-32 text-xs"
+          type="number"
+          min={0}
+          step="1000"
+          placeholder="Unlimited"
+          defaultValue={budget.monthlyTokenLimit ?? ""}
+          onBlur={(e) => onSettingChange("budget", "monthlyTokenLimit", parseLimit(e.target.value, true))}
+        />
+      </SettingItem>
+
+      <Separator />
+
+      <SettingItem icon={<Coins className="w-4 h-4" />} label="Daily cost limit ($)" htmlFor="budget-daily-cost-limit">
+        <Input
+          id="budget-daily-cost-limit"
+          key={`daily-cost-${budget.dailyCostLimit}`}
+          className="h-8 w-32 text-xs"
+          type="number"
+          min={0}
+          step="0.01"
+          placeholder="Unlimited"
+          defaultValue={budget.dailyCostLimit ?? ""}
+          onBlur={(e) => onSettingChange("budget", "dailyCostLimit", parseLimit(e.target.value))}
+        />
+      </SettingItem>
+
+      <Separator />
+
+      <SettingItem icon={<Hash className="w-4 h-4" />} label="Daily token limit" htmlFor="budget-daily-token-limit">
+        <Input
+          id="budget-daily-token-limit"
+          key={`daily-tokens-${budget.dailyTokenLimit}`}
+          className="h-8 w-32 text-xs"
+          type="number"
+          min={0}
+          step="1000"
+          placeholder="Unlimited"
+          defaultValue={budget.dailyTokenLimit ?? ""}
+          onBlur={(e) => onSettingChange("budget", "dailyTokenLimit", parseLimit(e.target.value, true))}
+        />
+      </SettingItem>
+
+      <Separator />
+
+      <SettingItem icon={<ShieldAlert className="w-4 h-4" />} label="When a limit is reached">
+        <Select value={budget.action ?? "warn"} onValueChange={(value) => onSettingChange("budget", "action", value)}>
+          <SelectTrigger className="w-48">
+            <SelectValue />
+          </SelectTrigger>
+          <SelectContent>
+            <SelectItem value="warn">Warn and keep going</SelectItem>
+            <SelectItem value="block">Block paid requests</SelectItem>
+          </SelectContent>
+        </Select>
+      </SettingItem>
+
+      <Separator />
+
+      <SettingItem icon={<Wallet className="w-4 h-4" />} label="This month">
+        <span className={`text-xs ${status?.state === "exceeded" ? "text-destructive" : "text-muted-foreground"}`}>{status ? describeBudgetStatus(status) : "..."}</span>
+      </SettingItem>
+      <SettingItem icon={<CalendarDays className="w-4 h-4" />} label="Today (UTC)">
+        <span className={`text-xs ${dailyStatus?.state === "exceeded" ? "text-destructive" : "text-muted-foreground"}`}>
+          {dailyStatus ? describeBudgetStatus(dailyStatus) : "..."}
+        </span>
+      </SettingItem>
+      <p className="text-xs text-muted-foreground">
+        Local engines like Ollama don't count. Cost is only tracked for models with input and output prices set in their configuration.
+      </p>
+    </SettingSection>
+  );
+};
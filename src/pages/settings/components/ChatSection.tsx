@@ -1,12 +1,22 @@
 import { Howl } from "howler";
 import React, { useCallback, useRef } from "react";
-import { LuBot, LuCircleUser, LuHighlighter, LuKeyboard, LuMessageSquare, LuPlay } from "react-icons/lu";
+import { LuBot, LuCalendar, LuCircleUser, LuHighlighter, LuKeyboard, LuMessageSquare, LuPlay, LuRuler } from "react-icons/lu";
 import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
 import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from "@/components/ui/select";
 import { Separator } from "@/components/ui/separator";
 import { StepButton } from "@/components/ui/step-button";
 import { Switch } from "@/components/ui/switch";
-import type { AppSettings, BeepSound, DelimiterHighlighting } from "@/schema/profiles-schema";
+import {
+  type AppSettings,
+  type BeepSound,
+  type DateTimeMacroSettings,
+  DateTimeMacroSettingsSchema,
+  type DelimiterHighlighting,
+  type ResponseLengthPresets,
+  ResponseLengthPresetsSchema,
+} from "@/schema/profiles-schema";
+import { RESPONSE_LENGTHS } from "@/services/inference/response-length";
 import { SettingCollapsible, SettingItem, SettingSection } from "./ui/setting-section";
 
 interface ChatSectionProps {
@@ -87,6 +97,8 @@ export const ChatSection: React.FC<ChatSectionProps> = ({ settings, onSettingCha
     typeof settings.chat.avatarBorderRadius === "number" && !Number.isNaN(settings.chat.avatarBorderRadius) ? Math.min(50, Math.max(0, settings.chat.avatarBorderRadius)) : 50;
 
   const delimiterHighlighting: DelimiterHighlighting = settings.appearance.delimiterHighlighting ?? DEFAULT_DELIMITER_HIGHLIGHTING;
+  const responseLengthPresets: ResponseLengthPresets = ResponseLengthPresetsSchema.parse(settings.chat.responseLengthPresets ?? {});
+  const dateTimeMacros: DateTimeMacroSettings = DateTimeMacroSettingsSchema.parse(settings.chat.dateTimeMacros ?? {});
 
   return (
     <SettingSection title="Chat / Messages">
@@ -143,6 +155,96 @@ export const ChatSection: React.FC<ChatSectionProps> = ({ settings, onSettingCha
 
       <Separator />
 
+      <SettingCollapsible icon={<LuRuler className="w-4 h-4" />} label="Response Length Presets">
+        {RESPONSE_LENGTHS.map((preset) => (
+          <React.Fragment key={preset}>
+            <Separator />
+            <SettingItem label={preset} labelClassName="capitalize">
+              <div className="flex items-center gap-2">
+                <Input
+                  key={`${preset}-${responseLengthPresets[preset].instruction}`}
+                  className="h-8 w-64 text-xs"
+                  placeholder="Instruction added to the system prompt"
+                  defaultValue={responseLengthPresets[preset].instruction}
+                  onBlur={(e) =>
+                    onSettingChange("chat", "responseLengthPresets", {
+                      ...responseLengthPresets,
+                      [preset]: { ...responseLengthPresets[preset], instruction: e.target.value },
+                    } satisfies ResponseLengthPresets)
+                  }
+                />
+                <div title="Max tokens, 0 keeps the chat template's Response Length">
+                  <StepButton
+                    className="w-28"
+                    min={0}
+                    max={99999}
+                    step={50}
+                    value={responseLengthPresets[preset].max_tokens}
+                    onValueChange={(value) =>
+                      onSettingChange("chat", "responseLengthPresets", {
+                        ...responseLengthPresets,
+                        [preset]: { ...responseLengthPresets[preset], max_tokens: value },
+                      } satisfies ResponseLengthPresets)
+                    }
+                  />
+                </div>
+              </div>
+            </SettingItem>
+          </React.Fragment>
+        ))}
+      </SettingCollapsible>
+
+      <Separator />
+
+      <SettingCollapsible icon={<LuCalendar className="w-4 h-4" />} label="Date & Time Macros">
+        <Separator />
+        <SettingItem label="Locale" htmlFor="datetime-locale">
+          <Input
+            id="datetime-locale"
+            key={`locale-${dateTimeMacros.locale}`}
+            className="h-8 w-48 text-xs"
+            placeholder="en-US (empty for system)"
+            defaultValue={dateTimeMacros.locale}
+            onBlur={(e) => onSettingChange("chat", "dateTimeMacros", { ...dateTimeMacros, locale: e.target.value.trim() } satisfies DateTimeMacroSettings)}
+          />
+        </SettingItem>
+        <Separator />
+        <SettingItem label="Time Zone" htmlFor="datetime-timeZone">
+          <Input
+            id="datetime-timeZone"
+            key={`timeZone-${dateTimeMacros.timeZone}`}
+            className="h-8 w-48 text-xs"
+            placeholder="System time zone"
+            defaultValue={dateTimeMacros.timeZone}
+            onBlur={(e) => onSettingChange("chat", "dateTimeMacros", { ...dateTimeMacros, timeZone: e.target.value.trim() } satisfies DateTimeMacroSettings)}
+          />
+        </SettingItem>
+        <Separator />
+        <SettingItem label="Date Format" htmlFor="datetime-dateFormat">
+          <Input
+            id="datetime-dateFormat"
+            key={`dateFormat-${dateTimeMacros.dateFormat}`}
+            className="h-8 w-48 text-xs"
+            placeholder="MMMM D, YYYY"
+            defaultValue={dateTimeMacros.dateFormat}
+            onBlur={(e) => onSettingChange("chat", "dateTimeMacros", { ...dateTimeMacros, dateFormat: e.target.value.trim() } satisfies DateTimeMacroSettings)}
+          />
+        </SettingItem>
+        <Separator />
+        <SettingItem label="Time Format" htmlFor="datetime-timeFormat">
+          <Input
+            id="datetime-timeFormat"
+            key={`timeFormat-${dateTimeMacros.timeFormat}`}
+            className="h-8 w-48 text-xs"
+            placeholder="h:mm A"
+            defaultValue={dateTimeMacros.timeFormat}
+            onBlur={(e) => onSettingChange("chat", "dateTimeMacros", { ...dateTimeMacros, timeFormat: e.target.value.trim() } satisfies DateTimeMacroSettings)}
+          />
+        </SettingItem>
+      </SettingCollapsible>
+
+      <Separator />
+
       <SettingCollapsible icon={<LuHighlighter className="w-4 h-4" />} label="Text Highlighting">
         <Separator />
         <SettingItem label={'Double Quotes "..."'} htmlFor="highlight-quote-double">
@@ -44,6 +44,8 @@ export const ProfileSection = ({ currentProfile, refreshProfiles }: ProfileSecti
   const [isAvatarDialogOpen, setIsAvatarDialogOpen] = useState(false);
   const [isLogoutDialogOpen, setIsLogoutDialogOpen] = useState(false);
   const [isDeleteDialogOpen, setIsDeleteDialogOpen] = useState(false);
+  // Set when the profile wants the deletion confirmed a second time
+  const [deleteConfirmation, setDeleteConfirmation] = useState<{ token: string; summary: string } | null>(null);
 
   useEffect(() => {
     if (currentProfile?.name) {
@@ -190,7 +192,12 @@ export const ProfileSection = ({ currentProfile, refreshProfiles }: ProfileSecti
 
     try {
       setIsDeleting(true);
-      await deleteProfileService(currentProfile.id);
+      const outcome = await deleteProfileService(currentProfile.id, deleteConfirmation?.token);
+      if (outcome.status === "confirmation_required") {
+        setDeleteConfirmation({ token: outcome.token, summary: outcome.summary });
+        return;
+      }
+      setDeleteConfirmation(null);
       await refreshProfiles();
       toast.success("Profile deleted successfully");
       logout();
@@ -198,6 +205,7 @@ export const ProfileSection = ({ currentProfile, refreshProfiles }: ProfileSecti
     } catch (error) {
       console.error("Failed to delete profile:", error);
       toast.error("Failed to delete profile.");
+      setDeleteConfirmation(null);
       setIsDeleteDialogOpen(false);
     } finally {
       setIsDeleting(false);
@@ -402,7 +410,13 @@ export const ProfileSection = ({ currentProfile, refreshProfiles }: ProfileSecti
       <Separator />
 
       <SettingItem icon={<Trash className="w-4 h-4" />} label="Delete your profile">
-        <Dialog open={isDeleteDialogOpen} onOpenChange={setIsDeleteDialogOpen}>
+        <Dialog
+          open={isDeleteDialogOpen}
+          onOpenChange={(open) => {
+            setIsDeleteDialogOpen(open);
+            setDeleteConfirmation(null);
+          }}
+        >
           <DialogTrigger asChild>
             <Button variant="destructive" className="flex items-center gap-2">
               <Trash className="w-4 h-4" />
@@ -412,7 +426,11 @@ export const ProfileSection = ({ currentProfile, refreshProfiles }: ProfileSecti
           <DialogContent className="sm:max-w-md">
             <DialogHeader>
               <DialogTitle>Delete Profile</DialogTitle>
-              <DialogDescription>Are you sure you want to delete your profile? This action cannot be undone.</DialogDescription>
+              <DialogDescription>
+                {deleteConfirmation
+                  ? `This will permanently delete the ${deleteConfirmation.summary}. Confirm again to go ahead.`
+                  : "Are you sure you want to delete your profile? This action cannot be undone."}
+              </DialogDescription>
             </DialogHeader>
             <DialogFooter className="mt-4">
               <DialogClose asChild>
@@ -425,7 +443,7 @@ export const ProfileSection = ({ currentProfile, refreshProfiles }: ProfileSecti
                   </>
                 ) : (
                   <>
-                    <Trash className="w-4 h-4" /> Delete Profile
+                    <Trash className="w-4 h-4" /> {deleteConfirmation ? "Delete Permanently" : "Delete Profile"}
                   </>
                 )}
               </Button>
@@ -1,8 +1,66 @@
-import { Download } from "lucide-react";
-import React from "react";
+import { open as openDialog, save as saveDialog } from "@tauri-apps/plugin-dialog";
+import { Activity, AppWindow, DatabaseZap, Download, FileArchive, FileSpreadsheet, FileText, FolderInput, Gauge, ImageOff, Link, MemoryStick, MessageSquareText, Monitor, PackagePlus, RefreshCw, ShieldAlert, SlidersHorizontal } from "lucide-react";
+import React, { useEffect, useState } from "react";
+import { toast } from "sonner";
+import { type AssetGcReport, cleanupOrphanedFiles, findOrphanedFiles, type OrphanInfo } from "@/commands/assets";
+import { type OrphanReport, repairOrphans } from "@/commands/database";
+import { clearTokenizerCache, exportInferenceLogs, preloadTokenizers } from "@/commands/inference";
+import { listAvailableStarterPacks, type StarterPackInfo } from "@/commands/starter";
+import { type AppStatus, createSupportBundle, getAppStatus } from "@/commands/support";
+import { resetWindowState } from "@/commands/windows";
+import { DestructiveConfirmDialog } from "@/components/shared/DestructiveConfirmDialog";
+import { Button } from "@/components/ui/button";
+import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from "@/components/ui/select";
+import { StepButton } from "@/components/ui/step-button";
+import { Switch } from "@/components/ui/switch";
+import { useConsoleStore } from "@/hooks/consoleStore";
+import { useCurrentProfile, useProfileActions } from "@/hooks/ProfileStore";
+import { trimIdleMemory } from "@/hooks/useIdleMemoryTrim";
 import { AppSettings } from "@/schema/profiles-schema";
+import { getGlobalConcurrencyState } from "@/services/inference/global-concurrency";
+import { seedStarterContent } from "@/services/starter-content-service";
 import { SettingItem, SettingSection } from "./ui/setting-section";
 
+function loadAppStatus(): Promise<AppStatus> {
+  const { active, waiting } = getGlobalConcurrencyState();
+  return getAppStatus({ active, queued: waiting });
+}
+
+function describeStarterPack(pack: StarterPackInfo): string {
+  const counts: [number, string][] = [
+    [pack.characters, "character"],
+    [pack.lorebooks, "lorebook"],
+    [pack.format_templates, "format template"],
+    [pack.quick_actions, "quick action"],
+  ];
+  const contents = counts.filter(([count]) => count > 0).map(([count, label]) => `${count} ${label}${count === 1 ? "" : "s"}`);
+  return `${pack.description} (${contents.join(", ")})`;
+}
+
+function formatMegabytes(bytes: number): string {
+  return `${(bytes / (1024 * 1024)).toFixed(1)} MB`;
+}
+
+function describeAppStatus(status: AppStatus): string {
+  const { database } = status;
+  const parts = [
+    database.reachable ? `Database v${database.version ?? "?"}/${database.latest_version}` : "Database unreachable",
+    `${status.inference.active} running, ${status.inference.queued} queued`,
+    `${status.tokenizers.filter((tokenizer) => tokenizer.loaded).length}/${status.tokenizers.length} tokenizers loaded`,
+    `${status.manifest_count} manifests`,
+  ];
+  if (!status.master_key_secure) {
+    parts.push("default master key");
+  }
+  if (status.window_state.last_recovery?.reason === "off_screen") {
+    parts.push("window moved back on screen");
+  }
+  if (status.window_state.check_error) {
+    parts.push(`window position not checked: ${status.window_state.check_error}`);
+  }
+  return parts.join(" · ");
+}
+
 /**
  * Props for the SystemSection component.
  */
@@ -14,13 +72,437 @@ interface SystemSectionProps {
 /**
  * System settings section for the settings page.
  */
-export const SystemSection: React.FC<SystemSectionProps> = () => {
+export const SystemSection: React.FC<SystemSectionProps> = ({ settings, onSettingChange }) => {
+  const [isResettingTokenizers, setIsResettingTokenizers] = useState(false);
+  const [isCreatingBundle, setIsCreatingBundle] = useState(false);
+  const [orphanReport, setOrphanReport] = useState<OrphanReport | null>(null);
+  const [isRepairingOrphans, setIsRepairingOrphans] = useState(false);
+  const [isRepairConfirmOpen, setIsRepairConfirmOpen] = useState(false);
+  const [orphanedFiles, setOrphanedFiles] = useState<OrphanInfo[] | null>(null);
+  const [fileCleanup, setFileCleanup] = useState<AssetGcReport | null>(null);
+  const [isCleaningFiles, setIsCleaningFiles] = useState(false);
+  const [isFileCleanupConfirmOpen, setIsFileCleanupConfirmOpen] = useState(false);
+  const [isResettingWindow, setIsResettingWindow] = useState(false);
+  const [appStatus, setAppStatus] = useState<AppStatus | null>(null);
+  const [starterPacks, setStarterPacks] = useState<StarterPackInfo[]>([]);
+  const [installingPack, setInstallingPack] = useState<string | null>(null);
+  const [logExportDays, setLogExportDays] = useState("30");
+  const [isExportingLog, setIsExportingLog] = useState(false);
+  const currentProfile = useCurrentProfile();
+  const { setCurrentProfile } = useProfileActions();
+  const installedPacks = settings.system.installedStarterPacks ?? [];
+
+  useEffect(() => {
+    repairOrphans(true)
+      .then(setOrphanReport)
+      .catch((error) => console.error("Failed to check database integrity:", error));
+    loadAppStatus()
+      .then(setAppStatus)
+      .catch((error) => console.error("Failed to load app status:", error));
+    listAvailableStarterPacks()
+      .then(setStarterPacks)
+      .catch((error) => console.error("Failed to list starter packs:", error));
+  }, []);
+
+  const handleRepairOrphans = async () => {
+    setIsRepairConfirmOpen(false);
+    setIsRepairingOrphans(true);
+    try {
+      setOrphanReport(await repairOrphans(false));
+    } catch (error) {
+      toast.error("Failed to clean up the database", { description: String(error) });
+    } finally {
+      setIsRepairingOrphans(false);
+    }
+  };
+
+  const handleResetWindowState = async () => {
+    setIsResettingWindow(true);
+    try {
+      await resetWindowState();
+    } catch (error) {
+      toast.error("Failed to reset the window position", { description: String(error) });
+    } finally {
+      setIsResettingWindow(false);
+    }
+  };
+
+  const handleFindOrphanedFiles = async () => {
+    if (!currentProfile) {
+      return;
+    }
+    setFileCleanup(null);
+    try {
+      setOrphanedFiles(await findOrphanedFiles(currentProfile.id));
+    } catch (error) {
+      toast.error("Failed to look for unused files", { description: String(error) });
+    }
+  };
+
+  const handleCleanupOrphanedFiles = async () => {
+    setIsFileCleanupConfirmOpen(false);
+    if (!currentProfile || !orphanedFiles?.length) {
+      return;
+    }
+
+    setIsCleaningFiles(true);
+    try {
+      setFileCleanup(await cleanupOrphanedFiles(currentProfile.id, true));
+      setOrphanedFiles(null);
+    } catch (error) {
+      toast.error("Failed to remove unused files", { description: String(error) });
+    } finally {
+      setIsCleaningFiles(false);
+    }
+  };
+
+  const handleInstallStarterPack = async (packId: string) => {
+    if (!currentProfile) {
+      return;
+    }
+    setInstallingPack(packId);
+    try {
+      const { profile } = await seedStarterContent(currentProfile.id, [packId]);
+      // Re-syncs every store, so the new characters, lorebooks and templates show up right away
+      setCurrentProfile(profile);
+    } catch (error) {
+      toast.error("Failed to install starter pack", { description: String(error) });
+    } finally {
+      setInstallingPack(null);
+    }
+  };
+
+  const handleChooseImportFolder = async () => {
+    const directory = await openDialog({ directory: true, multiple: false, title: "Folder to import character cards from" });
+    if (typeof directory === "string") {
+      onSettingChange("system", "autoImportDirectory", directory);
+    }
+  };
+
+  const handleExportInferenceLog = async (format: "csv" | "json") => {
+    if (!currentProfile) {
+      return;
+    }
+    const outputPath = await saveDialog({
+      defaultPath: `narratrix-inference-log.${format}`,
+      filters: [{ name: format.toUpperCase(), extensions: [format] }],
+    });
+    if (!outputPath) {
+      return;
+    }
+
+    setIsExportingLog(true);
+    try {
+      const days = Number(logExportDays);
+      const since = days > 0 ? new Date(Date.now() - days * 24 * 60 * 60 * 1000).toISOString() : undefined;
+      const count = await exportInferenceLogs(currentProfile.id, outputPath, format, since);
+      if (count === 0) {
+        toast.warning("No logged requests to export", { description: "Requests are only logged while logging to file is on." });
+      }
+    } catch (error) {
+      toast.error("Failed to export the inference log", { description: String(error) });
+    } finally {
+      setIsExportingLog(false);
+    }
+  };
+
+  const handleResetTokenizers = async () => {
+    setIsResettingTokenizers(true);
+    try {
+      await clearTokenizerCache();
+      await preloadTokenizers();
+    } catch (error) {
+      toast.error("Failed to reset tokenizer cache", { description: String(error) });
+    } finally {
+      setIsResettingTokenizers(false);
+    }
+  };
+
+  const handleCreateSupportBundle = async () => {
+    if (!currentProfile) {
+      return;
+    }
+    const outputPath = await saveDialog({
+      defaultPath: "narratrix-support-bundle.zip",
+      filters: [{ name: "Zip Archives", extensions: ["zip"] }],
+    });
+    if (!outputPath) {
+      return;
+    }
+
+    setIsCreatingBundle(true);
+    try {
+      // The backend redacts secret fields and scrubs every entry before anything is written
+      const debugCaptures = useConsoleStore.getState().requests.slice(0, 5);
+      const orphans = await repairOrphans(true).catch((error) => ({ error: String(error) }));
+      const status = await loadAppStatus().catch((error) => ({ error: String(error) }));
+      await createSupportBundle(
+        outputPath,
+        currentProfile.id,
+        { logs: true, diagnostics: true, last_debug_captures: true, manifest_list: true, model_configs_redacted: true },
+        {
+          diagnostics: {
+            user_agent: navigator.userAgent,
+            language: navigator.language,
+            screen: `${window.screen.width}x${window.screen.height}`,
+            inference_file_log: settings.system.inferenceFileLog,
+            orphans,
+            status,
+          },
+          debug_captures: debugCaptures,
+        },
+      );
+    } catch (error) {
+      toast.error("Failed to create support bundle", { description: String(error) });
+    } finally {
+      setIsCreatingBundle(false);
+    }
+  };
+
   return (
     <SettingSection title="System">
       <SettingItem icon={<Download className="w-4 h-4" />} label="Updates">
         <div className="bg-destructive text-destructive-foreground text-xs px-2 py-1 rounded">Move to page Settings {">>"} Updates</div>
       </SettingItem>
 
+      <SettingItem icon={<FileText className="w-4 h-4" />} label="Log inference requests to file" htmlFor="system-inference-log">
+        <Switch
+          id="system-inference-log"
+          checked={settings.system.inferenceFileLog}
+          onCheckedChange={(checked) => onSettingChange("system", "inferenceFileLog", !!checked)}
+        />
+      </SettingItem>
+
+      <SettingItem icon={<MessageSquareText className="w-4 h-4" />} label="Include prompts in the log (to inspect past messages)" htmlFor="system-inference-log-prompts">
+        <Switch
+          id="system-inference-log-prompts"
+          checked={settings.system.inferenceLogPrompts}
+          disabled={!settings.system.inferenceFileLog}
+          onCheckedChange={(checked) => onSettingChange("system", "inferenceLogPrompts", !!checked)}
+        />
+      </SettingItem>
+
+      <SettingItem icon={<FileSpreadsheet className="w-4 h-4" />} label="Export logged requests (model, status, latency, tokens)">
+        <div className="flex items-center gap-2">
+          <Select value={logExportDays} onValueChange={setLogExportDays}>
+            <SelectTrigger className="w-32 h-8">
+              <SelectValue />
+            </SelectTrigger>
+            <SelectContent>
+              <SelectItem value="1">Last day</SelectItem>
+              <SelectItem value="7">Last 7 days</SelectItem>
+              <SelectItem value="30">Last 30 days</SelectItem>
+              <SelectItem value="0">Everything</SelectItem>
+            </SelectContent>
+          </Select>
+          <Button variant="outline" size="sm" onClick={() => handleExportInferenceLog("csv")} disabled={isExportingLog}>
+            CSV
+          </Button>
+          <Button variant="outline" size="sm" onClick={() => handleExportInferenceLog("json")} disabled={isExportingLog}>
+            JSON
+          </Button>
+        </div>
+      </SettingItem>
+
+      <SettingItem icon={<Gauge className="w-4 h-4" />} label="Max concurrent requests (all models, 0 = unlimited)">
+        <StepButton
+          className="w-24"
+          min={0}
+          max={32}
+          step={1}
+          value={settings.system.maxConcurrentRequests}
+          onValueChange={(value) => onSettingChange("system", "maxConcurrentRequests", value)}
+        />
+      </SettingItem>
+
+      <SettingItem icon={<Gauge className="w-4 h-4" />} label="Ignore a chat message sent twice within (ms, 0 = never)">
+        <StepButton
+          className="w-24"
+          min={0}
+          max={10000}
+          step={500}
+          value={settings.system.dedupeWindowMs}
+          onValueChange={(value) => onSettingChange("system", "dedupeWindowMs", value)}
+        />
+      </SettingItem>
+
+      <SettingItem icon={<AppWindow className="w-4 h-4" />} label="Show generation progress on the taskbar or dock icon" htmlFor="system-taskbar-progress">
+        <Switch id="system-taskbar-progress" checked={settings.system.taskbarProgress} onCheckedChange={(checked) => onSettingChange("system", "taskbarProgress", !!checked)} />
+      </SettingItem>
+
+      <SettingItem icon={<ShieldAlert className="w-4 h-4" />} label="Confirm chat and profile deletion a second time" htmlFor="system-require-delete-confirmation">
+        <Switch
+          id="system-require-delete-confirmation"
+          checked={settings.system.requireDeleteConfirmation}
+          onCheckedChange={(checked) => onSettingChange("system", "requireDeleteConfirmation", !!checked)}
+        />
+      </SettingItem>
+
+      <SettingItem icon={<SlidersHorizontal className="w-4 h-4" />} label="Template parameters the model's engine doesn't take">
+        <Select value={settings.system.parameterStrictness} onValueChange={(value) => onSettingChange("system", "parameterStrictness", value)}>
+          <SelectTrigger className="w-40 h-8">
+            <SelectValue />
+          </SelectTrigger>
+          <SelectContent>
+            <SelectItem value="ignore">Send silently</SelectItem>
+            <SelectItem value="warn">Send and warn</SelectItem>
+            <SelectItem value="error">Refuse the request</SelectItem>
+          </SelectContent>
+        </Select>
+      </SettingItem>
+
+      <SettingItem icon={<RefreshCw className="w-4 h-4" />} label="Tokenizer cache">
+        <Button variant="outline" size="sm" onClick={handleResetTokenizers} disabled={isResettingTokenizers}>
+          {isResettingTokenizers ? "Downloading..." : "Reset and re-download"}
+        </Button>
+      </SettingItem>
+
+      <SettingItem icon={<MemoryStick className="w-4 h-4" />} label="Free unused caches after (minutes, 0 = never)">
+        <div className="flex items-center gap-2">
+          <Button variant="outline" size="sm" onClick={() => trimIdleMemory(0)} title="Drop every cache now, they are rebuilt when needed">
+            Free now
+          </Button>
+          <StepButton
+            className="w-24"
+            min={0}
+            max={1440}
+            step={5}
+            value={settings.system.idleTrimMinutes}
+            onValueChange={(value) => onSettingChange("system", "idleTrimMinutes", value)}
+          />
+        </div>
+      </SettingItem>
+
+      <SettingItem icon={<Activity className="w-4 h-4" />} label="Status">
+        <div className="flex items-center gap-2">
+          <span className="text-xs text-muted-foreground">{appStatus ? describeAppStatus(appStatus) : "Checking..."}</span>
+          <Button variant="ghost" size="icon" className="h-7 w-7" onClick={() => loadAppStatus().then(setAppStatus).catch((error) => toast.error("Failed to load app status", { description: String(error) }))} title="Refresh status">
+            <RefreshCw className="h-3.5 w-3.5" />
+          </Button>
+        </div>
+      </SettingItem>
+
+      <SettingItem icon={<FileArchive className="w-4 h-4" />} label="Support bundle">
+        <Button variant="outline" size="sm" onClick={handleCreateSupportBundle} disabled={isCreatingBundle}>
+          {isCreatingBundle ? "Creating..." : "Export for bug report"}
+        </Button>
+      </SettingItem>
+
+      <SettingItem icon={<FolderInput className="w-4 h-4" />} label="Import character cards dropped into a folder" htmlFor="system-auto-import">
+        <div className="flex items-center gap-2">
+          <span className="text-xs text-muted-foreground max-w-48 truncate" title={settings.system.autoImportDirectory}>
+            {settings.system.autoImportDirectory || "No folder chosen"}
+          </span>
+          <Button variant="outline" size="sm" onClick={handleChooseImportFolder}>
+            Choose folder
+          </Button>
+          <Switch
+            id="system-auto-import"
+            checked={settings.system.autoImportEnabled}
+            disabled={!settings.system.autoImportDirectory}
+            onCheckedChange={(checked) => onSettingChange("system", "autoImportEnabled", !!checked)}
+          />
+        </div>
+      </SettingItem>
+
+      <SettingItem icon={<Link className="w-4 h-4" />} label="Allow plain HTTP links in Import from URL" htmlFor="system-insecure-url-imports">
+        <Switch
+          id="system-insecure-url-imports"
+          checked={settings.system.allowInsecureUrlImports}
+          onCheckedChange={(checked) => onSettingChange("system", "allowInsecureUrlImports", !!checked)}
+        />
+      </SettingItem>
+
+      {starterPacks.length > 0 && (
+        <SettingItem icon={<PackagePlus className="w-4 h-4" />} label="Starter content">
+          <div className="flex flex-col items-end gap-1">
+            {starterPacks.map((pack) => {
+              const installed = installedPacks.includes(pack.id);
+              return (
+                <div key={pack.id} className="flex items-center gap-2">
+                  <span className="text-xs text-muted-foreground" title={describeStarterPack(pack)}>
+                    {pack.name}
+                  </span>
+                  <Button variant="outline" size="sm" onClick={() => handleInstallStarterPack(pack.id)} disabled={installed || installingPack !== null}>
+                    {installed ? "Installed" : installingPack === pack.id ? "Installing..." : "Install"}
+                  </Button>
+                </div>
+              );
+            })}
+          </div>
+        </SettingItem>
+      )}
+
+      <SettingItem icon={<DatabaseZap className="w-4 h-4" />} label="Database integrity">
+        {orphanReport && orphanReport.total > 0 && orphanReport.dry_run ? (
+          <div className="flex items-center gap-2">
+            <span className="text-xs text-muted-foreground" title={orphanReport.categories.map((category) => `${category.count} ${category.label}`).join("\n")}>
+              Your database has {orphanReport.total} orphaned {orphanReport.total === 1 ? "row" : "rows"}
+            </span>
+            <Button variant="outline" size="sm" onClick={() => setIsRepairConfirmOpen(true)} disabled={isRepairingOrphans}>
+              {isRepairingOrphans ? "Cleaning up..." : "Clean up"}
+            </Button>
+          </div>
+        ) : (
+          <span className="text-xs text-muted-foreground">
+            {!orphanReport ? "Checking..." : orphanReport.dry_run ? "No orphaned rows" : `Cleaned up ${orphanReport.total} rows, backup kept next to the database`}
+          </span>
+        )}
+      </SettingItem>
+      <DestructiveConfirmDialog
+        open={isRepairConfirmOpen}
+        onOpenChange={setIsRepairConfirmOpen}
+        onConfirm={handleRepairOrphans}
+        title="Clean up the database?"
+        description={
+          <>
+            {orphanReport?.total} {orphanReport?.total === 1 ? "row points" : "rows point"} to records that no longer exist. A backup of the database is kept next to it.
+            {orphanReport?.categories.map((category) => (
+              <span key={category.category} className="mt-1 block">
+                {category.count} {category.label}, {category.action === "delete" ? "deleted" : "reference cleared"}
+              </span>
+            ))}
+          </>
+        }
+        confirmText="Clean up"
+      />
+
+      <SettingItem icon={<Monitor className="w-4 h-4" />} label="Window position">
+        <Button variant="outline" size="sm" onClick={handleResetWindowState} disabled={isResettingWindow}>
+          {isResettingWindow ? "Resetting..." : "Reset"}
+        </Button>
+      </SettingItem>
+
+      <SettingItem icon={<ImageOff className="w-4 h-4" />} label="Unused image files">
+        <div className="flex items-center gap-2">
+          <span className="text-xs text-muted-foreground" title={orphanedFiles?.map((file) => file.path).join("\n")}>
+            {fileCleanup
+              ? `Removed ${fileCleanup.removed} files, ${formatMegabytes(fileCleanup.freed_bytes)} reclaimed`
+              : orphanedFiles
+                ? orphanedFiles.length > 0
+                  ? `${orphanedFiles.length} files, ${formatMegabytes(orphanedFiles.reduce((sum, file) => sum + file.size, 0))}`
+                  : "No unused files"
+                : ""}
+          </span>
+          {orphanedFiles && orphanedFiles.length > 0 ? (
+            <Button variant="outline" size="sm" onClick={() => setIsFileCleanupConfirmOpen(true)} disabled={isCleaningFiles}>
+              {isCleaningFiles ? "Removing..." : "Remove"}
+            </Button>
+          ) : (
+            <Button variant="outline" size="sm" onClick={handleFindOrphanedFiles} disabled={!currentProfile}>
+              Check
+            </Button>
+          )}
+        </div>
+      </SettingItem>
+      <DestructiveConfirmDialog
+        open={isFileCleanupConfirmOpen}
+        onOpenChange={setIsFileCleanupConfirmOpen}
+        onConfirm={handleCleanupOrphanedFiles}
+        title="Remove unused files"
+        description={`Delete ${orphanedFiles?.length ?? 0} image files (${formatMegabytes(orphanedFiles?.reduce((sum, file) => sum + file.size, 0) ?? 0)}) that nothing in the app uses anymore? This can't be undone.`}
+      />
+
       {/* <SettingItem label="Debug Mode" htmlFor="system-debug">
         <Switch
           id="system-debug"
@@ -0,0 +1,87 @@
+import { z } from "zod";
+import { ChatMessageTypeSchema } from "./chat-message-schema";
+import { chatDisplaySettingsSchema } from "./chat-schema";
+
+export const CHARACTER_BUNDLE_VERSION = 1;
+
+/**
+ * "self" is the bundled character. Anyone else is referenced by name only and
+ * resolved against the importing profile's characters.
+ */
+const bundleCharacterRefSchema = z.union([z.literal("self"), z.object({ name: z.string() })]);
+
+const bundleExpressionSchema = z.object({
+  id: z.string(),
+  name: z.string(),
+  // Image as a data URL, null when the expression had none
+  image: z.string().nullable(),
+});
+
+const bundleMessageSchema = z.object({
+  type: ChatMessageTypeSchema,
+  character: bundleCharacterRefSchema.nullable(),
+  position: z.number().int().positive(),
+  messages: z.array(z.string()),
+  message_index: z.number().int().min(0),
+  disabled: z.boolean().default(false),
+  pinned: z.boolean().default(false),
+  tokens: z.number().int().nullable().optional(),
+  extra: z.record(z.string(), z.unknown()).nullable().optional(),
+});
+
+// Scene heading shown before the first message placed after `position`
+const bundleMarkerSchema = z.object({
+  title: z.string(),
+  position: z.number().int().min(0),
+  color: z.string().nullable().optional(),
+  context_reset: z.boolean().default(false),
+});
+
+const bundleChapterSchema = z.object({
+  title: z.string(),
+  sequence: z.number().int().positive(),
+  scenario: z.string().nullable().optional(),
+  instructions: z.string().nullable().optional(),
+  start_message: z.string().nullable().optional(),
+  custom: z.record(z.string(), z.unknown()).nullable().optional(),
+  active: z.boolean().default(false),
+  messages: z.array(bundleMessageSchema).default([]),
+  markers: z.array(bundleMarkerSchema).default([]),
+});
+
+const bundleChatSchema = z.object({
+  name: z.string(),
+  participants: z
+    .array(
+      z.object({
+        character: bundleCharacterRefSchema,
+        enabled: z.boolean().default(true),
+        settings: z.record(z.string(), z.any()).default({}),
+      }),
+    )
+    .default([]),
+  user_character: bundleCharacterRefSchema.nullable().default(null),
+  settings: chatDisplaySettingsSchema.nullable().optional(),
+  synopsis: z.string().nullable().optional(),
+  chapters: z.array(bundleChapterSchema).default([]),
+});
+
+/**
+ * One character with its images, lorebook and (optionally) every chat it took part in.
+ * Ids are not kept: everything is recreated with fresh ids on import.
+ */
+export const characterBundleSchema = z.object({
+  export_type: z.literal("character_bundle"),
+  bundle_version: z.literal(CHARACTER_BUNDLE_VERSION),
+  app_version: z.string().optional(),
+  // Validated against CreateCharacterSchema on import, like a character JSON export
+  character: z.record(z.string(), z.unknown()),
+  avatar: z.string().nullable().default(null),
+  expressions: z.array(bundleExpressionSchema).default([]),
+  lorebook: z.unknown().nullable().optional(),
+  chats: z.array(bundleChatSchema).default([]),
+});
+
+export type BundleCharacterRef = z.infer<typeof bundleCharacterRefSchema>;
+export type BundleChat = z.infer<typeof bundleChatSchema>;
+export type CharacterBundle = z.infer<typeof characterBundleSchema>;
@@ -54,6 +54,7 @@ const BaseCharacterTypeSchema = z.object({
     .default("1.0.0"),
   external_update_link: z.string().url().nullable(),
   auto_update: z.boolean().default(true),
+  favorite: z.boolean().default(false),
   system_override: z.string().nullable(),
   settings: z
     .object({
@@ -61,6 +62,10 @@ const BaseCharacterTypeSchema = z.object({
     })
     .nullable()
     .default({}),
+  // Character card metadata. character_version is the card's own free-form version, unlike `version`
+  creator: z.string().nullable().default(null),
+  character_version: z.string().nullable().default(null),
+  creator_notes: z.string().nullable().default(null),
   created_at: z.coerce.date(),
   updated_at: z.coerce.date(),
 });
@@ -91,6 +96,9 @@ export const CreateCharacterSchema = CharacterSchema.omit({
   system_override: true,
   settings: true,
   custom: true,
+  creator: true,
+  character_version: true,
+  creator_notes: true,
 });
 
 // Update schemas (all fields optional)
@@ -0,0 +1,33 @@
+import { z } from "zod";
+import { uuidUtils } from "./utils-schema";
+
+/**
+ * Chat Marker Schema
+ * A scene/chapter heading shown between messages. Markers are never sent to the model.
+ *
+ * - position: the marker sits after the chapter's message with this position, 0 = before the first one
+ * - context_reset: the prompt only includes the messages after the latest such marker
+ */
+export const chatMarkerSchema = z.object({
+  id: uuidUtils.uuid(),
+  chat_id: uuidUtils.uuid(),
+  chapter_id: uuidUtils.uuid(),
+  title: z.string().trim().min(1).max(200),
+  position: z.number().int().min(0),
+  color: z.string().nullable().default(null),
+  context_reset: z.boolean().default(false),
+  created_at: z.date(),
+  updated_at: z.date(),
+});
+
+export const createChatMarkerSchema = chatMarkerSchema.omit({
+  id: true,
+  created_at: true,
+  updated_at: true,
+});
+
+export const updateChatMarkerSchema = createChatMarkerSchema.omit({ chat_id: true, chapter_id: true }).partial();
+
+export type ChatMarker = z.infer<typeof chatMarkerSchema>;
+export type CreateChatMarkerParams = z.input<typeof createChatMarkerSchema>;
+export type UpdateChatMarkerParams = z.input<typeof updateChatMarkerSchema>;
@@ -9,6 +9,8 @@ const functionSuggestionList: SuggestionItem[] = [
   { title: "roll:2d6", description: "Roll two d6 dice", type: "function" },
   { title: "roll:1d100", description: "Roll a percentile die", type: "function" },
   { title: "roll:3d6+2", description: "Roll 3d6 with +2 modifier", type: "function" },
+  { title: "roll:d20 adv", description: "Roll a d20 twice, keep the higher (dis: lower)", type: "function" },
+  { title: "roll:4d6!", description: "Roll 4d6, each 6 adds another die", type: "function" },
 ];
 
 const dateTimeSuggestionList: SuggestionItem[] = [
@@ -21,6 +23,8 @@ const dateTimeSuggestionList: SuggestionItem[] = [
 
 const commentSuggestionList: SuggestionItem[] = [{ title: "// comment text", description: "Internal note (removed from output)", type: "function" }];
 
+const noteSuggestionList: SuggestionItem[] = [{ title: "note:title", description: "Content of the profile note with that title", type: "function" }];
+
 export const basicPromptSuggestionList: SuggestionItem[] = [
   { title: "user", description: "User Character/Profile Name", section: "prompt" },
   { title: "char", description: "Character Name.", section: "prompt" },
@@ -31,6 +35,7 @@ export const basicPromptSuggestionList: SuggestionItem[] = [
   ...functionSuggestionList.map((item) => ({ ...item, section: "function" as const })),
   ...dateTimeSuggestionList.map((item) => ({ ...item, section: "function" as const })),
   ...commentSuggestionList.map((item) => ({ ...item, section: "function" as const })),
+  ...noteSuggestionList.map((item) => ({ ...item, section: "function" as const })),
 ];
 
 export const promptReplacementSuggestionList: SuggestionItem[] = [
@@ -38,6 +43,7 @@ export const promptReplacementSuggestionList: SuggestionItem[] = [
   { title: "character.expression", description: "Character latest expression", section: "prompt" },
   { title: "chapter.scenario", section: "prompt" },
   { title: "chapter.title", section: "prompt" },
+  { title: "synopsis", description: "Rolling synopsis of the chat", section: "prompt" },
   { title: "lorebook.top", section: "prompt" },
   { title: "lorebook.bottom", section: "prompt" },
 ];
@@ -59,17 +65,61 @@ export const promptConfigSchema = z.object({
 
 export type PromptConfig = z.infer<typeof promptConfigSchema>;
 
-const extraSchema = z.object({
-  script: z.enum(["agent", "summary", "start_chapter"]).optional(),
-  name: z.string().optional(),
-  startPosition: z.number().int().optional(),
-  endPosition: z.number().int().optional(),
-  agentId: z.string().optional(),
-  promptConfig: promptConfigSchema.optional(),
-  triggerContext: z.record(z.string(), z.unknown()).optional(),
-  executionId: z.string().optional(),
+// Model that produced a message variant, kept so regenerations with other models can be labelled
+export const variantModelSchema = z.object({
+  model_id: z.string(),
+  model_name: z.string().optional(),
+  actual_model: z.string().optional(),
+  // Request that generated the variant, to find its prompt again
+  request_id: z.string().optional(),
+});
+
+export type VariantModel = z.infer<typeof variantModelSchema>;
+
+// Text the model actually saw or wrote when the displayed variant is a translation
+export const messageTranslationSchema = z.object({
+  text: z.string(),
+  language: z.string(),
 });
 
+export type MessageTranslation = z.infer<typeof messageTranslationSchema>;
+
+// Matches `DiceRoll` in services/inference/formatter/dice.ts
+const diceRollSchema = z.object({
+  expression: z.string(),
+  rolls: z.array(z.number().int()),
+  discarded: z.array(z.number().int()).optional(),
+  modifier: z.number().int(),
+  total: z.number().int(),
+});
+
+// Passthrough: annotation keys other than the well-known ones are free-form and must survive a parse
+const extraSchema = z
+  .object({
+    script: z.enum(["agent", "summary", "start_chapter"]).optional(),
+    name: z.string().optional(),
+    startPosition: z.number().int().optional(),
+    endPosition: z.number().int().optional(),
+    agentId: z.string().optional(),
+    promptConfig: promptConfigSchema.optional(),
+    triggerContext: z.record(z.string(), z.unknown()).optional(),
+    executionId: z.string().optional(),
+    // Aligned with `messages`, null for variants that were not generated (user edits, imports)
+    variantModels: z.array(variantModelSchema.nullable()).optional(),
+    // Aligned with `messages`, null for variants shown untranslated
+    translations: z.array(messageTranslationSchema.nullable()).optional(),
+    // Aligned with `messages`: why generation of each variant ended (stop, length, content-filter...)
+    finishReasons: z.array(z.string().nullable()).optional(),
+    // Annotations, usually set in bulk with updateMessagesMetadataBulk.
+    // Excluded messages stay in the chat but never reach a prompt (e.g. OOC talk)
+    exclude_from_context: z.boolean().optional(),
+    // Marks a key plot event
+    key_event: z.boolean().optional(),
+    // Dice rolled when the message was sent ({{roll:...}} or a quick action), in order
+    dice_rolls: z.array(diceRollSchema).optional(),
+  })
+  .passthrough();
+
 /**
  * Chat Message Schema
  */
@@ -83,6 +133,8 @@ export const chatMessageSchema = z.object({
   messages: z.array(z.string()), // JSON Array of messages ["message", "message", "message"]
   message_index: z.number().int().min(0), // Use 0, 1, 2, 3... Refer to which message in the messages array this is
   disabled: z.boolean().optional().default(false),
+  // Pinned messages are never trimmed by the context limit
+  pinned: z.boolean().optional().default(false),
   tokens: z.number().int().nullable().optional(),
   extra: extraSchema.optional().nullable().default({}),
   created_at: z.date(),
@@ -102,6 +154,7 @@ export const updateChatMessageSchema = chatMessageSchema.partial().pick({
   messages: true,
   message_index: true,
   disabled: true,
+  pinned: true,
   tokens: true,
   extra: true,
   character_id: true,
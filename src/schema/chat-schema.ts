@@ -24,9 +24,40 @@ const chatParticipantSchema = z.object({
   settings: z.record(z.string(), z.any()).default({}),
 });
 
+// Outgoing translates user messages into the model language, incoming translates replies back
+const chatTranslationSettingsSchema = z.object({
+  enabled: z.boolean().default(false),
+  target_language: z.string().default("English"),
+  user_language: z.string().default(""),
+  direction: z.enum(["outgoing", "incoming", "both"]).default("both"),
+  // Empty uses the chat template's model
+  model_id: z.string().default(""),
+});
+
+// Rolling synopsis, see services/inference/synopsis.ts
+const chatSynopsisSettingsSchema = z.object({
+  // Refresh on its own once refresh_every messages were added since the last refresh
+  auto_refresh: z.boolean().default(false),
+  refresh_every: z.number().int().min(1).default(20),
+  // Empty uses the chat template's model
+  model_id: z.string().default(""),
+});
+
 const chatDisplaySettingsSchema = z.object({
   hideDisabledMessages: z.boolean().default(false),
   hideScriptMessages: z.boolean().default(false),
+  translation: chatTranslationSettingsSchema.optional(),
+  synopsis: chatSynopsisSettingsSchema.optional(),
+  // Locked system prompt rules, see services/inference/system-prompt-lock.ts
+  system_prompt_locked: z.boolean().optional(),
+  system_prompt_hash: z.string().optional(),
+  // Rules text the hash was taken from, to tell what changed
+  system_prompt_baseline: z.string().optional(),
+  // Refuse to generate on drift instead of only reporting it
+  system_prompt_lock_strict: z.boolean().optional(),
+  // Output guard, see services/inference/output-guard.ts
+  strip_name_prefix: z.boolean().optional(),
+  stop_on_user_impersonation: z.boolean().optional(),
 });
 
 const chatUserSettingsSchema = z.object({
@@ -47,6 +78,14 @@ const chatSchema = z.object({
   user_character_id: z.string().nullable().optional(),
   user_character_settings: chatUserSettingsSchema.array().default([]).optional(),
   settings: chatDisplaySettingsSchema.optional().nullable(),
+  favorite: z.boolean().default(false).optional(),
+  // Rolling summary of the whole chat, for {{synopsis}}
+  synopsis: z.string().nullable().optional(),
+  // rowid of the last message folded into the synopsis
+  synopsis_up_to_sequence: z.number().int().optional(),
+  synopsis_updated_at: z.date().nullable().optional(),
+  // Messages after the profile's read position, only filled by chat listings
+  unread_count: z.number().optional(),
   created_at: z.date(),
   updated_at: z.date(),
 });
@@ -56,14 +95,18 @@ const chatSchema = z.object({
  */
 const createChatSchema = chatSchema.omit({
   id: true,
+  synopsis_up_to_sequence: true,
+  synopsis_updated_at: true,
   created_at: true,
   updated_at: true,
 });
 
 export type { ChatTab, GridItem };
-export { chatDisplaySettingsSchema, chatSchema, createChatSchema };
+export { chatDisplaySettingsSchema, chatSchema, chatSynopsisSettingsSchema, chatTranslationSettingsSchema, createChatSchema };
 export type CreateChatParams = z.infer<typeof createChatSchema>;
 export type Chat = z.infer<typeof chatSchema>;
 export type ChatDisplaySettings = z.infer<typeof chatDisplaySettingsSchema>;
+export type ChatTranslationSettings = z.infer<typeof chatTranslationSettingsSchema>;
+export type ChatSynopsisSettings = z.infer<typeof chatSynopsisSettingsSchema>;
 export type ChatParticipant = z.infer<typeof chatParticipantSchema>;
 export type ChatUserSettings = z.infer<typeof chatUserSettingsSchema>;
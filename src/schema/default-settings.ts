@@ -43,5 +43,24 @@ export const defaultSettings = AppSettingsSchema.parse({
     expressionPackDirectory: "",
     debugMode: false,
     autoUpdate: true,
+    inferenceFileLog: false,
+    inferenceLogPrompts: false,
+    maxConcurrentRequests: 0,
+    dedupeWindowMs: 2000,
+    taskbarProgress: true,
+    requireDeleteConfirmation: false,
+    parameterStrictness: "warn",
+    installedStarterPacks: [],
+    autoImportEnabled: false,
+    autoImportDirectory: "",
+    allowInsecureUrlImports: false,
+    idleTrimMinutes: 30,
+  },
+  budget: {
+    monthlyCostLimit: null,
+    monthlyTokenLimit: null,
+    dailyCostLimit: null,
+    dailyTokenLimit: null,
+    action: "warn",
   },
 });
@@ -63,6 +63,7 @@ export const defaultPositions: GridPosition[] = [
   // { id: "memory", hidden: true, decorated: true },
   { id: "database", hidden: true, decorated: true },
   { id: "chapters", hidden: true, decorated: true },
+  { id: "synopsis", hidden: true, decorated: true },
   { id: "expressions", hidden: true, decorated: true },
   { id: "help", hidden: true, decorated: true },
 ];
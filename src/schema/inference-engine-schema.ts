@@ -1,4 +1,5 @@
 import { z } from "zod";
+import { ModelCapabilitiesSchema } from "./models-schema";
 import { uuidUtils } from "./utils-schema";
 
 // Types for inference messages
@@ -18,11 +19,20 @@ type InferenceToolCall = z.infer<typeof InferenceToolCallSchema>;
 
 type InferenceToolDefinition = z.infer<typeof InferenceToolDefinitionSchema>;
 
+// Every role a message may take; requests with any other are refused before they are queued
+const INFERENCE_MESSAGE_ROLES = ["assistant", "user", "tool"] as const;
+
+type InferenceMessageRole = (typeof INFERENCE_MESSAGE_ROLES)[number];
+
 const InferenceMessageSchema = z.object({
-  role: z.enum(["assistant", "user", "tool"]),
+  role: z.enum(INFERENCE_MESSAGE_ROLES),
   text: z.string(),
   tool_calls: z.array(InferenceToolCallSchema).optional(),
   tool_call_id: z.string().optional(),
+  // Kept by the context limit regardless of window pressure
+  pinned: z.boolean().optional(),
+  // Who speaks this turn (character, or the user's persona). Only sent with `inject_speaker_names`.
+  name: z.string().optional(),
 });
 
 type InferenceMessage = z.infer<typeof InferenceMessageSchema>;
@@ -32,9 +42,13 @@ const InferenceRequestSchema = z.object({
   id: uuidUtils.uuid(),
   message_list: z.array(InferenceMessageSchema),
   system_prompt: z.string().optional(),
+  // Few-shot examples, sent after the system prompt and before the conversation. Never trimmed.
+  examples: z.array(InferenceMessageSchema).optional(),
   parameters: z.record(z.string(), z.any()),
   stream: z.boolean(),
   tools: z.array(InferenceToolDefinitionSchema).optional(),
+  // Caller's own correlation data (UI element, retry count...), never sent to the provider
+  client_metadata: z.unknown().optional(),
 });
 
 type InferenceRequest = z.infer<typeof InferenceRequestSchema>;
@@ -44,29 +58,50 @@ const StreamingResultSchema = z.object({
   reasoning: z.string().optional(),
   full_response: z.string().optional(),
   tool_calls: z.array(InferenceToolCallSchema).optional(),
+  notices: z.array(z.string()).optional(),
+  guardrail_trace: z.unknown().optional(),
+  // Times the request was re-sent because the provider returned an empty response
+  empty_retries: z.number().optional(),
+  // Why generation ended (stop, length, content-filter...), set once the request completes
+  finish_reason: z.string().optional(),
+  // Model that produced the answer, a fallback when `fallback_from` is set
+  model_id: z.string().optional(),
+  fallback_from: z.string().optional(),
+  // Tokens of every attempt, when the provider reported them
+  usage: z.object({ inputTokens: z.number(), outputTokens: z.number() }).optional(),
+  // Cancelled responses only: time from the cancel until the provider stream closed (or the wait gave up),
+  // and whether it was seen closing. Tokens generated before the close may still be billed.
+  cancel_latency_ms: z.number().optional(),
+  stream_closed: z.boolean().optional(),
 });
 
+// Every response echoes the request's `client_metadata` as given
 const InferenceResponseSchema = z.discriminatedUnion("status", [
   z.object({
     request_id: z.string(),
+    client_metadata: z.unknown().optional(),
     status: z.literal("completed"),
     result: StreamingResultSchema,
     error: z.undefined().optional(),
   }),
   z.object({
     request_id: z.string(),
+    client_metadata: z.unknown().optional(),
     status: z.literal("streaming"),
     result: StreamingResultSchema,
     error: z.undefined().optional(),
   }),
   z.object({
     request_id: z.string(),
+    client_metadata: z.unknown().optional(),
     status: z.literal("error"),
-    result: z.undefined().optional(),
+    // Only set when something was produced before the failure, e.g. reasoning cut off before the answer
+    result: StreamingResultSchema.optional(),
     error: z.string(),
   }),
   z.object({
     request_id: z.string(),
+    client_metadata: z.unknown().optional(),
     status: z.literal("cancelled"),
     result: StreamingResultSchema.optional(),
     error: z.string().optional(),
@@ -84,6 +119,14 @@ const ModelSpecsSchema = z.object({
   config: z.record(z.string(), z.any()),
   max_concurrent_requests: z.number().int().positive(),
   engine: z.string(),
+  // From the manifest's inference_fields; undefined when unknown
+  supports_reasoning: z.boolean().optional(),
+  // The manifest's inference_fields, for the unknown parameter check; undefined skips the check
+  inference_fields: z.array(z.string()).optional(),
+  // The model's stored capabilities, checked before the request is sent
+  capabilities: ModelCapabilitiesSchema.nullable().optional(),
+  // Models to re-send the request to, in order, when this one fails before answering
+  fallback_model_ids: z.array(z.string()).optional(),
 });
 
 type ModelSpecs = z.infer<typeof ModelSpecsSchema>;
@@ -92,6 +135,7 @@ export type {
   InferenceCancelledResponse,
   InferenceCompletedResponse,
   InferenceMessage,
+  InferenceMessageRole,
   InferenceRequest,
   InferenceResponse,
   InferenceStreamingResponse,
@@ -99,4 +143,4 @@ export type {
   InferenceToolDefinition,
   ModelSpecs,
 };
-export { InferenceMessageSchema, InferenceRequestSchema, InferenceResponseSchema, InferenceToolCallSchema, InferenceToolDefinitionSchema, ModelSpecsSchema };
+export { INFERENCE_MESSAGE_ROLES, InferenceMessageSchema, InferenceRequestSchema, InferenceResponseSchema, InferenceToolCallSchema, InferenceToolDefinitionSchema, ModelSpecsSchema };
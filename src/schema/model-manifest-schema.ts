@@ -1,4 +1,5 @@
 import { z } from "zod";
+import { ModelCapabilitiesSchema } from "./models-schema";
 
 /**
  * Zod schema for manifest field structure
@@ -37,7 +38,7 @@ export const ManifestFieldSchema = z.object({
     .optional(),
 });
 
-const engineSchema = z.enum(["openai_compatible", "openai", "anthropic", "google", "runpod", "aws_bedrock", "openrouter", "ollama"]);
+const engineSchema = z.enum(["openai_compatible", "openai", "anthropic", "google", "runpod", "aws_bedrock", "openrouter", "ollama", "mock"]);
 /**
  * Zod schema representing a manifest file structure
  */
@@ -50,6 +51,10 @@ export const ManifestSchema = z.object({
   inference_type: z.array(z.string()),
   inference_fields: z.array(z.string()).optional(),
   engine: engineSchema,
+  // Runs on the user's machine, so its requests don't count against the profile budget
+  local: z.boolean().optional(),
+  // Defaults for models the provider can't describe
+  capabilities: ModelCapabilitiesSchema.omit({ source: true, refreshed_at: true }).partial().optional(),
   fields: z.array(ManifestFieldSchema),
 });
 
@@ -4,16 +4,37 @@ import { uuidUtils } from "./utils-schema";
 export const ModelTypeSchema = z.enum(["llm", "audio", "image", "embedding", "database"]);
 export type ModelType = z.infer<typeof ModelTypeSchema>;
 
+export const ModelModalitySchema = z.enum(["text", "image", "audio", "video", "file"]);
+
+// What a model accepts. Null means unknown, in which case requests are sent as they are.
+export const ModelCapabilitiesSchema = z.object({
+  context_length: z.number().int().positive().nullable().default(null),
+  supports_vision: z.boolean().nullable().default(null),
+  supports_tools: z.boolean().nullable().default(null),
+  supports_reasoning: z.boolean().nullable().default(null),
+  // Input modalities
+  modality: z.array(ModelModalitySchema).nullable().default(null),
+  // Whether the values came from the provider's model listing or only from the manifest defaults
+  source: z.enum(["provider", "manifest"]).default("manifest"),
+  refreshed_at: z.string().nullable().default(null),
+});
+
+export type ModelCapabilities = z.infer<typeof ModelCapabilitiesSchema>;
+
 export const ModelSchema = z.object({
   id: uuidUtils.uuid(),
   profile_id: uuidUtils.uuid(),
   name: z.string().min(1),
+  alias: z.string().min(1).nullable().optional(),
   type: ModelTypeSchema,
   manifest_id: z.string(),
   favorite: z.boolean().default(false),
   config: z.record(z.string(), z.any()),
   max_concurrency: z.number().min(1).max(10).default(1),
   inference_template_id: z.string().optional().nullable(),
+  capabilities: ModelCapabilitiesSchema.nullable().optional(),
+  // Models tried in order when this one's provider is unreachable or keeps failing
+  fallback_model_ids: z.array(uuidUtils.uuid()).default([]),
   created_at: z.date(),
   updated_at: z.date(),
 });
@@ -0,0 +1,40 @@
+import { z } from "zod";
+import { uuidUtils } from "./utils-schema";
+
+export const NoteSchema = z.object({
+  id: uuidUtils.uuid(),
+  profile_id: uuidUtils.uuid(),
+  // Unique per profile, also the name used by {{note:title}}
+  title: z.string().trim().min(1).max(200),
+  content: z.string().default(""),
+  tags: z.array(z.string()).default([]),
+  pinned: z.boolean().default(false),
+  // Content is stored encrypted with the same key as model API keys
+  encrypted: z.boolean().default(false),
+  created_at: z.date(),
+  updated_at: z.date(),
+});
+
+export const CreateNoteSchema = NoteSchema.omit({
+  id: true,
+  created_at: true,
+  updated_at: true,
+});
+
+export const UpdateNoteSchema = CreateNoteSchema.omit({ profile_id: true }).partial();
+
+// Notes backup file. Encrypted notes keep their ciphertext, so they only restore on an install with the same key.
+export const NotesBackupSchema = z.object({
+  export_type: z.literal("notes"),
+  notes: z.array(
+    CreateNoteSchema.omit({ profile_id: true }).extend({
+      created_at: z.string().optional(),
+      updated_at: z.string().optional(),
+    }),
+  ),
+});
+
+export type Note = z.infer<typeof NoteSchema>;
+export type CreateNoteParams = z.input<typeof CreateNoteSchema>;
+export type UpdateNoteParams = z.input<typeof UpdateNoteSchema>;
+export type NotesBackup = z.infer<typeof NotesBackupSchema>;
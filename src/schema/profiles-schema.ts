@@ -19,6 +19,30 @@ const NotificationSettingsSchema = z.object({
 
 const BeepSoundEnum = z.enum(["none", "longbeep4", "beep1", "beep2", "longbeep3"]);
 
+// max_tokens 0 keeps the chat template's Response Length
+const ResponseLengthPresetSchema = z.object({
+  max_tokens: z.coerce.number().int().min(0),
+  instruction: z.string(),
+});
+
+const ResponseLengthPresetsSchema = z.object({
+  short: ResponseLengthPresetSchema.default({ max_tokens: 200, instruction: "Respond in 1-2 sentences." }),
+  medium: ResponseLengthPresetSchema.default({ max_tokens: 500, instruction: "Respond in one or two short paragraphs." }),
+  long: ResponseLengthPresetSchema.default({ max_tokens: 1500, instruction: "Respond in detail, using several paragraphs." }),
+  unlimited: ResponseLengthPresetSchema.default({ max_tokens: 0, instruction: "" }),
+});
+
+// Overrides for the {{date}}, {{time}} and related prompt macros. Empty values use the defaults
+const DateTimeMacroSettingsSchema = z.object({
+  // BCP 47 locale for month and weekday names, empty for the system locale
+  locale: z.string().default("en-US"),
+  // IANA time zone, empty for the system time zone
+  timeZone: z.string().default(""),
+  // Token formats such as "DD/MM/YYYY" or "HH:mm"
+  dateFormat: z.string().default(""),
+  timeFormat: z.string().default(""),
+});
+
 const ChatSettingsSchema = z.object({
   timestampFormat: z.enum(["12h", "24h"]).default("12h"),
   beepSound: BeepSoundEnum.default("longbeep4"),
@@ -26,6 +50,8 @@ const ChatSettingsSchema = z.object({
   showAvatars: z.boolean().default(true),
   avatarBorderRadius: z.number().min(0).max(50).default(50),
   sendShortcut: z.enum(["Enter", "Ctrl+Enter", "Shift+Enter", "CMD+Enter"]).default("Ctrl+Enter"),
+  responseLengthPresets: ResponseLengthPresetsSchema.default({} as any),
+  dateTimeMacros: DateTimeMacroSettingsSchema.default({} as any),
 });
 
 const CensorshipSettingsSchema = z.object({
@@ -60,6 +86,38 @@ const SystemSettingsSchema = z.object({
   expressionPackDirectory: z.string().default(""),
   debugMode: z.boolean().default(false),
   autoUpdate: z.boolean().default(true),
+  inferenceFileLog: z.boolean().default(false),
+  // Also write each request's system prompt and messages to the log, so past generations can be inspected
+  inferenceLogPrompts: z.boolean().default(false),
+  // Cap on in-flight requests across all models, 0 = unlimited
+  maxConcurrentRequests: z.coerce.number().int().min(0).default(0),
+  // A chat request identical to one sent this many milliseconds earlier is dropped as a double send, 0 = never
+  dedupeWindowMs: z.coerce.number().int().min(0).default(2000),
+  // Inference progress on the taskbar or dock icon while requests run
+  taskbarProgress: z.boolean().default(true),
+  // Deleting a chat or profile asks a second time with a summary of what goes, checked by the backend
+  requireDeleteConfirmation: z.boolean().default(false),
+  // Chat template parameters the model's engine doesn't take: sent anyway, reported with a warning, or the request is refused
+  parameterStrictness: z.enum(["ignore", "warn", "error"]).default("warn"),
+  // Bundled starter packs already installed in this profile, so they aren't added twice
+  installedStarterPacks: z.array(z.string()).default([]),
+  // Import character cards dropped into this folder while the app runs
+  autoImportEnabled: z.boolean().default(false),
+  autoImportDirectory: z.string().default(""),
+  // Let "Import from URL" download over plain HTTP; HTTPS is required otherwise
+  allowInsecureUrlImports: z.boolean().default(false),
+  // Cached tokenizers, token counts and request snapshots unused for this long are dropped, 0 = never
+  idleTrimMinutes: z.coerce.number().int().min(0).default(30),
+});
+
+// Monthly and daily ceilings on paid API usage; local engines don't count. A null limit is unlimited.
+const BudgetSettingsSchema = z.object({
+  monthlyCostLimit: z.coerce.number().min(0).nullable().default(null),
+  monthlyTokenLimit: z.coerce.number().int().min(0).nullable().default(null),
+  dailyCostLimit: z.coerce.number().min(0).nullable().default(null),
+  dailyTokenLimit: z.coerce.number().int().min(0).nullable().default(null),
+  // What happens once a limit is reached: keep going with a warning, or refuse new requests
+  action: z.enum(["warn", "block"]).default("warn"),
 });
 
 /**
@@ -72,6 +130,7 @@ const AppSettingsSchema = z.object({
   censorship: CensorshipSettingsSchema.default({} as any),
   appearance: AppearanceSettingsSchema.default({} as any),
   system: SystemSettingsSchema.default({} as any),
+  budget: BudgetSettingsSchema.default({} as any),
 });
 
 // Define QuickAction schema for profile quick actions
@@ -84,6 +143,8 @@ const QuickActionSchema = z.object({
   systemPromptOverride: z.string(),
   streamOption: z.enum(["textarea", "userMessage", "participantMessage"]),
   participantMessageType: z.enum(["new", "swap"]).optional(),
+  // Dice notation rolled and posted as a system message before generating, so the model reads the result
+  rollBeforeGenerate: z.string().optional().nullable().default(null),
 });
 
 const ProfileSchema = z.object({
@@ -125,7 +186,10 @@ type QuickAction = z.infer<typeof QuickActionSchema>;
 type Profile = z.infer<typeof ProfileSchema>;
 type AppSettings = z.infer<typeof AppSettingsSchema>;
 type BeepSound = z.infer<typeof BeepSoundEnum>;
+type BudgetSettings = z.infer<typeof BudgetSettingsSchema>;
 type DelimiterHighlighting = z.infer<typeof DelimiterHighlightingSchema>;
+type DateTimeMacroSettings = z.infer<typeof DateTimeMacroSettingsSchema>;
+type ResponseLengthPresets = z.infer<typeof ResponseLengthPresetsSchema>;
 
 type ProfileResponse = Omit<Profile, "password"> & { hasPassword: boolean };
 
@@ -141,6 +205,9 @@ export {
   type AppSettings,
   AppSettingsSchema,
   type BeepSound,
+  type BudgetSettings,
+  type DateTimeMacroSettings,
+  DateTimeMacroSettingsSchema,
   type DelimiterHighlighting,
   type LoginPasswordParams,
   LoginPasswordSchema,
@@ -151,6 +218,8 @@ export {
   ProfileSchema,
   type QuickAction,
   QuickActionSchema,
+  type ResponseLengthPresets,
+  ResponseLengthPresetsSchema,
   type UpdatePasswordParams,
   UpdatePasswordSchema,
   type UpdateProfileParams,
@@ -56,10 +56,27 @@ export interface RandomNumberField extends BaseField {
   default: number;
 }
 
+/**
+ * Interface for on/off toggle fields
+ */
+export interface BooleanField extends BaseField {
+  type: "boolean";
+  default: boolean;
+}
+
+/**
+ * Interface for a choice among fixed values
+ */
+export interface SelectField extends BaseField {
+  type: "select";
+  options: { value: string; label: string }[];
+  default: string;
+}
+
 /**
  * Union type for all possible field types
  */
-export type ConfigField = NumericField | StringArrayField | DragArrayField | SectionField | RandomNumberField;
+export type ConfigField = NumericField | StringArrayField | DragArrayField | SectionField | RandomNumberField | BooleanField | SelectField;
 
 /**
  * Type for the entire configuration fields array
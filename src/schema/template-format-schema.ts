@@ -3,7 +3,7 @@ import { baseTemplateSchema } from "./template-base-schema";
 
 // Define the enum values for SystemPromptType since it's only a type in the schema
 
-const SYSTEM_PROMPT_TYPES = ["context", "chapter-context", "character-context", "user-context", "character-memory", "lorebook-top", "lorebook-bottom", "custom-field"] as const;
+const SYSTEM_PROMPT_TYPES = ["context", "chapter-context", "character-context", "user-context", "character-memory", "lorebook-top", "lorebook-bottom", "current-datetime", "custom-field"] as const;
 
 const SYSTEM_PROMPT_DEFAULT_CONTENT: Record<SystemPromptType, string> = {
   context: "You are a helpful assistant that can answer questions and help with tasks.",
@@ -13,6 +13,7 @@ const SYSTEM_PROMPT_DEFAULT_CONTENT: Record<SystemPromptType, string> = {
   "character-memory": "# Character Past Events\n{{character.name}}: {{character.memory}}",
   "lorebook-top": "{{lorebook.top}}",
   "lorebook-bottom": "{{lorebook.bottom}}",
+  "current-datetime": "# Current Date\nIt is {{weekday}}, {{date}} at {{time}} ({{timezone}}).",
   "custom-field": "",
 };
 const systemPromptTypeEnum = z.enum(SYSTEM_PROMPT_TYPES);
@@ -48,6 +49,8 @@ const templateSettingsSchema = z.object({
   apply_censorship: z.boolean().default(false),
   merge_messages_on_user: z.boolean().default(false),
   merge_subsequent_messages: z.boolean().default(true),
+  // Render macros ({{char}}, {{roll:1d20}}...) inside chat messages, not only in the system prompt and template prompts
+  substitute_message_macros: z.boolean().default(true),
 });
 
 const formatTemplateSchema = baseTemplateSchema.extend({
@@ -0,0 +1,44 @@
+import { z } from "zod";
+import { uuidUtils } from "./utils-schema";
+
+export const WebhookEventSchema = z.enum(["inference.completed", "inference.error", "chat.message_added"]);
+export type WebhookEvent = z.infer<typeof WebhookEventSchema>;
+
+export const WebhookSchema = z.object({
+  id: uuidUtils.uuid(),
+  profile_id: uuidUtils.uuid(),
+  name: z.string().min(1),
+  url: z.string().url(),
+  // Stored encrypted, never decrypted on the frontend
+  secret: z.string().nullable().optional(),
+  events: WebhookEventSchema.array().default([]),
+  enabled: z.boolean().default(false),
+  allow_insecure: z.boolean().default(false),
+  created_at: z.date(),
+  updated_at: z.date(),
+});
+
+export const CreateWebhookSchema = WebhookSchema.omit({
+  id: true,
+  created_at: true,
+  updated_at: true,
+});
+
+export const UpdateWebhookSchema = CreateWebhookSchema.omit({ profile_id: true }).partial();
+
+export const WebhookDeliverySchema = z.object({
+  id: uuidUtils.uuid(),
+  webhook_id: uuidUtils.uuid(),
+  profile_id: uuidUtils.uuid(),
+  event: WebhookEventSchema,
+  delivered: z.boolean(),
+  status_code: z.number().nullable(),
+  attempts: z.number(),
+  error: z.string().nullable(),
+  created_at: z.date(),
+});
+
+export type Webhook = z.infer<typeof WebhookSchema>;
+export type CreateWebhookParams = z.input<typeof CreateWebhookSchema>;
+export type UpdateWebhookParams = z.input<typeof UpdateWebhookSchema>;
+export type WebhookDelivery = z.infer<typeof WebhookDeliverySchema>;
@@ -47,8 +47,9 @@ export interface WorkflowDeps {
   // non-streaming inference
   runInference: (opts: {
     messages: any[];
-    modelSpecs: { id: string; model_type: "chat" | "completion"; config: any; max_concurrent_requests: number; engine: string };
+    modelSpecs: { id: string; model_type: "chat" | "completion"; config: any; max_concurrent_requests: number; engine: string; supports_reasoning?: boolean };
     systemPrompt?: string;
+    examples?: any[];
     parameters?: Record<string, any>;
     stream?: boolean;
     toolset?: WorkflowToolDefinition[];
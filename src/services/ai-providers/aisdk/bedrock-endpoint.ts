@@ -0,0 +1,45 @@
+// Where Bedrock requests go. The region is always needed (requests are signed for it), while the
+// endpoint can be the standard regional one, its FIPS variant, or a custom URL such as a
+// PrivateLink VPC endpoint.
+
+// e.g. us-east-1, eu-central-2, us-gov-west-1, ap-southeast-4
+const REGION_PATTERN = /^[a-z]{2}(-gov|-iso[a-z]?)?-[a-z]+-\d{1,2}$/;
+
+export interface BedrockConnection {
+  region: string;
+  // Undefined keeps the SDK's standard regional endpoint
+  baseURL?: string;
+}
+
+/**
+ * Region and endpoint from a Bedrock model config (`aws_region`, `endpoint_url`, `use_fips`).
+ * A custom endpoint URL takes precedence over the FIPS flag. Throws on an invalid region or URL.
+ */
+export function resolveBedrockConnection(config: Record<string, any> | undefined): BedrockConnection {
+  const region = String(config?.aws_region || "us-east-1")
+    .trim()
+    .toLowerCase();
+  if (!REGION_PATTERN.test(region)) {
+    throw new Error(`Invalid AWS region "${region}", expected something like us-east-1`);
+  }
+
+  const endpointUrl = typeof config?.endpoint_url === "string" ? config.endpoint_url.trim() : "";
+  if (endpointUrl) {
+    let url: URL;
+    try {
+      url = new URL(endpointUrl);
+    } catch {
+      throw new Error(`Invalid Bedrock endpoint URL "${endpointUrl}"`);
+    }
+    if (url.protocol !== "https:") {
+      throw new Error(`Bedrock endpoint URL must use https: "${endpointUrl}"`);
+    }
+    return { region, baseURL: endpointUrl.replace(/\/+$/, "") };
+  }
+
+  if (config?.use_fips === true || config?.use_fips === "true") {
+    return { region, baseURL: `https://bedrock-runtime-fips.${region}.amazonaws.com` };
+  }
+
+  return { region };
+}
@@ -0,0 +1,105 @@
+import type { ModelMessage } from "ai";
+
+// Remembers a rolling hash per message of the last prompt sent for each chat, so the next request
+// knows how many leading messages are unchanged. Providers with prompt caching can only reuse a cached
+// prefix, so editing an early message invalidates everything after it.
+
+// Chats whose last prompt is remembered, oldest dropped first
+const MAX_TRACKED_CHATS = 50;
+const FNV_OFFSET = 0x811c9dc5;
+const FNV_PRIME = 0x01000193;
+
+interface ChatPromptState {
+  // hashes[i] covers messages[0..i], so equal hashes mean an identical prefix
+  hashes: string[];
+  sizes: number[];
+  stableMessages: number;
+}
+
+interface CacheEfficiency {
+  // Leading messages of the last prompt that matched the prompt before it
+  stableMessages: number;
+  totalMessages: number;
+  // Share of the last prompt's characters inside the stable prefix, 0 to 1
+  ratio: number;
+}
+
+const chatPrompts = new Map<string, ChatPromptState>();
+
+function fnv1a(text: string, seed: number): number {
+  let hash = seed;
+  for (let i = 0; i < text.length; i++) {
+    hash ^= text.charCodeAt(i);
+    hash = Math.imul(hash, FNV_PRIME) >>> 0;
+  }
+  return hash;
+}
+
+// Only what reaches the provider as prompt text counts, not providerOptions such as cache markers
+function serializeMessage(message: ModelMessage): string {
+  return JSON.stringify([message.role, message.content]);
+}
+
+/**
+ * Rolling hash of every prefix of the messages: each entry hashes its message on top of the previous entry.
+ */
+function prefixHashes(messages: ModelMessage[]): string[] {
+  const hashes: string[] = [];
+  let hash = FNV_OFFSET;
+  for (const message of messages) {
+    hash = fnv1a(serializeMessage(message), hash);
+    hashes.push(hash.toString(16));
+  }
+  return hashes;
+}
+
+/**
+ * Compares the messages with the last prompt sent for the same chat and remembers them for the next call.
+ * @param chatId - The chat the prompt belongs to
+ * @param messages - The prompt, before any cache markers are added
+ * @returns How many leading messages are unchanged since the last request, 0 for the first one
+ */
+function trackStablePrefix(chatId: string, messages: ModelMessage[]): number {
+  const hashes = prefixHashes(messages);
+  const previous = chatPrompts.get(chatId)?.hashes ?? [];
+
+  let stableMessages = 0;
+  while (stableMessages < hashes.length && stableMessages < previous.length && hashes[stableMessages] === previous[stableMessages]) {
+    stableMessages++;
+  }
+
+  // Re-inserting keeps the map ordered by last use
+  chatPrompts.delete(chatId);
+  chatPrompts.set(chatId, { hashes, sizes: messages.map((message) => serializeMessage(message).length), stableMessages });
+  if (chatPrompts.size > MAX_TRACKED_CHATS) {
+    chatPrompts.delete(chatPrompts.keys().next().value!);
+  }
+
+  return stableMessages;
+}
+
+/**
+ * How much of the last prompt sent for a chat was cache-eligible, or null if none was sent this session.
+ */
+function getCacheEfficiency(chatId: string): CacheEfficiency | null {
+  const state = chatPrompts.get(chatId);
+  if (!state) {
+    return null;
+  }
+
+  const total = state.sizes.reduce((sum, size) => sum + size, 0);
+  const stable = state.sizes.slice(0, state.stableMessages).reduce((sum, size) => sum + size, 0);
+  return {
+    stableMessages: state.stableMessages,
+    totalMessages: state.sizes.length,
+    ratio: total > 0 ? stable / total : 0,
+  };
+}
+
+// Forget a chat's last prompt, e.g. when the chat is deleted
+function clearStablePrefix(chatId: string) {
+  chatPrompts.delete(chatId);
+}
+
+export type { CacheEfficiency };
+export { clearStablePrefix, getCacheEfficiency, prefixHashes, trackStablePrefix };
@@ -0,0 +1,42 @@
+import type { InferenceMessage, ModelSpecs } from "@/schema/inference-engine-schema";
+import { CAPABILITY_NOT_SUPPORTED } from "../types/ai-event.type";
+
+function modelLabel(modelSpecs: ModelSpecs): string {
+  return modelSpecs.config?.model ? ` "${modelSpecs.config.model}"` : "";
+}
+
+function rejection(message: string, code: string): Error {
+  return Object.assign(new Error(message), { code, retryable: false });
+}
+
+/**
+ * Rejects a request the model's stored capabilities say it can't handle, instead of letting the provider fail
+ * or silently drop part of it. Only flags that are known are checked: unknown (null) capabilities let the request through.
+ * @param messages - The messages as sent; user messages carry image attachments in `files`
+ * @param parameters - Flattened inference parameters, `max_context` being the chat template's context size
+ * @param hasTools - Whether tool definitions are attached
+ */
+function assertCapabilitiesSupported(modelSpecs: ModelSpecs, messages: InferenceMessage[], parameters: Record<string, any>, hasTools: boolean) {
+  const capabilities = modelSpecs.capabilities;
+  if (!capabilities) {
+    return;
+  }
+
+  if (capabilities.supports_vision === false && messages.some((message) => ((message as { files?: unknown[] }).files?.length ?? 0) > 0)) {
+    throw rejection(`The model${modelLabel(modelSpecs)} does not accept images, but the prompt has image attachments. Remove them or pick a vision model.`, CAPABILITY_NOT_SUPPORTED);
+  }
+
+  if (capabilities.supports_tools === false && hasTools) {
+    throw rejection(`The model${modelLabel(modelSpecs)} does not support tools, but the request defines some. Pick a model with tool support.`, CAPABILITY_NOT_SUPPORTED);
+  }
+
+  const maxContext = Number(parameters.max_context);
+  if (capabilities.context_length && maxContext > capabilities.context_length) {
+    throw rejection(
+      `The chat template allows a context of ${maxContext} tokens, but the model${modelLabel(modelSpecs)} only takes ${capabilities.context_length}. Lower the template's Max Context.`,
+      "context_overflow",
+    );
+  }
+}
+
+export { assertCapabilitiesSupported };
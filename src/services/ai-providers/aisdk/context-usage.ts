@@ -0,0 +1,142 @@
+import type { ModelMessage } from "ai";
+import { countTokens } from "@/commands/inference";
+import { emitToCurrentWindow, listenInCurrentWindow } from "@/commands/windows";
+
+// Warns while a long generation fills the model's context window, before the provider truncates it.
+// Usage is the prompt plus the output so far: a ~4 characters per token estimate on every delta, corrected
+// by an exact tokenizer count at most once per EXACT_COUNT_INTERVAL_MS.
+
+const CONTEXT_USAGE_EVENT = "context-usage";
+const CONTEXT_USAGE_THRESHOLDS = [0.7, 0.85, 0.95] as const;
+const EXACT_COUNT_INTERVAL_MS = 1000;
+
+interface ContextUsageEvent {
+  type: "context_usage";
+  requestId: string;
+  used: number;
+  limit: number;
+  // Highest threshold crossed, as a fraction of the limit
+  threshold: number;
+}
+
+interface ContextUsageOptions {
+  requestId: string;
+  // The model's context window, in tokens
+  limit: number;
+  promptText: string;
+  countTokens?: (text: string) => Promise<number>;
+  emit?: (event: ContextUsageEvent) => void;
+  now?: () => number;
+}
+
+interface ContextUsageTracker {
+  // Account for a streamed text delta
+  push: (text: string) => void;
+}
+
+const estimateTokenCount = (text: string) => Math.ceil(text.length / 4);
+
+const countWithTokenizer = async (text: string) => (await countTokens(text, "DEFAULT")).count;
+
+function emitContextUsage(event: ContextUsageEvent) {
+  emitToCurrentWindow(CONTEXT_USAGE_EVENT, event).catch((error) => console.error("Failed to emit context usage:", error));
+}
+
+/**
+ * The context window to track for a request: the model's declared `context_length`, unless unknown or the
+ * `context_usage_events` parameter is false
+ */
+function resolveContextUsageLimit(contextLength: number | null | undefined, parameters: Record<string, unknown>): number | undefined {
+  if (parameters.context_usage_events === false || !contextLength || contextLength <= 0) {
+    return undefined;
+  }
+  return contextLength;
+}
+
+// Text of the system prompt and every message, as sent
+function promptTextOf(system: string | undefined, messages: ModelMessage[] | undefined): string {
+  const parts = system ? [system] : [];
+  for (const message of messages ?? []) {
+    if (typeof message.content === "string") {
+      parts.push(message.content);
+      continue;
+    }
+    for (const part of message.content) {
+      if (part.type === "text" || part.type === "reasoning") {
+        parts.push(part.text);
+      }
+    }
+  }
+  return parts.join("\n");
+}
+
+function createContextUsageTracker({
+  requestId,
+  limit,
+  promptText,
+  countTokens = countWithTokenizer,
+  emit = emitContextUsage,
+  now = Date.now,
+}: ContextUsageOptions): ContextUsageTracker {
+  let promptTokens = estimateTokenCount(promptText);
+  let output = "";
+  // Output tokens as of the last exact count, and how many characters that count covered
+  let countedTokens = 0;
+  let countedLength = 0;
+  let lastCountAt = Number.NEGATIVE_INFINITY;
+  let counting = false;
+  let crossed = 0;
+
+  const check = () => {
+    const used = promptTokens + countedTokens + estimateTokenCount(output.slice(countedLength));
+    const reached = CONTEXT_USAGE_THRESHOLDS.filter((threshold) => used >= threshold * limit).length;
+    if (reached > crossed) {
+      crossed = reached;
+      emit({ type: "context_usage", requestId, used, limit, threshold: CONTEXT_USAGE_THRESHOLDS[reached - 1] });
+    }
+  };
+
+  const count = (text: string, apply: (tokens: number) => void) => {
+    counting = true;
+    lastCountAt = now();
+    countTokens(text)
+      .then((tokens) => {
+        apply(tokens);
+        check();
+      })
+      .catch((error) => console.error("Failed to count tokens for context usage:", error))
+      .finally(() => {
+        counting = false;
+      });
+  };
+
+  // The prompt is counted first, the output once it's a second later
+  count(promptText, (tokens) => {
+    promptTokens = tokens;
+  });
+
+  return {
+    push: (text) => {
+      output += text;
+      if (!counting && now() - lastCountAt >= EXACT_COUNT_INTERVAL_MS) {
+        const snapshot = output;
+        count(snapshot, (tokens) => {
+          countedTokens = tokens;
+          countedLength = snapshot.length;
+        });
+      }
+      check();
+    },
+  };
+}
+
+/**
+ * Subscribe to the context usage warnings of this window's requests
+ * @returns A function that removes the listener
+ */
+function onContextUsage(callback: (event: ContextUsageEvent) => void): Promise<() => void> {
+  return listenInCurrentWindow<ContextUsageEvent>(CONTEXT_USAGE_EVENT, (event) => callback(event.payload));
+}
+
+export type { ContextUsageEvent, ContextUsageTracker };
+export { CONTEXT_USAGE_EVENT, CONTEXT_USAGE_THRESHOLDS, createContextUsageTracker, EXACT_COUNT_INTERVAL_MS, onContextUsage, promptTextOf, resolveContextUsageLimit };
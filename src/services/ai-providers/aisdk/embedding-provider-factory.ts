@@ -6,6 +6,7 @@ import type { EmbeddingModel } from "ai";
 import { createOllama } from "ai-sdk-ollama";
 import { decryptApiKey } from "@/commands/security";
 import type { ModelSpecs } from "@/schema/inference-engine-schema";
+import { resolveBedrockConnection } from "./bedrock-endpoint";
 
 async function getEmbeddingModel(modelProvider: ModelSpecs): Promise<EmbeddingModel> {
   const engineName = modelProvider.engine;
@@ -36,10 +37,12 @@ async function getEmbeddingModel(modelProvider: ModelSpecs): Promise<EmbeddingMo
       throw new Error(`Embedding model "${modelName}" is missing AWS credentials (aws_access_key_id / aws_secret_access_key)`);
     }
     const secretKey = await decryptApiKey(authParams.aws_secret_access_key);
+    const { region, baseURL } = resolveBedrockConnection(authParams);
     const bedrock = createAmazonBedrock({
       accessKeyId: authParams.aws_access_key_id,
       secretAccessKey: secretKey,
-      region: authParams?.aws_region || "us-east-1",
+      region,
+      baseURL,
       fetch: fetchOverride,
     });
     return bedrock.embeddingModel(modelName);
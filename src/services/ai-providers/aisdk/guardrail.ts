@@ -0,0 +1,100 @@
+// Bedrock Guardrails: reads the guardrail trace from provider metadata.
+// The trace is only returned when the model config enables it, so without it we only know that a guardrail intervened.
+
+interface GuardrailSummary {
+  // Reasons the guardrail blocked the input or output (denied topics, content filters, words...)
+  blocked: string[];
+  // Notices for content the guardrail masked but let through (PII, regex matches)
+  masked: string[];
+  trace?: unknown;
+}
+
+type PolicyEntry = Record<string, any>;
+
+function entries(value: unknown): PolicyEntry[] {
+  return Array.isArray(value) ? value.filter((entry) => typeof entry === "object" && entry !== null) : [];
+}
+
+function collectAssessment(assessment: PolicyEntry, summary: GuardrailSummary) {
+  for (const topic of entries(assessment.topicPolicy?.topics)) {
+    if (topic.action === "BLOCKED") {
+      summary.blocked.push(`denied topic "${topic.name}"`);
+    }
+  }
+
+  for (const filter of entries(assessment.contentPolicy?.filters)) {
+    if (filter.action === "BLOCKED") {
+      summary.blocked.push(`content filter ${String(filter.type).toLowerCase()}${filter.confidence ? ` (${String(filter.confidence).toLowerCase()} confidence)` : ""}`);
+    }
+  }
+
+  for (const word of [...entries(assessment.wordPolicy?.customWords), ...entries(assessment.wordPolicy?.managedWordLists)]) {
+    if (word.action === "BLOCKED") {
+      summary.blocked.push(word.type ? `word list ${String(word.type).toLowerCase()}` : "blocked word");
+    }
+  }
+
+  for (const entity of entries(assessment.sensitiveInformationPolicy?.piiEntities)) {
+    const type = String(entity.type).toLowerCase();
+    if (entity.action === "BLOCKED") {
+      summary.blocked.push(`sensitive information (${type})`);
+    } else if (entity.action === "ANONYMIZED") {
+      summary.masked.push(`Masked sensitive information (${type})`);
+    }
+  }
+
+  for (const regex of entries(assessment.sensitiveInformationPolicy?.regexes)) {
+    if (regex.action === "BLOCKED") {
+      summary.blocked.push(`pattern "${regex.name}"`);
+    } else if (regex.action === "ANONYMIZED") {
+      summary.masked.push(`Masked pattern "${regex.name}"`);
+    }
+  }
+
+  for (const grounding of entries(assessment.contextualGroundingPolicy?.filters)) {
+    if (grounding.action === "BLOCKED") {
+      summary.blocked.push(`contextual grounding ${String(grounding.type).toLowerCase()}`);
+    }
+  }
+}
+
+/**
+ * Summarizes the Bedrock guardrail trace found in the provider metadata, if any.
+ */
+function summarizeGuardrail(providerMetadata: Record<string, any> | undefined): GuardrailSummary | null {
+  const guardrail = providerMetadata?.bedrock?.trace?.guardrail;
+  if (!guardrail || typeof guardrail !== "object") {
+    return null;
+  }
+
+  const summary: GuardrailSummary = { blocked: [], masked: [], trace: guardrail };
+
+  // inputAssessment maps guardrail id -> assessment, outputAssessments maps guardrail id -> assessment[]
+  for (const assessment of Object.values(guardrail.inputAssessment ?? {})) {
+    collectAssessment(assessment as PolicyEntry, summary);
+  }
+  for (const assessments of Object.values(guardrail.outputAssessments ?? {})) {
+    for (const assessment of entries(assessments)) {
+      collectAssessment(assessment, summary);
+    }
+  }
+
+  summary.blocked = [...new Set(summary.blocked)];
+  summary.masked = [...new Set(summary.masked)];
+  return summary;
+}
+
+function guardrailBlockedMessage(summary: GuardrailSummary | null): string {
+  if (!summary || summary.blocked.length === 0) {
+    return "Blocked by the model's guardrail. Enable guardrail trace on the model to see the reason.";
+  }
+  return `Blocked by the model's guardrail: ${summary.blocked.join("; ")}`;
+}
+
+// True when the guardrail only masked content, so the (masked) answer should still be delivered
+function isMaskOnlyIntervention(summary: GuardrailSummary | null): boolean {
+  return !!summary && summary.blocked.length === 0 && summary.masked.length > 0;
+}
+
+export type { GuardrailSummary };
+export { guardrailBlockedMessage, isMaskOnlyIntervention, summarizeGuardrail };
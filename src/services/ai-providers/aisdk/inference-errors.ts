@@ -0,0 +1,274 @@
+import { APICallError, RetryError } from "ai";
+import type { AIError, InferenceErrorCode } from "../types/ai-event.type";
+
+// Maps provider failures (HTTP statuses, provider error types, Bedrock exceptions, finish reasons)
+// onto InferenceErrorCode. Each provider's mapper only recognizes its own error shape.
+
+type CodeMatch = { code: InferenceErrorCode; retryable?: boolean };
+
+const SUMMARIES: Record<InferenceErrorCode, string> = {
+  auth_failed: "Authentication failed, check the API key",
+  rate_limited: "Rate limited by the provider",
+  context_overflow: "The prompt is larger than the model's context window",
+  model_not_found: "The model was not found",
+  network_unreachable: "Could not reach the endpoint",
+  timeout: "The request timed out",
+  content_filtered: "The provider's content filter blocked the answer",
+  provider_internal: "The provider had an internal error",
+  cancelled: "The request was cancelled",
+  unknown: "Inference failed",
+};
+
+const RETRYABLE_CODES = new Set<InferenceErrorCode>(["rate_limited", "network_unreachable", "timeout", "provider_internal"]);
+
+const CONTEXT_OVERFLOW_PATTERN = /context[_ ]length|context window|maximum context|prompt is too long|too many (input )?tokens|input is too long|exceeds the (maximum|max)/i;
+
+function parseBody(body: string | undefined): any {
+  if (!body) {
+    return undefined;
+  }
+  try {
+    return JSON.parse(body);
+  } catch {
+    return undefined;
+  }
+}
+
+// { "error": { "code": "context_length_exceeded", "type": "invalid_request_error", "message": ... } }
+function openAIErrorCode(body: any): CodeMatch | undefined {
+  const error = body?.error;
+  if (typeof error !== "object" || error === null || typeof error.status === "string") {
+    return undefined;
+  }
+  switch (error.code ?? error.type) {
+    case "context_length_exceeded":
+      return { code: "context_overflow" };
+    case "invalid_api_key":
+    case "invalid_organization":
+      return { code: "auth_failed" };
+    case "model_not_found":
+      return { code: "model_not_found" };
+    case "rate_limit_exceeded":
+      return { code: "rate_limited" };
+    // Out of credits: waiting won't help
+    case "insufficient_quota":
+      return { code: "rate_limited", retryable: false };
+    case "server_error":
+      return { code: "provider_internal" };
+    default:
+      return undefined;
+  }
+}
+
+// { "type": "error", "error": { "type": "overloaded_error", "message": ... } }
+function anthropicErrorCode(body: any): CodeMatch | undefined {
+  if (body?.type !== "error" || typeof body.error?.type !== "string") {
+    return undefined;
+  }
+  switch (body.error.type) {
+    case "authentication_error":
+    case "permission_error":
+      return { code: "auth_failed" };
+    case "not_found_error":
+      return { code: "model_not_found" };
+    case "rate_limit_error":
+      return { code: "rate_limited" };
+    case "overloaded_error":
+    case "api_error":
+      return { code: "provider_internal" };
+    case "request_too_large":
+      return { code: "context_overflow" };
+    case "invalid_request_error":
+      return CONTEXT_OVERFLOW_PATTERN.test(String(body.error.message)) ? { code: "context_overflow" } : undefined;
+    default:
+      return undefined;
+  }
+}
+
+// { "error": { "code": 429, "status": "RESOURCE_EXHAUSTED", "message": ... } }
+function googleErrorCode(body: any): CodeMatch | undefined {
+  const status = body?.error?.status;
+  if (typeof status !== "string") {
+    return undefined;
+  }
+  switch (status) {
+    case "UNAUTHENTICATED":
+    case "PERMISSION_DENIED":
+      return { code: "auth_failed" };
+    case "RESOURCE_EXHAUSTED":
+      return { code: "rate_limited" };
+    case "NOT_FOUND":
+      return { code: "model_not_found" };
+    case "DEADLINE_EXCEEDED":
+      return { code: "timeout" };
+    case "UNAVAILABLE":
+    case "INTERNAL":
+      return { code: "provider_internal" };
+    case "INVALID_ARGUMENT":
+      return CONTEXT_OVERFLOW_PATTERN.test(String(body.error.message)) ? { code: "context_overflow" } : undefined;
+    default:
+      return undefined;
+  }
+}
+
+// Bedrock names the exception in the x-amzn-errortype header ("ThrottlingException:http://...") or in `__type`
+function bedrockErrorCode(body: any, headers: Record<string, string> | undefined, message: string): CodeMatch | undefined {
+  const rawType = headers?.["x-amzn-errortype"] ?? body?.__type;
+  if (typeof rawType !== "string") {
+    return undefined;
+  }
+  switch (rawType.split(/[:#]/).find((part) => part.endsWith("Exception"))) {
+    case "AccessDeniedException":
+    case "UnrecognizedClientException":
+    case "ExpiredTokenException":
+      return { code: "auth_failed" };
+    case "ThrottlingException":
+    case "ServiceQuotaExceededException":
+      return { code: "rate_limited" };
+    case "ResourceNotFoundException":
+      return { code: "model_not_found" };
+    case "ModelTimeoutException":
+      return { code: "timeout" };
+    case "InternalServerException":
+    case "ServiceUnavailableException":
+    case "ModelNotReadyException":
+    case "ModelErrorException":
+      return { code: "provider_internal" };
+    case "ValidationException":
+      return CONTEXT_OVERFLOW_PATTERN.test(message) ? { code: "context_overflow" } : undefined;
+    default:
+      return undefined;
+  }
+}
+
+function statusErrorCode(statusCode: number | undefined, message: string): CodeMatch | undefined {
+  if (statusCode === undefined) {
+    return undefined;
+  }
+  if (statusCode === 401 || statusCode === 403) {
+    return { code: "auth_failed" };
+  }
+  if (statusCode === 404) {
+    return { code: "model_not_found" };
+  }
+  if (statusCode === 408 || statusCode === 504) {
+    return { code: "timeout" };
+  }
+  if (statusCode === 413 || (statusCode === 400 && CONTEXT_OVERFLOW_PATTERN.test(message))) {
+    return { code: "context_overflow" };
+  }
+  if (statusCode === 429) {
+    return { code: "rate_limited" };
+  }
+  if (statusCode >= 500) {
+    return { code: "provider_internal" };
+  }
+  return undefined;
+}
+
+// Errors without an HTTP response: the request never got an answer
+function messageErrorCode(error: unknown, message: string): CodeMatch | undefined {
+  const name = error instanceof Error ? error.name : "";
+  if (name === "TimeoutError" || /timed out|timeout/i.test(message)) {
+    return { code: "timeout" };
+  }
+  if (/fetch failed|failed to fetch|error sending request|network ?error|connection (refused|reset)|ECONNREFUSED|ECONNRESET|ENOTFOUND|EAI_AGAIN|dns error/i.test(message)) {
+    return { code: "network_unreachable" };
+  }
+  if (CONTEXT_OVERFLOW_PATTERN.test(message)) {
+    return { code: "context_overflow" };
+  }
+  if (/\b429\b|rate limit/i.test(message)) {
+    return { code: "rate_limited" };
+  }
+  if (/internal server error|\b50[0-3]\b/i.test(message)) {
+    return { code: "provider_internal" };
+  }
+  return undefined;
+}
+
+function errorMessage(error: unknown): string {
+  if (error instanceof Error) {
+    return error.message;
+  }
+  if (typeof error === "object" && error !== null && "message" in error) {
+    return String((error as { message: unknown }).message);
+  }
+  return String(error);
+}
+
+// The SDK wraps exhausted retries in a RetryError and some providers wrap errors in { error: ... }
+function unwrapError(error: unknown): unknown {
+  if (RetryError.isInstance(error)) {
+    return unwrapError(error.lastError);
+  }
+  if (typeof error === "object" && error !== null && !(error instanceof Error) && "error" in error && (error as { error: unknown }).error !== error) {
+    return unwrapError((error as { error: unknown }).error);
+  }
+  return error;
+}
+
+/**
+ * Maps any error thrown or streamed by a provider to a machine-readable code, a readable message,
+ * whether re-sending may help and the raw provider detail.
+ * Errors that already carry a code are returned as they are.
+ */
+function classifyInferenceError(error: unknown): AIError {
+  if (typeof error === "object" && error !== null && typeof (error as AIError).code === "string" && typeof (error as AIError).message === "string" && !(error instanceof Error)) {
+    return error as AIError;
+  }
+
+  const cause = unwrapError(error);
+  const message = errorMessage(cause);
+
+  if (cause instanceof Error && cause.name === "AbortError") {
+    return { message: SUMMARIES.cancelled, code: "cancelled", retryable: false };
+  }
+
+  let match: CodeMatch | undefined;
+  let details: unknown;
+  if (APICallError.isInstance(cause)) {
+    const body = parseBody(cause.responseBody);
+    match =
+      anthropicErrorCode(body) ??
+      googleErrorCode(body) ??
+      bedrockErrorCode(body, cause.responseHeaders, message) ??
+      openAIErrorCode(body) ??
+      statusErrorCode(cause.statusCode, message);
+    details = { statusCode: cause.statusCode, url: cause.url, responseBody: body ?? cause.responseBody };
+    match ??= { code: "unknown", retryable: cause.isRetryable };
+  } else {
+    // Bedrock SDK exceptions are named after their type
+    match = bedrockErrorCode(cause instanceof Error ? { __type: cause.name } : undefined, undefined, message) ?? messageErrorCode(cause, message) ?? { code: "unknown" };
+    details = message;
+  }
+
+  return {
+    message: match.code === "unknown" ? message : `${SUMMARIES[match.code]}: ${message}`,
+    code: match.code,
+    retryable: match.retryable ?? RETRYABLE_CODES.has(match.code),
+    details,
+  };
+}
+
+/**
+ * Error for a generation that ended abnormally, or undefined when it didn't. Hitting the token limit
+ * ("length") is not an error: the partial answer is kept and the finish reason tells the UI it was cut.
+ * Content filters (Gemini SAFETY/RECITATION/BLOCKLIST... finish reasons) get the `content_filtered` code.
+ */
+function finishReasonError(finishReason: string, providerMetadata?: Record<string, any>): AIError | undefined {
+  if (finishReason === "stop" || finishReason === "length") {
+    return undefined;
+  }
+  if (finishReason === "content-filter") {
+    return {
+      message: `${SUMMARIES.content_filtered} (${finishReason})`,
+      code: "content_filtered",
+      retryable: false,
+      details: providerMetadata?.google?.safetyRatings ?? providerMetadata?.google?.promptFeedback ?? undefined,
+    };
+  }
+  return { message: `Inference stopped: ${finishReason}` };
+}
+
+export { classifyInferenceError, finishReasonError };
@@ -0,0 +1,50 @@
+import type { AIEvent } from "../types/ai-event.type";
+
+// Some providers open the answer with spaces or newlines. Only the start of the answer is trimmed:
+// chunks after the first visible text are forwarded exactly as received, so internal spacing is untouched.
+
+function resolveNormalizeLeadingWhitespace(value: unknown): boolean {
+  return value !== false;
+}
+
+/**
+ * Wraps one attempt so its answer never starts with whitespace. Leading whitespace-only chunks are dropped
+ * and the first visible chunk is trimmed; the finish payload and the returned text are trimmed the same way.
+ */
+function withLeadingWhitespaceTrimmed(run: (event: AIEvent) => Promise<string>, enabled: boolean): (event: AIEvent) => Promise<string> {
+  if (!enabled) {
+    return run;
+  }
+
+  return async (event) => {
+    let started = false;
+
+    const trimmedEvent: AIEvent = {
+      ...event,
+      sendStream: (payload) => {
+        if (started || payload.text === undefined) {
+          event.sendStream(payload);
+          return;
+        }
+        const text = payload.text.trimStart();
+        started = text.length > 0;
+        if (started) {
+          event.sendStream({ ...payload, text });
+          return;
+        }
+        // Whatever else the chunk carries (reasoning, finish reason...) still goes through
+        const rest = { ...payload, text: undefined };
+        if (Object.values(rest).some((value) => value !== undefined)) {
+          event.sendStream(rest);
+        }
+      },
+      finish: (payload) => {
+        event.finish(payload?.fullResponse ? { ...payload, fullResponse: payload.fullResponse.trimStart() } : payload);
+      },
+    };
+
+    return (await run(trimmedEvent)).trimStart();
+  };
+}
+
+export { resolveNormalizeLeadingWhitespace, withLeadingWhitespaceTrimmed };
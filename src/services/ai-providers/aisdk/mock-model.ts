@@ -0,0 +1,195 @@
+import type { LanguageModel } from "ai";
+
+// Development engine: streams a canned response through the regular AI SDK path without any network.
+// Errors are simulated with messages the inference log classifies like the real thing.
+
+const DEFAULT_MOCK_RESPONSE = "This is a mock response. Configure it in the model settings to test how the chat renders *actions*, \"dialogue\" and **markdown**.";
+const DEFAULT_TOKENS_PER_SECOND = 20;
+const MOCK_TIMEOUT_MS = 10_000;
+
+type SimulatedError = "none" | "error" | "timeout" | "rate_limit" | "empty";
+
+interface MockModelConfig {
+  mockResponse: string;
+  reasoning: string;
+  tokensPerSecond: number;
+  simulateError: SimulatedError;
+}
+
+function parseMockConfig(config: Record<string, any> | undefined): MockModelConfig {
+  const tokensPerSecond = Number(config?.tokens_per_second);
+  const simulateError = String(config?.simulate_error || "none") as SimulatedError;
+
+  return {
+    mockResponse: typeof config?.mock_response === "string" && config.mock_response ? config.mock_response : DEFAULT_MOCK_RESPONSE,
+    reasoning: typeof config?.reasoning === "string" ? config.reasoning : "",
+    tokensPerSecond: Number.isFinite(tokensPerSecond) && tokensPerSecond >= 0 ? tokensPerSecond : DEFAULT_TOKENS_PER_SECOND,
+    simulateError: ["error", "timeout", "rate_limit", "empty"].includes(simulateError) ? simulateError : "none",
+  };
+}
+
+// Words with their trailing whitespace, so joining the tokens gives back the original text
+function tokenize(text: string): string[] {
+  return text.match(/\S+\s*|\s+/g) ?? [];
+}
+
+function abortError(): Error {
+  const error = new Error("The operation was aborted");
+  error.name = "AbortError";
+  return error;
+}
+
+function wait(ms: number, abortSignal?: AbortSignal): Promise<void> {
+  if (abortSignal?.aborted) {
+    return Promise.reject(abortError());
+  }
+  if (ms <= 0) {
+    return Promise.resolve();
+  }
+  return new Promise((resolve, reject) => {
+    const timer = setTimeout(() => {
+      abortSignal?.removeEventListener("abort", onAbort);
+      resolve();
+    }, ms);
+    const onAbort = () => {
+      clearTimeout(timer);
+      reject(abortError());
+    };
+    abortSignal?.addEventListener("abort", onAbort, { once: true });
+  });
+}
+
+function simulatedError(kind: SimulatedError): Error {
+  if (kind === "rate_limit") {
+    return new Error("429 Too Many Requests: rate limit exceeded (simulated by the mock engine)");
+  }
+  if (kind === "timeout") {
+    return new Error("Request timed out (simulated by the mock engine)");
+  }
+  return new Error("Internal server error (simulated by the mock engine)");
+}
+
+function usage(inputText: string, outputText: string, reasoning: string) {
+  const inputTokens = tokenize(inputText).length;
+  const textTokens = tokenize(outputText).length;
+  const reasoningTokens = tokenize(reasoning).length;
+  return {
+    inputTokens: { total: inputTokens, noCache: inputTokens, cacheRead: undefined, cacheWrite: undefined },
+    outputTokens: { total: textTokens + reasoningTokens, text: textTokens, reasoning: reasoningTokens },
+  };
+}
+
+// Prompt text only feeds the usage estimate
+function promptText(prompt: unknown): string {
+  if (!Array.isArray(prompt)) {
+    return "";
+  }
+  return prompt
+    .map((message: any) => (typeof message.content === "string" ? message.content : Array.isArray(message.content) ? message.content.map((part: any) => part.text ?? "").join(" ") : ""))
+    .join("\n");
+}
+
+/**
+ * Builds the "mock" engine model from the model config:
+ * - mock_response: the canned answer
+ * - tokens_per_second: streaming rate, 0 streams everything at once
+ * - reasoning: optional text streamed as a reasoning phase before the answer
+ * - simulate_error: none, error, timeout, rate_limit or empty
+ */
+function createMockLanguageModel(modelName: string | undefined, config: Record<string, any> | undefined): LanguageModel {
+  const settings = parseMockConfig(config);
+  const delay = settings.tokensPerSecond > 0 ? 1000 / settings.tokensPerSecond : 0;
+  const responseText = settings.simulateError === "empty" ? "" : settings.mockResponse;
+  const finishReason = { unified: "stop", raw: "stop" };
+
+  return {
+    specificationVersion: "v3",
+    provider: "mock",
+    modelId: modelName || "mock",
+    supportedUrls: {},
+
+    async doGenerate(options: any) {
+      const { abortSignal } = options;
+      if (settings.simulateError === "timeout") {
+        await wait(MOCK_TIMEOUT_MS, abortSignal);
+        throw simulatedError("timeout");
+      }
+      await wait(delay * tokenize(settings.reasoning + responseText).length, abortSignal);
+      if (settings.simulateError === "error" || settings.simulateError === "rate_limit") {
+        throw simulatedError(settings.simulateError);
+      }
+
+      return {
+        content: [...(settings.reasoning ? [{ type: "reasoning", text: settings.reasoning }] : []), { type: "text", text: responseText }],
+        finishReason,
+        usage: usage(promptText(options.prompt), responseText, settings.reasoning),
+        warnings: [],
+      };
+    },
+
+    async doStream(options: any) {
+      const { abortSignal, includeRawChunks } = options;
+      const inputText = promptText(options.prompt);
+
+      const stream = new ReadableStream({
+        async start(controller) {
+          try {
+            controller.enqueue({ type: "stream-start", warnings: [] });
+
+            if (settings.simulateError === "timeout") {
+              await wait(MOCK_TIMEOUT_MS, abortSignal);
+              controller.enqueue({ type: "error", error: simulatedError("timeout") });
+              controller.close();
+              return;
+            }
+
+            if (settings.reasoning) {
+              controller.enqueue({ type: "reasoning-start", id: "reasoning-0" });
+              for (const token of tokenize(settings.reasoning)) {
+                await wait(delay, abortSignal);
+                if (includeRawChunks) {
+                  controller.enqueue({ type: "raw", rawValue: { reasoning: token } });
+                }
+                controller.enqueue({ type: "reasoning-delta", id: "reasoning-0", delta: token });
+              }
+              controller.enqueue({ type: "reasoning-end", id: "reasoning-0" });
+            }
+
+            const tokens = tokenize(responseText);
+            // Errors fail halfway through, so partial text handling gets exercised too
+            const failAt = settings.simulateError === "error" || settings.simulateError === "rate_limit" ? Math.floor(tokens.length / 2) : -1;
+            if (failAt === 0 && tokens.length === 0) {
+              controller.enqueue({ type: "error", error: simulatedError(settings.simulateError) });
+              controller.close();
+              return;
+            }
+
+            controller.enqueue({ type: "text-start", id: "text-0" });
+            for (const [index, token] of tokens.entries()) {
+              if (index === failAt) {
+                controller.enqueue({ type: "error", error: simulatedError(settings.simulateError) });
+                controller.close();
+                return;
+              }
+              await wait(delay, abortSignal);
+              if (includeRawChunks) {
+                controller.enqueue({ type: "raw", rawValue: { text: token } });
+              }
+              controller.enqueue({ type: "text-delta", id: "text-0", delta: token });
+            }
+            controller.enqueue({ type: "text-end", id: "text-0" });
+
+            controller.enqueue({ type: "finish", finishReason, usage: usage(inputText, responseText, settings.reasoning) });
+            controller.close();
+          } catch (error) {
+            controller.error(error);
+          }
+        },
+      });
+
+      return { stream };
+    },
+  } as unknown as LanguageModel;
+}
+
+export { createMockLanguageModel, parseMockConfig, tokenize };
@@ -1,6 +1,9 @@
 import { generateText, stepCountIs } from "ai";
 import type { FinalParams } from "../start-inference";
-import type { AIEvent } from "../types/ai-event.type";
+import { type AIEvent, GUARDRAIL_INTERVENED, TRUNCATED_BEFORE_ANSWER } from "../types/ai-event.type";
+import { guardrailBlockedMessage, isMaskOnlyIntervention, summarizeGuardrail } from "./guardrail";
+import { finishReasonError } from "./inference-errors";
+import { toAIUsage } from "./usage";
 
 async function generateResponse(params: FinalParams, event?: AIEvent): Promise<string> {
   const abortController = new AbortController();
@@ -18,6 +21,37 @@ async function generateResponse(params: FinalParams, event?: AIEvent): Promise<s
       abortSignal: abortController.signal,
     });
 
+    if (event) {
+      // The caller builds the finish payload from the returned text, so the reason travels separately
+      event.sendStream({ finishReason: result.finishReason, usage: toAIUsage(result.totalUsage) });
+      const guardrail = summarizeGuardrail(result.providerMetadata);
+      if (guardrail && (guardrail.masked.length > 0 || guardrail.trace)) {
+        event.sendStream({ notices: guardrail.masked, guardrailTrace: guardrail.trace });
+      }
+      if (result.finishReason === "content-filter" && result.providerMetadata?.bedrock && !isMaskOnlyIntervention(guardrail)) {
+        event.sendError({ message: guardrailBlockedMessage(guardrail), code: GUARDRAIL_INTERVENED, details: guardrail?.blocked });
+        return result.text;
+      }
+      // Gemini safety stops and other content filters that left nothing to show
+      if (result.finishReason === "content-filter" && !result.text.trim()) {
+        const filterError = finishReasonError(result.finishReason, result.providerMetadata);
+        if (filterError) {
+          event.sendError(filterError);
+          return result.text;
+        }
+      }
+    }
+
+    if (event && result.reasoningText) {
+      event.sendStream({ reasoning: result.reasoningText });
+      if (result.finishReason !== "stop" && !result.text.trim()) {
+        event.sendError({
+          message: `Inference stopped (${result.finishReason}) before an answer was produced. Increase max tokens or lower the reasoning budget.`,
+          code: TRUNCATED_BEFORE_ANSWER,
+        });
+      }
+    }
+
     return result.text;
   } catch (error) {
     if (error instanceof Error && (error.name === "AbortError" || error.message.includes("abort"))) {
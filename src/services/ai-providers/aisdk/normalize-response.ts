@@ -0,0 +1,76 @@
+// Some OpenAI-compatible servers (mostly local ones) answer chat completions in other shapes:
+// `choices[0].text` like the legacy completions API, content parts instead of a string, or a top-level `content`.
+// The SDK only reads `choices[0].message.content`, so those answers came back empty.
+
+type FetchFunction = (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>;
+
+// Joins content given as a string or as an array of parts ({ text } or plain strings)
+function contentText(content: unknown): string | undefined {
+  if (typeof content === "string") {
+    return content;
+  }
+  if (!Array.isArray(content)) {
+    return undefined;
+  }
+  const parts = content.map((part) => (typeof part === "string" ? part : typeof part?.text === "string" ? part.text : undefined)).filter((part) => part !== undefined);
+  return parts.length > 0 ? parts.join("") : undefined;
+}
+
+/**
+ * Moves the answer of a non-streaming chat completion to `choices[0].message.content`, trying in order:
+ * `choices[0].message.content` (string or parts array), `choices[0].text` and a top-level `content`.
+ * Bodies without any of them are returned untouched.
+ */
+function normalizeChatCompletionBody(body: unknown): unknown {
+  if (typeof body !== "object" || body === null) {
+    return body;
+  }
+  const response = body as Record<string, any>;
+  const choice = Array.isArray(response.choices) ? response.choices[0] : undefined;
+
+  if (typeof choice?.message?.content === "string") {
+    return body;
+  }
+
+  const content = contentText(choice?.message?.content) ?? (typeof choice?.text === "string" ? choice.text : undefined) ?? contentText(response.content);
+  if (content === undefined) {
+    return body;
+  }
+
+  const normalizedChoice = {
+    index: 0,
+    finish_reason: response.finish_reason ?? "stop",
+    ...choice,
+    message: { role: "assistant", ...choice?.message, content },
+  };
+  return { ...response, choices: [normalizedChoice, ...(response.choices?.slice(1) ?? [])] };
+}
+
+/**
+ * Wraps a fetch so JSON chat completion answers are normalized before the SDK parses them.
+ * Streams (text/event-stream) and error responses pass through as is.
+ */
+function withNormalizedChatResponses(fetchImpl: FetchFunction): FetchFunction {
+  return async (input, init) => {
+    const response = await fetchImpl(input, init);
+    if (!response.ok || !response.headers.get("content-type")?.includes("application/json")) {
+      return response;
+    }
+
+    const text = await response.text();
+    let body: unknown;
+    try {
+      body = JSON.parse(text);
+    } catch {
+      // Let the SDK report the invalid body itself
+      return new Response(text, { status: response.status, statusText: response.statusText, headers: response.headers });
+    }
+
+    // The rewritten body no longer matches the original length
+    const headers = new Headers(response.headers);
+    headers.delete("content-length");
+    return new Response(JSON.stringify(normalizeChatCompletionBody(body)), { status: response.status, statusText: response.statusText, headers });
+  };
+}
+
+export { normalizeChatCompletionBody, withNormalizedChatResponses };
@@ -0,0 +1,89 @@
+import type { InferenceMessage } from "@/schema/inference-engine-schema";
+import type { Engine } from "@/schema/model-manifest-schema";
+
+// Engines whose APIs reject anything but strictly alternating user/assistant turns starting with user
+const ALTERNATING_ROLE_ENGINES: Engine[] = ["anthropic", "aws_bedrock"];
+
+// Sent ahead of a conversation that opens with a model turn, which Gemini rejects
+const GEMINI_OPENING_TURN = "[Start of conversation]";
+
+const GEMINI_HOST_PATTERN = /(generativelanguage|aiplatform)\.googleapis\.com/i;
+const GEMINI_MODEL_PATTERN = /(^|\/)gemini-/i;
+
+function requiresAlternatingRoles(engine: Engine): boolean {
+  return ALTERNATING_ROLE_ENGINES.includes(engine);
+}
+
+/**
+ * Whether the request ends up at Gemini: the Google engine, or an OpenAI-compatible/OpenRouter model
+ * pointed at Google's endpoints or named `gemini-*`.
+ */
+function isGeminiTarget(engine: Engine, config?: Record<string, any>): boolean {
+  if (engine === "google") {
+    return true;
+  }
+  if (engine !== "openai_compatible" && engine !== "openrouter") {
+    return false;
+  }
+  return GEMINI_HOST_PATTERN.test(String(config?.base_url ?? "")) || GEMINI_MODEL_PATTERN.test(String(config?.model ?? ""));
+}
+
+// Tool calls and tool results are paired by id, so those messages are never merged
+function canMerge(previous: InferenceMessage, next: InferenceMessage): boolean {
+  if (previous.role !== next.role || next.role === "tool") {
+    return false;
+  }
+  return !previous.tool_calls?.length && !next.tool_calls?.length;
+}
+
+/**
+ * Gemini wants the first turn after the system instruction to be a user turn and rejects an empty conversation.
+ * Chats that open with the character's greeting get a minimal user turn in front instead of a provider 400.
+ */
+function normalizeGeminiMessages(messages: InferenceMessage[]): InferenceMessage[] {
+  if (messages[0]?.role === "user") {
+    return messages;
+  }
+  return [{ role: "user", text: GEMINI_OPENING_TURN }, ...messages];
+}
+
+/**
+ * Normalize a message list for engines with role constraints.
+ * For engines that require alternating roles, adjacent messages of the same role are merged, and a list that
+ * opens with an assistant turn is rejected with a readable error instead of a provider 400.
+ * Gemini targets get a user turn in front when needed. Other engines get the list unchanged.
+ * @param config - The model config, used to recognize Gemini behind OpenAI-compatible endpoints
+ */
+function normalizeMessageRoles(engine: Engine, messages: InferenceMessage[], config?: Record<string, any>, separator = "\n\n"): InferenceMessage[] {
+  if (isGeminiTarget(engine, config)) {
+    return normalizeGeminiMessages(messages);
+  }
+  if (!requiresAlternatingRoles(engine)) {
+    return messages;
+  }
+
+  const result: InferenceMessage[] = [];
+  for (const message of messages) {
+    const previous = result[result.length - 1];
+    if (previous && canMerge(previous, message)) {
+      const files = [...((previous as any).files ?? []), ...((message as any).files ?? [])];
+      result[result.length - 1] = {
+        ...previous,
+        text: [previous.text, message.text].filter(Boolean).join(separator),
+        ...(files.length > 0 ? { files } : {}),
+      };
+    } else {
+      result.push({ ...message });
+    }
+  }
+
+  if (result[0]?.role === "assistant") {
+    throw new Error(
+      `${engine === "anthropic" ? "Anthropic" : "AWS Bedrock"} requires the conversation to start with a user message, but it starts with an assistant message. Add a user message before the first character message, or enable "Squash all messages on User" in the format template.`,
+    );
+  }
+
+  return result;
+}
+
+export { GEMINI_OPENING_TURN, isGeminiTarget, normalizeMessageRoles, requiresAlternatingRoles };
@@ -0,0 +1,207 @@
+// Per-model field mappings for servers that are almost, but not quite, OpenAI-compatible.
+// A mapping is a JSON object of `"source.path": "target.path"` entries, e.g. `{ "max_tokens": "n_predict" }`
+// for the request, or `{ "results[0].text": "choices[0].text" }` for the response.
+// Paths use dots for keys and [n] for array indexes. Each entry moves the value, entries apply in order.
+
+type FetchFunction = (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>;
+type PathSegment = string | number;
+type FieldMapping = [source: PathSegment[], target: PathSegment[]][];
+
+const SEGMENT_PATTERN = /([^.[\]]+)|\[(\d+)\]/g;
+
+function parsePath(path: string): PathSegment[] {
+  const segments: PathSegment[] = [];
+  for (const match of path.matchAll(SEGMENT_PATTERN)) {
+    segments.push(match[2] !== undefined ? Number(match[2]) : match[1]);
+  }
+  if (segments.length === 0) {
+    throw new Error(`Invalid field path "${path}"`);
+  }
+  return segments;
+}
+
+/**
+ * Parse a mapping from the model config, given as a JSON string or an already parsed object
+ * @returns The mapping, or null when none is configured
+ * @throws If the mapping is not an object of path strings
+ */
+function parseFieldMapping(value: unknown, label: string): FieldMapping | null {
+  if (value === undefined || value === null || (typeof value === "string" && value.trim() === "")) {
+    return null;
+  }
+
+  let mapping: unknown = value;
+  if (typeof value === "string") {
+    try {
+      mapping = JSON.parse(value);
+    } catch (error) {
+      throw new Error(`${label} is not valid JSON: ${error instanceof Error ? error.message : String(error)}`);
+    }
+  }
+
+  if (typeof mapping !== "object" || mapping === null || Array.isArray(mapping)) {
+    throw new Error(`${label} must be a JSON object of "source": "target" paths`);
+  }
+
+  const entries = Object.entries(mapping);
+  for (const [source, target] of entries) {
+    if (typeof target !== "string") {
+      throw new Error(`${label}: the target of "${source}" must be a path string`);
+    }
+  }
+  return entries.length > 0 ? entries.map(([source, target]) => [parsePath(source), parsePath(target as string)]) : null;
+}
+
+function getPath(root: unknown, path: PathSegment[]): unknown {
+  let current: any = root;
+  for (const segment of path) {
+    if (current === null || typeof current !== "object") {
+      return undefined;
+    }
+    current = current[segment];
+  }
+  return current;
+}
+
+function setPath(root: Record<string, any>, path: PathSegment[], value: unknown) {
+  let current: any = root;
+  path.forEach((segment, index) => {
+    if (index === path.length - 1) {
+      current[segment] = value;
+      return;
+    }
+    if (current[segment] === null || typeof current[segment] !== "object") {
+      current[segment] = typeof path[index + 1] === "number" ? [] : {};
+    }
+    current = current[segment];
+  });
+}
+
+function deletePath(root: unknown, path: PathSegment[]) {
+  const parent = getPath(root, path.slice(0, -1)) as any;
+  const key = path[path.length - 1];
+  if (parent === null || typeof parent !== "object") {
+    return;
+  }
+  if (Array.isArray(parent) && typeof key === "number") {
+    parent[key] = undefined;
+  } else {
+    delete parent[key];
+  }
+}
+
+/**
+ * Moves every mapped field of a JSON body to its target path. Missing sources are skipped.
+ * Returns a new object, the input is not modified.
+ */
+function applyFieldMapping(body: unknown, mapping: FieldMapping): unknown {
+  if (typeof body !== "object" || body === null) {
+    return body;
+  }
+
+  const result = structuredClone(body) as Record<string, any>;
+  for (const [source, target] of mapping) {
+    const value = getPath(result, source);
+    if (value === undefined) {
+      continue;
+    }
+    deletePath(result, source);
+    setPath(result, target, value);
+  }
+  return result;
+}
+
+// Rewrites each `data: {...}` line of a server-sent event stream. Lines that aren't JSON ([DONE]) pass through.
+function transformEventStream(stream: ReadableStream<Uint8Array>, mapping: FieldMapping): ReadableStream<Uint8Array> {
+  const decoder = new TextDecoder();
+  const encoder = new TextEncoder();
+  let buffer = "";
+
+  const transformLine = (line: string): string => {
+    const match = /^data:\s?(.*)$/.exec(line);
+    if (!match) {
+      return line;
+    }
+    try {
+      return `data: ${JSON.stringify(applyFieldMapping(JSON.parse(match[1]), mapping))}`;
+    } catch {
+      return line;
+    }
+  };
+
+  return stream.pipeThrough(
+    new TransformStream<Uint8Array, Uint8Array>({
+      transform(chunk, controller) {
+        buffer += decoder.decode(chunk, { stream: true });
+        const lines = buffer.split("\n");
+        // Keep the unfinished line for the next chunk
+        buffer = lines.pop() ?? "";
+        if (lines.length > 0) {
+          controller.enqueue(encoder.encode(`${lines.map(transformLine).join("\n")}\n`));
+        }
+      },
+      flush(controller) {
+        buffer += decoder.decode();
+        if (buffer) {
+          controller.enqueue(encoder.encode(transformLine(buffer)));
+        }
+      },
+    }),
+  );
+}
+
+/**
+ * Wraps a fetch so JSON request bodies and responses (plain JSON or event streams) go through the
+ * configured field mappings. Without any mapping the fetch is returned as is.
+ * @param requestTransform - Mapping applied to the outgoing payload
+ * @param responseTransform - Mapping applied to the response body, or to each streamed event
+ * @throws If a mapping is invalid, so a typo fails loudly instead of being ignored
+ */
+function withPayloadTransforms(fetchImpl: FetchFunction, requestTransform?: unknown, responseTransform?: unknown): FetchFunction {
+  const requestMapping = parseFieldMapping(requestTransform, "Request transform");
+  const responseMapping = parseFieldMapping(responseTransform, "Response transform");
+  if (!requestMapping && !responseMapping) {
+    return fetchImpl;
+  }
+
+  return async (input, init) => {
+    let requestInit = init;
+    if (requestMapping && typeof init?.body === "string") {
+      try {
+        requestInit = { ...init, body: JSON.stringify(applyFieldMapping(JSON.parse(init.body), requestMapping)) };
+      } catch {
+        // Not a JSON payload, send it untouched
+      }
+    }
+
+    const response = await fetchImpl(input, requestInit);
+    const contentType = response.headers.get("content-type") ?? "";
+    if (!responseMapping || !response.ok || !response.body) {
+      return response;
+    }
+
+    // The rewritten body no longer matches the original length
+    const headers = new Headers(response.headers);
+    headers.delete("content-length");
+
+    if (contentType.includes("text/event-stream")) {
+      return new Response(transformEventStream(response.body, responseMapping), { status: response.status, statusText: response.statusText, headers });
+    }
+    if (!contentType.includes("application/json")) {
+      return response;
+    }
+
+    const text = await response.text();
+    let body: unknown;
+    try {
+      body = JSON.parse(text);
+    } catch {
+      // Let the SDK report the invalid body itself
+      return new Response(text, { status: response.status, statusText: response.statusText, headers });
+    }
+    return new Response(JSON.stringify(applyFieldMapping(body, responseMapping)), { status: response.status, statusText: response.statusText, headers });
+  };
+}
+
+export type { FieldMapping };
+export { applyFieldMapping, parseFieldMapping, withPayloadTransforms };
@@ -0,0 +1,100 @@
+import type { ModelMessage } from "ai";
+import { z } from "zod";
+import type { Engine } from "@/schema/model-manifest-schema";
+
+// Anthropic and Bedrock both reject requests with more than four cache breakpoints
+const MAX_CACHE_BREAKPOINTS = 4;
+
+// Provider options that mark a message as a cache breakpoint. Engines missing here either cache automatically (OpenAI) or not at all.
+const CACHE_BREAKPOINT_OPTIONS: Partial<Record<Engine, Record<string, any>>> = {
+  anthropic: { anthropic: { cacheControl: { type: "ephemeral" } } },
+  aws_bedrock: { bedrock: { cachePoint: { type: "default" } } },
+};
+
+const cacheTtlSchema = z.enum(["5m", "1h"]);
+type CacheTtl = z.infer<typeof cacheTtlSchema>;
+
+// Breakpoints with a chosen lifetime, for engines that let the request pick it. Bedrock cache points
+// take no TTL, so it keeps the provider's default there.
+const CACHE_TTL_BREAKPOINT_OPTIONS: Partial<Record<Engine, (ttl: CacheTtl) => Record<string, any>>> = {
+  anthropic: (ttl) => ({ anthropic: { cacheControl: { type: "ephemeral", ttl } } }),
+};
+
+interface PromptCacheSettings {
+  cacheSystemPrompt: boolean;
+  cacheLastNMessages: number;
+  // Provider default (5 minutes) when unset
+  cacheTtl?: CacheTtl;
+}
+
+/**
+ * Read the engine-independent cache settings from flattened inference parameters.
+ * Caching is off unless the Prompt Cache section is present. `prompt_cache_depth` is the older Bedrock-only name.
+ * An invalid `cache_ttl` is ignored, leaving the provider default.
+ */
+function getPromptCacheSettings(parameters: Record<string, any>): PromptCacheSettings {
+  const hasCacheSettings = "cache_system_prompt" in parameters || "cache_last_n_messages" in parameters || "prompt_cache_depth" in parameters;
+  if (!hasCacheSettings) {
+    return { cacheSystemPrompt: false, cacheLastNMessages: 0 };
+  }
+
+  const lastN = Number(parameters.cache_last_n_messages ?? parameters.prompt_cache_depth ?? 0);
+  const cacheTtl = cacheTtlSchema.safeParse(parameters.cache_ttl);
+  if (parameters.cache_ttl !== undefined && !cacheTtl.success) {
+    console.warn(`Ignoring cache_ttl "${parameters.cache_ttl}", expected "5m" or "1h"`);
+  }
+  return {
+    cacheSystemPrompt: parameters.cache_system_prompt ?? true,
+    cacheLastNMessages: Number.isFinite(lastN) ? Math.max(0, Math.floor(lastN)) : 0,
+    cacheTtl: cacheTtl.success ? cacheTtl.data : undefined,
+  };
+}
+
+/**
+ * Add cache breakpoints to the system message and the last N conversation messages for engines that support it.
+ * Breakpoints are capped at the provider limit, keeping the system prompt and the most recent messages.
+ * On Anthropic they all get the `cache_ttl` lifetime when one is set.
+ * @param stablePrefix - Leading messages unchanged since the previous request of the same chat (see trackStablePrefix).
+ * When given, the end of that prefix gets a breakpoint right after the system prompt, so an edit early in the
+ * history still reuses the cache up to the edited message.
+ */
+function applyPromptCache(engine: Engine, messages: ModelMessage[], parameters: Record<string, any>, stablePrefix?: number): ModelMessage[] {
+  const { cacheSystemPrompt, cacheLastNMessages, cacheTtl } = getPromptCacheSettings(parameters);
+  const breakpoint = (cacheTtl && CACHE_TTL_BREAKPOINT_OPTIONS[engine]?.(cacheTtl)) || CACHE_BREAKPOINT_OPTIONS[engine];
+  if (!breakpoint || (!cacheSystemPrompt && cacheLastNMessages === 0)) {
+    return messages;
+  }
+
+  const marked = new Set<number>();
+  if (cacheSystemPrompt) {
+    const lastSystemIndex = messages.map((message) => message.role).lastIndexOf("system");
+    if (lastSystemIndex !== -1) {
+      marked.add(lastSystemIndex);
+    }
+  }
+
+  if (stablePrefix && stablePrefix <= messages.length && messages[stablePrefix - 1].role !== "system") {
+    marked.add(stablePrefix - 1);
+  }
+
+  let markedMessages = 0;
+  for (let index = messages.length - 1; index >= 0 && markedMessages < cacheLastNMessages && marked.size < MAX_CACHE_BREAKPOINTS; index--) {
+    if (messages[index].role !== "system") {
+      marked.add(index);
+      markedMessages++;
+    }
+  }
+
+  return messages.map((message, index) => {
+    if (!marked.has(index)) {
+      return message;
+    }
+    const providerOptions: Record<string, any> = { ...message.providerOptions };
+    for (const [provider, options] of Object.entries(breakpoint)) {
+      providerOptions[provider] = { ...providerOptions[provider], ...options };
+    }
+    return { ...message, providerOptions } as ModelMessage;
+  });
+}
+
+export { applyPromptCache, getPromptCacheSettings };
@@ -0,0 +1,95 @@
+import type { ModelSpecs } from "@/schema/inference-engine-schema";
+
+// Providers are reused across requests instead of being rebuilt, with the API key decrypted again,
+// for every message. Entries are keyed by a fingerprint of the engine, model type and the whole
+// config except the model name, so a new base URL, key, header or transform builds a new provider.
+// Editing or deleting a model drops its entries, and entries left unused expire.
+
+export const PROVIDER_IDLE_TTL_MS = 10 * 60 * 1000;
+
+interface CachedProvider {
+  // Shared by concurrent requests while the provider is being built
+  provider: Promise<unknown>;
+  modelIds: Set<string>;
+  lastUsedAt: number;
+}
+
+const providers = new Map<string, CachedProvider>();
+
+// Object keys sorted, so the same config loaded twice gives the same text
+function stableStringify(value: unknown): string {
+  if (Array.isArray(value)) {
+    return `[${value.map(stableStringify).join(",")}]`;
+  }
+  if (value && typeof value === "object") {
+    const entries = Object.entries(value as Record<string, unknown>)
+      .filter(([, entry]) => entry !== undefined)
+      .sort(([a], [b]) => (a < b ? -1 : a > b ? 1 : 0));
+    return `{${entries.map(([key, entry]) => `${JSON.stringify(key)}:${stableStringify(entry)}`).join(",")}}`;
+  }
+  return JSON.stringify(value) ?? "null";
+}
+
+/**
+ * SHA-256 of what the provider is built from. The config only holds encrypted keys, and the digest
+ * keeps even those out of the map keys.
+ */
+export async function providerFingerprint(specs: ModelSpecs): Promise<string> {
+  const { model: _model, ...config } = specs.config ?? {};
+  const text = stableStringify([specs.engine, specs.model_type, config]);
+  const digest = await crypto.subtle.digest("SHA-256", new TextEncoder().encode(text));
+  return Array.from(new Uint8Array(digest), (byte) => byte.toString(16).padStart(2, "0")).join("");
+}
+
+/**
+ * Drop providers not used for `maxIdleMs`, all of them with 0
+ * @returns How many were dropped
+ */
+export function trimProviderCache(maxIdleMs: number, now = Date.now()): number {
+  let evicted = 0;
+  for (const [key, entry] of providers) {
+    if (now - entry.lastUsedAt >= maxIdleMs) {
+      providers.delete(key);
+      evicted++;
+    }
+  }
+  return evicted;
+}
+
+/**
+ * The provider built for this model config, calling `create` only when there is none yet
+ */
+export async function getCachedProvider<T>(specs: ModelSpecs, create: () => Promise<T>, now = Date.now()): Promise<T> {
+  trimProviderCache(PROVIDER_IDLE_TTL_MS, now);
+
+  const key = await providerFingerprint(specs);
+  const cached = providers.get(key);
+  if (cached) {
+    cached.lastUsedAt = now;
+    cached.modelIds.add(specs.id);
+    return cached.provider as Promise<T>;
+  }
+
+  const provider = create();
+  providers.set(key, { provider, modelIds: new Set([specs.id]), lastUsedAt: now });
+  try {
+    return await provider;
+  } catch (error) {
+    // A failed build (e.g. the key couldn't be decrypted) is retried by the next request
+    if (providers.get(key)?.provider === provider) {
+      providers.delete(key);
+    }
+    throw error;
+  }
+}
+
+/**
+ * Forget the providers built for a model, after it was edited or deleted
+ */
+export function invalidateProviders(modelId: string) {
+  for (const [key, entry] of providers) {
+    if (entry.modelIds.has(modelId)) {
+      providers.delete(key);
+    }
+  }
+}
@@ -0,0 +1,124 @@
+import { fetch as tauriFetch } from "@tauri-apps/plugin-http";
+import { decryptApiKey } from "@/commands/security";
+import type { Engine } from "@/schema/model-manifest-schema";
+import type { ModelCapabilities } from "@/schema/models-schema";
+import { ModelModalitySchema } from "@/schema/models-schema";
+
+// Asks the provider what a model supports. Only OpenRouter, Ollama and Gemini describe their models;
+// the other engines fall back to the manifest defaults.
+
+type ProviderCapabilities = Partial<Omit<ModelCapabilities, "source" | "refreshed_at">>;
+
+function toModalities(values: unknown): ModelCapabilities["modality"] | undefined {
+  if (!Array.isArray(values)) {
+    return undefined;
+  }
+  const modalities = values.flatMap((value) => {
+    const parsed = ModelModalitySchema.safeParse(String(value).toLowerCase());
+    return parsed.success ? [parsed.data] : [];
+  });
+  return modalities.length > 0 ? [...new Set(modalities)] : undefined;
+}
+
+function positiveInteger(value: unknown): number | undefined {
+  const number = Number(value);
+  return Number.isInteger(number) && number > 0 ? number : undefined;
+}
+
+/**
+ * One entry of OpenRouter's GET /models:
+ * { id, context_length, architecture: { input_modalities: ["text", "image"] }, supported_parameters: ["tools", "reasoning"] }
+ */
+function parseOpenRouterModel(entry: any): ProviderCapabilities {
+  const modality = toModalities(entry?.architecture?.input_modalities);
+  const parameters: string[] | undefined = Array.isArray(entry?.supported_parameters) ? entry.supported_parameters : undefined;
+  return {
+    context_length: positiveInteger(entry?.context_length ?? entry?.top_provider?.context_length),
+    modality,
+    supports_vision: modality ? modality.includes("image") : undefined,
+    supports_tools: parameters ? parameters.includes("tools") : undefined,
+    supports_reasoning: parameters ? parameters.includes("reasoning") || parameters.includes("include_reasoning") : undefined,
+  };
+}
+
+/**
+ * Ollama's POST /api/show: { capabilities: ["completion", "vision", "tools", "thinking"], model_info: { "llama.context_length": 131072 } }
+ */
+function parseOllamaShow(body: any): ProviderCapabilities {
+  const capabilities: string[] | undefined = Array.isArray(body?.capabilities) ? body.capabilities : undefined;
+  const contextKey = Object.keys(body?.model_info ?? {}).find((key) => key.endsWith(".context_length"));
+  return {
+    context_length: contextKey ? positiveInteger(body.model_info[contextKey]) : undefined,
+    supports_vision: capabilities ? capabilities.includes("vision") : undefined,
+    supports_tools: capabilities ? capabilities.includes("tools") : undefined,
+    supports_reasoning: capabilities ? capabilities.includes("thinking") : undefined,
+    modality: capabilities ? (capabilities.includes("vision") ? ["text", "image"] : ["text"]) : undefined,
+  };
+}
+
+/**
+ * Gemini's GET /v1beta/models/{model}: { inputTokenLimit, supportedGenerationMethods, thinking }
+ */
+function parseGoogleModel(body: any): ProviderCapabilities {
+  return {
+    context_length: positiveInteger(body?.inputTokenLimit),
+    supports_reasoning: typeof body?.thinking === "boolean" ? body.thinking : undefined,
+  };
+}
+
+async function getJSON(url: string, init?: RequestInit): Promise<any> {
+  const response = await tauriFetch(url, init);
+  if (!response.ok) {
+    throw new Error(`${response.status} ${response.statusText}`);
+  }
+  return response.json();
+}
+
+/**
+ * Capabilities reported by the provider for the configured model, or null when the engine can't be asked.
+ * @param engine - The manifest engine
+ * @param config - The model config, with its API key still encrypted
+ * @throws If the provider can be asked but the request fails
+ */
+async function fetchProviderCapabilities(engine: Engine, config: Record<string, any>): Promise<ProviderCapabilities | null> {
+  const modelName = typeof config.model === "string" ? config.model.trim() : "";
+  if (!modelName) {
+    return null;
+  }
+
+  if (engine === "openrouter") {
+    const baseURL = String(config.base_url || "https://openrouter.ai/api/v1").replace(/\/+$/, "");
+    const body = await getJSON(`${baseURL}/models`);
+    const entry = Array.isArray(body?.data) ? body.data.find((model: any) => model?.id === modelName) : undefined;
+    if (!entry) {
+      throw new Error(`OpenRouter does not list the model "${modelName}"`);
+    }
+    return parseOpenRouterModel(entry);
+  }
+
+  if (engine === "ollama") {
+    const baseURL = String(config.base_url || "http://127.0.0.1:11434").replace(/\/+$/, "");
+    const apiKey = config.api_key ? await decryptApiKey(config.api_key) : undefined;
+    const body = await getJSON(`${baseURL}/api/show`, {
+      method: "POST",
+      headers: { "Content-Type": "application/json", ...(apiKey ? { Authorization: `Bearer ${apiKey}` } : {}) },
+      body: JSON.stringify({ model: modelName }),
+    });
+    return parseOllamaShow(body);
+  }
+
+  if (engine === "google") {
+    if (!config.api_key) {
+      return null;
+    }
+    const apiKey = await decryptApiKey(config.api_key);
+    const name = modelName.startsWith("models/") ? modelName : `models/${modelName}`;
+    const body = await getJSON(`https://generativelanguage.googleapis.com/v1beta/${name}`, { headers: { "x-goog-api-key": apiKey } });
+    return parseGoogleModel(body);
+  }
+
+  return null;
+}
+
+export type { ProviderCapabilities };
+export { fetchProviderCapabilities, parseGoogleModel, parseOllamaShow, parseOpenRouterModel };
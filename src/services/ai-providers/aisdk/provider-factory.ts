@@ -7,55 +7,82 @@ import { fetch as tauriFetch } from "@tauri-apps/plugin-http";
 import { createOllama } from "ai-sdk-ollama";
 import { decryptApiKey } from "@/commands/security";
 import { ModelSpecs } from "@/schema/inference-engine-schema";
+import { resolveBedrockConnection } from "./bedrock-endpoint";
+import { createMockLanguageModel } from "./mock-model";
+import { withNormalizedChatResponses } from "./normalize-response";
+import { withPayloadTransforms } from "./payload-transform";
+import { getCachedProvider } from "./provider-cache";
 import { getOllamaModelSettings } from "./provider-options/ollama";
+import { withRawCompletionPrompt } from "./raw-prompt";
+import { withSpeakerNames } from "./speaker-names";
+import { withStreamUsageFallback } from "./stream-usage";
 
-async function getAISDKModel(modelProvider: ModelSpecs, inferenceParameters?: Record<string, any>) {
+async function getAISDKModel(modelProvider: ModelSpecs, inferenceParameters?: Record<string, any>, rawPrompt?: string) {
   const engineName = modelProvider.engine;
   const authParams = modelProvider.config;
 
   const modelName = modelProvider.config.model;
+
+  // Offline engine for UI development and tests, no credentials involved
+  if (engineName === "mock") {
+    return createMockLanguageModel(modelName, authParams);
+  }
+
   if (!modelName && engineName !== "openai_compatible") {
     throw new Error("Model name is required");
   }
 
   const fetchOverride = (input: RequestInfo | URL, init?: RequestInit) => tauriFetch(input, init);
+  // Completion endpoints get a raw prompt as is, before any field mapping of the payload
+  const withRawPrompt = (fetchImpl: typeof fetchOverride) =>
+    rawPrompt !== undefined && modelProvider.model_type === "completion"
+      ? withRawCompletionPrompt(fetchImpl, rawPrompt, inferenceParameters?.stop?.[0] ? inferenceParameters.stop : undefined)
+      : fetchImpl;
+  // A raw prompt is baked into the fetch wrapper, so those requests get a provider of their own
+  const reuse = <T>(create: () => Promise<T>): Promise<T> => (rawPrompt === undefined ? getCachedProvider(modelProvider, create) : create());
 
   if (engineName === "google") {
-    const APIKey = authParams?.api_key ? await decryptApiKey(authParams?.api_key) : "None";
-    const google = createGoogleGenerativeAI({
-      apiKey: APIKey,
-      fetch: fetchOverride,
-    });
+    const google = await reuse(async () =>
+      createGoogleGenerativeAI({
+        apiKey: authParams?.api_key ? await decryptApiKey(authParams?.api_key) : "None",
+        fetch: fetchOverride,
+      }),
+    );
     return google(modelName);
   }
 
   if (engineName === "aws_bedrock") {
-    const AWSSecretAccessKey = authParams?.aws_secret_access_key ? await decryptApiKey(authParams?.aws_secret_access_key) : "None";
-    const awsBedrock = createAmazonBedrock({
-      accessKeyId: authParams?.aws_access_key_id || "",
-      secretAccessKey: AWSSecretAccessKey,
-      region: authParams?.aws_region || "us-east-1",
-    });
+    const { region, baseURL } = resolveBedrockConnection(authParams);
+    const awsBedrock = await reuse(async () =>
+      createAmazonBedrock({
+        accessKeyId: authParams?.aws_access_key_id || "",
+        secretAccessKey: authParams?.aws_secret_access_key ? await decryptApiKey(authParams?.aws_secret_access_key) : "None",
+        region,
+        baseURL,
+      }),
+    );
     return awsBedrock(modelName);
   }
 
   if (engineName === "anthropic") {
-    const APIKey = authParams?.api_key ? await decryptApiKey(authParams?.api_key) : "None";
-    const anthropic = createAnthropic({
-      apiKey: APIKey,
-      baseURL: authParams?.base_url,
-      fetch: fetchOverride,
-    });
+    const anthropic = await reuse(async () =>
+      createAnthropic({
+        apiKey: authParams?.api_key ? await decryptApiKey(authParams?.api_key) : "None",
+        baseURL: authParams?.base_url,
+        fetch: fetchOverride,
+      }),
+    );
     return anthropic(modelName);
   }
 
   if (engineName === "openrouter") {
-    const APIKey = authParams?.api_key ? await decryptApiKey(authParams?.api_key) : "None";
-    const openrouter = createOpenRouter({
-      apiKey: APIKey,
-      baseURL: authParams?.base_url || "https://openrouter.ai/api/v1",
-      fetch: fetchOverride,
-    });
+    const openrouter = await reuse(async () =>
+      createOpenRouter({
+        apiKey: authParams?.api_key ? await decryptApiKey(authParams?.api_key) : "None",
+        baseURL: authParams?.base_url || "https://openrouter.ai/api/v1",
+        fetch: withRawPrompt(withSpeakerNames(fetchOverride)),
+      }),
+    );
 
     if (modelProvider.model_type === "chat") {
       return openrouter.chat(modelName);
@@ -65,41 +92,48 @@ async function getAISDKModel(modelProvider: ModelSpecs, inferenceParameters?: Re
   }
 
   if (engineName === "ollama") {
-    const APIKey = authParams?.api_key ? await decryptApiKey(authParams?.api_key) : undefined;
     const ollamaSettings = getOllamaModelSettings(inferenceParameters ?? {});
-    const ollamaProvider = createOllama({
-      baseURL: authParams?.base_url || "http://127.0.0.1:11434",
-      apiKey: APIKey,
-      fetch: fetchOverride,
-    });
+    const ollamaProvider = await reuse(async () =>
+      createOllama({
+        baseURL: authParams?.base_url || "http://127.0.0.1:11434",
+        apiKey: authParams?.api_key ? await decryptApiKey(authParams?.api_key) : undefined,
+        fetch: fetchOverride,
+      }),
+    );
     return ollamaProvider(modelName, ollamaSettings);
   }
 
   if (engineName === "openai_compatible") {
-    const APIKey = authParams?.api_key ? await decryptApiKey(authParams?.api_key) : "None";
-    const openai = createOpenAI({
-      apiKey: APIKey,
-      baseURL: authParams?.base_url,
-      fetch: fetchOverride,
-    });
+    // Field mappings run closest to the wire, so the normalizer sees the mapped answer and they see the speaker names
+    const transformedFetch = withSpeakerNames(withPayloadTransforms(withStreamUsageFallback(fetchOverride, authParams?.base_url), authParams?.request_transform, authParams?.response_transform));
+    const openai = await reuse(async () =>
+      createOpenAI({
+        apiKey: authParams?.api_key ? await decryptApiKey(authParams?.api_key) : "None",
+        baseURL: authParams?.base_url,
+        // Local servers don't always put the answer where the SDK expects it
+        fetch: modelProvider.model_type === "chat" && !authParams?.response_api ? withNormalizedChatResponses(transformedFetch) : withRawPrompt(transformedFetch),
+      }),
+    );
 
     if (modelProvider.model_type === "chat") {
-      return openai.chat(modelName || "any");
+      return authParams?.response_api ? openai.responses(modelName || "any") : openai.chat(modelName || "any");
     } else {
       return openai.completion(modelName || "any");
     }
   }
 
   // OpenAI (default)
-  const APIKey = authParams?.api_key ? await decryptApiKey(authParams?.api_key) : "None";
-  const openai = createOpenAI({
-    apiKey: APIKey,
-    organization: authParams?.apiOrg,
-    baseURL: authParams?.base_url,
-    fetch: fetchOverride,
-  });
-
-  return openai.chat(modelName);
+  const openai = await reuse(async () =>
+    createOpenAI({
+      apiKey: authParams?.api_key ? await decryptApiKey(authParams?.api_key) : "None",
+      organization: authParams?.apiOrg,
+      baseURL: authParams?.base_url,
+      fetch: withSpeakerNames(withPayloadTransforms(withStreamUsageFallback(fetchOverride, authParams?.base_url), authParams?.request_transform, authParams?.response_transform)),
+    }),
+  );
+
+  // Chat completions stay the default; the Responses API (/v1/responses) is opt-in per model
+  return authParams?.response_api ? openai.responses(modelName) : openai.chat(modelName);
 }
 
 export { getAISDKModel };
@@ -25,9 +25,19 @@ function mapAdaptiveEffort(value: number | undefined): AdaptiveEffort | undefine
   }
 }
 
-function getAWSBedrockProviderOptions(parameters: Record<string, any>, modelName?: string) {
+function getAWSBedrockProviderOptions(parameters: Record<string, any>, modelName?: string, modelConfig?: Record<string, any>) {
   const providerOptions: BedrockProviderOptions = {};
 
+  // Guardrails come from the model config, not the chat template, so they apply to every request of the model
+  const guardrailIdentifier = typeof modelConfig?.guardrail_identifier === "string" ? modelConfig.guardrail_identifier.trim() : "";
+  if (guardrailIdentifier) {
+    providerOptions.guardrailConfig = {
+      guardrailIdentifier,
+      guardrailVersion: String(modelConfig?.guardrail_version ?? "").trim() || "DRAFT",
+      trace: modelConfig?.guardrail_trace ? "enabled" : "disabled",
+    };
+  }
+
   const hasBudget = "reasoning_budget" in parameters && parameters.reasoning_budget > 0;
   const hasEffort = "reasoning_temperature" in parameters && parameters.reasoning_temperature !== -1;
   const explicitlyOff = parameters.reasoning_temperature === -1;
@@ -6,12 +6,12 @@ import { getOpenAIProviderOptions } from "./openai";
 import { getOpenAICompatibleProviderOptions } from "./openai-compatible";
 import { getOpenRouterProviderOptions } from "./openrouter";
 
-function getProviderOptions(engine: Engine, parameters: Record<string, any>, modelName?: string) {
+function getProviderOptions(engine: Engine, parameters: Record<string, any>, modelName?: string, modelConfig?: Record<string, any>) {
   switch (engine) {
     case "google":
       return { google: getGeminiProviderOptions(parameters) };
     case "aws_bedrock":
-      return { bedrock: getAWSBedrockProviderOptions(parameters, modelName) };
+      return { bedrock: getAWSBedrockProviderOptions(parameters, modelName, modelConfig) };
     case "openai":
       return { openai: getOpenAIProviderOptions(parameters) };
     case "openrouter":
@@ -21,6 +21,7 @@ function getProviderOptions(engine: Engine, parameters: Record<string, any>, mod
     case "openai_compatible":
       return { openai: getOpenAICompatibleProviderOptions(parameters) };
     case "ollama":
+    case "mock":
       return {};
     default:
       return {};
@@ -57,3 +57,20 @@ describe("getAnthropicProviderOptions", () => {
     expect(opts.effort).toBeUndefined();
   });
 });
+
+describe("getAWSBedrockProviderOptions guardrails", () => {
+  it("does not send a guardrail when no identifier is configured", () => {
+    const opts = getAWSBedrockProviderOptions({}, OPUS_4_6, { guardrail_identifier: "  " });
+    expect(opts.guardrailConfig).toBeUndefined();
+  });
+
+  it("passes the configured guardrail with trace disabled by default", () => {
+    const opts = getAWSBedrockProviderOptions({}, OPUS_4_6, { guardrail_identifier: "gr-123", guardrail_version: "2" });
+    expect(opts.guardrailConfig).toEqual({ guardrailIdentifier: "gr-123", guardrailVersion: "2", trace: "disabled" });
+  });
+
+  it("defaults to the DRAFT version and enables trace when requested", () => {
+    const opts = getAWSBedrockProviderOptions({}, OPUS_4_6, { guardrail_identifier: "gr-123", guardrail_trace: true });
+    expect(opts.guardrailConfig).toEqual({ guardrailIdentifier: "gr-123", guardrailVersion: "DRAFT", trace: "enabled" });
+  });
+});
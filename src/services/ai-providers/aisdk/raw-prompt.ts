@@ -0,0 +1,65 @@
+// A raw prompt is sent as typed, for users debugging their templates. Completion endpoints get it
+// as the exact `prompt` field: the SDK would otherwise frame it as "user:\n...\n\nassistant:\n".
+// Engines that only speak chat receive it as a single user message.
+
+import type { InferenceMessage, ModelSpecs } from "@/schema/inference-engine-schema";
+
+type FetchFunction = (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>;
+
+interface RawPromptRequest {
+  rawPrompt?: string;
+  messages?: InferenceMessage[];
+  examples?: InferenceMessage[];
+  systemPrompt?: string;
+  modelSpecs: ModelSpecs;
+}
+
+/**
+ * Returns the raw prompt of a request, or undefined when it uses messages.
+ * @throws If the raw prompt comes with messages or examples, since only one of them can be the prompt,
+ * or with a system prompt on a completion model, which would be prepended to the verbatim text
+ */
+function resolveRawPrompt(request: RawPromptRequest): string | undefined {
+  if (request.rawPrompt === undefined) {
+    return undefined;
+  }
+  if (request.messages?.length || request.examples?.length) {
+    throw new Error("Send either a raw prompt or messages, not both");
+  }
+  if (request.modelSpecs.model_type === "completion" && request.systemPrompt) {
+    throw new Error("A raw prompt already is the whole prompt of a completion model, leave the system prompt empty");
+  }
+  return request.rawPrompt;
+}
+
+/**
+ * Replaces the `prompt` of a completion payload with the raw prompt, and its stop sequences with the
+ * request's own, dropping the "\nuser:" one the SDK adds for its chat framing.
+ */
+function applyRawCompletionPrompt(body: unknown, rawPrompt: string, stopSequences?: string[]): unknown {
+  if (typeof body !== "object" || body === null || !("prompt" in body)) {
+    return body;
+  }
+  const { stop: _stop, ...payload } = body as Record<string, unknown>;
+  return stopSequences?.length ? { ...payload, prompt: rawPrompt, stop: stopSequences } : { ...payload, prompt: rawPrompt };
+}
+
+/**
+ * Wraps a fetch so completion requests carry the raw prompt verbatim. Payloads that aren't JSON pass through.
+ */
+function withRawCompletionPrompt(fetchImpl: FetchFunction, rawPrompt: string, stopSequences?: string[]): FetchFunction {
+  return async (input, init) => {
+    if (typeof init?.body !== "string") {
+      return fetchImpl(input, init);
+    }
+    let body: unknown;
+    try {
+      body = JSON.parse(init.body);
+    } catch {
+      return fetchImpl(input, init);
+    }
+    return fetchImpl(input, { ...init, body: JSON.stringify(applyRawCompletionPrompt(body, rawPrompt, stopSequences)) });
+  };
+}
+
+export { applyRawCompletionPrompt, resolveRawPrompt, withRawCompletionPrompt };
@@ -0,0 +1,81 @@
+import { emitToCurrentWindow, listenInCurrentWindow } from "@/commands/windows";
+import type { InferenceParams } from "@/hooks/useInference";
+import { useProfileStore } from "@/hooks/ProfileStore";
+import type { ModelSpecs } from "@/schema/inference-engine-schema";
+import { callProviderConverseEndpoint } from "../start-inference";
+import type { AIEvent, AIRawPart } from "../types/ai-event.type";
+
+// Debug tool for "Unexpected chunk structure" reports: shows exactly what a nonstandard endpoint sends
+
+const RAW_INFERENCE_CHUNK_EVENT = "raw-inference-chunk";
+// Header names that can carry credentials
+const SENSITIVE_HEADER_PATTERN = /auth|api[-_]?key|secret|token|password|cookie|signature|credential|session/i;
+
+type RawInferencePart = AIRawPart | { type: "error"; message: string } | { type: "end" };
+
+interface RawInferenceChunk {
+  requestId: string;
+  // Emit order, events are not guaranteed to arrive in sequence
+  index: number;
+  part: RawInferencePart;
+}
+
+function redactHeaders(headers: Record<string, string>): Record<string, string> {
+  return Object.fromEntries(Object.entries(headers).map(([name, value]) => [name, SENSITIVE_HEADER_PATTERN.test(name) ? `[redacted: ${value.length} chars]` : value]));
+}
+
+function isRawStreamEnabled(): boolean {
+  return import.meta.env.DEV || !!useProfileStore.getState().currentProfile?.settings?.system?.debugMode;
+}
+
+/**
+ * Sends the request through the regular streaming path and emits every raw provider chunk, before any
+ * text/reasoning mapping, as a `raw-inference-chunk` event. Only available in debug mode.
+ * Retries on empty responses are disabled so every chunk belongs to a single attempt.
+ * @returns The mapped text, to compare against the raw chunks
+ */
+async function streamRaw(specs: ModelSpecs, request: Omit<InferenceParams, "modelSpecs" | "stream">): Promise<string> {
+  if (!isRawStreamEnabled()) {
+    throw new Error("Raw streaming is only available with debug mode enabled");
+  }
+
+  const requestId = request.requestId ?? crypto.randomUUID();
+  let index = 0;
+  const publish = (part: RawInferencePart) => {
+    const chunk: RawInferenceChunk = { requestId, index: index++, part };
+    emitToCurrentWindow(RAW_INFERENCE_CHUNK_EVENT, chunk).catch((error) => console.error("Failed to emit raw inference chunk:", error));
+  };
+
+  const event: AIEvent = {
+    requestId,
+    sendStream: () => {},
+    sendError: (error) => publish({ type: "error", message: error.message }),
+    finish: () => publish({ type: "end" }),
+    registerAborter: () => {},
+    sendRaw: (part) => publish(part.type === "headers" ? { type: "headers", headers: redactHeaders(part.headers) } : part),
+  };
+
+  try {
+    return await callProviderConverseEndpoint(event, {
+      ...request,
+      requestId,
+      modelSpecs: specs,
+      stream: true,
+      parameters: { ...request.parameters, retry_on_empty: { enabled: false } },
+    });
+  } catch (error) {
+    publish({ type: "error", message: error instanceof Error ? error.message : String(error) });
+    throw error;
+  }
+}
+
+/**
+ * Subscribe to raw chunks emitted by streamRaw
+ * @returns A function that removes the listener
+ */
+function onRawInferenceChunk(callback: (chunk: RawInferenceChunk) => void): Promise<() => void> {
+  return listenInCurrentWindow<RawInferenceChunk>(RAW_INFERENCE_CHUNK_EVENT, (event) => callback(event.payload));
+}
+
+export type { RawInferenceChunk, RawInferencePart };
+export { onRawInferenceChunk, RAW_INFERENCE_CHUNK_EVENT, redactHeaders, streamRaw };
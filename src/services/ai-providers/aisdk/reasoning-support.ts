@@ -0,0 +1,61 @@
+import type { ModelSpecs } from "@/schema/inference-engine-schema";
+import type { Manifest } from "@/schema/model-manifest-schema";
+import { REASONING_NOT_SUPPORTED } from "../types/ai-event.type";
+
+// Bedrock only accepts a reasoning budget (reasoningConfig) for Claude 3.7 and Claude 4+
+const BEDROCK_BUDGET_REASONING_PATTERN = /claude-3-7|claude-(?:opus|sonnet|haiku)-(?:[4-9]|\d{2})(?!\d)|claude-(?:[4-9]|\d{2})-/;
+
+/**
+ * Engine-level capability from the manifest: reasoning settings are only offered when listed in `inference_fields`.
+ */
+function manifestSupportsReasoning(manifest: Pick<Manifest, "inference_fields"> | null | undefined): boolean | undefined {
+  return manifest?.inference_fields ? manifest.inference_fields.includes("reasoning") : undefined;
+}
+
+/**
+ * Whether the model accepts a reasoning budget. Undefined when unknown, in which case the request is sent as is.
+ * A `supports_reasoning` of "true" or "false" in the model config overrides the detection, "auto" keeps it.
+ * The model's stored capabilities come next, then the manifest and the model name.
+ */
+function resolveReasoningSupport(modelSpecs: ModelSpecs): boolean | undefined {
+  const override = String(modelSpecs.config?.supports_reasoning ?? "auto").toLowerCase();
+  if (override === "true" || override === "false") {
+    return override === "true";
+  }
+  // Refreshed from the provider or the manifest, so it beats the name-based guess below
+  if (typeof modelSpecs.capabilities?.supports_reasoning === "boolean") {
+    return modelSpecs.capabilities.supports_reasoning;
+  }
+  if (modelSpecs.supports_reasoning === false) {
+    return false;
+  }
+  if (modelSpecs.engine === "aws_bedrock") {
+    const modelName = String(modelSpecs.config?.model ?? "");
+    // Application inference profile ARNs don't name the model
+    if (!modelName || modelName.startsWith("arn:")) {
+      return undefined;
+    }
+    return BEDROCK_BUDGET_REASONING_PATTERN.test(modelName);
+  }
+  return modelSpecs.supports_reasoning;
+}
+
+/**
+ * Rejects a reasoning budget for models that can't reason, instead of letting the provider fail with a validation error.
+ */
+function assertReasoningBudgetSupported(modelSpecs: ModelSpecs, parameters: Record<string, any>) {
+  const budget = Number(parameters.reasoning_budget);
+  if (!(budget > 0) || resolveReasoningSupport(modelSpecs) !== false) {
+    return;
+  }
+
+  const modelName = modelSpecs.config?.model ? ` "${modelSpecs.config.model}"` : "";
+  throw Object.assign(
+    new Error(
+      `The model${modelName} does not support reasoning, but the chat template sets a Reasoning Budget of ${budget} tokens. Set the Reasoning Budget to 0, or set "Supports Reasoning" to true on the model if it does.`,
+    ),
+    { code: REASONING_NOT_SUPPORTED },
+  );
+}
+
+export { assertReasoningBudgetSupported, manifestSupportsReasoning, resolveReasoningSupport };
@@ -0,0 +1,151 @@
+import { type AIError, type AIEvent, type AIStreamPayload, EMPTY_RESPONSE } from "../types/ai-event.type";
+import { classifyInferenceError } from "./inference-errors";
+
+// Some providers occasionally answer 200 with no content, or fail with a retryable error (rate limit, timeout...)
+// before sending anything; re-sending the same request almost always works
+interface RetryOnEmptySettings {
+  enabled: boolean;
+  max_attempts: number;
+}
+
+const DEFAULT_RETRY_ON_EMPTY: RetryOnEmptySettings = { enabled: true, max_attempts: 1 };
+const MAX_RETRY_ATTEMPTS = 5;
+
+function resolveRetryOnEmpty(value: unknown): RetryOnEmptySettings {
+  if (typeof value !== "object" || value === null) {
+    return DEFAULT_RETRY_ON_EMPTY;
+  }
+  const settings = value as Partial<RetryOnEmptySettings>;
+  const maxAttempts = Number(settings.max_attempts ?? DEFAULT_RETRY_ON_EMPTY.max_attempts);
+
+  return {
+    enabled: settings.enabled ?? DEFAULT_RETRY_ON_EMPTY.enabled,
+    max_attempts: Number.isFinite(maxAttempts) ? Math.min(MAX_RETRY_ATTEMPTS, Math.max(0, Math.floor(maxAttempts))) : DEFAULT_RETRY_ON_EMPTY.max_attempts,
+  };
+}
+
+type AttemptOutcome = { kind: "settled" } | { kind: "failed"; error: AIError } | { kind: "completed"; payload: AIStreamPayload };
+
+/**
+ * Wraps the request event for one attempt. Everything is forwarded except `finish` and errors flagged
+ * `retryable` that arrive before any output, which are held back so the attempt can be re-sent.
+ */
+function createAttemptEvent(event: AIEvent) {
+  let errored = false;
+  let retryableError: AIError | undefined;
+  let cancelled = false;
+  let sawOutput = false;
+  let finishPayload: AIStreamPayload | undefined;
+
+  const attemptEvent: AIEvent = {
+    ...event,
+    sendStream: (payload) => {
+      if (payload.text?.trim() || payload.reasoning || payload.toolCalls?.length) {
+        sawOutput = true;
+      }
+      event.sendStream(payload);
+    },
+    sendThinkingStream: event.sendThinkingStream
+      ? (text) => {
+          sawOutput = true;
+          event.sendThinkingStream?.(text);
+        }
+      : undefined,
+    sendToolCallStart: event.sendToolCallStart
+      ? (payload) => {
+          sawOutput = true;
+          event.sendToolCallStart?.(payload);
+        }
+      : undefined,
+    sendError: (error) => {
+      // Follow-up errors of a held back failure (e.g. the "error" finish reason) are dropped with it
+      if (retryableError) {
+        return;
+      }
+      if (error.retryable && !sawOutput && !errored) {
+        retryableError = error;
+        return;
+      }
+      errored = true;
+      event.sendError(error);
+    },
+    finish: (payload) => {
+      finishPayload = payload ?? {};
+    },
+    registerAborter: (aborter) => {
+      event.registerAborter(() => {
+        cancelled = true;
+        aborter();
+      });
+    },
+  };
+
+  // Non-streaming calls return the text instead of calling finish
+  const outcome = (response: string, finishedByCaller: boolean): AttemptOutcome => {
+    if (errored || cancelled) {
+      return { kind: "settled" };
+    }
+    if (retryableError) {
+      return { kind: "failed", error: retryableError };
+    }
+    const payload = finishedByCaller ? { fullResponse: response } : finishPayload;
+    if (!payload) {
+      return { kind: "settled" };
+    }
+    if (!sawOutput && !(payload.fullResponse ?? response).trim() && !payload.toolCalls?.length) {
+      return { kind: "failed", error: emptyResponseError(1) };
+    }
+    return { kind: "completed", payload };
+  };
+
+  return { attemptEvent, outcome };
+}
+
+function emptyResponseError(attempts: number): AIError {
+  return {
+    message: `The model returned an empty response${attempts > 1 ? ` ${attempts} times in a row` : ""}. Try a different temperature, a higher max tokens, or fewer stop strings.`,
+    code: EMPTY_RESPONSE,
+    retryable: true,
+    details: { attempts },
+  };
+}
+
+/**
+ * Runs the request, re-sending it while the attempt failed with a `retryable` error (an empty answer counts
+ * as one) and attempts remain. Gives up with the last error, `empty_response` suggesting parameter changes.
+ */
+async function runWithEmptyRetry(event: AIEvent, retryOnEmpty: unknown, run: (attemptEvent: AIEvent) => Promise<string>, finishedByCaller: boolean): Promise<string> {
+  const settings = resolveRetryOnEmpty(retryOnEmpty);
+  const maxRetries = settings.enabled ? settings.max_attempts : 0;
+
+  for (let retries = 0; ; retries++) {
+    const { attemptEvent, outcome } = createAttemptEvent(event);
+    let response = "";
+    try {
+      response = await run(attemptEvent);
+    } catch (error) {
+      const classified = classifyInferenceError(error);
+      if (classified.code === "cancelled") {
+        return response;
+      }
+      attemptEvent.sendError(classified);
+    }
+    const result = outcome(response, finishedByCaller);
+
+    if (result.kind === "settled") {
+      return response;
+    }
+    if (result.kind === "completed") {
+      event.finish(retries > 0 ? { ...result.payload, emptyRetries: retries } : result.payload);
+      return response;
+    }
+    if (retries >= maxRetries) {
+      event.sendError(result.error.code === EMPTY_RESPONSE ? emptyResponseError(retries + 1) : result.error);
+      return response;
+    }
+    console.warn(`${result.error.code === EMPTY_RESPONSE ? "Empty response from the model" : `Retryable ${result.error.code} error`}, retrying (${retries + 1}/${maxRetries})`);
+  }
+}
+
+export type { RetryOnEmptySettings };
+export { resolveRetryOnEmpty, runWithEmptyRetry };
@@ -0,0 +1,128 @@
+import type { InferenceMessage, ModelSpecs } from "@/schema/inference-engine-schema";
+import type { Engine } from "@/schema/model-manifest-schema";
+import { isGeminiTarget } from "./normalize-roles";
+
+// With `inject_speaker_names`, each user and assistant turn tells the model who is speaking. OpenAI-style
+// chat endpoints take it as the message `name` field; other engines get "Name: " in front of the text.
+// The SDK has no per-message name, so the names of a request travel in a header that the fetch wrapper
+// removes again, putting them on the payload's messages. That keeps the cached providers request-agnostic.
+
+type FetchFunction = (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>;
+
+const SPEAKER_NAMES_HEADER = "x-narratrix-speaker-names";
+
+// OpenAI only accepts letters, digits, underscores and dashes, up to 64 characters
+const MESSAGE_NAME_MAX_LENGTH = 64;
+
+function isNamedRole(role: unknown): boolean {
+  return role === "user" || role === "assistant";
+}
+
+/**
+ * Whether the engine sends the speaker as the message `name` field: OpenAI, OpenAI-compatible and OpenRouter
+ * chat completions. The Responses API and Gemini behind an OpenAI-compatible endpoint don't take it.
+ */
+function supportsMessageNames(modelSpecs: ModelSpecs): boolean {
+  const engine = modelSpecs.engine as Engine;
+  if (modelSpecs.model_type !== "chat" || modelSpecs.config?.response_api) {
+    return false;
+  }
+  if (engine !== "openai" && engine !== "openai_compatible" && engine !== "openrouter") {
+    return false;
+  }
+  return !isGeminiTarget(engine, modelSpecs.config);
+}
+
+/**
+ * A speaker name in the form the `name` field accepts, or undefined when nothing usable is left
+ */
+function toMessageName(name: string | undefined): string | undefined {
+  const sanitized = (name ?? "")
+    .trim()
+    .replace(/[^A-Za-z0-9_-]+/g, "_")
+    .replace(/^_+|_+$/g, "")
+    .slice(0, MESSAGE_NAME_MAX_LENGTH);
+  return sanitized || undefined;
+}
+
+/**
+ * Puts "Name: " in front of named user and assistant turns, for engines without a `name` field.
+ * Text the format template already prefixed with the same name is left as is.
+ */
+function prefixSpeakerNames(messages: InferenceMessage[]): InferenceMessage[] {
+  return messages.map((message) => {
+    const name = message.name?.trim();
+    if (!name || !isNamedRole(message.role) || message.text.startsWith(`${name}:`)) {
+      return message;
+    }
+    return { ...message, text: `${name}: ${message.text}` };
+  });
+}
+
+/**
+ * Request headers carrying the names of the user and assistant turns, in order. None when no turn is named.
+ */
+function speakerNamesHeaders(messages: InferenceMessage[]): Record<string, string> | undefined {
+  const names = messages.filter((message) => isNamedRole(message.role)).map((message) => toMessageName(message.name) ?? null);
+  if (!names.some(Boolean)) {
+    return undefined;
+  }
+  return { [SPEAKER_NAMES_HEADER]: encodeURIComponent(JSON.stringify(names)) };
+}
+
+/**
+ * Sets `name` on the user and assistant messages of a chat payload, matching them in order with `names`.
+ * Payloads without a `messages` list are returned unchanged.
+ */
+function applySpeakerNames(body: unknown, names: (string | null)[]): unknown {
+  if (typeof body !== "object" || body === null || !Array.isArray((body as { messages?: unknown }).messages)) {
+    return body;
+  }
+  let turn = 0;
+  const messages = (body as { messages: unknown[] }).messages.map((message) => {
+    if (typeof message !== "object" || message === null || !isNamedRole((message as { role?: unknown }).role)) {
+      return message;
+    }
+    const name = names[turn++];
+    return name ? { ...message, name } : message;
+  });
+  return { ...body, messages };
+}
+
+function parseNames(header: string): (string | null)[] | undefined {
+  try {
+    const names: unknown = JSON.parse(decodeURIComponent(header));
+    return Array.isArray(names) ? names.map((name) => (typeof name === "string" ? name : null)) : undefined;
+  } catch {
+    return undefined;
+  }
+}
+
+/**
+ * Wraps a fetch so the speaker names header of a request ends up as message names. The header never
+ * reaches the provider; requests without it pass through untouched.
+ */
+function withSpeakerNames(fetchImpl: FetchFunction): FetchFunction {
+  return async (input, init) => {
+    const headers = new Headers(init?.headers);
+    const header = headers.get(SPEAKER_NAMES_HEADER);
+    if (header === null) {
+      return fetchImpl(input, init);
+    }
+    headers.delete(SPEAKER_NAMES_HEADER);
+
+    const names = parseNames(header);
+    if (!names || typeof init?.body !== "string") {
+      return fetchImpl(input, { ...init, headers });
+    }
+    let body: unknown;
+    try {
+      body = JSON.parse(init.body);
+    } catch {
+      return fetchImpl(input, { ...init, headers });
+    }
+    return fetchImpl(input, { ...init, headers, body: JSON.stringify(applySpeakerNames(body, names)) });
+  };
+}
+
+export { applySpeakerNames, prefixSpeakerNames, SPEAKER_NAMES_HEADER, speakerNamesHeaders, supportsMessageNames, toMessageName, withSpeakerNames };
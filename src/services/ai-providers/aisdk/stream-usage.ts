@@ -0,0 +1,68 @@
+// The SDK asks for token usage on streams with `stream_options: { include_usage: true }`. Some
+// OpenAI-compatible servers reject the unknown field instead of ignoring it. Those get the request
+// again without it, and their base URL is remembered so later streams skip the failing attempt.
+// Usage is then simply missing for that server.
+
+type FetchFunction = (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>;
+
+const STREAM_OPTIONS_ERROR_PATTERN = /stream_options|include_usage/i;
+
+// Base URLs known to reject `stream_options`, for the lifetime of the app
+const unsupportedEndpoints = new Set<string>();
+
+function endpointKey(baseUrl: string | undefined): string {
+  return (baseUrl ?? "").trim().replace(/\/+$/, "").toLowerCase();
+}
+
+function supportsStreamUsage(baseUrl: string | undefined): boolean {
+  return !unsupportedEndpoints.has(endpointKey(baseUrl));
+}
+
+/**
+ * Whether an error response is the server refusing `stream_options`. Only client errors naming the
+ * field count, so auth or rate limit failures are never retried.
+ */
+function isStreamOptionsRejection(status: number, errorBody: string): boolean {
+  return status >= 400 && status < 500 && status !== 401 && status !== 403 && status !== 429 && STREAM_OPTIONS_ERROR_PATTERN.test(errorBody);
+}
+
+/**
+ * Wraps a fetch so servers at `baseUrl` that reject `stream_options` still stream, retrying once
+ * without the field. Requests without it and non-JSON payloads pass through.
+ */
+function withStreamUsageFallback(fetchImpl: FetchFunction, baseUrl: string | undefined): FetchFunction {
+  return async (input, init) => {
+    if (typeof init?.body !== "string") {
+      return fetchImpl(input, init);
+    }
+    let body: Record<string, unknown>;
+    try {
+      body = JSON.parse(init.body);
+    } catch {
+      return fetchImpl(input, init);
+    }
+    if (typeof body !== "object" || body === null || !("stream_options" in body)) {
+      return fetchImpl(input, init);
+    }
+
+    const { stream_options: _streamOptions, ...withoutStreamOptions } = body;
+    const retryInit = { ...init, body: JSON.stringify(withoutStreamOptions) };
+    if (!supportsStreamUsage(baseUrl)) {
+      return fetchImpl(input, retryInit);
+    }
+
+    const response = await fetchImpl(input, init);
+    if (response.ok) {
+      return response;
+    }
+    const errorBody = await response.clone().text().catch(() => "");
+    if (!isStreamOptionsRejection(response.status, errorBody)) {
+      return response;
+    }
+
+    unsupportedEndpoints.add(endpointKey(baseUrl));
+    return fetchImpl(input, retryInit);
+  };
+}
+
+export { isStreamOptionsRejection, supportsStreamUsage, withStreamUsageFallback };
@@ -1,31 +1,16 @@
 import { stepCountIs, streamText } from "ai";
 import { FinalParams } from "../start-inference";
-import type { AIEvent } from "../types/ai-event.type";
+import { type AIEvent, GUARDRAIL_INTERVENED, TRUNCATED_BEFORE_ANSWER } from "../types/ai-event.type";
+import { createContextUsageTracker, promptTextOf } from "./context-usage";
+import { guardrailBlockedMessage, isMaskOnlyIntervention, summarizeGuardrail } from "./guardrail";
+import { classifyInferenceError, finishReasonError } from "./inference-errors";
+import { createTextDeltaBuffer } from "./text-boundaries";
+import { toAIUsage } from "./usage";
 
-function getErrorMessage(error: unknown): string {
-  if (error instanceof Error) {
-    return error.message;
-  }
-
-  if (typeof error === "object" && error !== null) {
-    // Handle wrapper objects like { error: ... }
-    if ("error" in error) {
-      const nestedError = (error as any).error;
-      // Prevent infinite recursion if error points to itself
-      if (nestedError !== error) {
-        return getErrorMessage(nestedError);
-      }
-    }
-    // Handle plain objects with message
-    if ("message" in error) {
-      return String((error as any).message);
-    }
-  }
-
-  return String(error);
-}
-
-async function streamResponse(event: AIEvent, params: FinalParams): Promise<string> {
+/**
+ * @param contextLimit - The model's context window; when set, `context-usage` events warn as prompt and output fill it
+ */
+async function streamResponse(event: AIEvent, params: FinalParams, contextLimit?: number): Promise<string> {
   const abortController = new AbortController();
   let isAborted = false;
 
@@ -35,18 +20,52 @@ async function streamResponse(event: AIEvent, params: FinalParams): Promise<stri
   });
 
   let fullText = "";
+  let reasoningText = "";
+  let finishReason: string | undefined;
+  const deltaBuffer = createTextDeltaBuffer();
+  const contextUsage = contextLimit ? createContextUsageTracker({ requestId: event.requestId, limit: contextLimit, promptText: promptTextOf(params.system, params.messages) }) : null;
 
   try {
     const { textStream } = streamText({
       ...params,
       stopWhen: stepCountIs(15),
       abortSignal: abortController.signal,
-      onError: (error) => {
-        event.sendError({ message: getErrorMessage(error) });
+      includeRawChunks: !!event.sendRaw,
+      onError: ({ error }) => {
+        event.sendError(classifyInferenceError(error));
       },
-      onFinish({ finishReason }) {
-        if (finishReason !== "stop") {
-          event.sendError({ message: `Inference stopped: ${finishReason}` });
+      onFinish({ finishReason: reason, providerMetadata, response, totalUsage }) {
+        finishReason = reason;
+
+        const usage = toAIUsage(totalUsage);
+        if (usage) {
+          event.sendStream({ usage });
+        }
+
+        if (event.sendRaw && response.headers) {
+          event.sendRaw({ type: "headers", headers: response.headers });
+        }
+
+        const guardrail = summarizeGuardrail(providerMetadata);
+        if (guardrail && (guardrail.masked.length > 0 || guardrail.trace)) {
+          event.sendStream({ notices: guardrail.masked, guardrailTrace: guardrail.trace });
+        }
+
+        if (reason === "content-filter" && providerMetadata?.bedrock) {
+          if (!isMaskOnlyIntervention(guardrail)) {
+            event.sendError({ message: guardrailBlockedMessage(guardrail), code: GUARDRAIL_INTERVENED, details: guardrail?.blocked });
+          }
+        } else if (reason !== "stop" && !fullText.trim() && reasoningText.trim()) {
+          // The model spent its output budget thinking; the caller still gets the reasoning
+          event.sendError({
+            message: `Inference stopped (${reason}) before an answer was produced. Increase max tokens or lower the reasoning budget.`,
+            code: TRUNCATED_BEFORE_ANSWER,
+          });
+        } else {
+          const stopError = finishReasonError(reason, providerMetadata);
+          if (stopError) {
+            event.sendError(stopError);
+          }
         }
       },
       onChunk: ({ chunk }) => {
@@ -58,7 +77,11 @@ async function streamResponse(event: AIEvent, params: FinalParams): Promise<stri
         //     text: chunk.text,
         //   });
         // }
-        if (chunk.type === "reasoning-delta") {
+        if (chunk.type === "raw") {
+          event.sendRaw?.({ type: "chunk", value: chunk.rawValue });
+        } else if (chunk.type === "reasoning-delta") {
+          reasoningText += chunk.text;
+          contextUsage?.push(chunk.text);
           event.sendStream({
             reasoning: chunk.text,
           });
@@ -71,16 +94,24 @@ async function streamResponse(event: AIEvent, params: FinalParams): Promise<stri
         break;
       }
       fullText += textPart;
+      contextUsage?.push(textPart);
 
-      // Direct streaming
-      event.sendStream({
-        text: textPart,
-      });
+      // Direct streaming, minus a character cut in half at the end of the delta
+      const text = deltaBuffer.push(textPart);
+      if (text) {
+        event.sendStream({
+          text,
+        });
+      }
     }
 
     // Signal completion if not aborted
     if (!isAborted) {
-      event.finish({ fullResponse: fullText });
+      const rest = deltaBuffer.flush();
+      if (rest) {
+        event.sendStream({ text: rest });
+      }
+      event.finish({ fullResponse: fullText, finishReason });
     }
   } catch (error) {
     // Check if error is due to abort or no output
@@ -89,7 +120,7 @@ async function streamResponse(event: AIEvent, params: FinalParams): Promise<stri
     if (!isAbortError) {
       console.error("Stream Error:", error);
       if (!isAborted) {
-        event.sendError({ message: getErrorMessage(error) });
+        event.sendError(classifyInferenceError(error));
       }
     }
   }
@@ -0,0 +1,29 @@
+import { describe, expect, it } from "vitest";
+import { resolveBedrockConnection } from "../bedrock-endpoint";
+
+describe("resolveBedrockConnection", () => {
+  it("uses the standard regional endpoint by default", () => {
+    expect(resolveBedrockConnection({ aws_region: " EU-Central-1 " })).toEqual({ region: "eu-central-1" });
+    expect(resolveBedrockConnection({})).toEqual({ region: "us-east-1" });
+    expect(resolveBedrockConnection({ aws_region: "us-gov-west-1" })).toEqual({ region: "us-gov-west-1" });
+  });
+
+  it("switches to the FIPS endpoint with the flag", () => {
+    expect(resolveBedrockConnection({ aws_region: "us-east-2", use_fips: true })).toEqual({
+      region: "us-east-2",
+      baseURL: "https://bedrock-runtime-fips.us-east-2.amazonaws.com",
+    });
+  });
+
+  it("prefers a custom endpoint, keeping the region for signing", () => {
+    expect(
+      resolveBedrockConnection({ aws_region: "us-east-1", use_fips: "true", endpoint_url: "https://vpce-0abc.bedrock-runtime.us-east-1.vpce.amazonaws.com/" }),
+    ).toEqual({ region: "us-east-1", baseURL: "https://vpce-0abc.bedrock-runtime.us-east-1.vpce.amazonaws.com" });
+  });
+
+  it("rejects invalid regions and endpoints", () => {
+    expect(() => resolveBedrockConnection({ aws_region: "bedrock-runtime.us-east-1.amazonaws.com" })).toThrow(/Invalid AWS region/);
+    expect(() => resolveBedrockConnection({ aws_region: "us-east-1", endpoint_url: "not a url" })).toThrow(/Invalid Bedrock endpoint URL/);
+    expect(() => resolveBedrockConnection({ aws_region: "us-east-1", endpoint_url: "http://vpce.example.com" })).toThrow(/https/);
+  });
+});
@@ -0,0 +1,92 @@
+import type { ModelMessage } from "ai";
+import { beforeEach, describe, expect, it } from "vitest";
+import { clearStablePrefix, getCacheEfficiency, prefixHashes, trackStablePrefix } from "../cache-prefix";
+import { applyPromptCache } from "../prompt-cache";
+
+const CHAT_ID = "chat-1";
+
+function conversation(...contents: string[]): ModelMessage[] {
+  return [{ role: "system", content: "System" }, ...contents.map((content, i): ModelMessage => (i % 2 === 0 ? { role: "user", content } : { role: "assistant", content }))];
+}
+
+function withoutProviderOptions(messages: ModelMessage[]) {
+  return messages.map(({ providerOptions: _providerOptions, ...message }) => message);
+}
+
+function cachedIndexes(messages: ModelMessage[]): number[] {
+  return messages.flatMap((message, index) => (message.providerOptions ? [index] : []));
+}
+
+describe("trackStablePrefix", () => {
+  beforeEach(() => clearStablePrefix(CHAT_ID));
+
+  it("has no stable prefix on the first request", () => {
+    expect(trackStablePrefix(CHAT_ID, conversation("a", "b"))).toBe(0);
+    expect(getCacheEfficiency(CHAT_ID)).toEqual({ stableMessages: 0, totalMessages: 3, ratio: 0 });
+  });
+
+  it("keeps the whole previous prompt when messages are appended", () => {
+    trackStablePrefix(CHAT_ID, conversation("a", "b"));
+    expect(trackStablePrefix(CHAT_ID, conversation("a", "b", "c", "d"))).toBe(3);
+  });
+
+  it("stops at the first edited message", () => {
+    trackStablePrefix(CHAT_ID, conversation("a", "b", "c", "d"));
+    expect(trackStablePrefix(CHAT_ID, conversation("a", "edited", "c", "d", "e"))).toBe(2);
+
+    const efficiency = getCacheEfficiency(CHAT_ID)!;
+    expect(efficiency.stableMessages).toBe(2);
+    expect(efficiency.totalMessages).toBe(6);
+    expect(efficiency.ratio).toBeGreaterThan(0);
+    expect(efficiency.ratio).toBeLessThan(1);
+  });
+
+  it("ignores cache markers when comparing", () => {
+    const messages = conversation("a", "b");
+    trackStablePrefix(CHAT_ID, applyPromptCache("anthropic", messages, { cache_last_n_messages: 2 }));
+    expect(trackStablePrefix(CHAT_ID, messages)).toBe(3);
+    expect(getCacheEfficiency(CHAT_ID)?.ratio).toBe(1);
+  });
+
+  it("tracks chats separately", () => {
+    trackStablePrefix(CHAT_ID, conversation("a"));
+    expect(trackStablePrefix("chat-2", conversation("a"))).toBe(0);
+    expect(getCacheEfficiency("unknown")).toBeNull();
+  });
+
+  it("chains the hashes so a later prefix depends on every earlier message", () => {
+    const first = prefixHashes(conversation("a", "b"));
+    const second = prefixHashes(conversation("x", "b"));
+    expect(first[0]).toBe(second[0]);
+    expect(first[2]).not.toBe(second[2]);
+  });
+});
+
+describe("applyPromptCache with a stable prefix", () => {
+  it("adds a breakpoint at the end of the stable prefix", () => {
+    const messages = conversation("a", "edited", "c", "d", "e");
+    const result = applyPromptCache("anthropic", messages, { cache_system_prompt: true, cache_last_n_messages: 1 }, 2);
+    expect(cachedIndexes(result)).toEqual([0, 1, 5]);
+  });
+
+  it("keeps the stable prefix breakpoint within the provider limit", () => {
+    const result = applyPromptCache("aws_bedrock", conversation("a", "b", "c", "d", "e", "f"), { cache_system_prompt: true, cache_last_n_messages: 10 }, 2);
+    expect(cachedIndexes(result)).toEqual([0, 1, 5, 6]);
+  });
+
+  it("skips a prefix that ends on the system prompt", () => {
+    const result = applyPromptCache("anthropic", conversation("a", "b"), { cache_system_prompt: false, cache_last_n_messages: 1 }, 1);
+    expect(cachedIndexes(result)).toEqual([2]);
+  });
+
+  it("never changes the prompt itself", () => {
+    const messages = conversation("a", "edited", "c", "d");
+    const result = applyPromptCache("anthropic", messages, { cache_system_prompt: true, cache_last_n_messages: 2 }, 2);
+    expect(withoutProviderOptions(result)).toEqual(messages);
+  });
+
+  it("does nothing when caching is off", () => {
+    const messages = conversation("a", "b");
+    expect(applyPromptCache("anthropic", messages, {}, 2)).toBe(messages);
+  });
+});
@@ -0,0 +1,110 @@
+import { describe, expect, it, vi } from "vitest";
+import type { InferenceMessage, ModelSpecs } from "@/schema/inference-engine-schema";
+import type { ModelCapabilities } from "@/schema/models-schema";
+import { CAPABILITY_NOT_SUPPORTED } from "../../types/ai-event.type";
+import { assertCapabilitiesSupported } from "../capability-checks";
+import { parseGoogleModel, parseOllamaShow, parseOpenRouterModel } from "../provider-capabilities";
+import { resolveReasoningSupport } from "../reasoning-support";
+
+vi.mock("@tauri-apps/plugin-http", () => ({ fetch: vi.fn() }));
+vi.mock("@/commands/security", () => ({ decryptApiKey: vi.fn(async (value: string) => value) }));
+
+const UNKNOWN: ModelCapabilities = {
+  context_length: null,
+  supports_vision: null,
+  supports_tools: null,
+  supports_reasoning: null,
+  modality: null,
+  source: "manifest",
+  refreshed_at: null,
+};
+
+function specs(capabilities: Partial<ModelCapabilities> | null, supports_reasoning?: boolean): ModelSpecs {
+  return {
+    id: "model-1",
+    model_type: "chat",
+    config: { model: "small-model" },
+    max_concurrent_requests: 1,
+    engine: "ollama",
+    supports_reasoning,
+    capabilities: capabilities && { ...UNKNOWN, ...capabilities },
+  };
+}
+
+const textMessages: InferenceMessage[] = [{ role: "user", text: "Hello" }];
+const imageMessages = [{ role: "user", text: "What is this?", files: ["data:image/png;base64,AAAA"] }] as InferenceMessage[];
+
+function codeOf(run: () => void): string | undefined {
+  try {
+    run();
+  } catch (error) {
+    return (error as { code?: string }).code;
+  }
+  return undefined;
+}
+
+describe("assertCapabilitiesSupported", () => {
+  it("lets everything through when the capabilities are unknown", () => {
+    expect(() => assertCapabilitiesSupported(specs(null), imageMessages, { max_context: 1_000_000 }, true)).not.toThrow();
+    expect(() => assertCapabilitiesSupported(specs({}), imageMessages, { max_context: 1_000_000 }, true)).not.toThrow();
+  });
+
+  it("rejects image attachments on models without vision", () => {
+    expect(codeOf(() => assertCapabilitiesSupported(specs({ supports_vision: false }), imageMessages, {}, false))).toBe(CAPABILITY_NOT_SUPPORTED);
+    expect(() => assertCapabilitiesSupported(specs({ supports_vision: false }), textMessages, {}, false)).not.toThrow();
+    expect(() => assertCapabilitiesSupported(specs({ supports_vision: true }), imageMessages, {}, false)).not.toThrow();
+  });
+
+  it("rejects tools on models without tool support", () => {
+    expect(codeOf(() => assertCapabilitiesSupported(specs({ supports_tools: false }), textMessages, {}, true))).toBe(CAPABILITY_NOT_SUPPORTED);
+    expect(() => assertCapabilitiesSupported(specs({ supports_tools: false }), textMessages, {}, false)).not.toThrow();
+  });
+
+  it("rejects a template context larger than the model's", () => {
+    expect(codeOf(() => assertCapabilitiesSupported(specs({ context_length: 8192 }), textMessages, { max_context: 32000 }, false))).toBe("context_overflow");
+    expect(() => assertCapabilitiesSupported(specs({ context_length: 8192 }), textMessages, { max_context: 8000 }, false)).not.toThrow();
+  });
+});
+
+describe("resolveReasoningSupport with capabilities", () => {
+  it("prefers the stored capability over the manifest", () => {
+    expect(resolveReasoningSupport(specs({ supports_reasoning: false }, true))).toBe(false);
+    expect(resolveReasoningSupport(specs({ supports_reasoning: true }, false))).toBe(true);
+    expect(resolveReasoningSupport(specs({}, true))).toBe(true);
+  });
+});
+
+describe("provider capability parsers", () => {
+  it("reads OpenRouter model entries", () => {
+    expect(
+      parseOpenRouterModel({
+        id: "vendor/model",
+        context_length: 131072,
+        architecture: { input_modalities: ["text", "image", "unknown"] },
+        supported_parameters: ["tools", "temperature"],
+      }),
+    ).toEqual({ context_length: 131072, modality: ["text", "image"], supports_vision: true, supports_tools: true, supports_reasoning: false });
+  });
+
+  it("reads Ollama show responses", () => {
+    expect(parseOllamaShow({ capabilities: ["completion", "tools", "thinking"], model_info: { "qwen3.context_length": 40960 } })).toEqual({
+      context_length: 40960,
+      supports_vision: false,
+      supports_tools: true,
+      supports_reasoning: true,
+      modality: ["text"],
+    });
+    // Older Ollama versions don't report capabilities
+    expect(parseOllamaShow({ model_info: {} })).toEqual({
+      context_length: undefined,
+      supports_vision: undefined,
+      supports_tools: undefined,
+      supports_reasoning: undefined,
+      modality: undefined,
+    });
+  });
+
+  it("reads Gemini model descriptions", () => {
+    expect(parseGoogleModel({ name: "models/gemini-2.5-flash", inputTokenLimit: 1048576, thinking: true })).toEqual({ context_length: 1048576, supports_reasoning: true });
+  });
+});
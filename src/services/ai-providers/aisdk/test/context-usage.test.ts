@@ -0,0 +1,93 @@
+import { describe, expect, it, vi } from "vitest";
+import { type ContextUsageEvent, createContextUsageTracker, EXACT_COUNT_INTERVAL_MS, promptTextOf, resolveContextUsageLimit } from "../context-usage";
+
+vi.mock("@/commands/windows", () => ({ emitToCurrentWindow: vi.fn(async () => undefined), listenInCurrentWindow: vi.fn() }));
+vi.mock("@/commands/inference", () => ({ countTokens: vi.fn(async () => ({ count: 0 })) }));
+
+// 400 characters, about 100 tokens by the estimate
+const PROMPT = "p".repeat(400);
+
+const tracker = (counts: number[] = []) => {
+  let clock = 0;
+  const events: ContextUsageEvent[] = [];
+  const countTokens = vi.fn(async () => counts.shift() ?? 0);
+  const usage = createContextUsageTracker({ requestId: "req-1", limit: 1000, promptText: PROMPT, countTokens, emit: (event) => events.push(event), now: () => clock });
+  return {
+    usage,
+    events,
+    countTokens,
+    advance: (ms: number) => {
+      clock += ms;
+    },
+  };
+};
+
+describe("resolveContextUsageLimit", () => {
+  it("uses the model's context length unless turned off", () => {
+    expect(resolveContextUsageLimit(8192, {})).toBe(8192);
+    expect(resolveContextUsageLimit(8192, { context_usage_events: false })).toBeUndefined();
+    expect(resolveContextUsageLimit(null, {})).toBeUndefined();
+    expect(resolveContextUsageLimit(undefined, {})).toBeUndefined();
+  });
+});
+
+describe("promptTextOf", () => {
+  it("joins the system prompt and the text of every message", () => {
+    expect(
+      promptTextOf("System", [
+        { role: "user", content: "Hi" },
+        { role: "assistant", content: [{ type: "text", text: "Hello" }] },
+      ]),
+    ).toBe("System\nHi\nHello");
+  });
+});
+
+describe("createContextUsageTracker", () => {
+  it("emits each threshold once as the output grows", () => {
+    const { usage, events } = tracker([100]);
+
+    // 100 prompt tokens + 600 output tokens = 70%
+    usage.push("o".repeat(2400));
+    expect(events.map((event) => event.threshold)).toEqual([0.7]);
+
+    usage.push("o");
+    expect(events).toHaveLength(1);
+
+    // 100 + 900 = 100%: 85% and 95% are both crossed, only the highest is reported
+    usage.push("o".repeat(1200));
+    expect(events.map((event) => event.threshold)).toEqual([0.7, 0.95]);
+    expect(events[1]).toMatchObject({ type: "context_usage", requestId: "req-1", limit: 1000 });
+    expect(events[1].used).toBeGreaterThanOrEqual(950);
+  });
+
+  it("counts exactly at most once per interval", async () => {
+    const { usage, countTokens, advance } = tracker([100, 10, 20]);
+    // The prompt is counted right away
+    expect(countTokens).toHaveBeenCalledTimes(1);
+    await new Promise((resolve) => setTimeout(resolve, 0));
+
+    usage.push("a");
+    usage.push("b");
+    expect(countTokens).toHaveBeenCalledTimes(1);
+
+    advance(EXACT_COUNT_INTERVAL_MS);
+    usage.push("c");
+    usage.push("d");
+    expect(countTokens).toHaveBeenCalledTimes(2);
+    expect(countTokens).toHaveBeenLastCalledWith("abc");
+
+    advance(EXACT_COUNT_INTERVAL_MS / 2);
+    usage.push("e");
+    expect(countTokens).toHaveBeenCalledTimes(2);
+  });
+
+  it("corrects the estimate with the exact counts", async () => {
+    // The tokenizer says the prompt is 800 tokens, four times the estimate
+    const { usage, events } = tracker([800]);
+    await new Promise((resolve) => setTimeout(resolve, 0));
+    expect(events.map((event) => event.threshold)).toEqual([0.7]);
+
+    usage.push("short");
+    expect(events).toHaveLength(1);
+  });
+});
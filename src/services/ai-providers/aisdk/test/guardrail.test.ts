@@ -0,0 +1,49 @@
+import { describe, expect, it } from "vitest";
+import { guardrailBlockedMessage, isMaskOnlyIntervention, summarizeGuardrail } from "../guardrail";
+
+describe("summarizeGuardrail", () => {
+  it("returns null without a guardrail trace", () => {
+    expect(summarizeGuardrail(undefined)).toBeNull();
+    expect(summarizeGuardrail({ bedrock: { usage: {} } })).toBeNull();
+  });
+
+  it("collects blocked topics from the input assessment", () => {
+    const summary = summarizeGuardrail({
+      bedrock: {
+        trace: {
+          guardrail: {
+            inputAssessment: {
+              "gr-123": { topicPolicy: { topics: [{ name: "Investment advice", type: "DENY", action: "BLOCKED" }] } },
+            },
+          },
+        },
+      },
+    });
+
+    expect(summary?.blocked).toEqual(['denied topic "Investment advice"']);
+    expect(guardrailBlockedMessage(summary)).toBe('Blocked by the model\'s guardrail: denied topic "Investment advice"');
+    expect(isMaskOnlyIntervention(summary)).toBe(false);
+  });
+
+  it("reports masked output as notices", () => {
+    const summary = summarizeGuardrail({
+      bedrock: {
+        trace: {
+          guardrail: {
+            outputAssessments: {
+              "gr-123": [{ sensitiveInformationPolicy: { piiEntities: [{ type: "EMAIL", match: "a@b.c", action: "ANONYMIZED" }] } }],
+            },
+          },
+        },
+      },
+    });
+
+    expect(summary?.blocked).toEqual([]);
+    expect(summary?.masked).toEqual(["Masked sensitive information (email)"]);
+    expect(isMaskOnlyIntervention(summary)).toBe(true);
+  });
+
+  it("falls back to a generic message when the trace is disabled", () => {
+    expect(guardrailBlockedMessage(null)).toContain("Enable guardrail trace");
+  });
+});
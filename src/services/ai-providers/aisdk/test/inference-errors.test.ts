@@ -0,0 +1,98 @@
+import { APICallError, RetryError } from "ai";
+import { describe, expect, it } from "vitest";
+import { classifyInferenceError, finishReasonError } from "../inference-errors";
+
+function apiError(statusCode: number, body: unknown, responseHeaders?: Record<string, string>) {
+  const responseBody = typeof body === "string" ? body : JSON.stringify(body);
+  return new APICallError({ message: `Request failed with status ${statusCode}`, url: "https://provider.test/v1", requestBodyValues: {}, statusCode, responseHeaders, responseBody });
+}
+
+describe("classifyInferenceError", () => {
+  describe("openai", () => {
+    it.each([
+      [400, { error: { message: "This model's maximum context length is 8192 tokens", type: "invalid_request_error", code: "context_length_exceeded" } }, "context_overflow", false],
+      [401, { error: { message: "Incorrect API key provided", type: "invalid_request_error", code: "invalid_api_key" } }, "auth_failed", false],
+      [404, { error: { message: "The model `gpt-9` does not exist", type: "invalid_request_error", code: "model_not_found" } }, "model_not_found", false],
+      [429, { error: { message: "Rate limit reached", type: "requests", code: "rate_limit_exceeded" } }, "rate_limited", true],
+      [429, { error: { message: "You exceeded your current quota", type: "insufficient_quota", code: "insufficient_quota" } }, "rate_limited", false],
+      [500, { error: { message: "The server had an error", type: "server_error", code: null } }, "provider_internal", true],
+    ])("maps %i %j", (status, body, code, retryable) => {
+      expect(classifyInferenceError(apiError(status, body))).toMatchObject({ code, retryable });
+    });
+  });
+
+  describe("anthropic", () => {
+    it.each([
+      [401, "authentication_error", "invalid x-api-key", "auth_failed", false],
+      [400, "invalid_request_error", "prompt is too long: 210000 tokens > 200000 maximum", "context_overflow", false],
+      [429, "rate_limit_error", "Number of request tokens has exceeded your rate limit", "rate_limited", true],
+      [529, "overloaded_error", "Overloaded", "provider_internal", true],
+    ])("maps %i %s", (status, type, message, code, retryable) => {
+      expect(classifyInferenceError(apiError(status, { type: "error", error: { type, message } }))).toMatchObject({ code, retryable });
+    });
+  });
+
+  describe("google", () => {
+    it.each([
+      [400, "INVALID_ARGUMENT", "The input token count (1200000) exceeds the maximum number of tokens allowed", "context_overflow"],
+      [403, "PERMISSION_DENIED", "API key not valid", "auth_failed"],
+      [429, "RESOURCE_EXHAUSTED", "Resource has been exhausted", "rate_limited"],
+      [503, "UNAVAILABLE", "The model is overloaded", "provider_internal"],
+    ])("maps %i %s", (status, googleStatus, message, code) => {
+      expect(classifyInferenceError(apiError(status, { error: { code: status, message, status: googleStatus } })).code).toBe(code);
+    });
+
+    it("maps SAFETY finish reasons to content_filtered", () => {
+      const safetyRatings = [{ category: "HARM_CATEGORY_DANGEROUS_CONTENT", probability: "HIGH", blocked: true }];
+      expect(finishReasonError("content-filter", { google: { safetyRatings } })).toMatchObject({ code: "content_filtered", retryable: false, details: safetyRatings });
+      expect(finishReasonError("stop")).toBeUndefined();
+      expect(finishReasonError("length")).toBeUndefined();
+    });
+  });
+
+  describe("aws_bedrock", () => {
+    it.each([
+      ["ThrottlingException:http://internal.amazon.com/coral/com.amazon.bedrock/", "Too many requests, please wait", "rate_limited"],
+      ["AccessDeniedException", "You don't have access to the model with the specified model ID.", "auth_failed"],
+      ["ValidationException", "Input is too long for requested model.", "context_overflow"],
+      ["ModelTimeoutException", "Model has timed out in processing the request", "timeout"],
+      ["ResourceNotFoundException", "Could not resolve the foundation model", "model_not_found"],
+    ])("maps %s", (errorType, message, code) => {
+      expect(classifyInferenceError(apiError(400, { message }, { "x-amzn-errortype": errorType })).code).toBe(code);
+    });
+
+    it("maps SDK exceptions by name", () => {
+      const error = Object.assign(new Error("Rate exceeded"), { name: "ThrottlingException" });
+      expect(classifyInferenceError(error)).toMatchObject({ code: "rate_limited", retryable: true });
+    });
+  });
+
+  it("falls back to the HTTP status for unknown bodies", () => {
+    expect(classifyInferenceError(apiError(502, "<html>Bad Gateway</html>"))).toMatchObject({ code: "provider_internal", retryable: true });
+    expect(classifyInferenceError(apiError(418, "teapot"))).toMatchObject({ code: "unknown", retryable: false });
+  });
+
+  it("keeps the raw detail", () => {
+    const body = { error: { message: "Incorrect API key provided", code: "invalid_api_key" } };
+    expect(classifyInferenceError(apiError(401, body)).details).toMatchObject({ statusCode: 401, responseBody: body });
+  });
+
+  it("unwraps exhausted SDK retries", () => {
+    const lastError = apiError(429, { error: { message: "Rate limit reached", code: "rate_limit_exceeded" } });
+    const error = new RetryError({ message: "Failed after 3 attempts", reason: "maxRetriesExceeded", errors: [lastError, lastError, lastError] });
+    expect(classifyInferenceError(error).code).toBe("rate_limited");
+  });
+
+  it("maps errors that never got a response", () => {
+    expect(classifyInferenceError(new TypeError("fetch failed"))).toMatchObject({ code: "network_unreachable", retryable: true });
+    expect(classifyInferenceError(new Error("error sending request for url (http://127.0.0.1:5001/v1/chat/completions)")).code).toBe("network_unreachable");
+    expect(classifyInferenceError(Object.assign(new Error("The operation timed out"), { name: "TimeoutError" })).code).toBe("timeout");
+    expect(classifyInferenceError(Object.assign(new Error("aborted"), { name: "AbortError" })).code).toBe("cancelled");
+    expect(classifyInferenceError(new Error("Something odd"))).toMatchObject({ code: "unknown", message: "Something odd", retryable: false });
+  });
+
+  it("passes errors that already have a code through", () => {
+    const error = { message: "Blocked", code: "guardrail_intervened" };
+    expect(classifyInferenceError(error)).toBe(error);
+  });
+});
@@ -0,0 +1,59 @@
+import { describe, expect, it, vi } from "vitest";
+import type { AIEvent } from "../../types/ai-event.type";
+import { resolveNormalizeLeadingWhitespace, withLeadingWhitespaceTrimmed } from "../leading-whitespace";
+
+function createEvent() {
+  return {
+    requestId: "request-1",
+    sendStream: vi.fn(),
+    sendError: vi.fn(),
+    finish: vi.fn(),
+    registerAborter: vi.fn(),
+  } satisfies AIEvent;
+}
+
+// Streams the chunks, then finishes with their concatenation
+const streamChunks = (chunks: string[]) => async (event: AIEvent) => {
+  for (const text of chunks) {
+    event.sendStream({ text });
+  }
+  const fullResponse = chunks.join("");
+  event.finish({ fullResponse });
+  return fullResponse;
+};
+
+describe("resolveNormalizeLeadingWhitespace", () => {
+  it("is enabled unless turned off", () => {
+    expect(resolveNormalizeLeadingWhitespace(undefined)).toBe(true);
+    expect(resolveNormalizeLeadingWhitespace(false)).toBe(false);
+  });
+});
+
+describe("withLeadingWhitespaceTrimmed", () => {
+  it("trims the start of the answer and keeps later chunks as received", async () => {
+    const event = createEvent();
+    const response = await withLeadingWhitespaceTrimmed(streamChunks(["\n ", "  Hel", "lo ", " wor", "ld\n"]), true)(event);
+
+    expect(event.sendStream.mock.calls.map(([payload]) => payload.text)).toEqual(["Hel", "lo ", " wor", "ld\n"]);
+    expect(event.finish).toHaveBeenCalledWith({ fullResponse: "Hello  world\n" });
+    expect(response).toBe("Hello  world\n");
+  });
+
+  it("still forwards what else a whitespace chunk carries", async () => {
+    const event = createEvent();
+    await withLeadingWhitespaceTrimmed(async (attemptEvent) => {
+      attemptEvent.sendStream({ text: " ", reasoning: "thinking" });
+      return "";
+    }, true)(event);
+
+    expect(event.sendStream).toHaveBeenCalledWith({ text: undefined, reasoning: "thinking" });
+  });
+
+  it("leaves the answer untouched when disabled", async () => {
+    const event = createEvent();
+    const response = await withLeadingWhitespaceTrimmed(streamChunks([" Hi"]), false)(event);
+
+    expect(event.sendStream).toHaveBeenCalledWith({ text: " Hi" });
+    expect(response).toBe(" Hi");
+  });
+});
@@ -0,0 +1,125 @@
+import { describe, expect, it, vi } from "vitest";
+import type { FinalParams } from "../../start-inference";
+import type { AIEvent } from "../../types/ai-event.type";
+import { createMockLanguageModel, parseMockConfig, tokenize } from "../mock-model";
+import { generateResponse } from "../non-streaming";
+import { streamResponse } from "../streaming";
+
+function createEvent() {
+  let aborter: (() => void) | undefined;
+  const event = {
+    requestId: "request-1",
+    sendStream: vi.fn(),
+    sendError: vi.fn(),
+    finish: vi.fn(),
+    registerAborter: vi.fn((fn: () => void) => {
+      aborter = fn;
+    }),
+  } satisfies AIEvent;
+  return { event, abort: () => aborter?.() };
+}
+
+function paramsFor(config: Record<string, any>): FinalParams {
+  return {
+    model: createMockLanguageModel("mock", config),
+    messages: [{ role: "user", content: "Hello there" }],
+  } as FinalParams;
+}
+
+describe("tokenize", () => {
+  it("splits into words that join back into the original text", () => {
+    const text = "Hello,  world!\nSecond line ";
+    const tokens = tokenize(text);
+    expect(tokens).toEqual(["Hello,  ", "world!\n", "Second ", "line "]);
+    expect(tokens.join("")).toBe(text);
+  });
+});
+
+describe("parseMockConfig", () => {
+  it("falls back to defaults for missing or invalid values", () => {
+    const config = parseMockConfig({ tokens_per_second: "fast", simulate_error: "explode" });
+    expect(config.mockResponse).toContain("mock response");
+    expect(config.tokensPerSecond).toBe(20);
+    expect(config.simulateError).toBe("none");
+    expect(config.reasoning).toBe("");
+  });
+
+  it("coerces numeric fields stored as strings", () => {
+    expect(parseMockConfig({ tokens_per_second: "0" }).tokensPerSecond).toBe(0);
+  });
+});
+
+describe("mock engine streaming", () => {
+  it("streams the canned response and finishes with the full text", async () => {
+    const { event } = createEvent();
+
+    const result = await streamResponse(event, paramsFor({ mock_response: "One two three", tokens_per_second: 0 }));
+
+    expect(result).toBe("One two three");
+    const streamed = event.sendStream.mock.calls.map(([payload]) => payload.text ?? "").join("");
+    expect(streamed).toBe("One two three");
+    expect(event.finish).toHaveBeenCalledWith({ fullResponse: "One two three", finishReason: "stop" });
+    expect(event.sendError).not.toHaveBeenCalled();
+  });
+
+  it("streams the reasoning phase before the answer", async () => {
+    const { event } = createEvent();
+
+    await streamResponse(event, paramsFor({ mock_response: "Answer", reasoning: "Let me think", tokens_per_second: 0 }));
+
+    const payloads = event.sendStream.mock.calls.map(([payload]) => payload);
+    const reasoning = payloads.map((payload) => payload.reasoning ?? "").join("");
+    const firstTextIndex = payloads.findIndex((payload) => payload.text);
+    const lastReasoningIndex = payloads.map((payload) => !!payload.reasoning).lastIndexOf(true);
+
+    expect(reasoning).toBe("Let me think");
+    expect(lastReasoningIndex).toBeLessThan(firstTextIndex);
+  });
+
+  it("forwards raw provider chunks only when the event asks for them", async () => {
+    const { event } = createEvent();
+    const sendRaw = vi.fn();
+
+    await streamResponse({ ...event, sendRaw }, paramsFor({ mock_response: "One two", tokens_per_second: 0 }));
+    await streamResponse(event, paramsFor({ mock_response: "One two", tokens_per_second: 0 }));
+
+    const rawChunks = sendRaw.mock.calls.map(([part]) => part).filter((part) => part.type === "chunk");
+    expect(rawChunks).toEqual([
+      { type: "chunk", value: { text: "One " } },
+      { type: "chunk", value: { text: "two" } },
+    ]);
+  });
+
+  it("fails halfway through when an error is simulated", async () => {
+    const { event } = createEvent();
+
+    const result = await streamResponse(event, paramsFor({ mock_response: "a b c d", tokens_per_second: 0, simulate_error: "rate_limit" }));
+
+    expect(result).toBe("a b ");
+    expect(event.sendError).toHaveBeenCalledWith(expect.objectContaining({ message: expect.stringContaining("429") }));
+  });
+
+  it("stops a simulated timeout without an error when cancelled", async () => {
+    const { event, abort } = createEvent();
+
+    const pending = streamResponse(event, paramsFor({ simulate_error: "timeout" }));
+    await new Promise((resolve) => setTimeout(resolve, 10));
+    abort();
+    await pending;
+
+    expect(event.finish).not.toHaveBeenCalled();
+    expect(event.sendError).not.toHaveBeenCalled();
+  });
+});
+
+describe("mock engine non-streaming", () => {
+  it("returns the text and reports the finish reason", async () => {
+    const { event } = createEvent();
+
+    const result = await generateResponse(paramsFor({ mock_response: "One two three", tokens_per_second: 0 }), event);
+
+    expect(result).toBe("One two three");
+    expect(event.sendStream).toHaveBeenCalledWith({ finishReason: "stop" });
+    expect(event.sendError).not.toHaveBeenCalled();
+  });
+});
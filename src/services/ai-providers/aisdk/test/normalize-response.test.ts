@@ -0,0 +1,56 @@
+import { describe, expect, it, vi } from "vitest";
+import { normalizeChatCompletionBody, withNormalizedChatResponses } from "../normalize-response";
+
+function contentOf(body: unknown): unknown {
+  return (body as any).choices[0].message.content;
+}
+
+describe("normalizeChatCompletionBody", () => {
+  it("keeps standard chat completions untouched", () => {
+    const body = { choices: [{ index: 0, message: { role: "assistant", content: "Hello" }, finish_reason: "stop" }] };
+    expect(normalizeChatCompletionBody(body)).toBe(body);
+  });
+
+  it("reads choices[0].text", () => {
+    const normalized = normalizeChatCompletionBody({ choices: [{ index: 0, text: "Hello", finish_reason: "length" }] });
+    expect(contentOf(normalized)).toBe("Hello");
+    expect((normalized as any).choices[0].finish_reason).toBe("length");
+  });
+
+  it("reads a top-level content", () => {
+    const normalized = normalizeChatCompletionBody({ model: "local", content: "Hello" });
+    expect(contentOf(normalized)).toBe("Hello");
+    expect((normalized as any).choices[0].finish_reason).toBe("stop");
+  });
+
+  it("joins content given as an array of parts", () => {
+    const normalized = normalizeChatCompletionBody({
+      choices: [{ index: 0, message: { role: "assistant", content: [{ type: "text", text: "Hel" }, { type: "text", text: "lo" }] } }],
+    });
+    expect(contentOf(normalized)).toBe("Hello");
+  });
+
+  it("prefers message content over choices[0].text", () => {
+    const normalized = normalizeChatCompletionBody({ choices: [{ message: { content: [{ text: "message" }] }, text: "text" }] });
+    expect(contentOf(normalized)).toBe("message");
+  });
+
+  it("leaves tool call answers without content alone", () => {
+    const body = { choices: [{ message: { role: "assistant", content: null, tool_calls: [{ id: "call-1" }] } }] };
+    expect(normalizeChatCompletionBody(body)).toBe(body);
+  });
+});
+
+describe("withNormalizedChatResponses", () => {
+  it("rewrites JSON answers and passes event streams through", async () => {
+    const json = new Response(JSON.stringify({ choices: [{ text: "Hello" }] }), { headers: { "content-type": "application/json" } });
+    const stream = new Response("data: {}\n\n", { headers: { "content-type": "text/event-stream" } });
+    const fetchImpl = vi.fn().mockResolvedValueOnce(json).mockResolvedValueOnce(stream);
+    const fetchWithNormalization = withNormalizedChatResponses(fetchImpl);
+
+    const normalized = await fetchWithNormalization("http://localhost/v1/chat/completions");
+    expect(contentOf(await normalized.json())).toBe("Hello");
+
+    expect(await fetchWithNormalization("http://localhost/v1/chat/completions")).toBe(stream);
+  });
+});
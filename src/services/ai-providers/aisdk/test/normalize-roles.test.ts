@@ -0,0 +1,103 @@
+import { describe, expect, it } from "vitest";
+import type { InferenceMessage } from "@/schema/inference-engine-schema";
+import { GEMINI_OPENING_TURN, isGeminiTarget, normalizeMessageRoles } from "../normalize-roles";
+
+describe("normalizeMessageRoles", () => {
+  it("leaves messages untouched for engines without role constraints", () => {
+    const messages: InferenceMessage[] = [
+      { role: "assistant", text: "Hello" },
+      { role: "assistant", text: "Again" },
+    ];
+    expect(normalizeMessageRoles("openai", messages)).toBe(messages);
+  });
+
+  it("merges adjacent user messages", () => {
+    const messages: InferenceMessage[] = [
+      { role: "user", text: "First" },
+      { role: "user", text: "Second" },
+      { role: "assistant", text: "Reply" },
+    ];
+    expect(normalizeMessageRoles("anthropic", messages)).toEqual([
+      { role: "user", text: "First\n\nSecond" },
+      { role: "assistant", text: "Reply" },
+    ]);
+  });
+
+  it("merges adjacent assistant messages", () => {
+    const messages: InferenceMessage[] = [
+      { role: "user", text: "Hi" },
+      { role: "assistant", text: "One" },
+      { role: "assistant", text: "Two" },
+    ];
+    expect(normalizeMessageRoles("aws_bedrock", messages)).toEqual([
+      { role: "user", text: "Hi" },
+      { role: "assistant", text: "One\n\nTwo" },
+    ]);
+  });
+
+  it("skips empty text when merging", () => {
+    const messages: InferenceMessage[] = [
+      { role: "user", text: "" },
+      { role: "user", text: "Only" },
+    ];
+    expect(normalizeMessageRoles("anthropic", messages)).toEqual([{ role: "user", text: "Only" }]);
+  });
+
+  it("does not merge messages carrying tool calls or tool results", () => {
+    const messages: InferenceMessage[] = [
+      { role: "user", text: "Roll" },
+      { role: "assistant", text: "", tool_calls: [{ id: "a", name: "roll", arguments: "{}" }] },
+      { role: "tool", text: "4", tool_call_id: "a" },
+      { role: "tool", text: "6", tool_call_id: "b" },
+      { role: "assistant", text: "Done" },
+    ];
+    expect(normalizeMessageRoles("anthropic", messages)).toEqual(messages);
+  });
+
+  it("rejects a conversation that starts with an assistant message", () => {
+    const messages: InferenceMessage[] = [
+      { role: "assistant", text: "Greetings" },
+      { role: "user", text: "Hi" },
+    ];
+    expect(() => normalizeMessageRoles("anthropic", messages)).toThrow(/start with a user message/);
+    expect(() => normalizeMessageRoles("aws_bedrock", messages)).toThrow(/AWS Bedrock/);
+  });
+
+  it("does not mutate the input list", () => {
+    const messages: InferenceMessage[] = [
+      { role: "user", text: "A" },
+      { role: "user", text: "B" },
+    ];
+    normalizeMessageRoles("anthropic", messages);
+    expect(messages).toEqual([
+      { role: "user", text: "A" },
+      { role: "user", text: "B" },
+    ]);
+  });
+
+  it("puts a user turn ahead of an assistant-first conversation for Gemini", () => {
+    const messages: InferenceMessage[] = [
+      { role: "assistant", text: "Greetings, traveler" },
+      { role: "user", text: "Hi" },
+    ];
+    expect(normalizeMessageRoles("google", messages)).toEqual([{ role: "user", text: GEMINI_OPENING_TURN }, ...messages]);
+    expect(normalizeMessageRoles("google", [])).toEqual([{ role: "user", text: GEMINI_OPENING_TURN }]);
+  });
+
+  it("leaves a user-first conversation untouched for Gemini", () => {
+    const messages: InferenceMessage[] = [
+      { role: "user", text: "Hi" },
+      { role: "assistant", text: "Hello" },
+    ];
+    expect(normalizeMessageRoles("google", messages)).toBe(messages);
+  });
+
+  it("recognizes Gemini behind OpenAI-compatible endpoints", () => {
+    const messages: InferenceMessage[] = [{ role: "assistant", text: "Greetings" }];
+    const config = { base_url: "https://generativelanguage.googleapis.com/v1beta/openai", model: "some-model" };
+    expect(normalizeMessageRoles("openai_compatible", messages, config)[0]).toEqual({ role: "user", text: GEMINI_OPENING_TURN });
+    expect(isGeminiTarget("openrouter", { model: "google/gemini-2.5-pro" })).toBe(true);
+    expect(isGeminiTarget("openai_compatible", { base_url: "http://localhost:5000/v1", model: "llama-3" })).toBe(false);
+    expect(isGeminiTarget("openai", { model: "gemini-2.5-flash" })).toBe(false);
+  });
+});
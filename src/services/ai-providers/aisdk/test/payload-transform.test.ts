@@ -0,0 +1,77 @@
+import { describe, expect, it, vi } from "vitest";
+import { applyFieldMapping, parseFieldMapping, withPayloadTransforms } from "../payload-transform";
+
+function mapping(value: unknown) {
+  const parsed = parseFieldMapping(value, "Mapping");
+  if (!parsed) {
+    throw new Error("expected a mapping");
+  }
+  return parsed;
+}
+
+describe("parseFieldMapping", () => {
+  it("returns null when nothing is configured", () => {
+    expect(parseFieldMapping(undefined, "Mapping")).toBeNull();
+    expect(parseFieldMapping("  ", "Mapping")).toBeNull();
+    expect(parseFieldMapping("{}", "Mapping")).toBeNull();
+  });
+
+  it("rejects invalid mappings", () => {
+    expect(() => parseFieldMapping("{ max_tokens: ", "Mapping")).toThrow(/not valid JSON/);
+    expect(() => parseFieldMapping('["max_tokens"]', "Mapping")).toThrow(/JSON object/);
+    expect(() => parseFieldMapping('{ "max_tokens": 1 }', "Mapping")).toThrow(/path string/);
+  });
+});
+
+describe("applyFieldMapping", () => {
+  it("renames a top-level field", () => {
+    const body = { max_tokens: 200, temperature: 0.7 };
+    expect(applyFieldMapping(body, mapping('{ "max_tokens": "n_predict" }'))).toEqual({ n_predict: 200, temperature: 0.7 });
+    expect(body).toEqual({ max_tokens: 200, temperature: 0.7 });
+  });
+
+  it("moves nested and indexed paths, creating missing containers", () => {
+    const mapped = applyFieldMapping({ results: [{ text: "Hello" }] }, mapping({ "results[0].text": "choices[0].message.content" }));
+    expect(mapped).toEqual({ results: [{}], choices: [{ message: { content: "Hello" } }] });
+  });
+
+  it("skips sources that are missing", () => {
+    expect(applyFieldMapping({ prompt: "Hi" }, mapping({ max_tokens: "n_predict" }))).toEqual({ prompt: "Hi" });
+  });
+});
+
+describe("withPayloadTransforms", () => {
+  it("returns the fetch untouched without mappings", () => {
+    const fetchImpl = vi.fn();
+    expect(withPayloadTransforms(fetchImpl, "", undefined)).toBe(fetchImpl);
+  });
+
+  it("rewrites the request body and the JSON response", async () => {
+    const fetchImpl = vi.fn(async (_input: RequestInfo | URL, _init?: RequestInit) => Response.json({ results: [{ text: "Hello" }] }));
+    const transformed = withPayloadTransforms(fetchImpl, '{ "max_tokens": "n_predict" }', '{ "results[0].text": "choices[0].text" }');
+
+    const response = await transformed("http://localhost/v1/completions", { method: "POST", body: JSON.stringify({ max_tokens: 64 }) });
+
+    expect(JSON.parse(fetchImpl.mock.calls[0][1]?.body as string)).toEqual({ n_predict: 64 });
+    expect((await response.json()).choices[0].text).toBe("Hello");
+  });
+
+  it("rewrites each event of a stream, even split across chunks", async () => {
+    const encoder = new TextEncoder();
+    const parts = ['data: {"results":[{"te', 'xt":"Hel"}]}\n\ndata: {"results":[{"text":"lo"}]}\n\n', "data: [DONE]\n\n"];
+    const stream = new ReadableStream<Uint8Array>({
+      start(controller) {
+        parts.forEach((part) => controller.enqueue(encoder.encode(part)));
+        controller.close();
+      },
+    });
+    const fetchImpl = vi.fn(async () => new Response(stream, { headers: { "content-type": "text/event-stream" } }));
+
+    const response = await withPayloadTransforms(fetchImpl, undefined, { "results[0].text": "choices[0].delta.content" })("http://localhost");
+    const text = await response.text();
+
+    expect(text).toContain('data: {"results":[{}],"choices":[{"delta":{"content":"Hel"}}]}');
+    expect(text).toContain('"content":"lo"');
+    expect(text).toContain("data: [DONE]");
+  });
+});
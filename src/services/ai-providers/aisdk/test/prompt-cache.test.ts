@@ -0,0 +1,87 @@
+import type { ModelMessage } from "ai";
+import { describe, expect, it } from "vitest";
+import { applyPromptCache, getPromptCacheSettings } from "../prompt-cache";
+
+const ANTHROPIC_BREAKPOINT = { anthropic: { cacheControl: { type: "ephemeral" } } };
+const BEDROCK_BREAKPOINT = { bedrock: { cachePoint: { type: "default" } } };
+
+function conversation(length: number): ModelMessage[] {
+  const messages: ModelMessage[] = [{ role: "system", content: "System" }];
+  for (let i = 0; i < length; i++) {
+    messages.push(i % 2 === 0 ? { role: "user", content: `User ${i}` } : { role: "assistant", content: `Assistant ${i}` });
+  }
+  return messages;
+}
+
+function cachedIndexes(messages: ModelMessage[]): number[] {
+  return messages.flatMap((message, index) => (message.providerOptions ? [index] : []));
+}
+
+describe("getPromptCacheSettings", () => {
+  it("is off when no cache parameters are present", () => {
+    expect(getPromptCacheSettings({ temperature: 1 })).toEqual({ cacheSystemPrompt: false, cacheLastNMessages: 0 });
+  });
+
+  it("reads the legacy Bedrock depth as the message count", () => {
+    expect(getPromptCacheSettings({ prompt_cache_depth: 2 })).toEqual({ cacheSystemPrompt: true, cacheLastNMessages: 2 });
+  });
+
+  it("accepts only the TTLs providers offer", () => {
+    expect(getPromptCacheSettings({ cache_last_n_messages: 1, cache_ttl: "1h" }).cacheTtl).toBe("1h");
+    expect(getPromptCacheSettings({ cache_last_n_messages: 1, cache_ttl: "5m" }).cacheTtl).toBe("5m");
+    expect(getPromptCacheSettings({ cache_last_n_messages: 1, cache_ttl: "24h" }).cacheTtl).toBeUndefined();
+  });
+
+  it("prefers the new parameter names", () => {
+    expect(getPromptCacheSettings({ cache_system_prompt: false, cache_last_n_messages: 3, prompt_cache_depth: 1 })).toEqual({
+      cacheSystemPrompt: false,
+      cacheLastNMessages: 3,
+    });
+  });
+});
+
+describe("applyPromptCache", () => {
+  it("marks the system prompt and the last message for Anthropic", () => {
+    const result = applyPromptCache("anthropic", conversation(4), { cache_system_prompt: true, cache_last_n_messages: 1 });
+    expect(cachedIndexes(result)).toEqual([0, 4]);
+    expect(result[0].providerOptions).toEqual(ANTHROPIC_BREAKPOINT);
+  });
+
+  it("uses cache points for Bedrock", () => {
+    const result = applyPromptCache("aws_bedrock", conversation(2), { cache_system_prompt: false, cache_last_n_messages: 2 });
+    expect(cachedIndexes(result)).toEqual([1, 2]);
+    expect(result[2].providerOptions).toEqual(BEDROCK_BREAKPOINT);
+  });
+
+  it("caps breakpoints at the provider limit", () => {
+    const result = applyPromptCache("anthropic", conversation(8), { cache_system_prompt: true, cache_last_n_messages: 10 });
+    expect(cachedIndexes(result)).toEqual([0, 6, 7, 8]);
+  });
+
+  it("gives Anthropic breakpoints the chosen lifetime", () => {
+    const result = applyPromptCache("anthropic", conversation(2), { cache_system_prompt: true, cache_last_n_messages: 1, cache_ttl: "1h" });
+    expect(cachedIndexes(result)).toEqual([0, 2]);
+    expect(result[0].providerOptions).toEqual({ anthropic: { cacheControl: { type: "ephemeral", ttl: "1h" } } });
+    expect(result[2].providerOptions).toEqual({ anthropic: { cacheControl: { type: "ephemeral", ttl: "1h" } } });
+  });
+
+  it("keeps the default lifetime on Bedrock and for invalid values", () => {
+    const bedrock = applyPromptCache("aws_bedrock", conversation(2), { cache_system_prompt: false, cache_last_n_messages: 1, cache_ttl: "1h" });
+    expect(bedrock[2].providerOptions).toEqual(BEDROCK_BREAKPOINT);
+
+    const invalid = applyPromptCache("anthropic", conversation(2), { cache_system_prompt: false, cache_last_n_messages: 1, cache_ttl: "2h" });
+    expect(invalid[2].providerOptions).toEqual(ANTHROPIC_BREAKPOINT);
+  });
+
+  it("ignores the settings on engines without explicit caching", () => {
+    const messages = conversation(2);
+    expect(applyPromptCache("openai", messages, { cache_system_prompt: true, cache_last_n_messages: 2 })).toBe(messages);
+    expect(applyPromptCache("google", messages, { cache_system_prompt: true, cache_last_n_messages: 2 })).toBe(messages);
+  });
+
+  it("keeps existing provider options on marked messages", () => {
+    const messages: ModelMessage[] = [{ role: "user", content: "Hi", providerOptions: { anthropic: { foo: "bar" } } }];
+    const result = applyPromptCache("anthropic", messages, { cache_last_n_messages: 1 });
+    expect(result[0].providerOptions).toEqual({ anthropic: { foo: "bar", cacheControl: { type: "ephemeral" } } });
+  });
+});
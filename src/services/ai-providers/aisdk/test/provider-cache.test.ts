@@ -0,0 +1,70 @@
+import { afterEach, describe, expect, it, vi } from "vitest";
+import type { ModelSpecs } from "@/schema/inference-engine-schema";
+import { getCachedProvider, invalidateProviders, PROVIDER_IDLE_TTL_MS, providerFingerprint, trimProviderCache } from "../provider-cache";
+
+const specs = (id: string, config: Record<string, unknown>): ModelSpecs => ({
+  id,
+  engine: "openai_compatible",
+  model_type: "chat",
+  max_concurrent_requests: 1,
+  config: { model: "local", base_url: "http://localhost:5001/v1", api_key: "encrypted-a", ...config },
+});
+
+const builder = () => vi.fn(async () => ({ built: Symbol("provider") }));
+
+describe("provider cache", () => {
+  afterEach(() => {
+    trimProviderCache(0);
+  });
+
+  it("builds a provider once for repeated and concurrent requests", async () => {
+    const create = builder();
+    const model = specs("m1", {});
+
+    const [first, second] = await Promise.all([getCachedProvider(model, create), getCachedProvider(model, create)]);
+    const third = await getCachedProvider(model, create);
+
+    expect(create).toHaveBeenCalledTimes(1);
+    expect(second).toBe(first);
+    expect(third).toBe(first);
+  });
+
+  it("shares providers across model names but not across keys, URLs or transforms", async () => {
+    const base = await providerFingerprint(specs("m1", {}));
+    expect(await providerFingerprint(specs("m2", { model: "other" }))).toBe(base);
+    expect(await providerFingerprint(specs("m1", { api_key: "encrypted-b" }))).not.toBe(base);
+    expect(await providerFingerprint(specs("m1", { base_url: "http://localhost:8080/v1" }))).not.toBe(base);
+    expect(await providerFingerprint(specs("m1", { request_transform: { max_tokens: "options.num_predict" } }))).not.toBe(base);
+    expect(await providerFingerprint({ ...specs("m1", {}), model_type: "completion" })).not.toBe(base);
+    expect(base).not.toContain("encrypted");
+  });
+
+  it("drops the providers of an edited model", async () => {
+    const create = builder();
+    const first = await getCachedProvider(specs("m1", {}), create);
+
+    invalidateProviders("other-model");
+    expect(await getCachedProvider(specs("m1", {}), create)).toBe(first);
+
+    invalidateProviders("m1");
+    expect(await getCachedProvider(specs("m1", {}), create)).not.toBe(first);
+    expect(create).toHaveBeenCalledTimes(2);
+  });
+
+  it("expires providers left unused", async () => {
+    const create = builder();
+    const now = 1_000_000;
+    const first = await getCachedProvider(specs("m1", {}), create, now);
+
+    expect(await getCachedProvider(specs("m1", {}), create, now + PROVIDER_IDLE_TTL_MS - 1)).toBe(first);
+    expect(await getCachedProvider(specs("m1", {}), create, now + 2 * PROVIDER_IDLE_TTL_MS)).not.toBe(first);
+  });
+
+  it("retries a build that failed", async () => {
+    const create = vi.fn().mockRejectedValueOnce(new Error("Failed to decrypt")).mockResolvedValue({ built: true });
+
+    await expect(getCachedProvider(specs("m1", {}), create)).rejects.toThrow("Failed to decrypt");
+    await expect(getCachedProvider(specs("m1", {}), create)).resolves.toEqual({ built: true });
+    expect(create).toHaveBeenCalledTimes(2);
+  });
+});
@@ -0,0 +1,61 @@
+import { describe, expect, it, vi } from "vitest";
+import type { ModelSpecs } from "@/schema/inference-engine-schema";
+import { applyRawCompletionPrompt, resolveRawPrompt, withRawCompletionPrompt } from "../raw-prompt";
+
+const specs = (model_type: "chat" | "completion"): ModelSpecs => ({
+  id: "model-1",
+  model_type,
+  config: { model: "local" },
+  max_concurrent_requests: 1,
+  engine: "openai_compatible",
+});
+
+describe("resolveRawPrompt", () => {
+  it("returns undefined for message requests", () => {
+    expect(resolveRawPrompt({ messages: [{ role: "user", text: "Hi" }], modelSpecs: specs("chat") })).toBeUndefined();
+  });
+
+  it("accepts a raw prompt without messages, even an empty one", () => {
+    expect(resolveRawPrompt({ rawPrompt: "<s>[INST] Hi", messages: [], modelSpecs: specs("completion") })).toBe("<s>[INST] Hi");
+    expect(resolveRawPrompt({ rawPrompt: "", messages: [], modelSpecs: specs("completion") })).toBe("");
+  });
+
+  it("rejects a raw prompt combined with messages or examples", () => {
+    expect(() => resolveRawPrompt({ rawPrompt: "Hi", messages: [{ role: "user", text: "Hi" }], modelSpecs: specs("chat") })).toThrow(/not both/);
+    expect(() => resolveRawPrompt({ rawPrompt: "Hi", messages: [], examples: [{ role: "assistant", text: "Hey" }], modelSpecs: specs("chat") })).toThrow(/not both/);
+  });
+
+  it("keeps the system prompt for chat models only", () => {
+    expect(resolveRawPrompt({ rawPrompt: "Hi", messages: [], systemPrompt: "Be brief", modelSpecs: specs("chat") })).toBe("Hi");
+    expect(() => resolveRawPrompt({ rawPrompt: "Hi", messages: [], systemPrompt: "Be brief", modelSpecs: specs("completion") })).toThrow(/system prompt/);
+  });
+});
+
+describe("applyRawCompletionPrompt", () => {
+  it("replaces the framed prompt and the SDK's stop sequence", () => {
+    const body = { model: "local", prompt: "user:\nHi\n\nassistant:\n", stop: ["\nuser:"], temperature: 0.7 };
+    expect(applyRawCompletionPrompt(body, "Hi", undefined)).toEqual({ model: "local", prompt: "Hi", temperature: 0.7 });
+    expect(applyRawCompletionPrompt(body, "Hi", ["</s>"])).toEqual({ model: "local", prompt: "Hi", stop: ["</s>"], temperature: 0.7 });
+  });
+
+  it("leaves chat payloads alone", () => {
+    const body = { model: "local", messages: [{ role: "user", content: "Hi" }] };
+    expect(applyRawCompletionPrompt(body, "Raw", undefined)).toBe(body);
+  });
+});
+
+describe("withRawCompletionPrompt", () => {
+  it("sends the raw prompt verbatim", async () => {
+    const fetchImpl = vi.fn(async () => new Response("{}"));
+    const rawPrompt = "  ### Instruction:\nHi\n\n### Response:\n";
+    await withRawCompletionPrompt(fetchImpl, rawPrompt)("https://local/v1/completions", { method: "POST", body: JSON.stringify({ prompt: "user:\nHi" }) });
+    expect(JSON.parse((fetchImpl.mock.calls[0] as any[])[1].body).prompt).toBe(rawPrompt);
+  });
+
+  it("passes non-JSON bodies through", async () => {
+    const fetchImpl = vi.fn(async () => new Response("{}"));
+    const init = { method: "POST", body: "not json" };
+    await withRawCompletionPrompt(fetchImpl, "Hi")("https://local", init);
+    expect(fetchImpl).toHaveBeenCalledWith("https://local", init);
+  });
+});
@@ -0,0 +1,56 @@
+import { describe, expect, it } from "vitest";
+import type { ModelSpecs } from "@/schema/inference-engine-schema";
+import { REASONING_NOT_SUPPORTED } from "../../types/ai-event.type";
+import { assertReasoningBudgetSupported, manifestSupportsReasoning, resolveReasoningSupport } from "../reasoning-support";
+
+function specs(engine: string, config: Record<string, any>, supports_reasoning?: boolean): ModelSpecs {
+  return { id: "model-1", model_type: "chat", config, max_concurrent_requests: 1, engine, supports_reasoning };
+}
+
+describe("manifestSupportsReasoning", () => {
+  it("reads the reasoning inference field", () => {
+    expect(manifestSupportsReasoning({ inference_fields: ["temperature", "reasoning"] })).toBe(true);
+    expect(manifestSupportsReasoning({ inference_fields: ["temperature"] })).toBe(false);
+    expect(manifestSupportsReasoning({})).toBeUndefined();
+    expect(manifestSupportsReasoning(undefined)).toBeUndefined();
+  });
+});
+
+describe("resolveReasoningSupport", () => {
+  it("detects Bedrock models that accept a reasoning budget", () => {
+    expect(resolveReasoningSupport(specs("aws_bedrock", { model: "us.anthropic.claude-3-7-sonnet-20250219-v1:0" }, true))).toBe(true);
+    expect(resolveReasoningSupport(specs("aws_bedrock", { model: "anthropic.claude-sonnet-4-20250514-v1:0" }, true))).toBe(true);
+    expect(resolveReasoningSupport(specs("aws_bedrock", { model: "anthropic.claude-3-5-sonnet-20240620-v1:0" }, true))).toBe(false);
+    expect(resolveReasoningSupport(specs("aws_bedrock", { model: "amazon.nova-pro-v1:0" }, true))).toBe(false);
+  });
+
+  it("leaves inference profile ARNs undecided", () => {
+    expect(resolveReasoningSupport(specs("aws_bedrock", { model: "arn:aws:bedrock:us-east-1:123:application-inference-profile/abc" }, true))).toBeUndefined();
+  });
+
+  it("lets the model config override the detection", () => {
+    expect(resolveReasoningSupport(specs("aws_bedrock", { model: "amazon.nova-pro-v1:0", supports_reasoning: "true" }, true))).toBe(true);
+    expect(resolveReasoningSupport(specs("anthropic", { model: "claude-sonnet-4-5", supports_reasoning: "false" }, true))).toBe(false);
+    expect(resolveReasoningSupport(specs("aws_bedrock", { model: "amazon.nova-pro-v1:0", supports_reasoning: "auto" }, true))).toBe(false);
+  });
+
+  it("falls back to the manifest capability for other engines", () => {
+    expect(resolveReasoningSupport(specs("openai", { model: "gpt-5" }, true))).toBe(true);
+    expect(resolveReasoningSupport(specs("runpod", { model: "any" }, false))).toBe(false);
+    expect(resolveReasoningSupport(specs("openai", { model: "gpt-5" }))).toBeUndefined();
+  });
+});
+
+describe("assertReasoningBudgetSupported", () => {
+  it("rejects a budget for models without reasoning", () => {
+    expect(() => assertReasoningBudgetSupported(specs("aws_bedrock", { model: "amazon.nova-pro-v1:0" }, true), { reasoning_budget: 2048 })).toThrow(
+      expect.objectContaining({ code: REASONING_NOT_SUPPORTED, message: expect.stringContaining("amazon.nova-pro-v1:0") }),
+    );
+  });
+
+  it("accepts a zero budget or a reasoning model", () => {
+    expect(() => assertReasoningBudgetSupported(specs("aws_bedrock", { model: "amazon.nova-pro-v1:0" }, true), { reasoning_budget: 0 })).not.toThrow();
+    expect(() => assertReasoningBudgetSupported(specs("aws_bedrock", { model: "anthropic.claude-opus-4-1-20250805-v1:0" }, true), { reasoning_budget: 2048 })).not.toThrow();
+    expect(() => assertReasoningBudgetSupported(specs("openai_compatible", { model: "local" }), { reasoning_budget: 2048 })).not.toThrow();
+  });
+});
@@ -0,0 +1,149 @@
+import { describe, expect, it, vi } from "vitest";
+import { type AIEvent, EMPTY_RESPONSE } from "../../types/ai-event.type";
+import { createMockLanguageModel } from "../mock-model";
+import { resolveRetryOnEmpty, runWithEmptyRetry } from "../retry-on-empty";
+import { streamResponse } from "../streaming";
+
+function createEvent() {
+  return {
+    requestId: "request-1",
+    sendStream: vi.fn(),
+    sendError: vi.fn(),
+    finish: vi.fn(),
+    registerAborter: vi.fn(),
+  } satisfies AIEvent;
+}
+
+// Streams the given responses in order, one per attempt
+function streamingAttempts(responses: string[]) {
+  let attempt = 0;
+  return vi.fn((attemptEvent: AIEvent) => {
+    const model = createMockLanguageModel("mock", { mock_response: responses[attempt] ?? "", tokens_per_second: 0, simulate_error: responses[attempt] ? "none" : "empty" });
+    attempt++;
+    return streamResponse(attemptEvent, { model, messages: [{ role: "user", content: "Hi" }] } as any);
+  });
+}
+
+describe("resolveRetryOnEmpty", () => {
+  it("defaults to one retry", () => {
+    expect(resolveRetryOnEmpty(undefined)).toEqual({ enabled: true, max_attempts: 1 });
+  });
+
+  it("clamps the attempts", () => {
+    expect(resolveRetryOnEmpty({ max_attempts: 50 }).max_attempts).toBe(5);
+    expect(resolveRetryOnEmpty({ max_attempts: -2 }).max_attempts).toBe(0);
+    expect(resolveRetryOnEmpty({ enabled: false }).enabled).toBe(false);
+  });
+});
+
+describe("runWithEmptyRetry", () => {
+  it("completes normally when the first answer has content", async () => {
+    const event = createEvent();
+    const run = streamingAttempts(["Hello"]);
+
+    await runWithEmptyRetry(event, undefined, run, false);
+
+    expect(run).toHaveBeenCalledTimes(1);
+    expect(event.finish).toHaveBeenCalledWith({ fullResponse: "Hello", finishReason: "stop" });
+  });
+
+  it("re-sends the request after an empty streamed answer and records the retry", async () => {
+    const event = createEvent();
+    const run = streamingAttempts(["", "Second try"]);
+
+    const response = await runWithEmptyRetry(event, { enabled: true, max_attempts: 1 }, run, false);
+
+    expect(run).toHaveBeenCalledTimes(2);
+    expect(response).toBe("Second try");
+    expect(event.finish).toHaveBeenCalledTimes(1);
+    expect(event.finish).toHaveBeenCalledWith({ fullResponse: "Second try", finishReason: "stop", emptyRetries: 1 });
+    expect(event.sendError).not.toHaveBeenCalled();
+  });
+
+  it("reports empty_response once the attempts are exhausted", async () => {
+    const event = createEvent();
+    const run = streamingAttempts(["", "", ""]);
+
+    await runWithEmptyRetry(event, { enabled: true, max_attempts: 2 }, run, false);
+
+    expect(run).toHaveBeenCalledTimes(3);
+    expect(event.finish).not.toHaveBeenCalled();
+    expect(event.sendError).toHaveBeenCalledWith(expect.objectContaining({ code: EMPTY_RESPONSE, details: { attempts: 3 } }));
+  });
+
+  it("does not retry when disabled", async () => {
+    const event = createEvent();
+    const run = streamingAttempts(["", "Never sent"]);
+
+    await runWithEmptyRetry(event, { enabled: false }, run, false);
+
+    expect(run).toHaveBeenCalledTimes(1);
+    expect(event.sendError).toHaveBeenCalledWith(expect.objectContaining({ code: EMPTY_RESPONSE }));
+  });
+
+  it("retries whitespace-only non-streaming answers", async () => {
+    const event = createEvent();
+    const run = vi.fn().mockResolvedValueOnce("  \n").mockResolvedValueOnce("Done");
+
+    await runWithEmptyRetry(event, undefined, run, true);
+
+    expect(run).toHaveBeenCalledTimes(2);
+    expect(event.finish).toHaveBeenCalledWith({ fullResponse: "Done", emptyRetries: 1 });
+  });
+
+  it("does not retry answers that only produced reasoning", async () => {
+    const event = createEvent();
+    const run = vi.fn(async (attemptEvent: AIEvent) => {
+      attemptEvent.sendStream({ reasoning: "Thinking..." });
+      return "";
+    });
+
+    await runWithEmptyRetry(event, undefined, run, true);
+
+    expect(run).toHaveBeenCalledTimes(1);
+    expect(event.finish).toHaveBeenCalledWith({ fullResponse: "" });
+  });
+
+  it("leaves failed attempts to the error path", async () => {
+    const event = createEvent();
+    const run = vi.fn(async (attemptEvent: AIEvent) => {
+      attemptEvent.sendError({ message: "boom" });
+      return "";
+    });
+
+    await runWithEmptyRetry(event, undefined, run, true);
+
+    expect(run).toHaveBeenCalledTimes(1);
+    expect(event.sendError).toHaveBeenCalledTimes(1);
+    expect(event.finish).not.toHaveBeenCalled();
+  });
+
+  it("re-sends the request after a retryable error before any output", async () => {
+    const event = createEvent();
+    const run = vi
+      .fn()
+      .mockImplementationOnce(async (attemptEvent: AIEvent) => {
+        attemptEvent.sendError({ message: "Rate limited", code: "rate_limited", retryable: true });
+        attemptEvent.sendError({ message: "Inference stopped: error" });
+        return "";
+      })
+      .mockResolvedValueOnce("Done");
+
+    await runWithEmptyRetry(event, undefined, run, true);
+
+    expect(run).toHaveBeenCalledTimes(2);
+    expect(event.sendError).not.toHaveBeenCalled();
+    expect(event.finish).toHaveBeenCalledWith({ fullResponse: "Done", emptyRetries: 1 });
+  });
+
+  it("classifies thrown errors and reports the last retryable one", async () => {
+    const event = createEvent();
+    const run = vi.fn().mockRejectedValue(new TypeError("fetch failed"));
+
+    await runWithEmptyRetry(event, { enabled: true, max_attempts: 1 }, run, true);
+
+    expect(run).toHaveBeenCalledTimes(2);
+    expect(event.sendError).toHaveBeenCalledTimes(1);
+    expect(event.sendError).toHaveBeenCalledWith(expect.objectContaining({ code: "network_unreachable", retryable: true }));
+  });
+});
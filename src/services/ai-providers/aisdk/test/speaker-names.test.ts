@@ -0,0 +1,122 @@
+import { describe, expect, it, vi } from "vitest";
+import type { InferenceMessage, ModelSpecs } from "@/schema/inference-engine-schema";
+import { applySpeakerNames, prefixSpeakerNames, SPEAKER_NAMES_HEADER, speakerNamesHeaders, supportsMessageNames, toMessageName, withSpeakerNames } from "../speaker-names";
+
+const specs = (engine: string, config: Record<string, unknown> = {}, model_type: "chat" | "completion" = "chat"): ModelSpecs => ({
+  id: "model-1",
+  engine,
+  model_type,
+  max_concurrent_requests: 1,
+  config: { model: "local", ...config },
+});
+
+const conversation: InferenceMessage[] = [
+  { role: "user", text: "Hello there.", name: "Bob" },
+  { role: "assistant", text: "Welcome to the inn.", name: "Mary Ann" },
+  { role: "user", text: "[Summary] They met." },
+];
+
+describe("supportsMessageNames", () => {
+  it("uses the name field on OpenAI-style chat completions only", () => {
+    expect(supportsMessageNames(specs("openai"))).toBe(true);
+    expect(supportsMessageNames(specs("openai_compatible", { base_url: "http://localhost:5001/v1" }))).toBe(true);
+    expect(supportsMessageNames(specs("openrouter"))).toBe(true);
+    expect(supportsMessageNames(specs("openai", { response_api: true }))).toBe(false);
+    expect(supportsMessageNames(specs("openai_compatible", { model: "gemini-2.5-pro" }))).toBe(false);
+    expect(supportsMessageNames(specs("openai_compatible", {}, "completion"))).toBe(false);
+    expect(supportsMessageNames(specs("anthropic"))).toBe(false);
+    expect(supportsMessageNames(specs("ollama"))).toBe(false);
+  });
+});
+
+describe("toMessageName", () => {
+  it("keeps only the characters the name field accepts", () => {
+    expect(toMessageName("Mary Ann")).toBe("Mary_Ann");
+    expect(toMessageName("  Sir. Reginald, III ")).toBe("Sir_Reginald_III");
+    expect(toMessageName("Kōji-2")).toBe("K_ji-2");
+    expect(toMessageName("x".repeat(80))).toHaveLength(64);
+    expect(toMessageName("???")).toBeUndefined();
+    expect(toMessageName(undefined)).toBeUndefined();
+  });
+});
+
+describe("prefixSpeakerNames", () => {
+  it("prefixes named turns and leaves the rest alone", () => {
+    expect(prefixSpeakerNames(conversation).map((message) => message.text)).toEqual(["Bob: Hello there.", "Mary Ann: Welcome to the inn.", "[Summary] They met."]);
+  });
+
+  it("doesn't prefix twice text the format template already prefixed", () => {
+    const messages: InferenceMessage[] = [{ role: "assistant", text: "Mary Ann: Welcome.", name: "Mary Ann" }];
+    expect(prefixSpeakerNames(messages)).toEqual(messages);
+  });
+});
+
+describe("speakerNamesHeaders", () => {
+  it("lists the names of the user and assistant turns in order", () => {
+    const withTool: InferenceMessage[] = [...conversation, { role: "tool", text: "42", tool_call_id: "call-1", name: "ignored" }];
+    const headers = speakerNamesHeaders(withTool);
+    expect(JSON.parse(decodeURIComponent(headers?.[SPEAKER_NAMES_HEADER] ?? ""))).toEqual(["Bob", "Mary_Ann", null]);
+  });
+
+  it("sends nothing when no turn is named", () => {
+    expect(speakerNamesHeaders([{ role: "user", text: "Hi" }])).toBeUndefined();
+  });
+});
+
+describe("applySpeakerNames", () => {
+  it("names the payload's turns, skipping system and tool messages", () => {
+    const body = {
+      model: "local",
+      messages: [
+        { role: "system", content: "Stay in character." },
+        { role: "user", content: "Hello there." },
+        { role: "assistant", content: "Welcome to the inn." },
+        { role: "tool", content: "42", tool_call_id: "call-1" },
+        { role: "user", content: "[Summary] They met." },
+      ],
+    };
+    expect(applySpeakerNames(body, ["Bob", "Mary_Ann", null])).toEqual({
+      model: "local",
+      messages: [
+        { role: "system", content: "Stay in character." },
+        { role: "user", content: "Hello there.", name: "Bob" },
+        { role: "assistant", content: "Welcome to the inn.", name: "Mary_Ann" },
+        { role: "tool", content: "42", tool_call_id: "call-1" },
+        { role: "user", content: "[Summary] They met." },
+      ],
+    });
+  });
+
+  it("leaves payloads without messages alone", () => {
+    const body = { model: "local", prompt: "Hi" };
+    expect(applySpeakerNames(body, ["Bob"])).toBe(body);
+  });
+});
+
+describe("withSpeakerNames", () => {
+  it("moves the names from the header onto the messages", async () => {
+    const fetchImpl = vi.fn(async (_input: RequestInfo | URL, _init?: RequestInit) => new Response("{}"));
+    const body = JSON.stringify({ messages: [{ role: "user", content: "Hello there." }] });
+
+    await withSpeakerNames(fetchImpl)("https://api.example.com/v1/chat/completions", {
+      method: "POST",
+      body,
+      headers: { "content-type": "application/json", ...speakerNamesHeaders(conversation) },
+    });
+
+    const init = fetchImpl.mock.calls[0][1];
+    const headers = new Headers(init?.headers);
+    expect(headers.has(SPEAKER_NAMES_HEADER)).toBe(false);
+    expect(headers.get("content-type")).toBe("application/json");
+    expect(JSON.parse(String(init?.body))).toEqual({ messages: [{ role: "user", content: "Hello there.", name: "Bob" }] });
+  });
+
+  it("passes requests without the header through untouched", async () => {
+    const fetchImpl = vi.fn(async () => new Response("{}"));
+    const init = { method: "POST", body: "{}", headers: { "content-type": "application/json" } };
+
+    await withSpeakerNames(fetchImpl)("https://api.example.com/v1/chat/completions", init);
+
+    expect(fetchImpl).toHaveBeenCalledWith("https://api.example.com/v1/chat/completions", init);
+  });
+});
@@ -0,0 +1,70 @@
+import { describe, expect, it, vi } from "vitest";
+import { isStreamOptionsRejection, supportsStreamUsage, withStreamUsageFallback } from "../stream-usage";
+
+const streamBody = JSON.stringify({ model: "local", stream: true, stream_options: { include_usage: true }, messages: [] });
+const sentBody = (fetchImpl: ReturnType<typeof vi.fn>, call: number) => JSON.parse((fetchImpl.mock.calls[call] as any[])[1].body);
+
+const rejecting = () =>
+  vi.fn(async (_input: RequestInfo | URL, init?: RequestInit) => {
+    if (JSON.parse(String(init?.body)).stream_options) {
+      return new Response(JSON.stringify({ error: { message: "Unrecognized request argument supplied: stream_options" } }), { status: 400 });
+    }
+    return new Response("data: [DONE]\n\n");
+  });
+
+describe("isStreamOptionsRejection", () => {
+  it("matches client errors that name the field", () => {
+    expect(isStreamOptionsRejection(400, '{"error":"unknown field `stream_options`"}')).toBe(true);
+    expect(isStreamOptionsRejection(422, '{"detail":[{"loc":["body","stream_options","include_usage"]}]}')).toBe(true);
+  });
+
+  it("ignores other failures", () => {
+    expect(isStreamOptionsRejection(400, '{"error":"max_tokens is too large"}')).toBe(false);
+    expect(isStreamOptionsRejection(401, "invalid key for stream_options")).toBe(false);
+    expect(isStreamOptionsRejection(429, "stream_options")).toBe(false);
+    expect(isStreamOptionsRejection(500, "stream_options")).toBe(false);
+  });
+});
+
+describe("withStreamUsageFallback", () => {
+  it("keeps stream_options for servers that accept it", async () => {
+    const fetchImpl = vi.fn(async () => new Response("data: [DONE]\n\n"));
+    const response = await withStreamUsageFallback(fetchImpl, "http://compliant:8080/v1")("http://compliant:8080/v1/chat/completions", { method: "POST", body: streamBody });
+    expect(response.ok).toBe(true);
+    expect(fetchImpl).toHaveBeenCalledTimes(1);
+    expect(sentBody(fetchImpl, 0).stream_options).toEqual({ include_usage: true });
+    expect(supportsStreamUsage("http://compliant:8080/v1")).toBe(true);
+  });
+
+  it("retries once without the field and remembers the server", async () => {
+    const fetchImpl = rejecting();
+    const fetchWithFallback = withStreamUsageFallback(fetchImpl, "http://strict:5000/v1/");
+    const response = await fetchWithFallback("http://strict:5000/v1/chat/completions", { method: "POST", body: streamBody });
+
+    expect(response.ok).toBe(true);
+    expect(fetchImpl).toHaveBeenCalledTimes(2);
+    expect(sentBody(fetchImpl, 1)).toEqual({ model: "local", stream: true, messages: [] });
+    expect(supportsStreamUsage("http://strict:5000/v1")).toBe(false);
+
+    // A new model handle on the same server skips the failing attempt
+    await withStreamUsageFallback(fetchImpl, "http://strict:5000/v1")("http://strict:5000/v1/chat/completions", { method: "POST", body: streamBody });
+    expect(fetchImpl).toHaveBeenCalledTimes(3);
+    expect(sentBody(fetchImpl, 2).stream_options).toBeUndefined();
+  });
+
+  it("returns unrelated errors untouched", async () => {
+    const fetchImpl = vi.fn(async () => new Response('{"error":"model not loaded"}', { status: 400 }));
+    const response = await withStreamUsageFallback(fetchImpl, "http://other:1234/v1")("http://other:1234/v1/chat/completions", { method: "POST", body: streamBody });
+    expect(response.status).toBe(400);
+    expect(await response.text()).toBe('{"error":"model not loaded"}');
+    expect(fetchImpl).toHaveBeenCalledTimes(1);
+    expect(supportsStreamUsage("http://other:1234/v1")).toBe(true);
+  });
+
+  it("passes requests without stream_options through", async () => {
+    const fetchImpl = vi.fn(async () => new Response("{}"));
+    const init = { method: "POST", body: JSON.stringify({ model: "local", messages: [] }) };
+    await withStreamUsageFallback(fetchImpl, "http://strict:5000/v1")("http://strict:5000/v1/chat/completions", init);
+    expect(fetchImpl).toHaveBeenCalledWith("http://strict:5000/v1/chat/completions", init);
+  });
+});
@@ -0,0 +1,52 @@
+import { describe, expect, it } from "vitest";
+import { withPayloadTransforms } from "../payload-transform";
+import { createTextDeltaBuffer } from "../text-boundaries";
+
+const GRINNING = "\u{1F600}";
+const FAMILY = "\u{1F468}\u200d\u{1F469}\u200d\u{1F467}";
+
+const streamDeltas = (deltas: string[]) => {
+  const buffer = createTextDeltaBuffer();
+  const emitted = deltas.map((delta) => buffer.push(delta));
+  emitted.push(buffer.flush());
+  return emitted;
+};
+
+describe("createTextDeltaBuffer", () => {
+  it("never emits half of a surrogate pair", () => {
+    const emitted = streamDeltas([`Hi ${GRINNING[0]}`, `${GRINNING[1]}!`]);
+    expect(emitted).toEqual(["Hi ", `${GRINNING}!`, ""]);
+    expect(emitted.join("")).toBe(`Hi ${GRINNING}!`);
+  });
+
+  it("holds a joined emoji back until the next part arrives", () => {
+    const emitted = streamDeltas([FAMILY.slice(0, 3), FAMILY.slice(3)]);
+    expect(emitted[0]).toBe("\u{1F468}");
+    expect(emitted.join("")).toBe(FAMILY);
+  });
+
+  it("hands over what is left when the stream ends", () => {
+    expect(streamDeltas(["abc", GRINNING[0]])).toEqual(["abc", "", GRINNING[0]]);
+  });
+});
+
+describe("byte-level streams", () => {
+  it("keeps a 4-byte emoji split across two network chunks intact", async () => {
+    const bytes = new TextEncoder().encode(`data: {"results":[{"text":"Hi ${GRINNING}"}]}\n\n`);
+    const cut = bytes.indexOf(0xf0) + 2;
+    const stream = new ReadableStream<Uint8Array>({
+      start(controller) {
+        controller.enqueue(bytes.slice(0, cut));
+        controller.enqueue(bytes.slice(cut));
+        controller.close();
+      },
+    });
+    const fetchImpl = async () => new Response(stream, { headers: { "content-type": "text/event-stream" } });
+
+    const response = await withPayloadTransforms(fetchImpl, undefined, { "results[0].text": "choices[0].delta.content" })("http://localhost");
+    const text = await response.text();
+
+    expect(text).toContain(`"content":"Hi ${GRINNING}"`);
+    expect(text).not.toContain("\uFFFD");
+  });
+});
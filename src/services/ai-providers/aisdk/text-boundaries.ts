@@ -0,0 +1,47 @@
+// Text deltas are JS strings, but a provider can still cut a character in two: an emoji escaped as a
+// surrogate pair ("\ud83d\ude00") split across events, or a joined emoji cut after its zero-width
+// joiner. Forwarding the first half shows a replacement character until the rest arrives, and a
+// stream stopped in between keeps it. Byte-level streams (payload-transform.ts) decode with
+// `TextDecoder`'s `stream` option, which already holds back incomplete UTF-8 sequences.
+
+const ZERO_WIDTH_JOINER = "\u200d";
+
+function isHighSurrogate(code: number): boolean {
+  return code >= 0xd800 && code <= 0xdbff;
+}
+
+// Length of the tail that only makes sense with what comes next
+function incompleteTailLength(text: string): number {
+  let length = 0;
+  if (text.length > 0 && isHighSurrogate(text.charCodeAt(text.length - 1))) {
+    length = 1;
+  }
+  if (text.slice(0, text.length - length).endsWith(ZERO_WIDTH_JOINER)) {
+    length += 1;
+  }
+  return length;
+}
+
+/**
+ * Buffer for streamed text deltas that never emits half a character. `push` returns the text safe
+ * to forward, `flush` whatever was held back once the stream ends.
+ */
+function createTextDeltaBuffer() {
+  let pending = "";
+
+  return {
+    push(delta: string): string {
+      const text = pending + delta;
+      const held = incompleteTailLength(text);
+      pending = text.slice(text.length - held);
+      return text.slice(0, text.length - held);
+    },
+    flush(): string {
+      const text = pending;
+      pending = "";
+      return text;
+    },
+  };
+}
+
+export { createTextDeltaBuffer };
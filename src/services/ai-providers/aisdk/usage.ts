@@ -0,0 +1,14 @@
+import type { LanguageModelUsage } from "ai";
+import type { AIUsage } from "../types/ai-event.type";
+
+/**
+ * Token counts of a finished request, or undefined when the provider reported none
+ */
+function toAIUsage(usage: LanguageModelUsage | undefined): AIUsage | undefined {
+  if (!usage || (usage.inputTokens === undefined && usage.outputTokens === undefined)) {
+    return undefined;
+  }
+  return { inputTokens: usage.inputTokens ?? 0, outputTokens: usage.outputTokens ?? 0 };
+}
+
+export { toAIUsage };
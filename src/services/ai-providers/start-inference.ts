@@ -1,11 +1,22 @@
 import { CallSettings, LanguageModel, Prompt, ToolSet } from "ai";
 import { InferenceParams } from "@/hooks/useInference";
+import type { InferenceMessage } from "@/schema/inference-engine-schema";
 import { Engine } from "@/schema/model-manifest-schema";
 import { toCoreMessages } from "./aisdk/convert-messages";
+import { trackStablePrefix } from "./aisdk/cache-prefix";
+import { assertCapabilitiesSupported } from "./aisdk/capability-checks";
+import { resolveContextUsageLimit } from "./aisdk/context-usage";
 import { convertToolsToAISDK } from "./aisdk/convert-tools";
 import { generateResponse } from "./aisdk/non-streaming";
+import { isGeminiTarget, normalizeMessageRoles } from "./aisdk/normalize-roles";
+import { resolveNormalizeLeadingWhitespace, withLeadingWhitespaceTrimmed } from "./aisdk/leading-whitespace";
 import { getAISDKModel } from "./aisdk/provider-factory";
+import { applyPromptCache } from "./aisdk/prompt-cache";
 import { getProviderOptions } from "./aisdk/provider-options";
+import { resolveRawPrompt } from "./aisdk/raw-prompt";
+import { assertReasoningBudgetSupported } from "./aisdk/reasoning-support";
+import { runWithEmptyRetry } from "./aisdk/retry-on-empty";
+import { prefixSpeakerNames, speakerNamesHeaders, supportsMessageNames } from "./aisdk/speaker-names";
 import { streamResponse } from "./aisdk/streaming";
 import type { AIEvent } from "./types/ai-event.type";
 
@@ -21,31 +32,52 @@ const DEFAULT_MAX_TOKENS = 4000;
  * @returns A Promise that resolves to the generated response text.
  */
 async function callProviderConverseEndpoint(event: AIEvent, params: InferenceParams) {
+  assertReasoningBudgetSupported(params.modelSpecs, (params.parameters as Record<string, any>) || {});
+  const rawPrompt = resolveRawPrompt(params);
+
   // 1. Create Model Instance
-  const model = await getAISDKModel(params.modelSpecs, params.parameters as Record<string, any>);
+  const model = await getAISDKModel(params.modelSpecs, params.parameters as Record<string, any>, rawPrompt);
   const isChatModel = params.modelSpecs.model_type === "chat";
 
   // 2. Convert Messages
   // params.messages is InferenceMessage[]
   // params.system_message is string | undefined
-  if (!params.messages) {
+  if (!params.messages && rawPrompt === undefined) {
     throw new Error("No messages provided");
   }
-  const messages = toCoreMessages(isChatModel ? params.systemPrompt : undefined, params.messages);
+  const engine = params.modelSpecs.engine as Engine;
+  // A raw prompt travels as one user message, which completion endpoints then replace with the verbatim text
+  const requestMessages: InferenceMessage[] = rawPrompt !== undefined ? [{ role: "user", text: rawPrompt }] : params.messages;
+  assertCapabilitiesSupported(params.modelSpecs, requestMessages, (params.parameters as Record<string, any>) || {}, !!params.tools?.length);
+  // Few-shot examples go right after the system prompt, ahead of the conversation
+  const conversation = params.examples?.length ? [...params.examples, ...requestMessages] : requestMessages;
+  // Speaker names go in the `name` field where the API has one, otherwise in front of the text before turns are merged
+  const injectSpeakerNames = isChatModel && rawPrompt === undefined && !!params.parameters?.inject_speaker_names;
+  const namesAsField = injectSpeakerNames && supportsMessageNames(params.modelSpecs);
+  const namedConversation = injectSpeakerNames && !namesAsField ? prefixSpeakerNames(conversation) : conversation;
+  const inferenceMessages = isChatModel ? normalizeMessageRoles(engine, namedConversation, params.modelSpecs.config) : namedConversation;
+  const coreMessages = toCoreMessages(isChatModel ? params.systemPrompt : undefined, inferenceMessages);
+  // Chat requests remember their prompt so the next one can keep its cache breakpoint on the unchanged prefix.
+  // A raw prompt is a one-off test, it leaves the chat's tracked prefix alone.
+  const stablePrefix = params.chatId && rawPrompt === undefined ? trackStablePrefix(params.chatId, coreMessages) : undefined;
+  const messages = applyPromptCache(engine, coreMessages, params.parameters || {}, stablePrefix);
   const tools = params.tools && params.tools.length > 0 ? convertToolsToAISDK(params.tools) : undefined;
 
   // 3. Prepare Options
   // Extract provider specific options from params.parameters
-  const providerOptions = getProviderOptions(params.modelSpecs.engine as Engine, params.parameters || {}, params.modelSpecs.config?.model);
+  const providerOptions = getProviderOptions(engine, params.parameters || {}, params.modelSpecs.config?.model, params.modelSpecs.config);
 
   const parameters = params.parameters as Record<string, any>;
+  // Gemini takes the system prompt once, as the leading system message that becomes its system instruction
+  const routeSystemInMessages = isChatModel && !!params.systemPrompt && isGeminiTarget(engine, params.modelSpecs.config);
   // Ensure defaults
   const finalParams: FinalParams = {
     model,
     messages,
     tools,
-    system: params.systemPrompt,
+    system: routeSystemInMessages ? undefined : params.systemPrompt,
     providerOptions,
+    headers: namesAsField ? speakerNamesHeaders(inferenceMessages) : undefined,
     maxOutputTokens: parameters.max_tokens || DEFAULT_MAX_TOKENS,
     temperature: parameters.temperature,
     topP: parameters.top_p,
@@ -68,13 +100,13 @@ async function callProviderConverseEndpoint(event: AIEvent, params: InferencePar
     providerOptions: providerOptions as Record<string, unknown>,
   });
 
-  // 4. Execute
+  // 4. Execute, re-sending the identical request when the provider answers with nothing
+  const trimLeading = resolveNormalizeLeadingWhitespace(parameters.normalize_leading_whitespace);
   if (params.stream) {
-    return streamResponse(event, finalParams);
+    const contextLimit = resolveContextUsageLimit(params.modelSpecs.capabilities?.context_length, parameters);
+    return runWithEmptyRetry(event, parameters.retry_on_empty, withLeadingWhitespaceTrimmed((attemptEvent) => streamResponse(attemptEvent, finalParams, contextLimit), trimLeading), false);
   } else {
-    const response = await generateResponse(finalParams, event);
-    event.finish({ fullResponse: response });
-    return response;
+    return runWithEmptyRetry(event, parameters.retry_on_empty, withLeadingWhitespaceTrimmed((attemptEvent) => generateResponse(finalParams, attemptEvent), trimLeading), true);
   }
 }
 
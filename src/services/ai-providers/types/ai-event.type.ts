@@ -5,6 +5,21 @@ interface AIStreamPayload {
   reasoning?: string;
   fullResponse?: string;
   toolCalls?: InferenceToolCall[];
+  // Provider notices about the completed answer, e.g. content masked by a guardrail
+  notices?: string[];
+  // Raw guardrail trace, only present when the model config enables tracing
+  guardrailTrace?: unknown;
+  // Set on finish when the request had to be re-sent after empty responses
+  emptyRetries?: number;
+  // Why generation ended: stop, length, content-filter, tool-calls, error or other
+  finishReason?: string;
+  // Tokens the provider billed for the request, every step included; sent once generation ends
+  usage?: AIUsage;
+}
+
+interface AIUsage {
+  inputTokens: number;
+  outputTokens: number;
 }
 
 interface AIToolCallPayload {
@@ -17,6 +32,9 @@ interface AIToolResultPayload extends AIToolCallPayload {
   error?: string;
 }
 
+// Provider data before our text/reasoning mapping, only produced when the event asks for it
+type AIRawPart = { type: "chunk"; value: unknown } | { type: "headers"; headers: Record<string, string> };
+
 interface ResolvedParameters {
   maxOutputTokens?: number;
   temperature?: number;
@@ -29,6 +47,57 @@ interface ResolvedParameters {
   providerOptions?: Record<string, unknown>;
 }
 
+// Provider failures mapped to a stable code, so callers never match on provider wording
+const INFERENCE_ERROR_CODES = [
+  "auth_failed",
+  "rate_limited",
+  "context_overflow",
+  "model_not_found",
+  "network_unreachable",
+  "timeout",
+  "content_filtered",
+  "provider_internal",
+  "cancelled",
+  "unknown",
+] as const;
+type InferenceErrorCode = (typeof INFERENCE_ERROR_CODES)[number];
+
+interface AIError {
+  message: string;
+  // An InferenceErrorCode, or one of the more specific codes below
+  code?: string;
+  // Whether re-sending the identical request may succeed
+  retryable?: boolean;
+  details?: unknown;
+}
+
+// Error code for generations that ended (length, content filter...) after reasoning but before any answer text
+const TRUNCATED_BEFORE_ANSWER = "truncated_before_answer";
+
+// Error code for requests a provider guardrail (Bedrock Guardrails) blocked
+const GUARDRAIL_INTERVENED = "guardrail_intervened";
+
+// Error code for a reasoning budget sent to a model that can't reason, rejected before the request
+const REASONING_NOT_SUPPORTED = "reasoning_not_supported";
+
+// Error code for image attachments or tools sent to a model whose capabilities say it can't take them
+const CAPABILITY_NOT_SUPPORTED = "capability_not_supported";
+
+// Error code for providers that kept answering with no content, after the automatic retries
+const EMPTY_RESPONSE = "empty_response";
+
+// Error code for parameters the engine doesn't take, refused before the request with strict parameter checks
+const PARAMETER_NOT_SUPPORTED = "parameter_not_supported";
+
+// Error code for requests to a paid provider refused because the profile's monthly budget is used up
+const BUDGET_EXCEEDED = "budget_exceeded";
+
+// Error code for malformed requests (unknown role, empty id, too many messages...) refused before they are queued
+const INVALID_REQUEST = "invalid_request";
+
+// Error code for double sends refused before the request (see request-dedupe.ts); callers ignore it silently
+const DUPLICATE_REQUEST = "duplicate_request";
+
 interface AIEvent {
   readonly requestId: string;
   sendStream: (payload: AIStreamPayload) => void;
@@ -36,10 +105,12 @@ interface AIEvent {
   sendImage?: (image: string) => void;
   sendToolCallStart?: (payload: AIToolCallPayload) => void;
   sendToolCallResult?: (payload: AIToolResultPayload) => void;
-  sendError: (error: { message: string; code?: string; details?: unknown }) => void;
+  sendError: (error: AIError) => void;
   finish: (payload?: AIStreamPayload) => void;
   registerAborter: (aborter: () => void) => void;
   reportResolvedParams?: (params: ResolvedParameters) => void;
+  sendRaw?: (part: AIRawPart) => void;
 }
 
-export type { AIEvent, AIStreamPayload, AIToolCallPayload, AIToolResultPayload, ResolvedParameters };
+export { BUDGET_EXCEEDED, CAPABILITY_NOT_SUPPORTED, DUPLICATE_REQUEST, EMPTY_RESPONSE, GUARDRAIL_INTERVENED, INFERENCE_ERROR_CODES, INVALID_REQUEST, PARAMETER_NOT_SUPPORTED, REASONING_NOT_SUPPORTED, TRUNCATED_BEFORE_ANSWER };
+export type { AIError, AIEvent, AIRawPart, AIStreamPayload, AIToolCallPayload, AIToolResultPayload, AIUsage, InferenceErrorCode, ResolvedParameters };
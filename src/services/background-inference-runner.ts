@@ -2,11 +2,12 @@ import { useCurrentChatActiveChapterID } from "@/hooks/chatStore";
 import { useModelManifests } from "@/hooks/manifestStore";
 import { useInference } from "@/hooks/useInference";
 import type { InferenceMessage } from "@/schema/inference-engine-schema";
+import { manifestSupportsReasoning } from "@/services/ai-providers/aisdk/reasoning-support";
 import { getChatChapterById } from "@/services/chat-chapter-service";
 import { ChatMessage } from "@/services/chat-message-service";
 import { formatPrompt } from "@/services/inference/formatter";
 import { removeNestedFields } from "@/services/inference/formatter/remove-nested-fields";
-import { getModelById, Model } from "@/services/model-service";
+import { getModelByIdOrAlias, Model } from "@/services/model-service";
 import { getChatTemplateById } from "@/services/template-chat-service";
 import { getFormatTemplateById } from "@/services/template-format-service";
 import { getInferenceTemplateById } from "@/services/template-inference-service";
@@ -38,7 +39,7 @@ export async function runBackgroundInference(options: BackgroundRunOptions): Pro
     return null;
   }
 
-  const model = chatTemplate?.model_id ? await getModelById(chatTemplate.model_id) : null;
+  const model = chatTemplate?.model_id ? await getModelByIdOrAlias(chatTemplate.model_id, chatTemplate.profile_id) : null;
   if (!model) {
     console.error(`Model with ID ${chatTemplate?.model_id} not found`);
     return null;
@@ -85,6 +86,10 @@ export async function runBackgroundInference(options: BackgroundRunOptions): Pro
     config: model.config,
     max_concurrent_requests: model.max_concurrency,
     engine: manifest.engine,
+    supports_reasoning: manifestSupportsReasoning(manifest),
+    inference_fields: manifest.inference_fields,
+    capabilities: model.capabilities,
+    fallback_model_ids: model.fallback_model_ids,
   };
 
   // Fire and wait (non-streaming)
@@ -94,6 +99,7 @@ export async function runBackgroundInference(options: BackgroundRunOptions): Pro
     systemPrompt: formattedSystemPrompt,
     parameters: fixedParameters,
     stream: false,
+    priority: "background",
   });
 
   // We do not have stream events here; instead we rely on the returned promise resolving through
@@ -1,9 +1,10 @@
 import { useModelManifests } from "@/hooks/manifestStore";
 import { useInference } from "@/hooks/useInference";
 import type { InferenceMessage, ModelSpecs } from "@/schema/inference-engine-schema";
+import { manifestSupportsReasoning } from "@/services/ai-providers/aisdk/reasoning-support";
 import { formatPrompt } from "@/services/inference/formatter";
 import { removeNestedFields } from "@/services/inference/formatter/remove-nested-fields";
-import { getModelById, Model } from "@/services/model-service";
+import { getModelByIdOrAlias, Model } from "@/services/model-service";
 import { getChatTemplateById } from "@/services/template-chat-service";
 import { getFormatTemplateById } from "@/services/template-format-service";
 import { getInferenceTemplateById } from "@/services/template-inference-service";
@@ -29,7 +30,7 @@ export async function runBackgroundInference(options: BridgeRunOptions): Promise
     return null;
   }
 
-  const model = chatTemplate?.model_id ? await getModelById(chatTemplate.model_id) : null;
+  const model = chatTemplate?.model_id ? await getModelByIdOrAlias(chatTemplate.model_id, chatTemplate.profile_id) : null;
   if (!model) {
     return null;
   }
@@ -65,6 +66,10 @@ export async function runBackgroundInference(options: BridgeRunOptions): Promise
     config: model.config,
     max_concurrent_requests: model.max_concurrency,
     engine: manifest.engine,
+    supports_reasoning: manifestSupportsReasoning(manifest),
+    inference_fields: manifest.inference_fields,
+    capabilities: model.capabilities,
+    fallback_model_ids: model.fallback_model_ids,
   };
 
   await runInference({
@@ -73,6 +78,7 @@ export async function runBackgroundInference(options: BridgeRunOptions): Promise
     systemPrompt: formattedSystemPrompt,
     parameters: fixedParameters,
     stream: false,
+    priority: "background",
   });
 
   // The current inference hook resolves via events; for now return null.
@@ -5,13 +5,15 @@ import { useCurrentProfile } from "@/hooks/ProfileStore";
 import { useInference } from "@/hooks/useInference";
 import { Character } from "@/schema/characters-schema";
 import { InferenceMessage, ModelSpecs } from "@/schema/inference-engine-schema";
+import { manifestSupportsReasoning } from "@/services/ai-providers/aisdk/reasoning-support";
 import { INFERENCE_TIMEOUT_MS } from "@/services/ai-providers/constants";
 import { listCharacters } from "./character-service";
 import { getChatChapterById } from "./chat-chapter-service";
 import { ChatMessage } from "./chat-message-service";
 import { formatPrompt } from "./inference/formatter";
 import { removeNestedFields } from "./inference/formatter/remove-nested-fields";
-import { getModelById, Model } from "./model-service";
+import type { RequestPriority } from "./inference/request-priority";
+import { getModelByIdOrAlias, Model } from "./model-service";
 import { getChatTemplateById } from "./template-chat-service";
 import { getFormatTemplateById } from "./template-format-service";
 import { getInferenceTemplateById } from "./template-inference-service";
@@ -26,6 +28,8 @@ export interface BackgroundInferenceOptions {
   systemPrompt?: string;
   parameters?: Record<string, any>;
   disableLogs?: boolean;
+  // "background" by default; "interactive" when the user waits on the result
+  priority?: RequestPriority;
 }
 
 /**
@@ -118,6 +122,10 @@ export function useBackgroundInference() {
           config: model.config,
           max_concurrent_requests: model.max_concurrency,
           engine: manifest?.engine || "",
+          supports_reasoning: manifestSupportsReasoning(manifest),
+          inference_fields: manifest?.inference_fields,
+          capabilities: model.capabilities,
+          fallback_model_ids: model.fallback_model_ids,
         };
 
         // Queue the inference request
@@ -128,6 +136,7 @@ export function useBackgroundInference() {
           parameters,
           stream: false, // No streaming for background inference
           disableLogs: options.disableLogs,
+          priority: options.priority ?? "background",
         })
           .then((requestId) => {
             if (!requestId) {
@@ -173,7 +182,7 @@ export function useBackgroundInference() {
           return null;
         }
 
-        const model = chatTemplate?.model_id ? await getModelById(chatTemplate.model_id) : null;
+        const model = chatTemplate?.model_id ? await getModelByIdOrAlias(chatTemplate.model_id, chatTemplate.profile_id) : null;
         if (!model) {
           console.error(`Model with ID ${chatTemplate?.model_id} not found`);
           return null;
@@ -202,6 +211,7 @@ export function useBackgroundInference() {
           formatTemplate,
           chatTemplate,
           systemOverridePrompt: systemPrompt,
+          profileId: currentProfile!.id,
           chatConfig: {
             character: characterList.find((character) => character.id === context?.characterID),
             user_character: characterList.find((character) => character.id === context?.userCharacterID) as Character,
@@ -3,8 +3,8 @@ import { parseBoolean } from "@/pages/agents/components/json-schema/schema-utils
 import { formatDateTime } from "@/utils/date-time";
 import { Character, CharacterSchema, CreateCharacterSchema, UpdateCharacterSchema } from "../schema/characters-schema";
 import { uuidUtils } from "../schema/utils-schema";
-import { buildUpdateParams, executeDBQuery, selectDBQuery } from "../utils/database";
-import { removeDirectoryRecursive, removeFile } from "./file-system-service";
+import { buildUpdateParams, executeDBQuery, selectDBQuery, setFavorite } from "../utils/database";
+import { isAssetStorePath, removeDirectoryRecursive, removeFile } from "./file-system-service";
 
 // Interface for filtering characters
 export interface CharacterFilter {
@@ -39,8 +39,8 @@ export async function createCharacter(characterData: z.infer<typeof CreateCharac
   await executeDBQuery(
     `INSERT INTO characters (
       id, profile_id, name, type, version, avatar_path, external_update_link, auto_update, system_override, settings, custom, expressions, character_manifest_id, lorebook_id,
-      created_at, updated_at, tags
-    ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)`,
+      created_at, updated_at, tags, creator, character_version, creator_notes
+    ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20)`,
     [
       id,
       profileId,
@@ -59,6 +59,9 @@ export async function createCharacter(characterData: z.infer<typeof CreateCharac
       now,
       now,
       tags,
+      validatedCharacter.creator,
+      validatedCharacter.character_version,
+      validatedCharacter.creator_notes,
     ],
   );
 
@@ -84,6 +87,7 @@ export async function getCharacterById(id: string): Promise<Character | null> {
   character.tags = character.tags ? JSON.parse(character.tags) : [];
 
   character.auto_update = parseBoolean(character.auto_update);
+  character.favorite = parseBoolean(character.favorite);
 
   // Convert dates
   character.created_at = new Date(character.created_at);
@@ -128,7 +132,8 @@ export async function listCharacters(profile_id: string, filter?: CharacterFilte
     query += ` WHERE ${conditions.join(" AND ")}`;
   }
 
-  query += " ORDER BY created_at DESC";
+  // Favorites first. id breaks ties between rows created within the same second so list order is stable
+  query += " ORDER BY favorite DESC, created_at DESC, id ASC";
 
   const results = await selectDBQuery<any[]>(query, params);
   return results.map((character) => {
@@ -139,6 +144,7 @@ export async function listCharacters(profile_id: string, filter?: CharacterFilte
     character.tags = character.tags ? JSON.parse(character.tags) : [];
 
     character.auto_update = parseBoolean(character.auto_update);
+    character.favorite = parseBoolean(character.favorite);
 
     // Convert dates
     character.created_at = new Date(character.created_at);
@@ -175,6 +181,11 @@ export async function updateCharacter(id: string, updateData: z.infer<typeof Upd
   return getCharacterById(characterId);
 }
 
+// Mark or unmark a character as favorite
+export async function setCharacterFavorite(id: string, profileId: string, favorite: boolean): Promise<boolean> {
+  return setFavorite("characters", id, profileId, favorite);
+}
+
 // Delete a character
 export async function deleteCharacter(id: string): Promise<boolean> {
   const characterId = uuidUtils.uuid().parse(id);
@@ -186,15 +197,15 @@ export async function deleteCharacter(id: string): Promise<boolean> {
   // Delete from DB first
   const result = await executeDBQuery("DELETE FROM characters WHERE id = $1", [characterId]);
   if (result.rowsAffected > 0) {
-    // Remove avatar file if present
-    if (character.avatar_path) {
+    // Remove avatar file if present. Asset store files may be shared and are left to asset garbage collection.
+    if (character.avatar_path && !isAssetStorePath(character.avatar_path)) {
       try {
         await removeFile(character.avatar_path);
       } catch (err) {
         console.warn(`Failed to remove avatar for character ${character.id}:`, err);
       }
     }
-    // Remove expression folder for type 'character'
+    // Remove legacy expression folder for type 'character'
     if (character.type === "character") {
       try {
         await removeDirectoryRecursive(`images/characters/${character.id}`);
@@ -0,0 +1,67 @@
+import { ChatMarker, CreateChatMarkerParams, chatMarkerSchema, createChatMarkerSchema, UpdateChatMarkerParams, updateChatMarkerSchema } from "@/schema/chat-marker-schema";
+import { parseBoolean } from "@/pages/agents/components/json-schema/schema-utils";
+import { formatDateTime } from "@/utils/date-time";
+import { uuidUtils } from "../schema/utils-schema";
+import { buildUpdateParams, executeDBQuery, selectDBQuery } from "../utils/database";
+
+function parseMarkerRow(row: any): ChatMarker {
+  return chatMarkerSchema.parse({
+    ...row,
+    context_reset: parseBoolean(row.context_reset),
+    created_at: new Date(row.created_at),
+    updated_at: new Date(row.updated_at),
+  });
+}
+
+// Create a marker in a chapter
+export async function createChatMarker(markerData: CreateChatMarkerParams): Promise<ChatMarker> {
+  const validated = createChatMarkerSchema.parse({
+    ...markerData,
+    chat_id: uuidUtils.uuid().parse(markerData.chat_id),
+    chapter_id: uuidUtils.uuid().parse(markerData.chapter_id),
+  });
+  const id = crypto.randomUUID();
+  const now = formatDateTime();
+
+  await executeDBQuery(
+    `INSERT INTO chat_markers (id, chat_id, chapter_id, title, position, color, context_reset, created_at, updated_at)
+     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)`,
+    [id, validated.chat_id, validated.chapter_id, validated.title, validated.position, validated.color, validated.context_reset ? 1 : 0, now, now],
+  );
+
+  return chatMarkerSchema.parse({ ...validated, id, created_at: new Date(now), updated_at: new Date(now) });
+}
+
+// List a chat's markers, optionally for one chapter, in display order
+export async function listChatMarkers(chatId: string, chapterId?: string): Promise<ChatMarker[]> {
+  const params: string[] = [uuidUtils.uuid().parse(chatId)];
+  let query = "SELECT * FROM chat_markers WHERE chat_id = $1";
+  if (chapterId) {
+    params.push(uuidUtils.uuid().parse(chapterId));
+    query += " AND chapter_id = $2";
+  }
+
+  const result = await selectDBQuery<any[]>(`${query} ORDER BY chapter_id, position ASC, created_at ASC`, params);
+  return result.map(parseMarkerRow);
+}
+
+// Update a marker's title, position, color or context reset flag
+export async function updateChatMarker(id: string, chatId: string, updateData: UpdateChatMarkerParams): Promise<ChatMarker | null> {
+  const markerId = uuidUtils.uuid().parse(id);
+  const validChatId = uuidUtils.uuid().parse(chatId);
+  const validated = updateChatMarkerSchema.parse(updateData);
+
+  const { updates, values, whereClause } = buildUpdateParams(markerId, validated);
+  if (updates.length > 0) {
+    await executeDBQuery(`UPDATE chat_markers SET ${updates.join(", ")}${whereClause} AND chat_id = $${values.length + 1}`, [...values, validChatId]);
+  }
+
+  const result = await selectDBQuery<any[]>("SELECT * FROM chat_markers WHERE id = $1 AND chat_id = $2", [markerId, validChatId]);
+  return result.length > 0 ? parseMarkerRow(result[0]) : null;
+}
+
+// Delete a marker
+export async function deleteChatMarker(id: string, chatId: string): Promise<boolean> {
+  const result = await executeDBQuery("DELETE FROM chat_markers WHERE id = $1 AND chat_id = $2", [uuidUtils.uuid().parse(id), uuidUtils.uuid().parse(chatId)]);
+  return result.rowsAffected > 0;
+}
@@ -1,7 +1,7 @@
 import { ChatMessage, ChatMessageType, CreateChatMessageParams, chatMessageSchema, UpdateChatMessageParams } from "@/schema/chat-message-schema";
 import { formatDateTime } from "@/utils/date-time";
 import { uuidUtils } from "../schema/utils-schema";
-import { buildUpdateParams, executeDBQuery, selectDBQuery } from "../utils/database";
+import { buildUpdateParams, executeDBQuery, executeDBWrite, selectDBQuery } from "../utils/database";
 
 // Interface for filtering chat messages
 export interface ChatMessageFilter {
@@ -12,6 +12,11 @@ export interface ChatMessageFilter {
   disabled?: boolean;
 }
 
+// A stale index, e.g. after variants were removed, falls back to the last variant
+function clampMessageIndex(messages: string[], index: number): number {
+  return Math.min(Math.max(index || 0, 0), Math.max(messages.length - 1, 0));
+}
+
 // Create a new chat message
 export async function createChatMessage(messageData: CreateChatMessageParams): Promise<ChatMessage> {
   // Validate chat_id is a valid UUID
@@ -42,7 +47,7 @@ export async function createChatMessage(messageData: CreateChatMessageParams): P
   // Convert messages array to JSON string for storage
   const messagesStr = JSON.stringify(validatedMessage.messages);
 
-  await executeDBQuery(
+  await executeDBWrite(
     `INSERT INTO chat_messages (
       id, 
       chat_id,
@@ -94,6 +99,7 @@ export async function getChatMessageById(id: string): Promise<ChatMessage | null
       messages, 
       message_index,
       disabled, 
+      pinned,
       tokens,
       extra,
       created_at, 
@@ -112,6 +118,7 @@ export async function getChatMessageById(id: string): Promise<ChatMessage | null
   // Parse JSON string back to array
   message.messages = JSON.parse(message.messages || "[]");
   message.extra = JSON.parse(message.extra || "{}");
+  message.message_index = clampMessageIndex(message.messages, message.message_index);
 
   // Convert date strings to Date objects
   message.created_at = new Date(message.created_at);
@@ -121,6 +128,7 @@ export async function getChatMessageById(id: string): Promise<ChatMessage | null
   if (typeof message.disabled === "string") {
     message.disabled = message.disabled.toLowerCase() === "true" || message.disabled === "1";
   }
+  message.pinned = message.pinned === "true" || message.pinned === 1 || message.pinned === true;
 
   return message as ChatMessage;
 }
@@ -138,6 +146,7 @@ export async function listChatMessages(filter?: ChatMessageFilter): Promise<Chat
       messages, 
       message_index,
       disabled, 
+      pinned,
       tokens,
       extra,
       created_at, 
@@ -197,14 +206,19 @@ export async function listChatMessages(filter?: ChatMessageFilter): Promise<Chat
   const result = await selectDBQuery<any[]>(query, params);
 
   // Process results
-  return result.map((message) => ({
-    ...message,
-    messages: JSON.parse(message.messages || "[]"),
-    extra: JSON.parse(message.extra || "{}"),
-    disabled: message.disabled === "true" || message.disabled === 1,
-    created_at: new Date(message.created_at),
-    updated_at: new Date(message.updated_at),
-  })) as ChatMessage[];
+  return result.map((message) => {
+    const messages: string[] = JSON.parse(message.messages || "[]");
+    return {
+      ...message,
+      messages,
+      message_index: clampMessageIndex(messages, message.message_index),
+      extra: JSON.parse(message.extra || "{}"),
+      disabled: message.disabled === "true" || message.disabled === 1,
+      pinned: message.pinned === "true" || message.pinned === 1,
+      created_at: new Date(message.created_at),
+      updated_at: new Date(message.updated_at),
+    };
+  }) as ChatMessage[];
 }
 
 // Update a chat message
@@ -234,13 +248,59 @@ export async function updateChatMessage(id: string, updateData: UpdateChatMessag
 
   // Execute update if there are fields to update
   if (updates.length > 0) {
-    await executeDBQuery(`UPDATE chat_messages SET ${updates.join(", ")}${whereClause}`, values);
+    await executeDBWrite(`UPDATE chat_messages SET ${updates.join(", ")}${whereClause}`, values);
   }
 
   // Return the updated message
   return getChatMessageById(messageId);
 }
 
+/**
+ * Set top-level keys of a message's `extra` in one statement, so keys written at the same time by another
+ * update (variant models, translations) are kept. A key set to null is removed.
+ * @param messages Also replaces the variants, in the same statement
+ */
+export async function mergeChatMessageExtra(id: string, values: Record<string, unknown>, messages?: string[]): Promise<boolean> {
+  const messageId = uuidUtils.uuid().parse(id);
+  const params: unknown[] = [JSON.stringify(values), formatDateTime()];
+  let setMessages = "";
+  if (messages) {
+    params.push(JSON.stringify(messages));
+    setMessages = `, messages = $${params.length}`;
+  }
+  params.push(messageId);
+
+  const result = await executeDBWrite(
+    `UPDATE chat_messages SET extra = json_patch(COALESCE(NULLIF(extra, ''), '{}'), $1), updated_at = $2${setMessages} WHERE id = $${params.length}`,
+    params,
+  );
+  return result.rowsAffected > 0;
+}
+
+// Make one of a message's variants the active one: shown in the transcript and sent as context
+export async function setActiveVariant(id: string, chatId: string, variantIndex: number): Promise<void> {
+  const message = await getChatMessageById(id);
+  if (!message || message.chat_id !== uuidUtils.uuid().parse(chatId)) {
+    throw new Error(`Message ${id} not found`);
+  }
+  if (!Number.isInteger(variantIndex) || variantIndex < 0 || variantIndex >= message.messages.length) {
+    throw new Error(`Message ${id} has no variant ${variantIndex}`);
+  }
+
+  await executeDBQuery("UPDATE chat_messages SET message_index = $1, updated_at = $2 WHERE id = $3 AND chat_id = $4", [variantIndex, formatDateTime(), message.id, message.chat_id]);
+}
+
+// Pin or unpin a message. Pinned messages are never trimmed from the context.
+export async function setMessagePinned(id: string, chatId: string, pinned: boolean): Promise<boolean> {
+  const result = await executeDBQuery("UPDATE chat_messages SET pinned = $1, updated_at = $2 WHERE id = $3 AND chat_id = $4", [
+    pinned ? 1 : 0,
+    formatDateTime(),
+    uuidUtils.uuid().parse(id),
+    uuidUtils.uuid().parse(chatId),
+  ]);
+  return result.rowsAffected > 0;
+}
+
 // Delete a chat message
 export async function deleteChatMessage(id: string): Promise<boolean> {
   // Validate ID input
@@ -258,6 +318,76 @@ export async function getChatMessagesByChatId(chatId: string, chapterId: string)
   return listChatMessages({ chat_id: validChatId, chapter_id: chapterId });
 }
 
+// A message as read by the chat synopsis and next speaker selection, `sequence` being its rowid
+export interface SequencedChatMessage {
+  sequence: number;
+  type: ChatMessageType;
+  character_id: string | null;
+  text: string;
+  // Left out of the prompt, see exclude_from_context
+  excluded: boolean;
+}
+
+interface SequencedMessageRow {
+  sequence: number;
+  type: ChatMessageType;
+  character_id: string | null;
+  messages: string;
+  message_index: number;
+  extra: string | null;
+}
+
+function toSequencedMessage(row: SequencedMessageRow): SequencedChatMessage {
+  const messages: string[] = JSON.parse(row.messages || "[]");
+  const extra = JSON.parse(row.extra || "{}");
+  return {
+    sequence: Number(row.sequence),
+    type: row.type,
+    character_id: row.character_id,
+    text: messages[clampMessageIndex(messages, row.message_index)] ?? "",
+    excluded: !!extra?.exclude_from_context,
+  };
+}
+
+// Enabled messages of a chat added after the given rowid, oldest first, with their active variant
+export async function listMessagesAfterSequence(chatId: string, afterSequence: number, limit: number): Promise<SequencedChatMessage[]> {
+  const validChatId = uuidUtils.uuid().parse(chatId);
+
+  const result = await selectDBQuery<SequencedMessageRow[]>(
+    "SELECT rowid AS sequence, type, character_id, messages, message_index, extra FROM chat_messages WHERE chat_id = $1 AND rowid > $2 AND disabled = 0 ORDER BY rowid LIMIT $3",
+    [validChatId, afterSequence, limit],
+  );
+
+  return result.map(toSequencedMessage);
+}
+
+// Newest enabled messages of a chat, at most `limit`, oldest first
+export async function listRecentMessages(chatId: string, limit: number): Promise<SequencedChatMessage[]> {
+  const validChatId = uuidUtils.uuid().parse(chatId);
+
+  const result = await selectDBQuery<SequencedMessageRow[]>(
+    "SELECT rowid AS sequence, type, character_id, messages, message_index, extra FROM chat_messages WHERE chat_id = $1 AND disabled = 0 ORDER BY rowid DESC LIMIT $2",
+    [validChatId, limit],
+  );
+
+  return result.map(toSequencedMessage).reverse();
+}
+
+export async function countMessagesAfterSequence(chatId: string, afterSequence: number): Promise<number> {
+  const validChatId = uuidUtils.uuid().parse(chatId);
+
+  const result = await selectDBQuery<{ count: number }[]>("SELECT COUNT(*) AS count FROM chat_messages WHERE chat_id = $1 AND rowid > $2 AND disabled = 0", [validChatId, afterSequence]);
+  return Number(result[0]?.count) || 0;
+}
+
+// rowid of the newest message of a chat, 0 without messages
+export async function getLatestMessageSequence(chatId: string): Promise<number> {
+  const validChatId = uuidUtils.uuid().parse(chatId);
+
+  const result = await selectDBQuery<{ sequence: number | null }[]>("SELECT MAX(rowid) AS sequence FROM chat_messages WHERE chat_id = $1", [validChatId]);
+  return Number(result[0]?.sequence) || 0;
+}
+
 // Get the latest position for a chat to add a new message
 export async function getNextMessagePosition(chatId: string, chapterId: string): Promise<number> {
   const validChatId = uuidUtils.uuid().parse(chatId);
@@ -1,8 +1,10 @@
+import { type DeleteOutcome, deleteChatCommand } from "@/commands/database";
+import { parseBoolean } from "@/pages/agents/components/json-schema/schema-utils";
 import type { ChatDisplaySettings } from "@/schema/chat-schema";
 import { Chat, ChatParticipant, ChatUserSettings, CreateChatParams, chatSchema } from "@/schema/chat-schema";
 import { formatDateTime } from "@/utils/date-time";
 import { uuidUtils } from "../schema/utils-schema";
-import { buildUpdateParams, executeDBQuery, selectDBQuery } from "../utils/database";
+import { buildUpdateParams, executeDBQuery, selectDBQuery, setFavorite } from "../utils/database";
 
 // Interface for filtering chats
 export interface ChatFilter {
@@ -87,6 +89,10 @@ export async function getChatById(id: string, profileId?: string): Promise<Chat
       user_character_id,
       user_character_settings,
       settings,
+      favorite,
+      synopsis,
+      synopsis_up_to_sequence,
+      synopsis_updated_at,
       created_at, 
       updated_at
     FROM chats 
@@ -111,8 +117,11 @@ export async function getChatById(id: string, profileId?: string): Promise<Chat
   chat.participants = JSON.parse(chat.participants || "[]");
   chat.user_character_settings = JSON.parse(chat.user_character_settings || "[]");
   chat.settings = chat.settings ? JSON.parse(chat.settings) : null;
+  chat.favorite = parseBoolean(chat.favorite);
+  chat.synopsis_up_to_sequence = Number(chat.synopsis_up_to_sequence) || 0;
 
   // Convert date strings to Date objects
+  chat.synopsis_updated_at = chat.synopsis_updated_at ? new Date(chat.synopsis_updated_at) : null;
   chat.created_at = new Date(chat.created_at);
   chat.updated_at = new Date(chat.updated_at);
 
@@ -132,8 +141,24 @@ export async function listChats(filter?: ChatFilter): Promise<Chat[]> {
       user_character_id,
       user_character_settings,
       settings,
+      favorite,
+      synopsis,
+      synopsis_up_to_sequence,
+      synopsis_updated_at,
       created_at, 
-      updated_at
+      updated_at,
+      (
+        SELECT COUNT(*)
+        FROM chat_messages m
+        JOIN chat_chapters c ON c.id = m.chapter_id
+        LEFT JOIN chat_read_state rs ON rs.chat_id = m.chat_id AND rs.profile_id = chats.profile_id
+        WHERE m.chat_id = chats.id
+          AND (
+            rs.chat_id IS NULL
+            OR c.sequence > rs.last_read_chapter_sequence
+            OR (m.chapter_id = rs.last_read_chapter_id AND m.position > rs.last_read_position)
+          )
+      ) AS unread_count
     FROM chats
   `;
 
@@ -161,8 +186,8 @@ export async function listChats(filter?: ChatFilter): Promise<Chat[]> {
     query += ` WHERE ${conditions.join(" AND ")}`;
   }
 
-  // Add order by to ensure consistent results
-  query += " ORDER BY created_at DESC";
+  // Favorites first, then newest. id breaks ties between chats created in the same second
+  query += " ORDER BY favorite DESC, created_at DESC, id ASC";
 
   const result = await selectDBQuery<any[]>(query, params);
 
@@ -172,14 +197,24 @@ export async function listChats(filter?: ChatFilter): Promise<Chat[]> {
     participants: JSON.parse(chat.participants || "[]"),
     user_character_settings: JSON.parse(chat.user_character_settings || "[]"),
     settings: chat.settings ? JSON.parse(chat.settings) : null,
+    favorite: parseBoolean(chat.favorite),
+    unread_count: Number(chat.unread_count) || 0,
+    synopsis_up_to_sequence: Number(chat.synopsis_up_to_sequence) || 0,
+    synopsis_updated_at: chat.synopsis_updated_at ? new Date(chat.synopsis_updated_at) : null,
     created_at: new Date(chat.created_at),
     updated_at: new Date(chat.updated_at),
   })) as Chat[];
 }
 
 // Update a chat
-export async function updateChat(id: string, updateData: Partial<Omit<Chat, "id" | "profile_id" | "created_at" | "updated_at">>): Promise<Chat | null> {
+export async function updateChat(
+  id: string,
+  updateData: Partial<Omit<Chat, "id" | "profile_id" | "created_at" | "updated_at" | "unread_count" | "synopsis_up_to_sequence" | "synopsis_updated_at">>,
+): Promise<Chat | null> {
   const chatId = uuidUtils.uuid().parse(id);
+  // unread_count is computed by listChats, never stored; drop it when a listed chat is passed back.
+  // The synopsis refresh point only moves through setChatSynopsis
+  const { unread_count: _unreadCount, synopsis_up_to_sequence: _synopsisUpToSequence, synopsis_updated_at: _synopsisUpdatedAt, ...chatUpdate } = updateData as Partial<Chat>;
 
   // Get the current chat to ensure it exists
   const currentChat = await getChatById(chatId);
@@ -195,7 +230,7 @@ export async function updateChat(id: string, updateData: Partial<Omit<Chat, "id"
   };
 
   // Build update parameters
-  const { updates, values, whereClause } = buildUpdateParams(chatId, updateData, fieldMapping);
+  const { updates, values, whereClause } = buildUpdateParams(chatId, chatUpdate, fieldMapping);
 
   // Execute update if there are fields to update
   if (updates.length > 0) {
@@ -206,14 +241,98 @@ export async function updateChat(id: string, updateData: Partial<Omit<Chat, "id"
   return getChatById(chatId);
 }
 
-// Delete a chat
-export async function deleteChat(id: string): Promise<boolean> {
+// Mark or unmark a chat as favorite
+export async function setChatFavorite(id: string, profileId: string, favorite: boolean): Promise<boolean> {
+  return setFavorite("chats", id, profileId, favorite);
+}
+
+// Delete a chat of the profile. The backend may ask for a second call with a confirmation token first.
+export async function deleteChat(id: string, profileId: string, confirmationToken?: string): Promise<DeleteOutcome> {
   // Validate ID input
   const chatId = uuidUtils.uuid().parse(id);
+  const validProfileId = uuidUtils.uuid().parse(profileId);
+
+  return deleteChatCommand(chatId, validProfileId, confirmationToken);
+}
+
+export interface ChatReadPosition {
+  // Null when the read message and every earlier one were deleted
+  message_id: string | null;
+  last_read_at: Date;
+}
+
+// Remember the last message the profile read in a chat
+export async function setChatReadPosition(chatId: string, profileId: string, messageId: string): Promise<boolean> {
+  const validChatId = uuidUtils.uuid().parse(chatId);
+  const validProfileId = uuidUtils.uuid().parse(profileId);
+
+  const result = await executeDBQuery(
+    `INSERT INTO chat_read_state (chat_id, profile_id, last_read_message_id, last_read_chapter_id, last_read_chapter_sequence, last_read_position, last_read_at)
+     SELECT m.chat_id, ch.profile_id, m.id, m.chapter_id, c.sequence, m.position, $4
+     FROM chat_messages m
+     JOIN chat_chapters c ON c.id = m.chapter_id
+     JOIN chats ch ON ch.id = m.chat_id
+     WHERE m.id = $1 AND m.chat_id = $2 AND ch.profile_id = $3
+     ON CONFLICT(chat_id, profile_id) DO UPDATE SET
+       last_read_message_id = excluded.last_read_message_id,
+       last_read_chapter_id = excluded.last_read_chapter_id,
+       last_read_chapter_sequence = excluded.last_read_chapter_sequence,
+       last_read_position = excluded.last_read_position,
+       last_read_at = excluded.last_read_at`,
+    [messageId, validChatId, validProfileId, formatDateTime()],
+  );
+
+  return result.rowsAffected > 0;
+}
+
+// Get where the profile left off in a chat, falling back to the nearest earlier message if the read one was deleted
+export async function getChatReadPosition(chatId: string, profileId: string): Promise<ChatReadPosition | null> {
+  const validChatId = uuidUtils.uuid().parse(chatId);
+  const validProfileId = uuidUtils.uuid().parse(profileId);
+
+  const result = await selectDBQuery<{ message_id: string | null; last_read_at: string }[]>(
+    `SELECT
+       rs.last_read_at,
+       (
+         SELECT m.id
+         FROM chat_messages m
+         JOIN chat_chapters c ON c.id = m.chapter_id
+         WHERE m.chat_id = rs.chat_id
+           AND (
+             m.id = rs.last_read_message_id
+             OR c.sequence < rs.last_read_chapter_sequence
+             OR (m.chapter_id = rs.last_read_chapter_id AND m.position <= rs.last_read_position)
+           )
+         ORDER BY (m.id = rs.last_read_message_id) DESC, c.sequence DESC, m.position DESC
+         LIMIT 1
+       ) AS message_id
+     FROM chat_read_state rs
+     WHERE rs.chat_id = $1 AND rs.profile_id = $2`,
+    [validChatId, validProfileId],
+  );
+
+  if (result.length === 0) {
+    return null;
+  }
+
+  return { message_id: result[0].message_id, last_read_at: new Date(result[0].last_read_at) };
+}
 
-  const result = await executeDBQuery("DELETE FROM chats WHERE id = $1", [chatId]);
+/**
+ * Store a chat's synopsis. upToSequence moves the refresh point to that message rowid, omitted the
+ * point stays where it was (e.g. when the synopsis is edited by hand).
+ */
+export async function setChatSynopsis(chatId: string, profileId: string, synopsis: string | null, upToSequence?: number): Promise<boolean> {
+  const validChatId = uuidUtils.uuid().parse(chatId);
+  const validProfileId = uuidUtils.uuid().parse(profileId);
+
+  const result = await executeDBQuery(
+    `UPDATE chats
+     SET synopsis = $1, synopsis_up_to_sequence = COALESCE($2, synopsis_up_to_sequence), synopsis_updated_at = $3
+     WHERE id = $4 AND profile_id = $5`,
+    [synopsis, upToSequence ?? null, formatDateTime(), validChatId, validProfileId],
+  );
 
-  // Return true if a row was affected (chat was deleted)
   return result.rowsAffected > 0;
 }
 
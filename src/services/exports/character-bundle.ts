@@ -0,0 +1,136 @@
+import { getVersion } from "@tauri-apps/api/app";
+import { writeFile } from "@tauri-apps/plugin-fs";
+import { type BundleCharacterRef, type BundleChat, CHARACTER_BUNDLE_VERSION, type CharacterBundle } from "@/schema/character-bundle-schema";
+import type { Chat } from "@/schema/chat-schema";
+import { getCharacterById, listCharacters } from "@/services/character-service";
+import { getChaptersByChatId } from "@/services/chat-chapter-service";
+import { listChatMarkers } from "@/services/chat-marker-service";
+import { listChatMessages } from "@/services/chat-message-service";
+import { listChats } from "@/services/chat-service";
+import { readImageAsDataUrl } from "@/services/file-system-service";
+import { prepareLorebookForEmbedding } from "@/services/imports/shared/lorebook-export";
+
+export interface CharacterBundleExportSummary {
+  chats: number;
+  messages: number;
+  // Other characters the chats mention, exported by name only
+  referencedCharacters: string[];
+}
+
+async function readImageOrNull(path: string | null | undefined): Promise<string | null> {
+  if (!path) {
+    return null;
+  }
+  try {
+    return await readImageAsDataUrl(path);
+  } catch (error) {
+    console.warn(`Failed to read ${path} for the character bundle:`, error);
+    return null;
+  }
+}
+
+function isParticipant(chat: Chat, characterId: string): boolean {
+  return chat.user_character_id === characterId || !!chat.participants?.some((participant) => participant.id === characterId);
+}
+
+/**
+ * Writes one character, its avatar/expression images and linked lorebook to a single JSON bundle,
+ * optionally with every chat it took part in. Other characters are referenced by name only.
+ * @param characterId - The character to export
+ * @param profileId - The profile that owns the character
+ * @param includeChats - Also export the character's chats with all their chapters and messages
+ * @param outputPath - Where to write the bundle
+ */
+export async function exportCharacterBundle(characterId: string, profileId: string, includeChats: boolean, outputPath: string): Promise<CharacterBundleExportSummary> {
+  const character = await getCharacterById(characterId);
+  if (!character || character.profile_id !== profileId) {
+    throw new Error("Character not found");
+  }
+
+  const namesById = new Map((await listCharacters(profileId)).map((entry) => [entry.id, entry.name]));
+  const referencedCharacters = new Set<string>();
+  const toRef = (id: string | null | undefined): BundleCharacterRef | null => {
+    if (!id) {
+      return null;
+    }
+    if (id === characterId) {
+      return "self";
+    }
+    const name = namesById.get(id);
+    if (!name) {
+      return null;
+    }
+    referencedCharacters.add(name);
+    return { name };
+  };
+
+  const chats: BundleChat[] = [];
+  let messageCount = 0;
+  if (includeChats) {
+    const characterChats = (await listChats({ profile_id: profileId })).filter((chat) => isParticipant(chat, characterId));
+    for (const chat of characterChats) {
+      const chapters = await getChaptersByChatId(chat.id);
+      const messages = await listChatMessages({ chat_id: chat.id });
+      const markers = await listChatMarkers(chat.id);
+      messageCount += messages.length;
+
+      chats.push({
+        name: chat.name,
+        participants: (chat.participants ?? []).flatMap((participant) => {
+          const ref = toRef(participant.id);
+          return ref ? [{ character: ref, enabled: participant.enabled, settings: participant.settings }] : [];
+        }),
+        user_character: toRef(chat.user_character_id),
+        settings: chat.settings,
+        synopsis: chat.synopsis ?? null,
+        chapters: chapters.map((chapter) => ({
+          title: chapter.title,
+          sequence: chapter.sequence,
+          scenario: chapter.scenario,
+          instructions: chapter.instructions,
+          start_message: chapter.start_message,
+          custom: chapter.custom,
+          active: chapter.id === chat.active_chapter_id,
+          messages: messages
+            .filter((message) => message.chapter_id === chapter.id)
+            .map((message) => ({
+              type: message.type,
+              character: toRef(message.character_id),
+              position: message.position,
+              messages: message.messages,
+              message_index: message.message_index,
+              disabled: !!message.disabled,
+              pinned: !!message.pinned,
+              tokens: message.tokens,
+              extra: message.extra,
+            })),
+          markers: markers
+            .filter((marker) => marker.chapter_id === chapter.id)
+            .map((marker) => ({ title: marker.title, position: marker.position, color: marker.color, context_reset: marker.context_reset })),
+        })),
+      });
+    }
+  }
+
+  const { id: _id, profile_id: _profileId, created_at: _createdAt, updated_at: _updatedAt, lorebook_id: _lorebookId, ...characterData } = character;
+  const bundle: CharacterBundle & { app_version: string } = {
+    export_type: "character_bundle",
+    bundle_version: CHARACTER_BUNDLE_VERSION,
+    app_version: await getVersion(),
+    character: { ...characterData, avatar_path: null, expressions: [] },
+    avatar: await readImageOrNull(character.avatar_path),
+    expressions: await Promise.all(
+      (character.expressions ?? []).map(async (expression) => ({
+        id: expression.id,
+        name: expression.name,
+        image: await readImageOrNull(expression.image_path),
+      })),
+    ),
+    lorebook: character.lorebook_id ? await prepareLorebookForEmbedding(character.lorebook_id) : null,
+    chats,
+  };
+
+  await writeFile(outputPath, new TextEncoder().encode(JSON.stringify(bundle, null, 2)));
+
+  return { chats: chats.length, messages: messageCount, referencedCharacters: [...referencedCharacters].sort() };
+}
@@ -4,6 +4,7 @@ import PNGtext from "png-chunk-text";
 import encodeChunks from "png-chunks-encode";
 import extractChunks from "png-chunks-extract";
 import { toast } from "sonner";
+import { sanitizeFileName } from "@/commands/assets";
 import { readImageAsDataUrl } from "@/services/file-system-service";
 
 /**
@@ -179,7 +180,7 @@ export async function exportCharacterToPng(characterData: any, avatarPath: strin
 
     // Open save dialog
     const filePath = await saveDialog({
-      defaultPath: `${defaultFileName}.png`,
+      defaultPath: await sanitizeFileName(defaultFileName, "png"),
       filters: [
         {
           name: "PNG Files",
@@ -1,14 +1,14 @@
 import { convertFileSrc } from "@tauri-apps/api/core";
-import { appDataDir, join, sep } from "@tauri-apps/api/path";
-import { BaseDirectory, exists, mkdir, readFile, remove, writeFile } from "@tauri-apps/plugin-fs";
+import { appDataDir, join } from "@tauri-apps/api/path";
+import { BaseDirectory, readFile, remove } from "@tauri-apps/plugin-fs";
+import { storeAsset } from "@/commands/assets";
+import { executeDBQuery } from "@/utils/database";
 
 /**
  * Constants for file system paths and directories
  */
-const PROFILES_DIR = "profiles";
-const IMAGES_DIR = "images";
 const AVATAR_PREFIX = "avatar_";
-const CHARACTERS_DIR = "characters";
+const ASSET_STORE_DIR = "objects";
 
 let cachedAppDataDir: string | null = null;
 
@@ -21,26 +21,28 @@ export async function getAppDataDir(): Promise<string> {
 }
 
 /**
- * Convert a data URL to a binary array
- * @param dataUrl - The data URL to convert
- * @returns Binary array of the image data
+ * Extract the base64 payload of a data URL
+ * @param dataUrl - The data URL
+ * @returns The base64 encoded content
  */
-function dataUrlToBinary(dataUrl: string): Uint8Array {
-  // Extract the base64 part of the data URL
+function dataUrlToBase64(dataUrl: string): string {
   const base64 = dataUrl.split(",")[1];
   if (!base64) {
     throw new Error("Invalid data URL format");
   }
+  return base64;
+}
 
-  // Convert base64 to binary
-  const binaryString = atob(base64);
-  const bytes = new Uint8Array(binaryString.length);
-
-  for (let i = 0; i < binaryString.length; i++) {
-    bytes[i] = binaryString.charCodeAt(i);
+/**
+ * Encode binary data as base64, in chunks so large images don't overflow the call stack
+ */
+function binaryToBase64(data: Uint8Array): string {
+  let binary = "";
+  const chunkSize = 0x8000;
+  for (let i = 0; i < data.length; i += chunkSize) {
+    binary += String.fromCharCode(...data.subarray(i, i + chunkSize));
   }
-
-  return bytes;
+  return btoa(binary);
 }
 
 /**
@@ -54,32 +56,44 @@ function getExtensionFromDataUrl(dataUrl: string): string {
 }
 
 /**
- * Ensure the required directories exist
+ * Whether a stored path points into the content-addressed asset store.
+ * Those files are shared between entities and only removed by asset garbage collection.
  */
-async function ensureDirectories(): Promise<void> {
-  const appData = await getAppDataDir();
-
-  // Ensure profiles directory exists
-  const profilesPath = await join(appData, PROFILES_DIR);
-  const profilesDirExists = await exists(profilesPath, { baseDir: BaseDirectory.AppData });
-
-  if (!profilesDirExists) {
-    await mkdir(profilesPath, { baseDir: BaseDirectory.AppData, recursive: true });
-  }
+export function isAssetStorePath(relativePath: string): boolean {
+  return relativePath.startsWith(`${ASSET_STORE_DIR}/`);
+}
 
-  // Ensure images directory exists
-  const imagesPath = await join(appData, IMAGES_DIR);
-  const imagesDirExists = await exists(imagesPath, { baseDir: BaseDirectory.AppData });
+/**
+ * Write a file into the content-addressed asset store and register it so row triggers can count references
+ * @param base64 - File contents as base64
+ * @param extension - File extension without the dot
+ * @param originalName - Name the file was saved under, kept for reference
+ * @returns The relative path to store on the entity
+ */
+export async function saveToAssetStore(base64: string, extension: string, originalName: string): Promise<string> {
+  const asset = await storeAsset(base64, extension);
+  await executeDBQuery("INSERT INTO assets (hash, original_name, size) VALUES ($1, $2, $3) ON CONFLICT(hash) DO NOTHING", [asset.hash, originalName, asset.size]);
+  return asset.path;
+}
 
-  if (!imagesDirExists) {
-    await mkdir(imagesPath, { baseDir: BaseDirectory.AppData, recursive: true });
-  }
+/**
+ * Copy an existing file from the app data directory into the asset store.
+ * The original file is left in place; remove it once the owning row points at the new path.
+ * @param relativePath - The relative path to the file (from app data root)
+ * @returns The asset store path
+ */
+export async function copyFileToAssetStore(relativePath: string): Promise<string> {
+  const appData = await getAppDataDir();
+  const data = await readFile(await join(appData, relativePath));
+  const fileName = relativePath.split(/[\\/]/).pop() || relativePath;
+  const extension = fileName.includes(".") ? fileName.split(".").pop() || "bin" : "bin";
+  return saveToAssetStore(binaryToBase64(new Uint8Array(data)), extension, relativePath);
 }
 
 /**
- * Save an avatar image from a data URL to the app data directory
+ * Save an avatar image from a data URL to the asset store
  * @param dataUrl - The image data URL or path URL
- * @param nameID - The ID to use in the filename
+ * @param nameID - The ID to use in the original filename
  * @returns The path to the saved image
  */
 export async function saveAvatarImage(dataUrl: string, nameID: string): Promise<string> {
@@ -88,20 +102,8 @@ export async function saveAvatarImage(dataUrl: string, nameID: string): Promise<
     return dataUrl;
   }
 
-  await ensureDirectories();
-
-  const imageId = `${AVATAR_PREFIX}${nameID}`;
   const extension = getExtensionFromDataUrl(dataUrl);
-  const fileName = `${imageId}.${extension}`;
-
-  const appData = await getAppDataDir();
-  const filePath = await join(appData, PROFILES_DIR, fileName);
-
-  const binaryData = dataUrlToBinary(dataUrl);
-  await writeFile(filePath, binaryData, { baseDir: BaseDirectory.AppData });
-
-  const separator = await sep();
-  return `${PROFILES_DIR}${separator}${fileName}`;
+  return saveToAssetStore(dataUrlToBase64(dataUrl), extension, `${AVATAR_PREFIX}${nameID}.${extension}`);
 }
 
 /**
@@ -145,9 +147,10 @@ export async function readImageAsDataUrl(relativePath: string): Promise<string>
 }
 
 /**
- * Save a general image to the app data directory
+ * Save a general image to the asset store
  * @param dataUrl - The image data URL
- * @param subDirectory - Optional subdirectory within images/ to save to
+ * @param nameID - The name to record as the original filename
+ * @param subDirectory - Optional category, recorded in the original filename
  * @returns The path to the saved image
  */
 export async function saveImage(dataUrl: string, nameID: string, subDirectory?: string): Promise<string> {
@@ -155,62 +158,17 @@ export async function saveImage(dataUrl: string, nameID: string, subDirectory?:
   if (!dataUrl.startsWith("data:")) {
     return dataUrl;
   }
-  await ensureDirectories();
 
-  const imageId = `${nameID}`;
   const extension = getExtensionFromDataUrl(dataUrl);
-  const fileName = `${imageId}.${extension}`;
-
-  const appData = await getAppDataDir();
-  let directory = IMAGES_DIR;
-
-  // If subdirectory is specified, make sure it exists
-  if (subDirectory) {
-    directory = await join(IMAGES_DIR, subDirectory);
-    const subDirPath = await join(appData, directory);
-    const subDirExists = await exists(subDirPath, { baseDir: BaseDirectory.AppData });
-
-    if (!subDirExists) {
-      await mkdir(subDirPath, { baseDir: BaseDirectory.AppData, recursive: true });
-    }
-  }
-
-  const filePath = await join(appData, directory, fileName);
-
-  // Convert data URL to binary data
-  const binaryData = dataUrlToBinary(dataUrl);
-
-  // Write the file
-  await writeFile(filePath, binaryData, { baseDir: BaseDirectory.AppData, create: true });
-
-  // Return the relative path for storing in the database
-  const separator = await sep();
-  return `${directory}${separator}${fileName}`;
+  const originalName = subDirectory ? `${subDirectory}/${nameID}.${extension}` : `${nameID}.${extension}`;
+  return saveToAssetStore(dataUrlToBase64(dataUrl), extension, originalName);
 }
 
 /**
- * Save binary image data directly to disk as an expression image,
- * avoiding the data URL round-trip (binary → base64 → binary).
+ * Save binary image data as an expression image in the asset store
  */
 export async function saveExpressionImageFromBinary(data: Uint8Array, extension: string, nameID: string, characterId: string): Promise<string> {
-  await ensureDirectories();
-
-  const fileName = `${nameID}.${extension}`;
-  const appData = await getAppDataDir();
-
-  const characterImageDir = await join(IMAGES_DIR, CHARACTERS_DIR, characterId);
-  const fullDirPath = await join(appData, characterImageDir);
-
-  const dirExists = await exists(fullDirPath, { baseDir: BaseDirectory.AppData });
-  if (!dirExists) {
-    await mkdir(fullDirPath, { baseDir: BaseDirectory.AppData, recursive: true });
-  }
-
-  const filePath = await join(fullDirPath, fileName);
-  await writeFile(filePath, data, { baseDir: BaseDirectory.AppData, create: true });
-
-  const separator = await sep();
-  return `${characterImageDir}${separator}${fileName}`;
+  return saveToAssetStore(binaryToBase64(data), extension, `characters/${characterId}/${nameID}.${extension}`);
 }
 
 /**
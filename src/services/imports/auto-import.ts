@@ -0,0 +1,107 @@
+import { emit } from "@tauri-apps/api/event";
+import { basename } from "@tauri-apps/api/path";
+import { readFile } from "@tauri-apps/plugin-fs";
+import { listCharacters } from "../character-service";
+import { saveImage } from "../file-system-service";
+import { extractCharacterSpecV2FromPng } from "./formats/character_spec_png";
+import { importCharacter, parseCharacterContent, validateAndTransformCharacterData } from "./import-character";
+import { importCharacterBundle } from "./import-character-bundle";
+
+export const AUTO_IMPORT_COMPLETED_EVENT = "auto-import-completed";
+
+export interface AutoImportResult {
+  path: string;
+  fileName: string;
+  status: "imported" | "failed";
+  characterId?: string;
+  name?: string;
+  // Set when the name was taken and the character was imported under `name` instead
+  renamedFrom?: string;
+  error?: string;
+}
+
+export interface AutoImportCompletedPayload {
+  profileId: string;
+  results: AutoImportResult[];
+}
+
+/**
+ * `name`, or the first of "name (2)", "name (3)"... not in `taken`
+ */
+export function resolveNameConflict(name: string, taken: Set<string>): string {
+  if (!taken.has(name)) {
+    return name;
+  }
+  let suffix = 2;
+  while (taken.has(`${name} (${suffix})`)) {
+    suffix++;
+  }
+  return `${name} (${suffix})`;
+}
+
+function bytesToDataUrl(bytes: Uint8Array, mimeType: string): Promise<string> {
+  return new Promise((resolve, reject) => {
+    const reader = new FileReader();
+    reader.onload = () => resolve(reader.result as string);
+    reader.onerror = reject;
+    reader.readAsDataURL(new Blob([bytes as any], { type: mimeType }));
+  });
+}
+
+async function importCardFile(profileId: string, path: string, fileName: string, takenNames: Set<string>): Promise<AutoImportResult> {
+  const bytes = await readFile(path);
+  const isPng = fileName.toLowerCase().endsWith(".png");
+
+  let parsedData: any;
+  if (isPng) {
+    parsedData = extractCharacterSpecV2FromPng(bytes);
+  } else {
+    parsedData = parseCharacterContent(new TextDecoder("utf-8").decode(bytes));
+    // Bundles bring their own images and chats and are imported as a whole, like in CharacterImport
+    if (parsedData?.export_type === "character_bundle") {
+      const report = await importCharacterBundle(profileId, path);
+      takenNames.add(report.character.name);
+      return { path, fileName, status: "imported", characterId: report.character.id, name: report.character.name };
+    }
+  }
+
+  const validation = validateAndTransformCharacterData(parsedData, profileId);
+  if (!validation.valid || !validation.data) {
+    throw new Error(`Invalid character file (${validation.format}): ${validation.errors.join("; ")}`);
+  }
+
+  const originalName = validation.data.name;
+  const name = resolveNameConflict(originalName, takenNames);
+  const data = { ...validation.data, name };
+  if (isPng) {
+    data.avatar_path = await saveImage(await bytesToDataUrl(bytes, "image/png"), name, "characters");
+  }
+
+  // No greeting chat: nobody is there to confirm it, unlike a manual import
+  const character = await importCharacter(data, undefined, validation.lorebookData);
+  takenNames.add(character.name);
+  return { path, fileName, status: "imported", characterId: character.id, name: character.name, ...(name !== originalName && { renamedFrom: originalName }) };
+}
+
+/**
+ * Import the character cards reported by the folder watch into a profile. A name the profile
+ * already has gets a " (2)" style suffix instead of replacing or updating anything. One failing
+ * file doesn't stop the others; the results go out as `auto-import-completed`.
+ */
+export async function importWatchedFiles(profileId: string, paths: string[]): Promise<AutoImportResult[]> {
+  const takenNames = new Set((await listCharacters(profileId)).map((character) => character.name));
+
+  const results: AutoImportResult[] = [];
+  for (const path of paths) {
+    const fileName = await basename(path).catch(() => path);
+    try {
+      results.push(await importCardFile(profileId, path, fileName, takenNames));
+    } catch (error) {
+      results.push({ path, fileName, status: "failed", error: error instanceof Error ? error.message : String(error) });
+    }
+  }
+
+  const payload: AutoImportCompletedPayload = { profileId, results };
+  await emit(AUTO_IMPORT_COMPLETED_EVENT, payload).catch((error) => console.error("Failed to emit auto import event:", error));
+  return results;
+}
@@ -96,6 +96,9 @@ export function transformCharacterSpecV2(data: CharaCardV2, profileId: string):
     auto_update: true,
     system_override: d.system_prompt || null,
     settings: d.creator ? { author: d.creator } : { author: "Unknown" },
+    creator: d.creator || null,
+    character_version: d.character_version || null,
+    creator_notes: d.creator_notes || null,
     custom: { personality: personalityJoined },
     expressions: null,
     character_manifest_id: null,
@@ -209,6 +209,7 @@ export function transformSillyTavernFormatTemplate(data: SillyTavernFormatTempla
     apply_censorship: false, // Default value, not in SillyTavern
     merge_messages_on_user: false, // Default value, not in SillyTavern
     merge_subsequent_messages: true, // Default value, not in SillyTavern
+    substitute_message_macros: true, // SillyTavern always replaces macros in messages
   };
 
   // Map reasoning configuration
@@ -0,0 +1,221 @@
+import { readTextFile } from "@tauri-apps/plugin-fs";
+import type { Character, Expression } from "@/schema/characters-schema";
+import { type BundleCharacterRef, type CharacterBundle, characterBundleSchema } from "@/schema/character-bundle-schema";
+import { createCharacter, listCharacters } from "../character-service";
+import { createChatChapter } from "../chat-chapter-service";
+import { createChatMarker } from "../chat-marker-service";
+import { createChatMessage, getLatestMessageSequence, setMessagePinned } from "../chat-message-service";
+import { createChat, setChatSynopsis, updateChat } from "../chat-service";
+import { saveAvatarImage, saveImage } from "../file-system-service";
+import { validateAndTransformCharacterData } from "./import-character";
+import { importLorebook, validateAndTransformLorebookData } from "./import-lorebook";
+
+export interface CharacterBundleImportReport {
+  character: Character;
+  lorebookId: string | null;
+  expressions: number;
+  chats: number;
+  messages: number;
+  // Referenced characters matched by name to ones the profile already has
+  linkedCharacters: string[];
+  // Referenced characters the profile didn't have, created as empty placeholders
+  placeholderCharacters: string[];
+}
+
+/**
+ * Parse and validate a character bundle written by exportCharacterBundle
+ * @throws If the content is not a valid bundle
+ */
+export function parseCharacterBundle(fileContent: string): CharacterBundle {
+  let data: unknown;
+  try {
+    data = JSON.parse(fileContent);
+  } catch (error) {
+    throw new Error(`Failed to parse file content as JSON: ${error instanceof Error ? error.message : String(error)}`);
+  }
+
+  const result = characterBundleSchema.safeParse(data);
+  if (!result.success) {
+    throw new Error(`Invalid character bundle: ${result.error.issues.map((issue) => `${issue.path.join(".")}: ${issue.message}`).join("; ")}`);
+  }
+  return result.data;
+}
+
+async function saveImageOrNull(dataUrl: string | null, save: (dataUrl: string) => Promise<string>): Promise<string | null> {
+  if (!dataUrl) {
+    return null;
+  }
+  try {
+    return await save(dataUrl);
+  } catch (error) {
+    console.warn("Failed to save a bundled image:", error);
+    return null;
+  }
+}
+
+/**
+ * Recreates a bundled character in the profile with fresh ids: its images, lorebook and chats.
+ * Other characters referenced by the chats are matched by name, or created as placeholders.
+ * @param profileId - The profile to import into
+ * @param filePath - The bundle file written by exportCharacterBundle
+ */
+export async function importCharacterBundle(profileId: string, filePath: string): Promise<CharacterBundleImportReport> {
+  const bundle = parseCharacterBundle(await readTextFile(filePath));
+
+  const characterResult = validateAndTransformCharacterData({ ...bundle.character, lorebook: undefined }, profileId);
+  if (!characterResult.valid || !characterResult.data || characterResult.format !== "internal_json") {
+    throw new Error(`Invalid character in bundle: ${characterResult.errors.join("; ")}`);
+  }
+  const characterName = characterResult.data.name;
+
+  let lorebookId: string | null = null;
+  if (bundle.lorebook) {
+    const lorebookResult = validateAndTransformLorebookData(bundle.lorebook, profileId, `${characterName}_lorebook`);
+    if (lorebookResult.valid && lorebookResult.data) {
+      lorebookId = (await importLorebook(lorebookResult.data)).id;
+    } else {
+      console.warn("Skipping invalid bundled lorebook:", lorebookResult.errors);
+    }
+  }
+
+  const expressions: Expression[] = [];
+  for (const expression of bundle.expressions) {
+    expressions.push({
+      id: expression.id,
+      name: expression.name,
+      image_path: await saveImageOrNull(expression.image, (dataUrl) => saveImage(dataUrl, expression.name, `characters/${characterName}`)),
+    });
+  }
+
+  const character = await createCharacter({
+    ...characterResult.data,
+    avatar_path: await saveImageOrNull(bundle.avatar, (dataUrl) => saveAvatarImage(dataUrl, characterName)),
+    expressions,
+    lorebook_id: lorebookId,
+  });
+
+  const report: CharacterBundleImportReport = {
+    character,
+    lorebookId,
+    expressions: expressions.length,
+    chats: 0,
+    messages: 0,
+    linkedCharacters: [],
+    placeholderCharacters: [],
+  };
+
+  if (bundle.chats.length === 0) {
+    return report;
+  }
+
+  const existingByName = new Map<string, string>();
+  for (const existing of await listCharacters(profileId)) {
+    if (existing.id !== character.id && !existingByName.has(existing.name)) {
+      existingByName.set(existing.name, existing.id);
+    }
+  }
+  const resolvedByName = new Map<string, string>();
+
+  const resolveRef = async (ref: BundleCharacterRef | null): Promise<string | null> => {
+    if (!ref) {
+      return null;
+    }
+    if (ref === "self") {
+      return character.id;
+    }
+    const resolved = resolvedByName.get(ref.name);
+    if (resolved) {
+      return resolved;
+    }
+
+    const existingId = existingByName.get(ref.name);
+    if (existingId) {
+      report.linkedCharacters.push(ref.name);
+      resolvedByName.set(ref.name, existingId);
+      return existingId;
+    }
+
+    const placeholder = await createCharacter({
+      profile_id: profileId,
+      name: ref.name,
+      type: "character",
+      tags: ["placeholder"],
+      avatar_path: null,
+      lorebook_id: null,
+      expressions: null,
+      character_manifest_id: null,
+    });
+    report.placeholderCharacters.push(ref.name);
+    resolvedByName.set(ref.name, placeholder.id);
+    return placeholder.id;
+  };
+
+  for (const bundledChat of bundle.chats) {
+    const participants = [];
+    for (const participant of bundledChat.participants) {
+      const id = await resolveRef(participant.character);
+      if (id) {
+        participants.push({ id, enabled: participant.enabled, settings: participant.settings });
+      }
+    }
+
+    const chat = await createChat({
+      name: bundledChat.name,
+      profile_id: profileId,
+      participants,
+      user_character_id: await resolveRef(bundledChat.user_character),
+      settings: bundledChat.settings,
+    });
+
+    let activeChapterId: string | null = null;
+    for (const bundledChapter of bundledChat.chapters) {
+      const chapter = await createChatChapter({
+        chat_id: chat.id,
+        title: bundledChapter.title,
+        sequence: bundledChapter.sequence,
+        scenario: bundledChapter.scenario,
+        instructions: bundledChapter.instructions,
+        start_message: bundledChapter.start_message,
+        custom: bundledChapter.custom as any,
+      });
+      // The first chapter unless another one was active
+      if (bundledChapter.active || !activeChapterId) {
+        activeChapterId = chapter.id;
+      }
+
+      for (const bundledMessage of bundledChapter.messages) {
+        const message = await createChatMessage({
+          chat_id: chat.id,
+          chapter_id: chapter.id,
+          character_id: await resolveRef(bundledMessage.character),
+          type: bundledMessage.type,
+          position: bundledMessage.position,
+          messages: bundledMessage.messages,
+          message_index: bundledMessage.message_index,
+          disabled: bundledMessage.disabled,
+          tokens: bundledMessage.tokens,
+          extra: bundledMessage.extra as any,
+        });
+        if (bundledMessage.pinned) {
+          await setMessagePinned(message.id, chat.id, true);
+        }
+        report.messages++;
+      }
+
+      for (const bundledMarker of bundledChapter.markers) {
+        await createChatMarker({ ...bundledMarker, color: bundledMarker.color ?? null, chat_id: chat.id, chapter_id: chapter.id });
+      }
+    }
+
+    if (activeChapterId) {
+      await updateChat(chat.id, { active_chapter_id: activeChapterId });
+    }
+    // The imported messages count as folded in, the next refresh starts from new ones
+    if (bundledChat.synopsis) {
+      await setChatSynopsis(chat.id, profileId, bundledChat.synopsis, await getLatestMessageSequence(chat.id));
+    }
+    report.chats++;
+  }
+
+  return report;
+}
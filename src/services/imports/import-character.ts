@@ -1,6 +1,6 @@
 import { z } from "zod";
 import { Character, CreateCharacterSchema } from "@/schema/characters-schema";
-import { createCharacter } from "../character-service";
+import { createCharacter, updateCharacter } from "../character-service";
 import { createChatChapter } from "../chat-chapter-service";
 import { createChat, updateChat } from "../chat-service";
 import { CharaCardV2, CharacterSpecV2TransformResult, transformCharacterSpecV2, validateCharacterSpecV2 } from "./formats/character_spec_v2";
@@ -175,6 +175,69 @@ export async function importCharacter(transformedData: z.infer<typeof CreateChar
   return character;
 }
 
+/**
+ * Compares two card versions ("1.2", "v2.0.1") segment by segment, numbers numerically.
+ * A missing version is older than any other.
+ * @returns A negative number when `a` is older, positive when newer, 0 when equal
+ */
+export function compareCharacterVersions(a: string | null | undefined, b: string | null | undefined): number {
+  const segments = (version: string) => version.trim().replace(/^v/i, "").split(/[^a-z0-9]+/i).filter(Boolean);
+  if (!a || !b) {
+    return (a ? 1 : 0) - (b ? 1 : 0);
+  }
+
+  const left = segments(a);
+  const right = segments(b);
+  for (let i = 0; i < Math.max(left.length, right.length); i++) {
+    // "1.2" equals "1.2.0"
+    const l = left[i] ?? "0";
+    const r = right[i] ?? "0";
+    const difference = /^\d+$/.test(l) && /^\d+$/.test(r) ? Number(l) - Number(r) : l.localeCompare(r);
+    if (difference !== 0) {
+      return Math.sign(difference);
+    }
+  }
+  return 0;
+}
+
+/**
+ * Finds the profile character an import is a newer version of: same name and creator (when both have one),
+ * with an older `character_version`. Imports without a version never match.
+ */
+export function findOutdatedCharacter(incoming: z.infer<typeof CreateCharacterSchema>, existing: Character[]): Character | null {
+  if (!incoming.character_version) {
+    return null;
+  }
+  const name = incoming.name.trim().toLowerCase();
+  const candidates = existing.filter(
+    (character) =>
+      character.type === "character" &&
+      character.name.trim().toLowerCase() === name &&
+      (!character.creator || !incoming.creator || character.creator === incoming.creator) &&
+      compareCharacterVersions(character.character_version, incoming.character_version) < 0,
+  );
+  // The closest match is the newest of the older versions
+  return candidates.sort((a, b) => compareCharacterVersions(b.character_version, a.character_version))[0] ?? null;
+}
+
+/**
+ * Replaces an existing character's card content with a newer imported version. The character keeps its id,
+ * chats, lorebook link and favorite state; the avatar is only replaced when the import brought one.
+ */
+export async function updateCharacterFromImport(existingId: string, incoming: z.infer<typeof CreateCharacterSchema>): Promise<Character | null> {
+  return updateCharacter(existingId, {
+    name: incoming.name,
+    tags: incoming.tags,
+    system_override: incoming.system_override ?? null,
+    settings: incoming.settings,
+    custom: incoming.custom,
+    creator: incoming.creator ?? null,
+    character_version: incoming.character_version ?? null,
+    creator_notes: incoming.creator_notes ?? null,
+    ...(incoming.avatar_path ? { avatar_path: incoming.avatar_path } : {}),
+  });
+}
+
 /**
  * Parse character file content (JSON only for now).
  * @param fileContent - The string content of the file.
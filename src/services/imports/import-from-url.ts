@@ -0,0 +1,136 @@
+import { type UrlImportError, type UrlImportResult, importFromUrl as importUrlResource } from "@/commands/imports";
+import { saveImage } from "@/services/file-system-service";
+import { extractCharacterSpecV2FromPng } from "./formats/character_spec_png";
+import { importCharacter, parseCharacterContent, validateAndTransformCharacterData } from "./import-character";
+import { importLorebook, parseLorebookContent, validateAndTransformLorebookData } from "./import-lorebook";
+
+export type UrlImportErrorKind = UrlImportError["kind"] | "invalid";
+
+/**
+ * Error thrown by `importFromUrl`. `kind` lets the UI tell network failures apart from bad files.
+ */
+export class UrlImportFailure extends Error {
+  kind: UrlImportErrorKind;
+
+  constructor(kind: UrlImportErrorKind, message: string) {
+    super(message);
+    this.name = "UrlImportFailure";
+    this.kind = kind;
+  }
+}
+
+export interface UrlImportSummary {
+  kind: "character" | "lorebook" | "chat";
+  // The first chat when an export held several conversations
+  id: string;
+  name: string;
+  sourceUrl: string;
+  // How many entities were created: more than one only for chat exports
+  count: number;
+}
+
+function isUrlImportError(error: unknown): error is UrlImportError {
+  return typeof error === "object" && error !== null && "kind" in error && "message" in error;
+}
+
+function base64ToUint8Array(base64: string): Uint8Array {
+  const binary = atob(base64);
+  const bytes = new Uint8Array(binary.length);
+  for (let i = 0; i < binary.length; i++) {
+    bytes[i] = binary.charCodeAt(i);
+  }
+  return bytes;
+}
+
+// Cards and lorebooks come back as data for the importers below
+function resourceData(resource: UrlImportResult): string {
+  if (resource.data === null) {
+    throw new UrlImportFailure("parse", `Nothing to import from ${resource.final_url}`);
+  }
+  return resource.data;
+}
+
+function fileNameFromUrl(url: string): string {
+  try {
+    const segment = new URL(url).pathname.split("/").filter(Boolean).pop();
+    return segment ? decodeURIComponent(segment).replace(/\.[^.]+$/, "") : "Imported Lorebook";
+  } catch {
+    return "Imported Lorebook";
+  }
+}
+
+/**
+ * Downloads a resource from a URL and imports it through the matching import routine: chat exports are
+ * imported by the backend, character cards and lorebooks by the same chain as a file picked from disk.
+ * HTTPS is enforced unless the profile allows plain HTTP (`system.allowInsecureUrlImports`).
+ * @param profileId - The profile that will own the imported entity.
+ * @param url - The URL of the character card, lorebook or chat export.
+ * @param kindHint - Optional hint used when the downloaded content is ambiguous.
+ * @returns A summary of the created entity.
+ * @throws {UrlImportFailure} With a `kind` describing where the import failed.
+ */
+export async function importFromUrl(profileId: string, url: string, kindHint?: "character" | "lorebook"): Promise<UrlImportSummary> {
+  let resource: UrlImportResult;
+  try {
+    resource = await importUrlResource(profileId, url, kindHint);
+  } catch (error) {
+    if (isUrlImportError(error)) {
+      throw new UrlImportFailure(error.kind, error.message);
+    }
+    throw new UrlImportFailure("network", error instanceof Error ? error.message : String(error));
+  }
+
+  switch (resource.kind) {
+    case "openai_chat":
+    case "anthropic_chat":
+    case "sillytavern_chat": {
+      const [chat] = resource.chats;
+      if (!chat) {
+        throw new UrlImportFailure("import", "The chat export held no conversation");
+      }
+      return { kind: "chat", id: chat.id, name: chat.name, sourceUrl: resource.final_url, count: resource.chats.length };
+    }
+
+    case "character_png":
+    case "character_json": {
+      const data = resourceData(resource);
+      let parsedData: any;
+      try {
+        parsedData =
+          resource.kind === "character_png" ? extractCharacterSpecV2FromPng(base64ToUint8Array(data)) : parseCharacterContent(data);
+      } catch (error) {
+        throw new UrlImportFailure("parse", error instanceof Error ? error.message : String(error));
+      }
+
+      const validationResult = validateAndTransformCharacterData(parsedData, profileId);
+      if (!validationResult.valid || !validationResult.data) {
+        throw new UrlImportFailure("invalid", validationResult.errors.join("\n"));
+      }
+      // The card image becomes the avatar, as with a PNG imported from disk
+      if (resource.kind === "character_png") {
+        validationResult.data.avatar_path = await saveImage(`data:image/png;base64,${data}`, validationResult.data.name, "characters");
+      }
+
+      const character = await importCharacter(validationResult.data, validationResult.chatFields, validationResult.lorebookData);
+      return { kind: "character", id: character.id, name: character.name, sourceUrl: resource.final_url, count: 1 };
+    }
+
+    case "lorebook_json": {
+      const data = resourceData(resource);
+      let parsedData: any;
+      try {
+        parsedData = parseLorebookContent(data);
+      } catch (error) {
+        throw new UrlImportFailure("parse", error instanceof Error ? error.message : String(error));
+      }
+
+      const validationResult = validateAndTransformLorebookData(parsedData, profileId, fileNameFromUrl(resource.final_url));
+      if (!validationResult.valid || !validationResult.data) {
+        throw new UrlImportFailure("invalid", validationResult.errors.join("\n"));
+      }
+
+      const lorebook = await importLorebook(validationResult.data);
+      return { kind: "lorebook", id: lorebook.id, name: lorebook.name, sourceUrl: resource.final_url, count: 1 };
+    }
+  }
+}
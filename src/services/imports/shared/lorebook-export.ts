@@ -45,7 +45,7 @@ export async function exportLorebook(lorebookId: string, fileName?: string): Pro
     };
 
     // Generate filename if not provided
-    const defaultFileName = fileName || `lorebook_${lorebook.name.replace(/[^a-zA-Z0-9]/g, "_")}`;
+    const defaultFileName = fileName || `lorebook_${lorebook.name}`;
 
     // Export the lorebook
     return await exportSingleToJsonFile(finalExport, exportType, defaultFileName);
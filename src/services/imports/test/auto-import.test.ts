@@ -0,0 +1,18 @@
+import { describe, expect, it, vi } from "vitest";
+import { resolveNameConflict } from "../auto-import";
+
+vi.mock("../../character-service", () => ({ listCharacters: vi.fn() }));
+vi.mock("../../file-system-service", () => ({ saveImage: vi.fn() }));
+vi.mock("../import-character", () => ({ importCharacter: vi.fn(), parseCharacterContent: vi.fn(), validateAndTransformCharacterData: vi.fn() }));
+vi.mock("../import-character-bundle", () => ({ importCharacterBundle: vi.fn() }));
+
+describe("resolveNameConflict", () => {
+  it("keeps a free name", () => {
+    expect(resolveNameConflict("Alice", new Set(["Bob"]))).toBe("Alice");
+  });
+
+  it("adds the first free numeric suffix", () => {
+    expect(resolveNameConflict("Alice", new Set(["Alice"]))).toBe("Alice (2)");
+    expect(resolveNameConflict("Alice", new Set(["Alice", "Alice (2)", "Alice (3)"]))).toBe("Alice (4)");
+  });
+});
@@ -0,0 +1,87 @@
+import { describe, expect, it, vi } from "vitest";
+import type { Character } from "@/schema/characters-schema";
+import { compareCharacterVersions, findOutdatedCharacter, validateAndTransformCharacterData } from "../import-character";
+
+vi.mock("../../character-service", () => ({ createCharacter: vi.fn(), updateCharacter: vi.fn() }));
+vi.mock("../../chat-chapter-service", () => ({ createChatChapter: vi.fn() }));
+vi.mock("../../chat-service", () => ({ createChat: vi.fn(), updateChat: vi.fn() }));
+vi.mock("../import-lorebook", () => ({ importLorebook: vi.fn(), validateAndTransformLorebookData: vi.fn() }));
+
+const PROFILE = "00000000-0000-4000-8000-000000000001";
+
+const existing = (id: string, name: string, character_version: string | null, creator: string | null = "Ann"): Character => ({
+  id,
+  profile_id: PROFILE,
+  type: "character",
+  name,
+  tags: [],
+  avatar_path: null,
+  lorebook_id: null,
+  version: "1.0.0",
+  external_update_link: null,
+  auto_update: true,
+  favorite: false,
+  system_override: null,
+  settings: {},
+  creator,
+  character_version,
+  creator_notes: null,
+  expressions: null,
+  character_manifest_id: null,
+  custom: {},
+  created_at: new Date(),
+  updated_at: new Date(),
+});
+
+const card = (data: Record<string, unknown>) => ({ spec: "chara_card_v2", data: { name: "Alice", ...data } });
+
+describe("compareCharacterVersions", () => {
+  it("compares numeric segments as numbers", () => {
+    expect(compareCharacterVersions("1.10", "1.9")).toBe(1);
+    expect(compareCharacterVersions("v2.0.1", "2.0.1")).toBe(0);
+    expect(compareCharacterVersions("1.2", "1.2.0")).toBe(0);
+    expect(compareCharacterVersions("1.2", "1.2.1")).toBe(-1);
+  });
+
+  it("ranks a missing version below any version", () => {
+    expect(compareCharacterVersions(null, "0.1")).toBe(-1);
+    expect(compareCharacterVersions("0.1", undefined)).toBe(1);
+    expect(compareCharacterVersions(null, null)).toBe(0);
+  });
+});
+
+describe("validateAndTransformCharacterData", () => {
+  it("keeps the card's creator metadata and tags", () => {
+    const result = validateAndTransformCharacterData(card({ creator: "Ann", character_version: "2.1", creator_notes: "Use with care", tags: ["fantasy"] }), PROFILE);
+    expect(result.format).toBe("chara_card_v2");
+    expect(result.data).toMatchObject({ creator: "Ann", character_version: "2.1", creator_notes: "Use with care", tags: ["fantasy"] });
+  });
+
+  it("round-trips the metadata through the internal format", () => {
+    const imported = validateAndTransformCharacterData(card({ creator: "Ann", character_version: "2.1", creator_notes: "Notes" }), PROFILE).data;
+    const reimported = validateAndTransformCharacterData({ export_type: "character", ...imported, profile_id: undefined }, PROFILE);
+    expect(reimported.format).toBe("internal_json");
+    expect(reimported.data).toMatchObject({ creator: "Ann", character_version: "2.1", creator_notes: "Notes" });
+  });
+});
+
+describe("findOutdatedCharacter", () => {
+  const incoming = (character_version: string | null, creator: string | null = "Ann") =>
+    validateAndTransformCharacterData(card({ creator: creator ?? undefined, character_version: character_version ?? undefined }), PROFILE).data!;
+
+  it("finds an older version of the same character", () => {
+    const profile = [existing("a", "alice", "1.0"), existing("b", "Bob", "0.1")];
+    expect(findOutdatedCharacter(incoming("1.1"), profile)?.id).toBe("a");
+  });
+
+  it("prefers the newest of several older versions", () => {
+    const profile = [existing("old", "Alice", "1.0"), existing("newer", "Alice", "1.5")];
+    expect(findOutdatedCharacter(incoming("2.0"), profile)?.id).toBe("newer");
+  });
+
+  it("ignores same or newer versions, other creators and unversioned imports", () => {
+    expect(findOutdatedCharacter(incoming("1.0"), [existing("a", "Alice", "1.0")])).toBeNull();
+    expect(findOutdatedCharacter(incoming("1.1"), [existing("a", "Alice", "1.0", "Someone else")])).toBeNull();
+    expect(findOutdatedCharacter(incoming(null), [existing("a", "Alice", null)])).toBeNull();
+  });
+});
@@ -0,0 +1,60 @@
+import { beforeEach, describe, expect, it, vi } from "vitest";
+import { importFromUrl as importUrlResource, type UrlImportResult } from "@/commands/imports";
+import type { Chat } from "@/schema/chat-schema";
+import { importFromUrl, UrlImportFailure } from "../import-from-url";
+import { importLorebook, parseLorebookContent, validateAndTransformLorebookData } from "../import-lorebook";
+
+vi.mock("@/commands/imports", () => ({ importFromUrl: vi.fn() }));
+vi.mock("@/services/file-system-service", () => ({ saveImage: vi.fn() }));
+vi.mock("../import-character", () => ({ importCharacter: vi.fn(), parseCharacterContent: vi.fn(), validateAndTransformCharacterData: vi.fn() }));
+vi.mock("../import-lorebook", () => ({ importLorebook: vi.fn(), parseLorebookContent: vi.fn(), validateAndTransformLorebookData: vi.fn() }));
+
+const PROFILE = "00000000-0000-4000-8000-000000000001";
+
+const chat = (id: string, name: string) => ({ id, name }) as Chat;
+
+const result = (overrides: Partial<UrlImportResult>): UrlImportResult => ({
+  kind: "lorebook_json",
+  final_url: "https://example.com/files/World.json",
+  content_type: "application/json",
+  size: 2,
+  data: null,
+  chats: [],
+  ...overrides,
+});
+
+describe("importFromUrl", () => {
+  beforeEach(() => {
+    vi.mocked(importUrlResource).mockReset();
+  });
+
+  it("summarizes the chats a chat export created", async () => {
+    vi.mocked(importUrlResource).mockResolvedValue(result({ kind: "openai_chat", chats: [chat("c1", "Trip ideas"), chat("c2", "Recipes")] }));
+
+    const summary = await importFromUrl(PROFILE, "https://example.com/conversations.json");
+
+    expect(importUrlResource).toHaveBeenCalledWith(PROFILE, "https://example.com/conversations.json", undefined);
+    expect(summary).toEqual({ kind: "chat", id: "c1", name: "Trip ideas", sourceUrl: "https://example.com/files/World.json", count: 2 });
+  });
+
+  it("runs lorebooks through the frontend importer", async () => {
+    vi.mocked(importUrlResource).mockResolvedValue(result({ data: "{}" }));
+    vi.mocked(parseLorebookContent).mockReturnValue({});
+    vi.mocked(validateAndTransformLorebookData).mockReturnValue({ valid: true, errors: [], data: { name: "World" } } as never);
+    vi.mocked(importLorebook).mockResolvedValue({ id: "l1", name: "World" } as never);
+
+    const summary = await importFromUrl(PROFILE, "https://example.com/files/World.json", "lorebook");
+
+    expect(validateAndTransformLorebookData).toHaveBeenCalledWith({}, PROFILE, "World");
+    expect(summary).toMatchObject({ kind: "lorebook", id: "l1", count: 1 });
+  });
+
+  it("keeps the kind of a backend failure", async () => {
+    vi.mocked(importUrlResource).mockRejectedValue({ kind: "rejected", message: "192.168.1.20 is a local or private address and can't be imported from" });
+
+    const failure = await importFromUrl(PROFILE, "https://192.168.1.20/card.png").catch((error: unknown) => error);
+
+    expect(failure).toBeInstanceOf(UrlImportFailure);
+    expect(failure).toMatchObject({ kind: "rejected" });
+  });
+});
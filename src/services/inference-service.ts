@@ -4,15 +4,24 @@ import { useCurrentChatActiveChapterID, useCurrentChatId } from "@/hooks/chatSto
 import { useCurrentProfile } from "@/hooks/ProfileStore";
 import { useInference } from "@/hooks/useInference";
 import type { ModelSpecs } from "@/schema/inference-engine-schema";
+import { ResponseLengthPresetsSchema } from "@/schema/profiles-schema";
+import { manifestSupportsReasoning } from "./ai-providers/aisdk/reasoning-support";
+import { DUPLICATE_REQUEST, EMPTY_RESPONSE, TRUNCATED_BEFORE_ANSWER } from "./ai-providers/types/ai-event.type";
 import { chatEventBus } from "./chat-event-bus";
 import { formatFinalText } from "./inference/formatter/format-response";
 import { removeNestedFields } from "./inference/formatter/remove-nested-fields";
 import { useMessageManager } from "./inference/message-manager";
+import { applyOutputGuard, processGuardedChunk } from "./inference/output-guard";
 import { usePromptFormatter } from "./inference/prompt-formatter";
+import { applyResponseLength, appendResponseLengthInstruction } from "./inference/response-length";
+import { emitMessageRegenerated, loadRegenerationTarget, type RegenerateMode, regenerationIndex } from "./inference/regenerate";
 import { processStreamChunk } from "./inference/stream-processor";
 import { useStreamingStateManager } from "./inference/streaming-state-manager";
+import { useChatSynopsis } from "./inference/synopsis";
+import { getChatTranslation, translatesDirection, useTranslation } from "./inference/translation";
 import type { GenerationOptions } from "./inference/types";
 import { batchedStreamingUpdate, playBeepSound } from "./inference/utils";
+import { dispatchWebhookEvent } from "./webhook-service";
 
 /**
  * Main inference service hook that orchestrates all inference functionality.
@@ -30,6 +39,8 @@ export function useInferenceService() {
   const streamingManager = useStreamingStateManager();
   const messageManager = useMessageManager();
   const promptFormatter = usePromptFormatter();
+  const { translateText } = useTranslation();
+  const { refreshChatSynopsisIfDue } = useChatSynopsis();
 
   // Per-request message snapshot: preserves the messages array at generation start
   // so streaming updates can correctly rebuild the array for any chat (not just the selected one).
@@ -47,7 +58,20 @@ export function useInferenceService() {
       session.accumulatedReasoning += response.result.reasoning || "";
 
       const currentChunk = response.result.text || response.result.full_response || "";
-      const { textToAdd, reasoningToAdd } = processStreamChunk(currentChunk, session, session.formatTemplate);
+      const { textToAdd: chunkText, reasoningToAdd } = processStreamChunk(currentChunk, session, session.formatTemplate);
+
+      let textToAdd = chunkText;
+      if (session.outputGuard) {
+        const guarded = processGuardedChunk(chunkText, session.outputGuard);
+        textToAdd = guarded.text;
+        if (guarded.stop) {
+          // The model is writing for someone else: end the reply here, completion keeps the text so far
+          cancelRequest(requestId).catch((error) => {
+            console.error("Failed to stop the reply at the output guard:", error);
+            toast.error("Couldn't stop the reply", { description: error instanceof Error ? error.message : String(error) });
+          });
+        }
+      }
 
       streamingManager.batchUpdateSessionByRequest(requestId, (s) => ({
         accumulatedText: s.accumulatedText + textToAdd,
@@ -71,19 +95,66 @@ export function useInferenceService() {
 
       if (session.characterId && session.messageId) {
         const rawText = response.result?.full_response || response.result?.text || session.accumulatedText;
-        const { text: finalText, reasoning: finalReasoning } = formatFinalText(rawText, session.formatTemplate);
+        const { text: formattedText, reasoning: finalReasoning } = formatFinalText(rawText, session.formatTemplate);
+        const finalText = session.outputGuard ? applyOutputGuard(formattedText, session.outputGuard) : formattedText;
 
         streamingManager.batchUpdateSessionByRequest(requestId, () => ({
           accumulatedReasoning: finalReasoning || "",
         }));
 
         const snapshot = messageSnapshotsRef.current.get(requestId);
-        messageManager.updateMessageDirect(session.chatId!, session.messageId, finalText, session.messageIndex || 0, snapshot);
+        const saved = messageManager.updateMessageDirect(session.chatId!, session.messageId, finalText, session.messageIndex || 0, snapshot);
+        const finishReason = response.status === "completed" ? response.result.finish_reason : undefined;
+        if (session.variantModel || finishReason) {
+          messageManager
+            .recordVariantMetadata(session.messageId, session.messageIndex || 0, { model: session.variantModel, finishReason })
+            .catch((error) => console.error("Failed to record variant metadata:", error));
+        }
+
+        const translation = session.translation;
+        if (translation && translatesDirection(translation, "incoming") && finalText.trim()) {
+          const messageId = session.messageId;
+          const messageIndex = session.messageIndex || 0;
+          translateText(finalText, translation.user_language, translation.model_id)
+            .then((translated) => messageManager.recordTranslation(messageId, messageIndex, translated, { text: finalText, language: translation.target_language }))
+            .catch((error) => {
+              toast.warning("Reply translation failed, showing the original text", {
+                description: error instanceof Error ? error.message : String(error),
+              });
+            });
+        }
+
+        // The synopsis reads the reply from the database, so it waits for it to be saved
+        const chatId = session.chatId;
+        if (chatId) {
+          saved
+            .then(() => refreshChatSynopsisIfDue(chatId))
+            .catch((error) => {
+              toast.warning("Couldn't refresh the chat synopsis", {
+                description: error instanceof Error ? error.message : String(error),
+              });
+            });
+        }
+
+        if (session.regenerateMode && session.chatId) {
+          emitMessageRegenerated({
+            chatId: session.chatId,
+            messageId: session.messageId,
+            messageIndex: session.messageIndex || 0,
+            mode: session.regenerateMode,
+            requestId,
+            text: finalText,
+          });
+        }
 
         streamingManager.resetSessionByRequest(requestId);
         messageSnapshotsRef.current.delete(requestId);
         playBeepSound(currentProfile.settings.chat.beepSound);
 
+        const messageData = { chat_id: session.chatId, message_id: session.messageId, participant_id: session.characterId, text: finalText };
+        dispatchWebhookEvent(currentProfile.id, "inference.completed", messageData);
+        dispatchWebhookEvent(currentProfile.id, "chat.message_added", messageData);
+
         // Emit after_participant_message event so agent triggers can react.
         // The emitChatEvents flag is stored on the session so the onComplete handler can read it.
         if (session.chatId && session.characterId !== "generate-input-area" && session.emitChatEvents !== false) {
@@ -98,6 +169,11 @@ export function useInferenceService() {
     },
 
     onError: (error: unknown, requestId) => {
+      // A double send; the first request carries on and generateMessage drops this one's session
+      if (typeof error === "object" && error && "code" in error && error.code === DUPLICATE_REQUEST) {
+        return;
+      }
+
       const session = streamingManager.getSessionByRequest(requestId);
       if (!session) {
         return;
@@ -105,11 +181,29 @@ export function useInferenceService() {
 
       const message = error instanceof Error ? error.message : typeof error === "object" && error && "message" in error ? String((error as { message?: unknown }).message) : "Unknown error";
 
-      toast.error("Inference error:", {
-        description: message,
-      });
+      if (typeof error === "object" && error && "code" in error && error.code === TRUNCATED_BEFORE_ANSWER) {
+        // The reasoning is kept on the request in the inference console
+        toast.warning("The model ran out of tokens while reasoning", {
+          description: message,
+        });
+      } else if (typeof error === "object" && error && "code" in error && error.code === EMPTY_RESPONSE) {
+        toast.warning("The model returned an empty response", {
+          description: message,
+        });
+      } else {
+        toast.error("Inference error:", {
+          description: message,
+        });
+      }
       console.error("Inference error:", error);
 
+      dispatchWebhookEvent(currentProfile.id, "inference.error", {
+        chat_id: session.chatId,
+        message_id: session.messageId,
+        participant_id: session.characterId,
+        error: message,
+      });
+
       streamingManager.resetSessionByRequest(requestId);
       messageSnapshotsRef.current.delete(requestId);
     },
@@ -127,6 +221,7 @@ export function useInferenceService() {
         quietResponse = false,
         systemPromptOverride = "",
         parametersOverride,
+        parameterOverrides,
         stream = true,
         existingMessageId = null,
         messageIndex = 0,
@@ -134,6 +229,7 @@ export function useInferenceService() {
         extraSuggestions = {},
         messageHistoryOverride,
         emitChatEvents = true,
+        regenerateMode = null,
       } = options;
 
       const chatId = optionsChatId ?? currentChatId;
@@ -153,6 +249,7 @@ export function useInferenceService() {
           messageId: existingMessageId,
           messageIndex,
           emitChatEvents,
+          regenerateMode,
         });
 
         if (onStreamingStateChange) {
@@ -161,24 +258,50 @@ export function useInferenceService() {
 
         const freshMessages = messageHistoryOverride || (await promptFormatter.fetchChatMessages(chatId, chapterId));
 
-        const promptResult = await promptFormatter.formatPrompt(userMessage, characterId, systemPromptOverride, chatTemplateID, freshMessages, extraSuggestions, existingMessageId || undefined);
+        const translation = await getChatTranslation(chatId, currentProfile.id);
+        let promptUserMessage = userMessage;
+        if (userMessage && translation && translatesDirection(translation, "outgoing")) {
+          try {
+            promptUserMessage = await translateText(userMessage, translation.target_language, translation.model_id);
+          } catch (error) {
+            toast.warning("Message translation failed, sending the original text", {
+              description: error instanceof Error ? error.message : String(error),
+            });
+          }
+        }
+
+        const promptResult = await promptFormatter.formatPrompt(promptUserMessage, characterId, systemPromptOverride, chatTemplateID, freshMessages, extraSuggestions, existingMessageId || undefined);
 
         if (!promptResult) {
           throw new Error("Failed to format prompt");
         }
 
-        const { inferenceMessages, systemPrompt, manifestSettings, modelSettings, chatTemplate, formatTemplate, isChat, customStopStrings } = promptResult;
+        const { inferenceMessages, systemPrompt, manifestSettings, modelSettings, chatTemplate, formatTemplate, isChat, customStopStrings, outputGuard } = promptResult;
 
         if (!modelSettings || !manifestSettings) {
           throw new Error("Model or manifest settings not available. Check chat template configuration.");
         }
 
-        streamingManager.updateSessionByRequest(localRequestId, { formatTemplate });
+        streamingManager.updateSessionByRequest(localRequestId, {
+          formatTemplate,
+          translation,
+          outputGuard,
+          variantModel: {
+            model_id: modelSettings.id,
+            model_name: modelSettings.name,
+            actual_model: typeof modelSettings.config?.model === "string" ? modelSettings.config.model : undefined,
+            request_id: localRequestId,
+          },
+        });
 
         let messageId: string;
 
         if (userMessage && !quietUserMessage) {
-          await messageManager.createUserMessage(userMessage, chatId, chapterId);
+          const userChatMessage = await messageManager.createUserMessage(userMessage, chatId, chapterId);
+          if (promptUserMessage && promptUserMessage !== userMessage && translation) {
+            await messageManager.recordTranslation(userChatMessage.id, 0, userMessage, { text: promptUserMessage, language: translation.target_language });
+          }
+          dispatchWebhookEvent(currentProfile.id, "chat.message_added", { chat_id: chatId, message_id: userChatMessage.id, participant_id: "user", text: userMessage });
           // Emit after_user_message so agents can react to the user's input
           if (emitChatEvents) {
             chatEventBus.emit({
@@ -227,9 +350,17 @@ export function useInferenceService() {
           config: modelSettings.config || {},
           max_concurrent_requests: modelSettings.max_concurrency || 1,
           engine: manifestSettings.engine,
+          supports_reasoning: manifestSupportsReasoning(manifestSettings),
+          inference_fields: manifestSettings.inference_fields,
+          capabilities: modelSettings.capabilities,
+          fallback_model_ids: modelSettings.fallback_model_ids,
         };
 
-        const parameters = removeNestedFields(parametersOverride || chatTemplate?.config || {});
+        const { parameters, instruction: responseLengthInstruction } = applyResponseLength(
+          removeNestedFields({ ...(parametersOverride || chatTemplate?.config || {}), ...parameterOverrides }),
+          ResponseLengthPresetsSchema.parse(currentProfile.settings.chat.responseLengthPresets ?? {}),
+          typeof parameterOverrides?.max_tokens === "number" ? parameterOverrides.max_tokens : parametersOverride?.max_tokens,
+        );
         if (customStopStrings) {
           parameters.stop = parameters.stop ? [...parameters.stop, ...customStopStrings] : customStopStrings;
         }
@@ -237,10 +368,12 @@ export function useInferenceService() {
         const confirmID = await runInference({
           messages: inferenceMessages,
           modelSpecs,
-          systemPrompt,
+          systemPrompt: appendResponseLengthInstruction(systemPrompt, responseLengthInstruction),
           parameters,
           stream,
           requestId: localRequestId,
+          chatId,
+          priority: "interactive",
         });
 
         if (!confirmID) {
@@ -273,12 +406,21 @@ export function useInferenceService() {
         throw error;
       }
     },
-    [streamingManager, messageManager, promptFormatter, currentChatId, currentChapterID, runInference],
+    [streamingManager, messageManager, promptFormatter, currentChatId, currentChapterID, runInference, translateText],
   );
 
+  /**
+   * Regenerate a character message from the history before it, with the chat's prompt and parameters rebuilt as
+   * for the original reply. `mode` "replace" rewrites the version on screen (or `messageIndex`), "add_version"
+   * writes a new version after the last. `parameterOverrides` are merged over the template's parameters.
+   * Emits `message-regenerated` once the reply is saved.
+   * @throws {RegenerateError} When the chat or message doesn't exist, or the message isn't a character's
+   */
   const regenerateMessage = useCallback(
-    async (messageId: string, options: Partial<Omit<GenerationOptions, "existingMessageId">> = {}): Promise<string | null> => {
+    async (messageId: string, options: Partial<Omit<GenerationOptions, "existingMessageId" | "regenerateMode">> & { mode?: RegenerateMode } = {}): Promise<string | null> => {
       const chatId = options.chatId ?? currentChatId;
+      const { mode = "replace", ...generationOptions } = options;
+      const target = await loadRegenerationTarget(messageId, chatId, currentProfile.id);
 
       // Cancel any ongoing request for THIS chat only
       if (streamingManager.isStreaming(chatId)) {
@@ -289,25 +431,23 @@ export function useInferenceService() {
         }
       }
 
-      if (!options.characterId) {
-        console.error("Character ID is required for regeneration");
-        return null;
-      }
-
       return generateMessage({
         chatId,
-        characterId: options.characterId,
-        userMessage: options.userMessage,
-        systemPromptOverride: options.systemPromptOverride,
-        parametersOverride: options.parametersOverride,
-        stream: options.stream !== undefined ? options.stream : true,
+        chapterId: generationOptions.chapterId ?? target.chapter_id,
+        characterId: generationOptions.characterId ?? (target.character_id as string),
+        userMessage: generationOptions.userMessage,
+        systemPromptOverride: generationOptions.systemPromptOverride,
+        parametersOverride: generationOptions.parametersOverride,
+        parameterOverrides: generationOptions.parameterOverrides,
+        stream: generationOptions.stream !== undefined ? generationOptions.stream : true,
         existingMessageId: messageId,
-        messageIndex: options.messageIndex !== undefined ? options.messageIndex : 0,
-        onStreamingStateChange: options.onStreamingStateChange,
-        emitChatEvents: options.emitChatEvents,
+        messageIndex: regenerationIndex(target, mode, generationOptions.messageIndex),
+        onStreamingStateChange: generationOptions.onStreamingStateChange,
+        emitChatEvents: generationOptions.emitChatEvents,
+        regenerateMode: mode,
       });
     },
-    [cancelRequest, generateMessage, streamingManager, currentChatId],
+    [cancelRequest, generateMessage, streamingManager, currentChatId, currentProfile.id],
   );
 
   const cancelGeneration = useCallback(
@@ -0,0 +1,276 @@
+import { emitToCurrentWindow } from "@/commands/windows";
+import type { BudgetSettings } from "@/schema/profiles-schema";
+import type { AIUsage } from "@/services/ai-providers/types/ai-event.type";
+import { getModelManifestById } from "@/services/manifest-service";
+import { getModelById } from "@/services/model-service";
+import { getProfileById } from "@/services/profile-service";
+import { executeDBWrite, selectDBQuery } from "@/utils/database";
+
+/**
+ * Budget: the tokens and cost of requests to paid providers are added up per profile, both per calendar month
+ * and per day (UTC), and checked against the profile's `budget` settings before each request is queued. Models
+ * of manifests flagged `local` never count. Cost needs the model's `input_cost_per_million`/`output_cost_per_million`
+ * config; without them only tokens count.
+ */
+
+const BUDGET_WARNING_EVENT = "budget-warning";
+// Share of a limit at which the profile is warned
+const BUDGET_WARNING_RATIO = 0.8;
+
+type BudgetState = "ok" | "warning" | "exceeded";
+
+type BudgetPeriod = "month" | "day";
+
+// Table holding each period's totals, one row per profile and bucket
+const USAGE_TABLES: Record<BudgetPeriod, { table: string; column: string }> = {
+  month: { table: "profile_usage", column: "month" },
+  day: { table: "profile_usage_daily", column: "day" },
+};
+
+interface BudgetUsage {
+  period: BudgetPeriod;
+  // "YYYY-MM" for a month, "YYYY-MM-DD" for a day
+  bucket: string;
+  inputTokens: number;
+  outputTokens: number;
+  cost: number;
+  requestCount: number;
+}
+
+interface BudgetStatus {
+  profileId: string;
+  period: BudgetPeriod;
+  bucket: string;
+  tokensUsed: number;
+  costUsed: number;
+  requestCount: number;
+  tokenLimit: number | null;
+  costLimit: number | null;
+  // Highest share used of the limits that are set, null when none is
+  usedRatio: number | null;
+  state: BudgetState;
+  action: BudgetSettings["action"];
+}
+
+interface BudgetWarningPayload {
+  profileId: string;
+  period: BudgetPeriod;
+  bucket: string;
+  state: Exclude<BudgetState, "ok">;
+  usedRatio: number;
+  action: BudgetSettings["action"];
+}
+
+interface BudgetCheck {
+  // Of the period closest to its limit
+  status: BudgetStatus;
+  // The request must not be sent
+  blocked: boolean;
+  // The usage just crossed 80% or 100% of a limit, and `budget-warning` went out
+  warned: boolean;
+}
+
+// Calendar month in UTC, e.g. "2026-10"
+function usageMonth(date = new Date()): string {
+  return date.toISOString().slice(0, 7);
+}
+
+// Calendar day in UTC, e.g. "2026-10-16", so the day rolls over with the month
+function usageDay(date = new Date()): string {
+  return date.toISOString().slice(0, 10);
+}
+
+function usageBucket(period: BudgetPeriod, date = new Date()): string {
+  return period === "month" ? usageMonth(date) : usageDay(date);
+}
+
+function periodLimits(period: BudgetPeriod, settings: Partial<BudgetSettings> | undefined): { tokenLimit: number | null; costLimit: number | null } {
+  return period === "month"
+    ? { tokenLimit: settings?.monthlyTokenLimit || null, costLimit: settings?.monthlyCostLimit || null }
+    : { tokenLimit: settings?.dailyTokenLimit || null, costLimit: settings?.dailyCostLimit || null };
+}
+
+function pricePerMillion(config: Record<string, unknown> | undefined, key: string): number {
+  const price = Number(config?.[key]);
+  return Number.isFinite(price) && price > 0 ? price : 0;
+}
+
+/**
+ * Cost of a request from the prices in its model's config, 0 when they aren't set
+ */
+function estimateCost(usage: AIUsage, config: Record<string, unknown> | undefined): number {
+  return (usage.inputTokens * pricePerMillion(config, "input_cost_per_million") + usage.outputTokens * pricePerMillion(config, "output_cost_per_million")) / 1_000_000;
+}
+
+/**
+ * Compare a month's or day's usage with that period's budget settings. A limit that is null or 0 is unlimited.
+ */
+function evaluateBudget(profileId: string, usage: BudgetUsage, settings: Partial<BudgetSettings> | undefined): BudgetStatus {
+  const { tokenLimit, costLimit } = periodLimits(usage.period, settings);
+  const tokensUsed = usage.inputTokens + usage.outputTokens;
+
+  const ratios: number[] = [];
+  if (tokenLimit) {
+    ratios.push(tokensUsed / tokenLimit);
+  }
+  if (costLimit) {
+    ratios.push(usage.cost / costLimit);
+  }
+  const usedRatio = ratios.length > 0 ? Math.max(...ratios) : null;
+
+  return {
+    profileId,
+    period: usage.period,
+    bucket: usage.bucket,
+    tokensUsed,
+    costUsed: usage.cost,
+    requestCount: usage.requestCount,
+    tokenLimit,
+    costLimit,
+    usedRatio,
+    state: usedRatio === null ? "ok" : usedRatio >= 1 ? "exceeded" : usedRatio >= BUDGET_WARNING_RATIO ? "warning" : "ok",
+    action: settings?.action ?? "warn",
+  };
+}
+
+async function getUsage(period: BudgetPeriod, profileId: string, bucket = usageBucket(period)): Promise<BudgetUsage> {
+  const { table, column } = USAGE_TABLES[period];
+  const rows = await selectDBQuery<any[]>(`SELECT input_tokens, output_tokens, cost, request_count FROM ${table} WHERE profile_id = $1 AND ${column} = $2`, [profileId, bucket]);
+  const row = rows[0];
+  return {
+    period,
+    bucket,
+    inputTokens: Number(row?.input_tokens ?? 0),
+    outputTokens: Number(row?.output_tokens ?? 0),
+    cost: Number(row?.cost ?? 0),
+    requestCount: Number(row?.request_count ?? 0),
+  };
+}
+
+function getMonthlyUsage(profileId: string, month = usageMonth()): Promise<BudgetUsage> {
+  return getUsage("month", profileId, month);
+}
+
+function getDailyUsage(profileId: string, day = usageDay()): Promise<BudgetUsage> {
+  return getUsage("day", profileId, day);
+}
+
+async function recordUsage(profileId: string, usage: AIUsage, cost: number, date = new Date()): Promise<void> {
+  for (const period of Object.keys(USAGE_TABLES) as BudgetPeriod[]) {
+    await recordPeriodUsage(period, profileId, usage, cost, date);
+  }
+}
+
+async function recordPeriodUsage(period: BudgetPeriod, profileId: string, usage: AIUsage, cost: number, date: Date): Promise<void> {
+  const { table, column } = USAGE_TABLES[period];
+  await executeDBWrite(
+    `INSERT INTO ${table} (profile_id, ${column}, input_tokens, output_tokens, cost, request_count, updated_at)
+     VALUES ($1, $2, $3, $4, $5, 1, CURRENT_TIMESTAMP)
+     ON CONFLICT (profile_id, ${column}) DO UPDATE SET
+       input_tokens = input_tokens + excluded.input_tokens,
+       output_tokens = output_tokens + excluded.output_tokens,
+       cost = cost + excluded.cost,
+       request_count = request_count + 1,
+       updated_at = CURRENT_TIMESTAMP`,
+    [profileId, usageBucket(period, date), usage.inputTokens, usage.outputTokens, cost],
+  );
+}
+
+// Manifest id -> whether it is flagged local; manifests only change with the app
+const localManifests = new Map<string, boolean>();
+
+/**
+ * Whether a model's requests are free (a local engine), so they neither count nor get blocked
+ */
+async function isBudgetExempt(modelId: string): Promise<boolean> {
+  const model = await getModelById(modelId).catch(() => null);
+  if (!model) {
+    return false;
+  }
+  if (!localManifests.has(model.manifest_id)) {
+    const manifest = await getModelManifestById(model.manifest_id).catch(() => null);
+    localManifests.set(model.manifest_id, !!manifest?.local);
+  }
+  return localManifests.get(model.manifest_id) ?? false;
+}
+
+/**
+ * Month-to-date or today's usage and limits of a profile, for the settings screen
+ */
+async function getBudgetStatus(profileId: string, period: BudgetPeriod = "month"): Promise<BudgetStatus> {
+  const profile = await getProfileById(profileId);
+  if (!profile) {
+    throw new Error(`Profile ${profileId} not found`);
+  }
+  return evaluateBudget(profileId, await getUsage(period, profileId), profile.settings?.budget);
+}
+
+const STATE_SEVERITY: Record<BudgetState, number> = { ok: 0, warning: 1, exceeded: 2 };
+
+// Warnings already sent, so each crossing is reported once. Keyed by the limits too, so raising them re-arms the warning.
+const sentWarnings = new Set<string>();
+
+/**
+ * Check the budget before a request to a model is queued. Null when the model is exempt.
+ */
+async function checkBudget(profileId: string, settings: Partial<BudgetSettings> | undefined, modelId: string): Promise<BudgetCheck | null> {
+  const periods = (Object.keys(USAGE_TABLES) as BudgetPeriod[]).filter((period) => {
+    const { tokenLimit, costLimit } = periodLimits(period, settings);
+    return tokenLimit !== null || costLimit !== null;
+  });
+  if (periods.length === 0) {
+    return null;
+  }
+  if (await isBudgetExempt(modelId)) {
+    return null;
+  }
+
+  const statuses = await Promise.all(periods.map(async (period) => evaluateBudget(profileId, await getUsage(period, profileId), settings)));
+  const status = statuses.reduce((worst, next) =>
+    STATE_SEVERITY[next.state] > STATE_SEVERITY[worst.state] || (next.state === worst.state && (next.usedRatio ?? 0) > (worst.usedRatio ?? 0)) ? next : worst,
+  );
+  let warned = false;
+  if (status.state !== "ok" && status.usedRatio !== null) {
+    const key = [profileId, status.period, status.bucket, status.state, status.tokenLimit, status.costLimit].join(":");
+    if (!sentWarnings.has(key)) {
+      sentWarnings.add(key);
+      warned = true;
+      const payload: BudgetWarningPayload = {
+        profileId,
+        period: status.period,
+        bucket: status.bucket,
+        state: status.state,
+        usedRatio: status.usedRatio,
+        action: status.action,
+      };
+      emitToCurrentWindow(BUDGET_WARNING_EVENT, payload).catch((error) => console.error("Failed to emit budget warning:", error));
+    }
+  }
+
+  return { status, blocked: status.state === "exceeded" && status.action === "block", warned };
+}
+
+/**
+ * Add a finished request's tokens and cost to the profile's month and day, unless its model is exempt
+ */
+async function recordRequestUsage(profileId: string, modelId: string, config: Record<string, unknown> | undefined, usage: AIUsage): Promise<void> {
+  if (await isBudgetExempt(modelId)) {
+    return;
+  }
+  await recordUsage(profileId, usage, estimateCost(usage, config));
+}
+
+export type { BudgetCheck, BudgetPeriod, BudgetState, BudgetStatus, BudgetUsage, BudgetWarningPayload };
+export {
+  BUDGET_WARNING_EVENT,
+  BUDGET_WARNING_RATIO,
+  checkBudget,
+  estimateCost,
+  evaluateBudget,
+  getBudgetStatus,
+  getDailyUsage,
+  getMonthlyUsage,
+  recordRequestUsage,
+  usageDay,
+  usageMonth,
+};
@@ -0,0 +1,61 @@
+import type { ChatMessage } from "@/schema/chat-message-schema";
+import type { ContextLimitStatistics } from "./formatter/apply-context-limit";
+
+/**
+ * How much of the context window the next request of a chat takes: the system prompt, few-shot
+ * examples and history as `applyContextLimit` counted them, what is left for the reply, and the
+ * messages the limit leaves out. Counts use the same token cache, so they match what gets sent.
+ */
+export interface ContextBudget {
+  systemTokens: number;
+  exampleTokens: number;
+  historyTokens: number;
+  usedTokens: number;
+  // The chat template's max_context
+  contextWindow: number;
+  // Null when the model doesn't declare one
+  modelContextLength: number | null;
+  // max_tokens, set aside for the reply out of the window
+  responseTokens: number;
+  remainingTokens: number;
+  // Oldest first
+  trimmedMessageIds: string[];
+}
+
+/**
+ * Messages `getChatHistory` turns into an unpinned history entry, in chat order
+ */
+function trimmableMessages(messages: ChatMessage[]): ChatMessage[] {
+  return messages.filter((message) => {
+    if (message.disabled || message.pinned || message.extra?.exclude_from_context || message.extra?.promptConfig) {
+      return false;
+    }
+    const text = message.messages[message.message_index || 0];
+    return message.type === "system" || !!text;
+  });
+}
+
+/**
+ * Budget for a prompt formatted over `messages` (after context resets). The limit drops the oldest
+ * unpinned entries, so the trimmed ids are the oldest trimmable messages; with message merging or
+ * depth-inserted prompts the mapping is approximate.
+ */
+export function buildContextBudget(
+  statistics: ContextLimitStatistics,
+  options: { maxContext: number; modelContextLength?: number | null; messages: ChatMessage[] },
+): ContextBudget {
+  const usedTokens = statistics.systemTokens + statistics.exampleTokens + statistics.historyTokens;
+  const trimmed = trimmableMessages(options.messages).slice(0, statistics.trimmedMessages);
+
+  return {
+    systemTokens: statistics.systemTokens,
+    exampleTokens: statistics.exampleTokens,
+    historyTokens: statistics.historyTokens,
+    usedTokens,
+    contextWindow: options.maxContext,
+    modelContextLength: options.modelContextLength ?? null,
+    responseTokens: statistics.responseTokens,
+    remainingTokens: Math.max(0, options.maxContext - usedTokens),
+    trimmedMessageIds: trimmed.map((message) => message.id),
+  };
+}
@@ -1,16 +1,18 @@
 import { Character } from "@/schema/characters-schema";
 import { ChatChapter } from "@/schema/chat-chapter-schema";
 import { ChatMessage } from "@/schema/chat-message-schema";
+import { Chat } from "@/schema/chat-schema";
 import { InferenceMessage } from "@/schema/inference-engine-schema";
 import { Model } from "@/schema/models-schema";
 import { ChatTemplate, ChatTemplateCustomPrompt } from "@/schema/template-chat-schema";
 import { FormatTemplate } from "@/schema/template-format-schema";
 import { InferenceTemplate } from "@/schema/template-inferance-schema";
-import { applyContextLimit } from "./formatter/apply-context-limit";
+import { getNoteSnippets } from "@/services/notes-service";
+import { applyContextLimit, type ContextLimitStatistics } from "./formatter/apply-context-limit";
 import { applyInferenceTemplate } from "./formatter/apply-inference-template";
 import { getLorebookContent, LorebookContentResponse, processLorebookMessages } from "./formatter/apply-lorebook";
 import { collapseConsecutiveLines, mergeMessagesOnUser, mergeSubsequentMessages } from "./formatter/format-template-utils";
-import { replaceTextPlaceholders } from "./formatter/replace-text-placeholders";
+import { type DateTimePatternOptions, findNoteReferences, protectMacros, replaceTextPlaceholders, restoreMacros } from "./formatter/replace-text-placeholders";
 
 /**
  * Interface for message with character information
@@ -27,8 +29,12 @@ export interface PromptFormatterConfig {
   messageHistory: MessageWithCharacter[] | ChatMessage[];
   userPrompt?: string;
   systemOverridePrompt?: string;
+  // Few-shot examples kept apart from the history: placeholders are replaced but the context limit never trims them
+  examples?: InferenceMessage[];
 
   // Settings
+  // Profile that owns the notes referenced by {{note:title}}
+  profileId?: string;
   modelSettings?: Model | null;
   formatTemplate?: FormatTemplate | null;
   inferenceTemplate?: InferenceTemplate | null;
@@ -42,10 +48,15 @@ export interface PromptFormatterConfig {
     user_character?: Pick<Character, "name" | "custom" | "lorebook_id">;
     character?: Pick<Character, "name" | "settings" | "custom" | "type" | "lorebook_id">;
     chapter?: Pick<ChatChapter, "title" | "scenario" | "instructions">;
+    // For {{chat.name}} and {{synopsis}}
+    chat?: Pick<Chat, "name" | "synopsis">;
     extra?: Record<string, string>;
     censorship?: {
       words?: string[];
     };
+    dateTime?: DateTimePatternOptions;
+    // Note contents by title, for {{note:title}}. Loaded by formatPrompt when missing.
+    notes?: Record<string, string>;
   };
 }
 
@@ -55,7 +66,10 @@ export interface PromptFormatterConfig {
 export interface FormattedPromptResult {
   inferenceMessages: InferenceMessage[];
   systemPrompt?: string;
+  // Sent between the system prompt and inferenceMessages
+  examples?: InferenceMessage[];
   customStopStrings?: string[];
+  statistics?: ContextLimitStatistics;
 }
 
 const addPrefix = (string: string, prefix: string) => {
@@ -81,7 +95,7 @@ export function getChatHistory(
   // Process existing chat messages
   if (messages && messages.length > 0) {
     for (const message of messages) {
-      if (message.messages.length === 0 || message.disabled) {
+      if (message.messages.length === 0 || message.disabled || message.extra?.exclude_from_context) {
         continue;
       }
 
@@ -92,17 +106,22 @@ export function getChatHistory(
 
       const character = message.character_name || "";
       const index = message.message_index || 0;
-      const messageText = message.messages[index];
+      // Translated variants are sent in the language the model wrote or received them in
+      const messageText = message.extra?.translations?.[index]?.text ?? message.messages[index];
 
       if (message.type === "user" && messageText) {
         inferenceMessages.push({
           role: "user",
           text: canInsertPrefix ? addPrefix(messageText, character) : messageText,
+          ...(character && { name: character }),
+          ...(message.pinned && { pinned: true }),
         });
       } else if (message.type === "character" && messageText) {
         inferenceMessages.push({
           role: "assistant",
           text: canInsertPrefix ? addPrefix(messageText, character) : messageText,
+          ...(character && { name: character }),
+          ...(message.pinned && { pinned: true }),
         });
       } else if (message.type === "system") {
         // Handle summary messages specially
@@ -114,12 +133,14 @@ export function getChatHistory(
           inferenceMessages.push({
             role: "user",
             text: formattedSummary,
+            ...(message.pinned && { pinned: true }),
           });
         } else {
           // Regular system message
           inferenceMessages.push({
             role: "user",
             text: messageText,
+            ...(message.pinned && { pinned: true }),
           });
         }
       }
@@ -144,12 +165,14 @@ interface CreateSystemPromptConfig {
   systemOverridePrompt?: string | null;
   contextSeparator?: string;
   customPrompts?: ChatTemplateCustomPrompt[];
+  // Keep the character, chapter, user and lorebook sections even when there is nothing to fill them with
+  keepAllSections?: boolean;
 }
 /**
  * Create system prompt from template
  */
 export function createSystemPrompt(config: CreateSystemPromptConfig): string | undefined {
-  const { systemPromptTemplate, chatConfig, lorebookContent, systemOverridePrompt, contextSeparator, customPrompts } = config;
+  const { systemPromptTemplate, chatConfig, lorebookContent, systemOverridePrompt, contextSeparator, customPrompts, keepAllSections } = config;
 
   let prompts = structuredClone(systemPromptTemplate?.prompts?.filter((prompt) => prompt.enabled) || []);
 
@@ -263,24 +286,24 @@ export function createSystemPrompt(config: CreateSystemPromptConfig): string | u
     }
   }
 
-  if (!hasCharacter) {
+  if (!keepAllSections && !hasCharacter) {
     prompts = prompts.filter((prompt) => prompt.type !== "character-context");
     prompts = prompts.filter((prompt) => prompt.type !== "character-memory");
   }
 
-  if (!hasChapter) {
+  if (!keepAllSections && !hasChapter) {
     prompts = prompts.filter((prompt) => prompt.type !== "chapter-context");
   }
 
-  if (!hasUserCharacter) {
+  if (!keepAllSections && !hasUserCharacter) {
     prompts = prompts.filter((prompt) => prompt.type !== "user-context");
   }
 
-  if (!lorebookContent?.lorebook_top) {
+  if (!keepAllSections && !lorebookContent?.lorebook_top) {
     prompts = prompts.filter((prompt) => prompt.type !== "lorebook-top");
   }
 
-  if (!lorebookContent?.lorebook_bottom) {
+  if (!keepAllSections && !lorebookContent?.lorebook_bottom) {
     prompts = prompts.filter((prompt) => prompt.type !== "lorebook-bottom");
   }
 
@@ -456,7 +479,12 @@ export async function formatPrompt(config: PromptFormatterConfig): Promise<Forma
   const mergedCustomPrompts = [...(config.chatTemplate?.custom_prompts ?? []), ...scriptedPrompts];
 
   // Step 1: Get chat history with user message (scripted prompt messages are skipped inside)
-  const chatHistory = getChatHistory(structuredClone(config.messageHistory), config.userPrompt, prefixOption, config.chatConfig?.injectionPrompts);
+  let chatHistory = getChatHistory(structuredClone(config.messageHistory), config.userPrompt, prefixOption, config.chatConfig?.injectionPrompts);
+  // Hidden from placeholder replacement until the prompt is rendered, whatever merging does to them
+  const protectMessages = config.formatTemplate?.config.settings.substitute_message_macros === false;
+  if (protectMessages) {
+    chatHistory = chatHistory.map((message) => ({ ...message, text: protectMacros(message.text) }));
+  }
   // Step 2: Process custom prompts (template + resolved scripted prompts)
   let processedMessages = processCustomPrompts(chatHistory, mergedCustomPrompts);
 
@@ -494,12 +522,24 @@ export async function formatPrompt(config: PromptFormatterConfig): Promise<Forma
     contextSeparator,
   });
 
+  const noteTitles = findNoteReferences([rawSystemPrompt, ...[...(config.examples ?? []), ...processedMessages].map((message) => message.text)]);
+  if (noteTitles.length > 0 && config.profileId && !config.chatConfig.notes) {
+    config.chatConfig = { ...config.chatConfig, notes: await getNoteSnippets(config.profileId, noteTitles) };
+  }
+
   let formattedPrompt = replaceTextPlaceholders(processedMessages, rawSystemPrompt, config.chatConfig);
+  if (config.examples?.length) {
+    formattedPrompt.examples = replaceTextPlaceholders(config.examples, undefined, config.chatConfig).inferenceMessages;
+  }
 
   if (config.formatTemplate?.config.settings.collapse_consecutive_lines) {
     formattedPrompt = collapseConsecutiveLines(structuredClone(formattedPrompt));
   }
 
+  if (protectMessages) {
+    formattedPrompt.inferenceMessages = formattedPrompt.inferenceMessages.map((message) => ({ ...message, text: restoreMacros(message.text) }));
+  }
+
   const limitedPrompt = await applyContextLimit(formattedPrompt, {
     config: config.chatTemplate?.config || { max_context: 100, max_tokens: 1500, max_depth: 100 },
     custom_prompts: mergedCustomPrompts,
@@ -513,7 +553,8 @@ export async function formatPrompt(config: PromptFormatterConfig): Promise<Forma
     const inferencePrompt = await applyInferenceTemplate({
       systemPrompt: limitedPrompt.systemPrompt,
       inferenceTemplate: config.inferenceTemplate,
-      messages: limitedPrompt.inferenceMessages,
+      // A completion prompt is a single text, so the examples become its first turns
+      messages: [...(limitedPrompt.examples ?? []), ...limitedPrompt.inferenceMessages],
       chatConfig: config.chatConfig,
       prefixOption,
     });
@@ -522,6 +563,7 @@ export async function formatPrompt(config: PromptFormatterConfig): Promise<Forma
       inferenceMessages: inferencePrompt.messages,
       systemPrompt: inferencePrompt.systemPrompt,
       customStopStrings: inferencePrompt.customStopStrings,
+      statistics: limitedPrompt.statistics,
     };
   }
 
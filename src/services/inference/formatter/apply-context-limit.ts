@@ -3,12 +3,17 @@ import { countTokens } from "@/commands/inference";
 import { ChatTemplate } from "@/schema/template-chat-schema";
 import { FormattedPromptResult } from "../formatter";
 
+export interface ContextLimitStatistics {
+  systemTokens: number;
+  exampleTokens: number;
+  historyTokens: number;
+  responseTokens: number;
+  // Unpinned messages dropped for the window or max_depth, always the oldest ones
+  trimmedMessages: number;
+}
+
 interface FormattedPromptCutResult extends FormattedPromptResult {
-  statistics: {
-    systemTokens: number;
-    historyTokens: number;
-    responseTokens: number;
-  };
+  statistics: ContextLimitStatistics;
 }
 
 export function estimateTokens(text: string, padding = 32): number {
@@ -18,14 +23,16 @@ export function estimateTokens(text: string, padding = 32): number {
 export const USE_TOKENIZER = true as const;
 export const USE_ESTIMATOR = false as const;
 
-// Token count cache to avoid redundant tokenization
-const tokenCache = new Map<string, number>();
+// Token count cache to avoid redundant tokenization, keyed by the whole text
+const tokenCache = new Map<string, { count: number; lastUsed: number }>();
 
 export const getTokenCount = async (text: string, useTokenizer = false) => {
   // Check cache first
   const cacheKey = `${text}-${useTokenizer}`;
-  if (tokenCache.has(cacheKey)) {
-    return tokenCache.get(cacheKey)!;
+  const cached = tokenCache.get(cacheKey);
+  if (cached) {
+    cached.lastUsed = Date.now();
+    return cached.count;
   }
 
   // ? Tokenizer slows down the APP.
@@ -37,12 +44,41 @@ export const getTokenCount = async (text: string, useTokenizer = false) => {
   }
 
   // Cache the result
-  tokenCache.set(cacheKey, result);
+  tokenCache.set(cacheKey, { count: result, lastUsed: Date.now() });
   return result;
 };
 
 /**
- * Applies a context limit to the formatted prompt
+ * Drop the cached token counts not used for `maxIdleMs`; they are counted again on next use.
+ * Returns how many were dropped and roughly how much memory their keys held.
+ */
+export function trimTokenCache(maxIdleMs: number, now = Date.now()): { evicted: number; estimatedBytes: number } {
+  let evicted = 0;
+  let estimatedBytes = 0;
+  for (const [key, entry] of tokenCache) {
+    if (now - entry.lastUsed >= maxIdleMs) {
+      tokenCache.delete(key);
+      evicted++;
+      // UTF-16 strings
+      estimatedBytes += key.length * 2;
+    }
+  }
+  return { evicted, estimatedBytes };
+}
+
+/**
+ * Thrown when the pinned messages alone don't fit in the context window.
+ */
+export class PromptTooLargeError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = "PromptTooLarge";
+  }
+}
+
+/**
+ * Applies a context limit to the formatted prompt.
+ * Few-shot examples and pinned messages are always kept; unpinned ones are dropped oldest-first.
  * @returns The formatted prompt with the context limit applied
  */
 export async function applyContextLimit(formattedPrompt: FormattedPromptResult, chatConfig: Pick<ChatTemplate, "config" | "custom_prompts">): Promise<FormattedPromptCutResult> {
@@ -51,21 +87,39 @@ export async function applyContextLimit(formattedPrompt: FormattedPromptResult,
   const maxResponseTokens = chatConfig.config.max_tokens as number;
   const maxContextSize = (chatConfig.config.max_context as number) - maxResponseTokens;
 
-  const maxMessageTokens = maxContextSize - frozenTokens;
+  // Examples are sent with every request, so they come out of the budget like the system prompt
+  const exampleTokenCounts = await Promise.all((formattedPrompt.examples ?? []).map((example) => getTokenCount(example.text, USE_TOKENIZER)));
+  const exampleTokens = exampleTokenCounts.reduce((total, count) => total + count, 0);
+  if (exampleTokens > 0 && frozenTokens + exampleTokens > maxContextSize) {
+    throw new PromptTooLargeError(
+      `The few-shot examples need about ${exampleTokens} tokens but only ${Math.max(0, maxContextSize - frozenTokens)} are left after the system prompt. Shorten the examples or increase the context size.`,
+    );
+  }
+
+  const maxMessageTokens = maxContextSize - frozenTokens - exampleTokens;
 
   // Use a hybrid approach: estimate tokens first, then refine with tokenizer if needed
   const messagesWithEstimatedTokens = await Promise.all(
-    formattedPrompt.inferenceMessages.map(async (message) => ({
+    formattedPrompt.inferenceMessages.map(async (message, order) => ({
       ...message,
+      order,
       tokens: await getTokenCount(message.text, USE_ESTIMATOR), // Use estimator first
     })),
   );
 
+  const pinnedMessages = messagesWithEstimatedTokens.filter((message) => message.pinned);
+  const pinnedTokenCount = pinnedMessages.reduce((total, message) => total + message.tokens, 0);
+  if (pinnedMessages.length > 0 && pinnedTokenCount > maxMessageTokens) {
+    throw new PromptTooLargeError(
+      `Pinned messages need about ${pinnedTokenCount} tokens but only ${Math.max(0, maxMessageTokens)} are left for messages. Unpin some messages or increase the context size.`,
+    );
+  }
+
   // Preserve messages from tail (most recent), so reverse to process newest first
-  const reversedMessages = [...messagesWithEstimatedTokens].reverse();
+  const reversedMessages = messagesWithEstimatedTokens.filter((message) => !message.pinned).reverse();
 
   const includedMessages = [];
-  let currentTokenCount = 0;
+  let currentTokenCount = pinnedTokenCount;
   const maxDepth = chatConfig.config.max_depth as number;
 
   // First pass: Add messages using estimated tokens
@@ -83,7 +137,7 @@ export async function applyContextLimit(formattedPrompt: FormattedPromptResult,
   // Second pass (optimization): Only use tokenizer for the most recent messages (up to 3)
   // and only if we're close to the limit (within 10% margin)
   if (currentTokenCount > maxMessageTokens * 0.9) {
-    currentTokenCount = 0;
+    currentTokenCount = pinnedTokenCount;
     const recentMessagesToTokenize = includedMessages.slice(-3); // Last 3 messages
 
     for (let i = 0; i < includedMessages.length; i++) {
@@ -94,25 +148,28 @@ export async function applyContextLimit(formattedPrompt: FormattedPromptResult,
       currentTokenCount += message.tokens;
     }
 
-    // If we exceed the limit after accurate tokenization, remove messages as needed
+    // If we exceed the limit after accurate tokenization, drop the oldest unpinned messages
     while (includedMessages.length > 0 && currentTokenCount > maxMessageTokens) {
-      const removed = includedMessages.shift();
+      const removed = includedMessages.pop();
       if (removed) {
         currentTokenCount -= removed.tokens;
       }
     }
   }
 
-  // Reverse back to original order (oldest to newest)
-  const finalMessages = includedMessages.reverse().map(({ tokens, ...message }) => message);
+  // Back to original order (oldest to newest), with pinned messages in their original positions
+  const finalMessages = [...pinnedMessages, ...includedMessages].sort((a, b) => a.order - b.order).map(({ tokens, order, pinned, ...message }) => message);
 
   return {
     inferenceMessages: finalMessages,
     systemPrompt: formattedPrompt.systemPrompt,
+    examples: formattedPrompt.examples,
     statistics: {
       systemTokens: frozenTokens,
+      exampleTokens,
       historyTokens: currentTokenCount,
       responseTokens: maxResponseTokens,
+      trimmedMessages: reversedMessages.length - includedMessages.length,
     },
   };
 }
@@ -0,0 +1,46 @@
+import type { ChatMarker } from "@/schema/chat-marker-schema";
+
+type PositionedMessage = { chapter_id: string; position: number };
+
+/**
+ * The latest marker flagged `context_reset` in a chapter, only counting markers placed before `beforePosition`
+ * (the message being regenerated), so regenerating an older message keeps the history it was written with.
+ */
+export function getLatestContextReset(markers: ChatMarker[], chapterId: string, beforePosition?: number): ChatMarker | null {
+  let latest: ChatMarker | null = null;
+  for (const marker of markers) {
+    if (!marker.context_reset || marker.chapter_id !== chapterId) {
+      continue;
+    }
+    if (beforePosition !== undefined && marker.position >= beforePosition) {
+      continue;
+    }
+    if (!latest || marker.position > latest.position) {
+      latest = marker;
+    }
+  }
+  return latest;
+}
+
+/**
+ * Drops the messages placed before the latest context reset marker of their chapter.
+ * @param beforePosition - Position of the message being regenerated, if any
+ */
+export function applyContextReset<T extends PositionedMessage>(messages: T[], markers: ChatMarker[], beforePosition?: number): T[] {
+  if (!markers.some((marker) => marker.context_reset)) {
+    return messages;
+  }
+
+  const resetByChapter = new Map<string, number>();
+  for (const chapterId of new Set(messages.map((message) => message.chapter_id))) {
+    const reset = getLatestContextReset(markers, chapterId, beforePosition);
+    if (reset) {
+      resetByChapter.set(chapterId, reset.position);
+    }
+  }
+
+  return messages.filter((message) => {
+    const resetPosition = resetByChapter.get(message.chapter_id);
+    return resetPosition === undefined || message.position > resetPosition;
+  });
+}
@@ -25,44 +25,108 @@ export interface LorebookContentResponse {
 // thresholds (0.35–0.7) without making keyword presence an unconditional trigger at very high thresholds.
 export const KEYWORD_MATCH_BOOST = 0.5;
 
+export interface KeywordMatch {
+  keyword: string;
+  // Span of the match in the scanned text
+  start: number;
+  end: number;
+  text: string;
+}
+
 /**
- * Matches keywords in a given text based on entry settings.
+ * Finds the first keyword that matches the text based on entry settings.
  */
-export function matchKeywords(text: string, keywords: string[], caseSensitive: boolean, matchPartialWords: boolean): boolean {
-  if (keywords.length === 0) {
-    return false; // No keywords to match
-  }
-
-  const flags = caseSensitive ? "g" : "gi";
+export function findKeywordMatch(text: string, keywords: string[], caseSensitive: boolean, matchPartialWords: boolean): KeywordMatch | null {
+  const flags = caseSensitive ? "" : "i";
 
   for (const keyword of keywords) {
     const escapedKeyword = keyword.replace(/[.*+?^${}()|[\]\\]/g, "\\$&"); // Escape regex special chars
     const pattern = matchPartialWords ? escapedKeyword : `\\b${escapedKeyword}\\b`; // Match whole word if partial not allowed
-    const regex = new RegExp(pattern, flags);
-    if (regex.test(text)) {
-      return true; // Found a match
+    const match = new RegExp(pattern, flags).exec(text);
+    if (match) {
+      return { keyword, start: match.index, end: match.index + match[0].length, text: match[0] };
     }
   }
-  return false; // No keywords matched
+  return null; // No keywords matched
+}
+
+/**
+ * Matches keywords in a given text based on entry settings.
+ */
+export function matchKeywords(text: string, keywords: string[], caseSensitive: boolean, matchPartialWords: boolean): boolean {
+  return findKeywordMatch(text, keywords, caseSensitive, matchPartialWords) !== null;
 }
 
-function entryKeywordMatches(entry: LorebookEntry, reversedMessages: { text: string }[]): boolean {
+// messageDepth counts back from the latest message, 0 being the latest
+export type MessageKeywordMatch = KeywordMatch & { messageDepth: number };
+
+function findEntryKeywordMatch(entry: LorebookEntry, reversedMessages: { text: string }[], caseSensitive = entry.case_sensitive): MessageKeywordMatch | null {
   if (entry.keywords.length === 0) {
-    return false;
+    return null;
   }
   const scanDepth = entry.depth > 0 ? Math.min(entry.depth, reversedMessages.length) : reversedMessages.length;
-  if (scanDepth === 0) {
-    return false;
-  }
-  for (const message of reversedMessages.slice(0, scanDepth)) {
-    if (matchKeywords(message.text, entry.keywords, entry.case_sensitive, entry.match_partial_words)) {
-      return true;
+  for (let messageDepth = 0; messageDepth < scanDepth; messageDepth++) {
+    const match = findKeywordMatch(reversedMessages[messageDepth].text, entry.keywords, caseSensitive, entry.match_partial_words);
+    if (match) {
+      return { ...match, messageDepth };
     }
   }
-  return false;
+  return null;
 }
 
-export async function getLorebookContent(orderedLorebookIds: string[], budget: number, Messages: InferenceMessage[], lorebookSeparator = "\n---\n"): Promise<LorebookContentResponse> {
+export type LorebookWithEntries = Lorebook & { entries: LorebookEntry[] };
+
+export type LorebookEntryScanStatus = "inserted" | "over_budget" | "not_triggered" | "too_few_messages" | "failed_trigger_chance" | "not_reached";
+
+export interface LorebookEntryScan {
+  lorebookId: string;
+  entryId: string;
+  title: string;
+  status: LorebookEntryScanStatus;
+  trigger: "constant" | "keyword" | "similarity" | null;
+  match: MessageKeywordMatch | null;
+  // Cosine similarity to the scanned messages, for lorebooks with RAG
+  similarity: number | null;
+  // Only for entries that got past the trigger checks
+  tokens: number | null;
+}
+
+export interface LorebookBudgetStep {
+  lorebookId: string;
+  entryId: string;
+  tokens: number;
+  inserted: boolean;
+  // Why a triggered entry was left out
+  reason: "budget" | "lorebook_max_tokens" | null;
+  remainingBudget: number;
+  lorebookTokensUsed: number;
+}
+
+export interface LorebookScanReport {
+  // Every enabled entry of the lorebooks that were scanned, in lorebook order
+  entries: LorebookEntryScan[];
+  // Triggered entry ids after sorting by priority, in the order the budget is spent on them
+  order: string[];
+  budgetSteps: LorebookBudgetStep[];
+  // Case-sensitive entries whose keywords only match when case is ignored
+  nearMisses: (MessageKeywordMatch & { lorebookId: string; entryId: string })[];
+  content: LorebookContentResponse;
+}
+
+export interface LorebookScanOptions {
+  separator?: string;
+  // Chat length compared to each entry's min_chat_messages, defaults to the number of messages
+  messageCount?: number;
+  // Roll for trigger_chance, [0, 1)
+  random?: () => number;
+}
+
+/**
+ * The lorebook scanner: triggers entries against the messages, sorts them by priority and spends the
+ * token budget on them, recording each decision. Shared by inference and the lorebook sandbox.
+ */
+export async function scanLorebooks(lorebooks: LorebookWithEntries[], messages: { text: string }[], budget: number, options: LorebookScanOptions = {}): Promise<LorebookScanReport> {
+  const { separator: lorebookSeparator = "\n---\n", messageCount: messagesLength = messages.length, random = Math.random } = options;
   const response: LorebookContentResponse = {
     replacers: {
       lorebook_top: "",
@@ -70,32 +134,14 @@ export async function getLorebookContent(orderedLorebookIds: string[], budget: n
     },
     messages: [],
   };
+  const report: LorebookScanReport = { entries: [], order: [], budgetSteps: [], nearMisses: [], content: response };
 
   let currentBudget = budget;
   if (currentBudget <= 0) {
-    return response; // No budget available
-  }
-
-  // Fetch all lorebooks with their entries upfront
-  const lorebooks: (Lorebook & { entries: LorebookEntry[] })[] = [];
-  for (const id of orderedLorebookIds) {
-    // Assuming getLorebookById(id, true) fetches the lorebook AND its entries
-    const lorebook = await getLorebookById(id, true);
-    if (lorebook) {
-      // Ensure entries array exists, even if empty
-      lorebooks.push({ ...lorebook, entries: lorebook.entries || [] });
-    } else {
-      console.warn(`Lorebook with ID ${id} not found.`);
-      // Optionally, decide whether to continue or throw an error
-    }
-  }
-
-  if (lorebooks.length === 0) {
-    return response; // No valid lorebooks found or provided
+    return report; // No budget available
   }
 
-  const reversedMessages = [...Messages].reverse();
-  const messagesLength = Messages.length;
+  const reversedMessages = [...messages].reverse();
 
   for (const lorebook of lorebooks) {
     if (currentBudget <= 0) {
@@ -107,6 +153,7 @@ export async function getLorebookContent(orderedLorebookIds: string[], budget: n
     const lorebookMaxTokens = lorebook.max_tokens > 0 ? lorebook.max_tokens : Number.POSITIVE_INFINITY;
 
     const enabledEntries = lorebook.entries.filter((entry) => entry.enabled);
+    const scans = new Map<string, LorebookEntryScan>();
 
     // RAG uses lorebook.max_depth as the scan window for the query (per-entry depth is irrelevant when matching by similarity).
     let queryEmbedding: number[] | null = null;
@@ -125,15 +172,28 @@ export async function getLorebookContent(orderedLorebookIds: string[], budget: n
     }
 
     for (const entry of enabledEntries) {
+      const scan: LorebookEntryScan = { lorebookId: lorebook.id, entryId: entry.id, title: entry.comment, status: "not_triggered", trigger: null, match: null, similarity: null, tokens: null };
+      scans.set(entry.id, scan);
+      report.entries.push(scan);
+
       let triggered = false;
       if (entry.constant) {
         triggered = true;
+        scan.trigger = "constant";
       } else {
         if (messagesLength < entry.min_chat_messages) {
+          scan.status = "too_few_messages";
           continue;
         }
 
-        const keywordMatched = entryKeywordMatches(entry, reversedMessages);
+        scan.match = findEntryKeywordMatch(entry, reversedMessages);
+        const keywordMatched = scan.match !== null;
+        if (!keywordMatched && entry.case_sensitive) {
+          const nearMiss = findEntryKeywordMatch(entry, reversedMessages, false);
+          if (nearMiss) {
+            report.nearMisses.push({ ...nearMiss, lorebookId: lorebook.id, entryId: entry.id });
+          }
+        }
 
         if (queryEmbedding) {
           // Combined RAG + keyword scoring. Keywords act as an additive boost on top of cosine similarity
@@ -142,9 +202,12 @@ export async function getLorebookContent(orderedLorebookIds: string[], budget: n
           const similarity = entryVector && entryVector.length === queryEmbedding.length ? cosineSimilarity(queryEmbedding, entryVector) : 0;
           const effectiveScore = keywordMatched ? Math.min(1, similarity + KEYWORD_MATCH_BOOST) : similarity;
           triggered = effectiveScore >= lorebook.similarity_threshold;
+          scan.similarity = similarity;
+          scan.trigger = triggered ? "similarity" : null;
         } else {
           // Keyword-only path: RAG disabled or embedding failed.
           triggered = keywordMatched;
+          scan.trigger = triggered ? "keyword" : null;
         }
       }
 
@@ -153,14 +216,17 @@ export async function getLorebookContent(orderedLorebookIds: string[], budget: n
       }
 
       // Per-entry probability gate. Default is 100 (always include).
-      if (entry.trigger_chance < 100 && Math.random() * 100 >= entry.trigger_chance) {
+      if (entry.trigger_chance < 100 && random() * 100 >= entry.trigger_chance) {
+        scan.status = "failed_trigger_chance";
         continue;
       }
 
+      scan.status = "not_reached";
       candidateEntries.push(entry);
     }
 
     candidateEntries.sort((a, b) => b.priority - a.priority);
+    report.order.push(...candidateEntries.map((entry) => entry.id));
 
     for (const entry of candidateEntries) {
       if (currentBudget <= 0) {
@@ -170,11 +236,15 @@ export async function getLorebookContent(orderedLorebookIds: string[], budget: n
         break;
       }
 
+      const scan = scans.get(entry.id) as LorebookEntryScan;
       const entryTokens = estimateTokens(entry.content);
+      scan.tokens = entryTokens;
 
-      if (currentBudget >= entryTokens && lorebookBudgetConsumed + entryTokens <= lorebookMaxTokens) {
+      const inserted = currentBudget >= entryTokens && lorebookBudgetConsumed + entryTokens <= lorebookMaxTokens;
+      if (inserted) {
         currentBudget -= entryTokens;
         lorebookBudgetConsumed += entryTokens;
+        scan.status = "inserted";
 
         switch (entry.insertion_type) {
           case "lorebook_top":
@@ -194,14 +264,78 @@ export async function getLorebookContent(orderedLorebookIds: string[], budget: n
           default:
             console.warn(`Unknown insertion type: ${entry.insertion_type}`);
         }
+      } else {
+        scan.status = "over_budget";
       }
+
+      report.budgetSteps.push({
+        lorebookId: lorebook.id,
+        entryId: entry.id,
+        tokens: entryTokens,
+        inserted,
+        reason: inserted ? null : currentBudget < entryTokens ? "budget" : "lorebook_max_tokens",
+        remainingBudget: currentBudget,
+        lorebookTokensUsed: lorebookBudgetConsumed,
+      });
     }
   }
 
   // Sort inserted messages by depth before returning, if needed for specific ordering
   // response.messages.sort((a, b) => a.depth - b.depth); // Or descending? Decide based on requirement
 
-  return response;
+  return report;
+}
+
+async function loadLorebooks(orderedLorebookIds: string[]): Promise<LorebookWithEntries[]> {
+  // Fetch all lorebooks with their entries upfront
+  const lorebooks: LorebookWithEntries[] = [];
+  for (const id of orderedLorebookIds) {
+    // Assuming getLorebookById(id, true) fetches the lorebook AND its entries
+    const lorebook = await getLorebookById(id, true);
+    if (lorebook) {
+      // Ensure entries array exists, even if empty
+      lorebooks.push({ ...lorebook, entries: lorebook.entries || [] });
+    } else {
+      console.warn(`Lorebook with ID ${id} not found.`);
+      // Optionally, decide whether to continue or throw an error
+    }
+  }
+  return lorebooks;
+}
+
+export async function getLorebookContent(orderedLorebookIds: string[], budget: number, Messages: InferenceMessage[], lorebookSeparator = "\n---\n"): Promise<LorebookContentResponse> {
+  if (budget <= 0) {
+    return { replacers: { lorebook_top: "", lorebook_bottom: "" }, messages: [] }; // No budget available
+  }
+
+  const lorebooks = await loadLorebooks(orderedLorebookIds);
+  const report = await scanLorebooks(lorebooks, Messages, budget, { separator: lorebookSeparator });
+  return report.content;
+}
+
+export interface LorebookTestOptions {
+  // Token budget for all lorebooks together
+  budget?: number;
+  separator?: string;
+  // Pretend the chat is this long, for entries with min_chat_messages
+  chatLength?: number;
+  // Let entries with a trigger_chance below 100 fire as if every roll succeeded
+  ignoreTriggerChance?: boolean;
+}
+
+export const LOREBOOK_TEST_DEFAULT_BUDGET = 2048;
+
+/**
+ * Run the lorebook scanner over a sample text instead of a chat, for authoring triggers. The text is
+ * scanned as the latest message. Lorebooks of other profiles are left out.
+ */
+export async function testLorebookScan(profileId: string, lorebookIds: string[], sampleText: string, options: LorebookTestOptions = {}): Promise<LorebookScanReport> {
+  const lorebooks = (await loadLorebooks(lorebookIds)).filter((lorebook) => lorebook.profile_id === profileId);
+  return scanLorebooks(lorebooks, [{ text: sampleText }], options.budget ?? LOREBOOK_TEST_DEFAULT_BUDGET, {
+    separator: options.separator,
+    messageCount: options.chatLength,
+    random: options.ignoreTriggerChance ? () => 0 : undefined,
+  });
 }
 
 /**
@@ -0,0 +1,142 @@
+/**
+ * Dice notation shared by the {{roll:...}} macro, user messages and quick actions:
+ * - `3d6+2`: three six-sided dice plus a modifier. The dice count defaults to 1 (`d20`)
+ * - `1d6!`: exploding dice, each maximum roll adds another die
+ * - `d20 adv` / `d20 dis`: roll twice, keep the higher / lower total
+ */
+
+// Above these the expression is rejected, so a typo can't freeze the chat
+export const MAX_DICE = 100;
+export const MAX_SIDES = 1000;
+// Extra dice an exploding roll may add in total
+const MAX_EXPLOSIONS = 100;
+
+const DICE_EXPRESSION = /^(\d*)\s*d\s*(\d+)\s*(!?)\s*(?:([+-])\s*(\d+))?(?:\s+(adv|dis))?$/i;
+
+export interface DiceExpression {
+  count: number;
+  sides: number;
+  exploding: boolean;
+  modifier: number;
+  keep: "all" | "highest" | "lowest";
+}
+
+export interface DiceRoll {
+  expression: string;
+  // Every die of the kept set, explosions included
+  rolls: number[];
+  // The other set of an adv/dis roll
+  discarded?: number[];
+  modifier: number;
+  total: number;
+}
+
+export type RandomSource = () => number;
+
+/**
+ * Parse dice notation. Throws with a message fit for the user when it isn't valid.
+ */
+export function parseDiceExpression(expression: string): DiceExpression {
+  const match = expression.trim().match(DICE_EXPRESSION);
+  if (!match) {
+    throw new Error(`Invalid dice expression "${expression}", expected something like 3d6+2, d20 adv or 1d6!`);
+  }
+
+  const count = match[1] ? Number.parseInt(match[1], 10) : 1;
+  const sides = Number.parseInt(match[2], 10);
+  const exploding = match[3] === "!";
+
+  if (count < 1 || count > MAX_DICE) {
+    throw new Error(`Dice count must be between 1 and ${MAX_DICE}`);
+  }
+  if (sides < 1 || sides > MAX_SIDES) {
+    throw new Error(`Dice must have between 1 and ${MAX_SIDES} sides`);
+  }
+  if (exploding && sides === 1) {
+    throw new Error("A one-sided die can't explode");
+  }
+
+  const modifier = match[5] ? Number.parseInt(match[5], 10) * (match[4] === "-" ? -1 : 1) : 0;
+  const keep = match[6]?.toLowerCase() === "adv" ? "highest" : match[6]?.toLowerCase() === "dis" ? "lowest" : "all";
+  return { count, sides, exploding, modifier, keep };
+}
+
+/**
+ * Deterministic random source in [0, 1) (mulberry32), for reproducible rolls
+ */
+export function createSeededRandom(seed: number): RandomSource {
+  let state = seed >>> 0;
+  return () => {
+    state = (state + 0x6d2b79f5) >>> 0;
+    let t = state;
+    t = Math.imul(t ^ (t >>> 15), t | 1);
+    t ^= t + Math.imul(t ^ (t >>> 7), t | 61);
+    return ((t ^ (t >>> 14)) >>> 0) / 4294967296;
+  };
+}
+
+function rollSet(dice: DiceExpression, random: RandomSource): number[] {
+  const rolls: number[] = [];
+  let explosions = 0;
+  for (let i = 0; i < dice.count; i++) {
+    let roll = Math.floor(random() * dice.sides) + 1;
+    rolls.push(roll);
+    while (dice.exploding && roll === dice.sides && explosions < MAX_EXPLOSIONS) {
+      explosions++;
+      roll = Math.floor(random() * dice.sides) + 1;
+      rolls.push(roll);
+    }
+  }
+  return rolls;
+}
+
+const sum = (values: number[]) => values.reduce((total, value) => total + value, 0);
+
+/**
+ * Roll dice notation, returning every die and the total. Throws on invalid notation.
+ */
+export function rollDice(expression: string, random: RandomSource = Math.random): DiceRoll {
+  const dice = parseDiceExpression(expression);
+
+  let rolls = rollSet(dice, random);
+  let discarded: number[] | undefined;
+  if (dice.keep !== "all") {
+    const other = rollSet(dice, random);
+    const otherIsBetter = dice.keep === "highest" ? sum(other) > sum(rolls) : sum(other) < sum(rolls);
+    [rolls, discarded] = otherIsBetter ? [other, rolls] : [rolls, other];
+  }
+
+  return {
+    expression: expression.trim(),
+    rolls,
+    ...(discarded && { discarded }),
+    modifier: dice.modifier,
+    total: sum(rolls) + dice.modifier,
+  };
+}
+
+/**
+ * One-line summary of a roll, e.g. "🎲 2d6+1: [3, 5] + 1 = 9"
+ */
+export function formatDiceRoll(roll: DiceRoll): string {
+  const modifier = roll.modifier > 0 ? ` + ${roll.modifier}` : roll.modifier < 0 ? ` - ${-roll.modifier}` : "";
+  const discarded = roll.discarded ? ` (discarded [${roll.discarded.join(", ")}])` : "";
+  return `🎲 ${roll.expression}: [${roll.rolls.join(", ")}]${discarded}${modifier} = ${roll.total}`;
+}
+
+/**
+ * Replace every {{roll:...}} in the text by its total. Invalid expressions are left as written.
+ */
+export function resolveDiceMacros(text: string, random: RandomSource = Math.random): { text: string; rolls: DiceRoll[] } {
+  const rolls: DiceRoll[] = [];
+  const resolved = text.replace(/\{\{roll:([^}]+)\}\}/g, (match, expression: string) => {
+    try {
+      const roll = rollDice(expression, random);
+      rolls.push(roll);
+      return roll.total.toString();
+    } catch {
+      return match;
+    }
+  });
+  return { text: resolved, rolls };
+}
@@ -63,6 +63,8 @@ export function mergeSubsequentMessages(messages: InferenceMessage[], separator
   const result: InferenceMessage[] = [];
   let currentRole: string | null = null;
   let currentTexts: string[] = [];
+  // A merged message is pinned when any of its parts was
+  let currentPinned = false;
 
   // Process each message
   for (const message of messages) {
@@ -73,17 +75,20 @@ export function mergeSubsequentMessages(messages: InferenceMessage[], separator
         result.push({
           role: currentRole as any, // Type assertion to handle any role
           text: currentTexts.join(separator),
+          ...(currentPinned && { pinned: true }),
         });
       }
 
       // Start new role tracking
       currentRole = message.role;
       currentTexts = message.text ? [message.text] : [];
+      currentPinned = !!message.pinned;
     } else {
       // Same role, append text
       if (message.text) {
         currentTexts.push(message.text);
       }
+      currentPinned = currentPinned || !!message.pinned;
     }
   }
 
@@ -92,6 +97,7 @@ export function mergeSubsequentMessages(messages: InferenceMessage[], separator
     result.push({
       role: currentRole as any, // Type assertion to handle any role
       text: currentTexts.join(separator),
+      ...(currentPinned && { pinned: true }),
     });
   }
 
@@ -1,37 +1,50 @@
 import { InferenceMessage } from "@/schema/inference-engine-schema";
 import { FormattedPromptResult, PromptFormatterConfig } from "../formatter";
 import { applyCensorship } from "./apply-censorship";
+import { resolveDiceMacros } from "./dice";
 
 /**
  * Applies placeholder replacements to a given text string based on the configuration.
  */
 export function applyTextReplacements(text: string, config: PromptFormatterConfig["chatConfig"]): string {
-  const { character, user_character, chapter, extra } = config || {};
+  const { character, user_character, chapter, chat, extra } = config || {};
   let processedText = structuredClone(text);
 
+  // SillyTavern treats {{char}} and {{user}} case-insensitively, and cards rely on it
   if (character?.name) {
-    processedText = processedText.replace(/\{\{char\}\}/g, character.name);
+    processedText = processedText.replace(/\{\{char\}\}/gi, character.name);
     processedText = processedText.replace(/\{\{character\.name\}\}/g, character.name);
   }
   if (user_character?.name) {
-    processedText = processedText.replace(/\{\{user\}\}/g, user_character.name);
+    processedText = processedText.replace(/\{\{user\}\}/gi, user_character.name);
     processedText = processedText.replace(/\{\{user\.name\}\}/g, user_character.name);
   }
   if (character?.type === "character") {
     const personality = (character?.custom as any)?.personality;
     if (personality) {
       processedText = processedText.replace(/\{\{character\.personality\}\}/g, personality);
+      processedText = processedText.replace(/\{\{personality\}\}/g, personality);
     }
   }
   if (user_character?.custom?.personality) {
     processedText = processedText.replace(/\{\{user\.personality\}\}/g, user_character.custom.personality);
+    processedText = processedText.replace(/\{\{persona\}\}/g, user_character.custom.personality);
   }
   if (chapter?.scenario) {
     processedText = processedText.replace(/\{\{chapter\.scenario\}\}/g, chapter.scenario);
+    processedText = processedText.replace(/\{\{scenario\}\}/g, chapter.scenario);
   }
   if (chapter?.title) {
     processedText = processedText.replace(/\{\{chapter\.title\}\}/g, chapter.title);
   }
+  if (chat?.name) {
+    processedText = processedText.replace(/\{\{chat\.name\}\}/g, chat.name);
+  }
+  // Empty until the chat's first synopsis refresh
+  if (chat) {
+    processedText = processedText.replace(/\{\{synopsis\}\}/g, () => chat.synopsis ?? "");
+  }
+  processedText = processedText.replace(/\{\{newline\}\}/g, "\n").replace(/\{\{noop\}\}/g, "");
 
   // Process extra replacements
   if (extra && typeof extra === "object") {
@@ -73,6 +86,21 @@ function normalizeConfig(config: PromptFormatterConfig["chatConfig"]): PromptFor
   return newconfig;
 }
 
+/**
+ * SillyTavern's random list, one option picked per render:
+ * - {{random:red,green,blue}}
+ * - {{random::red, with a comma::green}} - "::" separates options that contain commas
+ */
+export function replaceRandomListPattern(text: string): string {
+  return text.replace(/\{\{random:(:?)([^{}]*)\}\}/gi, (match, doubleColon, content) => {
+    const options = (doubleColon ? content.split("::") : content.split(",")).map((option: string) => option.trim());
+    if (options.length === 0 || (options.length === 1 && !options[0])) {
+      return match;
+    }
+    return options[Math.floor(Math.random() * options.length)];
+  });
+}
+
 /**
  * Random Pattern is a text embraced by a single bracket:
  * Using this script, the prompt:
@@ -120,91 +148,170 @@ export function replaceRandomPattern(text: string): string {
 
 /**
  * A dice Roll Pattern is a text started with "roll:" embraced by a single bracket:
- * - {{roll:1d20+5}}
- * It must roll a dice with the number of sides and modifier.
- * The result will be a random number between 1 and the number of sides, plus the modifier.
+ * - {{roll:1d20+5}}, {{roll:d20 adv}}, {{roll:4d6!}}
+ * It is replaced by the total of the roll, see `dice.ts` for the notation.
+ * Invalid expressions are left as written.
  * @param text
  */
 export function replaceDiceRollPattern(text: string): string {
-  // Regular expression to match dice roll patterns like {{roll:XdY+Z}} or {{roll:XdY-Z}} or {{roll:XdY}}
-  const diceRollRegex = /\{\{roll:([^}]+)\}\}/g;
-
-  return text.replace(diceRollRegex, (match, rollExpression) => {
-    try {
-      // Parse the dice roll expression (e.g., "1d20+5", "2d6-1", "1d100")
-      // Updated regex to handle optional whitespace around components
-      const dicePattern = /^\s*(\d+)\s*d\s*(\d+)\s*([+-]\s*\d+)?\s*$/i;
-      const diceMatch = rollExpression.trim().match(dicePattern);
-
-      if (!diceMatch) {
-        // Invalid dice notation, return original text
-        return match;
-      }
+  return resolveDiceMacros(text).text;
+}
 
-      const numDice = Number.parseInt(diceMatch[1], 10);
-      const numSides = Number.parseInt(diceMatch[2], 10);
-      // Handle modifier with potential whitespace by removing all spaces before parsing
-      const modifier = diceMatch[3] ? Number.parseInt(diceMatch[3].replace(/\s/g, ""), 10) : 0;
+export interface DateTimePatternOptions {
+  // BCP 47 locale for names and default formats. Undefined keeps en-US, empty uses the system locale
+  locale?: string;
+  // IANA time zone, empty or invalid uses the system time zone
+  timeZone?: string;
+  // Token formats overriding {{date}} and {{time}}
+  dateFormat?: string;
+  timeFormat?: string;
+}
 
-      // Validate dice parameters
-      if (numDice <= 0 || numDice > 100 || numSides <= 0 || numSides > 1000) {
-        // Invalid parameters, return original text
-        return match;
-      }
+// Longest tokens first so "MMMM" isn't read as "MM" + "MM". [text] is kept literally
+const DATE_FORMAT_TOKENS = /\[([^\]]*)\]|YYYY|YY|MMMM|MMM|MM|M|DD|D|dddd|ddd|HH|H|hh|h|mm|ss|A/g;
 
-      // Roll the dice
-      let total = 0;
-      for (let i = 0; i < numDice; i++) {
-        total += Math.floor(Math.random() * numSides) + 1;
-      }
+function resolveTimeZone(timeZone?: string): string {
+  const systemTimeZone = Intl.DateTimeFormat().resolvedOptions().timeZone;
+  if (!timeZone) {
+    return systemTimeZone;
+  }
+  try {
+    new Intl.DateTimeFormat("en-US", { timeZone });
+    return timeZone;
+  } catch {
+    return systemTimeZone;
+  }
+}
 
-      // Apply modifier
-      const finalResult = total + modifier;
+function getZonedParts(now: Date, timeZone: string) {
+  const parts = new Intl.DateTimeFormat("en-US", {
+    timeZone,
+    year: "numeric",
+    month: "2-digit",
+    day: "2-digit",
+    hour: "2-digit",
+    minute: "2-digit",
+    second: "2-digit",
+    hourCycle: "h23",
+  }).formatToParts(now);
+  const part = (type: Intl.DateTimeFormatPartTypes) => parts.find((p) => p.type === type)?.value ?? "";
+
+  return { year: part("year"), month: part("month"), day: part("day"), hour: part("hour"), minute: part("minute"), second: part("second") };
+}
 
-      return finalResult.toString();
-    } catch (error) {
-      // If parsing fails, return the original match
-      return match;
+/**
+ * Formats a date with tokens: YYYY, YY, MMMM, MMM, MM, M, DD, D, dddd, ddd, HH, H, hh, h, mm, ss, A.
+ * Text inside [brackets] is kept as is.
+ */
+export function formatDateTokens(now: Date, format: string, locale: string | undefined, timeZone: string): string {
+  const { year, month, day, hour, minute, second } = getZonedParts(now, timeZone);
+  const hour24 = Number(hour);
+  const hour12 = hour24 % 12 || 12;
+  const name = (options: Intl.DateTimeFormatOptions) => now.toLocaleDateString(locale, { ...options, timeZone });
+
+  return format.replace(DATE_FORMAT_TOKENS, (token, literal) => {
+    if (literal !== undefined) {
+      return literal;
+    }
+    switch (token) {
+      case "YYYY":
+        return year;
+      case "YY":
+        return year.slice(-2);
+      case "MMMM":
+        return name({ month: "long" });
+      case "MMM":
+        return name({ month: "short" });
+      case "MM":
+        return month;
+      case "M":
+        return String(Number(month));
+      case "DD":
+        return day;
+      case "D":
+        return String(Number(day));
+      case "dddd":
+        return name({ weekday: "long" });
+      case "ddd":
+        return name({ weekday: "short" });
+      case "HH":
+        return hour;
+      case "H":
+        return String(hour24);
+      case "hh":
+        return String(hour12).padStart(2, "0");
+      case "h":
+        return String(hour12);
+      case "mm":
+        return minute;
+      case "ss":
+        return second;
+      case "A":
+        return hour24 < 12 ? "AM" : "PM";
+      default:
+        return token;
     }
   });
 }
 
 /**
  * Replaces date and time patterns with current date/time values:
- * - {{time}} - the current time (12-hour format with AM/PM)
- * - {{date}} - the current date (localized format)
+ * - {{time}} - the current time (12-hour format with AM/PM, or options.timeFormat)
+ * - {{date}} - the current date (localized format, or options.dateFormat)
  * - {{weekday}} - the current weekday name
  * - {{isotime}} - the current ISO time (24-hour clock, HH:MM:SS)
  * - {{isodate}} - the current ISO date (YYYY-MM-DD)
+ * - {{timezone}} - the IANA time zone the values are expressed in
+ * Values are computed from `now` on every call, so each request renders its own time.
  * @param text
  */
-export function replaceDateTimePattern(text: string, now = new Date()): string {
+export function replaceDateTimePattern(text: string, now = new Date(), options: DateTimePatternOptions = {}): string {
+  if (!text.includes("{{")) {
+    return text;
+  }
+
+  const locale = options.locale === undefined ? "en-US" : options.locale || undefined;
+  const timeZone = resolveTimeZone(options.timeZone);
+  const { year, month, day, hour, minute, second } = getZonedParts(now, timeZone);
+
   // Define all date/time patterns and their replacements
   const patterns: Record<string, string> = {
     // Current time in 12-hour format with AM/PM
-    "{{time}}": now.toLocaleTimeString("en-US", {
-      hour: "numeric",
-      minute: "2-digit",
-      hour12: true,
-    }),
+    "{{time}}": options.timeFormat
+      ? formatDateTokens(now, options.timeFormat, locale, timeZone)
+      : now
+          .toLocaleTimeString(locale, {
+            hour: "numeric",
+            minute: "2-digit",
+            hour12: true,
+            timeZone,
+          })
+          // Newer ICU versions separate AM/PM with a narrow no-break space
+          .replace(/\u202f/g, " "),
 
     // Current date in localized format
-    "{{date}}": now.toLocaleDateString("en-US", {
-      year: "numeric",
-      month: "long",
-      day: "numeric",
-    }),
+    "{{date}}": options.dateFormat
+      ? formatDateTokens(now, options.dateFormat, locale, timeZone)
+      : now.toLocaleDateString(locale, {
+          year: "numeric",
+          month: "long",
+          day: "numeric",
+          timeZone,
+        }),
 
     // Current weekday name
-    "{{weekday}}": now.toLocaleDateString("en-US", {
+    "{{weekday}}": now.toLocaleDateString(locale, {
       weekday: "long",
+      timeZone,
     }),
 
     // Current ISO time (24-hour clock)
-    "{{isotime}}": now.toTimeString().split(" ")[0], // HH:MM:SS format
+    "{{isotime}}": `${hour}:${minute}:${second}`,
 
     // Current ISO date (YYYY-MM-DD)
-    "{{isodate}}": now.toISOString().split("T")[0], // YYYY-MM-DD format
+    "{{isodate}}": `${year}-${month}-${day}`,
+
+    "{{timezone}}": timeZone,
   };
 
   let processedText = text;
@@ -220,6 +327,35 @@ export function replaceDateTimePattern(text: string, now = new Date()): string {
   return processedText;
 }
 
+const NOTE_PATTERN = /\{\{note:([^{}]+)\}\}/g;
+
+/**
+ * Titles referenced by {{note:title}} in the given texts, so the notes can be loaded before rendering
+ */
+export function findNoteReferences(texts: (string | undefined)[]): string[] {
+  const titles = new Set<string>();
+  for (const text of texts) {
+    for (const match of text?.matchAll(NOTE_PATTERN) ?? []) {
+      titles.add(match[1].trim());
+    }
+  }
+  return [...titles];
+}
+
+/**
+ * A note pattern inserts the content of a profile note by title:
+ * - {{note:Plot outline}}
+ * Notes that weren't loaded (unknown titles) leave the pattern untouched.
+ * @param text
+ * @param notes Note contents by title
+ */
+export function replaceNotePattern(text: string, notes: Record<string, string> | undefined): string {
+  if (!notes) {
+    return text;
+  }
+  return text.replace(NOTE_PATTERN, (match, title: string) => notes[title.trim()] ?? match);
+}
+
 /**
  * Removes comment/note patterns from text:
  * - {{// this is a note}} - removes the entire pattern including the comment
@@ -236,6 +372,21 @@ export function replaceCommentPattern(text: string): string {
   return text.replace(commentRegex, "");
 }
 
+// Stands in for "{{" in text that must reach the model as written, see protectMacros
+const MACRO_GUARD = "\uE000";
+
+/**
+ * Hide the macros of a text from replaceTextPlaceholders, e.g. chat messages when the format
+ * template turns off macros in messages. restoreMacros puts them back once the prompt is rendered.
+ */
+export function protectMacros(text: string): string {
+  return text.replaceAll("{{", MACRO_GUARD);
+}
+
+export function restoreMacros(text: string): string {
+  return text.replaceAll(MACRO_GUARD, "{{");
+}
+
 /**
  * Replace placeholder text in a string
  * Alternative to replaceTextPlaceholders for strings
@@ -252,20 +403,23 @@ export function replaceStringPlaceholders(text: string, config: PromptFormatterC
  * Replace placeholder text in messages and system prompt
  */
 export function replaceTextPlaceholders(messages: InferenceMessage[], systemPrompt: string | undefined, config: PromptFormatterConfig["chatConfig"]): FormattedPromptResult {
-  const { character, user_character, chapter, extra, censorship } = config || {};
+  const { character, user_character, chapter, chat, extra, censorship, dateTime, notes } = config || {};
 
   // Skip if no replacements needed
-  if (!character && !user_character && !chapter && !extra && !censorship) {
+  if (!character && !user_character && !chapter && !chat && !extra && !censorship && !dateTime && !notes) {
     return { inferenceMessages: messages, systemPrompt };
   }
 
   const normalizedConfig = normalizeConfig(config);
+  // Taken once per render so every macro in the prompt agrees on the same instant
+  const now = new Date();
 
   const processText = (text: string): string => {
-    const withReplacements = applyTextReplacements(text, normalizedConfig);
-    const withRandomPattern = replaceRandomPattern(withReplacements);
+    // Notes go first so the placeholders inside them are rendered too
+    const withReplacements = applyTextReplacements(replaceNotePattern(text, notes), normalizedConfig);
+    const withRandomPattern = replaceRandomPattern(replaceRandomListPattern(withReplacements));
     const withDiceRolls = replaceDiceRollPattern(withRandomPattern);
-    const withDateTimePattern = replaceDateTimePattern(withDiceRolls);
+    const withDateTimePattern = replaceDateTimePattern(withDiceRolls, now, dateTime);
     const withCommentPattern = replaceCommentPattern(withDateTimePattern);
     return applyCensorship(withCommentPattern, censorship?.words || []);
   };
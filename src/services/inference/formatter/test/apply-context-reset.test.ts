@@ -0,0 +1,42 @@
+import { describe, expect, it } from "vitest";
+import type { ChatMarker } from "@/schema/chat-marker-schema";
+import { applyContextReset, getLatestContextReset } from "../apply-context-reset";
+
+const CHAPTER = "chapter-1";
+const OTHER_CHAPTER = "chapter-2";
+
+const marker = (id: string, position: number, context_reset = true, chapter_id = CHAPTER): ChatMarker => ({
+  id,
+  chat_id: "chat-1",
+  chapter_id,
+  title: id,
+  position,
+  color: null,
+  context_reset,
+  created_at: new Date(),
+  updated_at: new Date(),
+});
+
+const messages = [100, 200, 300, 400].map((position) => ({ id: `m${position}`, chapter_id: CHAPTER, position }));
+
+describe("applyContextReset", () => {
+  it("keeps every message without reset markers", () => {
+    expect(applyContextReset(messages, [marker("scene", 200, false)])).toBe(messages);
+  });
+
+  it("drops the messages up to the latest reset marker", () => {
+    const result = applyContextReset(messages, [marker("first", 100), marker("second", 200), marker("plain", 300, false)]);
+    expect(result.map((message) => message.position)).toEqual([300, 400]);
+  });
+
+  it("only counts markers of the message's chapter", () => {
+    expect(applyContextReset(messages, [marker("elsewhere", 300, true, OTHER_CHAPTER)])).toEqual(messages);
+  });
+
+  it("ignores resets placed after the regenerated message", () => {
+    const markers = [marker("early", 100), marker("late", 300)];
+    expect(applyContextReset(messages, markers, 300).map((message) => message.position)).toEqual([200, 300, 400]);
+    expect(getLatestContextReset(markers, CHAPTER, 300)?.id).toBe("early");
+    expect(getLatestContextReset(markers, CHAPTER, 100)).toBeNull();
+  });
+});
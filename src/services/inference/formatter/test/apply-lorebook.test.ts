@@ -0,0 +1,113 @@
+import { describe, expect, it, vi } from "vitest";
+import type { LorebookEntry } from "@/schema/lorebook-schema";
+import { getLorebookById } from "@/services/lorebook-service";
+import { estimateTokens } from "../apply-context-limit";
+import { findKeywordMatch, type LorebookWithEntries, scanLorebooks, testLorebookScan } from "../apply-lorebook";
+
+vi.mock("@/commands/inference", () => ({ countTokens: vi.fn() }));
+vi.mock("@/services/embedding-service", () => ({ embedText: vi.fn() }));
+vi.mock("@/services/lorebook-indexing-service", () => ({ parseStoredVector: vi.fn() }));
+vi.mock("@/services/lorebook-service", () => ({ getLorebookById: vi.fn() }));
+
+const PROFILE = "6f1c2d3e-4b5a-4c6d-8e7f-9a0b1c2d3e4f";
+
+const entry = (id: string, overrides: Partial<LorebookEntry> = {}): LorebookEntry => ({
+  id,
+  lorebook_id: "book",
+  enabled: true,
+  comment: id,
+  content: `About ${id}`,
+  insertion_type: "lorebook_top",
+  depth: 1,
+  trigger_chance: 100,
+  priority: 100,
+  constant: false,
+  keywords: [id],
+  case_sensitive: false,
+  match_partial_words: true,
+  min_chat_messages: 1,
+  extra: {},
+  created_at: new Date(0),
+  updated_at: new Date(0),
+  ...overrides,
+});
+
+const lorebook = (entries: LorebookEntry[], overrides: Partial<LorebookWithEntries> = {}): LorebookWithEntries => ({
+  id: "book",
+  profile_id: PROFILE,
+  favorite: false,
+  name: "World",
+  description: null,
+  category: "world",
+  tags: [],
+  allow_recursion: false,
+  max_recursion_depth: 25,
+  max_depth: 25,
+  max_tokens: 0,
+  group_keys: [],
+  rag_enabled: false,
+  embedding_model_id: null,
+  similarity_threshold: 0.7,
+  extra: {},
+  created_at: new Date(0),
+  updated_at: new Date(0),
+  entries,
+  ...overrides,
+});
+
+describe("findKeywordMatch", () => {
+  it("returns the span of the first matching keyword", () => {
+    expect(findKeywordMatch("The Old Tower stands", ["castle", "tower"], false, true)).toEqual({ keyword: "tower", start: 8, end: 13, text: "Tower" });
+    expect(findKeywordMatch("towers", ["tower"], false, false)).toBeNull();
+    expect(findKeywordMatch("Tower", ["tower"], true, true)).toBeNull();
+  });
+});
+
+describe("scanLorebooks", () => {
+  it("explains triggers, priority order and budget spending", async () => {
+    const book = lorebook([
+      entry("castle", { priority: 10 }),
+      entry("dragon", { priority: 50, content: "x ".repeat(200) }),
+      entry("forest"),
+      entry("rules", { constant: true, keywords: [], priority: 1 }),
+    ]);
+    const budget = estimateTokens("About castle") + estimateTokens("About rules");
+
+    const report = await scanLorebooks([book], [{ text: "A dragon circles the castle" }], budget);
+
+    expect(report.order).toEqual(["dragon", "castle", "rules"]);
+    expect(report.entries.find((scan) => scan.entryId === "forest")).toMatchObject({ status: "not_triggered", trigger: null });
+    expect(report.entries.find((scan) => scan.entryId === "castle")).toMatchObject({ status: "inserted", trigger: "keyword", match: { keyword: "castle", start: 21, messageDepth: 0 } });
+    expect(report.budgetSteps.map((step) => [step.entryId, step.inserted, step.reason])).toEqual([
+      ["dragon", false, "budget"],
+      ["castle", true, null],
+      ["rules", true, null],
+    ]);
+    expect(report.budgetSteps[2].remainingBudget).toBe(0);
+    expect(report.content.replacers.lorebook_top).toBe("About rules\n---\nAbout castle");
+  });
+
+  it("reports case-sensitive keywords that only match when case is ignored", async () => {
+    const report = await scanLorebooks([lorebook([entry("Mira", { case_sensitive: true })])], [{ text: "mira waves" }], 1000);
+    expect(report.entries[0].status).toBe("not_triggered");
+    expect(report.nearMisses).toEqual([{ lorebookId: "book", entryId: "Mira", keyword: "Mira", start: 0, end: 4, text: "mira", messageDepth: 0 }]);
+  });
+
+  it("follows the chat length and trigger chance it is given", async () => {
+    const book = lorebook([entry("late", { min_chat_messages: 5 }), entry("rare", { trigger_chance: 10 })]);
+    const report = await scanLorebooks([book], [{ text: "late rare" }], 1000, { random: () => 0.5 });
+    expect(report.entries.map((scan) => scan.status)).toEqual(["too_few_messages", "failed_trigger_chance"]);
+
+    const longChat = await scanLorebooks([book], [{ text: "late rare" }], 1000, { messageCount: 5, random: () => 0 });
+    expect(longChat.entries.map((scan) => scan.status)).toEqual(["inserted", "inserted"]);
+  });
+});
+
+describe("testLorebookScan", () => {
+  it("only scans lorebooks of the profile", async () => {
+    vi.mocked(getLorebookById).mockImplementation(async (id: string) => lorebook([entry("castle")], { id, profile_id: id === "mine" ? PROFILE : "someone-else" }) as any);
+    const report = await testLorebookScan(PROFILE, ["mine", "theirs"], "the castle");
+    expect(report.entries.map((scan) => scan.lorebookId)).toEqual(["mine"]);
+    expect(report.content.replacers.lorebook_top).toBe("About castle");
+  });
+});
@@ -1,6 +1,7 @@
 import { afterEach, describe, expect, it, vi } from "vitest";
+import { countTokens } from "@/commands/inference";
 import { InferenceMessage } from "@/schema/inference-engine-schema";
-import { applyContextLimit, estimateTokens } from "../apply-context-limit";
+import { applyContextLimit, estimateTokens, getTokenCount, PromptTooLargeError, trimTokenCache } from "../apply-context-limit";
 
 // Mock the countTokens function
 vi.mock("@/commands/inference", () => ({
@@ -50,8 +51,10 @@ describe("applyContextLimit", () => {
       systemPrompt: "System prompt",
       statistics: {
         systemTokens: 66,
+        exampleTokens: 0,
         historyTokens: 164,
         responseTokens: 180,
+        trimmedMessages: 0,
       },
     });
   });
@@ -74,6 +77,7 @@ describe("applyContextLimit", () => {
     expect(result.statistics.systemTokens).toBe(65);
     expect(result.statistics.historyTokens).toBe(72);
     expect(result.statistics.responseTokens).toBe(50);
+    expect(result.statistics.trimmedMessages).toBe(2);
     expect(result.systemPrompt).toBe("Sys");
   });
 
@@ -94,6 +98,68 @@ describe("applyContextLimit", () => {
     expect(result.inferenceMessages[1].text).toBe("Message 5");
   });
 
+  it("Should keep pinned messages and trim unpinned ones oldest-first", async () => {
+    const messages: InferenceMessage[] = [
+      { role: "user", text: "Msg 1 User", pinned: true },
+      { role: "assistant", text: "Msg 2 Assistant" },
+      { role: "user", text: "Msg 3 User" },
+      { role: "assistant", text: "Msg 4 Assistant" },
+    ];
+
+    const result = await applyContextLimit({ inferenceMessages: messages, systemPrompt: "Sys" }, { config: { max_context: 200, max_tokens: 50, max_depth: 100 }, custom_prompts: [] });
+
+    expect(result.inferenceMessages.map((message) => message.text)).toEqual(["Msg 1 User", "Msg 4 Assistant"]);
+    expect(result.inferenceMessages[0]).not.toHaveProperty("pinned");
+  });
+
+  it("Should keep pinned messages beyond max_depth", async () => {
+    const messages: InferenceMessage[] = Array.from({ length: 5 }, (_, i) => ({
+      role: i % 2 === 0 ? "user" : "assistant",
+      text: `Message ${i + 1}`,
+      pinned: i === 0,
+    }));
+
+    const result = await applyContextLimit({ inferenceMessages: messages, systemPrompt: "Sys" }, { config: { max_context: 1000, max_tokens: 50, max_depth: 2 }, custom_prompts: [] });
+
+    expect(result.inferenceMessages.map((message) => message.text)).toEqual(["Message 1", "Message 4", "Message 5"]);
+  });
+
+  it("Should throw PromptTooLarge when pinned messages alone exceed the window", async () => {
+    const messages: InferenceMessage[] = [
+      { role: "user", text: "A long pinned character sheet ".repeat(20), pinned: true },
+      { role: "assistant", text: "Reply" },
+    ];
+
+    await expect(
+      applyContextLimit({ inferenceMessages: messages, systemPrompt: "Sys" }, { config: { max_context: 200, max_tokens: 50, max_depth: 100 }, custom_prompts: [] }),
+    ).rejects.toBeInstanceOf(PromptTooLargeError);
+  });
+
+  it("Should keep few-shot examples and take their tokens out of the history budget", async () => {
+    const messages: InferenceMessage[] = Array.from({ length: 4 }, (_, i) => ({ role: i % 2 === 0 ? "user" : "assistant", text: `Message ${i + 1}` }));
+    const examples: InferenceMessage[] = [
+      { role: "user", text: "Example question ".repeat(10) },
+      { role: "assistant", text: "Example answer" },
+    ];
+    const config = { config: { max_context: 360, max_tokens: 50, max_depth: 100 }, custom_prompts: [] };
+
+    const withoutExamples = await applyContextLimit({ inferenceMessages: messages, systemPrompt: "Sys" }, config);
+    const withExamples = await applyContextLimit({ inferenceMessages: messages, systemPrompt: "Sys", examples }, config);
+
+    expect(withoutExamples.inferenceMessages).toHaveLength(4);
+    expect(withExamples.examples).toEqual(examples);
+    expect(withExamples.inferenceMessages.length).toBeLessThan(4);
+    expect(withExamples.inferenceMessages.at(-1)?.text).toBe("Message 4");
+  });
+
+  it("Should throw PromptTooLarge when the examples alone exceed the window", async () => {
+    const examples: InferenceMessage[] = [{ role: "user", text: "A very long example ".repeat(40) }];
+
+    await expect(
+      applyContextLimit({ inferenceMessages: [{ role: "user", text: "Hi" }], systemPrompt: "Sys", examples }, { config: { max_context: 200, max_tokens: 50, max_depth: 100 }, custom_prompts: [] }),
+    ).rejects.toBeInstanceOf(PromptTooLargeError);
+  });
+
   // Clean up mocks after each test
   afterEach(() => {
     vi.clearAllMocks();
@@ -133,3 +199,24 @@ describe("applyContextLimit", () => {
   //   expect(result.inferenceMessages).toHaveLength(0); // No messages should be included due to token limits
   // });
 });
+
+describe("trimTokenCache", () => {
+  it("counts an evicted text again on next use", async () => {
+    const text = "A text only this test counts.";
+    vi.mocked(countTokens).mockClear();
+
+    const first = await getTokenCount(text, true);
+    await getTokenCount(text, true);
+    expect(countTokens).toHaveBeenCalledTimes(1);
+
+    // Still in use: kept
+    expect(trimTokenCache(60_000).evicted).toBe(0);
+
+    const trimmed = trimTokenCache(60_000, Date.now() + 120_000);
+    expect(trimmed.evicted).toBeGreaterThanOrEqual(1);
+    expect(trimmed.estimatedBytes).toBeGreaterThan(0);
+
+    expect(await getTokenCount(text, true)).toBe(first);
+    expect(countTokens).toHaveBeenCalledTimes(2);
+  });
+});
@@ -0,0 +1,73 @@
+import { describe, expect, it } from "vitest";
+import { createSeededRandom, formatDiceRoll, parseDiceExpression, resolveDiceMacros, rollDice } from "../dice";
+
+// Plays back the given [0, 1) values in order
+const sequence = (...values: number[]) => {
+  let index = 0;
+  return () => values[index++ % values.length];
+};
+
+describe("parseDiceExpression", () => {
+  it("parses standard notation", () => {
+    expect(parseDiceExpression("3d6+2")).toEqual({ count: 3, sides: 6, exploding: false, modifier: 2, keep: "all" });
+    expect(parseDiceExpression(" 2D10 - 1 ")).toEqual({ count: 2, sides: 10, exploding: false, modifier: -1, keep: "all" });
+    expect(parseDiceExpression("d20")).toMatchObject({ count: 1, sides: 20 });
+  });
+
+  it("parses exploding dice and advantage", () => {
+    expect(parseDiceExpression("4d6!")).toMatchObject({ count: 4, exploding: true });
+    expect(parseDiceExpression("d20 adv")).toMatchObject({ keep: "highest" });
+    expect(parseDiceExpression("1d20+5 DIS")).toMatchObject({ modifier: 5, keep: "lowest" });
+  });
+
+  it("rejects invalid notation", () => {
+    for (const expression of ["", "abc", "1d", "d", "1d20+", "1d20+abc", "1d20+5+3", "1d20adv", "1d20 adv dis", "2x6", "-1d6"]) {
+      expect(() => parseDiceExpression(expression), expression).toThrow(/Invalid dice expression/);
+    }
+  });
+
+  it("rejects out of range dice", () => {
+    expect(() => parseDiceExpression("0d6")).toThrow(/Dice count/);
+    expect(() => parseDiceExpression("101d6")).toThrow(/Dice count/);
+    expect(() => parseDiceExpression("1d0")).toThrow(/sides/);
+    expect(() => parseDiceExpression("1d1001")).toThrow(/sides/);
+    expect(() => parseDiceExpression("1d1!")).toThrow(/explode/);
+  });
+});
+
+describe("rollDice", () => {
+  it("returns every die and the total", () => {
+    // 0.0 -> 1, 0.5 -> 4, 0.99 -> 6 on a d6
+    expect(rollDice("3d6+2", sequence(0, 0.5, 0.99))).toEqual({ expression: "3d6+2", rolls: [1, 4, 6], modifier: 2, total: 13 });
+  });
+
+  it("adds a die for each maximum roll of exploding dice", () => {
+    expect(rollDice("2d6!", sequence(0.99, 0.99, 0.2, 0.5)).rolls).toEqual([6, 6, 2, 4]);
+  });
+
+  it("keeps the better or worse set with adv and dis", () => {
+    expect(rollDice("d20 adv", sequence(0.1, 0.9))).toMatchObject({ rolls: [19], discarded: [3], total: 19 });
+    expect(rollDice("d20 dis", sequence(0.1, 0.9))).toMatchObject({ rolls: [3], discarded: [19], total: 3 });
+  });
+
+  it("is reproducible with a seeded random source", () => {
+    const first = rollDice("10d20", createSeededRandom(42));
+    const second = rollDice("10d20", createSeededRandom(42));
+
+    expect(second).toEqual(first);
+    expect(first.rolls.every((roll) => roll >= 1 && roll <= 20)).toBe(true);
+  });
+
+  it("formats the roll for the chat", () => {
+    expect(formatDiceRoll(rollDice("2d6-1", sequence(0.2, 0.5)))).toBe("🎲 2d6-1: [2, 4] - 1 = 5");
+  });
+});
+
+describe("resolveDiceMacros", () => {
+  it("replaces the macros and reports the rolls in order", () => {
+    const result = resolveDiceMacros("Hit {{roll:d20+3}}, damage {{roll:2d6}}, {{roll:oops}}", sequence(0.5, 0, 0.99));
+
+    expect(result.text).toBe("Hit 14, damage 7, {{roll:oops}}");
+    expect(result.rolls.map((roll) => roll.total)).toEqual([14, 7]);
+  });
+});
@@ -0,0 +1,42 @@
+import { describe, expect, it, vi } from "vitest";
+import { formatPrompt } from "../../formatter";
+import { estimateTokens } from "../apply-context-limit";
+
+vi.mock("@/commands/inference", () => ({
+  countTokens: vi.fn(async (text: string) => ({ count: estimateTokens(text) })),
+}));
+vi.mock("@/services/notes-service", () => ({ getNoteSnippets: vi.fn(async () => ({})) }));
+vi.mock("../apply-lorebook", () => ({
+  getLorebookContent: vi.fn(async () => ({ replacers: { lorebook_top: "", lorebook_bottom: "" }, messages: [] })),
+  processLorebookMessages: (messages: unknown) => messages,
+}));
+
+const message = (id: string, type: "user" | "character", text: string, position: number, extra: Record<string, unknown> = {}): any => ({
+  id,
+  chat_id: "chat-1",
+  chapter_id: "chapter-1",
+  character_id: type === "character" ? "character-1" : null,
+  type,
+  position,
+  messages: [text],
+  message_index: 0,
+  disabled: false,
+  pinned: false,
+  extra,
+});
+
+describe("exclude_from_context", () => {
+  it("keeps excluded messages out of the dispatched prompt", async () => {
+    const result = await formatPrompt({
+      messageHistory: [
+        message("m1", "user", "We enter the tavern.", 100),
+        message("m2", "user", "(OOC: brb, grabbing coffee)", 200, { exclude_from_context: true }),
+        message("m3", "character", "The innkeeper waves you over.", 300, { key_event: true, tags: ["arrival"] }),
+      ],
+      userPrompt: "I sit down.",
+      chatTemplate: { config: { max_context: 4000, max_tokens: 200, max_depth: 100 } },
+    });
+
+    expect(result.inferenceMessages.map((entry) => entry.text)).toEqual(["We enter the tavern.", "The innkeeper waves you over.", "I sit down."]);
+  });
+});
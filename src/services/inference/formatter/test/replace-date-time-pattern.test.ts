@@ -1,5 +1,6 @@
-import { beforeEach, describe, expect, it } from "vitest";
-import { replaceDateTimePattern } from "../replace-text-placeholders";
+import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
+import { createSystemPrompt } from "../../formatter";
+import { formatDateTokens, replaceDateTimePattern, replaceTextPlaceholders } from "../replace-text-placeholders";
 
 describe("replaceDateTimePattern", () => {
   let mockDate: Date;
@@ -199,3 +200,106 @@ describe("replaceDateTimePattern", () => {
     expect(result1).not.toContain("}}");
   });
 });
+
+describe("replaceDateTimePattern with options", () => {
+  // January 15, 2024, 2:30:45 PM UTC (Monday)
+  const mockDate = new Date("2024-01-15T14:30:45.123Z");
+
+  it("should replace {{timezone}} with the configured time zone", () => {
+    expect(replaceDateTimePattern("Zone: {{timezone}}", mockDate, { timeZone: "America/New_York" })).toBe("Zone: America/New_York");
+  });
+
+  it("should replace {{timezone}} with the system time zone by default", () => {
+    const systemTimeZone = Intl.DateTimeFormat().resolvedOptions().timeZone;
+    expect(replaceDateTimePattern("{{timezone}}", mockDate)).toBe(systemTimeZone);
+  });
+
+  it("should fall back to the system time zone when the override is invalid", () => {
+    const systemTimeZone = Intl.DateTimeFormat().resolvedOptions().timeZone;
+    expect(replaceDateTimePattern("{{timezone}}", mockDate, { timeZone: "Not/AZone" })).toBe(systemTimeZone);
+  });
+
+  it("should express every macro in the configured time zone", () => {
+    const result = replaceDateTimePattern("{{isodate}} {{isotime}} {{time}} {{weekday}}", mockDate, { timeZone: "America/New_York" });
+    expect(result).toBe("2024-01-15 09:30:45 9:30 AM Monday");
+  });
+
+  it("should roll the date over with the time zone", () => {
+    const newYearsEve = new Date("2023-12-31T23:59:59.999Z");
+    const result = replaceDateTimePattern("{{weekday}}, {{date}} ({{isodate}})", newYearsEve, { timeZone: "Asia/Tokyo" });
+    expect(result).toBe("Monday, January 1, 2024 (2024-01-01)");
+  });
+
+  it("should use the configured date and time formats", () => {
+    const result = replaceDateTimePattern("{{date}} {{time}}", mockDate, { timeZone: "UTC", dateFormat: "DD/MM/YYYY", timeFormat: "HH:mm" });
+    expect(result).toBe("15/01/2024 14:30");
+  });
+
+  it("should use the configured locale for names", () => {
+    const result = replaceDateTimePattern("{{weekday}}", mockDate, { timeZone: "UTC", locale: "de-DE" });
+    expect(result).toBe("Montag");
+  });
+
+  it("should keep the default formats when the format strings are empty", () => {
+    const withEmptyFormats = replaceDateTimePattern("{{date}} {{time}}", mockDate, { timeZone: "UTC", dateFormat: "", timeFormat: "" });
+    expect(withEmptyFormats).toBe("January 15, 2024 2:30 PM");
+  });
+});
+
+describe("formatDateTokens", () => {
+  const mockDate = new Date("2024-01-05T07:04:09.000Z");
+
+  it("should format all tokens", () => {
+    expect(formatDateTokens(mockDate, "YYYY YY MMMM MMM MM M DD D dddd ddd", "en-US", "UTC")).toBe("2024 24 January Jan 01 1 05 5 Friday Fri");
+    expect(formatDateTokens(mockDate, "HH H hh h mm ss A", "en-US", "UTC")).toBe("07 7 07 7 04 09 AM");
+  });
+
+  it("should keep bracketed text literally", () => {
+    expect(formatDateTokens(mockDate, "[Day] D [of] MMMM", "en-US", "UTC")).toBe("Day 5 of January");
+  });
+
+  it("should handle afternoon hours in 12-hour tokens", () => {
+    const afternoon = new Date("2024-01-05T12:15:00.000Z");
+    expect(formatDateTokens(afternoon, "h:mm A", "en-US", "UTC")).toBe("12:15 PM");
+  });
+});
+
+describe("date/time macros at render time", () => {
+  beforeEach(() => {
+    vi.useFakeTimers();
+  });
+
+  afterEach(() => {
+    vi.useRealTimers();
+  });
+
+  it("should compute the values on each render instead of caching them", () => {
+    const config = { dateTime: { timeZone: "UTC", timeFormat: "HH:mm" } };
+
+    vi.setSystemTime(new Date("2024-01-15T14:30:00.000Z"));
+    const first = replaceTextPlaceholders([{ role: "user", text: "{{time}}" }], undefined, config);
+
+    vi.setSystemTime(new Date("2024-01-15T15:45:00.000Z"));
+    const second = replaceTextPlaceholders([{ role: "user", text: "{{time}}" }], undefined, config);
+
+    expect(first.inferenceMessages[0].text).toBe("14:30");
+    expect(second.inferenceMessages[0].text).toBe("15:45");
+  });
+
+  it("should render the opt-in current date/time system prompt section", () => {
+    vi.setSystemTime(new Date("2024-01-15T14:30:45.000Z"));
+
+    const systemPrompt = createSystemPrompt({
+      systemPromptTemplate: {
+        prompts: [
+          { type: "context", content: "You are a helpful assistant.", enabled: true },
+          { type: "current-datetime", content: "It is {{weekday}}, {{date}} at {{time}} ({{timezone}}).", enabled: true },
+        ],
+      } as any,
+      contextSeparator: "\n\n",
+    });
+    const result = replaceTextPlaceholders([], systemPrompt, { dateTime: { timeZone: "UTC" } });
+
+    expect(result.systemPrompt).toBe("You are a helpful assistant.\n\nIt is Monday, January 15, 2024 at 2:30 PM (UTC).");
+  });
+});
@@ -95,7 +95,6 @@ describe("replaceDiceRollPattern", () => {
     const invalidPatterns = [
       "Invalid: {{roll:abc}}",
       "Invalid: {{roll:1d}}",
-      "Invalid: {{roll:d20}}",
       "Invalid: {{roll:1d20+}}",
       "Invalid: {{roll:1d20+abc}}",
       "Invalid: {{roll:}}",
@@ -0,0 +1,29 @@
+import { describe, expect, it } from "vitest";
+import { findNoteReferences, replaceNotePattern, replaceStringPlaceholders } from "../replace-text-placeholders";
+
+describe("findNoteReferences", () => {
+  it("collects each referenced title once", () => {
+    const titles = findNoteReferences(["Use {{note:Style guide}} and {{note: Plot }}", undefined, "Again {{note:Style guide}}"]);
+    expect(titles).toEqual(["Style guide", "Plot"]);
+  });
+
+  it("ignores other placeholders", () => {
+    expect(findNoteReferences(["{{char}} rolls {{roll:1d20}} {{// note: not a reference}}"])).toEqual([]);
+  });
+});
+
+describe("replaceNotePattern", () => {
+  it("inserts the note content by title", () => {
+    expect(replaceNotePattern("Rules: {{note:Style guide}}", { "Style guide": "Write in third person." })).toBe("Rules: Write in third person.");
+  });
+
+  it("leaves unknown titles untouched", () => {
+    expect(replaceNotePattern("{{note:Missing}}", { Other: "text" })).toBe("{{note:Missing}}");
+    expect(replaceNotePattern("{{note:Missing}}", undefined)).toBe("{{note:Missing}}");
+  });
+
+  it("renders placeholders inside the note", () => {
+    const result = replaceStringPlaceholders("{{note:Greeting}}", { character: { name: "Alice" } as any, notes: { Greeting: "{{char}} waves." } });
+    expect(result).toBe("Alice waves.");
+  });
+});
@@ -0,0 +1,76 @@
+import { describe, expect, it, vi } from "vitest";
+import { formatPrompt } from "../../formatter";
+import { estimateTokens } from "../apply-context-limit";
+import { replaceRandomListPattern, replaceTextPlaceholders } from "../replace-text-placeholders";
+
+vi.mock("@/commands/inference", () => ({
+  countTokens: vi.fn(async (text: string) => ({ count: estimateTokens(text) })),
+}));
+vi.mock("@/services/notes-service", () => ({ getNoteSnippets: vi.fn(async () => ({})) }));
+vi.mock("../apply-lorebook", () => ({
+  getLorebookContent: vi.fn(async () => ({ replacers: { lorebook_top: "", lorebook_bottom: "" }, messages: [] })),
+  processLorebookMessages: (messages: unknown) => messages,
+}));
+
+const chatConfig: any = {
+  character: { name: "Alice", type: "character", custom: { personality: "Curious" } },
+  user_character: { name: "Bob", custom: { personality: "Stoic" } },
+  chapter: { title: "Arrival", scenario: "A rainy harbor" },
+  chat: { name: "Harbor Tales" },
+};
+
+describe("SillyTavern macros", () => {
+  it("renders the short aliases and the chat name", () => {
+    const result = replaceTextPlaceholders([], "{{Char}} ({{personality}}) meets {{USER}} ({{persona}}) in {{scenario}}.{{newline}}{{chat.name}}{{noop}}", chatConfig);
+
+    expect(result.systemPrompt).toBe("Alice (Curious) meets Bob (Stoic) in A rainy harbor.\nHarbor Tales");
+  });
+
+  it("renders the chat synopsis, empty before the first refresh", () => {
+    expect(replaceTextPlaceholders([], "Story so far: {{synopsis}}", { ...chatConfig, chat: { name: "Harbor Tales", synopsis: "Bob boards the ship." } }).systemPrompt).toBe(
+      "Story so far: Bob boards the ship.",
+    );
+    expect(replaceTextPlaceholders([], "Story so far: {{synopsis}}", chatConfig).systemPrompt).toBe("Story so far: ");
+  });
+
+  it("picks one option of a random list", () => {
+    for (let i = 0; i < 20; i++) {
+      expect(["red", "green", "blue"]).toContain(replaceRandomListPattern("{{random:red, green,blue}}"));
+      expect(["a, b", "c"]).toContain(replaceRandomListPattern("{{random::a, b::c}}"));
+    }
+    expect(replaceRandomListPattern("{{random:}}")).toBe("{{random:}}");
+  });
+
+  it("leaves macros in messages alone when the format template turns them off", async () => {
+    const formatTemplate: any = { config: { settings: { substitute_message_macros: false, merge_subsequent_messages: true } } };
+    const result = await formatPrompt({
+      messageHistory: [
+        {
+          id: "m1",
+          chat_id: "chat-1",
+          chapter_id: "chapter-1",
+          character_id: null,
+          type: "user",
+          position: 100,
+          messages: ["Write {{char}} in braces, then {{roll:1d20}}."],
+          message_index: 0,
+          disabled: false,
+          pinned: false,
+          extra: {},
+        } as any,
+      ],
+      userPrompt: "And greet {{user}}.",
+      formatTemplate,
+      chatTemplate: {
+        config: { max_context: 4000, max_tokens: 200, max_depth: 100 },
+        custom_prompts: [{ id: "p1", name: "Reminder", role: "user", position: "bottom", depth: 0, prompt: "Stay in character as {{char}}.", enabled: true, filter: {} } as any],
+      },
+      chatConfig,
+    });
+
+    const texts = result.inferenceMessages.map((entry) => entry.text).join("\n");
+    expect(texts).toContain("Write {{char}} in braces, then {{roll:1d20}}.");
+    expect(texts).toContain("And greet {{user}}.");
+    expect(texts).toContain("Stay in character as Alice.");
+  });
+});
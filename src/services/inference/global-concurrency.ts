@@ -0,0 +1,60 @@
+import { type PrioritizedRequest, type RequestPriority, takeNextRequest } from "./request-priority";
+
+/**
+ * Process-wide cap on in-flight inference requests, shared by every useInference instance.
+ * Per-model limits still apply; a request holds its model slot while it waits here.
+ * Waiting requests start by priority, then focused chat first (see request-priority.ts).
+ */
+
+let globalLimit = 0; // 0 = unlimited
+let active = 0;
+const waiting: Array<PrioritizedRequest & { start: () => void }> = [];
+
+function hasCapacity(): boolean {
+  return globalLimit <= 0 || active < globalLimit;
+}
+
+function drain() {
+  while (waiting.length > 0 && hasCapacity()) {
+    active += 1;
+    takeNextRequest(waiting)?.start();
+  }
+}
+
+/**
+ * Set the maximum number of concurrent requests across all models. 0 removes the cap.
+ * Raising the limit starts waiting requests immediately.
+ */
+export function setGlobalConcurrency(limit: number) {
+  globalLimit = Number.isFinite(limit) ? Math.max(0, Math.floor(limit)) : 0;
+  drain();
+}
+
+/**
+ * Wait for a global slot. The returned function releases it and must be called exactly once.
+ * `chatId` tags the request so it can be boosted while its chat is focused; `priority` defaults to "normal".
+ */
+export function acquireGlobalSlot(chatId?: string, priority?: RequestPriority): Promise<() => void> {
+  return new Promise((resolve) => {
+    let released = false;
+    const release = () => {
+      if (released) {
+        return;
+      }
+      released = true;
+      active = Math.max(0, active - 1);
+      drain();
+    };
+
+    if (hasCapacity() && waiting.length === 0) {
+      active += 1;
+      resolve(release);
+    } else {
+      waiting.push({ chatId, priority, enqueuedAt: Date.now(), start: () => resolve(release) });
+    }
+  });
+}
+
+export function getGlobalConcurrencyState() {
+  return { limit: globalLimit, active, waiting: waiting.length };
+}
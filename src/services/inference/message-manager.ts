@@ -1,14 +1,26 @@
 import { useCallback, useMemo } from "react";
+import { toast } from "sonner";
 import { getCurrentChatId, useChatActions, useChatStore, useCurrentChatMessages } from "@/hooks/chatStore";
-import type { ChatMessage, ChatMessageType } from "@/schema/chat-message-schema";
+import type { ChatMessage, ChatMessageType, MessageTranslation, VariantModel } from "@/schema/chat-message-schema";
 import {
   createChatMessage as apiCreateChatMessage,
   updateChatMessage as apiUpdateChatMessage,
   getChatMessageById,
   getChatMessagesByChatId,
   getNextMessagePosition,
+  mergeChatMessageExtra,
 } from "@/services/chat-message-service";
 
+// Copy of a per-variant array (aligned with `messages`) with `value` set at `index`, padding with null
+function alignedWith<T>(values: (T | null)[] | undefined, index: number, value: T): (T | null)[] {
+  const aligned = [...(values ?? [])];
+  while (aligned.length < index) {
+    aligned.push(null);
+  }
+  aligned[index] = value;
+  return aligned;
+}
+
 /**
  * Hook for managing chat messages during inference.
  *
@@ -111,6 +123,71 @@ export function useMessageManager() {
     }
   }, []);
 
+  /**
+   * Records which model produced a message variant in `extra.variantModels` and why it stopped in `extra.finishReasons`.
+   * Only merges those keys into `extra`, so it can run alongside `updateMessageDirect` and `recordTranslation` for the
+   * same message. Failures are reported with a toast.
+   */
+  const recordVariantMetadata = useCallback(async (messageId: string, messageIndex: number, metadata: { model?: VariantModel | null; finishReason?: string }): Promise<void> => {
+    try {
+      if (messageId === "generate-input-area" || !messageId) {
+        return;
+      }
+
+      const message = await getChatMessageById(messageId);
+      if (!message) {
+        return;
+      }
+
+      const values: Record<string, unknown> = {};
+      if (metadata.model) {
+        values.variantModels = alignedWith(message.extra?.variantModels, messageIndex, metadata.model);
+      }
+      if (metadata.finishReason) {
+        values.finishReasons = alignedWith(message.extra?.finishReasons, messageIndex, metadata.finishReason);
+      }
+
+      await mergeChatMessageExtra(messageId, values);
+    } catch (err) {
+      console.error("Failed to record variant metadata:", err);
+      toast.error("Couldn't save which model wrote this reply", { description: err instanceof Error ? err.message : String(err) });
+    }
+  }, []);
+
+  /**
+   * Shows `displayText` for a variant and keeps the text the model saw in `extra.translations`.
+   * Reads the stored message so the other variants are preserved, and only merges `translations` into `extra`.
+   * Failures are reported with a toast.
+   */
+  const recordTranslation = useCallback(async (messageId: string, messageIndex: number, displayText: string, translation: MessageTranslation): Promise<void> => {
+    try {
+      if (messageId === "generate-input-area" || !messageId) {
+        return;
+      }
+
+      const message = await getChatMessageById(messageId);
+      if (!message) {
+        return;
+      }
+
+      const messages = [...message.messages];
+      messages[messageIndex] = displayText;
+
+      const translations = alignedWith(message.extra?.translations, messageIndex, translation);
+      await mergeChatMessageExtra(messageId, { translations }, messages);
+
+      if (message.chat_id === getCurrentChatId()) {
+        const chapterMessages = await getChatMessagesByChatId(message.chat_id, message.chapter_id);
+        if (message.chat_id === getCurrentChatId()) {
+          useChatStore.setState({ selectedChatMessages: chapterMessages });
+        }
+      }
+    } catch (err) {
+      console.error("Failed to record message translation:", err);
+      toast.error("Couldn't save the translated message", { description: err instanceof Error ? err.message : String(err) });
+    }
+  }, []);
+
   const batchUpdateMessages = useCallback(
     async (
       updates: Array<{
@@ -239,6 +316,8 @@ export function useMessageManager() {
   return {
     updateMessageById,
     updateMessageDirect,
+    recordVariantMetadata,
+    recordTranslation,
     batchUpdateMessages,
     createUserMessage,
     createCharacterMessage,
@@ -0,0 +1,172 @@
+import { emitToCurrentWindow } from "@/commands/windows";
+import type { ModelSpecs } from "@/schema/inference-engine-schema";
+import { manifestSupportsReasoning } from "@/services/ai-providers/aisdk/reasoning-support";
+import type { AIEvent } from "@/services/ai-providers/types/ai-event.type";
+import { getModelManifestById } from "@/services/manifest-service";
+import { getModelById } from "@/services/model-service";
+
+/**
+ * Fallback chain: when a model fails before producing anything, with an error that re-sending could fix
+ * (unreachable endpoint, or a retryable error left after the retries), the request goes to the next model
+ * of the primary model's `fallback_model_ids`.
+ */
+
+const FALLBACK_USED_EVENT = "fallback-used";
+
+interface FallbackUsedPayload {
+  requestId: string;
+  fromModelId: string;
+  toModelId: string;
+  // Error code that triggered the fallback
+  reason?: string;
+}
+
+// What a request produced so far, and whether it was cancelled
+interface FallbackProgress {
+  cancelled?: boolean;
+  accumulatedText: string;
+  accumulatedReasoning: string;
+  toolCalls: unknown[];
+}
+
+// The model a request is attributed to
+interface ServingModel {
+  modelId: string;
+  modelConfig: Record<string, unknown>;
+  engine: string;
+  actualModel?: string;
+  finishReason?: string;
+  // The primary, once a fallback took over
+  fallbackFrom?: string;
+}
+
+function errorCode(error: unknown): string | undefined {
+  return typeof error === "object" && error !== null && "code" in error ? String((error as { code?: unknown }).code || "") || undefined : undefined;
+}
+
+/**
+ * Whether a failed request may move to the next model. Only failures before any output qualify,
+ * which the caller checks; cancellations never do.
+ */
+function shouldFallBack(error: unknown): boolean {
+  const code = errorCode(error);
+  if (code === "cancelled") {
+    return false;
+  }
+  if (code === "network_unreachable") {
+    return true;
+  }
+  return typeof error === "object" && error !== null && "retryable" in error && !!(error as { retryable?: unknown }).retryable;
+}
+
+/**
+ * Whether a request that failed on its `attempts`-th model may move on: it wasn't cancelled, produced nothing yet,
+ * the chain has models left and the error qualifies.
+ */
+function canFallBack(progress: FallbackProgress, attempts: number, chainLength: number, error: unknown): boolean {
+  return (
+    !progress.cancelled &&
+    attempts <= chainLength &&
+    !progress.accumulatedText &&
+    !progress.accumulatedReasoning &&
+    progress.toolCalls.length === 0 &&
+    shouldFallBack(error)
+  );
+}
+
+/**
+ * Wraps a request's event so the first failure that may fall back is held instead of reported, along with the
+ * finish that follows it. Once the provider call returned, `heldError()` tells whether to try the next model.
+ */
+function holdFallbackError(event: AIEvent, mayFallBack: (error: unknown) => boolean) {
+  let held: unknown;
+  // True when the error is held, or an earlier one already is
+  const hold = (error: unknown): boolean => {
+    if (held !== undefined) {
+      return true;
+    }
+    if (!mayFallBack(error)) {
+      return false;
+    }
+    held = error;
+    return true;
+  };
+
+  const wrapped: AIEvent = {
+    ...event,
+    sendError: (error) => {
+      if (!hold(error)) {
+        event.sendError(error);
+      }
+    },
+    finish: (payload) => {
+      if (held === undefined) {
+        event.finish(payload);
+      }
+    },
+  };
+  return { event: wrapped, hold, heldError: () => held };
+}
+
+/**
+ * Attribute the rest of a request to the fallback model, so its result and usage name the model that answered
+ */
+function attributeToFallback(runtime: ServingModel, primaryId: string, next: ModelSpecs) {
+  runtime.fallbackFrom ??= primaryId;
+  runtime.modelId = next.id;
+  runtime.modelConfig = next.config;
+  runtime.engine = next.engine;
+  runtime.actualModel = typeof next.config?.model === "string" ? next.config.model : undefined;
+  runtime.finishReason = undefined;
+}
+
+/**
+ * The next usable model of the primary's chain, skipping models already tried, deleted or from another profile.
+ * A fallback only qualifies when it takes the same prompt shape (chat or completion) as the primary.
+ */
+async function resolveFallbackModel(primary: ModelSpecs, tried: string[]): Promise<ModelSpecs | null> {
+  const primaryModel = await getModelById(primary.id);
+  if (!primaryModel) {
+    return null;
+  }
+
+  for (const modelId of primary.fallback_model_ids ?? []) {
+    if (tried.includes(modelId)) {
+      continue;
+    }
+    const model = await getModelById(modelId).catch(() => null);
+    if (!model || model.profile_id !== primaryModel.profile_id) {
+      continue;
+    }
+    const modelType = model.inference_template_id ? "completion" : "chat";
+    if (modelType !== primary.model_type) {
+      console.warn(`Skipping fallback model ${model.name}: it expects a ${modelType} prompt`);
+      continue;
+    }
+    const manifest = await getModelManifestById(model.manifest_id);
+    if (!manifest) {
+      continue;
+    }
+
+    return {
+      id: model.id,
+      model_type: modelType,
+      config: model.config,
+      max_concurrent_requests: model.max_concurrency || 1,
+      engine: manifest.engine,
+      supports_reasoning: manifestSupportsReasoning(manifest),
+      capabilities: model.capabilities,
+      // The primary's chain drives the whole request, so fallbacks don't chain further
+      fallback_model_ids: primary.fallback_model_ids,
+    };
+  }
+
+  return null;
+}
+
+function emitFallbackUsed(payload: FallbackUsedPayload) {
+  emitToCurrentWindow(FALLBACK_USED_EVENT, payload).catch((error) => console.error("Failed to emit fallback event:", error));
+}
+
+export type { FallbackProgress, FallbackUsedPayload, ServingModel };
+export { attributeToFallback, canFallBack, emitFallbackUsed, FALLBACK_USED_EVENT, holdFallbackError, resolveFallbackModel, shouldFallBack };
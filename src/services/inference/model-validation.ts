@@ -0,0 +1,118 @@
+import type { ModelSpecs } from "@/schema/inference-engine-schema";
+import { manifestSupportsReasoning } from "@/services/ai-providers/aisdk/reasoning-support";
+import { callProviderConverseEndpoint } from "@/services/ai-providers/start-inference";
+import type { AIError, AIEvent } from "@/services/ai-providers/types/ai-event.type";
+import { getModelManifestById } from "@/services/manifest-service";
+import { listModels, type Model } from "@/services/model-service";
+import { acquireGlobalSlot } from "./global-concurrency";
+
+/**
+ * Bulk health check: sends the same one-message test as the model form's "Test Connection" to every
+ * language model of a profile, a few at a time, and reports which ones still answer.
+ */
+
+const DEFAULT_CONCURRENCY = 3;
+const TEST_TIMEOUT_MS = 60_000;
+
+export interface ModelValidation {
+  model_id: string;
+  name: string;
+  // Only language models can be tested this way; the others are skipped
+  status: "ok" | "error" | "skipped";
+  latency_ms: number | null;
+  error?: { code?: string; message: string };
+}
+
+async function toModelSpecs(model: Model): Promise<ModelSpecs> {
+  const manifest = await getModelManifestById(model.manifest_id);
+  if (!manifest) {
+    throw new Error(`Manifest ${model.manifest_id} not found`);
+  }
+  return {
+    id: model.id,
+    model_type: model.inference_template_id ? "completion" : "chat",
+    config: model.config,
+    max_concurrent_requests: 1,
+    engine: manifest.engine,
+    supports_reasoning: manifestSupportsReasoning(manifest),
+    capabilities: model.capabilities,
+  };
+}
+
+// Resolves with the first error reported, or null once the model answered
+function sendTestRequest(modelSpecs: ModelSpecs): Promise<AIError | null> {
+  return new Promise((resolve) => {
+    let aborter: (() => void) | undefined;
+    let settled = false;
+    const timeout = setTimeout(() => {
+      aborter?.();
+      settle({ message: `No answer after ${TEST_TIMEOUT_MS / 1000} seconds`, code: "timeout" });
+    }, TEST_TIMEOUT_MS);
+    const settle = (error: AIError | null) => {
+      if (!settled) {
+        settled = true;
+        clearTimeout(timeout);
+        resolve(error);
+      }
+    };
+
+    const event: AIEvent = {
+      requestId: `validate_${modelSpecs.id}_${Date.now()}`,
+      sendStream: () => {},
+      sendError: (error) => settle(error),
+      finish: () => settle(null),
+      registerAborter: (abort) => {
+        aborter = abort;
+      },
+    };
+
+    callProviderConverseEndpoint(event, {
+      messages: [{ role: "user", text: `Test connection - ${Date.now()}` }],
+      modelSpecs,
+      systemPrompt: "You are a helpful assistant. Reply with 'Connection successful' to confirm the connection works.",
+      parameters: { retry_on_empty: { enabled: false } },
+      stream: false,
+    })
+      // Non-streaming calls return the text instead of calling finish
+      .then(() => settle(null))
+      .catch((error) => settle({ message: error instanceof Error ? error.message : String(error) }));
+  });
+}
+
+async function validateModel(model: Model): Promise<ModelValidation> {
+  const base = { model_id: model.id, name: model.name };
+  if (model.type !== "llm") {
+    return { ...base, status: "skipped", latency_ms: null };
+  }
+
+  const releaseGlobalSlot = await acquireGlobalSlot();
+  const startedAt = Date.now();
+  try {
+    const error = await sendTestRequest(await toModelSpecs(model));
+    const latency_ms = Date.now() - startedAt;
+    return error ? { ...base, status: "error", latency_ms, error: { code: error.code, message: error.message } } : { ...base, status: "ok", latency_ms };
+  } catch (error) {
+    return { ...base, status: "error", latency_ms: null, error: { message: error instanceof Error ? error.message : String(error) } };
+  } finally {
+    releaseGlobalSlot();
+  }
+}
+
+/**
+ * Test every model of the profile, at most `concurrency` at a time. Results keep the order of the model list.
+ */
+export async function validateAllModels(profileId: string, concurrency = DEFAULT_CONCURRENCY): Promise<ModelValidation[]> {
+  const models = await listModels({ profile_id: profileId });
+  const results: ModelValidation[] = new Array(models.length);
+  let next = 0;
+
+  const worker = async () => {
+    while (next < models.length) {
+      const index = next++;
+      results[index] = await validateModel(models[index]);
+    }
+  };
+
+  await Promise.all(Array.from({ length: Math.max(1, Math.min(concurrency, models.length)) }, worker));
+  return results;
+}
@@ -0,0 +1,181 @@
+import { useCallback } from "react";
+import { useCurrentProfile } from "@/hooks/ProfileStore";
+import { useBackgroundInference } from "../background-inference-service";
+import { listCharacters } from "../character-service";
+import { listRecentMessages, type SequencedChatMessage } from "../chat-message-service";
+import { getChatById } from "../chat-service";
+import { getModelByIdOrAlias } from "../model-service";
+import { getChatTemplateById } from "../template-chat-service";
+
+/**
+ * Picks which character of a group chat speaks next. `round_robin` takes turns in candidate order,
+ * `random` draws one that didn't just speak, `llm` asks the chat's model with the recent messages.
+ */
+export type NextSpeakerStrategy = "round_robin" | "random" | "llm";
+
+export interface SpeakerCandidate {
+  id: string;
+  name: string;
+}
+
+// Messages the model reads to pick the speaker, and the ones searched for the last speaker
+export const NEXT_SPEAKER_CONTEXT_SIZE = 20;
+
+export const NEXT_SPEAKER_SYSTEM_PROMPT = `You direct a group roleplay chat. You get the latest messages and the characters who can speak next.
+Pick the character who should reply now, going by who was addressed, who is present in the scene and who has something to add.
+Reply with that character's name only, exactly as listed.`;
+
+/**
+ * The candidate who wrote the most recent character message, if any
+ */
+export function findLastSpeaker(messages: SequencedChatMessage[], candidateIds: string[]): string | null {
+  for (let index = messages.length - 1; index >= 0; index--) {
+    const message = messages[index];
+    if (message.type === "character" && message.character_id && candidateIds.includes(message.character_id)) {
+      return message.character_id;
+    }
+  }
+  return null;
+}
+
+/**
+ * The candidate after the last speaker, wrapping around. The first one when none of them spoke yet.
+ */
+export function pickRoundRobin(candidateIds: string[], lastSpeakerId: string | null): string {
+  const index = lastSpeakerId ? candidateIds.indexOf(lastSpeakerId) : -1;
+  return candidateIds[(index + 1) % candidateIds.length];
+}
+
+/**
+ * A random candidate, never the last speaker when someone else can take the turn
+ */
+export function pickRandom(candidateIds: string[], lastSpeakerId: string | null, random: () => number = Math.random): string {
+  const pool = candidateIds.length > 1 ? candidateIds.filter((id) => id !== lastSpeakerId) : candidateIds;
+  return pool[Math.min(Math.floor(random() * pool.length), pool.length - 1)];
+}
+
+/**
+ * Prompt asking the model to pick the next speaker among `candidates`
+ */
+export function buildNextSpeakerPrompt(messages: SequencedChatMessage[], candidates: SpeakerCandidate[], nameOf: (message: SequencedChatMessage) => string): string {
+  const transcript = messages
+    .filter((message) => !message.excluded && message.text.trim())
+    .map((message) => `${nameOf(message)}: ${message.text.trim()}`)
+    .join("\n\n");
+
+  return `Latest messages:\n${transcript || "(the chat has no messages yet)"}\n\nCharacters who can speak next:\n${candidates.map((candidate) => `- ${candidate.name}`).join("\n")}\n\nWho speaks next?`;
+}
+
+/**
+ * The candidate the model named. An exact answer wins; otherwise the name mentioned first, the longer one
+ * on a tie ("Anna" over "Ann"). Null when the answer names nobody.
+ */
+export function parseNextSpeaker(answer: string, candidates: SpeakerCandidate[]): string | null {
+  const cleaned = answer
+    .trim()
+    .replace(/^["'*\s-]+|["'*.!\s]+$/g, "")
+    .toLowerCase();
+  const exact = candidates.find((candidate) => candidate.name.trim().toLowerCase() === cleaned);
+  if (exact) {
+    return exact.id;
+  }
+
+  let best: { id: string; position: number; length: number } | null = null;
+  for (const candidate of candidates) {
+    const name = candidate.name.trim().toLowerCase();
+    const position = name ? cleaned.indexOf(name) : -1;
+    if (position === -1) {
+      continue;
+    }
+    if (!best || position < best.position || (position === best.position && name.length > best.length)) {
+      best = { id: candidate.id, position, length: name.length };
+    }
+  }
+  return best?.id ?? null;
+}
+
+/**
+ * Hook choosing the next speaker of group chats, the model strategy built on background inference.
+ */
+export function useNextSpeaker() {
+  const currentProfile = useCurrentProfile();
+  const { executeInference } = useBackgroundInference();
+
+  /**
+   * Choose who speaks next among `candidateCharacterIds`, in participant order.
+   * Resolves with the chosen character id. Rejects without candidates or with characters not found.
+   * @param modelId Model for the `llm` strategy instead of the chat template's
+   */
+  const selectNextSpeaker = useCallback(
+    async (chatId: string, candidateCharacterIds: string[], strategy: NextSpeakerStrategy, modelId?: string): Promise<string> => {
+      if (!currentProfile) {
+        throw new Error("No profile selected");
+      }
+      const candidateIds = [...new Set(candidateCharacterIds)];
+      if (candidateIds.length === 0) {
+        throw new Error("No characters to choose the next speaker from");
+      }
+
+      const chat = await getChatById(chatId, currentProfile.id);
+      if (!chat) {
+        throw new Error(`Chat ${chatId} not found`);
+      }
+
+      const characters = await listCharacters(currentProfile.id);
+      const namesById = new Map(characters.map((character) => [character.id, character.name]));
+      const missing = candidateIds.find((id) => !namesById.has(id));
+      if (missing) {
+        throw new Error(`Character ${missing} not found`);
+      }
+      if (candidateIds.length === 1) {
+        return candidateIds[0];
+      }
+
+      const messages = await listRecentMessages(chatId, NEXT_SPEAKER_CONTEXT_SIZE);
+      const lastSpeakerId = findLastSpeaker(messages, candidateIds);
+
+      if (strategy === "round_robin") {
+        return pickRoundRobin(candidateIds, lastSpeakerId);
+      }
+      if (strategy === "random") {
+        return pickRandom(candidateIds, lastSpeakerId);
+      }
+
+      const chatTemplate = chat.chat_template_id ? await getChatTemplateById(chat.chat_template_id).catch(() => null) : null;
+      const resolvedModelId = modelId || chatTemplate?.model_id;
+      const model = resolvedModelId ? await getModelByIdOrAlias(resolvedModelId, currentProfile.id) : null;
+      if (!model) {
+        throw new Error("No model to choose the next speaker with, set one in the chat template");
+      }
+
+      const userName = (chat.user_character_id && namesById.get(chat.user_character_id)) || currentProfile.name;
+      const nameOf = (message: SequencedChatMessage) => {
+        if (message.type === "user") {
+          return userName;
+        }
+        return (message.character_id && namesById.get(message.character_id)) || (message.type === "system" ? "Narrator" : "Character");
+      };
+      const candidates = candidateIds.map((id) => ({ id, name: namesById.get(id) ?? "" }));
+
+      const response = await executeInference({
+        model,
+        prompt: [{ role: "user", text: buildNextSpeakerPrompt(messages, candidates, nameOf) }],
+        systemPrompt: NEXT_SPEAKER_SYSTEM_PROMPT,
+        parameters: { temperature: 0.2, max_tokens: 64 },
+        // The reply waits on the choice
+        priority: "interactive",
+      });
+
+      const chosen = parseNextSpeaker(response, candidates);
+      if (chosen) {
+        return chosen;
+      }
+      // A model that names nobody shouldn't stall the group, the turn goes on in order
+      console.warn("The model didn't name a character to speak next, taking turns instead");
+      return pickRoundRobin(candidateIds, lastSpeakerId);
+    },
+    [currentProfile, executeInference],
+  );
+
+  return { selectNextSpeaker };
+}
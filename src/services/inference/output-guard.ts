@@ -0,0 +1,105 @@
+import type { ChatDisplaySettings } from "@/schema/chat-schema";
+
+/**
+ * Per-chat guard on the text a character streams: strips a leading "{{char}}:" that local models
+ * like to open with, and ends the reply where the model starts writing for the user or another
+ * participant ("\n{{user}}:"). Chunks can split a name, so text that may still become a match is
+ * held back until the next chunk decides it, like partial reasoning tags in stream-processor.ts.
+ */
+
+export interface OutputGuard {
+  characterName: string;
+  // The user and the other participants; a line opening with one of these names ends the reply
+  otherNames: string[];
+  stripNamePrefix: boolean;
+  stopOnImpersonation: boolean;
+  // Streaming state
+  buffer: string;
+  atStart: boolean;
+  stopped: boolean;
+}
+
+/**
+ * Guard for a reply of `characterName`, or null when the chat enables neither option
+ */
+export function createOutputGuard(settings: ChatDisplaySettings | null | undefined, characterName: string | undefined, otherNames: string[]): OutputGuard | null {
+  const stripNamePrefix = !!settings?.strip_name_prefix && !!characterName;
+  const stopOnImpersonation = !!settings?.stop_on_user_impersonation;
+  if (!stripNamePrefix && !stopOnImpersonation) {
+    return null;
+  }
+
+  return {
+    characterName: characterName ?? "",
+    otherNames: [...new Set(otherNames.filter((name) => name && name !== characterName))],
+    stripNamePrefix,
+    stopOnImpersonation,
+    buffer: "",
+    atStart: true,
+    stopped: false,
+  };
+}
+
+/**
+ * Run the next piece of streamed text through the guard. Returns the text safe to show and whether
+ * the reply should end here. With `final`, nothing is held back.
+ */
+export function processGuardedChunk(chunk: string, guard: OutputGuard, final = false): { text: string; stop: boolean } {
+  if (guard.stopped) {
+    return { text: "", stop: true };
+  }
+
+  let working = guard.buffer + chunk;
+  guard.buffer = "";
+
+  if (guard.atStart) {
+    const trimmed = working.trimStart();
+    const prefix = `${guard.characterName}:`;
+
+    if (guard.stripNamePrefix && trimmed.startsWith(prefix)) {
+      // Still at the start: the whitespace after the prefix goes too
+      return processGuardedChunk(trimmed.slice(prefix.length), guard, final);
+    }
+    if (!final && (!trimmed || (guard.stripNamePrefix && prefix.startsWith(trimmed)))) {
+      guard.buffer = working;
+      return { text: "", stop: false };
+    }
+
+    working = trimmed;
+    guard.atStart = false;
+  }
+
+  if (!guard.stopOnImpersonation) {
+    return { text: working, stop: false };
+  }
+
+  const markers = guard.otherNames.map((name) => `\n${name}:`);
+
+  const stopAt = markers.reduce((earliest, marker) => {
+    const index = working.indexOf(marker);
+    return index !== -1 && (earliest === -1 || index < earliest) ? index : earliest;
+  }, -1);
+  if (stopAt !== -1) {
+    guard.stopped = true;
+    return { text: working.slice(0, stopAt), stop: true };
+  }
+
+  // Names hold no newline, so only the text from the last one can still grow into a marker
+  const lastNewline = working.lastIndexOf("\n");
+  if (!final && lastNewline !== -1) {
+    const tail = working.slice(lastNewline);
+    if (markers.some((marker) => marker.startsWith(tail))) {
+      guard.buffer = tail;
+      return { text: working.slice(0, lastNewline), stop: false };
+    }
+  }
+
+  return { text: working, stop: false };
+}
+
+/**
+ * The guard applied to a whole reply, e.g. the final text of a completed or stopped request
+ */
+export function applyOutputGuard(text: string, guard: OutputGuard): string {
+  return processGuardedChunk(text, { ...guard, buffer: "", atStart: true, stopped: false }, true).text;
+}
@@ -0,0 +1,107 @@
+import { emitToCurrentWindow } from "@/commands/windows";
+import { configFields } from "@/pages/chat/manifests/configFields";
+import type { ModelSpecs } from "@/schema/inference-engine-schema";
+import type { ConfigField } from "@/schema/template-chat-settings-types";
+import { type AIError, PARAMETER_NOT_SUPPORTED } from "@/services/ai-providers/types/ai-event.type";
+
+/**
+ * Pre-flight check of the request parameters against the engine's `inference_fields` (from its
+ * manifest). Parameters the engine doesn't take are dropped by the payload mapping, so a Typical P
+ * or Top K set on an engine without it silently does nothing. Depending on the profile setting the
+ * request goes out anyway, goes out with a `parameter-warning` event, or is refused.
+ */
+
+export type ParameterStrictness = "ignore" | "warn" | "error";
+
+const PARAMETER_WARNING_EVENT = "parameter-warning";
+
+// Read by the pipeline itself on every engine: response length, context trimming, retries, stop words, speaker names, context usage events
+const PIPELINE_KEYS = new Set([
+  "max_tokens",
+  "max_context",
+  "max_depth",
+  "lorebook_token_budget",
+  "response_length",
+  "stop",
+  "retry_on_empty",
+  "normalize_leading_whitespace",
+  "inject_speaker_names",
+  "context_usage_events",
+]);
+
+interface ParameterWarningPayload {
+  requestId: string;
+  modelId: string;
+  engine: string;
+  // Parameter names as set in the chat template, sorted
+  keys: string[];
+}
+
+// A section (Reasoning, DRY...) listed in inference_fields covers the parameters it holds
+function sectionChildren(fields: ConfigField[]): Map<string, string[]> {
+  const children = new Map<string, string[]>();
+  for (const field of fields) {
+    if (field.type === "section") {
+      children.set(field.name, field.fields.map((child) => child.name));
+    }
+  }
+  return children;
+}
+
+/**
+ * Parameters with a value that the engine doesn't take. Empty when the engine's fields are unknown.
+ * @param parameters - Flattened request parameters, as produced by `removeNestedFields`
+ * @param inferenceFields - The `inference_fields` of the model's manifest
+ */
+function findUnsupportedParameters(parameters: Record<string, unknown>, inferenceFields: string[] | undefined, fields: ConfigField[] = configFields): string[] {
+  if (!inferenceFields) {
+    return [];
+  }
+
+  const supported = new Set(inferenceFields);
+  const children = sectionChildren(fields);
+  for (const field of inferenceFields) {
+    for (const child of children.get(field) ?? []) {
+      supported.add(child);
+    }
+  }
+
+  return Object.entries(parameters)
+    .filter(([key, value]) => value !== undefined && value !== null && !PIPELINE_KEYS.has(key) && !supported.has(key))
+    .map(([key]) => key)
+    .sort();
+}
+
+function emitParameterWarning(payload: ParameterWarningPayload) {
+  emitToCurrentWindow(PARAMETER_WARNING_EVENT, payload).catch((error) => console.error("Failed to emit parameter warning:", error));
+}
+
+/**
+ * Apply the strictness to a request before it is queued
+ * @returns The error to fail the request with in "error" mode, otherwise null
+ */
+function checkParameterStrictness(requestId: string, modelSpecs: ModelSpecs, parameters: Record<string, unknown>, strictness: ParameterStrictness): AIError | null {
+  if (strictness === "ignore") {
+    return null;
+  }
+  const keys = findUnsupportedParameters(parameters, modelSpecs.inference_fields);
+  if (keys.length === 0) {
+    return null;
+  }
+
+  if (strictness === "warn") {
+    console.warn(`Parameters the ${modelSpecs.engine} engine doesn't take: ${keys.join(", ")}`);
+    emitParameterWarning({ requestId, modelId: modelSpecs.id, engine: modelSpecs.engine, keys });
+    return null;
+  }
+
+  return {
+    message: `The ${modelSpecs.engine} engine doesn't take ${keys.length === 1 ? "the parameter" : "these parameters"}: ${keys.join(", ")}. Remove ${keys.length === 1 ? "it" : "them"} from the chat template, or let unsupported parameters through in Settings.`,
+    code: PARAMETER_NOT_SUPPORTED,
+    retryable: false,
+    details: { keys },
+  };
+}
+
+export type { ParameterWarningPayload };
+export { checkParameterStrictness, findUnsupportedParameters, PARAMETER_WARNING_EVENT };
@@ -0,0 +1,93 @@
+/**
+ * Parameter sweep: the same prompt sent once per value of one sampler parameter (and repetition), to compare
+ * a model's outputs side by side. Members run as background requests, so they never hold up a chat.
+ */
+
+export const MAX_SWEEP_MEMBERS = 50;
+
+export interface SweepAxis {
+  // Inference parameter key, e.g. "temperature"
+  parameter: string;
+  values: unknown[];
+}
+
+export interface SweepMember {
+  // Parameter combination the member runs with, e.g. "temperature=0.7"
+  combination: string;
+  repetition: number;
+  parameters: Record<string, unknown>;
+}
+
+export interface SweepMemberResult {
+  combination: string;
+  repetition: number;
+  status: "completed" | "error" | "cancelled";
+  text?: string;
+  error?: string;
+  latency_ms: number;
+  input_tokens?: number;
+  output_tokens?: number;
+}
+
+export interface SweepResult {
+  sweep_id: string;
+  parameter: string;
+  // Results of every member, keyed by combination, in repetition order
+  results: Record<string, SweepMemberResult[]>;
+}
+
+/**
+ * Cartesian set of the axis values and repetitions, on top of the base parameters
+ */
+export function buildSweepMembers(axis: SweepAxis, repetitions: number, baseParameters: Record<string, unknown> = {}): SweepMember[] {
+  const parameter = axis.parameter.trim();
+  if (!parameter) {
+    throw new Error("Choose the parameter to sweep");
+  }
+  if (axis.values.length === 0) {
+    throw new Error("Give at least one value to sweep");
+  }
+  const count = Math.max(1, Math.floor(repetitions));
+  if (axis.values.length * count > MAX_SWEEP_MEMBERS) {
+    throw new Error(`A sweep runs at most ${MAX_SWEEP_MEMBERS} requests`);
+  }
+
+  return axis.values.flatMap((value) =>
+    Array.from({ length: count }, (_, repetition) => ({
+      combination: `${parameter}=${JSON.stringify(value)}`,
+      repetition,
+      parameters: { ...baseParameters, [parameter]: value },
+    })),
+  );
+}
+
+/**
+ * Values typed as a comma separated list: numbers and booleans are parsed, anything else stays text
+ */
+export function parseSweepValues(input: string): unknown[] {
+  return input
+    .split(",")
+    .map((value) => value.trim())
+    .filter(Boolean)
+    .map((value) => {
+      if (value === "true" || value === "false") {
+        return value === "true";
+      }
+      const number = Number(value);
+      return Number.isFinite(number) ? number : value;
+    });
+}
+
+/**
+ * Groups member results by combination, keeping the axis order
+ */
+export function collectSweepResults(sweepId: string, parameter: string, members: SweepMember[], results: SweepMemberResult[]): SweepResult {
+  const grouped: Record<string, SweepMemberResult[]> = {};
+  for (const member of members) {
+    grouped[member.combination] ??= [];
+  }
+  for (const result of [...results].sort((a, b) => a.repetition - b.repetition)) {
+    grouped[result.combination]?.push(result);
+  }
+  return { sweep_id: sweepId, parameter, results: grouped };
+}
@@ -0,0 +1,69 @@
+import type { Engine, Manifest } from "@/schema/model-manifest-schema";
+
+/**
+ * Advisory checks on a chat template's inference parameters. Nothing here blocks a request: the
+ * warnings explain why outputs the user expects to be repeatable (temperature 0, a fixed seed) still
+ * differ between runs.
+ */
+
+export type ParameterWarningCode = "top_p_with_zero_temperature" | "top_k_with_zero_temperature" | "no_seed_support" | "seed_without_zero_temperature";
+
+export interface ParameterWarning {
+  code: ParameterWarningCode;
+  message: string;
+  // Parameters involved, as named in the template config
+  fields: string[];
+}
+
+// Routers that pass the seed to whichever provider serves the request, several of which only honor
+// it with greedy decoding. Local backends seed their sampler at any temperature.
+const SEED_NEEDS_ZERO_TEMPERATURE: Engine[] = ["openrouter"];
+
+// A seed of -1 (or none) asks for a random one
+function hasFixedSeed(seed: unknown): seed is number {
+  return typeof seed === "number" && seed >= 0;
+}
+
+/**
+ * Warnings for parameter combinations that won't give the determinism the user likely expects.
+ * With a manifest, parameters its engine doesn't take are ignored and engine quirks are considered.
+ */
+export function validateParameters(parameters: Record<string, any>, manifest?: Pick<Manifest, "name" | "engine" | "inference_fields"> | null): ParameterWarning[] {
+  const supports = (field: string) => !manifest?.inference_fields || manifest.inference_fields.includes(field);
+  const temperature = supports("temperature") ? parameters.temperature : undefined;
+  const warnings: ParameterWarning[] = [];
+
+  if (temperature === 0) {
+    if (supports("top_p") && typeof parameters.top_p === "number" && parameters.top_p < 1) {
+      warnings.push({
+        code: "top_p_with_zero_temperature",
+        message: `Temperature 0 with Top P ${parameters.top_p}: some providers still sample among the remaining tokens, so replies can differ. Set Top P to 1 for repeatable output.`,
+        fields: ["temperature", "top_p"],
+      });
+    }
+    if (supports("top_k") && typeof parameters.top_k === "number" && parameters.top_k > 0) {
+      warnings.push({
+        code: "top_k_with_zero_temperature",
+        message: `Temperature 0 with Top K ${parameters.top_k}: some providers still sample among the top tokens, so replies can differ. Set Top K to 0 for repeatable output.`,
+        fields: ["temperature", "top_k"],
+      });
+    }
+    if (manifest && !supports("seed")) {
+      warnings.push({
+        code: "no_seed_support",
+        message: `${manifest.name} takes no seed, so even at temperature 0 replies are not guaranteed to repeat.`,
+        fields: ["temperature"],
+      });
+    }
+  }
+
+  if (hasFixedSeed(parameters.seed) && supports("seed") && temperature !== 0 && (!manifest || SEED_NEEDS_ZERO_TEMPERATURE.includes(manifest.engine))) {
+    warnings.push({
+      code: "seed_without_zero_temperature",
+      message: `Seed ${parameters.seed} with ${temperature === undefined ? "the default temperature" : `temperature ${temperature}`}: some providers ignore the seed unless temperature is 0.`,
+      fields: ["seed", "temperature"],
+    });
+  }
+
+  return warnings;
+}
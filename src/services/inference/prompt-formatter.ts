@@ -3,12 +3,17 @@ import { useChatActions, useCurrentChatActiveChapterID, useCurrentChatChapters,
 import { useModelManifests } from "@/hooks/manifestStore";
 import { useCurrentProfile } from "@/hooks/ProfileStore";
 import { Character } from "@/schema/characters-schema";
+import { ChatMarker } from "@/schema/chat-marker-schema";
 import { ChatMessage } from "@/schema/chat-message-schema";
 import { formatPrompt as formatPromptUtil } from "@/services/inference/formatter";
+import { applyContextReset } from "@/services/inference/formatter/apply-context-reset";
+import { createOutputGuard } from "@/services/inference/output-guard";
+import { verifySystemPromptLock } from "@/services/inference/system-prompt-lock";
 import { useLocalSummarySettings } from "@/utils/local-storage";
 import { listCharacters } from "../character-service";
+import { listChatMarkers } from "../chat-marker-service";
 import { ChatParticipant, getChatById } from "../chat-service";
-import { listModels } from "../model-service";
+import { findModelByIdOrAlias, listModels } from "../model-service";
 import { listChatTemplates } from "../template-chat-service";
 import { getFormatTemplateById } from "../template-format-service";
 import { listInferenceTemplates } from "../template-inference-service";
@@ -78,16 +83,20 @@ export function usePromptFormatter() {
 
   // Memoize message filtering and name assignment
   const processMessagesWithNames = useCallback(
-    async (messagesToUse: ChatMessage[] | undefined, characterList: Character[], userCharacterOrProfileName: string, excludeMessageId?: string) => {
+    async (messagesToUse: ChatMessage[] | undefined, characterList: Character[], userCharacterOrProfileName: string, markers: ChatMarker[], excludeMessageId?: string) => {
       const characterNameMap = await createCharacterNameMap(characterList);
+      const messages = messagesToUse || chatMessages;
 
-      return (messagesToUse || chatMessages)
-        ?.map((msg) => ({
+      // When regenerating, only reset markers placed before the regenerated message apply
+      const excludedPosition = messages?.find((msg) => msg.id === excludeMessageId)?.position;
+
+      return applyContextReset(messages || [], markers, excludedPosition)
+        .map((msg) => ({
           ...msg,
           character_name: msg.character_id ? characterNameMap.get(msg.character_id) : msg.type === "user" ? userCharacterOrProfileName : undefined,
         }))
-        ?.filter((msg) => msg.id !== excludeMessageId)
-        ?.filter((msg) => !msg.disabled);
+        .filter((msg) => msg.id !== excludeMessageId)
+        .filter((msg) => !msg.disabled);
     },
     [chatMessages, createCharacterNameMap],
   );
@@ -106,12 +115,13 @@ export function usePromptFormatter() {
       excludeMessageId?: string,
     ) => {
       // Batch all async operations for better performance
-      const [currentChat, chatTemplateList, modelList, characterList, inferenceTemplateList] = await Promise.all([
+      const [currentChat, chatTemplateList, modelList, characterList, inferenceTemplateList, markers] = await Promise.all([
         getChatById(currentChatId),
         memoizedDataFetchers.getChatTemplates(),
         memoizedDataFetchers.getModels(),
         memoizedDataFetchers.getCharacters(),
         memoizedDataFetchers.getInferenceTemplates(),
+        listChatMarkers(currentChatId),
       ]);
 
       const chatTemplate = chatTemplateID ? chatTemplateList.find((template) => template.id === chatTemplateID)! : chatTemplateList.find((template) => template.id === currentChat?.chat_template_id)!;
@@ -120,7 +130,7 @@ export function usePromptFormatter() {
         throw new Error("Chat template not found");
       }
 
-      const modelSettings = modelList.find((model) => model.id === chatTemplate.model_id)!;
+      const modelSettings = findModelByIdOrAlias(modelList, chatTemplate.model_id)!;
 
       if (!modelSettings) {
         throw new Error(`Model settings for chat template ${chatTemplate.name} not found`);
@@ -136,6 +146,8 @@ export function usePromptFormatter() {
         throw new Error(`Format template for chat template ${chatTemplate.name} not found`);
       }
 
+      await verifySystemPromptLock(currentChatId, currentChat?.settings, { formatTemplate, customPrompts: chatTemplate.custom_prompts });
+
       const inferenceTemplate = inferenceTemplateList.find((template) => template.id === modelSettings.inference_template_id)!;
 
       // Get the user character name or the profile name
@@ -143,7 +155,7 @@ export function usePromptFormatter() {
       const userCharacterOrProfileName = userCharacter?.name || currentProfile?.name;
 
       // Process messages with optimized character name mapping
-      const chatWithNames = await processMessagesWithNames(messagesToUse, characterList, userCharacterOrProfileName, excludeMessageId);
+      const chatWithNames = await processMessagesWithNames(messagesToUse, characterList, userCharacterOrProfileName, markers, excludeMessageId);
 
       const characterPromptOverride = characterList.find((character) => character.id === characterId)?.system_override;
       const character = characterList.find((character) => character.id === characterId);
@@ -157,6 +169,7 @@ export function usePromptFormatter() {
         messageHistory: chatWithNames || [],
         userPrompt: userMessage,
         systemOverridePrompt: systemPromptOverride || characterPromptOverride || undefined,
+        profileId: currentProfile.id,
         modelSettings,
         formatTemplate,
         inferenceTemplate,
@@ -172,15 +185,23 @@ export function usePromptFormatter() {
           character,
           user_character: (userCharacter as Character) || { name: userCharacterOrProfileName, custom: { personality: "" } },
           chapter: chapterList.find((chapter) => chapter.id === currentChapterID),
+          chat: currentChat ? { name: currentChat.name, synopsis: currentChat.synopsis } : undefined,
           extra: chatExtra,
           censorship: {
             words: formatTemplate.config.settings.apply_censorship ? currentProfile?.settings?.censorship?.customWords : [],
           },
+          dateTime: currentProfile?.settings?.chat?.dateTimeMacros,
         },
       });
 
+      const otherParticipantNames = (participantsList || [])
+        .filter((participant) => participant.enabled && participant.id !== characterId)
+        .map((participant) => characterList.find((entry) => entry.id === participant.id)?.name)
+        .filter((name): name is string => !!name);
+
       return {
         ...prompt,
+        outputGuard: createOutputGuard(currentChat?.settings, character?.name, [userCharacterOrProfileName, ...otherParticipantNames].filter((name): name is string => !!name)),
         manifestSettings,
         modelSettings,
         chatTemplate,
@@ -0,0 +1,101 @@
+import type { InferenceLogEntry } from "@/commands/inference";
+import type { ConsoleRequest } from "@/hooks/consoleStore";
+import type { InferenceMessage } from "@/schema/inference-engine-schema";
+import type { Engine } from "@/schema/model-manifest-schema";
+
+/**
+ * "Why did the model say that": the prompt behind a past message. Sources, most exact first:
+ * the in-memory debug captures of the console, then the inference log when the profile stores
+ * prompts in it. Without either, the caller rebuilds it from the current chat data and marks the
+ * snapshot `reconstructed`, since templates, characters and lorebooks may have changed since.
+ */
+
+export interface PromptSnapshot {
+  message_id: string;
+  // Null for messages generated before requests were recorded on them
+  request_id: string | null;
+  system_prompt: string;
+  messages: InferenceMessage[];
+  parameters: Record<string, unknown>;
+  model: { id: string; name?: string; actual_model?: string; engine?: string };
+  reconstructed: boolean;
+  warnings: string[];
+}
+
+// Same list as the inference log writer (request_log.rs)
+const SENSITIVE_KEYS = ["api_key", "apikey", "secret", "password", "authorization", "access_token", "bearer"];
+
+/**
+ * Copy of the value with every secret-looking key replaced by "[redacted]", at any depth
+ */
+export function redactSecrets<T>(value: T): T {
+  if (Array.isArray(value)) {
+    return value.map(redactSecrets) as T;
+  }
+  if (typeof value !== "object" || value === null) {
+    return value;
+  }
+  return Object.fromEntries(
+    Object.entries(value).map(([key, entry]) => [key, SENSITIVE_KEYS.some((sensitive) => key.toLowerCase().includes(sensitive)) ? "[redacted]" : redactSecrets(entry)]),
+  ) as T;
+}
+
+export function snapshotFromCapture(messageId: string, capture: ConsoleRequest): PromptSnapshot {
+  return {
+    message_id: messageId,
+    request_id: capture.id,
+    system_prompt: capture.systemPrompt,
+    messages: capture.messages,
+    parameters: redactSecrets(capture.parameters),
+    model: {
+      id: capture.modelSpecs.id,
+      actual_model: typeof capture.modelSpecs.config?.model === "string" ? capture.modelSpecs.config.model : undefined,
+      engine: capture.engine,
+    },
+    reconstructed: false,
+    warnings: [],
+  };
+}
+
+/**
+ * Snapshot from a log entry, or null when the entry was written without its prompt
+ */
+export function snapshotFromLogEntry(messageId: string, entry: InferenceLogEntry): PromptSnapshot | null {
+  if (!entry.prompt) {
+    return null;
+  }
+  return {
+    message_id: messageId,
+    request_id: entry.request_id,
+    system_prompt: entry.prompt.system_prompt,
+    messages: entry.prompt.messages,
+    parameters: redactSecrets(entry.params),
+    model: { id: entry.model_id, actual_model: entry.actual_model, engine: entry.engine },
+    reconstructed: false,
+    warnings: [],
+  };
+}
+
+/**
+ * Console request for a snapshot, so the Live Inspector shows it like a live capture
+ */
+export function snapshotToConsoleRequest(snapshot: PromptSnapshot, response?: string): Omit<ConsoleRequest, "timestamp"> {
+  return {
+    // Its own id, so a capture of the same request isn't replaced
+    id: `prompt_${snapshot.message_id}`,
+    systemPrompt: snapshot.system_prompt,
+    messages: snapshot.messages,
+    modelSpecs: {
+      id: snapshot.model.id,
+      model_type: "chat",
+      config: { model: snapshot.model.actual_model },
+      max_concurrent_requests: 1,
+      engine: snapshot.model.engine ?? "",
+    },
+    parameters: snapshot.parameters,
+    engine: (snapshot.model.engine ?? "") as Engine,
+    fullResponse: response,
+    reconstructed: snapshot.reconstructed,
+    warnings: snapshot.warnings,
+  };
+}
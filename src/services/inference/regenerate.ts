@@ -0,0 +1,81 @@
+import { emitToCurrentWindow, listenInCurrentWindow } from "@/commands/windows";
+import type { ChatMessage } from "@/schema/chat-message-schema";
+import { getChatMessageById } from "@/services/chat-message-service";
+import { getChatById } from "@/services/chat-service";
+
+// Regenerating a character message: "replace" rewrites the version on screen, "add_version" streams into a new
+// version (swipe) after the last one. The history before the message, the prompt and the parameters are rebuilt by
+// `generateMessage` the same way as for the original reply.
+
+const MESSAGE_REGENERATED_EVENT = "message-regenerated";
+
+type RegenerateMode = "replace" | "add_version";
+
+interface MessageRegeneratedPayload {
+  chatId: string;
+  messageId: string;
+  // The version that was written
+  messageIndex: number;
+  mode: RegenerateMode;
+  requestId: string;
+  text: string;
+}
+
+type RegenerateErrorReason = "chat_not_found" | "message_not_found" | "not_a_character_message";
+
+class RegenerateError extends Error {
+  readonly reason: RegenerateErrorReason;
+
+  constructor(reason: RegenerateErrorReason, message: string) {
+    super(message);
+    this.name = "RegenerateError";
+    this.reason = reason;
+  }
+}
+
+/**
+ * Load the message to regenerate, checking the chat belongs to the profile and the message to the chat
+ * @throws {RegenerateError} When either is missing or the message wasn't written by a character
+ */
+async function loadRegenerationTarget(messageId: string, chatId: string, profileId: string): Promise<ChatMessage> {
+  const chat = await getChatById(chatId, profileId);
+  if (!chat) {
+    throw new RegenerateError("chat_not_found", "The chat of this message no longer exists");
+  }
+
+  const message = await getChatMessageById(messageId);
+  if (!message || message.chat_id !== chatId) {
+    throw new RegenerateError("message_not_found", "The message no longer exists");
+  }
+  if (message.type !== "character" || !message.character_id) {
+    throw new RegenerateError("not_a_character_message", "Only character messages can be regenerated");
+  }
+  return message;
+}
+
+/**
+ * Version a regeneration writes: a new one after the last for "add_version", otherwise the requested one or the
+ * one on screen
+ */
+function regenerationIndex(message: Pick<ChatMessage, "messages" | "message_index">, mode: RegenerateMode, messageIndex?: number): number {
+  if (mode === "add_version") {
+    return message.messages.length;
+  }
+  const last = Math.max(message.messages.length - 1, 0);
+  return Math.min(Math.max(messageIndex ?? message.message_index, 0), last);
+}
+
+function emitMessageRegenerated(payload: MessageRegeneratedPayload) {
+  emitToCurrentWindow(MESSAGE_REGENERATED_EVENT, payload).catch((error) => console.error("Failed to emit message regenerated event:", error));
+}
+
+/**
+ * Subscribe to the regenerations completed in this window
+ * @returns A function that removes the listener
+ */
+function onMessageRegenerated(callback: (payload: MessageRegeneratedPayload) => void): Promise<() => void> {
+  return listenInCurrentWindow<MessageRegeneratedPayload>(MESSAGE_REGENERATED_EVENT, (event) => callback(event.payload));
+}
+
+export type { MessageRegeneratedPayload, RegenerateErrorReason, RegenerateMode };
+export { emitMessageRegenerated, loadRegenerationTarget, MESSAGE_REGENERATED_EVENT, onMessageRegenerated, RegenerateError, regenerationIndex };
@@ -0,0 +1,54 @@
+/**
+ * Double-send protection: a request reusing the id of one still running, or a chat request with the same model,
+ * prompt and parameters as one submitted less than the profile's `dedupeWindowMs` ago and still running, is
+ * refused with DUPLICATE_REQUEST instead of being billed twice. Callers ignore that code rather than reporting
+ * an error.
+ */
+
+const recentRequests = new Map<string, { requestId: string; submittedAt: number }>();
+
+/**
+ * SHA-256 of everything that makes two requests identical, as lowercase hex
+ */
+export async function requestFingerprint(content: unknown): Promise<string> {
+  const digest = await crypto.subtle.digest("SHA-256", new TextEncoder().encode(JSON.stringify(content)));
+  return Array.from(new Uint8Array(digest), (byte) => byte.toString(16).padStart(2, "0")).join("");
+}
+
+/**
+ * Remember a submitted request, unless an identical one was submitted within the window.
+ * @returns The id of that earlier request, or null when this one may run
+ */
+export function claimRequest(fingerprint: string, requestId: string, windowMs: number, now = Date.now()): string | null {
+  if (windowMs <= 0) {
+    return null;
+  }
+
+  for (const [key, entry] of recentRequests) {
+    if (now - entry.submittedAt >= windowMs) {
+      recentRequests.delete(key);
+    }
+  }
+
+  const earlier = recentRequests.get(fingerprint);
+  if (earlier) {
+    return earlier.requestId;
+  }
+  recentRequests.set(fingerprint, { requestId, submittedAt: now });
+  return null;
+}
+
+/**
+ * Forget a finished request, so sending the same prompt again (a regenerate after a cancel) isn't refused
+ */
+export function releaseRequest(requestId: string) {
+  for (const [key, entry] of recentRequests) {
+    if (entry.requestId === requestId) {
+      recentRequests.delete(key);
+    }
+  }
+}
+
+export function clearRecentRequests() {
+  recentRequests.clear();
+}
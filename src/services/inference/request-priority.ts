@@ -0,0 +1,66 @@
+/**
+ * Request priorities: every queue (per model and global) starts interactive requests (the user waits on the
+ * reply) before normal ones (agents) and those before background jobs (summaries, titles). Waiting raises a
+ * request's priority one level every PRIORITY_AGING_MS, past interactive if needed, so background jobs still
+ * run under sustained interactive load.
+ *
+ * Focused chat boost: among requests of the same priority, the chat the user is looking at goes first. The
+ * focus is read on every dequeue, so switching chats re-orders what is already waiting.
+ */
+
+export type RequestPriority = "interactive" | "normal" | "background";
+
+export const PRIORITY_AGING_MS = 15_000;
+
+const PRIORITY_RANK: Record<RequestPriority, number> = {
+  interactive: 0,
+  normal: 1,
+  background: 2,
+};
+
+let focusedChatId: string | null = null;
+
+// Called on navigation; null when no chat is open
+export function setFocusedChat(chatId: string | null) {
+  focusedChatId = chatId || null;
+}
+
+export function getFocusedChat(): string | null {
+  return focusedChatId;
+}
+
+export interface PrioritizedRequest {
+  chatId?: string;
+  // Defaults to "normal"
+  priority?: RequestPriority;
+  // Date.now() when queued; without it the request doesn't age
+  enqueuedAt?: number;
+}
+
+// Lower starts first
+function effectiveRank(entry: PrioritizedRequest, now: number): number {
+  const waited = entry.enqueuedAt === undefined ? 0 : Math.max(0, now - entry.enqueuedAt);
+  return PRIORITY_RANK[entry.priority ?? "normal"] - Math.floor(waited / PRIORITY_AGING_MS);
+}
+
+/**
+ * Remove and return the next queued entry to start: the one with the highest priority after aging, the focused
+ * chat's first among equals, otherwise the oldest.
+ */
+export function takeNextRequest<T extends PrioritizedRequest>(queue: T[], now = Date.now()): T | undefined {
+  let bestIndex = -1;
+  let bestRank = Number.POSITIVE_INFINITY;
+  let bestFocused = false;
+
+  queue.forEach((entry, index) => {
+    const rank = effectiveRank(entry, now);
+    const focused = focusedChatId !== null && entry.chatId === focusedChatId;
+    if (rank < bestRank || (rank === bestRank && focused && !bestFocused)) {
+      bestIndex = index;
+      bestRank = rank;
+      bestFocused = focused;
+    }
+  });
+
+  return bestIndex < 0 ? undefined : queue.splice(bestIndex, 1)[0];
+}
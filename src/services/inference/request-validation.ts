@@ -0,0 +1,83 @@
+import { INFERENCE_MESSAGE_ROLES, type InferenceMessage } from "@/schema/inference-engine-schema";
+import { INVALID_REQUEST } from "@/services/ai-providers/types/ai-event.type";
+
+/**
+ * Shape checks run by `runInference` before a request is tracked or queued, so a malformed request fails the
+ * same way on every engine instead of deep inside one provider's message conversion (or mid-stream).
+ */
+
+// Far above any context window, so only a runaway caller hits it
+export const MAX_INFERENCE_MESSAGES = 5000;
+
+export type InvalidRequestReason = "invalid_role" | "empty_id" | "too_many_messages" | "invalid_parameters";
+
+/**
+ * Thrown by `runInference` for a request that can't be sent to any provider
+ */
+export class InvalidInferenceRequestError extends Error {
+  readonly code = INVALID_REQUEST;
+  readonly retryable = false;
+  readonly reason: InvalidRequestReason;
+  readonly details?: Record<string, unknown>;
+
+  constructor(reason: InvalidRequestReason, message: string, details?: Record<string, unknown>) {
+    super(message);
+    this.name = "InvalidInferenceRequest";
+    this.reason = reason;
+    this.details = details;
+  }
+}
+
+export interface ValidatedRequest {
+  requestId?: string;
+  messages: InferenceMessage[];
+  examples?: InferenceMessage[];
+  parameters?: unknown;
+}
+
+const ROLES = new Set<string>(INFERENCE_MESSAGE_ROLES);
+
+function checkMessages(messages: InferenceMessage[], list: "messages" | "examples") {
+  messages.forEach((message, index) => {
+    if (!ROLES.has(message.role)) {
+      throw new InvalidInferenceRequestError("invalid_role", `Message ${index} of ${list} has the role "${String(message.role)}", expected one of ${INFERENCE_MESSAGE_ROLES.join(", ")}`, {
+        list,
+        index,
+        role: message.role,
+      });
+    }
+    if (message.role === "tool" && !message.tool_call_id?.trim()) {
+      throw new InvalidInferenceRequestError("empty_id", `Tool message ${index} of ${list} has no tool call id`, { list, index });
+    }
+    message.tool_calls?.forEach((call, callIndex) => {
+      if (call.id !== undefined && !call.id.trim()) {
+        throw new InvalidInferenceRequestError("empty_id", `Tool call ${callIndex} of message ${index} of ${list} has an empty id`, { list, index, tool_call: callIndex });
+      }
+    });
+  });
+}
+
+/**
+ * Throws an InvalidInferenceRequestError for the first problem found
+ */
+export function validateInferenceRequest(request: ValidatedRequest) {
+  if (request.requestId !== undefined && !request.requestId.trim()) {
+    throw new InvalidInferenceRequestError("empty_id", "The request id is empty");
+  }
+
+  const total = request.messages.length + (request.examples?.length ?? 0);
+  if (total > MAX_INFERENCE_MESSAGES) {
+    throw new InvalidInferenceRequestError("too_many_messages", `The request has ${total} messages, at most ${MAX_INFERENCE_MESSAGES} are sent`, {
+      count: total,
+      limit: MAX_INFERENCE_MESSAGES,
+    });
+  }
+
+  const { parameters } = request;
+  if (parameters !== undefined && (typeof parameters !== "object" || parameters === null || Array.isArray(parameters))) {
+    throw new InvalidInferenceRequestError("invalid_parameters", "The request parameters must be an object");
+  }
+
+  checkMessages(request.messages, "messages");
+  checkMessages(request.examples ?? [], "examples");
+}
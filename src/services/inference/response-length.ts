@@ -0,0 +1,39 @@
+import type { ResponseLengthPresets } from "@/schema/profiles-schema";
+
+export const RESPONSE_LENGTHS = ["short", "medium", "long", "unlimited"] as const;
+export type ResponseLength = (typeof RESPONSE_LENGTHS)[number];
+
+export function isResponseLength(value: unknown): value is ResponseLength {
+  return typeof value === "string" && (RESPONSE_LENGTHS as readonly string[]).includes(value);
+}
+
+/**
+ * Resolves the `response_length` preset of the parameters into an effective max_tokens and a prompt nudge.
+ * The preset replaces the template's Response Length, but an explicit max_tokens from the caller always wins.
+ * Parameters without a known preset are returned unchanged.
+ */
+export function applyResponseLength(
+  parameters: Record<string, any>,
+  presets: ResponseLengthPresets,
+  explicitMaxTokens?: number,
+): { parameters: Record<string, any>; instruction: string } {
+  const responseLength = parameters.response_length;
+  if (!isResponseLength(responseLength)) {
+    return { parameters, instruction: "" };
+  }
+
+  const preset = presets[responseLength];
+  const maxTokens = explicitMaxTokens || preset.max_tokens || parameters.max_tokens;
+
+  return {
+    parameters: maxTokens ? { ...parameters, max_tokens: maxTokens } : parameters,
+    instruction: preset.instruction.trim(),
+  };
+}
+
+export function appendResponseLengthInstruction(systemPrompt: string | undefined, instruction: string): string | undefined {
+  if (!instruction) {
+    return systemPrompt;
+  }
+  return systemPrompt?.trim() ? `${systemPrompt}\n\n${instruction}` : instruction;
+}
@@ -0,0 +1,63 @@
+// What each in-flight request of this window has streamed so far, so a view that mounts mid-generation (a chat
+// reopened, a remounted widget, a hot reload of a component) can show the text and append the next chunks to it.
+// Kept at module level, outside any hook instance; a full page reload ends the requests along with it.
+// Snapshots are dropped when their request finishes. Each keeps at most MAX_SNAPSHOT_CHARS, the most recent text.
+
+const MAX_SNAPSHOT_CHARS = 100_000;
+
+type SnapshotStatus = "queued" | "started" | "streaming";
+
+interface ActiveRequestSnapshot {
+  requestId: string;
+  chatId: string | null;
+  status: SnapshotStatus;
+  text: string;
+  // Characters received in total; more than text.length once the start was dropped
+  length: number;
+  truncated: boolean;
+  updatedAt: number;
+}
+
+const snapshots = new Map<string, ActiveRequestSnapshot>();
+
+function trackRequestSnapshot(requestId: string, chatId: string | null, now = Date.now()) {
+  snapshots.set(requestId, { requestId, chatId, status: "queued", text: "", length: 0, truncated: false, updatedAt: now });
+}
+
+function setSnapshotStatus(requestId: string, status: SnapshotStatus, now = Date.now()) {
+  const snapshot = snapshots.get(requestId);
+  if (snapshot) {
+    snapshot.status = status;
+    snapshot.updatedAt = now;
+  }
+}
+
+function appendSnapshotText(requestId: string, text: string, now = Date.now()) {
+  const snapshot = snapshots.get(requestId);
+  if (!snapshot) {
+    return;
+  }
+  snapshot.status = "streaming";
+  snapshot.updatedAt = now;
+  snapshot.length += text.length;
+  snapshot.text += text;
+  if (snapshot.text.length > MAX_SNAPSHOT_CHARS) {
+    snapshot.text = snapshot.text.slice(-MAX_SNAPSHOT_CHARS);
+    snapshot.truncated = true;
+  }
+}
+
+function dropRequestSnapshot(requestId: string) {
+  snapshots.delete(requestId);
+}
+
+/**
+ * The requests still running in this window, oldest first, with the text each streamed so far
+ * @param chatId Only the requests of this chat
+ */
+function getActiveRequestSnapshots(chatId?: string): ActiveRequestSnapshot[] {
+  return [...snapshots.values()].filter((snapshot) => chatId === undefined || snapshot.chatId === chatId).map((snapshot) => ({ ...snapshot }));
+}
+
+export type { ActiveRequestSnapshot, SnapshotStatus };
+export { appendSnapshotText, dropRequestSnapshot, getActiveRequestSnapshots, MAX_SNAPSHOT_CHARS, setSnapshotStatus, trackRequestSnapshot };
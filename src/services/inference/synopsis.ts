@@ -0,0 +1,175 @@
+import { useCallback } from "react";
+import { useChatStore } from "@/hooks/chatStore";
+import { useCurrentProfile } from "@/hooks/ProfileStore";
+import { type ChatSynopsisSettings, chatSynopsisSettingsSchema } from "@/schema/chat-schema";
+import { useBackgroundInference } from "../background-inference-service";
+import { listCharacters } from "../character-service";
+import { countMessagesAfterSequence, listMessagesAfterSequence, type SequencedChatMessage } from "../chat-message-service";
+import { type Chat, getChatById, setChatSynopsis } from "../chat-service";
+import { getModelByIdOrAlias } from "../model-service";
+import { getChatTemplateById } from "../template-chat-service";
+
+export const SYNOPSIS_SYSTEM_PROMPT = `You maintain the synopsis of an ongoing roleplay chat.
+You get the current synopsis and the messages written since it was last updated. Reply with the updated synopsis only.
+Keep it a single, always-current summary of the whole story: who the characters are, where they are, what happened and what is unresolved.
+Fold the new events in, drop details that no longer matter, and keep it under 400 words. Write in the present tense, without notes or headings.`;
+
+// Messages folded in per model call, a long backlog takes several calls
+export const SYNOPSIS_BATCH_SIZE = 60;
+
+export function getSynopsisSettings(chat: Pick<Chat, "settings"> | null | undefined): ChatSynopsisSettings {
+  return chatSynopsisSettingsSchema.parse(chat?.settings?.synopsis ?? {});
+}
+
+/**
+ * Prompt folding `messages` into the current synopsis
+ */
+export function buildSynopsisPrompt(synopsis: string | null | undefined, messages: SequencedChatMessage[], nameOf: (message: SequencedChatMessage) => string): string {
+  const transcript = messages
+    .filter((message) => !message.excluded && message.text.trim())
+    .map((message) => `${nameOf(message)}: ${message.text.trim()}`)
+    .join("\n\n");
+
+  return `Current synopsis:\n${synopsis?.trim() || "(none yet, this is the start of the chat)"}\n\nNew messages:\n${transcript}`;
+}
+
+// One refresh per chat at a time, a second caller waits for the running one
+const refreshesInFlight = new Map<string, Promise<string | null>>();
+
+// Keep the open chat in the store in step with what was saved
+function syncSelectedChat(chatId: string, changes: Pick<Chat, "synopsis" | "synopsis_up_to_sequence">) {
+  const state = useChatStore.getState();
+  if (state.selectedChat?.id === chatId) {
+    useChatStore.setState({ selectedChat: { ...state.selectedChat, ...changes, synopsis_updated_at: new Date() } });
+  }
+}
+
+/**
+ * Hook keeping the rolling synopsis of chats, built on background inference.
+ */
+export function useChatSynopsis() {
+  const currentProfile = useCurrentProfile();
+  const { executeInference } = useBackgroundInference();
+
+  const runRefresh = useCallback(
+    async (chatId: string, modelId?: string): Promise<string | null> => {
+      if (!currentProfile) {
+        throw new Error("No profile selected");
+      }
+
+      const chat = await getChatById(chatId, currentProfile.id);
+      if (!chat) {
+        throw new Error(`Chat ${chatId} not found`);
+      }
+
+      const settings = getSynopsisSettings(chat);
+      const chatTemplate = chat.chat_template_id ? await getChatTemplateById(chat.chat_template_id).catch(() => null) : null;
+      const resolvedModelId = modelId || settings.model_id || chatTemplate?.model_id;
+      const model = resolvedModelId ? await getModelByIdOrAlias(resolvedModelId, currentProfile.id) : null;
+      if (!model) {
+        throw new Error("No model to write the synopsis with, set one in the chat template or the synopsis settings");
+      }
+
+      const characters = await listCharacters(currentProfile.id);
+      const namesById = new Map(characters.map((character) => [character.id, character.name]));
+      const userName = (chat.user_character_id && namesById.get(chat.user_character_id)) || currentProfile.name;
+      const nameOf = (message: SequencedChatMessage) => {
+        if (message.type === "user") {
+          return userName;
+        }
+        return (message.character_id && namesById.get(message.character_id)) || (message.type === "system" ? "Narrator" : "Character");
+      };
+
+      let synopsis = chat.synopsis ?? null;
+      let upToSequence = chat.synopsis_up_to_sequence ?? 0;
+
+      for (;;) {
+        const messages = await listMessagesAfterSequence(chatId, upToSequence, SYNOPSIS_BATCH_SIZE);
+        if (messages.length === 0) {
+          break;
+        }
+        const lastSequence = messages[messages.length - 1].sequence;
+
+        // Batches with nothing to tell (disabled or excluded messages only) just move the refresh point
+        if (messages.some((message) => !message.excluded && message.text.trim())) {
+          const response = await executeInference({
+            model,
+            prompt: [{ role: "user", text: buildSynopsisPrompt(synopsis, messages, nameOf) }],
+            systemPrompt: SYNOPSIS_SYSTEM_PROMPT,
+            parameters: { temperature: 0.3 },
+          });
+          const updated = response.trim();
+          if (!updated) {
+            throw new Error("The model returned an empty synopsis");
+          }
+          synopsis = updated;
+        }
+
+        upToSequence = lastSequence;
+        await setChatSynopsis(chatId, currentProfile.id, synopsis, upToSequence);
+      }
+
+      syncSelectedChat(chatId, { synopsis, synopsis_up_to_sequence: upToSequence });
+      return synopsis;
+    },
+    [currentProfile, executeInference],
+  );
+
+  /**
+   * Fold the messages written since the last refresh into the chat's synopsis and store it.
+   * Resolves with the synopsis, unchanged when nothing new was written. Rejects on failure.
+   * @param modelId Model to use instead of the one in the synopsis settings or chat template
+   */
+  const refreshChatSynopsis = useCallback(
+    (chatId: string, modelId?: string): Promise<string | null> => {
+      const running = refreshesInFlight.get(chatId);
+      if (running) {
+        return running;
+      }
+      const refresh = runRefresh(chatId, modelId).finally(() => refreshesInFlight.delete(chatId));
+      refreshesInFlight.set(chatId, refresh);
+      return refresh;
+    },
+    [runRefresh],
+  );
+
+  /**
+   * Refresh the synopsis when the chat has it on auto refresh and enough messages were added since
+   * the last one. Returns whether a refresh ran.
+   */
+  const refreshChatSynopsisIfDue = useCallback(
+    async (chatId: string): Promise<boolean> => {
+      if (!currentProfile || refreshesInFlight.has(chatId)) {
+        return false;
+      }
+      const chat = await getChatById(chatId, currentProfile.id);
+      const settings = getSynopsisSettings(chat);
+      if (!chat || !settings.auto_refresh) {
+        return false;
+      }
+      if ((await countMessagesAfterSequence(chatId, chat.synopsis_up_to_sequence ?? 0)) < settings.refresh_every) {
+        return false;
+      }
+      await refreshChatSynopsis(chatId);
+      return true;
+    },
+    [currentProfile, refreshChatSynopsis],
+  );
+
+  /**
+   * Replace the synopsis by hand. The refresh point stays, so the next refresh builds on this text.
+   */
+  const editChatSynopsis = useCallback(
+    async (chatId: string, synopsis: string): Promise<void> => {
+      if (!currentProfile) {
+        throw new Error("No profile selected");
+      }
+      const text = synopsis.trim() || null;
+      await setChatSynopsis(chatId, currentProfile.id, text);
+      syncSelectedChat(chatId, { synopsis: text });
+    },
+    [currentProfile],
+  );
+
+  return { refreshChatSynopsis, refreshChatSynopsisIfDue, editChatSynopsis };
+}
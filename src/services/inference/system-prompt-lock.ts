@@ -0,0 +1,176 @@
+import { emitToCurrentWindow } from "@/commands/windows";
+import type { Chat, ChatDisplaySettings } from "@/schema/chat-schema";
+import type { ChatTemplateCustomPrompt } from "@/schema/template-chat-schema";
+import type { FormatTemplate } from "@/schema/template-format-schema";
+import { getChatTemplateById } from "@/services/template-chat-service";
+import { getFormatTemplateById } from "@/services/template-format-service";
+import { createSystemPrompt } from "./formatter";
+
+/**
+ * Locked system prompt: a chat can pin the rules its system prompt is built from, so an edit to the
+ * format template or to the chat template's system prompts doesn't silently change a long roleplay.
+ *
+ * The rules are the system prompt before placeholders are replaced, with every section kept: character,
+ * chapter and lorebook contents change from one message to the next, the rules around them don't.
+ * Scripted prompts and the character's system override are left out, since they legitimately vary
+ * within a chat (the override follows whoever speaks in a group chat).
+ */
+
+const PROMPT_DRIFT_EVENT = "prompt-drift-detected";
+
+interface PromptDriftSummary {
+  addedLines: number;
+  removedLines: number;
+  // First line, 1-based, where the current rules differ from the locked ones
+  firstChangedLine: number | null;
+}
+
+interface PromptDriftPayload extends PromptDriftSummary {
+  chatId: string;
+  expectedHash: string;
+  actualHash: string;
+  // Whether generation was refused
+  blocked: boolean;
+}
+
+interface SystemPromptRulesSource {
+  formatTemplate?: FormatTemplate | null;
+  customPrompts?: ChatTemplateCustomPrompt[];
+}
+
+/**
+ * The text a lock is taken from, see the top of this file
+ */
+function buildSystemPromptRules({ formatTemplate, customPrompts }: SystemPromptRulesSource): string {
+  const contextSeparator = formatTemplate?.config.context_separator?.replaceAll("\\n", "\n");
+  return createSystemPrompt({ systemPromptTemplate: formatTemplate, customPrompts, contextSeparator, keepAllSections: true }) ?? "";
+}
+
+/**
+ * SHA-256 of the text, as lowercase hex
+ */
+async function hashSystemPrompt(text: string): Promise<string> {
+  const digest = await crypto.subtle.digest("SHA-256", new TextEncoder().encode(text));
+  return Array.from(new Uint8Array(digest), (byte) => byte.toString(16).padStart(2, "0")).join("");
+}
+
+/**
+ * Line counts of what changed between the locked rules and the current ones. Lines are compared as
+ * multisets, so a moved line counts as removed and added only when its count changes.
+ */
+function summarizePromptDrift(baseline: string, current: string): PromptDriftSummary {
+  const baselineLines = baseline.split("\n");
+  const currentLines = current.split("\n");
+
+  const remaining = new Map<string, number>();
+  for (const line of baselineLines) {
+    remaining.set(line, (remaining.get(line) ?? 0) + 1);
+  }
+  let addedLines = 0;
+  for (const line of currentLines) {
+    const count = remaining.get(line) ?? 0;
+    if (count > 0) {
+      remaining.set(line, count - 1);
+    } else {
+      addedLines++;
+    }
+  }
+  const removedLines = [...remaining.values()].reduce((total, count) => total + count, 0);
+
+  let firstChangedLine: number | null = null;
+  for (let index = 0; index < Math.max(baselineLines.length, currentLines.length); index++) {
+    if (baselineLines[index] !== currentLines[index]) {
+      firstChangedLine = index + 1;
+      break;
+    }
+  }
+
+  return { addedLines, removedLines, firstChangedLine };
+}
+
+/**
+ * Checks the current rules against the chat's lock. On a mismatch, emits `prompt-drift-detected` with a
+ * summary of the change, then throws when the lock is strict. Unlocked chats pass without hashing.
+ */
+async function verifySystemPromptLock(chatId: string, settings: ChatDisplaySettings | null | undefined, source: SystemPromptRulesSource): Promise<void> {
+  if (!settings?.system_prompt_locked || !settings.system_prompt_hash) {
+    return;
+  }
+
+  const rules = buildSystemPromptRules(source);
+  const actualHash = await hashSystemPrompt(rules);
+  if (actualHash === settings.system_prompt_hash) {
+    return;
+  }
+
+  const blocked = !!settings.system_prompt_lock_strict;
+  const summary = summarizePromptDrift(settings.system_prompt_baseline ?? "", rules);
+  const payload: PromptDriftPayload = { chatId, expectedHash: settings.system_prompt_hash, actualHash, blocked, ...summary };
+  await emitToCurrentWindow(PROMPT_DRIFT_EVENT, payload).catch((error) => console.error("Failed to emit prompt drift event:", error));
+
+  if (blocked) {
+    throw new Error(
+      `The system prompt changed since it was locked (${summary.addedLines} lines added, ${summary.removedLines} removed). Unlock it or take a new baseline to keep generating.`,
+    );
+  }
+}
+
+async function loadRulesSource(chat: Pick<Chat, "chat_template_id">): Promise<SystemPromptRulesSource> {
+  const chatTemplate = chat.chat_template_id ? await getChatTemplateById(chat.chat_template_id) : null;
+  if (!chatTemplate) {
+    throw new Error("The chat has no chat template to lock the system prompt of");
+  }
+  const formatTemplate = chatTemplate.format_template_id ? await getFormatTemplateById(chatTemplate.format_template_id) : null;
+  return { formatTemplate, customPrompts: chatTemplate.custom_prompts };
+}
+
+/**
+ * Chat settings with the system prompt locked to the chat's current templates.
+ * Save them through the chat store so the open chat sees the lock.
+ */
+async function lockSystemPrompt(chat: Pick<Chat, "chat_template_id" | "settings">, strict = false): Promise<ChatDisplaySettings> {
+  const rules = buildSystemPromptRules(await loadRulesSource(chat));
+  return {
+    hideDisabledMessages: false,
+    hideScriptMessages: false,
+    ...chat.settings,
+    system_prompt_locked: true,
+    system_prompt_hash: await hashSystemPrompt(rules),
+    system_prompt_baseline: rules,
+    system_prompt_lock_strict: strict,
+  };
+}
+
+/**
+ * Chat settings with the lock taken again from the current templates, keeping its strictness,
+ * to accept a template edit
+ */
+async function rebaselineSystemPrompt(chat: Pick<Chat, "chat_template_id" | "settings">): Promise<ChatDisplaySettings> {
+  return lockSystemPrompt(chat, !!chat.settings?.system_prompt_lock_strict);
+}
+
+/**
+ * Chat settings without the lock
+ */
+function unlockSystemPrompt(settings: ChatDisplaySettings | null | undefined): ChatDisplaySettings {
+  const {
+    system_prompt_locked: _locked,
+    system_prompt_hash: _hash,
+    system_prompt_baseline: _baseline,
+    system_prompt_lock_strict: _strict,
+    ...rest
+  } = settings ?? { hideDisabledMessages: false, hideScriptMessages: false };
+  return rest;
+}
+
+export type { PromptDriftPayload, PromptDriftSummary };
+export {
+  buildSystemPromptRules,
+  hashSystemPrompt,
+  lockSystemPrompt,
+  PROMPT_DRIFT_EVENT,
+  rebaselineSystemPrompt,
+  summarizePromptDrift,
+  unlockSystemPrompt,
+  verifySystemPromptLock,
+};
@@ -0,0 +1,111 @@
+import { getCurrentWindow, ProgressBarStatus } from "@tauri-apps/api/window";
+import { useProfileStore } from "@/hooks/ProfileStore";
+
+/**
+ * Progress of the inference queue on the taskbar (Windows) or dock and launcher icon (macOS, Linux
+ * with the Unity launcher API), so a long generation can be followed while the app is minimized.
+ * Shared by every useInference instance, like global-concurrency.ts.
+ */
+
+// A few updates per second at most, streams report far more often and the icon would flicker
+export const TASKBAR_UPDATE_INTERVAL_MS = 250;
+
+interface TrackedRequest {
+  maxTokens?: number;
+  outputTokens: number;
+  streaming: boolean;
+}
+
+export interface TaskbarState {
+  status: ProgressBarStatus;
+  // 0-100, only with a Normal status
+  progress?: number;
+  // Requests queued or running, shown on the dock or launcher icon
+  badge?: number;
+}
+
+const tracked = new Map<string, TrackedRequest>();
+let lastApplied: TaskbarState = { status: ProgressBarStatus.None };
+let lastRenderAt = 0;
+let pendingRender: ReturnType<typeof setTimeout> | null = null;
+
+/**
+ * What the icon should show for the tracked requests: a bar filling up with the tokens streamed so
+ * far against max_tokens, or a pulsing bar while requests wait or have no known length.
+ */
+export function computeTaskbarState(requests: Iterable<TrackedRequest>): TaskbarState {
+  const all = [...requests];
+  if (all.length === 0) {
+    return { status: ProgressBarStatus.None };
+  }
+
+  const measurable = all.filter((request) => request.streaming && request.maxTokens && request.maxTokens > 0);
+  if (measurable.length === 0) {
+    return { status: ProgressBarStatus.Indeterminate, badge: all.length };
+  }
+
+  const done = measurable.reduce((sum, request) => sum + Math.min(1, request.outputTokens / (request.maxTokens as number)), 0);
+  return { status: ProgressBarStatus.Normal, progress: Math.round((done / measurable.length) * 100), badge: all.length };
+}
+
+function isEnabled(): boolean {
+  return useProfileStore.getState().currentProfile?.settings?.system?.taskbarProgress ?? true;
+}
+
+function render() {
+  pendingRender = null;
+  lastRenderAt = Date.now();
+
+  const next: TaskbarState = isEnabled() ? computeTaskbarState(tracked.values()) : { status: ProgressBarStatus.None };
+  if (next.status === lastApplied.status && next.progress === lastApplied.progress && next.badge === lastApplied.badge) {
+    return;
+  }
+
+  const appWindow = getCurrentWindow();
+  // Each platform supports only some of these (no badge count on Windows), the rest is a no-op
+  if (next.status !== lastApplied.status || next.progress !== lastApplied.progress) {
+    appWindow.setProgressBar({ status: next.status, progress: next.progress }).catch(() => {});
+  }
+  if (next.badge !== lastApplied.badge) {
+    appWindow.setBadgeCount(next.badge).catch(() => {});
+  }
+  lastApplied = next;
+}
+
+function scheduleRender() {
+  if (pendingRender !== null) {
+    return;
+  }
+  const wait = Math.max(0, lastRenderAt + TASKBAR_UPDATE_INTERVAL_MS - Date.now());
+  pendingRender = setTimeout(render, wait);
+}
+
+/**
+ * Show a queued request on the icon. `maxTokens` lets its progress be measured once it streams.
+ */
+export function trackInferenceProgress(requestId: string, maxTokens?: number) {
+  tracked.set(requestId, { maxTokens, outputTokens: 0, streaming: false });
+  scheduleRender();
+}
+
+/**
+ * Report the tokens generated so far, an estimate is fine
+ */
+export function updateInferenceProgress(requestId: string, outputTokens: number) {
+  const request = tracked.get(requestId);
+  if (!request) {
+    return;
+  }
+  request.streaming = true;
+  request.outputTokens = outputTokens;
+  scheduleRender();
+}
+
+/**
+ * Drop a finished, failed or cancelled request. The icon is cleared once none are left.
+ */
+export function untrackInferenceProgress(requestId: string) {
+  if (tracked.delete(requestId)) {
+    scheduleRender();
+  }
+}
@@ -0,0 +1,107 @@
+import { beforeEach, describe, expect, it, vi } from "vitest";
+import { emitToCurrentWindow } from "@/commands/windows";
+import { getModelManifestById } from "@/services/manifest-service";
+import { getModelById } from "@/services/model-service";
+import { selectDBQuery } from "@/utils/database";
+import { type BudgetUsage, checkBudget, estimateCost, evaluateBudget, usageDay, usageMonth } from "../budget";
+
+vi.mock("@/commands/windows", () => ({ emitToCurrentWindow: vi.fn(async () => undefined) }));
+vi.mock("@/services/model-service", () => ({ getModelById: vi.fn() }));
+vi.mock("@/services/manifest-service", () => ({ getModelManifestById: vi.fn() }));
+vi.mock("@/services/profile-service", () => ({ getProfileById: vi.fn() }));
+vi.mock("@/utils/database", () => ({ executeDBWrite: vi.fn(), selectDBQuery: vi.fn() }));
+
+const usage = (inputTokens: number, outputTokens: number, cost = 0): BudgetUsage => ({ period: "month", bucket: "2026-10", inputTokens, outputTokens, cost, requestCount: 1 });
+
+describe("evaluateBudget", () => {
+  it("is unlimited without limits", () => {
+    expect(evaluateBudget("p1", usage(1_000_000, 0), undefined)).toMatchObject({ usedRatio: null, state: "ok", action: "warn" });
+    expect(evaluateBudget("p1", usage(1_000_000, 0), { monthlyTokenLimit: 0, monthlyCostLimit: null })).toMatchObject({ tokenLimit: null, state: "ok" });
+  });
+
+  it("warns from 80% and is exceeded at the limit", () => {
+    const settings = { monthlyTokenLimit: 1000, monthlyCostLimit: null, action: "block" as const };
+    expect(evaluateBudget("p1", usage(500, 200), settings).state).toBe("ok");
+    expect(evaluateBudget("p1", usage(600, 200), settings)).toMatchObject({ tokensUsed: 800, usedRatio: 0.8, state: "warning" });
+    expect(evaluateBudget("p1", usage(900, 100), settings)).toMatchObject({ state: "exceeded", action: "block" });
+  });
+
+  it("goes by the limit closest to being reached", () => {
+    const status = evaluateBudget("p1", usage(100, 0, 9.5), { monthlyTokenLimit: 1000, monthlyCostLimit: 10 });
+    expect(status.usedRatio).toBeCloseTo(0.95);
+    expect(status.state).toBe("warning");
+  });
+
+  it("checks a day against the daily limits only", () => {
+    const settings = { monthlyTokenLimit: 100_000, dailyTokenLimit: 1000, dailyCostLimit: null };
+    const today: BudgetUsage = { ...usage(900, 100), period: "day", bucket: "2026-10-16" };
+    expect(evaluateBudget("p1", today, settings)).toMatchObject({ period: "day", bucket: "2026-10-16", tokenLimit: 1000, state: "exceeded" });
+    expect(evaluateBudget("p1", usage(900, 100), settings)).toMatchObject({ period: "month", tokenLimit: 100_000, state: "ok" });
+  });
+});
+
+describe("estimateCost", () => {
+  it("prices input and output tokens per million", () => {
+    expect(estimateCost({ inputTokens: 2_000_000, outputTokens: 500_000 }, { input_cost_per_million: 3, output_cost_per_million: "15" })).toBeCloseTo(13.5);
+  });
+
+  it("is free without prices", () => {
+    expect(estimateCost({ inputTokens: 1000, outputTokens: 1000 }, { model: "gpt" })).toBe(0);
+    expect(estimateCost({ inputTokens: 1000, outputTokens: 1000 }, { input_cost_per_million: "abc", output_cost_per_million: -1 })).toBe(0);
+  });
+});
+
+describe("usageMonth", () => {
+  it("uses the UTC calendar month", () => {
+    expect(usageMonth(new Date("2026-10-31T23:30:00-03:00"))).toBe("2026-11");
+  });
+});
+
+describe("usageDay", () => {
+  it("uses the UTC calendar day", () => {
+    expect(usageDay(new Date("2026-10-16T22:30:00-03:00"))).toBe("2026-10-17");
+  });
+});
+
+describe("checkBudget", () => {
+  const models: Record<string, any> = {
+    paid: { id: "paid", manifest_id: "openai" },
+    local: { id: "local", manifest_id: "ollama" },
+  };
+
+  beforeEach(() => {
+    vi.mocked(emitToCurrentWindow).mockClear();
+    vi.mocked(getModelById).mockImplementation(async (id: string) => models[id] ?? null);
+    vi.mocked(getModelManifestById).mockImplementation(async (id: string) => ({ id, local: id === "ollama" }) as any);
+    vi.mocked(selectDBQuery).mockResolvedValue([{ input_tokens: 900, output_tokens: 200, cost: 0, request_count: 4 }]);
+  });
+
+  it("blocks paid models once the limit is reached, and warns once", async () => {
+    const settings = { monthlyTokenLimit: 1000, monthlyCostLimit: null, action: "block" as const };
+
+    const first = await checkBudget("p1", settings, "paid");
+    expect(first).toMatchObject({ blocked: true, warned: true, status: { state: "exceeded", tokensUsed: 1100 } });
+    expect(emitToCurrentWindow).toHaveBeenCalledWith("budget-warning", expect.objectContaining({ profileId: "p1", state: "exceeded" }));
+
+    const second = await checkBudget("p1", settings, "paid");
+    expect(second).toMatchObject({ blocked: true, warned: false });
+    expect(emitToCurrentWindow).toHaveBeenCalledTimes(1);
+  });
+
+  it("only warns with the warn action", async () => {
+    expect(await checkBudget("p2", { monthlyTokenLimit: 1000, monthlyCostLimit: null, action: "warn" }, "paid")).toMatchObject({ blocked: false, warned: true });
+  });
+
+  it("blocks on the daily limit while the month has room", async () => {
+    vi.mocked(selectDBQuery).mockImplementation(async (query: string) =>
+      query.includes("profile_usage_daily") ? [{ input_tokens: 900, output_tokens: 200, cost: 0, request_count: 4 }] : [{ input_tokens: 5000, output_tokens: 0, cost: 0, request_count: 20 }],
+    );
+    const check = await checkBudget("p4", { monthlyTokenLimit: 1_000_000, dailyTokenLimit: 1000, action: "block" }, "paid");
+    expect(check).toMatchObject({ blocked: true, status: { period: "day", state: "exceeded", tokensUsed: 1100 } });
+    expect(emitToCurrentWindow).toHaveBeenCalledWith("budget-warning", expect.objectContaining({ profileId: "p4", period: "day", state: "exceeded" }));
+  });
+
+  it("leaves local engines alone", async () => {
+    expect(await checkBudget("p3", { monthlyTokenLimit: 1000, monthlyCostLimit: null, action: "block" }, "local")).toBeNull();
+  });
+});
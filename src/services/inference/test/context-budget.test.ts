@@ -0,0 +1,62 @@
+import { describe, expect, it, vi } from "vitest";
+import { buildContextBudget } from "../context-budget";
+import { formatPrompt } from "../formatter";
+import { estimateTokens } from "../formatter/apply-context-limit";
+
+vi.mock("@/commands/inference", () => ({
+  countTokens: vi.fn(async (text: string) => ({ count: estimateTokens(text) })),
+}));
+vi.mock("@/services/notes-service", () => ({ getNoteSnippets: vi.fn(async () => ({})) }));
+vi.mock("../formatter/apply-lorebook", () => ({
+  getLorebookContent: vi.fn(async () => ({ replacers: { lorebook_top: "", lorebook_bottom: "" }, messages: [] })),
+  processLorebookMessages: (messages: unknown) => messages,
+}));
+
+const message = (id: string, type: "user" | "character", text: string, position: number, overrides: Record<string, unknown> = {}): any => ({
+  id,
+  chat_id: "chat-1",
+  chapter_id: "chapter-1",
+  character_id: type === "character" ? "character-1" : null,
+  type,
+  position,
+  messages: [text],
+  message_index: 0,
+  disabled: false,
+  pinned: false,
+  extra: {},
+  ...overrides,
+});
+
+const history = [
+  message("m1", "user", "The oldest message of the chat, long enough to be dropped first.", 100),
+  message("m2", "character", "An early reply that should not fit either.", 200),
+  message("m3", "user", "A pinned line.", 300, { pinned: true }),
+  message("m4", "user", "(OOC: brb)", 400, { extra: { exclude_from_context: true } }),
+  message("m5", "character", "Recent reply.", 500),
+  message("m6", "user", "Latest.", 600),
+];
+
+describe("buildContextBudget", () => {
+  it("reports what the context limit kept and dropped", async () => {
+    const config = { max_context: 250, max_tokens: 50, max_depth: 100 };
+    const prompt = await formatPrompt({ messageHistory: history, chatTemplate: { config } });
+
+    const budget = buildContextBudget(prompt.statistics!, { maxContext: config.max_context, modelContextLength: 8192, messages: history });
+
+    expect(prompt.inferenceMessages.map((entry) => entry.text)).toEqual(["A pinned line.", "Recent reply.", "Latest."]);
+    expect(budget.trimmedMessageIds).toEqual(["m1", "m2"]);
+    expect(budget.usedTokens).toBe(budget.systemTokens + budget.exampleTokens + budget.historyTokens);
+    expect(budget.remainingTokens).toBe(config.max_context - budget.usedTokens);
+    expect(budget.remainingTokens).toBeGreaterThanOrEqual(config.max_tokens);
+    expect(budget.modelContextLength).toBe(8192);
+  });
+
+  it("trims nothing when the history fits", () => {
+    const budget = buildContextBudget(
+      { systemTokens: 40, exampleTokens: 0, historyTokens: 100, responseTokens: 200, trimmedMessages: 0 },
+      { maxContext: 1000, messages: history },
+    );
+
+    expect(budget).toMatchObject({ usedTokens: 140, remainingTokens: 860, trimmedMessageIds: [], modelContextLength: null });
+  });
+});
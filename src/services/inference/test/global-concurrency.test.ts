@@ -0,0 +1,64 @@
+import { afterEach, describe, expect, it } from "vitest";
+import { acquireGlobalSlot, getGlobalConcurrencyState, setGlobalConcurrency } from "../global-concurrency";
+
+describe("global concurrency", () => {
+  afterEach(() => {
+    setGlobalConcurrency(0);
+  });
+
+  it("does not limit when the cap is 0", async () => {
+    setGlobalConcurrency(0);
+    const releases = await Promise.all([acquireGlobalSlot(), acquireGlobalSlot(), acquireGlobalSlot()]);
+    expect(getGlobalConcurrencyState().active).toBe(3);
+    for (const release of releases) {
+      release();
+    }
+    expect(getGlobalConcurrencyState().active).toBe(0);
+  });
+
+  it("queues requests beyond the cap and starts them in order as slots free up", async () => {
+    setGlobalConcurrency(1);
+    const started: number[] = [];
+    const first = await acquireGlobalSlot();
+    const second = acquireGlobalSlot().then((release) => {
+      started.push(2);
+      return release;
+    });
+    const third = acquireGlobalSlot().then((release) => {
+      started.push(3);
+      return release;
+    });
+
+    await Promise.resolve();
+    expect(started).toEqual([]);
+    expect(getGlobalConcurrencyState().waiting).toBe(2);
+
+    first();
+    const releaseSecond = await second;
+    expect(started).toEqual([2]);
+
+    releaseSecond();
+    (await third)();
+    expect(started).toEqual([2, 3]);
+    expect(getGlobalConcurrencyState().active).toBe(0);
+  });
+
+  it("ignores repeated releases", async () => {
+    setGlobalConcurrency(2);
+    const release = await acquireGlobalSlot();
+    release();
+    release();
+    expect(getGlobalConcurrencyState().active).toBe(0);
+  });
+
+  it("starts waiting requests when the cap is raised", async () => {
+    setGlobalConcurrency(1);
+    const first = await acquireGlobalSlot();
+    const second = acquireGlobalSlot();
+    setGlobalConcurrency(2);
+    const releaseSecond = await second;
+    expect(getGlobalConcurrencyState().active).toBe(2);
+    first();
+    releaseSecond();
+  });
+});
@@ -0,0 +1,150 @@
+import { beforeEach, describe, expect, it, vi } from "vitest";
+import type { ModelSpecs } from "@/schema/inference-engine-schema";
+import { getModelManifestById } from "@/services/manifest-service";
+import { getModelById } from "@/services/model-service";
+import type { AIEvent } from "@/services/ai-providers/types/ai-event.type";
+import { attributeToFallback, canFallBack, holdFallbackError, resolveFallbackModel, type ServingModel, shouldFallBack } from "../model-fallback";
+
+vi.mock("@/commands/windows", () => ({ emitToCurrentWindow: vi.fn(async () => undefined) }));
+vi.mock("@/services/model-service", () => ({ getModelById: vi.fn() }));
+vi.mock("@/services/manifest-service", () => ({ getModelManifestById: vi.fn() }));
+
+const PROFILE = "profile-1";
+
+const models: Record<string, any> = {
+  primary: { id: "primary", profile_id: PROFILE, name: "Primary", manifest_id: "openai", config: { model: "gpt" }, max_concurrency: 2, inference_template_id: null },
+  backup: { id: "backup", profile_id: PROFILE, name: "Backup", manifest_id: "anthropic", config: { model: "claude" }, max_concurrency: 1, inference_template_id: null },
+  completion: { id: "completion", profile_id: PROFILE, name: "Completion", manifest_id: "openai", config: {}, max_concurrency: 1, inference_template_id: "template-1" },
+  foreign: { id: "foreign", profile_id: "profile-2", name: "Foreign", manifest_id: "openai", config: {}, max_concurrency: 1, inference_template_id: null },
+};
+
+const primarySpecs = (fallback_model_ids: string[]): ModelSpecs => ({
+  id: "primary",
+  model_type: "chat",
+  config: { model: "gpt" },
+  max_concurrent_requests: 2,
+  engine: "openai",
+  fallback_model_ids,
+});
+
+describe("shouldFallBack", () => {
+  it("falls back on unreachable endpoints and retryable errors", () => {
+    expect(shouldFallBack({ code: "network_unreachable", retryable: true })).toBe(true);
+    expect(shouldFallBack({ code: "rate_limited", retryable: true })).toBe(true);
+    expect(shouldFallBack({ code: "provider_internal", retryable: true })).toBe(true);
+  });
+
+  it("does not fall back on errors another model wouldn't fix", () => {
+    expect(shouldFallBack({ code: "auth_failed", retryable: false })).toBe(false);
+    expect(shouldFallBack({ code: "cancelled", retryable: true })).toBe(false);
+    expect(shouldFallBack(new Error("Invalid prompt"))).toBe(false);
+  });
+});
+
+describe("resolveFallbackModel", () => {
+  beforeEach(() => {
+    vi.mocked(getModelById).mockImplementation(async (id: string) => models[id] ?? null);
+    vi.mocked(getModelManifestById).mockImplementation(async (id: string) => ({ id, engine: id, inference_fields: [] }) as any);
+  });
+
+  it("returns the next model of the chain with its own limits", async () => {
+    const next = await resolveFallbackModel(primarySpecs(["backup"]), ["primary"]);
+    expect(next).toMatchObject({ id: "backup", engine: "anthropic", max_concurrent_requests: 1, config: { model: "claude" }, fallback_model_ids: ["backup"] });
+  });
+
+  it("skips models of another profile", async () => {
+    expect(await resolveFallbackModel(primarySpecs(["foreign"]), ["primary"])).toBeNull();
+  });
+
+  it("skips models that take a different prompt type", async () => {
+    expect(await resolveFallbackModel(primarySpecs(["completion"]), ["primary"])).toBeNull();
+    expect((await resolveFallbackModel({ ...primarySpecs(["backup", "completion"]), model_type: "completion" }, ["primary"]))?.id).toBe("completion");
+  });
+
+  it("skips tried, deleted, foreign and completion models", async () => {
+    const specs = primarySpecs(["missing", "foreign", "completion", "backup"]);
+    expect((await resolveFallbackModel(specs, ["primary"]))?.id).toBe("backup");
+    expect(await resolveFallbackModel(specs, ["primary", "backup"])).toBeNull();
+  });
+
+  it("returns null without a chain", async () => {
+    expect(await resolveFallbackModel(primarySpecs([]), ["primary"])).toBeNull();
+  });
+});
+
+describe("canFallBack", () => {
+  const unreachable = { code: "network_unreachable", retryable: true };
+  const nothingYet = { cancelled: false, accumulatedText: "", accumulatedReasoning: "", toolCalls: [] };
+
+  it("allows a failure before any output while the chain has models left", () => {
+    expect(canFallBack(nothingYet, 1, 1, unreachable)).toBe(true);
+    expect(canFallBack(nothingYet, 2, 1, unreachable)).toBe(false);
+  });
+
+  it("never falls back once output started", () => {
+    expect(canFallBack({ ...nothingYet, accumulatedText: "Once upon" }, 1, 2, unreachable)).toBe(false);
+    expect(canFallBack({ ...nothingYet, accumulatedReasoning: "Thinking" }, 1, 2, unreachable)).toBe(false);
+    expect(canFallBack({ ...nothingYet, toolCalls: [{ id: "call-1" }] }, 1, 2, unreachable)).toBe(false);
+  });
+
+  it("never falls back on a cancelled request", () => {
+    expect(canFallBack({ ...nothingYet, cancelled: true }, 1, 2, unreachable)).toBe(false);
+    expect(canFallBack(nothingYet, 1, 2, { code: "cancelled", retryable: true })).toBe(false);
+  });
+});
+
+describe("holdFallbackError", () => {
+  const baseEvent = (): AIEvent => ({
+    requestId: "request-1",
+    sendStream: vi.fn(),
+    sendError: vi.fn(),
+    finish: vi.fn(),
+    registerAborter: vi.fn(),
+  });
+
+  it("holds a failure that may fall back, with the finish after it", () => {
+    const event = baseEvent();
+    const guard = holdFallbackError(event, () => true);
+
+    guard.event.sendError({ message: "down", code: "network_unreachable", retryable: true });
+    guard.event.sendError({ message: "again", code: "network_unreachable", retryable: true });
+    guard.event.finish();
+
+    expect(event.sendError).not.toHaveBeenCalled();
+    expect(event.finish).not.toHaveBeenCalled();
+    expect(guard.heldError()).toMatchObject({ message: "down" });
+  });
+
+  it("reports a failure that can't fall back", () => {
+    const event = baseEvent();
+    const guard = holdFallbackError(event, () => false);
+
+    guard.event.sendError({ message: "bad key", code: "auth_failed", retryable: false });
+    guard.event.finish();
+
+    expect(event.sendError).toHaveBeenCalledWith(expect.objectContaining({ message: "bad key" }));
+    expect(event.finish).toHaveBeenCalled();
+    expect(guard.heldError()).toBeUndefined();
+  });
+
+  it("holds a thrown error through hold()", () => {
+    const guard = holdFallbackError(baseEvent(), (error) => error instanceof Error && error.message === "timeout");
+    expect(guard.hold(new Error("invalid"))).toBe(false);
+    expect(guard.hold(new Error("timeout"))).toBe(true);
+    expect(guard.heldError()).toEqual(new Error("timeout"));
+  });
+});
+
+describe("attributeToFallback", () => {
+  it("names the model that answered and keeps the primary as the origin", () => {
+    const runtime: ServingModel = { modelId: "primary", modelConfig: { model: "gpt" }, engine: "openai", actualModel: "gpt", finishReason: "error" };
+    const backup: ModelSpecs = { id: "backup", model_type: "chat", config: { model: "claude" }, max_concurrent_requests: 1, engine: "anthropic" };
+    const last: ModelSpecs = { id: "last", model_type: "chat", config: {}, max_concurrent_requests: 1, engine: "ollama" };
+
+    attributeToFallback(runtime, "primary", backup);
+    expect(runtime).toEqual({ modelId: "backup", modelConfig: { model: "claude" }, engine: "anthropic", actualModel: "claude", finishReason: undefined, fallbackFrom: "primary" });
+
+    attributeToFallback(runtime, "primary", last);
+    expect(runtime).toMatchObject({ modelId: "last", engine: "ollama", actualModel: undefined, fallbackFrom: "primary" });
+  });
+});
@@ -0,0 +1,58 @@
+import { beforeEach, describe, expect, it, vi } from "vitest";
+import { callProviderConverseEndpoint } from "@/services/ai-providers/start-inference";
+import { getModelManifestById } from "@/services/manifest-service";
+import { listModels } from "@/services/model-service";
+import { validateAllModels } from "../model-validation";
+
+vi.mock("@/services/model-service", () => ({ listModels: vi.fn() }));
+vi.mock("@/services/manifest-service", () => ({ getModelManifestById: vi.fn() }));
+vi.mock("@/services/ai-providers/start-inference", () => ({ callProviderConverseEndpoint: vi.fn() }));
+
+const model = (id: string, type = "llm") => ({ id, name: id, type, profile_id: "profile-1", manifest_id: "openai", config: {}, inference_template_id: null });
+
+describe("validateAllModels", () => {
+  beforeEach(() => {
+    vi.mocked(getModelManifestById).mockResolvedValue({ id: "openai", engine: "openai", inference_fields: [] } as any);
+  });
+
+  it("reports each model in list order and skips models that aren't language models", async () => {
+    vi.mocked(listModels).mockResolvedValue([model("working"), model("embedder", "embedding"), model("broken")] as any);
+    vi.mocked(callProviderConverseEndpoint).mockImplementation(async (event, params) => {
+      if (params.modelSpecs.id === "broken") {
+        event.sendError({ message: "Invalid API key", code: "auth_failed" });
+        return "";
+      }
+      event.finish({ fullResponse: "Connection successful" });
+      return "Connection successful";
+    });
+
+    const results = await validateAllModels("profile-1");
+
+    expect(results.map(({ model_id, status }) => [model_id, status])).toEqual([
+      ["working", "ok"],
+      ["embedder", "skipped"],
+      ["broken", "error"],
+    ]);
+    expect(results[0].latency_ms).toEqual(expect.any(Number));
+    expect(results[2].error).toEqual({ code: "auth_failed", message: "Invalid API key" });
+  });
+
+  it("never runs more tests at once than the bound", async () => {
+    vi.mocked(listModels).mockResolvedValue(["a", "b", "c", "d", "e"].map((id) => model(id)) as any);
+    let running = 0;
+    let peak = 0;
+    vi.mocked(callProviderConverseEndpoint).mockImplementation(async (event) => {
+      running++;
+      peak = Math.max(peak, running);
+      await new Promise((resolve) => setTimeout(resolve, 1));
+      running--;
+      event.finish({});
+      return "ok";
+    });
+
+    const results = await validateAllModels("profile-1", 2);
+
+    expect(peak).toBe(2);
+    expect(results.every((result) => result.status === "ok")).toBe(true);
+  });
+});
@@ -0,0 +1,74 @@
+import { describe, expect, it, vi } from "vitest";
+import type { SequencedChatMessage } from "@/services/chat-message-service";
+import { buildNextSpeakerPrompt, findLastSpeaker, parseNextSpeaker, pickRandom, pickRoundRobin } from "../next-speaker";
+
+vi.mock("@/hooks/ProfileStore", () => ({ useCurrentProfile: vi.fn() }));
+vi.mock("@/services/background-inference-service", () => ({ useBackgroundInference: vi.fn() }));
+vi.mock("@/services/character-service", () => ({ listCharacters: vi.fn() }));
+vi.mock("@/services/chat-message-service", () => ({ listRecentMessages: vi.fn() }));
+vi.mock("@/services/chat-service", () => ({ getChatById: vi.fn() }));
+vi.mock("@/services/model-service", () => ({ getModelByIdOrAlias: vi.fn() }));
+vi.mock("@/services/template-chat-service", () => ({ getChatTemplateById: vi.fn() }));
+
+const message = (sequence: number, type: SequencedChatMessage["type"], text: string, character_id: string | null = null, excluded = false): SequencedChatMessage => ({
+  sequence,
+  type,
+  character_id,
+  text,
+  excluded,
+});
+
+const candidates = [
+  { id: "ann", name: "Ann" },
+  { id: "anna", name: "Anna" },
+  { id: "bram", name: "Bram" },
+];
+const candidateIds = candidates.map((candidate) => candidate.id);
+
+describe("findLastSpeaker", () => {
+  it("finds the latest candidate who spoke", () => {
+    const messages = [message(1, "character", "Hi.", "bram"), message(2, "character", "Hello.", "narrator"), message(3, "user", "Hey all.")];
+    expect(findLastSpeaker(messages, candidateIds)).toBe("bram");
+    expect(findLastSpeaker([message(1, "user", "Hey all.")], candidateIds)).toBeNull();
+  });
+});
+
+describe("pickRoundRobin", () => {
+  it("takes turns in candidate order and wraps around", () => {
+    expect(pickRoundRobin(candidateIds, null)).toBe("ann");
+    expect(pickRoundRobin(candidateIds, "ann")).toBe("anna");
+    expect(pickRoundRobin(candidateIds, "bram")).toBe("ann");
+  });
+});
+
+describe("pickRandom", () => {
+  it("never repeats the last speaker when others can speak", () => {
+    expect(pickRandom(candidateIds, "ann", () => 0)).toBe("anna");
+    expect(pickRandom(candidateIds, "ann", () => 0.999)).toBe("bram");
+    expect(pickRandom(["ann"], "ann", () => 0.5)).toBe("ann");
+  });
+});
+
+describe("buildNextSpeakerPrompt", () => {
+  it("lists the recent messages and the candidates", () => {
+    const nameOf = (entry: SequencedChatMessage) => (entry.type === "user" ? "Bob" : "Ann");
+    const prompt = buildNextSpeakerPrompt([message(1, "user", " Anna, your turn. "), message(2, "character", "(OOC: brb)", "ann", true)], candidates, nameOf);
+
+    expect(prompt).toContain("Bob: Anna, your turn.");
+    expect(prompt).not.toContain("brb");
+    expect(prompt).toContain("- Ann\n- Anna\n- Bram");
+  });
+});
+
+describe("parseNextSpeaker", () => {
+  it("matches the name the model answered with", () => {
+    expect(parseNextSpeaker("Anna", candidates)).toBe("anna");
+    expect(parseNextSpeaker(' "bram". ', candidates)).toBe("bram");
+    expect(parseNextSpeaker("Anna should reply, since Bram asked her.", candidates)).toBe("anna");
+    expect(parseNextSpeaker("I think Bram, then Ann.", candidates)).toBe("bram");
+  });
+
+  it("returns null when the answer names nobody", () => {
+    expect(parseNextSpeaker("The narrator.", candidates)).toBeNull();
+  });
+});
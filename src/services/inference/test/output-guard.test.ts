@@ -0,0 +1,61 @@
+import { describe, expect, it } from "vitest";
+import { applyOutputGuard, createOutputGuard, processGuardedChunk } from "../output-guard";
+
+const bothOn = { hideDisabledMessages: false, hideScriptMessages: false, strip_name_prefix: true, stop_on_user_impersonation: true };
+
+const stream = (chunks: string[], characterName: string, otherNames: string[]) => {
+  const guard = createOutputGuard(bothOn, characterName, otherNames)!;
+  let text = "";
+  for (const chunk of chunks) {
+    const result = processGuardedChunk(chunk, guard);
+    text += result.text;
+    if (result.stop) {
+      return { text, stopped: true };
+    }
+  }
+  return { text: text + processGuardedChunk("", guard, true).text, stopped: false };
+};
+
+describe("createOutputGuard", () => {
+  it("is off unless the chat enables an option", () => {
+    expect(createOutputGuard({ hideDisabledMessages: false, hideScriptMessages: false }, "Anna", ["Ann"])).toBeNull();
+    expect(createOutputGuard(null, "Anna", ["Ann"])).toBeNull();
+  });
+
+  it("never treats the speaking character as someone else", () => {
+    expect(createOutputGuard(bothOn, "Anna", ["Ann", "Anna", "Bob", "Bob"])?.otherNames).toEqual(["Ann", "Bob"]);
+  });
+});
+
+describe("processGuardedChunk", () => {
+  it("strips the character's name prefix split across chunks", () => {
+    expect(stream(["  An", "na", ":", " Hello", " there"], "Anna", ["Ann"])).toEqual({ text: "Hello there", stopped: false });
+  });
+
+  it("releases text that only looked like the prefix", () => {
+    expect(stream(["Ann", "ounced"], "Anna", ["Bob"])).toEqual({ text: "Announced", stopped: false });
+    expect(stream(["An"], "Anna", ["Bob"])).toEqual({ text: "An", stopped: false });
+  });
+
+  it("stops when a line opens with the user's name, across chunks", () => {
+    expect(stream(["She smiles.\n", "Bo", "b: I", " nod."], "Anna", ["Bob"])).toEqual({ text: "She smiles.", stopped: true });
+  });
+
+  it("tells apart names that are substrings of each other", () => {
+    // The user is "Ann", the character "Anna": her own name on a new line is not impersonation
+    expect(stream(["Hi.\nAnn", "a: still me"], "Anna", ["Ann"])).toEqual({ text: "Hi.\nAnna: still me", stopped: false });
+    expect(stream(["Hi.\nAnn", ": hello"], "Anna", ["Ann"])).toEqual({ text: "Hi.", stopped: true });
+    // The user is "Anna", a participant "Ann": both end the reply
+    expect(stream(["Hi.\nAnna:", " x"], "Bob", ["Ann", "Anna"])).toEqual({ text: "Hi.", stopped: true });
+    expect(stream(["Hi.\nAnnie said"], "Bob", ["Ann", "Anna"])).toEqual({ text: "Hi.\nAnnie said", stopped: false });
+  });
+});
+
+describe("applyOutputGuard", () => {
+  it("guards a whole reply regardless of the streaming state", () => {
+    const guard = createOutputGuard(bothOn, "Anna", ["Bob"])!;
+    processGuardedChunk("Anna: Hi\nBob: hey", guard);
+    expect(guard.stopped).toBe(true);
+    expect(applyOutputGuard("Anna: Hi\nBob: hey", guard)).toBe("Hi");
+  });
+});
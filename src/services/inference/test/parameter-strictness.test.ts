@@ -0,0 +1,89 @@
+import { beforeEach, describe, expect, it, vi } from "vitest";
+import { emitToCurrentWindow } from "@/commands/windows";
+import type { ModelSpecs } from "@/schema/inference-engine-schema";
+import { PARAMETER_NOT_SUPPORTED } from "@/services/ai-providers/types/ai-event.type";
+import { checkParameterStrictness, findUnsupportedParameters, PARAMETER_WARNING_EVENT } from "../parameter-strictness";
+
+vi.mock("@/commands/windows", () => ({ emitToCurrentWindow: vi.fn(async () => undefined) }));
+
+// inference_fields of the bundled openai.jsonc and gemini.jsonc manifests
+const OPENAI_FIELDS = ["temperature", "verbosity", "top_p", "frequency_penalty", "presence_penalty", "reasoning", "stop"];
+const GEMINI_FIELDS = ["temperature", "top_p", "top_k", "reasoning"];
+
+const specs = (engine: string, inferenceFields?: string[]): ModelSpecs => ({
+  id: `${engine}-model`,
+  engine,
+  model_type: "chat",
+  max_concurrent_requests: 1,
+  config: { model: "any" },
+  inference_fields: inferenceFields,
+});
+
+// Flattened the way chat templates reach the request
+const parameters = {
+  max_tokens: 800,
+  max_context: 8000,
+  temperature: 0.8,
+  top_p: 0.95,
+  top_k: 40,
+  typical_p: 0.9,
+  reasoning_temperature: 2,
+  frequency_penalty: 0.2,
+  retry_on_empty: { enabled: true },
+  seed: undefined,
+};
+
+describe("findUnsupportedParameters", () => {
+  it("names what the OpenAI and Gemini engines drop", () => {
+    expect(findUnsupportedParameters(parameters, OPENAI_FIELDS)).toEqual(["top_k", "typical_p"]);
+    expect(findUnsupportedParameters(parameters, GEMINI_FIELDS)).toEqual(["frequency_penalty", "typical_p"]);
+  });
+
+  it("counts section fields through their section and skips pipeline keys and empty values", () => {
+    expect(findUnsupportedParameters({ reasoning_budget: 1024, stop: ["\n"], max_depth: 10, min_p: null }, GEMINI_FIELDS)).toEqual([]);
+    expect(findUnsupportedParameters({ cache_ttl: "1h" }, GEMINI_FIELDS)).toEqual(["cache_ttl"]);
+  });
+
+  it("checks nothing when the engine's fields are unknown", () => {
+    expect(findUnsupportedParameters(parameters, undefined)).toEqual([]);
+  });
+});
+
+describe("checkParameterStrictness", () => {
+  beforeEach(() => {
+    vi.mocked(emitToCurrentWindow).mockClear();
+    vi.spyOn(console, "warn").mockImplementation(() => undefined);
+  });
+
+  it("sends silently in ignore mode", () => {
+    expect(checkParameterStrictness("r1", specs("openai", OPENAI_FIELDS), parameters, "ignore")).toBeNull();
+    expect(checkParameterStrictness("r2", specs("google", GEMINI_FIELDS), parameters, "ignore")).toBeNull();
+    expect(emitToCurrentWindow).not.toHaveBeenCalled();
+  });
+
+  it("emits the unsupported keys before dispatch in warn mode", () => {
+    expect(checkParameterStrictness("r1", specs("openai", OPENAI_FIELDS), parameters, "warn")).toBeNull();
+    expect(checkParameterStrictness("r2", specs("google", GEMINI_FIELDS), parameters, "warn")).toBeNull();
+
+    expect(emitToCurrentWindow).toHaveBeenNthCalledWith(1, PARAMETER_WARNING_EVENT, { requestId: "r1", modelId: "openai-model", engine: "openai", keys: ["top_k", "typical_p"] });
+    expect(emitToCurrentWindow).toHaveBeenNthCalledWith(2, PARAMETER_WARNING_EVENT, { requestId: "r2", modelId: "google-model", engine: "google", keys: ["frequency_penalty", "typical_p"] });
+  });
+
+  it("refuses the request with the offending keys in error mode", () => {
+    const openai = checkParameterStrictness("r1", specs("openai", OPENAI_FIELDS), parameters, "error");
+    expect(openai).toMatchObject({ code: PARAMETER_NOT_SUPPORTED, retryable: false, details: { keys: ["top_k", "typical_p"] } });
+    expect(openai?.message).toContain("top_k, typical_p");
+
+    const gemini = checkParameterStrictness("r2", specs("google", GEMINI_FIELDS), parameters, "error");
+    expect(gemini).toMatchObject({ code: PARAMETER_NOT_SUPPORTED, details: { keys: ["frequency_penalty", "typical_p"] } });
+    expect(emitToCurrentWindow).not.toHaveBeenCalled();
+  });
+
+  it("lets supported parameters through in every mode", () => {
+    const supported = { temperature: 0.7, top_p: 0.9, top_k: 20, reasoning_budget: 2048 };
+    for (const strictness of ["ignore", "warn", "error"] as const) {
+      expect(checkParameterStrictness("r1", specs("google", GEMINI_FIELDS), supported, strictness)).toBeNull();
+    }
+    expect(emitToCurrentWindow).not.toHaveBeenCalled();
+  });
+});
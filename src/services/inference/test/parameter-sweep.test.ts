@@ -0,0 +1,38 @@
+import { describe, expect, it } from "vitest";
+import { buildSweepMembers, collectSweepResults, MAX_SWEEP_MEMBERS, parseSweepValues, type SweepMemberResult } from "../parameter-sweep";
+
+describe("parameter sweep", () => {
+  it("builds every value and repetition on top of the base parameters", () => {
+    const members = buildSweepMembers({ parameter: " temperature ", values: [0.2, 1] }, 2, { temperature: 0.7, max_tokens: 64 });
+
+    expect(members.map((member) => [member.combination, member.repetition])).toEqual([
+      ["temperature=0.2", 0],
+      ["temperature=0.2", 1],
+      ["temperature=1", 0],
+      ["temperature=1", 1],
+    ]);
+    expect(members[2].parameters).toEqual({ temperature: 1, max_tokens: 64 });
+  });
+
+  it("refuses empty and oversized sweeps", () => {
+    expect(() => buildSweepMembers({ parameter: "", values: [1] }, 1)).toThrow();
+    expect(() => buildSweepMembers({ parameter: "top_p", values: [] }, 1)).toThrow();
+    expect(() => buildSweepMembers({ parameter: "top_p", values: [0.5, 0.9] }, MAX_SWEEP_MEMBERS)).toThrow(`at most ${MAX_SWEEP_MEMBERS}`);
+  });
+
+  it("parses typed values", () => {
+    expect(parseSweepValues("0.2, 1,, true, greedy ")).toEqual([0.2, 1, true, "greedy"]);
+  });
+
+  it("groups results by combination in axis and repetition order", () => {
+    const members = buildSweepMembers({ parameter: "temperature", values: [0, 1, 2] }, 2);
+    const result = (combination: string, repetition: number): SweepMemberResult => ({ combination, repetition, status: "completed", text: `${combination}#${repetition}`, latency_ms: 10 });
+
+    const sweep = collectSweepResults("sweep-1", "temperature", members, [result("temperature=1", 1), result("temperature=0", 0), result("temperature=1", 0)]);
+
+    expect(Object.keys(sweep.results)).toEqual(["temperature=0", "temperature=1", "temperature=2"]);
+    expect(sweep.results["temperature=1"].map((member) => member.repetition)).toEqual([0, 1]);
+    // A member that never reported leaves its combination empty rather than missing
+    expect(sweep.results["temperature=2"]).toEqual([]);
+  });
+});
@@ -0,0 +1,40 @@
+import { describe, expect, it } from "vitest";
+import { validateParameters } from "../parameter-validation";
+
+const openrouter = { name: "OpenRouter", engine: "openrouter" as const, inference_fields: ["temperature", "top_p", "top_k", "seed"] };
+const ollama = { name: "Ollama", engine: "ollama" as const, inference_fields: ["temperature", "top_p", "top_k", "seed"] };
+const anthropic = { name: "Anthropic", engine: "anthropic" as const, inference_fields: ["temperature", "top_p", "top_k"] };
+
+const codes = (parameters: Record<string, any>, manifest?: Parameters<typeof validateParameters>[1]) => validateParameters(parameters, manifest).map((warning) => warning.code);
+
+describe("validateParameters", () => {
+  it("warns when temperature 0 is combined with nucleus or top-k sampling", () => {
+    expect(codes({ temperature: 0, top_p: 0.9, top_k: 40 }, ollama)).toEqual(["top_p_with_zero_temperature", "top_k_with_zero_temperature"]);
+    expect(codes({ temperature: 0, top_p: 1, top_k: 0 }, ollama)).toEqual([]);
+    expect(codes({ temperature: 0.7, top_p: 0.9, top_k: 40 }, ollama)).toEqual([]);
+  });
+
+  it("ignores parameters the engine doesn't take", () => {
+    expect(codes({ temperature: 0, top_p: 0.9 }, { ...ollama, inference_fields: ["temperature", "seed"] })).toEqual([]);
+  });
+
+  it("warns about seeds the provider may ignore", () => {
+    expect(codes({ temperature: 0.8, seed: 42 }, openrouter)).toEqual(["seed_without_zero_temperature"]);
+    expect(codes({ seed: 42 }, openrouter)).toEqual(["seed_without_zero_temperature"]);
+    expect(codes({ temperature: 0, seed: 42 }, openrouter)).toEqual([]);
+    expect(codes({ temperature: 0.8, seed: -1 }, openrouter)).toEqual([]);
+    // Local backends seed their sampler at any temperature
+    expect(codes({ temperature: 0.8, seed: 42 }, ollama)).toEqual([]);
+  });
+
+  it("warns that determinism can't be pinned without seed support", () => {
+    const [warning] = validateParameters({ temperature: 0 }, anthropic);
+    expect(warning.code).toBe("no_seed_support");
+    expect(warning.message).toContain("Anthropic");
+  });
+
+  it("assumes every parameter is sent when no model is selected", () => {
+    expect(codes({ temperature: 0, top_k: 40, seed: 7 })).toEqual(["top_k_with_zero_temperature"]);
+    expect(codes({ temperature: 1, seed: 7 })).toEqual(["seed_without_zero_temperature"]);
+  });
+});
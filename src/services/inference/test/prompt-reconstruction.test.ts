@@ -0,0 +1,59 @@
+import { describe, expect, it } from "vitest";
+import type { InferenceLogEntry } from "@/commands/inference";
+import type { ConsoleRequest } from "@/hooks/consoleStore";
+import { redactSecrets, snapshotFromCapture, snapshotFromLogEntry, snapshotToConsoleRequest } from "../prompt-reconstruction";
+
+const capture: ConsoleRequest = {
+  id: "req_1",
+  timestamp: 1,
+  systemPrompt: "You are a narrator.",
+  messages: [{ role: "user", text: "Hello" }],
+  modelSpecs: { id: "model-1", model_type: "chat", config: { model: "gpt-x", api_key: "sk-123" }, max_concurrent_requests: 1, engine: "openai" },
+  parameters: { temperature: 0.7, headers: { Authorization: "Bearer sk-123" } },
+  engine: "openai",
+  fullResponse: "Hi!",
+};
+
+const logEntry: InferenceLogEntry = {
+  timestamp: "2026-01-01T00:00:00Z",
+  request_id: "req_2",
+  model_id: "model-2",
+  engine: "anthropic",
+  actual_model: "claude-x",
+  params: { temperature: 1, api_key: "[redacted]" },
+  status: "completed",
+  latency_ms: 900,
+};
+
+describe("redactSecrets", () => {
+  it("redacts secret-looking keys at any depth", () => {
+    expect(redactSecrets({ temperature: 1, nested: [{ apiKey: "x", access_token: "y" }], password: "z" })).toEqual({
+      temperature: 1,
+      nested: [{ apiKey: "[redacted]", access_token: "[redacted]" }],
+      password: "[redacted]",
+    });
+  });
+});
+
+describe("prompt snapshots", () => {
+  it("takes the exact prompt from a debug capture", () => {
+    const snapshot = snapshotFromCapture("message-1", capture);
+    expect(snapshot).toMatchObject({ request_id: "req_1", system_prompt: "You are a narrator.", reconstructed: false });
+    expect(snapshot.model).toEqual({ id: "model-1", actual_model: "gpt-x", engine: "openai" });
+    expect(snapshot.parameters).toEqual({ temperature: 0.7, headers: { Authorization: "[redacted]" } });
+  });
+
+  it("needs the prompt in the log entry", () => {
+    expect(snapshotFromLogEntry("message-2", logEntry)).toBeNull();
+
+    const snapshot = snapshotFromLogEntry("message-2", { ...logEntry, prompt: { system_prompt: "Rules", messages: [{ role: "user", text: "Go" }] } });
+    expect(snapshot?.messages).toEqual([{ role: "user", text: "Go" }]);
+    expect(snapshot?.model).toEqual({ id: "model-2", actual_model: "claude-x", engine: "anthropic" });
+  });
+
+  it("converts to a console request under its own id", () => {
+    const request = snapshotToConsoleRequest({ ...snapshotFromCapture("message-1", capture), reconstructed: true, warnings: ["drift"] }, "Hi!");
+    expect(request.id).toBe("prompt_message-1");
+    expect(request).toMatchObject({ reconstructed: true, warnings: ["drift"], fullResponse: "Hi!", engine: "openai" });
+  });
+});
@@ -0,0 +1,87 @@
+import { beforeEach, describe, expect, it, vi } from "vitest";
+import { emitToCurrentWindow } from "@/commands/windows";
+import type { ChatMessage } from "@/schema/chat-message-schema";
+import type { Chat } from "@/schema/chat-schema";
+import { getChatMessageById } from "@/services/chat-message-service";
+import { getChatById } from "@/services/chat-service";
+import { emitMessageRegenerated, loadRegenerationTarget, MESSAGE_REGENERATED_EVENT, RegenerateError, regenerationIndex } from "../regenerate";
+
+vi.mock("@/commands/windows", () => ({ emitToCurrentWindow: vi.fn(async () => undefined), listenInCurrentWindow: vi.fn() }));
+vi.mock("@/services/chat-message-service", () => ({ getChatMessageById: vi.fn() }));
+vi.mock("@/services/chat-service", () => ({ getChatById: vi.fn() }));
+
+const PROFILE = "3e9f5c8b-1c86-4d76-ad0b-8a4c2f6e5d43";
+const CHAT = "0b6c2f5e-8f53-4a43-9a7e-5d1f9c3b2a10";
+const MESSAGE = "2d8e4b7a-0b75-4c65-9c9a-7f3b1e5d4c32";
+
+const message = (overrides: Partial<ChatMessage> = {}) =>
+  ({
+    id: MESSAGE,
+    chat_id: CHAT,
+    character_id: "c1",
+    type: "character",
+    messages: ["First", "Second", "Third"],
+    message_index: 1,
+    ...overrides,
+  }) as ChatMessage;
+
+// The reason of the error the target is refused with
+const reasonFor = async () => {
+  const error = await loadRegenerationTarget(MESSAGE, CHAT, PROFILE).catch((error: unknown) => error);
+  expect(error).toBeInstanceOf(RegenerateError);
+  return (error as RegenerateError).reason;
+};
+
+describe("loadRegenerationTarget", () => {
+  beforeEach(() => {
+    vi.mocked(getChatById).mockReset().mockResolvedValue({ id: CHAT } as Chat);
+    vi.mocked(getChatMessageById).mockReset().mockResolvedValue(message());
+  });
+
+  it("loads a character message of a chat of the profile", async () => {
+    await expect(loadRegenerationTarget(MESSAGE, CHAT, PROFILE)).resolves.toMatchObject({ id: MESSAGE });
+    expect(getChatById).toHaveBeenCalledWith(CHAT, PROFILE);
+  });
+
+  it("refuses a chat of another profile", async () => {
+    vi.mocked(getChatById).mockResolvedValue(null);
+    expect(await reasonFor()).toBe("chat_not_found");
+  });
+
+  it("refuses a missing message or one of another chat", async () => {
+    vi.mocked(getChatMessageById).mockResolvedValue(null);
+    expect(await reasonFor()).toBe("message_not_found");
+
+    vi.mocked(getChatMessageById).mockResolvedValue(message({ chat_id: "another-chat" }));
+    expect(await reasonFor()).toBe("message_not_found");
+  });
+
+  it("refuses user messages", async () => {
+    vi.mocked(getChatMessageById).mockResolvedValue(message({ type: "user", character_id: null }));
+    expect(await reasonFor()).toBe("not_a_character_message");
+  });
+});
+
+describe("regenerationIndex", () => {
+  it("adds a version after the last one", () => {
+    expect(regenerationIndex(message(), "add_version")).toBe(3);
+    expect(regenerationIndex(message(), "add_version", 0)).toBe(3);
+  });
+
+  it("replaces the version on screen unless one is asked for", () => {
+    expect(regenerationIndex(message(), "replace")).toBe(1);
+    expect(regenerationIndex(message(), "replace", 2)).toBe(2);
+    expect(regenerationIndex(message(), "replace", 9)).toBe(2);
+    expect(regenerationIndex(message({ messages: [], message_index: 0 }), "replace")).toBe(0);
+  });
+});
+
+describe("emitMessageRegenerated", () => {
+  it("emits to the current window", () => {
+    const payload = { chatId: CHAT, messageId: MESSAGE, messageIndex: 3, mode: "add_version" as const, requestId: "req-1", text: "Fourth" };
+
+    emitMessageRegenerated(payload);
+
+    expect(emitToCurrentWindow).toHaveBeenCalledWith(MESSAGE_REGENERATED_EVENT, payload);
+  });
+});
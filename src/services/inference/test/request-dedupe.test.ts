@@ -0,0 +1,41 @@
+import { afterEach, describe, expect, it } from "vitest";
+import { claimRequest, clearRecentRequests, releaseRequest, requestFingerprint } from "../request-dedupe";
+
+describe("request dedupe", () => {
+  afterEach(() => {
+    clearRecentRequests();
+  });
+
+  it("refuses an identical request within the window", async () => {
+    const fingerprint = await requestFingerprint(["model-1", "chat-1", "", [{ role: "user", text: "Hi" }], {}]);
+
+    expect(claimRequest(fingerprint, "req-1", 2000, 0)).toBeNull();
+    expect(claimRequest(fingerprint, "req-2", 2000, 500)).toBe("req-1");
+    // Past the window the same prompt runs again
+    expect(claimRequest(fingerprint, "req-3", 2000, 2000)).toBeNull();
+  });
+
+  it("tells requests apart by content", async () => {
+    const first = await requestFingerprint(["model-1", "chat-1", "", [{ role: "user", text: "Hi" }], {}]);
+    const second = await requestFingerprint(["model-1", "chat-1", "", [{ role: "user", text: "Hi" }], { temperature: 1 }]);
+
+    expect(first).not.toBe(second);
+    expect(claimRequest(first, "req-1", 2000, 0)).toBeNull();
+    expect(claimRequest(second, "req-2", 2000, 0)).toBeNull();
+  });
+
+  it("lets a finished request's prompt be sent again", async () => {
+    const fingerprint = await requestFingerprint(["model-1", "chat-1", "", [], {}]);
+
+    claimRequest(fingerprint, "req-1", 2000, 0);
+    releaseRequest("req-1");
+    expect(claimRequest(fingerprint, "req-2", 2000, 100)).toBeNull();
+  });
+
+  it("never refuses with the window off", async () => {
+    const fingerprint = await requestFingerprint(["model-1", "chat-1", "", [], {}]);
+
+    expect(claimRequest(fingerprint, "req-1", 0, 0)).toBeNull();
+    expect(claimRequest(fingerprint, "req-2", 0, 0)).toBeNull();
+  });
+});
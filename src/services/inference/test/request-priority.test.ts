@@ -0,0 +1,83 @@
+import { afterEach, describe, expect, it } from "vitest";
+import { acquireGlobalSlot, setGlobalConcurrency } from "../global-concurrency";
+import { PRIORITY_AGING_MS, type RequestPriority, setFocusedChat, takeNextRequest } from "../request-priority";
+
+describe("request priority", () => {
+  afterEach(() => {
+    setFocusedChat(null);
+    setGlobalConcurrency(0);
+  });
+
+  it("starts the focused chat's request before earlier background requests on the same model", () => {
+    // A model queue behind a busy slot: two agent requests, then the focused chat's
+    const queue = [{ id: "agent-1" }, { id: "other-chat", chatId: "chat-2" }, { id: "focused", chatId: "chat-1" }];
+    setFocusedChat("chat-1");
+
+    expect(takeNextRequest(queue)?.id).toBe("focused");
+    expect(takeNextRequest(queue)?.id).toBe("agent-1");
+    expect(takeNextRequest(queue)?.id).toBe("other-chat");
+    expect(takeNextRequest(queue)).toBeUndefined();
+  });
+
+  it("keeps the submission order without a focused chat", () => {
+    const queue = [{ id: "first", chatId: "chat-2" }, { id: "second", chatId: "chat-1" }];
+    expect(takeNextRequest(queue)?.id).toBe("first");
+  });
+
+  it("re-evaluates the focus on every dequeue of the global queue", async () => {
+    setGlobalConcurrency(1);
+    const started: string[] = [];
+    const first = await acquireGlobalSlot();
+    const waitFor = (name: string, chatId?: string) =>
+      acquireGlobalSlot(chatId).then((release) => {
+        started.push(name);
+        return release;
+      });
+    const agent = waitFor("agent");
+    const chat1 = waitFor("chat-1", "chat-1");
+    const chat2 = waitFor("chat-2", "chat-2");
+
+    // The user navigates after the requests were queued
+    setFocusedChat("chat-2");
+    first();
+    (await chat2)();
+    setFocusedChat("chat-1");
+    (await chat1)();
+    (await agent)();
+
+    expect(started).toEqual(["chat-2", "chat-1", "agent"]);
+  });
+
+  it("starts higher priorities first, then the focused chat among equals", () => {
+    const queue: Array<{ id: string; chatId?: string; priority?: RequestPriority }> = [
+      { id: "summary", priority: "background" },
+      { id: "agent" },
+      { id: "reply", chatId: "chat-2", priority: "interactive" },
+      { id: "focused-reply", chatId: "chat-1", priority: "interactive" },
+    ];
+    setFocusedChat("chat-1");
+
+    expect([takeNextRequest(queue, 0), takeNextRequest(queue, 0), takeNextRequest(queue, 0), takeNextRequest(queue, 0)].map((entry) => entry?.id)).toEqual([
+      "focused-reply",
+      "reply",
+      "agent",
+      "summary",
+    ]);
+  });
+
+  it("doesn't starve background jobs under sustained interactive load", () => {
+    setFocusedChat("chat-1");
+    const queue: Array<{ id: string; chatId?: string; priority: RequestPriority; enqueuedAt: number }> = [{ id: "summary", priority: "background", enqueuedAt: 0 }];
+    const started: string[] = [];
+
+    // A new reply of the focused chat is queued every second and one request starts per second
+    for (let now = 0; !started.includes("summary"); now += 1000) {
+      queue.push({ id: `reply-${now}`, chatId: "chat-1", priority: "interactive", enqueuedAt: now });
+      started.push(takeNextRequest(queue, now)!.id);
+      expect(now).toBeLessThanOrEqual(3 * PRIORITY_AGING_MS);
+    }
+
+    // Replies went first until the summary aged past interactive
+    expect(started.indexOf("summary")).toBeGreaterThan(2 * (PRIORITY_AGING_MS / 1000) - 1);
+  });
+});
@@ -0,0 +1,65 @@
+import { describe, expect, it } from "vitest";
+import type { InferenceMessage } from "@/schema/inference-engine-schema";
+import { INVALID_REQUEST } from "@/services/ai-providers/types/ai-event.type";
+import { InvalidInferenceRequestError, MAX_INFERENCE_MESSAGES, validateInferenceRequest } from "../request-validation";
+
+const user = (text: string): InferenceMessage => ({ role: "user", text });
+
+// The reason of the error the request is refused with, or null when it passes
+const reasonFor = (request: Parameters<typeof validateInferenceRequest>[0]) => {
+  try {
+    validateInferenceRequest(request);
+    return null;
+  } catch (error) {
+    expect(error).toBeInstanceOf(InvalidInferenceRequestError);
+    expect((error as InvalidInferenceRequestError).code).toBe(INVALID_REQUEST);
+    return (error as InvalidInferenceRequestError).reason;
+  }
+};
+
+describe("validateInferenceRequest", () => {
+  it("accepts a well formed request", () => {
+    expect(
+      reasonFor({
+        requestId: "req-1",
+        messages: [user("Hi"), { role: "assistant", text: "", tool_calls: [{ id: "call-1", name: "roll", arguments: {} }] }, { role: "tool", text: "4", tool_call_id: "call-1" }],
+        examples: [user("Example")],
+        parameters: { temperature: 0.7 },
+      }),
+    ).toBeNull();
+    expect(reasonFor({ messages: [] })).toBeNull();
+  });
+
+  it("refuses unknown roles in messages and examples", () => {
+    expect(reasonFor({ messages: [{ role: "system", text: "Be nice" } as unknown as InferenceMessage] })).toBe("invalid_role");
+    expect(reasonFor({ messages: [user("Hi")], examples: [{ role: "narrator", text: "..." } as unknown as InferenceMessage] })).toBe("invalid_role");
+  });
+
+  it("refuses empty ids", () => {
+    expect(reasonFor({ requestId: " ", messages: [user("Hi")] })).toBe("empty_id");
+    expect(reasonFor({ messages: [{ role: "tool", text: "4" }] })).toBe("empty_id");
+    expect(reasonFor({ messages: [{ role: "assistant", text: "", tool_calls: [{ id: "", name: "roll", arguments: {} }] }] })).toBe("empty_id");
+  });
+
+  it("refuses more messages than the cap", () => {
+    const messages = Array.from({ length: MAX_INFERENCE_MESSAGES }, (_, index) => user(String(index)));
+
+    expect(reasonFor({ messages })).toBeNull();
+    expect(reasonFor({ messages, examples: [user("One more")] })).toBe("too_many_messages");
+  });
+
+  it("refuses parameters that aren't an object", () => {
+    expect(reasonFor({ messages: [user("Hi")], parameters: null })).toBe("invalid_parameters");
+    expect(reasonFor({ messages: [user("Hi")], parameters: [0.7] })).toBe("invalid_parameters");
+    expect(reasonFor({ messages: [user("Hi")], parameters: "temperature=0.7" })).toBe("invalid_parameters");
+  });
+
+  it("tells where the bad message is", () => {
+    try {
+      validateInferenceRequest({ messages: [user("Hi"), { role: "bot", text: "Hello" } as unknown as InferenceMessage] });
+      expect.unreachable();
+    } catch (error) {
+      expect((error as InvalidInferenceRequestError).details).toEqual({ list: "messages", index: 1, role: "bot" });
+    }
+  });
+});
@@ -0,0 +1,51 @@
+import { describe, expect, it } from "vitest";
+import { ResponseLengthPresetsSchema } from "@/schema/profiles-schema";
+import { applyResponseLength, appendResponseLengthInstruction } from "../response-length";
+
+const presets = ResponseLengthPresetsSchema.parse({
+  short: { max_tokens: 120, instruction: "Respond in 1-2 sentences." },
+});
+
+describe("applyResponseLength", () => {
+  it("leaves parameters without a preset untouched", () => {
+    const parameters = { max_tokens: 1024, temperature: 0.7 };
+    expect(applyResponseLength(parameters, presets)).toEqual({ parameters, instruction: "" });
+  });
+
+  it("replaces the template max_tokens with the preset", () => {
+    const { parameters, instruction } = applyResponseLength({ max_tokens: 1024, response_length: "short" }, presets);
+    expect(parameters.max_tokens).toBe(120);
+    expect(instruction).toBe("Respond in 1-2 sentences.");
+  });
+
+  it("always lets an explicit max_tokens override the preset", () => {
+    const { parameters, instruction } = applyResponseLength({ max_tokens: 1024, response_length: "short" }, presets, 64);
+    expect(parameters.max_tokens).toBe(64);
+    expect(instruction).toBe("Respond in 1-2 sentences.");
+  });
+
+  it("keeps the template max_tokens when the preset has no cap", () => {
+    const { parameters, instruction } = applyResponseLength({ max_tokens: 1024, response_length: "unlimited" }, presets);
+    expect(parameters.max_tokens).toBe(1024);
+    expect(instruction).toBe("");
+  });
+
+  it("ignores unknown presets", () => {
+    const { parameters } = applyResponseLength({ max_tokens: 1024, response_length: "huge" }, presets);
+    expect(parameters.max_tokens).toBe(1024);
+  });
+});
+
+describe("appendResponseLengthInstruction", () => {
+  it("appends the instruction after the system prompt", () => {
+    expect(appendResponseLengthInstruction("You are Alice.", "Respond in 1-2 sentences.")).toBe("You are Alice.\n\nRespond in 1-2 sentences.");
+  });
+
+  it("uses the instruction alone when there is no system prompt", () => {
+    expect(appendResponseLengthInstruction(undefined, "Respond in 1-2 sentences.")).toBe("Respond in 1-2 sentences.");
+  });
+
+  it("keeps the system prompt when there is no instruction", () => {
+    expect(appendResponseLengthInstruction("You are Alice.", "")).toBe("You are Alice.");
+  });
+});
@@ -0,0 +1,51 @@
+import { beforeEach, describe, expect, it } from "vitest";
+import { appendSnapshotText, dropRequestSnapshot, getActiveRequestSnapshots, MAX_SNAPSHOT_CHARS, setSnapshotStatus, trackRequestSnapshot } from "../stream-snapshots";
+
+describe("stream snapshots", () => {
+  beforeEach(() => {
+    for (const snapshot of getActiveRequestSnapshots()) {
+      dropRequestSnapshot(snapshot.requestId);
+    }
+  });
+
+  it("follows a request from the queue to its last chunk", () => {
+    trackRequestSnapshot("req-1", "chat-1", 1);
+    expect(getActiveRequestSnapshots("chat-1")).toEqual([{ requestId: "req-1", chatId: "chat-1", status: "queued", text: "", length: 0, truncated: false, updatedAt: 1 }]);
+
+    setSnapshotStatus("req-1", "started", 2);
+    appendSnapshotText("req-1", "Hello", 3);
+    appendSnapshotText("req-1", " there", 4);
+
+    expect(getActiveRequestSnapshots("chat-1")[0]).toMatchObject({ status: "streaming", text: "Hello there", length: 11, updatedAt: 4 });
+  });
+
+  it("filters by chat and forgets finished requests", () => {
+    trackRequestSnapshot("req-1", "chat-1");
+    trackRequestSnapshot("req-2", "chat-2");
+    trackRequestSnapshot("req-3", null);
+
+    expect(getActiveRequestSnapshots("chat-2").map((snapshot) => snapshot.requestId)).toEqual(["req-2"]);
+    expect(getActiveRequestSnapshots()).toHaveLength(3);
+
+    dropRequestSnapshot("req-2");
+    appendSnapshotText("req-2", "late chunk");
+    expect(getActiveRequestSnapshots("chat-2")).toEqual([]);
+  });
+
+  it("keeps only the end of a long reply", () => {
+    trackRequestSnapshot("req-1", "chat-1");
+    appendSnapshotText("req-1", "a".repeat(MAX_SNAPSHOT_CHARS));
+    appendSnapshotText("req-1", "bcd");
+
+    const [snapshot] = getActiveRequestSnapshots();
+    expect(snapshot.text).toHaveLength(MAX_SNAPSHOT_CHARS);
+    expect(snapshot.text.endsWith("abcd")).toBe(true);
+    expect(snapshot).toMatchObject({ length: MAX_SNAPSHOT_CHARS + 3, truncated: true });
+  });
+
+  it("returns copies", () => {
+    trackRequestSnapshot("req-1", "chat-1");
+    getActiveRequestSnapshots()[0].text = "changed";
+    expect(getActiveRequestSnapshots()[0].text).toBe("");
+  });
+});
@@ -0,0 +1,48 @@
+import { describe, expect, it, vi } from "vitest";
+import type { SequencedChatMessage } from "@/services/chat-message-service";
+import { buildSynopsisPrompt, getSynopsisSettings } from "../synopsis";
+
+vi.mock("@/hooks/chatStore", () => ({ useChatStore: { getState: vi.fn(), setState: vi.fn() } }));
+vi.mock("@/hooks/ProfileStore", () => ({ useCurrentProfile: vi.fn() }));
+vi.mock("@/services/background-inference-service", () => ({ useBackgroundInference: vi.fn() }));
+vi.mock("@/services/character-service", () => ({ listCharacters: vi.fn() }));
+vi.mock("@/services/chat-message-service", () => ({ countMessagesAfterSequence: vi.fn(), listMessagesAfterSequence: vi.fn() }));
+vi.mock("@/services/chat-service", () => ({ getChatById: vi.fn(), setChatSynopsis: vi.fn() }));
+vi.mock("@/services/model-service", () => ({ getModelByIdOrAlias: vi.fn() }));
+vi.mock("@/services/template-chat-service", () => ({ getChatTemplateById: vi.fn() }));
+
+const message = (sequence: number, type: SequencedChatMessage["type"], text: string, excluded = false): SequencedChatMessage => ({
+  sequence,
+  type,
+  character_id: type === "character" ? "alice" : null,
+  text,
+  excluded,
+});
+
+describe("buildSynopsisPrompt", () => {
+  const nameOf = (entry: SequencedChatMessage) => (entry.type === "user" ? "Bob" : "Alice");
+
+  it("folds the new messages into the current synopsis", () => {
+    const prompt = buildSynopsisPrompt("Bob arrives at the harbor.", [message(4, "user", " Hello? "), message(5, "character", "Welcome aboard.")], nameOf);
+
+    expect(prompt).toBe("Current synopsis:\nBob arrives at the harbor.\n\nNew messages:\nBob: Hello?\n\nAlice: Welcome aboard.");
+  });
+
+  it("starts from scratch and skips excluded or empty messages", () => {
+    const prompt = buildSynopsisPrompt(null, [message(1, "user", "(OOC: brb)", true), message(2, "character", "  "), message(3, "character", "The ship sails.")], nameOf);
+
+    expect(prompt).toContain("(none yet");
+    expect(prompt).toContain("Alice: The ship sails.");
+    expect(prompt).not.toContain("brb");
+  });
+});
+
+describe("getSynopsisSettings", () => {
+  it("defaults to manual refreshes", () => {
+    expect(getSynopsisSettings(null)).toEqual({ auto_refresh: false, refresh_every: 20, model_id: "" });
+    expect(getSynopsisSettings({ settings: { hideDisabledMessages: false, hideScriptMessages: false, synopsis: { auto_refresh: true, refresh_every: 5, model_id: "" } } })).toMatchObject({
+      auto_refresh: true,
+      refresh_every: 5,
+    });
+  });
+});
@@ -0,0 +1,87 @@
+import { beforeEach, describe, expect, it, vi } from "vitest";
+import { emitToCurrentWindow } from "@/commands/windows";
+import { getChatTemplateById } from "@/services/template-chat-service";
+import { getFormatTemplateById } from "@/services/template-format-service";
+import { lockSystemPrompt, PROMPT_DRIFT_EVENT, rebaselineSystemPrompt, summarizePromptDrift, unlockSystemPrompt, verifySystemPromptLock } from "../system-prompt-lock";
+
+vi.mock("@/commands/windows", () => ({ emitToCurrentWindow: vi.fn(async () => undefined) }));
+vi.mock("@/services/notes-service", () => ({ getNoteSnippets: vi.fn() }));
+vi.mock("@/services/template-chat-service", () => ({ getChatTemplateById: vi.fn() }));
+vi.mock("@/services/template-format-service", () => ({ getFormatTemplateById: vi.fn() }));
+
+const formatTemplate = (context: string): any => ({
+  id: "format-1",
+  config: { context_separator: "\\n---\\n", settings: {} },
+  prompts: [
+    { type: "context", content: context, enabled: true },
+    { type: "character-context", content: "{{character.personality}}", enabled: true },
+    { type: "lorebook-top", content: "{{lorebook.top}}", enabled: true },
+  ],
+});
+
+const chatTemplate = { id: "template-1", format_template_id: "format-1", custom_prompts: [{ id: "p1", name: "Style", role: "system", position: "bottom", prompt: "Write in third person.", enabled: true }] };
+
+const CHAT = "chat-1";
+
+describe("system prompt lock", () => {
+  beforeEach(() => {
+    vi.mocked(emitToCurrentWindow).mockClear();
+    vi.mocked(getChatTemplateById).mockResolvedValue(chatTemplate as any);
+    vi.mocked(getFormatTemplateById).mockResolvedValue(formatTemplate("You are {{char}}. Stay in character."));
+  });
+
+  it("passes while the templates are unchanged, whatever fills the sections", async () => {
+    const settings = await lockSystemPrompt({ chat_template_id: "template-1", settings: { hideDisabledMessages: true, hideScriptMessages: false } });
+
+    expect(settings.hideDisabledMessages).toBe(true);
+    expect(settings.system_prompt_locked).toBe(true);
+    expect(settings.system_prompt_hash).toMatch(/^[0-9a-f]{64}$/);
+
+    await verifySystemPromptLock(CHAT, settings, { formatTemplate: formatTemplate("You are {{char}}. Stay in character."), customPrompts: chatTemplate.custom_prompts as any });
+    expect(emitToCurrentWindow).not.toHaveBeenCalled();
+  });
+
+  it("reports a format template edit without blocking", async () => {
+    const settings = await lockSystemPrompt({ chat_template_id: "template-1", settings: null });
+
+    await verifySystemPromptLock(CHAT, settings, { formatTemplate: formatTemplate("You are {{char}}. Break character freely."), customPrompts: chatTemplate.custom_prompts as any });
+
+    expect(emitToCurrentWindow).toHaveBeenCalledWith(
+      PROMPT_DRIFT_EVENT,
+      expect.objectContaining({ chatId: CHAT, expectedHash: settings.system_prompt_hash, blocked: false, addedLines: 1, removedLines: 1, firstChangedLine: 1 }),
+    );
+  });
+
+  it("blocks a custom prompt edit when strict", async () => {
+    const settings = await lockSystemPrompt({ chat_template_id: "template-1", settings: null }, true);
+    const editedPrompts = [{ ...chatTemplate.custom_prompts[0], prompt: "Write in first person." }];
+
+    await expect(verifySystemPromptLock(CHAT, settings, { formatTemplate: formatTemplate("You are {{char}}. Stay in character."), customPrompts: editedPrompts as any })).rejects.toThrow(
+      /changed since it was locked/,
+    );
+    expect(emitToCurrentWindow).toHaveBeenCalledWith(PROMPT_DRIFT_EVENT, expect.objectContaining({ blocked: true }));
+  });
+
+  it("accepts the edit after a new baseline and ignores unlocked chats", async () => {
+    const settings = await lockSystemPrompt({ chat_template_id: "template-1", settings: null }, true);
+    vi.mocked(getFormatTemplateById).mockResolvedValue(formatTemplate("Narrate the scene."));
+
+    const rebaselined = await rebaselineSystemPrompt({ chat_template_id: "template-1", settings });
+    expect(rebaselined.system_prompt_lock_strict).toBe(true);
+    expect(rebaselined.system_prompt_hash).not.toBe(settings.system_prompt_hash);
+    await verifySystemPromptLock(CHAT, rebaselined, { formatTemplate: formatTemplate("Narrate the scene."), customPrompts: chatTemplate.custom_prompts as any });
+
+    const unlocked = unlockSystemPrompt(settings);
+    expect(unlocked).toEqual({ hideDisabledMessages: false, hideScriptMessages: false });
+    await verifySystemPromptLock(CHAT, unlocked, { formatTemplate: formatTemplate("Something else entirely."), customPrompts: [] });
+    expect(emitToCurrentWindow).not.toHaveBeenCalled();
+  });
+});
+
+describe("summarizePromptDrift", () => {
+  it("counts added and removed lines", () => {
+    expect(summarizePromptDrift("a\nb\nc", "a\nb\nc")).toEqual({ addedLines: 0, removedLines: 0, firstChangedLine: null });
+    expect(summarizePromptDrift("a\nb\nc", "a\nx\nc\nd")).toEqual({ addedLines: 2, removedLines: 1, firstChangedLine: 2 });
+    expect(summarizePromptDrift("a\nb", "b\na")).toEqual({ addedLines: 0, removedLines: 0, firstChangedLine: 1 });
+  });
+});
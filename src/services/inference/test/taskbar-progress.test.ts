@@ -0,0 +1,84 @@
+import { ProgressBarStatus } from "@tauri-apps/api/window";
+import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
+import { computeTaskbarState, TASKBAR_UPDATE_INTERVAL_MS, trackInferenceProgress, untrackInferenceProgress, updateInferenceProgress } from "../taskbar-progress";
+
+const { appWindow, profileState } = vi.hoisted(() => ({
+  appWindow: { setProgressBar: vi.fn(async () => undefined), setBadgeCount: vi.fn(async () => undefined) },
+  profileState: { currentProfile: { settings: { system: { taskbarProgress: true } } } },
+}));
+
+vi.mock("@tauri-apps/api/window", async (importOriginal) => ({ ...(await importOriginal<object>()), getCurrentWindow: () => appWindow }));
+vi.mock("@/hooks/ProfileStore", () => ({ useProfileStore: { getState: () => profileState } }));
+
+describe("computeTaskbarState", () => {
+  it("clears the icon without requests", () => {
+    expect(computeTaskbarState([])).toEqual({ status: ProgressBarStatus.None });
+  });
+
+  it("pulses while requests wait or have no known length", () => {
+    expect(computeTaskbarState([{ maxTokens: 500, outputTokens: 0, streaming: false }])).toEqual({ status: ProgressBarStatus.Indeterminate, badge: 1 });
+    expect(computeTaskbarState([{ outputTokens: 80, streaming: true }])).toEqual({ status: ProgressBarStatus.Indeterminate, badge: 1 });
+  });
+
+  it("averages the streamed share of max_tokens and counts every request", () => {
+    const state = computeTaskbarState([
+      { maxTokens: 400, outputTokens: 100, streaming: true },
+      { maxTokens: 100, outputTokens: 250, streaming: true },
+      { maxTokens: 300, outputTokens: 0, streaming: false },
+    ]);
+    expect(state).toEqual({ status: ProgressBarStatus.Normal, progress: 63, badge: 3 });
+  });
+});
+
+describe("taskbar progress", () => {
+  beforeEach(() => {
+    vi.useFakeTimers();
+    appWindow.setProgressBar.mockClear();
+    appWindow.setBadgeCount.mockClear();
+    profileState.currentProfile.settings.system.taskbarProgress = true;
+  });
+
+  afterEach(() => {
+    untrackInferenceProgress("a");
+    untrackInferenceProgress("b");
+    vi.runAllTimers();
+    vi.useRealTimers();
+  });
+
+  it("throttles stream updates to a few per second", () => {
+    trackInferenceProgress("a", 1000);
+    vi.advanceTimersByTime(TASKBAR_UPDATE_INTERVAL_MS);
+    appWindow.setProgressBar.mockClear();
+
+    for (let tokens = 1; tokens <= 100; tokens++) {
+      updateInferenceProgress("a", tokens * 5);
+      vi.advanceTimersByTime(10);
+    }
+    vi.advanceTimersByTime(TASKBAR_UPDATE_INTERVAL_MS);
+
+    expect(appWindow.setProgressBar.mock.calls.length).toBeLessThanOrEqual(5);
+    expect(appWindow.setProgressBar).toHaveBeenLastCalledWith({ status: ProgressBarStatus.Normal, progress: 50 });
+  });
+
+  it("clears the icon once the queue is empty", () => {
+    trackInferenceProgress("a");
+    trackInferenceProgress("b");
+    vi.advanceTimersByTime(TASKBAR_UPDATE_INTERVAL_MS);
+    expect(appWindow.setBadgeCount).toHaveBeenLastCalledWith(2);
+
+    untrackInferenceProgress("a");
+    untrackInferenceProgress("b");
+    vi.advanceTimersByTime(TASKBAR_UPDATE_INTERVAL_MS);
+    expect(appWindow.setProgressBar).toHaveBeenLastCalledWith({ status: ProgressBarStatus.None, progress: undefined });
+    expect(appWindow.setBadgeCount).toHaveBeenLastCalledWith(undefined);
+  });
+
+  it("leaves the icon alone when turned off in the profile", () => {
+    profileState.currentProfile.settings.system.taskbarProgress = false;
+    trackInferenceProgress("a", 100);
+    updateInferenceProgress("a", 50);
+    vi.advanceTimersByTime(TASKBAR_UPDATE_INTERVAL_MS);
+    expect(appWindow.setProgressBar).not.toHaveBeenCalled();
+    expect(appWindow.setBadgeCount).not.toHaveBeenCalled();
+  });
+});
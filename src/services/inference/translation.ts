@@ -0,0 +1,83 @@
+import { useCallback } from "react";
+import type { ChatTranslationSettings } from "@/schema/chat-schema";
+import { useBackgroundInference } from "../background-inference-service";
+import { getChatById } from "../chat-service";
+import { getModelById, getModelByIdOrAlias } from "../model-service";
+import { getChatTemplateById } from "../template-chat-service";
+
+export const TRANSLATION_SYSTEM_PROMPT = `You are a translation engine. Translate the user's text into {{language}}.
+Preserve formatting, markdown, line breaks, names, and anything inside *asterisks* or "quotes" as prose to be translated.
+If the text is already in {{language}}, return it unchanged.
+Reply with the translated text only, without notes, quotes, or explanations.`;
+
+export type TranslationDirection = "outgoing" | "incoming";
+
+export function translatesDirection(settings: ChatTranslationSettings | null | undefined, direction: TranslationDirection): boolean {
+  if (!settings?.enabled) {
+    return false;
+  }
+  if (direction === "incoming" && !settings.user_language.trim()) {
+    return false;
+  }
+  return settings.direction === "both" || settings.direction === direction;
+}
+
+/**
+ * Translation settings of a chat, with the model resolved to the chat template's one when unset.
+ * Returns null when translation is disabled or no model can be resolved.
+ */
+export async function getChatTranslation(chatId: string, profileId: string): Promise<ChatTranslationSettings | null> {
+  const chat = await getChatById(chatId, profileId).catch(() => null);
+  const settings = chat?.settings?.translation;
+  if (!chat || !settings?.enabled) {
+    return null;
+  }
+
+  if (settings.model_id) {
+    return settings;
+  }
+
+  const chatTemplate = chat.chat_template_id ? await getChatTemplateById(chat.chat_template_id).catch(() => null) : null;
+  // The template may name its model by alias
+  const model = chatTemplate?.model_id ? await getModelByIdOrAlias(chatTemplate.model_id, profileId) : null;
+  return model ? { ...settings, model_id: model.id } : null;
+}
+
+/**
+ * Hook exposing a standalone `translateText` built on background inference.
+ * Rejects on failure; callers in the chat flow fall back to the untranslated text.
+ */
+export function useTranslation() {
+  const { executeInference } = useBackgroundInference();
+
+  const translateText = useCallback(
+    async (text: string, targetLanguage: string, modelId: string): Promise<string> => {
+      if (!text.trim()) {
+        return text;
+      }
+
+      const model = await getModelById(modelId);
+      if (!model) {
+        throw new Error(`Translation model ${modelId} not found`);
+      }
+
+      const translated = await executeInference({
+        model,
+        prompt: [{ role: "user", text }],
+        systemPrompt: TRANSLATION_SYSTEM_PROMPT.replace(/\{\{language\}\}/g, targetLanguage),
+        parameters: { temperature: 0.2 },
+        // Sending and showing messages wait on it
+        priority: "interactive",
+      });
+
+      const result = translated.trim();
+      if (!result) {
+        throw new Error("The translation model returned an empty response");
+      }
+      return result;
+    },
+    [executeInference],
+  );
+
+  return { translateText };
+}
@@ -1,5 +1,8 @@
-import { ChatMessage } from "@/schema/chat-message-schema";
+import { ChatMessage, VariantModel } from "@/schema/chat-message-schema";
+import type { ChatTranslationSettings } from "@/schema/chat-schema";
 import { FormatTemplate } from "@/schema/template-format-schema";
+import type { OutputGuard } from "./output-guard";
+import type { RegenerateMode } from "./regenerate";
 
 /**
  * StreamingState interface for tracking the streaming state of a message.
@@ -24,6 +27,22 @@ export interface StreamingState {
    * Set by the orchestrator to prevent double-firing agent triggers.
    */
   emitChatEvents?: boolean;
+  /**
+   * Model serving this request, recorded on the message variant once it completes.
+   */
+  variantModel?: VariantModel | null;
+  /**
+   * Chat translation settings, set when the reply must be translated back once it completes.
+   */
+  translation?: ChatTranslationSettings | null;
+  /**
+   * Name prefix / impersonation guard of the chat, null when the chat enables neither.
+   */
+  outputGuard?: OutputGuard | null;
+  /**
+   * Set when the session regenerates an existing message, so completion emits `message-regenerated`.
+   */
+  regenerateMode?: RegenerateMode | null;
 }
 
 /**
@@ -53,6 +72,7 @@ export interface GenerationOptions {
   // Prompt Configuration
   systemPromptOverride?: string; // Override System Prompt
   parametersOverride?: Record<string, any>; // Override Parameters
+  parameterOverrides?: Record<string, unknown>; // Merged over the template's (or parametersOverride's) parameters
   messageHistoryOverride?: ChatMessage[]; // Override Message History
 
   // Streaming Configuration
@@ -70,6 +90,11 @@ export interface GenerationOptions {
    * Defaults to true (events emitted normally).
    */
   emitChatEvents?: boolean;
+
+  /**
+   * Set by `regenerateMessage`; the reply emits `message-regenerated` once saved.
+   */
+  regenerateMode?: RegenerateMode | null;
 }
 
 export const DEFAULT_THINKING_CONFIG = {
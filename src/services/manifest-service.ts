@@ -69,6 +69,11 @@ export async function getManifest<T extends ManifestType>(type: T, filename: str
   }
 }
 
+// The mock engine is a development tool and stays out of release builds
+function isDevelopmentOnly(manifest: unknown): boolean {
+  return (manifest as Partial<Manifest>)?.engine === "mock";
+}
+
 /**
  * Reads and parses all available manifest files of a specific type
  * @param type - The type of manifest to retrieve
@@ -83,6 +88,9 @@ export async function getAllManifests<T extends ManifestType>(type: T): Promise<
 
     results.forEach((result, index) => {
       if (result.status === "fulfilled") {
+        if (isDevelopmentOnly(result.value) && !import.meta.env.DEV) {
+          return;
+        }
         validManifests.push(result.value);
       } else {
         const filename = filenames[index];
@@ -1,15 +1,23 @@
+import { z } from "zod";
+import { type ModelRebind, rebindModelCommand } from "@/commands/database.ts";
+import { parseConfigFile } from "@/commands/imports.ts";
 import { encryptApiKey } from "@/commands/security.ts";
 import { parseBoolean } from "@/pages/agents/components/json-schema/schema-utils";
 import { formatDateTime } from "@/utils/date-time.ts";
-import { Model, ModelSchema, ModelType } from "../schema/models-schema.ts";
+import type { Manifest } from "../schema/model-manifest-schema.ts";
+import { Model, ModelCapabilities, ModelCapabilitiesSchema, ModelSchema, ModelType, ModelTypeSchema } from "../schema/models-schema.ts";
 import { uuidUtils } from "../schema/utils-schema.ts";
-import { buildUpdateParams, executeDBQuery, selectDBQuery } from "../utils/database.ts";
+import { buildUpdateParams, executeDBQuery, selectDBQuery, setFavorite } from "../utils/database.ts";
+import { invalidateProviders } from "./ai-providers/aisdk/provider-cache.ts";
+import { fetchProviderCapabilities, type ProviderCapabilities } from "./ai-providers/aisdk/provider-capabilities.ts";
+import { manifestSupportsReasoning } from "./ai-providers/aisdk/reasoning-support.ts";
 import { getModelManifestById } from "./manifest-service.ts";
 
 // Interface for creating a new model
 export interface NewModelParams {
   profile_id: string;
   name: string;
+  alias?: string | null;
   type: ModelType;
   config: Record<string, any>;
   manifest_id: string;
@@ -23,6 +31,79 @@ export interface ModelFilter {
   type?: ModelType;
 }
 
+// Result of validating a model config against its manifest.
+// Errors block saving, warnings are surfaced to the user but don't prevent the save.
+export interface ModelConfigValidationReport {
+  errors: string[];
+  warnings: string[];
+}
+
+// Stored capabilities, or null when never refreshed or no longer valid
+function parseCapabilities(value: string | null | undefined): ModelCapabilities | null {
+  if (!value) {
+    return null;
+  }
+  try {
+    return ModelCapabilitiesSchema.parse(JSON.parse(value));
+  } catch {
+    return null;
+  }
+}
+
+const LOCAL_HOSTNAMES = ["localhost", "127.0.0.1", "0.0.0.0", "::1", "[::1]"];
+
+// Validate a model config against the fields declared in its manifest
+export function validateModelConfig(manifest: Manifest, config: Record<string, any>): ModelConfigValidationReport {
+  const report: ModelConfigValidationReport = { errors: [], warnings: [] };
+  const knownKeys = new Set(manifest.fields.map((field) => field.key));
+
+  for (const field of manifest.fields) {
+    const value = config[field.key];
+    const isEmpty = value === undefined || value === null || value === "";
+
+    if (isEmpty) {
+      if (field.required && field.field_type !== "hidden" && field.default === undefined) {
+        report.errors.push(`${field.label} is required.`);
+      } else if (field.field_type === "secret") {
+        report.warnings.push(`${field.label} is empty; requests will be sent without it.`);
+      }
+      continue;
+    }
+
+    switch (field.field_type) {
+      case "number":
+        if (Number.isNaN(Number(value))) {
+          report.errors.push(`${field.label} must be a number.`);
+        }
+        break;
+      case "url": {
+        let url: URL;
+        try {
+          url = new URL(String(value));
+        } catch {
+          report.errors.push(`${field.label} is not a valid URL.`);
+          break;
+        }
+        if (url.protocol === "http:" && !LOCAL_HOSTNAMES.includes(url.hostname)) {
+          report.warnings.push(`${field.label} uses plain HTTP for a remote host; credentials will be sent unencrypted.`);
+        }
+        if (/\/(chat\/)?completions\/?$|\/embeddings\/?$/.test(url.pathname)) {
+          report.warnings.push(`${field.label} looks like a full endpoint path; it usually should be the base URL (e.g. ending in /v1).`);
+        }
+        break;
+      }
+    }
+  }
+
+  for (const key of Object.keys(config)) {
+    if (!knownKeys.has(key)) {
+      report.warnings.push(`Unknown config field "${key}" is not used by ${manifest.name}.`);
+    }
+  }
+
+  return report;
+}
+
 // Create a new model
 export async function createModel(modelData: NewModelParams, disableEncryption?: boolean): Promise<Model> {
   // Validate profile_id is a valid UUID
@@ -33,6 +114,13 @@ export async function createModel(modelData: NewModelParams, disableEncryption?:
 
   const modelManifest = await getModelManifestById(modelData.manifest_id)!;
 
+  if (modelManifest && modelData.config) {
+    const { errors } = validateModelConfig(modelManifest, modelData.config);
+    if (errors.length > 0) {
+      throw new Error(`Invalid model configuration: ${errors.join(" ")}`);
+    }
+  }
+
   // Process and encrypt any secret fields in the config
   if (modelManifest && modelData.config) {
     const secretFields = modelManifest.fields.filter((field) => field.field_type === "secret").map((field) => field.key);
@@ -66,6 +154,7 @@ export async function createModel(modelData: NewModelParams, disableEncryption?:
     id,
     profile_id: profileId,
     name: modelData.name,
+    alias: modelData.alias ?? null,
     type: modelData.type,
     manifest_id: modelData.manifest_id,
     config: modelData.config,
@@ -78,12 +167,13 @@ export async function createModel(modelData: NewModelParams, disableEncryption?:
   const configStr = JSON.stringify(validatedModel.config);
 
   await executeDBQuery(
-    `INSERT INTO models (id, profile_id, name, type, config, manifest_id, max_concurrency, inference_template_id, created_at, updated_at) 
-     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)`,
+    `INSERT INTO models (id, profile_id, name, alias, type, config, manifest_id, max_concurrency, inference_template_id, created_at, updated_at) 
+     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)`,
     [
       validatedModel.id,
       validatedModel.profile_id,
       validatedModel.name,
+      validatedModel.alias ?? null,
       validatedModel.type,
       configStr,
       validatedModel.manifest_id,
@@ -108,12 +198,15 @@ export async function getModelById(id: string): Promise<Model | null> {
       id, 
       profile_id, 
       name, 
+      alias,
       type, 
       config,
       manifest_id,
       inference_template_id,
       max_concurrency,
       favorite,
+      capabilities,
+      fallback_model_ids,
       created_at, 
       updated_at
     FROM models 
@@ -129,6 +222,8 @@ export async function getModelById(id: string): Promise<Model | null> {
 
   model.config = JSON.parse(model.config || "{}");
   model.favorite = parseBoolean(model.favorite);
+  model.capabilities = parseCapabilities(model.capabilities);
+  model.fallback_model_ids = JSON.parse(model.fallback_model_ids || "[]");
   model.created_at = new Date(model.created_at);
   model.updated_at = new Date(model.updated_at);
 
@@ -142,12 +237,15 @@ export async function listModels(filter?: ModelFilter): Promise<Model[]> {
       id, 
       profile_id, 
       name, 
+      alias,
       type, 
       config,
       manifest_id,
       inference_template_id,
       max_concurrency,
       favorite,
+      capabilities,
+      fallback_model_ids,
       created_at, 
       updated_at
     FROM models
@@ -177,8 +275,8 @@ export async function listModels(filter?: ModelFilter): Promise<Model[]> {
     query += ` WHERE ${conditions.join(" AND ")}`;
   }
 
-  // Add order by to ensure consistent results
-  query += " ORDER BY created_at DESC";
+  // Favorites first, then newest (id breaks ties within the same second)
+  query += " ORDER BY favorite DESC, created_at DESC, id ASC";
 
   const result = await selectDBQuery<any[]>(query, params);
 
@@ -187,11 +285,48 @@ export async function listModels(filter?: ModelFilter): Promise<Model[]> {
     ...model,
     config: JSON.parse(model.config || "{}"),
     favorite: parseBoolean(model.favorite),
+    capabilities: parseCapabilities(model.capabilities),
+    fallback_model_ids: JSON.parse(model.fallback_model_ids || "[]"),
     created_at: new Date(model.created_at),
     updated_at: new Date(model.updated_at),
   })) as Model[];
 }
 
+// Find a model among already loaded ones by ID, or by alias when no ID matches.
+// References (chat templates, widgets) may hold either.
+export function findModelByIdOrAlias<T extends Pick<Model, "id" | "alias">>(models: T[], idOrAlias: string | null | undefined): T | undefined {
+  if (!idOrAlias) {
+    return undefined;
+  }
+  return models.find((model) => model.id === idOrAlias) ?? models.find((model) => model.alias === idOrAlias);
+}
+
+// Get a model by ID or by its alias within a profile
+export async function getModelByIdOrAlias(idOrAlias: string, profileId: string): Promise<Model | null> {
+  const validProfileId = uuidUtils.uuid().parse(profileId);
+
+  const result = await selectDBQuery<{ id: string }[]>("SELECT id FROM models WHERE profile_id = $1 AND (id = $2 OR alias = $2) ORDER BY id = $2 DESC LIMIT 1", [
+    validProfileId,
+    idOrAlias,
+  ]);
+
+  if (result.length === 0) {
+    return null;
+  }
+
+  return getModelById(result[0].id);
+}
+
+// Rebind everything that references a model to another model of the same profile, in one transaction.
+// The alias moves along with the references when the target model has none.
+export async function rebindModel(oldId: string, newId: string, profileId: string): Promise<ModelRebind> {
+  const validProfileId = uuidUtils.uuid().parse(profileId);
+  const oldModelId = uuidUtils.uuid().parse(oldId);
+  const newModelId = uuidUtils.uuid().parse(newId);
+
+  return rebindModelCommand(oldModelId, newModelId, validProfileId);
+}
+
 // Update a model
 export async function updateModel(id: string, updateData: Partial<Omit<Model, "id" | "profile_id" | "created_at" | "updated_at">>): Promise<Model | null> {
   const modelId = uuidUtils.uuid().parse(id);
@@ -202,9 +337,21 @@ export async function updateModel(id: string, updateData: Partial<Omit<Model, "i
     return null;
   }
 
+  if (updateData.config) {
+    const modelManifest = await getModelManifestById(updateData.manifest_id ?? currentModel.manifest_id);
+    if (modelManifest) {
+      const { errors } = validateModelConfig(modelManifest, updateData.config);
+      if (errors.length > 0) {
+        throw new Error(`Invalid model configuration: ${errors.join(" ")}`);
+      }
+    }
+  }
+
   // Define field transformations
   const fieldMapping = {
     config: (value: any) => (typeof value === "string" ? value : JSON.stringify(value)),
+    capabilities: (value: ModelCapabilities | null) => (value ? JSON.stringify(value) : null),
+    fallback_model_ids: (value: string[]) => JSON.stringify(value.filter((fallbackId) => fallbackId !== modelId)),
   };
 
   // Build update parameters
@@ -213,18 +360,117 @@ export async function updateModel(id: string, updateData: Partial<Omit<Model, "i
   // Execute update if there are fields to update
   if (updates.length > 0) {
     await executeDBQuery(`UPDATE models SET ${updates.join(", ")}${whereClause}`, values);
+    invalidateProviders(modelId);
   }
 
   // Return the updated model
   return getModelById(modelId);
 }
 
+/**
+ * Refresh what a model supports (context length, vision, tools, reasoning, input modalities).
+ * The provider is asked where it can describe the model; anything it doesn't report falls back to the manifest defaults.
+ * @returns The updated model, or null if it doesn't exist in the profile
+ */
+export async function refreshModelCapabilities(id: string, profileId: string): Promise<Model | null> {
+  const model = await getModelById(id);
+  if (!model || model.profile_id !== uuidUtils.uuid().parse(profileId)) {
+    return null;
+  }
+
+  const manifest = await getModelManifestById(model.manifest_id);
+  const defaults = manifest?.capabilities ?? {};
+
+  let reported: ProviderCapabilities | null = null;
+  if (manifest) {
+    try {
+      reported = await fetchProviderCapabilities(manifest.engine, model.config);
+    } catch (error) {
+      console.warn(`Could not read the capabilities of model ${model.name} from the provider, using the manifest defaults:`, error instanceof Error ? error.message : String(error));
+    }
+  }
+
+  const pick = <K extends keyof ProviderCapabilities>(key: K) => reported?.[key] ?? defaults[key] ?? null;
+  const capabilities = ModelCapabilitiesSchema.parse({
+    context_length: pick("context_length"),
+    supports_vision: pick("supports_vision"),
+    supports_tools: pick("supports_tools"),
+    supports_reasoning: reported?.supports_reasoning ?? defaults.supports_reasoning ?? manifestSupportsReasoning(manifest) ?? null,
+    modality: pick("modality"),
+    source: reported ? "provider" : "manifest",
+    refreshed_at: new Date().toISOString(),
+  });
+
+  return updateModel(model.id, { capabilities });
+}
+
+// Shape of a model entry in an imported JSON/YAML config file
+const ModelConfigFileEntrySchema = z.object({
+  name: z.string().min(1),
+  alias: z.string().min(1).nullable().optional(),
+  type: ModelTypeSchema.default("llm"),
+  manifest_id: z.string().min(1),
+  config: z.record(z.string(), z.any()).default({}),
+  max_concurrency: z.number().min(1).max(10).optional(),
+  inference_template_id: z.string().nullable().optional(),
+});
+
+// Create one or more models from a JSON/YAML config file. Every entry is validated before any model is created.
+export async function importModelConfig(profileId: string, fileContents: string, format: "json" | "yaml"): Promise<Model[]> {
+  const validProfileId = uuidUtils.uuid().parse(profileId);
+  const parsed = await parseConfigFile(fileContents, format);
+
+  const parseResult = z.union([ModelConfigFileEntrySchema, ModelConfigFileEntrySchema.array().min(1)]).safeParse(parsed);
+  if (!parseResult.success) {
+    throw new Error(`Invalid model config file: ${parseResult.error.issues.map((issue) => `${issue.path.join(".")}: ${issue.message}`).join("; ")}`);
+  }
+  const entries = Array.isArray(parseResult.data) ? parseResult.data : [parseResult.data];
+
+  const errors: string[] = [];
+  for (const [index, entry] of entries.entries()) {
+    const manifest = await getModelManifestById(entry.manifest_id);
+    if (!manifest) {
+      errors.push(`Model ${index + 1} (${entry.name}): unknown manifest "${entry.manifest_id}".`);
+      continue;
+    }
+    const report = validateModelConfig(manifest, entry.config);
+    errors.push(...report.errors.map((error) => `Model ${index + 1} (${entry.name}): ${error}`));
+  }
+  if (errors.length > 0) {
+    throw new Error(errors.join("\n"));
+  }
+
+  const models: Model[] = [];
+  for (const entry of entries) {
+    models.push(
+      await createModel({
+        profile_id: validProfileId,
+        name: entry.name,
+        alias: entry.alias,
+        type: entry.type,
+        config: entry.config,
+        manifest_id: entry.manifest_id,
+        max_concurrency: entry.max_concurrency,
+        inference_template_id: entry.inference_template_id ?? undefined,
+      }),
+    );
+  }
+
+  return models;
+}
+
+// Mark or unmark a model as favorite
+export async function setModelFavorite(id: string, profileId: string, favorite: boolean): Promise<boolean> {
+  return setFavorite("models", id, profileId, favorite);
+}
+
 // Delete a model
 export async function deleteModel(id: string): Promise<boolean> {
   // Validate ID input
   const modelId = uuidUtils.uuid().parse(id);
 
   const result = await executeDBQuery("DELETE FROM models WHERE id = $1", [modelId]);
+  invalidateProviders(modelId);
 
   // Return true if a row was affected (model was deleted)
   return result.rowsAffected > 0;
@@ -0,0 +1,202 @@
+import { decryptApiKey, encryptApiKey } from "@/commands/security";
+import { parseBoolean } from "@/pages/agents/components/json-schema/schema-utils";
+import { formatDateTime } from "@/utils/date-time";
+import { CreateNoteParams, CreateNoteSchema, Note, NotesBackup, NotesBackupSchema, NoteSchema, UpdateNoteParams, UpdateNoteSchema } from "../schema/note-schema";
+import { uuidUtils } from "../schema/utils-schema";
+import { buildUpdateParams, executeDBQuery, selectDBQuery } from "../utils/database";
+
+export interface NoteFilter {
+  // Matched against titles, tags and the content of notes that aren't encrypted
+  search?: string;
+  tag?: string;
+  pinned?: boolean;
+}
+
+// Rows as stored: encrypted notes still hold their ciphertext
+function parseStoredNoteRow(row: any): Note {
+  return NoteSchema.parse({
+    ...row,
+    tags: JSON.parse(row.tags || "[]"),
+    pinned: parseBoolean(row.pinned),
+    encrypted: parseBoolean(row.encrypted),
+    created_at: new Date(row.created_at),
+    updated_at: new Date(row.updated_at),
+  });
+}
+
+async function parseNoteRow(row: any): Promise<Note> {
+  const note = parseStoredNoteRow(row);
+  return note.encrypted && note.content ? { ...note, content: await decryptApiKey(note.content) } : note;
+}
+
+async function storedContent(content: string, encrypted: boolean): Promise<string> {
+  return encrypted && content ? encryptApiKey(content) : content;
+}
+
+function escapeLike(value: string): string {
+  return value.replace(/[\\%_]/g, (char) => `\\${char}`);
+}
+
+// Create a note. Titles are unique per profile.
+export async function createNote(noteData: CreateNoteParams): Promise<Note> {
+  const validated = CreateNoteSchema.parse(noteData);
+  const id = crypto.randomUUID();
+  const now = formatDateTime();
+
+  await executeDBQuery(
+    `INSERT INTO notes (id, profile_id, title, content, tags, pinned, encrypted, created_at, updated_at)
+     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)`,
+    [
+      id,
+      validated.profile_id,
+      validated.title,
+      await storedContent(validated.content, validated.encrypted),
+      JSON.stringify(validated.tags),
+      validated.pinned ? 1 : 0,
+      validated.encrypted ? 1 : 0,
+      now,
+      now,
+    ],
+  );
+
+  return NoteSchema.parse({
+    ...validated,
+    id,
+    created_at: new Date(now),
+    updated_at: new Date(now),
+  });
+}
+
+// Get a note by ID, with its content decrypted
+export async function getNoteById(id: string, profileId: string): Promise<Note | null> {
+  const result = await selectDBQuery<any[]>("SELECT * FROM notes WHERE id = $1 AND profile_id = $2", [uuidUtils.uuid().parse(id), uuidUtils.uuid().parse(profileId)]);
+  return result.length > 0 ? parseNoteRow(result[0]) : null;
+}
+
+// Get a note by its exact title, with its content decrypted
+export async function getNoteByTitle(profileId: string, title: string): Promise<Note | null> {
+  const result = await selectDBQuery<any[]>("SELECT * FROM notes WHERE profile_id = $1 AND title = $2", [uuidUtils.uuid().parse(profileId), title.trim()]);
+  return result.length > 0 ? parseNoteRow(result[0]) : null;
+}
+
+// List a profile's notes, pinned first then most recently edited
+export async function listNotes(profileId: string, filter?: NoteFilter): Promise<Note[]> {
+  const conditions = ["profile_id = $1"];
+  const params: any[] = [uuidUtils.uuid().parse(profileId)];
+
+  if (filter?.search?.trim()) {
+    params.push(`%${escapeLike(filter.search.trim())}%`);
+    const index = params.length;
+    conditions.push(`(title LIKE $${index} ESCAPE '\\' OR tags LIKE $${index} ESCAPE '\\' OR (encrypted = 0 AND content LIKE $${index} ESCAPE '\\'))`);
+  }
+  if (filter?.tag) {
+    params.push(filter.tag);
+    conditions.push(`EXISTS (SELECT 1 FROM json_each(notes.tags) WHERE json_each.value = $${params.length})`);
+  }
+  if (filter?.pinned !== undefined) {
+    params.push(filter.pinned ? 1 : 0);
+    conditions.push(`pinned = $${params.length}`);
+  }
+
+  const result = await selectDBQuery<any[]>(`SELECT * FROM notes WHERE ${conditions.join(" AND ")} ORDER BY pinned DESC, updated_at DESC, id ASC`, params);
+  return Promise.all(result.map(parseNoteRow));
+}
+
+// Update a note. Turning encryption on or off re-stores the content accordingly.
+export async function updateNote(id: string, profileId: string, updateData: UpdateNoteParams): Promise<Note | null> {
+  const current = await getNoteById(id, profileId);
+  if (!current) {
+    return null;
+  }
+
+  const validated = UpdateNoteSchema.parse(updateData);
+  const changes: Record<string, any> = { ...validated };
+  if (validated.content !== undefined || validated.encrypted !== undefined) {
+    changes.content = await storedContent(validated.content ?? current.content, validated.encrypted ?? current.encrypted);
+  }
+
+  const fieldMapping = {
+    tags: (value: string[]) => JSON.stringify(value),
+  };
+
+  const { updates, values, whereClause } = buildUpdateParams(current.id, changes, fieldMapping);
+  if (updates.length > 0) {
+    await executeDBQuery(`UPDATE notes SET ${updates.join(", ")}${whereClause} AND profile_id = $${values.length + 1}`, [...values, current.profile_id]);
+  }
+
+  return getNoteById(current.id, profileId);
+}
+
+// Delete a note
+export async function deleteNote(id: string, profileId: string): Promise<boolean> {
+  const result = await executeDBQuery("DELETE FROM notes WHERE id = $1 AND profile_id = $2", [uuidUtils.uuid().parse(id), uuidUtils.uuid().parse(profileId)]);
+  return result.rowsAffected > 0;
+}
+
+/**
+ * Contents of the notes with the given titles, for {{note:title}} in prompts.
+ * Titles without a note are left out, so the placeholder stays visible in the prompt.
+ */
+export async function getNoteSnippets(profileId: string, titles: string[]): Promise<Record<string, string>> {
+  const snippets: Record<string, string> = {};
+  for (const title of new Set(titles.map((title) => title.trim()))) {
+    const note = await getNoteByTitle(profileId, title);
+    if (note) {
+      snippets[title] = note.content;
+    }
+  }
+  return snippets;
+}
+
+/**
+ * All of a profile's notes as a backup. Encrypted notes are exported as stored (ciphertext),
+ * so their content never leaves the database in clear text.
+ */
+export async function exportNotes(profileId: string): Promise<NotesBackup> {
+  const result = await selectDBQuery<any[]>("SELECT * FROM notes WHERE profile_id = $1 ORDER BY created_at ASC, id ASC", [uuidUtils.uuid().parse(profileId)]);
+  return {
+    export_type: "notes",
+    notes: result.map(parseStoredNoteRow).map(({ id: _id, profile_id: _profileId, created_at, updated_at, ...note }) => ({
+      ...note,
+      created_at: created_at.toISOString(),
+      updated_at: updated_at.toISOString(),
+    })),
+  };
+}
+
+/**
+ * Restores a notes backup into the profile. Notes whose title already exists are skipped, as are
+ * encrypted notes that this install's key can't decrypt.
+ */
+export async function importNotes(profileId: string, data: unknown): Promise<{ imported: number; skipped: string[] }> {
+  const backup = NotesBackupSchema.parse(data);
+  const existing = new Set((await selectDBQuery<{ title: string }[]>("SELECT title FROM notes WHERE profile_id = $1", [uuidUtils.uuid().parse(profileId)])).map((row) => row.title));
+  const skipped: string[] = [];
+  let imported = 0;
+
+  for (const note of backup.notes) {
+    if (existing.has(note.title)) {
+      skipped.push(note.title);
+      continue;
+    }
+    if (note.encrypted && note.content) {
+      try {
+        await decryptApiKey(note.content);
+      } catch {
+        skipped.push(note.title);
+        continue;
+      }
+    }
+
+    const now = formatDateTime();
+    await executeDBQuery(
+      `INSERT INTO notes (id, profile_id, title, content, tags, pinned, encrypted, created_at, updated_at)
+       VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)`,
+      [crypto.randomUUID(), profileId, note.title, note.content, JSON.stringify(note.tags), note.pinned ? 1 : 0, note.encrypted ? 1 : 0, note.created_at ?? now, note.updated_at ?? now],
+    );
+    existing.add(note.title);
+    imported++;
+  }
+
+  return { imported, skipped };
+}
@@ -1,3 +1,4 @@
+import { type DeleteOutcome, deleteProfileCommand } from "@/commands/database.ts";
 import { formatDateTime } from "@/utils/date-time.ts";
 import { hashPassword, verifyPassword } from "../commands/security.ts";
 import { type AppSettings, LoginPasswordParams, LoginPasswordSchema, NewProfileParams, type Profile, type ProfileResponse, ProfileSchema, updateProfileSchema } from "../schema/profiles-schema.ts";
@@ -141,11 +142,12 @@ export async function updateProfile(id: string, updateData: Partial<Profile>): P
   return updatedProfile;
 }
 
-export async function deleteProfile(id: string): Promise<void> {
+// The backend may ask for a second call with a confirmation token first
+export async function deleteProfile(id: string, confirmationToken?: string): Promise<DeleteOutcome> {
   // Validate ID input
   const validId = uuidUtils.uuid().parse(id);
 
-  await executeDBQuery("DELETE FROM profiles WHERE id = $1", [validId]);
+  return deleteProfileCommand(validId, confirmationToken);
 }
 
 export async function loginProfile(loginData: LoginPasswordParams): Promise<ProfileResponse> {
@@ -0,0 +1,86 @@
+import { z } from "zod";
+import { uuidUtils } from "../schema/utils-schema";
+import { selectDBQuery } from "../utils/database";
+
+export const SEARCH_KINDS = ["chat", "character", "chat_template", "format_template", "inference_template", "lorebook", "model", "note"] as const;
+
+export type SearchKind = (typeof SEARCH_KINDS)[number];
+
+export interface SearchResult {
+  id: string;
+  kind: SearchKind;
+  name: string;
+  // Extra matched text shown under the name, when the entity has any
+  snippet: string | null;
+}
+
+const SNIPPET_LENGTH = 120;
+const DEFAULT_LIMIT = 20;
+const MAX_LIMIT = 100;
+
+/**
+ * One SELECT per kind, all sharing the same parameters:
+ * $1 profile id, $2 contains pattern, $3 prefix pattern.
+ * `secondary` matches text besides the name, which ranks below any name match.
+ */
+const KIND_QUERIES: Record<SearchKind, { table: string; name: string; snippet: string; secondary?: string }> = {
+  chat: { table: "chats", name: "name", snippet: "NULL" },
+  character: { table: "characters", name: "name", snippet: "NULL" },
+  chat_template: { table: "chat_template", name: "name", snippet: "NULL" },
+  format_template: { table: "format_template", name: "name", snippet: "NULL" },
+  inference_template: { table: "inference_template", name: "name", snippet: "NULL" },
+  lorebook: { table: "lorebooks", name: "name", snippet: `substr(description, 1, ${SNIPPET_LENGTH})`, secondary: "description" },
+  model: { table: "models", name: "name", snippet: "alias", secondary: "alias" },
+  // Encrypted content is ciphertext, so it is neither searched nor shown
+  note: {
+    table: "notes",
+    name: "title",
+    snippet: `CASE WHEN encrypted = 0 THEN substr(content, 1, ${SNIPPET_LENGTH}) END`,
+    secondary: "CASE WHEN encrypted = 0 THEN content END",
+  },
+};
+
+function escapeLike(value: string): string {
+  return value.replace(/[\\%_]/g, (char) => `\\${char}`);
+}
+
+function kindQuery(kind: SearchKind): string {
+  const { table, name, snippet, secondary } = KIND_QUERIES[kind];
+  const nameMatch = `${name} LIKE $2 ESCAPE '\\'`;
+  const match = secondary ? `(${nameMatch} OR ${secondary} LIKE $2 ESCAPE '\\')` : nameMatch;
+  return `SELECT id, '${kind}' AS kind, ${name} AS name, ${snippet} AS snippet,
+    CASE WHEN ${name} LIKE $3 ESCAPE '\\' THEN 0 WHEN ${nameMatch} THEN 1 ELSE 2 END AS rank
+    FROM ${table} WHERE profile_id = $1 AND ${match}`;
+}
+
+/**
+ * Build the single query behind a search. Names starting with the query come first,
+ * then names containing it, then entities only matching on their snippet text.
+ */
+export function buildGlobalSearchQuery(kinds: readonly SearchKind[], limit: number): string {
+  return `SELECT id, kind, name, snippet FROM (${kinds.map(kindQuery).join("\nUNION ALL\n")})
+    ORDER BY rank, length(name), name COLLATE NOCASE
+    LIMIT ${limit}`;
+}
+
+/**
+ * Search the profile's chats, characters, templates, lorebooks, models and notes by name in one round trip.
+ * Matching is case-insensitive; an empty query returns nothing.
+ */
+export async function globalSearch(profileId: string, query: string, kinds?: string[], limit = DEFAULT_LIMIT): Promise<SearchResult[]> {
+  const validProfileId = uuidUtils.uuid().parse(profileId);
+  const term = query.trim();
+  const selectedKinds = kinds?.length ? z.array(z.enum(SEARCH_KINDS)).parse(kinds) : SEARCH_KINDS;
+  if (!term || selectedKinds.length === 0) {
+    return [];
+  }
+
+  const safeLimit = Math.min(Math.max(Math.floor(limit) || DEFAULT_LIMIT, 1), MAX_LIMIT);
+  const escaped = escapeLike(term);
+
+  return selectDBQuery<SearchResult[]>(buildGlobalSearchQuery([...new Set(selectedKinds)], safeLimit), [
+    validProfileId,
+    `%${escaped}%`,
+    `${escaped}%`,
+  ]);
+}
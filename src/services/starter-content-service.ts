@@ -0,0 +1,111 @@
+import { getStarterPacks, type StarterPack } from "@/commands/starter";
+import { CreateCharacterSchema } from "@/schema/characters-schema";
+import { createLorebookEntrySchema, createLorebookSchema } from "@/schema/lorebook-schema";
+import { type ProfileResponse, type QuickAction, QuickActionSchema } from "@/schema/profiles-schema";
+import { newFormatTemplateSchema } from "@/schema/template-format-schema";
+import { createCharacter } from "./character-service";
+import { createLorebook, createLorebookEntry } from "./lorebook-service";
+import { getProfileById, updateProfile, updateProfileSettings } from "./profile-service";
+import { createFormatTemplate } from "./template-format-service";
+
+const starterEntrySchema = createLorebookEntrySchema.omit({ lorebook_id: true });
+
+/**
+ * A starter pack checked against the create schemas, ready to insert for a profile
+ */
+export function prepareStarterPack(pack: StarterPack, profileId: string) {
+  const withContext = (kind: string, index: number, parse: () => any) => {
+    try {
+      return parse();
+    } catch (error) {
+      throw new Error(`Starter pack "${pack.id}": invalid ${kind} #${index + 1}: ${error instanceof Error ? error.message : String(error)}`);
+    }
+  };
+
+  return {
+    characters: pack.characters.map((character, index) =>
+      withContext("character", index, () =>
+        CreateCharacterSchema.parse({
+          type: "character",
+          avatar_path: null,
+          lorebook_id: null,
+          expressions: null,
+          character_manifest_id: null,
+          external_update_link: null,
+          ...character,
+          profile_id: profileId,
+        }),
+      ),
+    ),
+    lorebooks: pack.lorebooks.map(({ entries = [], ...lorebook }, index) =>
+      withContext("lorebook", index, () => ({
+        lorebook: createLorebookSchema.parse({ description: null, ...lorebook, profile_id: profileId }),
+        entries: entries.map((entry) => starterEntrySchema.parse(entry)),
+      })),
+    ),
+    formatTemplates: pack.format_templates.map((template, index) =>
+      withContext("format template", index, () => newFormatTemplateSchema.parse({ favorite: false, ...template, profile_id: profileId })),
+    ),
+    // Fresh ids, so installing into several profiles never shares one
+    quickActions: pack.quick_actions.map((action, index) =>
+      withContext("quick action", index, (): QuickAction => QuickActionSchema.parse({ chatTemplateId: null, ...action, id: crypto.randomUUID() })),
+    ),
+  };
+}
+
+export interface SeedStarterContentResult {
+  installed: string[];
+  // Requested packs the profile already had
+  skipped: string[];
+  profile: ProfileResponse;
+}
+
+/**
+ * Install bundled starter packs into a profile through the regular create services. Packs recorded
+ * in the profile's `installedStarterPacks` are skipped; each pack is recorded as soon as it's in.
+ */
+export async function seedStarterContent(profileId: string, packs: string[]): Promise<SeedStarterContentResult> {
+  let profile = await getProfileById(profileId);
+  if (!profile) {
+    throw new Error("Profile not found");
+  }
+
+  const alreadyInstalled = profile.settings.system?.installedStarterPacks ?? [];
+  const requested = [...new Set(packs)];
+  const toInstall = requested.filter((id) => !alreadyInstalled.includes(id));
+  const skipped = requested.filter((id) => alreadyInstalled.includes(id));
+  if (toInstall.length === 0) {
+    return { installed: [], skipped, profile };
+  }
+
+  // Validate every pack before creating anything
+  const contents = await getStarterPacks(toInstall);
+  const prepared = contents.map((pack) => ({ id: pack.id, items: prepareStarterPack(pack, profileId) }));
+
+  const installed: string[] = [];
+  for (const { id, items } of prepared) {
+    for (const { lorebook, entries } of items.lorebooks) {
+      const created = await createLorebook(lorebook);
+      for (const entry of entries) {
+        await createLorebookEntry({ ...entry, lorebook_id: created.id });
+      }
+    }
+    for (const character of items.characters) {
+      await createCharacter(character);
+    }
+    for (const template of items.formatTemplates) {
+      await createFormatTemplate(template);
+    }
+    if (items.quickActions.length > 0) {
+      profile = await updateProfile(profileId, { quick_actions: [...(profile.quick_actions ?? []), ...items.quickActions] });
+    }
+
+    installed.push(id);
+    profile = await updateProfileSettings(profileId, {
+      ...profile.settings,
+      system: { ...profile.settings.system, installedStarterPacks: [...alreadyInstalled, ...installed] },
+    });
+  }
+
+  return { installed, skipped, profile };
+}
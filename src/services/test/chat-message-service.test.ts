@@ -0,0 +1,93 @@
+import { beforeEach, describe, expect, it, vi } from "vitest";
+import { executeDBQuery, executeDBWrite, selectDBQuery } from "../../utils/database";
+import { getChatMessageById, listRecentMessages, mergeChatMessageExtra, setActiveVariant } from "../chat-message-service";
+
+vi.mock("../../utils/database", () => ({
+  executeDBQuery: vi.fn(async () => ({ rowsAffected: 1 })),
+  executeDBWrite: vi.fn(async () => ({ rowsAffected: 1 })),
+  selectDBQuery: vi.fn(),
+  buildUpdateParams: vi.fn(),
+}));
+
+const CHAT = "0b6c2f5e-8f53-4a43-9a7e-5d1f9c3b2a10";
+const OTHER_CHAT = "1c7d3a6f-9a64-4b54-8b8f-6e2a0d4c3b21";
+const MESSAGE = "2d8e4b7a-0b75-4c65-9c9a-7f3b1e5d4c32";
+
+const row = (message_index: number) => ({
+  id: MESSAGE,
+  chat_id: CHAT,
+  messages: JSON.stringify(["first", "second"]),
+  message_index,
+  extra: "{}",
+  created_at: "2025-01-01 00:00:00",
+  updated_at: "2025-01-01 00:00:00",
+});
+
+describe("setActiveVariant", () => {
+  beforeEach(() => {
+    vi.mocked(executeDBQuery).mockClear();
+    vi.mocked(selectDBQuery).mockResolvedValue([row(0)]);
+  });
+
+  it("stores the chosen variant", async () => {
+    await setActiveVariant(MESSAGE, CHAT, 1);
+    expect(vi.mocked(executeDBQuery).mock.calls[0][1]).toEqual([1, expect.any(String), MESSAGE, CHAT]);
+  });
+
+  it("rejects variants that don't exist", async () => {
+    await expect(setActiveVariant(MESSAGE, CHAT, 2)).rejects.toThrow("has no variant 2");
+    await expect(setActiveVariant(MESSAGE, CHAT, -1)).rejects.toThrow();
+    expect(executeDBQuery).not.toHaveBeenCalled();
+  });
+
+  it("only updates messages of the given chat", async () => {
+    await expect(setActiveVariant(MESSAGE, OTHER_CHAT, 1)).rejects.toThrow("not found");
+    expect(executeDBQuery).not.toHaveBeenCalled();
+  });
+});
+
+describe("getChatMessageById", () => {
+  it("falls back to the last variant when the stored index is stale", async () => {
+    vi.mocked(selectDBQuery).mockResolvedValue([row(5)]);
+    expect((await getChatMessageById(MESSAGE))?.message_index).toBe(1);
+  });
+});
+
+describe("listRecentMessages", () => {
+  it("returns the newest messages oldest first", async () => {
+    vi.mocked(selectDBQuery).mockResolvedValue([
+      { sequence: 9, type: "character", character_id: "alice", messages: JSON.stringify(["Welcome."]), message_index: 0, extra: null },
+      { sequence: 7, type: "user", character_id: null, messages: JSON.stringify(["Hello?"]), message_index: 0, extra: "{}" },
+    ]);
+
+    const messages = await listRecentMessages(CHAT, 2);
+
+    expect(messages.map((message) => message.sequence)).toEqual([7, 9]);
+    expect(messages[1]).toEqual({ sequence: 9, type: "character", character_id: "alice", text: "Welcome.", excluded: false });
+    expect(vi.mocked(selectDBQuery).mock.calls.at(-1)?.[1]).toEqual([CHAT, 2]);
+  });
+});
+
+describe("mergeChatMessageExtra", () => {
+  beforeEach(() => {
+    vi.mocked(executeDBWrite).mockClear();
+  });
+
+  it("patches the given keys in a single update", async () => {
+    await mergeChatMessageExtra(MESSAGE, { finishReasons: [null, "stop"] });
+
+    expect(executeDBWrite).toHaveBeenCalledTimes(1);
+    const [query, params] = vi.mocked(executeDBWrite).mock.calls[0];
+    expect(query).toContain("json_patch(");
+    expect(query).not.toContain("messages =");
+    expect(params).toEqual([JSON.stringify({ finishReasons: [null, "stop"] }), expect.any(String), MESSAGE]);
+  });
+
+  it("replaces the variants in the same statement", async () => {
+    await mergeChatMessageExtra(MESSAGE, { translations: [{ text: "Hola", language: "es" }] }, ["Hello"]);
+
+    const [query, params] = vi.mocked(executeDBWrite).mock.calls[0];
+    expect(query).toContain("messages = $3");
+    expect(params).toEqual([expect.any(String), expect.any(String), JSON.stringify(["Hello"]), MESSAGE]);
+  });
+});
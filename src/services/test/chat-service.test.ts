@@ -0,0 +1,87 @@
+import { beforeEach, describe, expect, it, vi } from "vitest";
+import { selectDBQuery } from "../../utils/database";
+import { getChatReadPosition, listChats } from "../chat-service";
+
+vi.mock("../../utils/database", () => ({
+  executeDBQuery: vi.fn(async () => ({ rowsAffected: 1 })),
+  selectDBQuery: vi.fn(),
+  buildUpdateParams: vi.fn(),
+  setFavorite: vi.fn(),
+}));
+
+const CHAT = "0b6c2f5e-8f53-4a43-9a7e-5d1f9c3b2a10";
+const PROFILE = "3e9f5c8b-1c86-4d76-ad0b-8a4c2f6e5d43";
+const EARLIER_MESSAGE = "2d8e4b7a-0b75-4c65-9c9a-7f3b1e5d4c32";
+
+const chatRow = (unread_count: number) => ({
+  id: CHAT,
+  profile_id: PROFILE,
+  name: "Chat",
+  participants: "[]",
+  user_character_settings: "[]",
+  settings: null,
+  favorite: 0,
+  synopsis: null,
+  synopsis_up_to_sequence: 0,
+  synopsis_updated_at: null,
+  created_at: "2025-01-01 00:00:00",
+  updated_at: "2025-01-01 00:00:00",
+  unread_count,
+});
+
+describe("listChats", () => {
+  beforeEach(() => {
+    vi.mocked(selectDBQuery).mockReset();
+  });
+
+  it("counts every message as unread when the chat was never opened", async () => {
+    vi.mocked(selectDBQuery).mockResolvedValue([chatRow(4)]);
+
+    const [chat] = await listChats({ profile_id: PROFILE });
+
+    const query = vi.mocked(selectDBQuery).mock.calls[0][0];
+    expect(query).toContain("LEFT JOIN chat_read_state rs");
+    expect(query).toContain("rs.chat_id IS NULL");
+    expect(chat.unread_count).toBe(4);
+  });
+
+  it("only counts messages of the chat itself", async () => {
+    vi.mocked(selectDBQuery).mockResolvedValue([]);
+
+    await listChats({ profile_id: PROFILE });
+
+    expect(vi.mocked(selectDBQuery).mock.calls[0][0]).toContain("WHERE m.chat_id = chats.id");
+    expect(vi.mocked(selectDBQuery).mock.calls[0][1]).toEqual([PROFILE]);
+  });
+});
+
+describe("getChatReadPosition", () => {
+  beforeEach(() => {
+    vi.mocked(selectDBQuery).mockReset();
+  });
+
+  it("falls back to the nearest earlier message once the read one is deleted", async () => {
+    vi.mocked(selectDBQuery).mockResolvedValue([{ message_id: EARLIER_MESSAGE, last_read_at: "2025-01-01 00:00:00" }]);
+
+    const position = await getChatReadPosition(CHAT, PROFILE);
+
+    const [query, params] = vi.mocked(selectDBQuery).mock.calls[0];
+    expect(query).toContain("c.sequence < rs.last_read_chapter_sequence");
+    expect(query).toContain("m.position <= rs.last_read_position");
+    expect(query).toContain("ORDER BY (m.id = rs.last_read_message_id) DESC, c.sequence DESC, m.position DESC");
+    expect(params).toEqual([CHAT, PROFILE]);
+    expect(position?.message_id).toBe(EARLIER_MESSAGE);
+  });
+
+  it("has no message once every earlier one is deleted", async () => {
+    vi.mocked(selectDBQuery).mockResolvedValue([{ message_id: null, last_read_at: "2025-01-01 00:00:00" }]);
+
+    expect((await getChatReadPosition(CHAT, PROFILE))?.message_id).toBeNull();
+  });
+
+  it("has no position for a chat the profile never read", async () => {
+    vi.mocked(selectDBQuery).mockResolvedValue([]);
+
+    expect(await getChatReadPosition(CHAT, PROFILE)).toBeNull();
+  });
+});
@@ -0,0 +1,90 @@
+import { beforeEach, describe, expect, it, vi } from "vitest";
+import { rebindModelCommand } from "../../commands/database";
+import { selectDBQuery } from "../../utils/database";
+import { findModelByIdOrAlias, getModelByIdOrAlias, rebindModel } from "../model-service";
+
+vi.mock("../../utils/database", () => ({
+  executeDBQuery: vi.fn(async () => ({ rowsAffected: 1 })),
+  selectDBQuery: vi.fn(),
+  buildUpdateParams: vi.fn(),
+  setFavorite: vi.fn(),
+}));
+vi.mock("../../commands/database", () => ({ rebindModelCommand: vi.fn() }));
+
+const PROFILE = "0b6c2f5e-8f53-4a43-9a7e-5d1f9c3b2a10";
+const OLD = "1c7d3a6f-9a64-4b54-8b8f-6e2a0d4c3b21";
+const NEW = "2d8e4b7a-0b75-4c65-9c9a-7f3b1e5d4c32";
+
+const row = (id: string, alias: string | null) => ({
+  id,
+  profile_id: PROFILE,
+  name: "Writer",
+  alias,
+  type: "llm",
+  config: "{}",
+  manifest_id: "openai",
+  inference_template_id: null,
+  max_concurrency: 1,
+  favorite: 0,
+  capabilities: null,
+  fallback_model_ids: "[]",
+  created_at: "2025-01-01 00:00:00",
+  updated_at: "2025-01-01 00:00:00",
+});
+
+describe("findModelByIdOrAlias", () => {
+  const models = [
+    { id: OLD, alias: "writer" },
+    { id: NEW, alias: OLD },
+  ];
+
+  it("finds models by id first, then by alias", () => {
+    expect(findModelByIdOrAlias(models, NEW)?.id).toBe(NEW);
+    expect(findModelByIdOrAlias(models, "writer")?.id).toBe(OLD);
+    // An alias that happens to equal another model's id never shadows it
+    expect(findModelByIdOrAlias(models, OLD)?.id).toBe(OLD);
+  });
+
+  it("returns nothing for unknown or empty references", () => {
+    expect(findModelByIdOrAlias(models, "editor")).toBeUndefined();
+    expect(findModelByIdOrAlias(models, null)).toBeUndefined();
+    expect(findModelByIdOrAlias(models, "")).toBeUndefined();
+  });
+});
+
+describe("getModelByIdOrAlias", () => {
+  beforeEach(() => {
+    vi.mocked(selectDBQuery).mockReset();
+  });
+
+  it("loads the model an alias points to within the profile", async () => {
+    vi.mocked(selectDBQuery).mockResolvedValueOnce([{ id: NEW }]).mockResolvedValueOnce([row(NEW, "writer")]);
+
+    const model = await getModelByIdOrAlias("writer", PROFILE);
+    expect(model?.id).toBe(NEW);
+    expect(model?.alias).toBe("writer");
+    expect(vi.mocked(selectDBQuery).mock.calls[0][1]).toEqual([PROFILE, "writer"]);
+  });
+
+  it("returns null when nothing matches", async () => {
+    vi.mocked(selectDBQuery).mockResolvedValueOnce([]);
+    expect(await getModelByIdOrAlias("writer", PROFILE)).toBeNull();
+    expect(selectDBQuery).toHaveBeenCalledTimes(1);
+  });
+});
+
+describe("rebindModel", () => {
+  it("rebinds through the transactional command", async () => {
+    const report = { chat_templates: 2, chat_settings: 1, lorebooks: 0, fallback_chains: 1, alias_moved: true };
+    vi.mocked(rebindModelCommand).mockResolvedValue(report);
+
+    expect(await rebindModel(OLD, NEW, PROFILE)).toEqual(report);
+    expect(rebindModelCommand).toHaveBeenCalledWith(OLD, NEW, PROFILE);
+  });
+
+  it("rejects ids that aren't uuids before calling it", async () => {
+    vi.mocked(rebindModelCommand).mockClear();
+    await expect(rebindModel("writer", NEW, PROFILE)).rejects.toThrow();
+    expect(rebindModelCommand).not.toHaveBeenCalled();
+  });
+});
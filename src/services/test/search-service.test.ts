@@ -0,0 +1,58 @@
+import { beforeEach, describe, expect, it, vi } from "vitest";
+import { selectDBQuery } from "../../utils/database";
+import { buildGlobalSearchQuery, globalSearch } from "../search-service";
+
+vi.mock("../../utils/database", () => ({ selectDBQuery: vi.fn(async () => []) }));
+
+const PROFILE = "8f9e8a52-6c4b-4f57-9a1e-3f0c2b7d1e10";
+
+describe("globalSearch", () => {
+  beforeEach(() => {
+    vi.mocked(selectDBQuery).mockClear();
+  });
+
+  it("searches every kind of the profile in one query", async () => {
+    await globalSearch(PROFILE, "  Ali ");
+    expect(selectDBQuery).toHaveBeenCalledTimes(1);
+    const [query, params] = vi.mocked(selectDBQuery).mock.calls[0];
+    expect(params).toEqual([PROFILE, "%Ali%", "Ali%"]);
+    for (const table of ["chats", "characters", "chat_template", "format_template", "inference_template", "lorebooks", "models", "notes"]) {
+      expect(query).toContain(`FROM ${table} WHERE profile_id = $1`);
+    }
+  });
+
+  it("escapes LIKE wildcards in the query", async () => {
+    await globalSearch(PROFILE, "50%_off\\");
+    expect(vi.mocked(selectDBQuery).mock.calls[0][1]).toEqual([PROFILE, "%50\\%\\_off\\\\%", "50\\%\\_off\\\\%"]);
+  });
+
+  it("only searches the requested kinds", async () => {
+    await globalSearch(PROFILE, "ali", ["character", "note"], 5);
+    const query = vi.mocked(selectDBQuery).mock.calls[0][0] as string;
+    expect(query).toContain("FROM characters");
+    expect(query).toContain("FROM notes");
+    expect(query).not.toContain("FROM chats");
+    expect(query).toContain("LIMIT 5");
+  });
+
+  it("rejects unknown kinds and skips empty queries", async () => {
+    await expect(globalSearch(PROFILE, "ali", ["spaceship"])).rejects.toThrow();
+    expect(await globalSearch(PROFILE, "   ")).toEqual([]);
+    expect(selectDBQuery).not.toHaveBeenCalled();
+  });
+
+  it("clamps the limit", async () => {
+    await globalSearch(PROFILE, "ali", undefined, 10_000);
+    expect(vi.mocked(selectDBQuery).mock.calls[0][0]).toContain("LIMIT 100");
+  });
+});
+
+describe("buildGlobalSearchQuery", () => {
+  it("ranks name prefixes before other matches", () => {
+    expect(buildGlobalSearchQuery(["chat"], 10)).toMatch(/ORDER BY rank, length\(name\)/);
+  });
+
+  it("never matches encrypted note content", () => {
+    expect(buildGlobalSearchQuery(["note"], 10)).toContain("CASE WHEN encrypted = 0 THEN content END LIKE $2");
+  });
+});
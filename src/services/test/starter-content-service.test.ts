@@ -0,0 +1,59 @@
+import { describe, expect, it, vi } from "vitest";
+import type { StarterPack } from "../../commands/starter";
+import { prepareStarterPack } from "../starter-content-service";
+
+vi.mock("../../utils/database", () => ({
+  executeDBQuery: vi.fn(async () => ({ rowsAffected: 1 })),
+  selectDBQuery: vi.fn(async () => []),
+  buildUpdateParams: vi.fn(),
+}));
+
+const PROFILE = "3e9f5c8b-1c86-4d76-8a0b-8a4c2f6e5d43";
+
+// The packs shipped with the app (src-tauri/resources), so a pack that won't install fails the build
+const bundled = Object.entries(import.meta.glob<StarterPack>("../../../src-tauri/resources/starter-packs/*.json", { eager: true, import: "default" }));
+
+const pack = (overrides: Partial<StarterPack>): StarterPack => ({
+  id: "test",
+  name: "Test",
+  description: "Test pack",
+  characters: [],
+  lorebooks: [],
+  format_templates: [],
+  quick_actions: [],
+  ...overrides,
+});
+
+describe("bundled starter packs", () => {
+  it("are found", () => {
+    expect(bundled.length).toBeGreaterThan(0);
+  });
+
+  it.each(bundled)("%s passes the create schemas", (_path, content) => {
+    const prepared = prepareStarterPack(pack(content), PROFILE);
+    expect(prepared.characters.length + prepared.lorebooks.length + prepared.formatTemplates.length + prepared.quickActions.length).toBeGreaterThan(0);
+  });
+});
+
+describe("prepareStarterPack", () => {
+  it("fills defaults and binds everything to the profile", () => {
+    const prepared = prepareStarterPack(
+      pack({
+        characters: [{ name: "Mira", profile_id: "someone-else" }],
+        lorebooks: [{ name: "Lore", entries: [{ comment: "Entry", keywords: ["lore"] }] }],
+        quick_actions: [{ id: "fixed", icon: "map", label: "Go", userPrompt: "{{input}}", systemPromptOverride: "", streamOption: "textarea" }],
+      }),
+      PROFILE,
+    );
+
+    expect(prepared.characters[0]).toMatchObject({ name: "Mira", type: "character", profile_id: PROFILE, version: "1.0.0" });
+    expect(prepared.lorebooks[0].lorebook).toMatchObject({ name: "Lore", profile_id: PROFILE, description: null });
+    expect(prepared.lorebooks[0].entries[0]).toMatchObject({ comment: "Entry", enabled: true, insertion_type: "lorebook_top" });
+    expect(prepared.quickActions[0].id).not.toBe("fixed");
+    expect(prepared.quickActions[0].chatTemplateId).toBeNull();
+  });
+
+  it("names the pack and item that fail validation", () => {
+    expect(() => prepareStarterPack(pack({ id: "broken", quick_actions: [{ label: "No prompt" }] }), PROFILE)).toThrow(/"broken": invalid quick action #1/);
+  });
+});
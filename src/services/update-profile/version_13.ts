@@ -0,0 +1,81 @@
+import { Expression } from "@/schema/characters-schema";
+import { listCharacters, updateCharacter } from "@/services/character-service";
+import { copyFileToAssetStore, getImageSourceType, isAssetStorePath, removeFile } from "@/services/file-system-service";
+import { ProfileResponse, updateProfile } from "../profile-service";
+
+// Only relative files under the app data dir are moved; URLs, data URLs and absolute paths stay as they are
+function isLegacyFile(path: string | null | undefined): path is string {
+  return !!path && getImageSourceType(path) === "file-path" && !path.includes(":") && !isAssetStorePath(path);
+}
+
+async function removeLegacyFiles(paths: string[]) {
+  for (const path of paths) {
+    try {
+      await removeFile(path);
+    } catch (err) {
+      console.warn(`Migration v13: Failed to remove legacy file '${path}'`, err);
+    }
+  }
+}
+
+/**
+ * Migration for version 13:
+ * - Move the profile avatar, character avatars and expression images into the content-addressed asset store.
+ * - Legacy files are removed only after the row points at the stored copy.
+ */
+const v13Migration = async (profile: ProfileResponse): Promise<ProfileResponse> => {
+  let migratedProfile = profile;
+
+  if (isLegacyFile(profile.avatar_path)) {
+    try {
+      const avatarPath = await copyFileToAssetStore(profile.avatar_path);
+      migratedProfile = await updateProfile(profile.id, { avatar_path: avatarPath });
+      await removeLegacyFiles([profile.avatar_path]);
+    } catch (err) {
+      console.error("Migration v13: Failed to move profile avatar", err);
+    }
+  }
+
+  try {
+    const characters = await listCharacters(profile.id);
+    for (const character of characters) {
+      try {
+        const moved: string[] = [];
+        const update: { avatar_path?: string; expressions?: Expression[] } = {};
+
+        if (isLegacyFile(character.avatar_path)) {
+          update.avatar_path = await copyFileToAssetStore(character.avatar_path);
+          moved.push(character.avatar_path);
+        }
+
+        if (character.expressions?.some((expression) => isLegacyFile(expression.image_path))) {
+          update.expressions = [];
+          for (const expression of character.expressions) {
+            if (isLegacyFile(expression.image_path)) {
+              update.expressions.push({ ...expression, image_path: await copyFileToAssetStore(expression.image_path) });
+              moved.push(expression.image_path);
+            } else {
+              update.expressions.push(expression);
+            }
+          }
+        }
+
+        if (moved.length === 0) {
+          continue;
+        }
+
+        await updateCharacter(character.id, update);
+        await removeLegacyFiles(moved);
+      } catch (err) {
+        console.error(`Migration v13: Failed to move files for character '${character.name}'`, err);
+      }
+    }
+  } catch (error) {
+    // Log and continue with the original profile
+    console.error("Migration v13: Error moving character files to the asset store", error);
+  }
+
+  return migratedProfile;
+};
+
+export { v13Migration };
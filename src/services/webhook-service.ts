@@ -0,0 +1,165 @@
+import { encryptApiKey } from "@/commands/security";
+import { deliverWebhook } from "@/commands/webhooks";
+import { parseBoolean } from "@/pages/agents/components/json-schema/schema-utils";
+import { formatDateTime } from "@/utils/date-time";
+import { uuidUtils } from "../schema/utils-schema";
+import {
+  CreateWebhookParams,
+  CreateWebhookSchema,
+  UpdateWebhookParams,
+  UpdateWebhookSchema,
+  Webhook,
+  WebhookDelivery,
+  WebhookEvent,
+  WebhookSchema,
+} from "../schema/webhook-schema";
+import { buildUpdateParams, executeDBQuery, selectDBQuery } from "../utils/database";
+
+// Deliveries kept per webhook, older entries are pruned after each dispatch
+const MAX_DELIVERY_LOG = 100;
+
+function parseWebhookRow(row: any): Webhook {
+  return WebhookSchema.parse({
+    ...row,
+    events: JSON.parse(row.events || "[]"),
+    enabled: parseBoolean(row.enabled),
+    allow_insecure: parseBoolean(row.allow_insecure),
+    created_at: new Date(row.created_at),
+    updated_at: new Date(row.updated_at),
+  });
+}
+
+// Create a new webhook, disabled unless explicitly enabled
+export async function createWebhook(webhookData: CreateWebhookParams): Promise<Webhook> {
+  const validated = CreateWebhookSchema.parse(webhookData);
+  const id = crypto.randomUUID();
+  const now = formatDateTime();
+
+  const secret = validated.secret ? await encryptApiKey(validated.secret) : null;
+
+  await executeDBQuery(
+    `INSERT INTO webhooks (id, profile_id, name, url, secret, events, enabled, allow_insecure, created_at, updated_at)
+     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)`,
+    [id, validated.profile_id, validated.name, validated.url, secret, JSON.stringify(validated.events), validated.enabled ? 1 : 0, validated.allow_insecure ? 1 : 0, now, now],
+  );
+
+  return WebhookSchema.parse({
+    ...validated,
+    id,
+    secret,
+    created_at: new Date(now),
+    updated_at: new Date(now),
+  });
+}
+
+// Get a webhook by ID
+export async function getWebhookById(id: string, profileId: string): Promise<Webhook | null> {
+  const result = await selectDBQuery<any[]>("SELECT * FROM webhooks WHERE id = $1 AND profile_id = $2", [uuidUtils.uuid().parse(id), uuidUtils.uuid().parse(profileId)]);
+  return result.length > 0 ? parseWebhookRow(result[0]) : null;
+}
+
+// List a profile's webhooks
+export async function listWebhooks(profileId: string): Promise<Webhook[]> {
+  const result = await selectDBQuery<any[]>("SELECT * FROM webhooks WHERE profile_id = $1 ORDER BY created_at DESC, id ASC", [uuidUtils.uuid().parse(profileId)]);
+  return result.map(parseWebhookRow);
+}
+
+// Update a webhook. An empty secret clears it, undefined keeps the stored one.
+export async function updateWebhook(id: string, profileId: string, updateData: UpdateWebhookParams): Promise<Webhook | null> {
+  const current = await getWebhookById(id, profileId);
+  if (!current) {
+    return null;
+  }
+
+  const validated = UpdateWebhookSchema.parse(updateData);
+  const changes: Record<string, any> = { ...validated };
+  if (validated.secret !== undefined) {
+    changes.secret = validated.secret ? await encryptApiKey(validated.secret) : null;
+  }
+
+  const fieldMapping = {
+    events: (value: WebhookEvent[]) => JSON.stringify(value),
+  };
+
+  const { updates, values, whereClause } = buildUpdateParams(current.id, changes, fieldMapping);
+  if (updates.length > 0) {
+    await executeDBQuery(`UPDATE webhooks SET ${updates.join(", ")}${whereClause} AND profile_id = $${values.length + 1}`, [...values, current.profile_id]);
+  }
+
+  return getWebhookById(current.id, profileId);
+}
+
+// Delete a webhook and its delivery log
+export async function deleteWebhook(id: string, profileId: string): Promise<boolean> {
+  const result = await executeDBQuery("DELETE FROM webhooks WHERE id = $1 AND profile_id = $2", [uuidUtils.uuid().parse(id), uuidUtils.uuid().parse(profileId)]);
+  return result.rowsAffected > 0;
+}
+
+// Delivery log for a webhook, newest first
+export async function listWebhookDeliveries(webhookId: string, profileId: string, limit = 50): Promise<WebhookDelivery[]> {
+  const result = await selectDBQuery<any[]>("SELECT * FROM webhook_deliveries WHERE webhook_id = $1 AND profile_id = $2 ORDER BY created_at DESC, id ASC LIMIT $3", [
+    uuidUtils.uuid().parse(webhookId),
+    uuidUtils.uuid().parse(profileId),
+    limit,
+  ]);
+
+  return result.map((row) => ({
+    ...row,
+    delivered: parseBoolean(row.delivered),
+    created_at: new Date(row.created_at),
+  }));
+}
+
+async function recordDelivery(webhook: Webhook, event: WebhookEvent, result: { delivered: boolean; status_code: number | null; attempts: number; error: string | null }) {
+  await executeDBQuery(
+    `INSERT INTO webhook_deliveries (id, webhook_id, profile_id, event, delivered, status_code, attempts, error, created_at)
+     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)`,
+    [crypto.randomUUID(), webhook.id, webhook.profile_id, event, result.delivered ? 1 : 0, result.status_code, result.attempts, result.error, formatDateTime()],
+  );
+
+  await executeDBQuery(
+    `DELETE FROM webhook_deliveries WHERE webhook_id = $1 AND id NOT IN (
+       SELECT id FROM webhook_deliveries WHERE webhook_id = $1 ORDER BY created_at DESC, id ASC LIMIT $2
+     )`,
+    [webhook.id, MAX_DELIVERY_LOG],
+  );
+}
+
+/**
+ * Send an event to every enabled webhook of the profile that subscribes to it.
+ * Never throws: failures end up in the delivery log so generation is never interrupted.
+ * The payload must not contain credentials or decrypted config values.
+ */
+export async function dispatchWebhookEvent(profileId: string, event: WebhookEvent, data: Record<string, unknown>): Promise<void> {
+  try {
+    const webhooks = (await listWebhooks(profileId)).filter((webhook) => webhook.enabled && webhook.events.includes(event));
+    if (webhooks.length === 0) {
+      return;
+    }
+
+    const payload = {
+      event,
+      profile_id: profileId,
+      timestamp: new Date().toISOString(),
+      data,
+    };
+
+    await Promise.all(
+      webhooks.map(async (webhook) => {
+        try {
+          const result = await deliverWebhook(webhook.url, webhook.secret ?? null, event, payload, webhook.allow_insecure);
+          await recordDelivery(webhook, event, result);
+        } catch (error) {
+          await recordDelivery(webhook, event, {
+            delivered: false,
+            status_code: null,
+            attempts: 0,
+            error: typeof error === "string" ? error : error instanceof Error ? error.message : "Delivery failed",
+          });
+        }
+      }),
+    );
+  } catch (error) {
+    console.error(`Failed to dispatch webhook event ${event}:`, error);
+  }
+}
@@ -1,4 +1,5 @@
 import Database, { QueryResult } from "@tauri-apps/plugin-sql";
+import { waitForMigrations } from "@/commands/migrations";
 import { formatDateTime } from "./date-time";
 
 // Database connection singleton
@@ -13,6 +14,8 @@ const DB_URL = "sqlite:narratrix_main.db";
  */
 export async function getDatabase(): Promise<Database> {
   if (!db) {
+    // Migrations run in the backend at startup, the connection must wait for them
+    await waitForMigrations();
     try {
       db = await Database.load(DB_URL);
       // Enable foreign key constraints
@@ -38,6 +41,48 @@ export async function executeDBQuery(query: string, params: any[] = []): Promise
   }
 }
 
+// Pauses before each retry of a write that found the database locked. The connection already waits
+// on its own (sqlx sets busy_timeout to 5 s), these cover the writes that still lose the race,
+// e.g. a streamed message while a backend command holds a transaction.
+export const DB_LOCK_RETRY_DELAYS_MS = [50, 150, 400];
+
+/**
+ * Whether a database error is SQLite reporting a lock held by another connection (SQLITE_BUSY or
+ * SQLITE_LOCKED), which can succeed when tried again
+ */
+export function isDatabaseLocked(error: unknown): boolean {
+  const message = error instanceof Error ? error.message : String(error);
+  return /database (table )?is locked|SQLITE_BUSY|SQLITE_LOCKED|\(code: [56]\)/i.test(message);
+}
+
+/**
+ * Runs a database operation, trying it again with a short backoff while the database is locked.
+ * Other errors are thrown at once.
+ */
+export async function retryWhenLocked<T>(operation: () => Promise<T>, delaysMs: number[] = DB_LOCK_RETRY_DELAYS_MS): Promise<T> {
+  for (let attempt = 0; ; attempt++) {
+    try {
+      return await operation();
+    } catch (error) {
+      if (!isDatabaseLocked(error)) {
+        throw error;
+      }
+      if (attempt >= delaysMs.length) {
+        throw new Error(`The database stayed busy after ${attempt + 1} attempts, please try again`, { cause: error });
+      }
+      await new Promise((resolve) => setTimeout(resolve, delaysMs[attempt]));
+    }
+  }
+}
+
+/**
+ * executeDBQuery for the frequent writes (messages, usage records) that may meet a lock held by a
+ * background writer
+ */
+export function executeDBWrite(query: string, params: any[] = []): Promise<QueryResult> {
+  return retryWhenLocked(() => executeDBQuery(query, params));
+}
+
 /**
  * Performs a select query with proper error handling
  */
@@ -51,6 +96,23 @@ export async function selectDBQuery<T>(query: string, params: any[] = []): Promi
   }
 }
 
+// Tables that received a favorite column in migration 11
+export type FavoriteTable = "characters" | "models" | "chats" | "format_template" | "inference_template" | "chat_template";
+
+/**
+ * Sets the favorite flag on a record owned by the given profile
+ * @returns false when no record matched
+ */
+export async function setFavorite(table: FavoriteTable, id: string, profileId: string, favorite: boolean): Promise<boolean> {
+  const result = await executeDBQuery(`UPDATE ${table} SET favorite = $1, updated_at = $2 WHERE id = $3 AND profile_id = $4`, [
+    favorite ? 1 : 0,
+    formatDateTime(),
+    id,
+    profileId,
+  ]);
+  return result.rowsAffected > 0;
+}
+
 interface UpdateQueryBuilder {
   updates: string[];
   values: any[];
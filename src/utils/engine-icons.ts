@@ -1,5 +1,5 @@
 import type { IconType } from "react-icons";
-import { LuCpu, LuRoute, LuServer } from "react-icons/lu";
+import { LuCpu, LuFlaskConical, LuRoute, LuServer } from "react-icons/lu";
 import { SiAnthropic, SiGoogle, SiOllama, SiOpenai, SiRootsbedrock } from "react-icons/si";
 import type { Engine } from "@/schema/model-manifest-schema";
 
@@ -18,6 +18,7 @@ const ENGINE_CONFIG: Record<Engine, EngineIconConfig> = {
   runpod: { icon: LuServer, color: "#6c4de7" },
   ollama: { icon: SiOllama },
   openai_compatible: { icon: LuCpu },
+  mock: { icon: LuFlaskConical },
 };
 
 export function getEngineIcon(engine: Engine | undefined): IconType {
@@ -2,18 +2,26 @@ import { getVersion } from "@tauri-apps/api/app";
 import { save as saveDialog } from "@tauri-apps/plugin-dialog";
 import { writeFile } from "@tauri-apps/plugin-fs";
 import { toast } from "sonner";
+import { sanitizeFileName } from "@/commands/assets";
+import { type ScrubOptions, scrubText } from "@/commands/scrub";
 
 export type ExportType = "chat_template" | "format_template" | "instruction_template" | "lorebook" | "character";
 
+export interface ExportOptions {
+  /** Replace emails, phone numbers, IPs, paths and listed words with placeholders before writing */
+  scrub?: boolean | ScrubOptions;
+}
+
 /**
  * Export data to a JSON file with metadata
  *
  * @param data - The data to export (object or array)
  * @param exportType - The type of export (e.g., "chat_template", "format_template", etc.)
  * @param defaultFileName - Default filename for the export (without extension)
+ * @param options - Optional export settings (e.g. scrubbing)
  * @returns Promise<boolean> - Returns true if export was successful, false otherwise
  */
-export async function exportToJsonFile<T = any>(data: T, exportType: ExportType, defaultFileName = "export"): Promise<boolean> {
+export async function exportToJsonFile<T = any>(data: T, exportType: ExportType, defaultFileName = "export", options?: ExportOptions): Promise<boolean> {
   try {
     // Get the app version
     const appVersion = await getVersion();
@@ -26,7 +34,7 @@ export async function exportToJsonFile<T = any>(data: T, exportType: ExportType,
 
     // Open save dialog
     const filePath = await saveDialog({
-      defaultPath: `${defaultFileName}.json`,
+      defaultPath: await sanitizeFileName(defaultFileName, "json"),
       filters: [
         {
           name: "JSON Files",
@@ -41,7 +49,10 @@ export async function exportToJsonFile<T = any>(data: T, exportType: ExportType,
     }
 
     // Convert to JSON string with proper formatting
-    const jsonContent = JSON.stringify(exportData, null, 2);
+    let jsonContent = JSON.stringify(exportData, null, 2);
+    if (options?.scrub) {
+      jsonContent = await scrubText(jsonContent, options.scrub === true ? undefined : options.scrub);
+    }
 
     // Write the file with explicit UTF-8 encoding
     const encoder = new TextEncoder(); // TextEncoder defaults to UTF-8
@@ -71,8 +82,9 @@ export async function exportToJsonFile<T = any>(data: T, exportType: ExportType,
  * @param item - Single item to export
  * @param exportType - The type of export
  * @param defaultFileName - Default filename for the export
+ * @param options - Optional export settings (e.g. scrubbing)
  * @returns Promise<boolean> - Returns true if export was successful
  */
-export async function exportSingleToJsonFile<T = any>(item: T, exportType: ExportType, defaultFileName = "export"): Promise<boolean> {
-  return exportToJsonFile(item, exportType, defaultFileName);
+export async function exportSingleToJsonFile<T = any>(item: T, exportType: ExportType, defaultFileName = "export", options?: ExportOptions): Promise<boolean> {
+  return exportToJsonFile(item, exportType, defaultFileName, options);
 }
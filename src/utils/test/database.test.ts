@@ -0,0 +1,40 @@
+import { describe, expect, it, vi } from "vitest";
+import { isDatabaseLocked, retryWhenLocked } from "../database";
+
+vi.mock("@tauri-apps/plugin-sql", () => ({ default: { load: vi.fn() } }));
+vi.mock("@/commands/migrations", () => ({ waitForMigrations: vi.fn(async () => undefined) }));
+
+const locked = "error returned from database: (code: 5) database is locked";
+
+describe("isDatabaseLocked", () => {
+  it("recognizes busy and locked errors", () => {
+    expect(isDatabaseLocked(locked)).toBe(true);
+    expect(isDatabaseLocked(new Error("SQLITE_BUSY: database is locked"))).toBe(true);
+    expect(isDatabaseLocked("error returned from database: (code: 6) database table is locked")).toBe(true);
+  });
+
+  it("ignores other failures", () => {
+    expect(isDatabaseLocked("error returned from database: (code: 19) UNIQUE constraint failed: chats.id")).toBe(false);
+    expect(isDatabaseLocked(new Error("no such table: chat_messages"))).toBe(false);
+  });
+});
+
+describe("retryWhenLocked", () => {
+  it("tries again until the lock is released", async () => {
+    const operation = vi.fn().mockRejectedValueOnce(locked).mockRejectedValueOnce(locked).mockResolvedValue({ rowsAffected: 1 });
+    await expect(retryWhenLocked(operation, [0, 0, 0])).resolves.toEqual({ rowsAffected: 1 });
+    expect(operation).toHaveBeenCalledTimes(3);
+  });
+
+  it("reports a lock that outlasts every retry", async () => {
+    const operation = vi.fn().mockRejectedValue(locked);
+    await expect(retryWhenLocked(operation, [0, 0])).rejects.toThrow("The database stayed busy after 3 attempts");
+    expect(operation).toHaveBeenCalledTimes(3);
+  });
+
+  it("throws other errors at once", async () => {
+    const operation = vi.fn().mockRejectedValue(new Error("UNIQUE constraint failed"));
+    await expect(retryWhenLocked(operation, [0, 0])).rejects.toThrow("UNIQUE constraint failed");
+    expect(operation).toHaveBeenCalledTimes(1);
+  });
+});